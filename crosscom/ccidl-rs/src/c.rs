@@ -0,0 +1,300 @@
+//! C header emitter for crosscom IDL.
+//!
+//! Generates one `.h` per IDL from the same parsed unit that `RustGen`
+//! consumes. For each interface declared in the IDL the header carries
+//!
+//! - `IFooVtbl`: the flattened vtable, base methods first, in exactly
+//!   the slot order of the Rust `IFooVirtualTable`;
+//! - `struct IFoo { const IFooVtbl *vtbl; }`: what an interface pointer
+//!   points at;
+//! - `IID_IFoo`: the interface UUID;
+//!
+//! and each class gets a `CLSID_Foo`. Imported IDLs are pulled in with
+//! `#include "<stem>.h"`, so the headers for a whole IDL directory are
+//! meant to live side by side.
+//!
+//! Methods use the `CROSSCOM_CALL` calling convention, which matches
+//! Rust's `extern "system"`. Type mapping (IDL → C):
+//!
+//! | IDL              | C |
+//! | ---------------- | - |
+//! | `void`           | `void` |
+//! | `bool`           | `int` (non-zero is true) |
+//! | `int` / `long` / `longlong` | `int` / `long` / `long long` |
+//! | `byte` / `byte*` | `unsigned char` / `const unsigned char *` |
+//! | `float`          | `float` |
+//! | `float?`         | `float`, NaN meaning "none" |
+//! | `UUID`           | `CrosscomUuid` (16 bytes, by value) |
+//! | `&str` / `string` | `const char *` (UTF-8, NUL-terminated) |
+//! | `IFoo` / `IFoo?` | `IFoo *` (the `?` form may be `NULL`) |
+//! | `[out] IFoo`     | `IFoo **` |
+//! | `IFoo[]`         | `IObjectArray *` |
+//!
+//! Returned interface pointers carry a reference the caller must
+//! `release`. Returned strings are allocated by the Rust side and are
+//! never freed by it; C callers should copy them and may leak them.
+//! `[internal(), rust()]` methods take Rust types and cannot be called
+//! from C: their slot is emitted as an opaque `void *` so later slots
+//! keep their offsets.
+
+use std::collections::HashMap;
+
+use crate::{
+    CrossComIdl, Error, Interface, Item, Method, Module, Symbol, parse_uuid_bytes, rust_module,
+};
+
+/// Generate a C header from a parsed IDL unit (which must already have
+/// its imports processed). `stem` is the IDL file stem, used for the
+/// include guard.
+pub(crate) fn generate(unit: CrossComIdl, stem: &str) -> Result<String, Error> {
+    CGen::new(unit)?.r#gen(stem)
+}
+
+struct CGen {
+    unit: CrossComIdl,
+    symbols: HashMap<String, Symbol>,
+    current_module: Module,
+}
+
+impl CGen {
+    fn new(unit: CrossComIdl) -> Result<Self, Error> {
+        let current_module = rust_module(&unit)?.clone();
+        let mut symbols = HashMap::new();
+        for item in &unit.items {
+            match item {
+                Item::Interface(interface) => {
+                    symbols.insert(interface.name.clone(), Symbol::Interface(interface.clone()));
+                }
+                Item::Class(class) => {
+                    symbols.insert(class.name.clone(), Symbol::Class);
+                }
+            }
+        }
+
+        Ok(Self {
+            unit,
+            symbols,
+            current_module,
+        })
+    }
+
+    fn r#gen(&self, stem: &str) -> Result<String, Error> {
+        let guard = format!(
+            "CROSSCOM_{}_H",
+            stem.to_uppercase()
+                .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+        );
+        let mut out = String::new();
+        out.push_str(&format!(
+            "/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */\n\
+             /* Module: {module} */\n\n\
+             #ifndef {guard}\n#define {guard}\n\n",
+            module = self.current_module.module_name,
+        ));
+        out.push_str(COMMON_PRELUDE);
+
+        for import in &self.unit.imports {
+            let header = import
+                .file_name
+                .strip_suffix(".idl")
+                .unwrap_or(&import.file_name);
+            out.push_str(&format!("#include \"{header}.h\"\n"));
+        }
+        if !self.unit.imports.is_empty() {
+            out.push('\n');
+        }
+
+        out.push_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+
+        let interfaces: Vec<&Interface> = self
+            .unit
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Interface(interface) if is_local(interface) => Some(interface),
+                _ => None,
+            })
+            .collect();
+        for interface in &interfaces {
+            out.push_str(&format!(
+                "typedef struct {name} {name};\n",
+                name = interface.name
+            ));
+        }
+        if !interfaces.is_empty() {
+            out.push('\n');
+        }
+
+        for item in &self.unit.items {
+            match item {
+                Item::Interface(interface) if is_local(interface) => {
+                    self.gen_interface(interface, &mut out)?
+                }
+                Item::Class(class) => {
+                    if let Some(uuid) = class.attrs.get("uuid") {
+                        out.push_str(&format!(
+                            "/* Class {name}: {bases} */\n\
+                             static const CrosscomUuid CLSID_{name} = {bytes};\n\n",
+                            name = class.name,
+                            bases = class.bases.join(", "),
+                            bytes = uuid_initializer(uuid)?,
+                        ));
+                    }
+                }
+                Item::Interface(_) => {}
+            }
+        }
+
+        out.push_str("#ifdef __cplusplus\n}\n#endif\n\n");
+        out.push_str(&format!("#endif /* {guard} */\n"));
+        Ok(out)
+    }
+
+    fn gen_interface(&self, interface: &Interface, out: &mut String) -> Result<(), Error> {
+        let name = &interface.name;
+        let uuid = interface
+            .attrs
+            .get("uuid")
+            .ok_or_else(|| Error::Generate(format!("interface {name} is missing uuid")))?;
+
+        out.push_str(&format!("/* Interface {name} */\n\n"));
+        out.push_str(&format!("typedef struct {name}Vtbl {{\n"));
+        for method in self.collect_all_methods(name)? {
+            out.push_str(&format!("    {};\n", self.gen_slot(name, &method)?));
+        }
+        out.push_str(&format!("}} {name}Vtbl;\n\n"));
+        out.push_str(&format!(
+            "struct {name} {{\n    const {name}Vtbl *vtbl;\n}};\n\n"
+        ));
+        out.push_str(&format!(
+            "/* {uuid} */\nstatic const CrosscomUuid IID_{name} = {};\n\n",
+            uuid_initializer(uuid)?
+        ));
+        Ok(())
+    }
+
+    /// Every method in `iname`'s vtable, bases first.
+    fn collect_all_methods(&self, iname: &str) -> Result<Vec<Method>, Error> {
+        let interface = self.find_interface(iname)?;
+        let mut methods = match interface.bases.as_slice() {
+            [] => Vec::new(),
+            [base] => self.collect_all_methods(base)?,
+            _ => {
+                return Err(Error::Generate(format!(
+                    "cannot have more than one parent for interface: {}",
+                    interface.name
+                )));
+            }
+        };
+        methods.extend(interface.methods.iter().cloned());
+        Ok(methods)
+    }
+
+    fn gen_slot(&self, self_ty: &str, method: &Method) -> Result<String, Error> {
+        if method.attrs.contains_key("internal") {
+            return Ok(format!(
+                "void *{name} /* Rust-only, not callable from C */",
+                name = method.name
+            ));
+        }
+        let mut params = vec![format!("{self_ty} *self")];
+        for param in &method.params {
+            let ty = self.map_type(&param.ty, param.attrs.iter().any(|a| a == "out"))?;
+            params.push(format!("{ty}{}", param.name));
+        }
+        let ret = self.map_type(&method.ret_ty, false)?;
+        Ok(format!(
+            "{ret}(CROSSCOM_CALL *{name})({params})",
+            name = method.name,
+            params = params.join(", ")
+        ))
+    }
+
+    /// C spelling of `idl_ty`, including a trailing space or `*` so a
+    /// name can be appended directly.
+    fn map_type(&self, idl_ty: &str, is_out: bool) -> Result<String, Error> {
+        let idl_ty = idl_ty.trim();
+        if let Some(inner) = idl_ty.strip_suffix("[]") {
+            self.find_interface(inner.trim())?;
+            return Ok("IObjectArray *".to_string());
+        }
+        if let Some(inner) = idl_ty.strip_suffix('?') {
+            let inner = inner.trim();
+            if inner == "float" {
+                return Ok("float ".to_string());
+            }
+            return Ok(format!("{} *", self.find_interface(inner)?.name));
+        }
+        let primitive = match idl_ty {
+            "long" => Some("long "),
+            "longlong" => Some("long long "),
+            "int" | "bool" => Some("int "),
+            "float" => Some("float "),
+            "byte" => Some("unsigned char "),
+            "byte*" => Some("const unsigned char *"),
+            "UUID" => Some("CrosscomUuid "),
+            "&str" | "string" => Some("const char *"),
+            "void" => Some("void "),
+            _ => None,
+        };
+        if let Some(primitive) = primitive {
+            return Ok(primitive.to_string());
+        }
+        let interface = self.find_interface(idl_ty)?;
+        Ok(if is_out {
+            format!("{} **", interface.name)
+        } else {
+            format!("{} *", interface.name)
+        })
+    }
+
+    fn find_interface(&self, name: &str) -> Result<&Interface, Error> {
+        match self.symbols.get(name) {
+            Some(Symbol::Interface(interface)) => Ok(interface),
+            Some(Symbol::Class) => Err(Error::Generate(format!(
+                "cannot use class type here: {name}"
+            ))),
+            None => Err(Error::Generate(format!("cannot find type: {name}"))),
+        }
+    }
+}
+
+/// Declared in this IDL rather than pulled in by `process_imports`.
+/// `codegen(ignore)` alone is not enough: `crosscom.idl` declares
+/// `IUnknown` that way because the Rust runtime hand-writes it, but C
+/// has no such runtime and needs the definition.
+fn is_local(interface: &Interface) -> bool {
+    !interface.attrs.contains_key("idl_origin")
+}
+
+fn uuid_initializer(uuid: &str) -> Result<String, Error> {
+    let bytes = parse_uuid_bytes(uuid)?;
+    Ok(format!(
+        "{{{{{}}}}}",
+        bytes
+            .iter()
+            .map(|byte| format!("0x{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+}
+
+/// Shared by every header; guarded so including several is fine.
+const COMMON_PRELUDE: &str = r#"#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+"#;
@@ -0,0 +1,126 @@
+//! Source locations for IDL errors.
+//!
+//! Every parsed interface, class and method carries a [`Span`]: the
+//! file it came from plus a byte offset. Parse errors and validation
+//! problems are reported as [`Diagnostic`]s that resolve spans to
+//! `file:line:column` and quote the offending line:
+//!
+//! ```text
+//! duplicate UUID 3f1c2a9e-5b7d-4e60-9c1a-2d8e4b6f7a10 on interface IFoo
+//!   --> crosscom/idl/openpal5.idl:12:1
+//!    |
+//! 12 | interface IFoo: IUnknown {
+//!    | ^
+//! note: first used by interface IBar
+//!   --> crosscom/idl/openpal4.idl:40:1
+//!    |
+//! 40 | interface IBar: IUnknown {
+//!    | ^
+//! ```
+
+use std::fmt;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// An IDL file's path and original text, shared by every span into it.
+#[derive(Debug)]
+pub(crate) struct SourceFile {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+/// A position in an IDL file. `file` is `None` only for items built
+/// outside the parser.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Span {
+    pub file: Option<Rc<SourceFile>>,
+    pub offset: usize,
+}
+
+impl Span {
+    pub(crate) fn new(file: &Rc<SourceFile>, offset: usize) -> Self {
+        Self {
+            file: Some(file.clone()),
+            offset,
+        }
+    }
+
+    pub(crate) fn location(&self) -> Option<Location> {
+        let file = self.file.as_ref()?;
+        let offset = self.offset.min(file.text.len());
+        let line_start = file.text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = file.text[offset..]
+            .find('\n')
+            .map_or(file.text.len(), |i| offset + i);
+        Some(Location {
+            path: file.path.clone(),
+            line: file.text[..line_start].matches('\n').count() + 1,
+            column: file.text[line_start..offset].chars().count() + 1,
+            line_text: file.text[line_start..line_end].trim_end().to_string(),
+        })
+    }
+}
+
+/// A resolved source position. `line` and `column` are 1-based;
+/// `column` counts characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub line_text: String,
+}
+
+/// One problem in an IDL file.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub message: String,
+    pub location: Option<Location>,
+    /// Related positions, e.g. the first declaration of a duplicate.
+    pub notes: Vec<(String, Option<Location>)>,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(message: impl Into<String>, span: &Span) -> Self {
+        Self {
+            message: message.into(),
+            location: span.location(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_note(mut self, message: impl Into<String>, span: &Span) -> Self {
+        self.notes.push((message.into(), span.location()));
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(location) = &self.location {
+            write_location(f, location)?;
+        }
+        for (note, location) in &self.notes {
+            write!(f, "\nnote: {note}")?;
+            if let Some(location) = location {
+                write_location(f, location)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_location(f: &mut fmt::Formatter<'_>, location: &Location) -> fmt::Result {
+    let gutter = " ".repeat(location.line.to_string().len());
+    write!(
+        f,
+        "\n{gutter}--> {}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {}^",
+        location.path.display(),
+        location.line,
+        location.column,
+        location.line,
+        location.line_text,
+        " ".repeat(location.column - 1),
+    )
+}
@@ -1,9 +1,16 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
+mod c;
+mod diagnostic;
 mod protosept;
 mod script_bridge;
+mod validate;
+
+pub use diagnostic::{Diagnostic, Location};
+use diagnostic::{SourceFile, Span};
 
 pub struct GeneratedUnit {
     pub source: String,
@@ -21,6 +28,9 @@ pub enum Error {
         message: String,
     },
     Generate(String),
+    /// The IDL parsed but is not well-formed; every problem found is
+    /// listed.
+    Validation(Vec<Diagnostic>),
 }
 
 impl fmt::Display for Error {
@@ -33,6 +43,12 @@ impl fmt::Display for Error {
                 write!(f, "failed to parse {}: {}", path.display(), message)
             }
             Error::Generate(message) => write!(f, "failed to generate Rust source: {message}"),
+            Error::Validation(diagnostics) => {
+                for diagnostic in diagnostics {
+                    writeln!(f, "error: {diagnostic}\n")?;
+                }
+                write!(f, "{} error(s) found in IDL", diagnostics.len())
+            }
         }
     }
 }
@@ -47,6 +63,7 @@ pub fn generate(idl_path: impl AsRef<Path>) -> Result<GeneratedUnit, Error> {
 
     let mut unit = parse_file(idl_path)?;
     process_imports(idl_path, &mut unit)?;
+    validate::validate(&unit, &dependencies)?;
     let source = RustGen::new(unit)?.r#gen()?;
 
     Ok(GeneratedUnit {
@@ -83,6 +100,7 @@ pub fn generate_protosept(idl_path: impl AsRef<Path>) -> Result<GeneratedUnit, E
 
     let mut unit = parse_file(idl_path)?;
     process_imports(idl_path, &mut unit)?;
+    validate::validate(&unit, &dependencies)?;
     let source = protosept::generate(unit)?;
 
     Ok(GeneratedUnit {
@@ -105,6 +123,55 @@ pub fn generate_protosept_to_file(
     Ok(generated.dependencies)
 }
 
+/// Generate a C header for the given IDL.
+///
+/// The header declares a vtable struct, an interface struct and an
+/// `IID_` constant per interface plus a `CLSID_` constant per class, so
+/// C and C++ code can both call crosscom objects and implement
+/// interfaces for the Rust side. Imported IDLs become `#include`s of
+/// their own generated headers.
+pub fn generate_c(idl_path: impl AsRef<Path>) -> Result<GeneratedUnit, Error> {
+    let idl_path = idl_path.as_ref();
+    let mut dependencies = Vec::new();
+    let mut visited = HashSet::new();
+    collect_dependencies(idl_path, &mut visited, &mut dependencies)?;
+
+    let stem = idl_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| {
+            Error::Generate(format!(
+                "cannot derive IDL stem from {}",
+                idl_path.display()
+            ))
+        })?
+        .to_string();
+
+    let mut unit = parse_file(idl_path)?;
+    process_imports(idl_path, &mut unit)?;
+    validate::validate(&unit, &dependencies)?;
+    let source = c::generate(unit, &stem)?;
+
+    Ok(GeneratedUnit {
+        source,
+        dependencies,
+    })
+}
+
+/// Convenience wrapper around [`generate_c`] that writes the result to
+/// `output_path`.
+pub fn generate_c_to_file(
+    idl_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+) -> Result<Vec<PathBuf>, Error> {
+    let generated = generate_c(idl_path)?;
+    std::fs::write(output_path.as_ref(), generated.source).map_err(|source| Error::Io {
+        path: output_path.as_ref().to_path_buf(),
+        source,
+    })?;
+    Ok(generated.dependencies)
+}
+
 /// Generate the Rust *script bridge* for the given IDL.
 ///
 /// For each interface marked `[protosept(scriptable)]`, the bridge emits
@@ -144,6 +211,7 @@ pub fn generate_script_bridge(
 
     let mut unit = parse_file(idl_path)?;
     process_imports(idl_path, &mut unit)?;
+    validate::validate(&unit, &dependencies)?;
     // RustGen normalises local interfaces' `module` field. Replicate
     // that step so the script-bridge emitter can compute Rust paths
     // for locally-declared interfaces without a special case.
@@ -172,20 +240,63 @@ pub fn generate_script_bridge_to_file(
     Ok(generated.dependencies)
 }
 
+/// Validate a set of IDL files without generating anything.
+///
+/// Each file gets the same checks the generators run, and UUIDs must
+/// additionally be unique across the whole set: two IDLs that never
+/// import each other can still end up in one process, where a shared
+/// UUID makes `query_interface` return the wrong interface. Returns
+/// every problem found, each with its `file:line:column`.
+pub fn check(idl_paths: &[PathBuf]) -> Result<(), Error> {
+    let mut dependencies = Vec::new();
+    let mut visited = HashSet::new();
+    for idl_path in idl_paths {
+        collect_dependencies(idl_path, &mut visited, &mut dependencies)?;
+    }
+
+    let mut units = Vec::new();
+    for path in &dependencies {
+        units.push(parse_file(path)?);
+    }
+    let mut diagnostics = validate::check_uuids(&units);
+    for idl_path in idl_paths {
+        let mut unit = parse_file(idl_path)?;
+        process_imports(idl_path, &mut unit)?;
+        diagnostics.extend(validate::check_unit(&unit));
+    }
+    validate::into_result(diagnostics)
+}
+
 fn parse_file(path: &Path) -> Result<CrossComIdl, Error> {
     let content = std::fs::read_to_string(path).map_err(|source| Error::Io {
         path: path.to_path_buf(),
         source,
     })?;
-    parse_source(&content).map_err(|message| Error::Parse {
+    parse_text(content, path.to_path_buf()).map_err(|diagnostic| Error::Parse {
         path: path.to_path_buf(),
-        message,
+        message: diagnostic.to_string(),
     })
 }
 
+#[cfg(test)]
 pub(crate) fn parse_source(input: &str) -> Result<CrossComIdl, String> {
-    let cleaned = strip_comments(input)?;
-    Parser::new(&cleaned).parse()
+    parse_text(input.to_string(), PathBuf::from("<input>")).map_err(|d| d.to_string())
+}
+
+fn parse_text(text: String, path: PathBuf) -> Result<CrossComIdl, Diagnostic> {
+    let source = Rc::new(SourceFile { path, text });
+    let to_diagnostic =
+        |err: ParseError| Diagnostic::new(err.message, &Span::new(&source, err.offset));
+    let cleaned = strip_comments(&source.text).map_err(to_diagnostic)?;
+    Parser::new(&cleaned, &source)
+        .parse()
+        .map_err(to_diagnostic)
+}
+
+/// A parse failure at a byte offset into the original source.
+struct ParseError {
+    message: String,
+    offset: usize,
 }
 
 /// Replace every `//` line comment and `/* ... */` block comment with an
@@ -194,7 +305,7 @@ pub(crate) fn parse_source(input: &str) -> Result<CrossComIdl, String> {
 /// messages keep pointing at meaningful source positions.
 ///
 /// Block comments do not nest, matching the conventions of C / MIDL.
-fn strip_comments(input: &str) -> Result<String, String> {
+fn strip_comments(input: &str) -> Result<String, ParseError> {
     let bytes = input.as_bytes();
     let mut out = Vec::with_capacity(bytes.len());
     let mut i = 0;
@@ -229,16 +340,20 @@ fn strip_comments(input: &str) -> Result<String, String> {
                 i += 1;
             }
             if !closed {
-                return Err(format!(
-                    "unterminated block comment starting at byte {start}"
-                ));
+                return Err(ParseError {
+                    message: "unterminated block comment".to_string(),
+                    offset: start,
+                });
             }
         } else {
             out.push(bytes[i]);
             i += 1;
         }
     }
-    String::from_utf8(out).map_err(|err| format!("strip_comments produced invalid UTF-8: {err}"))
+    String::from_utf8(out).map_err(|err| ParseError {
+        message: format!("strip_comments produced invalid UTF-8: {err}"),
+        offset: 0,
+    })
 }
 
 fn collect_dependencies(
@@ -388,6 +503,7 @@ pub(crate) struct Interface {
     pub methods: Vec<Method>,
     pub attrs: Attrs,
     pub module: Option<Module>,
+    pub span: Span,
 }
 
 impl Interface {
@@ -425,6 +541,7 @@ pub(crate) struct Class {
     pub methods: Vec<Method>,
     pub attrs: Attrs,
     pub module: Option<Module>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug)]
@@ -434,6 +551,7 @@ pub(crate) struct Method {
     pub params: Vec<MethodParameter>,
     pub attrs: Attrs,
     pub interface_module: Option<Module>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug)]
@@ -448,14 +566,26 @@ pub(crate) type Attrs = HashMap<String, String>;
 struct Parser<'a> {
     input: &'a str,
     pos: usize,
+    source: &'a Rc<SourceFile>,
 }
 
 impl<'a> Parser<'a> {
-    fn new(input: &'a str) -> Self {
-        Self { input, pos: 0 }
+    fn new(input: &'a str, source: &'a Rc<SourceFile>) -> Self {
+        Self {
+            input,
+            pos: 0,
+            source,
+        }
     }
 
-    fn parse(mut self) -> Result<CrossComIdl, String> {
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            offset: self.pos,
+        }
+    }
+
+    fn parse(mut self) -> Result<CrossComIdl, ParseError> {
         let mut items = Vec::new();
         let mut imports = Vec::new();
         let mut modules = Vec::new();
@@ -490,12 +620,16 @@ impl<'a> Parser<'a> {
                     file_name,
                     protosept_module: None,
                 });
-            } else if self.consume("interface") {
-                items.push(Item::Interface(self.parse_interface(attrs)?));
-            } else if self.consume("class") {
-                items.push(Item::Class(self.parse_class(attrs)?));
+            } else if self.input[self.pos..].starts_with("interface") {
+                let span = Span::new(self.source, self.pos);
+                self.consume("interface");
+                items.push(Item::Interface(self.parse_interface(attrs, span)?));
+            } else if self.input[self.pos..].starts_with("class") {
+                let span = Span::new(self.source, self.pos);
+                self.consume("class");
+                items.push(Item::Class(self.parse_class(attrs, span)?));
             } else {
-                return Err(format!("unexpected token near byte {}", self.pos));
+                return Err(self.error("unexpected token"));
             }
         }
 
@@ -506,7 +640,7 @@ impl<'a> Parser<'a> {
         })
     }
 
-    fn parse_interface(&mut self, attrs: Attrs) -> Result<Interface, String> {
+    fn parse_interface(&mut self, attrs: Attrs, span: Span) -> Result<Interface, ParseError> {
         let (name, bases, methods) = self.parse_decl_body(true)?;
         Ok(Interface {
             name,
@@ -514,10 +648,11 @@ impl<'a> Parser<'a> {
             methods,
             attrs,
             module: None,
+            span,
         })
     }
 
-    fn parse_class(&mut self, attrs: Attrs) -> Result<Class, String> {
+    fn parse_class(&mut self, attrs: Attrs, span: Span) -> Result<Class, ParseError> {
         let (name, bases, methods) = self.parse_decl_body(false)?;
         Ok(Class {
             name,
@@ -525,13 +660,14 @@ impl<'a> Parser<'a> {
             methods,
             attrs,
             module: None,
+            span,
         })
     }
 
     fn parse_decl_body(
         &mut self,
         with_methods: bool,
-    ) -> Result<(String, Vec<String>, Vec<Method>), String> {
+    ) -> Result<(String, Vec<String>, Vec<Method>), ParseError> {
         self.skip_ws();
         let name = self.read_identifier()?;
         self.skip_ws();
@@ -557,7 +693,7 @@ impl<'a> Parser<'a> {
                 break;
             }
             if self.eof() {
-                return Err("unexpected end of file inside declaration".to_string());
+                return Err(self.error("unexpected end of file inside declaration"));
             }
 
             let attrs = if self.peek_char() == Some('[') {
@@ -565,17 +701,22 @@ impl<'a> Parser<'a> {
             } else {
                 Attrs::new()
             };
+            let span = Span::new(self.source, self.pos);
             let decl = self.read_until(';')?;
             self.expect_char(';')?;
             if with_methods {
-                methods.push(parse_method_decl(decl.trim(), attrs)?);
+                let method = parse_method_decl(decl.trim(), attrs, span.clone());
+                methods.push(method.map_err(|message| ParseError {
+                    message,
+                    offset: span.offset,
+                })?);
             }
         }
 
         Ok((name, bases, methods))
     }
 
-    fn parse_attrs(&mut self) -> Result<Attrs, String> {
+    fn parse_attrs(&mut self) -> Result<Attrs, ParseError> {
         self.expect_char('[')?;
         let mut attrs = Attrs::new();
         loop {
@@ -602,9 +743,9 @@ impl<'a> Parser<'a> {
                 Some(',') => self.pos += 1,
                 Some(']') => {}
                 other => {
-                    return Err(format!(
+                    return Err(self.error(format!(
                         "expected ',' or ']' in attributes, found {other:?}"
-                    ));
+                    )));
                 }
             }
         }
@@ -612,7 +753,7 @@ impl<'a> Parser<'a> {
         Ok(attrs)
     }
 
-    fn read_attr_name(&mut self) -> Result<String, String> {
+    fn read_attr_name(&mut self) -> Result<String, ParseError> {
         let start = self.pos;
         while let Some(ch) = self.peek_char() {
             if ch == '(' || ch == ',' || ch == ']' || ch.is_whitespace() {
@@ -621,13 +762,13 @@ impl<'a> Parser<'a> {
             self.pos += ch.len_utf8();
         }
         if start == self.pos {
-            Err(format!("expected attribute name near byte {}", self.pos))
+            Err(self.error("expected attribute name"))
         } else {
             Ok(self.input[start..self.pos].trim().to_string())
         }
     }
 
-    fn read_identifier(&mut self) -> Result<String, String> {
+    fn read_identifier(&mut self) -> Result<String, ParseError> {
         let start = self.pos;
         while let Some(ch) = self.peek_char() {
             if ch.is_whitespace() || matches!(ch, ':' | '{' | '}' | '(' | ')' | ';' | ',') {
@@ -636,13 +777,13 @@ impl<'a> Parser<'a> {
             self.pos += ch.len_utf8();
         }
         if start == self.pos {
-            Err(format!("expected identifier near byte {}", self.pos))
+            Err(self.error("expected identifier"))
         } else {
             Ok(self.input[start..self.pos].trim().to_string())
         }
     }
 
-    fn read_until(&mut self, target: char) -> Result<&'a str, String> {
+    fn read_until(&mut self, target: char) -> Result<&'a str, ParseError> {
         let start = self.pos;
         while let Some(ch) = self.peek_char() {
             if ch == target {
@@ -650,20 +791,20 @@ impl<'a> Parser<'a> {
             }
             self.pos += ch.len_utf8();
         }
-        Err(format!("expected '{target}' near byte {}", self.pos))
+        Err(ParseError {
+            message: format!("expected '{target}' before end of file"),
+            offset: start,
+        })
     }
 
-    fn expect_char(&mut self, expected: char) -> Result<(), String> {
+    fn expect_char(&mut self, expected: char) -> Result<(), ParseError> {
         self.skip_ws();
         match self.peek_char() {
             Some(ch) if ch == expected => {
                 self.pos += ch.len_utf8();
                 Ok(())
             }
-            other => Err(format!(
-                "expected '{expected}', found {other:?} near byte {}",
-                self.pos
-            )),
+            other => Err(self.error(format!("expected '{expected}', found {other:?}"))),
         }
     }
 
@@ -695,7 +836,7 @@ impl<'a> Parser<'a> {
     }
 }
 
-fn parse_method_decl(decl: &str, attrs: Attrs) -> Result<Method, String> {
+fn parse_method_decl(decl: &str, attrs: Attrs, span: Span) -> Result<Method, String> {
     let open = decl
         .find('(')
         .ok_or_else(|| format!("method missing '(' in {decl:?}"))?;
@@ -719,6 +860,7 @@ fn parse_method_decl(decl: &str, attrs: Attrs) -> Result<Method, String> {
         params,
         attrs,
         interface_module: None,
+        span,
     })
 }
 
@@ -857,9 +999,34 @@ impl RustGen {
             }
         }
 
+        out.push_str(&self.gen_register_reflection());
         Ok(out)
     }
 
+    /// `register_reflection()` for every interface generated here, so a
+    /// host can make this module's interfaces visible to
+    /// `crosscom::reflect` with one call.
+    fn gen_register_reflection(&self) -> String {
+        let mut registrations = String::new();
+        for item in &self.unit.items {
+            match item {
+                Item::Interface(interface) if !interface.codegen_ignore() => {
+                    registrations.push_str(&format!(
+                        "    {}::reflect::register::<{}>();\n",
+                        self.crosscom_module_name, interface.name
+                    ));
+                }
+                _ => {}
+            }
+        }
+        format!(
+            "\n/// Register this module's interfaces with `{crosscom}::reflect`.\n\
+             #[allow(dead_code)]\n\
+             pub fn register_reflection() {{\n{registrations}}}\n",
+            crosscom = self.crosscom_module_name,
+        )
+    }
+
     fn rust_crate(&self) -> Result<String, Error> {
         Ok(rust_module(&self.unit)?
             .module_name
@@ -949,6 +1116,10 @@ impl RustGen {
         let raw_methods = self.gen_raw_method_impl_for_class(class)?;
         let class_ccw_vtbl = self.gen_class_ccw_vtbl(class)?;
         let base_struct = self.gen_base_struct(class)?;
+        let class_id = match class.attrs.get("uuid") {
+            Some(uuid) => format!("const CLASS_ID: [u8; 16] = {};", uuid_to_hex_array(uuid)?),
+            None => String::new(),
+        };
         let name = &class.name;
         Ok(format!(
             r#"
@@ -996,6 +1167,7 @@ mod {name}_crosscom_impl {{
 
         let previous = (*object).ref_count.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
         if previous - 1 == 0 {{
+            {crosscom}::on_final_release(object as *const std::os::raw::c_void);
             let _ = Box::from_raw(object as *mut {name}Ccw);
         }}
 
@@ -1007,6 +1179,8 @@ mod {name}_crosscom_impl {{
     {class_ccw_vtbl}
     impl {crosscom}::ComObject for $impl_type {{
         type CcwType = {name}Ccw;
+        const CLASS_NAME: &'static str = "{name}";
+        {class_id}
 
         fn create_ccw(self) -> Self::CcwType {{
             Self::CcwType {{
@@ -1279,10 +1453,13 @@ impl {name} {{
         use crosscom::ComInterface;
         uuid::Uuid::from_bytes({name}::INTERFACE_ID)
     }}
+
+    {reflect_invoke}
 }}
 "#,
             name = interface.name,
-            safe_wrappers = self.gen_interface_method_safe_wrapper(interface)?
+            safe_wrappers = self.gen_interface_method_safe_wrapper(interface)?,
+            reflect_invoke = self.gen_reflect_invoke(interface)?
         ));
 
         out.push_str(&format!("pub trait {}Impl {{\n", interface.name));
@@ -1301,17 +1478,215 @@ impl {name} {{
 impl {crosscom}::ComInterface for {name} {{
     // {uuid}
     const INTERFACE_ID: [u8; 16] = {uuid_bytes};
+
+    const REFLECTION: Option<&'static {crosscom}::reflect::InterfaceDesc> = Some(&{reflection});
 }}
 
 "#,
             crosscom = self.crosscom_module_name,
             name = interface.name,
-            uuid_bytes = uuid_to_hex_array(uuid)?
+            uuid_bytes = uuid_to_hex_array(uuid)?,
+            reflection = self.gen_reflection(interface)?
         ));
 
         Ok(())
     }
 
+    /// Methods listed in `interface`'s reflection data, with their
+    /// vtable slots: everything in the vtable except the methods of the
+    /// synthetic root (`IUnknown`).
+    fn reflected_methods(&self, interface: &Interface) -> Result<Vec<(usize, Method)>, Error> {
+        let mut methods = Vec::new();
+        let mut slot = 0;
+        for iface in self.collect_inherit_chain(&interface.name)? {
+            let synthetic_root = iface.bases.is_empty() && iface.codegen_ignore();
+            for method in &iface.methods {
+                if !synthetic_root {
+                    let mut method = method.clone();
+                    method.interface_module = iface.module.clone();
+                    methods.push((slot, method));
+                }
+                slot += 1;
+            }
+        }
+        Ok(methods)
+    }
+
+    fn gen_reflection(&self, interface: &Interface) -> Result<String, Error> {
+        let crosscom = &self.crosscom_module_name;
+        let module = interface
+            .module
+            .as_ref()
+            .map_or("", |m| m.module_name.as_str());
+        let base = match interface.bases.first() {
+            Some(base) => format!("Some({})", self.gen_interface_ref(base)?),
+            None => "None".to_string(),
+        };
+        let mut methods = String::new();
+        for (slot, method) in self.reflected_methods(interface)? {
+            let internal = method.attrs.contains_key("internal");
+            let mut params = String::new();
+            for param in &method.params {
+                params.push_str(&format!(
+                    "{crosscom}::reflect::ParamDesc {{ name: {:?}, ty: {}, out: {} }},",
+                    param.name,
+                    self.gen_type_desc(&param.ty, internal)?,
+                    param.attrs.iter().any(|attr| attr == "out"),
+                ));
+            }
+            methods.push_str(&format!(
+                "{crosscom}::reflect::MethodDesc {{ name: {name:?}, slot: {slot}, params: &[{params}], \
+                 ret: {ret}, internal: {internal}, property: {property} }},\n",
+                name = method.name,
+                ret = self.gen_type_desc(&method.ret_ty, internal)?,
+                property = method.attrs.contains_key("property"),
+            ));
+        }
+        Ok(format!(
+            "{crosscom}::reflect::InterfaceDesc {{
+        name: {name:?},
+        module: {module:?},
+        iid: Self::INTERFACE_ID,
+        base: {base},
+        methods: &[{methods}],
+        invoke: {name}::reflect_invoke,
+    }}",
+            name = interface.name,
+        ))
+    }
+
+    fn gen_interface_ref(&self, name: &str) -> Result<String, Error> {
+        let interface = self
+            .interface_symbol(name)?
+            .ok_or_else(|| Error::Generate(format!("cannot find type: {name}")))?;
+        let module = &interface.module.as_ref().unwrap().module_name;
+        Ok(format!(
+            "{crosscom}::reflect::InterfaceRef {{ name: {name:?}, iid: <{module}::{name} as {crosscom}::ComInterface>::INTERFACE_ID }}",
+            crosscom = self.crosscom_module_name,
+            name = interface.name,
+        ))
+    }
+
+    fn gen_type_desc(&self, idl_ty: &str, internal: bool) -> Result<String, Error> {
+        let prefix = format!("{}::reflect::TypeDesc", self.crosscom_module_name);
+        let idl_ty = idl_ty.trim();
+        if internal {
+            return Ok(format!("{prefix}::Rust({idl_ty:?})"));
+        }
+        if let Some(inner) = idl_ty.strip_suffix("[]") {
+            return Ok(format!(
+                "{prefix}::Array({})",
+                self.gen_interface_ref(inner.trim())?
+            ));
+        }
+        if let Some(inner) = idl_ty.strip_suffix('?') {
+            let inner = inner.trim();
+            if inner == "float" {
+                return Ok(format!("{prefix}::OptionalFloat"));
+            }
+            return Ok(format!(
+                "{prefix}::OptionalInterface({})",
+                self.gen_interface_ref(inner)?
+            ));
+        }
+        let variant = match idl_ty {
+            "void" => "Void",
+            "bool" => "Bool",
+            "int" => "Int",
+            "long" => "Long",
+            "longlong" => "LongLong",
+            "byte" => "Byte",
+            "float" => "Float",
+            "UUID" => "Uuid",
+            "&str" | "string" => "Str",
+            "byte*" => "Bytes",
+            _ => {
+                return Ok(format!(
+                    "{prefix}::Interface({})",
+                    self.gen_interface_ref(idl_ty)?
+                ));
+            }
+        };
+        Ok(format!("{prefix}::{variant}"))
+    }
+
+    /// The `InterfaceDesc::invoke` thunk: unpacks `Variant` arguments
+    /// and calls the safe wrapper. Only methods `MethodDesc::is_invokable`
+    /// accepts get an arm.
+    fn gen_reflect_invoke(&self, interface: &Interface) -> Result<String, Error> {
+        let crosscom = &self.crosscom_module_name;
+        let mut arms = String::new();
+        for (index, (_, method)) in self.reflected_methods(interface)?.into_iter().enumerate() {
+            let Some(args) = self.gen_invoke_args(&method) else {
+                continue;
+            };
+            let call = if method.ret_ty.trim() == "void" {
+                format!(
+                    "this.{name}({args});
+                Ok({crosscom}::reflect::Variant::Void)",
+                    name = method.name,
+                )
+            } else {
+                format!(
+                    "Ok({crosscom}::reflect::Variant::from(this.{name}({args})))",
+                    name = method.name,
+                )
+            };
+            arms.push_str(&format!(
+                "            {index} => {{
+                {crosscom}::reflect::check_arity(args, {count})?;
+                {call}
+            }}
+",
+                count = method.params.len(),
+            ));
+        }
+        Ok(format!(
+            r#"#[doc(hidden)]
+    pub fn reflect_invoke(
+        this: &{crosscom}::ComRc<{crosscom}::IUnknown>,
+        method: usize,
+        args: &[{crosscom}::reflect::Variant],
+    ) -> Result<{crosscom}::reflect::Variant, {crosscom}::reflect::InvokeError> {{
+        let this = this
+            .query_interface::<{name}>()
+            .ok_or({crosscom}::reflect::InvokeError::NoInterface({name}::uuid()))?;
+        match method {{
+{arms}            _ => Err({crosscom}::reflect::InvokeError::NotInvokable {{
+                name: format!("{name} method #{{method}}"),
+            }}),
+        }}
+    }}"#,
+            name = interface.name,
+        ))
+    }
+
+    /// Argument list converting `args[i]` for each parameter, or `None`
+    /// if `method` cannot be called dynamically.
+    fn gen_invoke_args(&self, method: &Method) -> Option<String> {
+        if method.attrs.contains_key("internal") || !is_dynamic_type(&method.ret_ty) {
+            return None;
+        }
+        let mut args = Vec::new();
+        for (i, param) in method.params.iter().enumerate() {
+            if param.attrs.iter().any(|attr| attr == "out") || !is_dynamic_type(&param.ty) {
+                return None;
+            }
+            let conversion = match param.ty.trim() {
+                "bool" => "to_bool",
+                "int" | "long" | "longlong" | "byte" => "to_int",
+                "float" => "to_float",
+                "float?" => "to_optional_float",
+                "UUID" => "to_uuid",
+                "&str" | "string" => "to_str",
+                ty if ty.ends_with('?') => "to_optional_object",
+                _ => "to_object",
+            };
+            args.push(format!("args[{i}].{conversion}()?"));
+        }
+        Some(args.join(", "))
+    }
+
     fn gen_interface_method_safe_wrapper(&self, interface: &Interface) -> Result<String, Error> {
         let mut out = String::new();
         for method in self.collect_all_methods(&interface.name, false)? {
@@ -1599,6 +1974,13 @@ pub {signature} {{
     }
 }
 
+/// Whether `crosscom::reflect::Variant` can carry `idl_ty`; mirrors
+/// `TypeDesc::is_dynamic`.
+fn is_dynamic_type(idl_ty: &str) -> bool {
+    let idl_ty = idl_ty.trim();
+    !idl_ty.ends_with("[]") && idl_ty != "byte*"
+}
+
 fn type_map(idl_ty: &str) -> Option<(&'static str, &'static str, Option<&'static str>)> {
     match idl_ty {
         "long" => Some(("std::os::raw::c_long", "std::os::raw::c_long", None)),
@@ -1746,6 +2128,45 @@ mod tests {
         assert!(!body.contains("::"), "body still contains ::\n{body}");
     }
 
+    #[test]
+    fn generated_classes_report_identity_and_final_release() {
+        let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let idl_dir = manifest_dir.join("..").join("idl");
+        let out = generate(idl_dir.join("crosscom.idl")).unwrap().source;
+
+        assert!(
+            out.contains("const CLASS_NAME: &'static str = \"ObjectArray\";"),
+            "{out}"
+        );
+        assert!(out.contains("const CLASS_ID: [u8; 16] = [176u8,"), "{out}");
+        // Weak refs must be cleared before the inner value drops.
+        let notify = out.find("crosscom::on_final_release(object").expect("hook");
+        let free = out[notify..].find("Box::from_raw(object").expect("free");
+        assert!(free > 0);
+    }
+
+    #[test]
+    fn generated_interfaces_carry_reflection() {
+        let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let idl_dir = manifest_dir.join("..").join("idl");
+        let out = generate(idl_dir.join("test.idl")).unwrap().source;
+
+        // Slots count IUnknown's three methods; inherited methods come first.
+        assert!(out.contains("name: \"test\", slot: 3"), "{out}");
+        assert!(out.contains("name: \"mul\", slot: 4"), "{out}");
+        assert!(out.contains("invoke: ITest2::reflect_invoke"), "{out}");
+        assert!(
+            out.contains("TypeDesc::Interface(crosscom::reflect::InterfaceRef { name: \"ITest3\""),
+            "{out}"
+        );
+        assert!(
+            out.contains(
+                "pub fn register_reflection() {\n    crosscom::reflect::register::<ITest>();"
+            ),
+            "{out}"
+        );
+    }
+
     #[test]
     fn parses_uuid_bytes_in_network_order() {
         assert_eq!(
@@ -2091,4 +2512,257 @@ mod tests {
             assert!(found, "{file}::{iface} should be [protosept(scriptable)]");
         }
     }
+
+    // ---------- C header tests ----------
+
+    fn repository_idls() -> Vec<PathBuf> {
+        let idl_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("idl");
+        let mut idls: Vec<PathBuf> = std::fs::read_dir(&idl_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "idl"))
+            .collect();
+        idls.sort();
+        idls
+    }
+
+    #[test]
+    fn c_headers_are_up_to_date() {
+        let include_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("runtime")
+            .join("c")
+            .join("include");
+        for idl in repository_idls() {
+            let stem = idl.file_stem().unwrap().to_str().unwrap().to_string();
+            let generated = generate_c(&idl)
+                .unwrap_or_else(|err| panic!("C gen failed for {stem}: {err}"))
+                .source;
+            let checked_in =
+                std::fs::read_to_string(include_dir.join(format!("{stem}.h"))).unwrap_or_default();
+            assert!(
+                generated == checked_in,
+                "crosscom/runtime/c/include/{stem}.h is stale; regenerate with \
+                 `crosscom-ccidl --c crosscom/idl/{stem}.idl crosscom/runtime/c/include/{stem}.h`"
+            );
+        }
+    }
+
+    #[test]
+    fn c_vtable_matches_rust_slot_order() {
+        let idl_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("idl");
+        let out = generate_c(idl_dir.join("radiance.idl")).unwrap().source;
+        let start = out.find("typedef struct IComponentVtbl {").unwrap();
+        let body = &out[start..start + out[start..].find("} IComponentVtbl;").unwrap()];
+        let slots: Vec<&str> = body
+            .lines()
+            .skip(1)
+            .map(|line| {
+                let name_start = line.find("*").unwrap() + 1;
+                &line[name_start..line[name_start..].find(')').unwrap() + name_start]
+            })
+            .collect();
+        assert_eq!(
+            slots,
+            vec![
+                "query_interface",
+                "add_ref",
+                "release",
+                "on_loading",
+                "on_updating",
+                "on_unloading"
+            ]
+        );
+        assert!(
+            out.contains("void (CROSSCOM_CALL *on_updating)(IComponent *self, float delta_sec);")
+        );
+        assert!(out.contains("#include \"crosscom.h\""));
+        assert!(out.contains("static const CrosscomUuid IID_IComponent = {{0x03, 0x74,"));
+
+        // IUnknown is codegen(ignore) for Rust but C needs it spelled out.
+        let crosscom = generate_c(idl_dir.join("crosscom.idl")).unwrap().source;
+        assert!(crosscom.contains("struct IUnknown {"));
+        assert!(crosscom.contains("static const CrosscomUuid CLSID_ObjectArray"));
+    }
+
+    #[test]
+    fn c_headers_compile_when_a_c_compiler_is_available() {
+        let cc = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+        let out_dir =
+            std::env::temp_dir().join(format!("crosscom_c_headers_{}", std::process::id()));
+        std::fs::create_dir_all(&out_dir).unwrap();
+        let mut includes = String::new();
+        for idl in repository_idls() {
+            let stem = idl.file_stem().unwrap().to_str().unwrap().to_string();
+            generate_c_to_file(&idl, out_dir.join(format!("{stem}.h"))).unwrap();
+            includes.push_str(&format!("#include \"{stem}.h\"\n"));
+        }
+        let source = out_dir.join("all.c");
+        std::fs::write(&source, includes).unwrap();
+
+        let status = std::process::Command::new(&cc)
+            .arg("-fsyntax-only")
+            .arg("-I")
+            .arg(&out_dir)
+            .arg(&source)
+            .status();
+        let _ = std::fs::remove_dir_all(&out_dir);
+        match status {
+            Ok(status) => assert!(status.success(), "generated headers failed to compile"),
+            Err(err) => eprintln!("skipping: cannot run {cc}: {err}"),
+        }
+    }
+
+    // ---------- validation tests ----------
+
+    fn validation_errors(src: &str) -> Vec<String> {
+        let unit = parse_source(src).unwrap();
+        validate::check_unit(&unit)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn repository_idls_pass_check() {
+        check(&repository_idls()).unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    #[test]
+    fn duplicate_uuid_across_idls_reports_both_locations() {
+        let tmp = std::env::temp_dir().join(format!("ccidl-dup-uuid-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&tmp);
+        std::fs::create_dir_all(&tmp).unwrap();
+        let a = write_idl(
+            &tmp,
+            "a.idl",
+            "module(rust) demo::a;\n\n[uuid(11111111-2222-3333-4444-555555555555)]\ninterface IA {\n    void a();\n}\n",
+        );
+        let b = write_idl(
+            &tmp,
+            "b.idl",
+            "module(rust) demo::b;\n\n\n[uuid(11111111-2222-3333-4444-555555555555)]\n  interface IB {\n    void b();\n}\n",
+        );
+
+        let err = check(&[a, b]).unwrap_err().to_string();
+        assert!(
+            err.contains("duplicate UUID 11111111-2222-3333-4444-555555555555 on interface IB"),
+            "{err}"
+        );
+        assert!(err.contains("b.idl:5:3"), "{err}");
+        assert!(err.contains("5 |   interface IB {"), "{err}");
+        assert!(err.contains("note: first used by interface IA"), "{err}");
+        assert!(err.contains("a.idl:4:1"), "{err}");
+        std::fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn unknown_and_class_types_are_reported() {
+        let errors = validation_errors(
+            r#"module(rust) demo::v;
+[uuid(00000000-0000-0000-0000-000000000001)]
+interface IFoo {
+    IMissing get();
+    void take(Foo foo);
+    int[] numbers();
+}
+[uuid(00000000-0000-0000-0000-000000000002)]
+class Foo: IFoo {}
+"#,
+        );
+        assert_eq!(errors.len(), 3, "{errors:#?}");
+        assert!(errors[0].starts_with("in method IFoo.get, return type: unknown type `IMissing`"));
+        assert!(errors[0].contains("<input>:4:5"), "{}", errors[0]);
+        assert!(errors[1].contains("`Foo` is a class; use one of its interfaces (IFoo)"));
+        assert!(errors[2].contains("arrays can only hold interfaces"));
+    }
+
+    #[test]
+    fn duplicate_inherited_method_is_reported() {
+        let errors = validation_errors(
+            r#"module(rust) demo::v;
+[uuid(00000000-0000-0000-0000-000000000001)]
+interface IBase {
+    void update(float delta_sec);
+}
+[uuid(00000000-0000-0000-0000-000000000002)]
+interface IDerived: IBase {
+    int update();
+}
+"#,
+        );
+        assert_eq!(errors.len(), 1, "{errors:#?}");
+        assert!(errors[0].starts_with("method IDerived.update is already declared in IBase"));
+        assert!(errors[0].contains("<input>:8:5"), "{}", errors[0]);
+        assert!(errors[0].contains("<input>:4:5"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn bad_bases_are_reported() {
+        let errors = validation_errors(
+            r#"module(rust) demo::v;
+[uuid(00000000-0000-0000-0000-000000000001)]
+interface IA: IB {}
+[uuid(00000000-0000-0000-0000-000000000002)]
+interface IB: IA {}
+[uuid(00000000-0000-0000-0000-000000000003)]
+interface IC: Impl {}
+[uuid(00000000-0000-0000-0000-000000000004)]
+class Impl: IA, IA, Nope {}
+"#,
+        );
+        let joined = errors.join("\n");
+        assert!(
+            joined.contains("interface IA inherits from itself"),
+            "{joined}"
+        );
+        assert!(
+            joined.contains("interface IC inherits from class Impl"),
+            "{joined}"
+        );
+        assert!(
+            joined.contains("class Impl lists interface IA more than once"),
+            "{joined}"
+        );
+        assert!(
+            joined.contains("class Impl implements unknown interface Nope"),
+            "{joined}"
+        );
+    }
+
+    #[test]
+    fn unscriptable_signature_is_reported() {
+        let errors = validation_errors(
+            r#"module(rust) demo::v;
+[uuid(00000000-0000-0000-0000-000000000001)]
+interface IBase {
+    UUID id();
+}
+[uuid(00000000-0000-0000-0000-000000000002), protosept(scriptable)]
+interface IScripted: IBase {
+    void ok(int a, &str b);
+    string name();
+}
+"#,
+        );
+        assert_eq!(errors.len(), 2, "{errors:#?}");
+        assert!(errors[0].starts_with(
+            "return type `UUID` of IBase.id cannot cross the script bridge, but IScripted is \
+             [protosept(scriptable)]"
+        ));
+        assert!(errors[0].contains("note: IScripted is marked scriptable here"));
+        assert!(errors[1].starts_with("return type `string` of IScripted.name"));
+    }
+
+    #[test]
+    fn parse_errors_report_line_and_column() {
+        let src = "module(rust) demo::v;\n\ninterface IFoo {\n    void ok();\n    ???\n";
+        let err = parse_source(src).unwrap_err();
+        assert!(err.contains("<input>:5:5"), "{err}");
+        assert!(err.contains("5 |     ???"), "{err}");
+    }
 }
@@ -6,18 +6,34 @@ fn main() {
     for arg in std::env::args_os().skip(1) {
         match arg.to_string_lossy().as_ref() {
             "--protosept" => mode = "protosept",
+            "--c" => mode = "c",
+            "--check" => mode = "check",
             _ => positional.push(PathBuf::from(arg)),
         }
     }
+
+    if mode == "check" {
+        if positional.is_empty() {
+            eprintln!("usage: crosscom-ccidl --check <input.idl>...");
+            std::process::exit(2);
+        }
+        if let Err(err) = crosscom_ccidl::check(&positional) {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let mut args = positional.into_iter();
 
     let idl_path = args.next().unwrap_or_else(|| {
-        eprintln!("usage: crosscom-ccidl [--protosept] <input.idl> [output]");
+        eprintln!("usage: crosscom-ccidl [--protosept | --c] <input.idl> [output]\n       crosscom-ccidl --check <input.idl>...");
         std::process::exit(2);
     });
 
     let generated = match mode {
         "protosept" => crosscom_ccidl::generate_protosept(&idl_path),
+        "c" => crosscom_ccidl::generate_c(&idl_path),
         _ => crosscom_ccidl::generate(&idl_path),
     }
     .unwrap_or_else(|err| {
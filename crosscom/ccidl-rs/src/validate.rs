@@ -0,0 +1,446 @@
+//! Semantic checks on a parsed IDL unit.
+//!
+//! The generators assume a well-formed unit and fail on the first
+//! inconsistency with a bare message, or not at all: two interfaces
+//! sharing a UUID generate fine and only misbehave at runtime when
+//! `query_interface` hands out the wrong vtable. [`validate`] runs after
+//! `process_imports` and before any generator, collects every problem
+//! it can find and reports each one with its source location.
+//!
+//! Checked:
+//!
+//! - UUIDs are present on interfaces, well-formed, and unique across the
+//!   IDL and everything it imports, classes included;
+//! - names are declared once across the IDL and its imports;
+//! - interfaces have at most one base, which must be an interface, and
+//!   inheritance does not loop;
+//! - classes implement only interfaces, each at most once;
+//! - no method name is declared twice along an inheritance chain;
+//! - every type in a non-internal method resolves (classes are not
+//!   types);
+//! - `[protosept(scriptable)]` interfaces only use types the script
+//!   bridge can marshal (see the table in `script_bridge.rs`).
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::diagnostic::{Diagnostic, Span};
+use crate::{Class, CrossComIdl, Error, Interface, Item, Method, parse_file, parse_uuid_bytes};
+
+/// Validate `unit` (already through `process_imports`). `dependencies`
+/// are the IDL files `unit` pulls in, itself included, as returned by
+/// `collect_dependencies`; they are re-read so UUIDs of imported classes
+/// are covered too.
+pub(crate) fn validate(unit: &CrossComIdl, dependencies: &[PathBuf]) -> Result<(), Error> {
+    let mut units = Vec::new();
+    for path in dependencies {
+        units.push(parse_file(path)?);
+    }
+    let mut diagnostics = check_uuids(&units);
+    diagnostics.extend(check_unit(unit));
+    into_result(diagnostics)
+}
+
+/// Every check except cross-file UUID uniqueness.
+pub(crate) fn check_unit(unit: &CrossComIdl) -> Vec<Diagnostic> {
+    Validator::new(unit).check()
+}
+
+pub(crate) fn into_result(diagnostics: Vec<Diagnostic>) -> Result<(), Error> {
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Validation(diagnostics))
+    }
+}
+
+/// Every interface and class UUID across `units` must be unique.
+pub(crate) fn check_uuids(units: &[CrossComIdl]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut seen: HashMap<[u8; 16], (String, &Span)> = HashMap::new();
+    for unit in units {
+        for item in &unit.items {
+            let (kind, name, attrs, span) = match item {
+                Item::Interface(interface) => (
+                    "interface",
+                    &interface.name,
+                    &interface.attrs,
+                    &interface.span,
+                ),
+                Item::Class(class) => ("class", &class.name, &class.attrs, &class.span),
+            };
+            let what = format!("{kind} {name}");
+            let Some(uuid) = attrs.get("uuid") else {
+                if kind == "interface" {
+                    diagnostics.push(Diagnostic::new(
+                        format!("{what} has no [uuid(...)] attribute"),
+                        span,
+                    ));
+                }
+                continue;
+            };
+            let Ok(bytes) = parse_uuid_bytes(uuid) else {
+                diagnostics.push(Diagnostic::new(
+                    format!("malformed UUID {uuid:?} on {what}"),
+                    span,
+                ));
+                continue;
+            };
+            if let Some((first, first_span)) = seen.get(&bytes) {
+                diagnostics.push(
+                    Diagnostic::new(format!("duplicate UUID {uuid} on {what}"), span)
+                        .with_note(format!("first used by {first}"), first_span),
+                );
+            } else {
+                seen.insert(bytes, (what, span));
+            }
+        }
+    }
+    diagnostics
+}
+
+enum Symbol<'a> {
+    Interface(&'a Interface),
+    Class(&'a Class),
+}
+
+impl Symbol<'_> {
+    fn span(&self) -> &Span {
+        match self {
+            Symbol::Interface(interface) => &interface.span,
+            Symbol::Class(class) => &class.span,
+        }
+    }
+}
+
+struct Validator<'a> {
+    unit: &'a CrossComIdl,
+    symbols: HashMap<&'a str, Symbol<'a>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    fn new(unit: &'a CrossComIdl) -> Self {
+        Self {
+            unit,
+            symbols: HashMap::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn check(mut self) -> Vec<Diagnostic> {
+        for item in &self.unit.items {
+            let (name, symbol) = match item {
+                Item::Interface(interface) => {
+                    (interface.name.as_str(), Symbol::Interface(interface))
+                }
+                Item::Class(class) => (class.name.as_str(), Symbol::Class(class)),
+            };
+            if let Some(previous) = self.symbols.get(name) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        format!("`{name}` is declared more than once"),
+                        symbol.span(),
+                    )
+                    .with_note("previously declared here", previous.span()),
+                );
+            } else {
+                self.symbols.insert(name, symbol);
+            }
+        }
+
+        for item in &self.unit.items {
+            match item {
+                Item::Interface(interface) if is_local(interface) => {
+                    self.check_interface(interface)
+                }
+                Item::Class(class) => self.check_class(class),
+                Item::Interface(_) => {}
+            }
+        }
+        self.diagnostics
+    }
+
+    fn check_interface(&mut self, interface: &'a Interface) {
+        if interface.bases.len() > 1 {
+            self.diagnostics.push(Diagnostic::new(
+                format!(
+                    "interface {} has {} bases; an interface inherits from at most one",
+                    interface.name,
+                    interface.bases.len()
+                ),
+                &interface.span,
+            ));
+        }
+        if let Some(base) = interface.bases.first() {
+            match self.symbols.get(base.as_str()) {
+                Some(Symbol::Interface(_)) => {}
+                Some(Symbol::Class(class)) => self.diagnostics.push(
+                    Diagnostic::new(
+                        format!(
+                            "interface {} inherits from class {base}; only interfaces can be bases",
+                            interface.name
+                        ),
+                        &interface.span,
+                    )
+                    .with_note(format!("{base} is declared here"), &class.span),
+                ),
+                None => self.diagnostics.push(Diagnostic::new(
+                    format!(
+                        "interface {} inherits from unknown interface {base}",
+                        interface.name
+                    ),
+                    &interface.span,
+                )),
+            }
+        }
+
+        let Some(chain) = self.chain(interface) else {
+            self.diagnostics.push(Diagnostic::new(
+                format!("interface {} inherits from itself", interface.name),
+                &interface.span,
+            ));
+            return;
+        };
+
+        // Only this interface's own methods are reported; clashes inside
+        // a base are reported when the base itself is checked.
+        let mut declared: HashMap<&str, (&str, &Span)> = HashMap::new();
+        for ancestor in &chain[..chain.len() - 1] {
+            for method in &ancestor.methods {
+                declared
+                    .entry(method.name.as_str())
+                    .or_insert((ancestor.name.as_str(), &method.span));
+            }
+        }
+        for method in &interface.methods {
+            if let Some((owner, span)) = declared.get(method.name.as_str()) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        format!(
+                            "method {}.{} is already declared in {owner}",
+                            interface.name, method.name
+                        ),
+                        &method.span,
+                    )
+                    .with_note(format!("{owner}.{} is declared here", method.name), span),
+                );
+            } else {
+                declared.insert(&method.name, (&interface.name, &method.span));
+            }
+        }
+
+        for method in interface.public_methods() {
+            self.check_method_types(interface, &method);
+        }
+
+        if interface.lang_flag("protosept", "scriptable") {
+            for ancestor in chain {
+                if ancestor.bases.is_empty() && ancestor.codegen_ignore() {
+                    continue;
+                }
+                for method in ancestor.public_methods() {
+                    self.check_scriptable(interface, ancestor, &method);
+                }
+            }
+        }
+    }
+
+    fn check_class(&mut self, class: &'a Class) {
+        if class.bases.is_empty() {
+            self.diagnostics.push(Diagnostic::new(
+                format!("class {} implements no interfaces", class.name),
+                &class.span,
+            ));
+        }
+        let mut seen = HashSet::new();
+        for base in &class.bases {
+            if !seen.insert(base.as_str()) {
+                self.diagnostics.push(Diagnostic::new(
+                    format!("class {} lists interface {base} more than once", class.name),
+                    &class.span,
+                ));
+                continue;
+            }
+            match self.symbols.get(base.as_str()) {
+                Some(Symbol::Interface(_)) => {}
+                Some(Symbol::Class(other)) => self.diagnostics.push(
+                    Diagnostic::new(
+                        format!(
+                            "class {} implements class {base}; classes implement interfaces",
+                            class.name
+                        ),
+                        &class.span,
+                    )
+                    .with_note(format!("{base} is declared here"), &other.span),
+                ),
+                None => self.diagnostics.push(Diagnostic::new(
+                    format!("class {} implements unknown interface {base}", class.name),
+                    &class.span,
+                )),
+            }
+        }
+    }
+
+    /// `interface` and its resolvable ancestors, root first. `None` if
+    /// the chain loops.
+    fn chain(&self, interface: &'a Interface) -> Option<Vec<&'a Interface>> {
+        let mut chain = vec![interface];
+        let mut visited = HashSet::from([interface.name.as_str()]);
+        let mut current = interface;
+        while let Some(Symbol::Interface(base)) = current
+            .bases
+            .first()
+            .and_then(|base| self.symbols.get(base.as_str()))
+        {
+            if !visited.insert(base.name.as_str()) {
+                return None;
+            }
+            chain.push(base);
+            current = base;
+        }
+        chain.reverse();
+        Some(chain)
+    }
+
+    fn check_method_types(&mut self, interface: &Interface, method: &Method) {
+        let mut problems = Vec::new();
+        if let Some(problem) = self.type_problem(&method.ret_ty) {
+            problems.push(format!("return type: {problem}"));
+        }
+        for param in &method.params {
+            if param.ty.trim() == "void" {
+                problems.push(format!(
+                    "parameter {}: `void` is not a value type",
+                    param.name
+                ));
+            } else if let Some(problem) = self.type_problem(&param.ty) {
+                problems.push(format!("parameter {}: {problem}", param.name));
+            }
+        }
+        for problem in problems {
+            self.diagnostics.push(Diagnostic::new(
+                format!("in method {}.{}, {problem}", interface.name, method.name),
+                &method.span,
+            ));
+        }
+    }
+
+    /// Why `ty` is not a valid IDL type, if it is not.
+    fn type_problem(&self, ty: &str) -> Option<String> {
+        let ty = ty.trim();
+        if let Some(inner) = ty.strip_suffix("[]") {
+            return match self.symbols.get(inner.trim()) {
+                Some(Symbol::Interface(_)) => None,
+                _ if is_primitive(inner.trim()) => {
+                    Some(format!("`{ty}`: arrays can only hold interfaces"))
+                }
+                other => Some(self.unresolved(inner.trim(), other)),
+            };
+        }
+        if let Some(inner) = ty.strip_suffix('?') {
+            let inner = inner.trim();
+            return match self.symbols.get(inner) {
+                Some(Symbol::Interface(_)) => None,
+                _ if inner == "float" => None,
+                _ if is_primitive(inner) => Some(format!(
+                    "`{ty}`: only interfaces and `float` can be optional"
+                )),
+                other => Some(self.unresolved(inner, other)),
+            };
+        }
+        match self.symbols.get(ty) {
+            Some(Symbol::Interface(_)) => None,
+            _ if is_primitive(ty) => None,
+            other => Some(self.unresolved(ty, other)),
+        }
+    }
+
+    fn unresolved(&self, name: &str, symbol: Option<&Symbol>) -> String {
+        match symbol {
+            Some(Symbol::Class(class)) => format!(
+                "`{name}` is a class; use one of its interfaces ({})",
+                class.bases.join(", ")
+            ),
+            _ => format!("unknown type `{name}`"),
+        }
+    }
+
+    fn check_scriptable(&mut self, interface: &Interface, origin: &Interface, method: &Method) {
+        let mut problems = Vec::new();
+        if self.type_problem(&method.ret_ty).is_none() && !self.scriptable_ret(&method.ret_ty) {
+            problems.push(format!("return type `{}`", method.ret_ty));
+        }
+        for param in &method.params {
+            if self.type_problem(&param.ty).is_none() && !self.scriptable_arg(&param.ty) {
+                problems.push(format!("parameter `{}: {}`", param.name, param.ty));
+            }
+        }
+        for problem in problems {
+            let mut diagnostic = Diagnostic::new(
+                format!(
+                    "{problem} of {}.{} cannot cross the script bridge, but {} is \
+                     [protosept(scriptable)]",
+                    origin.name, method.name, interface.name
+                ),
+                &method.span,
+            );
+            if origin.name != interface.name {
+                diagnostic = diagnostic.with_note(
+                    format!("{} is marked scriptable here", interface.name),
+                    &interface.span,
+                );
+            }
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Mirrors `ScriptBridgeGen::idl_to_arg_kind`.
+    fn scriptable_arg(&self, ty: &str) -> bool {
+        let ty = ty.trim();
+        matches!(
+            ty,
+            "bool" | "int" | "long" | "longlong" | "byte" | "float" | "&str" | "string"
+        ) || self.is_bridgeable_interface(ty)
+    }
+
+    /// Mirrors `ScriptBridgeGen::idl_to_ret_kind`.
+    fn scriptable_ret(&self, ty: &str) -> bool {
+        let ty = ty.trim();
+        if matches!(
+            ty,
+            "void" | "bool" | "int" | "long" | "longlong" | "byte" | "float" | "float?"
+        ) {
+            return true;
+        }
+        let inner = ty.strip_suffix('?').unwrap_or(ty).trim();
+        self.is_bridgeable_interface(inner)
+    }
+
+    fn is_bridgeable_interface(&self, name: &str) -> bool {
+        name != "IUnknown" && matches!(self.symbols.get(name), Some(Symbol::Interface(_)))
+    }
+}
+
+fn is_primitive(ty: &str) -> bool {
+    matches!(
+        ty,
+        "long"
+            | "longlong"
+            | "int"
+            | "float"
+            | "byte"
+            | "byte*"
+            | "UUID"
+            | "&str"
+            | "string"
+            | "bool"
+            | "void"
+    )
+}
+
+/// Declared in the IDL being validated rather than pulled in by
+/// `process_imports`; imported interfaces are checked with their own IDL.
+fn is_local(interface: &Interface) -> bool {
+    !interface.attrs.contains_key("idl_origin")
+}
@@ -0,0 +1,150 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: crosscom */
+
+#ifndef CROSSCOM_CROSSCOM_H
+#define CROSSCOM_CROSSCOM_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IUnknown IUnknown;
+typedef struct IObjectArray IObjectArray;
+typedef struct IAction IAction;
+typedef struct IIntAction IIntAction;
+typedef struct IFloatAction IFloatAction;
+typedef struct IStrAction IStrAction;
+
+/* Interface IUnknown */
+
+typedef struct IUnknownVtbl {
+    long (CROSSCOM_CALL *query_interface)(IUnknown *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IUnknown *self);
+    long (CROSSCOM_CALL *release)(IUnknown *self);
+} IUnknownVtbl;
+
+struct IUnknown {
+    const IUnknownVtbl *vtbl;
+};
+
+/* 00000000-0000-0000-C000-000000000046 */
+static const CrosscomUuid IID_IUnknown = {{0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46}};
+
+/* Interface IObjectArray */
+
+typedef struct IObjectArrayVtbl {
+    long (CROSSCOM_CALL *query_interface)(IObjectArray *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IObjectArray *self);
+    long (CROSSCOM_CALL *release)(IObjectArray *self);
+    int (CROSSCOM_CALL *len)(IObjectArray *self);
+    IUnknown *(CROSSCOM_CALL *get)(IObjectArray *self, int index);
+} IObjectArrayVtbl;
+
+struct IObjectArray {
+    const IObjectArrayVtbl *vtbl;
+};
+
+/* 928e03ea-0017-4741-80f9-c70a93b16702 */
+static const CrosscomUuid IID_IObjectArray = {{0x92, 0x8e, 0x03, 0xea, 0x00, 0x17, 0x47, 0x41, 0x80, 0xf9, 0xc7, 0x0a, 0x93, 0xb1, 0x67, 0x02}};
+
+/* Class ObjectArray: IObjectArray */
+static const CrosscomUuid CLSID_ObjectArray = {{0xb0, 0xd3, 0x03, 0xba, 0x91, 0x8f, 0x44, 0x82, 0x8f, 0xbf, 0x4a, 0x7e, 0x8a, 0xbd, 0x4d, 0x35}};
+
+/* Interface IAction */
+
+typedef struct IActionVtbl {
+    long (CROSSCOM_CALL *query_interface)(IAction *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IAction *self);
+    long (CROSSCOM_CALL *release)(IAction *self);
+    void (CROSSCOM_CALL *invoke)(IAction *self);
+} IActionVtbl;
+
+struct IAction {
+    const IActionVtbl *vtbl;
+};
+
+/* 5a8b1d3f-7a26-4d4b-9c41-1e9b4f0a0c01 */
+static const CrosscomUuid IID_IAction = {{0x5a, 0x8b, 0x1d, 0x3f, 0x7a, 0x26, 0x4d, 0x4b, 0x9c, 0x41, 0x1e, 0x9b, 0x4f, 0x0a, 0x0c, 0x01}};
+
+/* Class Action: IAction */
+static const CrosscomUuid CLSID_Action = {{0x5a, 0x8b, 0x1d, 0x3f, 0x7a, 0x26, 0x4d, 0x4b, 0x9c, 0x41, 0x1e, 0x9b, 0x4f, 0x0a, 0x0c, 0x02}};
+
+/* Interface IIntAction */
+
+typedef struct IIntActionVtbl {
+    long (CROSSCOM_CALL *query_interface)(IIntAction *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IIntAction *self);
+    long (CROSSCOM_CALL *release)(IIntAction *self);
+    void (CROSSCOM_CALL *invoke)(IIntAction *self, int value);
+} IIntActionVtbl;
+
+struct IIntAction {
+    const IIntActionVtbl *vtbl;
+};
+
+/* 5a8b1d3f-7a26-4d4b-9c41-1e9b4f0a0c03 */
+static const CrosscomUuid IID_IIntAction = {{0x5a, 0x8b, 0x1d, 0x3f, 0x7a, 0x26, 0x4d, 0x4b, 0x9c, 0x41, 0x1e, 0x9b, 0x4f, 0x0a, 0x0c, 0x03}};
+
+/* Class IntAction: IIntAction */
+static const CrosscomUuid CLSID_IntAction = {{0x5a, 0x8b, 0x1d, 0x3f, 0x7a, 0x26, 0x4d, 0x4b, 0x9c, 0x41, 0x1e, 0x9b, 0x4f, 0x0a, 0x0c, 0x04}};
+
+/* Interface IFloatAction */
+
+typedef struct IFloatActionVtbl {
+    long (CROSSCOM_CALL *query_interface)(IFloatAction *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IFloatAction *self);
+    long (CROSSCOM_CALL *release)(IFloatAction *self);
+    void (CROSSCOM_CALL *invoke)(IFloatAction *self, float value);
+} IFloatActionVtbl;
+
+struct IFloatAction {
+    const IFloatActionVtbl *vtbl;
+};
+
+/* 5a8b1d3f-7a26-4d4b-9c41-1e9b4f0a0c05 */
+static const CrosscomUuid IID_IFloatAction = {{0x5a, 0x8b, 0x1d, 0x3f, 0x7a, 0x26, 0x4d, 0x4b, 0x9c, 0x41, 0x1e, 0x9b, 0x4f, 0x0a, 0x0c, 0x05}};
+
+/* Class FloatAction: IFloatAction */
+static const CrosscomUuid CLSID_FloatAction = {{0x5a, 0x8b, 0x1d, 0x3f, 0x7a, 0x26, 0x4d, 0x4b, 0x9c, 0x41, 0x1e, 0x9b, 0x4f, 0x0a, 0x0c, 0x06}};
+
+/* Interface IStrAction */
+
+typedef struct IStrActionVtbl {
+    long (CROSSCOM_CALL *query_interface)(IStrAction *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IStrAction *self);
+    long (CROSSCOM_CALL *release)(IStrAction *self);
+    void (CROSSCOM_CALL *invoke)(IStrAction *self, const char *value);
+} IStrActionVtbl;
+
+struct IStrAction {
+    const IStrActionVtbl *vtbl;
+};
+
+/* 5a8b1d3f-7a26-4d4b-9c41-1e9b4f0a0c07 */
+static const CrosscomUuid IID_IStrAction = {{0x5a, 0x8b, 0x1d, 0x3f, 0x7a, 0x26, 0x4d, 0x4b, 0x9c, 0x41, 0x1e, 0x9b, 0x4f, 0x0a, 0x0c, 0x07}};
+
+/* Class StrAction: IStrAction */
+static const CrosscomUuid CLSID_StrAction = {{0x5a, 0x8b, 0x1d, 0x3f, 0x7a, 0x26, 0x4d, 0x4b, 0x9c, 0x41, 0x1e, 0x9b, 0x4f, 0x0a, 0x0c, 0x08}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_CROSSCOM_H */
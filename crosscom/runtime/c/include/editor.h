@@ -0,0 +1,59 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: radiance_editor::comdef */
+
+#ifndef CROSSCOM_EDITOR_H
+#define CROSSCOM_EDITOR_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IViewContent IViewContent;
+
+/* Interface IViewContent */
+
+typedef struct IViewContentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IViewContent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IViewContent *self);
+    long (CROSSCOM_CALL *release)(IViewContent *self);
+    void (CROSSCOM_CALL *render)(IViewContent *self, float delta_sec);
+} IViewContentVtbl;
+
+struct IViewContent {
+    const IViewContentVtbl *vtbl;
+};
+
+/* 9bd86029-ff4e-4f89-a2e5-83c3ea43409c */
+static const CrosscomUuid IID_IViewContent = {{0x9b, 0xd8, 0x60, 0x29, 0xff, 0x4e, 0x4f, 0x89, 0xa2, 0xe5, 0x83, 0xc3, 0xea, 0x43, 0x40, 0x9c}};
+
+/* Class EditorApplicationLoaderComponent: IApplicationLoaderComponent */
+static const CrosscomUuid CLSID_EditorApplicationLoaderComponent = {{0x9a, 0x9b, 0xe0, 0xb9, 0xf9, 0xbd, 0x41, 0x07, 0x99, 0xba, 0x68, 0x13, 0xd8, 0x58, 0xc4, 0x64}};
+
+/* Class MainPageDirector: IDirector */
+static const CrosscomUuid CLSID_MainPageDirector = {{0xe9, 0x83, 0x40, 0x7c, 0x61, 0x97, 0x42, 0xf8, 0xbf, 0xb5, 0xcd, 0x78, 0xdf, 0xc8, 0x22, 0xb9}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_EDITOR_H */
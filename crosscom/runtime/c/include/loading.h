@@ -0,0 +1,62 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: shared::comdef::loading */
+
+#ifndef CROSSCOM_LOADING_H
+#define CROSSCOM_LOADING_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct ILoadingOverlay ILoadingOverlay;
+
+/* Interface ILoadingOverlay */
+
+typedef struct ILoadingOverlayVtbl {
+    long (CROSSCOM_CALL *query_interface)(ILoadingOverlay *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ILoadingOverlay *self);
+    long (CROSSCOM_CALL *release)(ILoadingOverlay *self);
+    void (CROSSCOM_CALL *request)(ILoadingOverlay *self, const char *scene_name, const char *block_name);
+    int (CROSSCOM_CALL *tick)(ILoadingOverlay *self, float dt);
+    void (CROSSCOM_CALL *notify_load_complete)(ILoadingOverlay *self);
+    int (CROSSCOM_CALL *is_active)(ILoadingOverlay *self);
+    void (CROSSCOM_CALL *cancel)(ILoadingOverlay *self);
+    void (CROSSCOM_CALL *set_progress)(ILoadingOverlay *self, float target);
+    void (CROSSCOM_CALL *render)(ILoadingOverlay *self, IUiHost *ui, float dt);
+} ILoadingOverlayVtbl;
+
+struct ILoadingOverlay {
+    const ILoadingOverlayVtbl *vtbl;
+};
+
+/* 2c5b9d10-7f48-4e6b-9f3c-1d8e0a4b5c6d */
+static const CrosscomUuid IID_ILoadingOverlay = {{0x2c, 0x5b, 0x9d, 0x10, 0x7f, 0x48, 0x4e, 0x6b, 0x9f, 0x3c, 0x1d, 0x8e, 0x0a, 0x4b, 0x5c, 0x6d}};
+
+/* Class LoadingOverlay: ILoadingOverlay */
+static const CrosscomUuid CLSID_LoadingOverlay = {{0x2c, 0x5b, 0x9d, 0x10, 0x7f, 0x48, 0x4e, 0x6b, 0x9f, 0x3c, 0x1d, 0x8e, 0x0a, 0x4b, 0x5c, 0x6e}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_LOADING_H */
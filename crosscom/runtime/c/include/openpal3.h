@@ -0,0 +1,242 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: shared::openpal3::comdef */
+
+#ifndef CROSSCOM_OPENPAL3_H
+#define CROSSCOM_OPENPAL3_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+#include "scripting_services.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IRoleController IRoleController;
+typedef struct ICvdModel ICvdModel;
+typedef struct IScnSceneComponent IScnSceneComponent;
+typedef struct IAdventureDirector IAdventureDirector;
+typedef struct IPal3Service IPal3Service;
+typedef struct IPal3UiAtlas IPal3UiAtlas;
+typedef struct IPal3DialogRenderer IPal3DialogRenderer;
+typedef struct IPal3StatusRenderer IPal3StatusRenderer;
+typedef struct IPal3ScriptFactory IPal3ScriptFactory;
+
+/* Interface IRoleController */
+
+typedef struct IRoleControllerVtbl {
+    long (CROSSCOM_CALL *query_interface)(IRoleController *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IRoleController *self);
+    long (CROSSCOM_CALL *release)(IRoleController *self);
+    void (CROSSCOM_CALL *on_loading)(IRoleController *self);
+    void (CROSSCOM_CALL *on_updating)(IRoleController *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IRoleController *self);
+} IRoleControllerVtbl;
+
+struct IRoleController {
+    const IRoleControllerVtbl *vtbl;
+};
+
+/* e11fe493-654a-4072-b883-a7ee1a35a24a */
+static const CrosscomUuid IID_IRoleController = {{0xe1, 0x1f, 0xe4, 0x93, 0x65, 0x4a, 0x40, 0x72, 0xb8, 0x83, 0xa7, 0xee, 0x1a, 0x35, 0xa2, 0x4a}};
+
+/* Class RoleController: IRoleController */
+static const CrosscomUuid CLSID_RoleController = {{0x85, 0x4b, 0x55, 0xb6, 0x80, 0x54, 0x4c, 0x4d, 0xaa, 0x76, 0x09, 0xe6, 0xd3, 0x28, 0x75, 0x44}};
+
+/* Interface ICvdModel */
+
+typedef struct ICvdModelVtbl {
+    long (CROSSCOM_CALL *query_interface)(ICvdModel *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ICvdModel *self);
+    long (CROSSCOM_CALL *release)(ICvdModel *self);
+    void (CROSSCOM_CALL *on_loading)(ICvdModel *self);
+    void (CROSSCOM_CALL *on_updating)(ICvdModel *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(ICvdModel *self);
+} ICvdModelVtbl;
+
+struct ICvdModel {
+    const ICvdModelVtbl *vtbl;
+};
+
+/* 9c6dc3a5-d858-40c0-960b-b3527ad4516f */
+static const CrosscomUuid IID_ICvdModel = {{0x9c, 0x6d, 0xc3, 0xa5, 0xd8, 0x58, 0x40, 0xc0, 0x96, 0x0b, 0xb3, 0x52, 0x7a, 0xd4, 0x51, 0x6f}};
+
+/* Class CvdModel: IComponent */
+static const CrosscomUuid CLSID_CvdModel = {{0xac, 0x6e, 0x67, 0x1f, 0x79, 0xfc, 0x4b, 0xee, 0xbf, 0x31, 0x65, 0xfd, 0x94, 0x7d, 0x82, 0x44}};
+
+/* Interface IScnSceneComponent */
+
+typedef struct IScnSceneComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IScnSceneComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IScnSceneComponent *self);
+    long (CROSSCOM_CALL *release)(IScnSceneComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IScnSceneComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IScnSceneComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IScnSceneComponent *self);
+} IScnSceneComponentVtbl;
+
+struct IScnSceneComponent {
+    const IScnSceneComponentVtbl *vtbl;
+};
+
+/* 77fe1a3d-05cf-47f9-b80a-08be6d19b0a4 */
+static const CrosscomUuid IID_IScnSceneComponent = {{0x77, 0xfe, 0x1a, 0x3d, 0x05, 0xcf, 0x47, 0xf9, 0xb8, 0x0a, 0x08, 0xbe, 0x6d, 0x19, 0xb0, 0xa4}};
+
+/* Class ScnSceneComponent: IScnSceneComponent */
+static const CrosscomUuid CLSID_ScnSceneComponent = {{0x69, 0xe2, 0xfd, 0x84, 0xc6, 0x79, 0x40, 0xc0, 0x96, 0x08, 0x95, 0x78, 0xd1, 0xaa, 0xec, 0xaf}};
+
+/* Interface IAdventureDirector */
+
+typedef struct IAdventureDirectorVtbl {
+    long (CROSSCOM_CALL *query_interface)(IAdventureDirector *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IAdventureDirector *self);
+    long (CROSSCOM_CALL *release)(IAdventureDirector *self);
+    void (CROSSCOM_CALL *activate)(IAdventureDirector *self);
+    IDirector *(CROSSCOM_CALL *update)(IAdventureDirector *self, float delta_sec);
+    void (CROSSCOM_CALL *deactivate)(IAdventureDirector *self);
+} IAdventureDirectorVtbl;
+
+struct IAdventureDirector {
+    const IAdventureDirectorVtbl *vtbl;
+};
+
+/* 4ca4e74c-c5a9-4356-8aae-19a0af9ac899 */
+static const CrosscomUuid IID_IAdventureDirector = {{0x4c, 0xa4, 0xe7, 0x4c, 0xc5, 0xa9, 0x43, 0x56, 0x8a, 0xae, 0x19, 0xa0, 0xaf, 0x9a, 0xc8, 0x99}};
+
+/* Class AdventureDirector: IAdventureDirector */
+static const CrosscomUuid CLSID_AdventureDirector = {{0x0a, 0xc4, 0x88, 0xa6, 0x7d, 0x94, 0x4b, 0x1d, 0xae, 0x37, 0x8d, 0x93, 0x65, 0x00, 0x5c, 0x7d}};
+
+/* Interface IPal3Service */
+
+typedef struct IPal3ServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal3Service *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal3Service *self);
+    long (CROSSCOM_CALL *release)(IPal3Service *self);
+    IDirector *(CROSSCOM_CALL *create_director)(IPal3Service *self, const char *asset_path, int game_ordinal);
+    IDirector *(CROSSCOM_CALL *create_adventure_director)(IPal3Service *self, const char *asset_path);
+    IDirector *(CROSSCOM_CALL *load_adventure_director)(IPal3Service *self, const char *asset_path, int slot);
+    int (CROSSCOM_CALL *save_slot_exists)(IPal3Service *self, int slot);
+    int (CROSSCOM_CALL *save_slot_count)(IPal3Service *self);
+    IAudioSource *(CROSSCOM_CALL *load_menu_bgm)(IPal3Service *self, const char *asset_path);
+    void (CROSSCOM_CALL *exit_app)(IPal3Service *self);
+    IVideoHandle *(CROSSCOM_CALL *play_intro_movie)(IPal3Service *self, const char *asset_path);
+    ISpriteService *(CROSSCOM_CALL *create_sprite_service)(IPal3Service *self, const char *asset_path);
+    IPal3UiAtlas *(CROSSCOM_CALL *create_ui_atlas)(IPal3Service *self, const char *asset_path);
+} IPal3ServiceVtbl;
+
+struct IPal3Service {
+    const IPal3ServiceVtbl *vtbl;
+};
+
+/* 0c3ea732-4091-46a2-8ef1-79be6acaec77 */
+static const CrosscomUuid IID_IPal3Service = {{0x0c, 0x3e, 0xa7, 0x32, 0x40, 0x91, 0x46, 0xa2, 0x8e, 0xf1, 0x79, 0xbe, 0x6a, 0xca, 0xec, 0x77}};
+
+/* Interface IPal3UiAtlas */
+
+typedef struct IPal3UiAtlasVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal3UiAtlas *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal3UiAtlas *self);
+    long (CROSSCOM_CALL *release)(IPal3UiAtlas *self);
+    int (CROSSCOM_CALL *has_sprite)(IPal3UiAtlas *self, const char *name);
+    ISprite *(CROSSCOM_CALL *sprite)(IPal3UiAtlas *self, const char *name);
+    int (CROSSCOM_CALL *native_width)(IPal3UiAtlas *self);
+    int (CROSSCOM_CALL *native_height)(IPal3UiAtlas *self);
+} IPal3UiAtlasVtbl;
+
+struct IPal3UiAtlas {
+    const IPal3UiAtlasVtbl *vtbl;
+};
+
+/* c3aea9e0-db66-47b9-96a6-38bfb741dd23 */
+static const CrosscomUuid IID_IPal3UiAtlas = {{0xc3, 0xae, 0xa9, 0xe0, 0xdb, 0x66, 0x47, 0xb9, 0x96, 0xa6, 0x38, 0xbf, 0xb7, 0x41, 0xdd, 0x23}};
+
+/* Class Pal3UiAtlas: IPal3UiAtlas */
+static const CrosscomUuid CLSID_Pal3UiAtlas = {{0x6f, 0xf6, 0x49, 0x59, 0xfd, 0x49, 0x4d, 0xb3, 0xb1, 0xe4, 0x25, 0x05, 0xbb, 0xc8, 0x81, 0x14}};
+
+/* Interface IPal3DialogRenderer */
+
+typedef struct IPal3DialogRendererVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal3DialogRenderer *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal3DialogRenderer *self);
+    long (CROSSCOM_CALL *release)(IPal3DialogRenderer *self);
+    void (CROSSCOM_CALL *render_dialog)(IPal3DialogRenderer *self, IUiHost *ui, const char *text, float dt);
+    void (CROSSCOM_CALL *render_curtain)(IPal3DialogRenderer *self, IUiHost *ui);
+    void (CROSSCOM_CALL *set_avatar)(IPal3DialogRenderer *self, const char *role, const char *face, int side);
+    void (CROSSCOM_CALL *clear_avatar)(IPal3DialogRenderer *self);
+    void (CROSSCOM_CALL *set_curtain)(IPal3DialogRenderer *self, float value);
+} IPal3DialogRendererVtbl;
+
+struct IPal3DialogRenderer {
+    const IPal3DialogRendererVtbl *vtbl;
+};
+
+/* 43092151-4d36-4f85-8268-51aaa24386fc */
+static const CrosscomUuid IID_IPal3DialogRenderer = {{0x43, 0x09, 0x21, 0x51, 0x4d, 0x36, 0x4f, 0x85, 0x82, 0x68, 0x51, 0xaa, 0xa2, 0x43, 0x86, 0xfc}};
+
+/* Class Pal3DialogRenderer: IPal3DialogRenderer */
+static const CrosscomUuid CLSID_Pal3DialogRenderer = {{0xc0, 0x37, 0x3b, 0xc2, 0x08, 0x23, 0x40, 0x93, 0x8f, 0x48, 0xd4, 0x2a, 0x07, 0x6e, 0x8a, 0x4a}};
+
+/* Interface IPal3StatusRenderer */
+
+typedef struct IPal3StatusRendererVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal3StatusRenderer *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal3StatusRenderer *self);
+    long (CROSSCOM_CALL *release)(IPal3StatusRenderer *self);
+    void (CROSSCOM_CALL *render_status)(IPal3StatusRenderer *self, IUiHost *ui, float dt);
+    void (CROSSCOM_CALL *set_menu_open)(IPal3StatusRenderer *self, int open);
+    int (CROSSCOM_CALL *is_menu_open)(IPal3StatusRenderer *self);
+} IPal3StatusRendererVtbl;
+
+struct IPal3StatusRenderer {
+    const IPal3StatusRendererVtbl *vtbl;
+};
+
+/* 7d4f0c2e-3b1a-4f5e-9c6d-2a8b1e74f9a0 */
+static const CrosscomUuid IID_IPal3StatusRenderer = {{0x7d, 0x4f, 0x0c, 0x2e, 0x3b, 0x1a, 0x4f, 0x5e, 0x9c, 0x6d, 0x2a, 0x8b, 0x1e, 0x74, 0xf9, 0xa0}};
+
+/* Class Pal3StatusRenderer: IPal3StatusRenderer */
+static const CrosscomUuid CLSID_Pal3StatusRenderer = {{0x8e, 0x5a, 0x1d, 0x3f, 0x4c, 0x2b, 0x40, 0x61, 0xad, 0x7e, 0x3b, 0x9c, 0x2f, 0x85, 0xfa, 0xb1}};
+
+/* Interface IPal3ScriptFactory */
+
+typedef struct IPal3ScriptFactoryVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal3ScriptFactory *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal3ScriptFactory *self);
+    long (CROSSCOM_CALL *release)(IPal3ScriptFactory *self);
+    IDirector *(CROSSCOM_CALL *make_pal3_start_menu)(IPal3ScriptFactory *self, const char *asset_path);
+    IPal3DialogRenderer *(CROSSCOM_CALL *make_pal3_dialog_renderer)(IPal3ScriptFactory *self, ISpriteService *sprites);
+    IPal3StatusRenderer *(CROSSCOM_CALL *make_pal3_status_renderer)(IPal3ScriptFactory *self, ISpriteService *sprites, IPal3UiAtlas *atlas);
+} IPal3ScriptFactoryVtbl;
+
+struct IPal3ScriptFactory {
+    const IPal3ScriptFactoryVtbl *vtbl;
+};
+
+/* 210ea28f-d971-414b-9fba-d3e942cc0516 */
+static const CrosscomUuid IID_IPal3ScriptFactory = {{0x21, 0x0e, 0xa2, 0x8f, 0xd9, 0x71, 0x41, 0x4b, 0x9f, 0xba, 0xd3, 0xe9, 0x42, 0xcc, 0x05, 0x16}};
+
+/* Class Pal3ScriptFactory: IPal3ScriptFactory */
+static const CrosscomUuid CLSID_Pal3ScriptFactory = {{0x4b, 0x76, 0xfb, 0x5a, 0x25, 0xc0, 0x4c, 0x83, 0x9e, 0xd3, 0xff, 0xd5, 0xbf, 0x34, 0x28, 0x56}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_OPENPAL3_H */
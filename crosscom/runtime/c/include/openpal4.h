@@ -0,0 +1,227 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: shared::openpal4::comdef */
+
+#ifndef CROSSCOM_OPENPAL4_H
+#define CROSSCOM_OPENPAL4_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+#include "scripting_services.h"
+#include "pal4_debug.h"
+#include "loading.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IOpenPAL4Director IOpenPAL4Director;
+typedef struct IPal4TransitionDirector IPal4TransitionDirector;
+typedef struct IPal4ActorAnimationController IPal4ActorAnimationController;
+typedef struct IPal4ActorController IPal4ActorController;
+typedef struct IPal4ObjectComponent IPal4ObjectComponent;
+typedef struct IPal4ScriptFactory IPal4ScriptFactory;
+typedef struct IPal4GameContext IPal4GameContext;
+typedef struct IPal4Service IPal4Service;
+
+/* Interface IOpenPAL4Director */
+
+typedef struct IOpenPAL4DirectorVtbl {
+    long (CROSSCOM_CALL *query_interface)(IOpenPAL4Director *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IOpenPAL4Director *self);
+    long (CROSSCOM_CALL *release)(IOpenPAL4Director *self);
+    void (CROSSCOM_CALL *activate)(IOpenPAL4Director *self);
+    IDirector *(CROSSCOM_CALL *update)(IOpenPAL4Director *self, float delta_sec);
+    void (CROSSCOM_CALL *deactivate)(IOpenPAL4Director *self);
+} IOpenPAL4DirectorVtbl;
+
+struct IOpenPAL4Director {
+    const IOpenPAL4DirectorVtbl *vtbl;
+};
+
+/* f3d7f0fd-20ca-450c-bd66-ad019b984a54 */
+static const CrosscomUuid IID_IOpenPAL4Director = {{0xf3, 0xd7, 0xf0, 0xfd, 0x20, 0xca, 0x45, 0x0c, 0xbd, 0x66, 0xad, 0x01, 0x9b, 0x98, 0x4a, 0x54}};
+
+/* Class OpenPAL4Director: IOpenPAL4Director, IUiLayer */
+static const CrosscomUuid CLSID_OpenPAL4Director = {{0x98, 0x18, 0x84, 0xa7, 0x63, 0xe8, 0x4e, 0xe1, 0x96, 0xf1, 0x6e, 0xfd, 0x25, 0x9e, 0xbe, 0x3b}};
+
+/* Interface IPal4TransitionDirector */
+
+typedef struct IPal4TransitionDirectorVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal4TransitionDirector *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal4TransitionDirector *self);
+    long (CROSSCOM_CALL *release)(IPal4TransitionDirector *self);
+    void (CROSSCOM_CALL *activate)(IPal4TransitionDirector *self);
+    IDirector *(CROSSCOM_CALL *update)(IPal4TransitionDirector *self, float delta_sec);
+    void (CROSSCOM_CALL *deactivate)(IPal4TransitionDirector *self);
+} IPal4TransitionDirectorVtbl;
+
+struct IPal4TransitionDirector {
+    const IPal4TransitionDirectorVtbl *vtbl;
+};
+
+/* 7f1a0c20-94d8-4d11-9b62-3a0e6c5b1f44 */
+static const CrosscomUuid IID_IPal4TransitionDirector = {{0x7f, 0x1a, 0x0c, 0x20, 0x94, 0xd8, 0x4d, 0x11, 0x9b, 0x62, 0x3a, 0x0e, 0x6c, 0x5b, 0x1f, 0x44}};
+
+/* Class Pal4TransitionDirector: IPal4TransitionDirector, IUiLayer */
+static const CrosscomUuid CLSID_Pal4TransitionDirector = {{0x2b, 0x5c, 0x0e, 0x10, 0x3d, 0x4f, 0x4a, 0x72, 0x9c, 0x81, 0x8f, 0x2a, 0x6b, 0x4d, 0x7e, 0x91}};
+
+/* Interface IPal4ActorAnimationController */
+
+typedef struct IPal4ActorAnimationControllerVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal4ActorAnimationController *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal4ActorAnimationController *self);
+    long (CROSSCOM_CALL *release)(IPal4ActorAnimationController *self);
+    void (CROSSCOM_CALL *on_loading)(IPal4ActorAnimationController *self);
+    void (CROSSCOM_CALL *on_updating)(IPal4ActorAnimationController *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IPal4ActorAnimationController *self);
+    void (CROSSCOM_CALL *play_default)(IPal4ActorAnimationController *self);
+    void (CROSSCOM_CALL *unhold)(IPal4ActorAnimationController *self);
+    int (CROSSCOM_CALL *animation_completed)(IPal4ActorAnimationController *self);
+    void (CROSSCOM_CALL *play_by_id)(IPal4ActorAnimationController *self, int anim, int config);
+    int (CROSSCOM_CALL *current_id)(IPal4ActorAnimationController *self);
+} IPal4ActorAnimationControllerVtbl;
+
+struct IPal4ActorAnimationController {
+    const IPal4ActorAnimationControllerVtbl *vtbl;
+};
+
+/* f6d70031-86e7-4efa-b1c5-5196063441ea */
+static const CrosscomUuid IID_IPal4ActorAnimationController = {{0xf6, 0xd7, 0x00, 0x31, 0x86, 0xe7, 0x4e, 0xfa, 0xb1, 0xc5, 0x51, 0x96, 0x06, 0x34, 0x41, 0xea}};
+
+/* Class Pal4ActorAnimationController: IPal4ActorAnimationController, IAnimationEventObserver */
+static const CrosscomUuid CLSID_Pal4ActorAnimationController = {{0xe2, 0x62, 0xbc, 0xd6, 0x41, 0xfd, 0x4c, 0xfd, 0xa2, 0xfd, 0x73, 0x76, 0x42, 0x1b, 0x6a, 0xe5}};
+
+/* Interface IPal4ActorController */
+
+typedef struct IPal4ActorControllerVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal4ActorController *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal4ActorController *self);
+    long (CROSSCOM_CALL *release)(IPal4ActorController *self);
+    void (CROSSCOM_CALL *on_loading)(IPal4ActorController *self);
+    void (CROSSCOM_CALL *on_updating)(IPal4ActorController *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IPal4ActorController *self);
+    void (CROSSCOM_CALL *lock_control)(IPal4ActorController *self, int lock);
+} IPal4ActorControllerVtbl;
+
+struct IPal4ActorController {
+    const IPal4ActorControllerVtbl *vtbl;
+};
+
+/* 9ccfa4a1-16f9-483c-95d8-6095fbf24e09 */
+static const CrosscomUuid IID_IPal4ActorController = {{0x9c, 0xcf, 0xa4, 0xa1, 0x16, 0xf9, 0x48, 0x3c, 0x95, 0xd8, 0x60, 0x95, 0xfb, 0xf2, 0x4e, 0x09}};
+
+/* Class Pal4ActorController: IPal4ActorController */
+static const CrosscomUuid CLSID_Pal4ActorController = {{0x06, 0x87, 0x03, 0x5c, 0xb5, 0xf3, 0x47, 0xe1, 0x9e, 0x4a, 0x80, 0xfb, 0xf5, 0x7b, 0x3a, 0x7c}};
+
+/* Interface IPal4ObjectComponent */
+
+typedef struct IPal4ObjectComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal4ObjectComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal4ObjectComponent *self);
+    long (CROSSCOM_CALL *release)(IPal4ObjectComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IPal4ObjectComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IPal4ObjectComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IPal4ObjectComponent *self);
+} IPal4ObjectComponentVtbl;
+
+struct IPal4ObjectComponent {
+    const IPal4ObjectComponentVtbl *vtbl;
+};
+
+/* a0e66ea8-cdaa-4342-b1c8-babec958cae0 */
+static const CrosscomUuid IID_IPal4ObjectComponent = {{0xa0, 0xe6, 0x6e, 0xa8, 0xcd, 0xaa, 0x43, 0x42, 0xb1, 0xc8, 0xba, 0xbe, 0xc9, 0x58, 0xca, 0xe0}};
+
+/* Class Pal4ObjectComponent: IPal4ObjectComponent */
+static const CrosscomUuid CLSID_Pal4ObjectComponent = {{0xd0, 0x68, 0x48, 0x74, 0x40, 0x97, 0x45, 0x3a, 0x9f, 0xba, 0x21, 0x1c, 0x5c, 0x4d, 0x76, 0x61}};
+
+/* Interface IPal4ScriptFactory */
+
+typedef struct IPal4ScriptFactoryVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal4ScriptFactory *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal4ScriptFactory *self);
+    long (CROSSCOM_CALL *release)(IPal4ScriptFactory *self);
+    IPal4ActorController *(CROSSCOM_CALL *make_actor_controller)(IPal4ScriptFactory *self, IPal4GameContext *game_ctx, IInputService *input, IEntity *entity_0, IEntity *entity_1, IEntity *entity_2, IEntity *entity_3, IPal4ActorAnimationController *anim_0, IPal4ActorAnimationController *anim_1, IPal4ActorAnimationController *anim_2, IPal4ActorAnimationController *anim_3, ICameraControl *camera, IRayCaster *ray_caster);
+    IDirector *(CROSSCOM_CALL *make_pal4_start_menu)(IPal4ScriptFactory *self, const char *asset_path);
+    IPal4DebugOverlay *(CROSSCOM_CALL *make_pal4_debug_overlay)(IPal4ScriptFactory *self, IPal4DebugContext *ctx);
+    ILoadingOverlay *(CROSSCOM_CALL *make_loading_overlay)(IPal4ScriptFactory *self);
+} IPal4ScriptFactoryVtbl;
+
+struct IPal4ScriptFactory {
+    const IPal4ScriptFactoryVtbl *vtbl;
+};
+
+/* 7a3e1c20-5d44-4b8e-9f12-0a6b3c8d2e44 */
+static const CrosscomUuid IID_IPal4ScriptFactory = {{0x7a, 0x3e, 0x1c, 0x20, 0x5d, 0x44, 0x4b, 0x8e, 0x9f, 0x12, 0x0a, 0x6b, 0x3c, 0x8d, 0x2e, 0x44}};
+
+/* Class Pal4ScriptFactory: IPal4ScriptFactory */
+static const CrosscomUuid CLSID_Pal4ScriptFactory = {{0xb6, 0xf4, 0xd2, 0xa8, 0x3c, 0x91, 0x4e, 0x07, 0x8a, 0x55, 0x1d, 0x2e, 0x9f, 0x0c, 0x7b, 0x63}};
+
+/* Interface IPal4GameContext */
+
+typedef struct IPal4GameContextVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal4GameContext *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal4GameContext *self);
+    long (CROSSCOM_CALL *release)(IPal4GameContext *self);
+    int (CROSSCOM_CALL *current_leader)(IPal4GameContext *self);
+    void (CROSSCOM_CALL *check_event_triggers)(IPal4GameContext *self, float ox, float oy, float oz, float mx, float my, float mz);
+} IPal4GameContextVtbl;
+
+struct IPal4GameContext {
+    const IPal4GameContextVtbl *vtbl;
+};
+
+/* 0687035c-b5f3-47e1-9e4a-80fbf57b3a7d */
+static const CrosscomUuid IID_IPal4GameContext = {{0x06, 0x87, 0x03, 0x5c, 0xb5, 0xf3, 0x47, 0xe1, 0x9e, 0x4a, 0x80, 0xfb, 0xf5, 0x7b, 0x3a, 0x7d}};
+
+/* Class Pal4GameContext: IPal4GameContext */
+static const CrosscomUuid CLSID_Pal4GameContext = {{0x06, 0x87, 0x03, 0x5c, 0xb5, 0xf3, 0x47, 0xe1, 0x9e, 0x4a, 0x80, 0xfb, 0xf5, 0x7b, 0x3a, 0x7e}};
+
+/* Interface IPal4Service */
+
+typedef struct IPal4ServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal4Service *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal4Service *self);
+    long (CROSSCOM_CALL *release)(IPal4Service *self);
+    IDirector *(CROSSCOM_CALL *create_director)(IPal4Service *self, const char *asset_path);
+    IDirector *(CROSSCOM_CALL *enter_new_game)(IPal4Service *self, const char *asset_path);
+    IDirector *(CROSSCOM_CALL *enter_load_game)(IPal4Service *self, const char *asset_path, int slot);
+    IUiLayoutHandle *(CROSSCOM_CALL *open_layout)(IPal4Service *self, const char *vfs_path);
+    int (CROSSCOM_CALL *save_slot_count)(IPal4Service *self);
+    int (CROSSCOM_CALL *save_slot_exists)(IPal4Service *self, int slot);
+    const char *(CROSSCOM_CALL *save_slot_summary)(IPal4Service *self, int slot);
+    IScene *(CROSSCOM_CALL *load_menu_scene)(IPal4Service *self);
+    IAudioSource *(CROSSCOM_CALL *load_music)(IPal4Service *self, const char *music_name);
+} IPal4ServiceVtbl;
+
+struct IPal4Service {
+    const IPal4ServiceVtbl *vtbl;
+};
+
+/* 60e42ff1-613a-4b0a-93c0-eada0bd0f9dd */
+static const CrosscomUuid IID_IPal4Service = {{0x60, 0xe4, 0x2f, 0xf1, 0x61, 0x3a, 0x4b, 0x0a, 0x93, 0xc0, 0xea, 0xda, 0x0b, 0xd0, 0xf9, 0xdd}};
+
+/* Class Pal4Service: IPal4Service */
+static const CrosscomUuid CLSID_Pal4Service = {{0x95, 0x6d, 0x18, 0xea, 0xcf, 0xfe, 0x47, 0xf6, 0x94, 0x58, 0xdd, 0xb3, 0xb0, 0x87, 0x11, 0x0f}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_OPENPAL4_H */
@@ -0,0 +1,53 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: shared::openpal5::comdef */
+
+#ifndef CROSSCOM_OPENPAL5_H
+#define CROSSCOM_OPENPAL5_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IPal5Service IPal5Service;
+
+/* Interface IPal5Service */
+
+typedef struct IPal5ServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal5Service *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal5Service *self);
+    long (CROSSCOM_CALL *release)(IPal5Service *self);
+    IDirector *(CROSSCOM_CALL *create_director)(IPal5Service *self, const char *asset_path, int game_ordinal);
+} IPal5ServiceVtbl;
+
+struct IPal5Service {
+    const IPal5ServiceVtbl *vtbl;
+};
+
+/* 57fa39fc-df8d-469e-9704-60848580343c */
+static const CrosscomUuid IID_IPal5Service = {{0x57, 0xfa, 0x39, 0xfc, 0xdf, 0x8d, 0x46, 0x9e, 0x97, 0x04, 0x60, 0x84, 0x85, 0x80, 0x34, 0x3c}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_OPENPAL5_H */
@@ -0,0 +1,78 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: shared::openswd5::comdef */
+
+#ifndef CROSSCOM_OPENSWD5_H
+#define CROSSCOM_OPENSWD5_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IOpenSWD5Director IOpenSWD5Director;
+typedef struct ISwd5Service ISwd5Service;
+
+/* Interface IOpenSWD5Director */
+
+typedef struct IOpenSWD5DirectorVtbl {
+    long (CROSSCOM_CALL *query_interface)(IOpenSWD5Director *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IOpenSWD5Director *self);
+    long (CROSSCOM_CALL *release)(IOpenSWD5Director *self);
+    void (CROSSCOM_CALL *activate)(IOpenSWD5Director *self);
+    IDirector *(CROSSCOM_CALL *update)(IOpenSWD5Director *self, float delta_sec);
+    void (CROSSCOM_CALL *deactivate)(IOpenSWD5Director *self);
+} IOpenSWD5DirectorVtbl;
+
+struct IOpenSWD5Director {
+    const IOpenSWD5DirectorVtbl *vtbl;
+};
+
+/* 58a80825-5ee3-436f-81fe-55bffe5d955e */
+static const CrosscomUuid IID_IOpenSWD5Director = {{0x58, 0xa8, 0x08, 0x25, 0x5e, 0xe3, 0x43, 0x6f, 0x81, 0xfe, 0x55, 0xbf, 0xfe, 0x5d, 0x95, 0x5e}};
+
+/* Class OpenSWD5Director: IOpenSWD5Director */
+static const CrosscomUuid CLSID_OpenSWD5Director = {{0xca, 0x7d, 0xe8, 0x30, 0x4a, 0xeb, 0x45, 0x38, 0xba, 0xcb, 0x34, 0xd7, 0xd5, 0x61, 0x43, 0x92}};
+
+/* Interface ISwd5Service */
+
+typedef struct ISwd5ServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(ISwd5Service *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ISwd5Service *self);
+    long (CROSSCOM_CALL *release)(ISwd5Service *self);
+    IDirector *(CROSSCOM_CALL *create_director)(ISwd5Service *self, const char *asset_path, int game_ordinal);
+} ISwd5ServiceVtbl;
+
+struct ISwd5Service {
+    const ISwd5ServiceVtbl *vtbl;
+};
+
+/* 64b879ec-b291-4cf1-ac6f-9e1452d6ca9c */
+static const CrosscomUuid IID_ISwd5Service = {{0x64, 0xb8, 0x79, 0xec, 0xb2, 0x91, 0x4c, 0xf1, 0xac, 0x6f, 0x9e, 0x14, 0x52, 0xd6, 0xca, 0x9c}};
+
+/* Class Swd5Service: ISwd5Service */
+static const CrosscomUuid CLSID_Swd5Service = {{0x4d, 0xa9, 0xd3, 0xe6, 0xe4, 0xd8, 0x4e, 0x54, 0xbe, 0x7d, 0x5c, 0x72, 0x97, 0xe2, 0x42, 0xb3}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_OPENSWD5_H */
@@ -0,0 +1,89 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: shared::openpal4::comdef::pal4_debug */
+
+#ifndef CROSSCOM_PAL4_DEBUG_H
+#define CROSSCOM_PAL4_DEBUG_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IPal4DebugContext IPal4DebugContext;
+typedef struct IPal4DebugOverlay IPal4DebugOverlay;
+
+/* Interface IPal4DebugContext */
+
+typedef struct IPal4DebugContextVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal4DebugContext *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal4DebugContext *self);
+    long (CROSSCOM_CALL *release)(IPal4DebugContext *self);
+    const char *(CROSSCOM_CALL *scene_name)(IPal4DebugContext *self);
+    const char *(CROSSCOM_CALL *block_name)(IPal4DebugContext *self);
+    int (CROSSCOM_CALL *leader_index)(IPal4DebugContext *self);
+    float (CROSSCOM_CALL *leader_pos_x)(IPal4DebugContext *self);
+    float (CROSSCOM_CALL *leader_pos_y)(IPal4DebugContext *self);
+    float (CROSSCOM_CALL *leader_pos_z)(IPal4DebugContext *self);
+    float (CROSSCOM_CALL *delta_time)(IPal4DebugContext *self);
+    float (CROSSCOM_CALL *fps)(IPal4DebugContext *self);
+    int (CROSSCOM_CALL *bsp_visible)(IPal4DebugContext *self);
+    void (CROSSCOM_CALL *set_bsp_visible)(IPal4DebugContext *self, int v);
+    int (CROSSCOM_CALL *nav_mesh_visible)(IPal4DebugContext *self);
+    void (CROSSCOM_CALL *set_nav_mesh_visible)(IPal4DebugContext *self, int v);
+    int (CROSSCOM_CALL *fast_forward)(IPal4DebugContext *self);
+    void (CROSSCOM_CALL *set_fast_forward)(IPal4DebugContext *self, int v);
+} IPal4DebugContextVtbl;
+
+struct IPal4DebugContext {
+    const IPal4DebugContextVtbl *vtbl;
+};
+
+/* 9c1b6f70-1f7a-4d31-9b6a-2e9d4a8e0b01 */
+static const CrosscomUuid IID_IPal4DebugContext = {{0x9c, 0x1b, 0x6f, 0x70, 0x1f, 0x7a, 0x4d, 0x31, 0x9b, 0x6a, 0x2e, 0x9d, 0x4a, 0x8e, 0x0b, 0x01}};
+
+/* Class Pal4DebugContext: IPal4DebugContext */
+static const CrosscomUuid CLSID_Pal4DebugContext = {{0x9c, 0x1b, 0x6f, 0x70, 0x1f, 0x7a, 0x4d, 0x31, 0x9b, 0x6a, 0x2e, 0x9d, 0x4a, 0x8e, 0x0b, 0x02}};
+
+/* Interface IPal4DebugOverlay */
+
+typedef struct IPal4DebugOverlayVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal4DebugOverlay *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal4DebugOverlay *self);
+    long (CROSSCOM_CALL *release)(IPal4DebugOverlay *self);
+    void (CROSSCOM_CALL *render)(IPal4DebugOverlay *self, IUiHost *ui, float dt, IPal4DebugContext *ctx);
+} IPal4DebugOverlayVtbl;
+
+struct IPal4DebugOverlay {
+    const IPal4DebugOverlayVtbl *vtbl;
+};
+
+/* 9c1b6f70-1f7a-4d31-9b6a-2e9d4a8e0b03 */
+static const CrosscomUuid IID_IPal4DebugOverlay = {{0x9c, 0x1b, 0x6f, 0x70, 0x1f, 0x7a, 0x4d, 0x31, 0x9b, 0x6a, 0x2e, 0x9d, 0x4a, 0x8e, 0x0b, 0x03}};
+
+/* Class Pal4DebugOverlay: IPal4DebugOverlay */
+static const CrosscomUuid CLSID_Pal4DebugOverlay = {{0x9c, 0x1b, 0x6f, 0x70, 0x1f, 0x7a, 0x4d, 0x31, 0x9b, 0x6a, 0x2e, 0x9d, 0x4a, 0x8e, 0x0b, 0x04}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_PAL4_DEBUG_H */
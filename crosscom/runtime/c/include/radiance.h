@@ -0,0 +1,720 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: radiance::comdef */
+
+#ifndef CROSSCOM_RADIANCE_H
+#define CROSSCOM_RADIANCE_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IComponent IComponent;
+typedef struct IComponentContainer IComponentContainer;
+typedef struct IApplication IApplication;
+typedef struct IApplicationLoaderComponent IApplicationLoaderComponent;
+typedef struct IScene IScene;
+typedef struct IEntity IEntity;
+typedef struct IStaticMeshComponent IStaticMeshComponent;
+typedef struct IUvAnimationComponent IUvAnimationComponent;
+typedef struct IFrameAnimationComponent IFrameAnimationComponent;
+typedef struct IBillboardComponent IBillboardComponent;
+typedef struct ISkyboxComponent ISkyboxComponent;
+typedef struct IDistanceCullComponent IDistanceCullComponent;
+typedef struct IAudioSourceComponent IAudioSourceComponent;
+typedef struct IAnimatedMeshComponent IAnimatedMeshComponent;
+typedef struct ICollisionMeshComponent ICollisionMeshComponent;
+typedef struct ITriggerVolumeComponent ITriggerVolumeComponent;
+typedef struct ICollisionWorldComponent ICollisionWorldComponent;
+typedef struct IDirector IDirector;
+typedef struct ISceneManager ISceneManager;
+typedef struct IArmatureComponent IArmatureComponent;
+typedef struct ISkinnedMeshComponent ISkinnedMeshComponent;
+typedef struct IHAnimBoneComponent IHAnimBoneComponent;
+typedef struct IAnimationEventObserver IAnimationEventObserver;
+typedef struct IRayCaster IRayCaster;
+typedef struct ICameraControl ICameraControl;
+typedef struct IUiHost IUiHost;
+typedef struct IUiLayer IUiLayer;
+
+/* Interface IComponent */
+
+typedef struct IComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IComponent *self);
+    long (CROSSCOM_CALL *release)(IComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IComponent *self);
+} IComponentVtbl;
+
+struct IComponent {
+    const IComponentVtbl *vtbl;
+};
+
+/* 03748ce3-689d-4325-b1de-59de516b576b */
+static const CrosscomUuid IID_IComponent = {{0x03, 0x74, 0x8c, 0xe3, 0x68, 0x9d, 0x43, 0x25, 0xb1, 0xde, 0x59, 0xde, 0x51, 0x6b, 0x57, 0x6b}};
+
+/* Interface IComponentContainer */
+
+typedef struct IComponentContainerVtbl {
+    long (CROSSCOM_CALL *query_interface)(IComponentContainer *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IComponentContainer *self);
+    long (CROSSCOM_CALL *release)(IComponentContainer *self);
+    void (CROSSCOM_CALL *add_component)(IComponentContainer *self, CrosscomUuid uuid, IComponent *component);
+    IComponent *(CROSSCOM_CALL *get_component)(IComponentContainer *self, CrosscomUuid uuid);
+    IComponent *(CROSSCOM_CALL *remove_component)(IComponentContainer *self, CrosscomUuid uuid);
+} IComponentContainerVtbl;
+
+struct IComponentContainer {
+    const IComponentContainerVtbl *vtbl;
+};
+
+/* b875bf54-8c4c-4926-a2bd-6ad6f7038cfe */
+static const CrosscomUuid IID_IComponentContainer = {{0xb8, 0x75, 0xbf, 0x54, 0x8c, 0x4c, 0x49, 0x26, 0xa2, 0xbd, 0x6a, 0xd6, 0xf7, 0x03, 0x8c, 0xfe}};
+
+/* Interface IApplication */
+
+typedef struct IApplicationVtbl {
+    long (CROSSCOM_CALL *query_interface)(IApplication *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IApplication *self);
+    long (CROSSCOM_CALL *release)(IApplication *self);
+    void (CROSSCOM_CALL *add_component)(IApplication *self, CrosscomUuid uuid, IComponent *component);
+    IComponent *(CROSSCOM_CALL *get_component)(IApplication *self, CrosscomUuid uuid);
+    IComponent *(CROSSCOM_CALL *remove_component)(IApplication *self, CrosscomUuid uuid);
+    void (CROSSCOM_CALL *initialize)(IApplication *self);
+    void (CROSSCOM_CALL *run)(IApplication *self);
+    void (CROSSCOM_CALL *request_exit)(IApplication *self);
+    float (CROSSCOM_CALL *dpi_scale)(IApplication *self);
+} IApplicationVtbl;
+
+struct IApplication {
+    const IApplicationVtbl *vtbl;
+};
+
+/* fd2f7f28-c3ea-442c-a6dc-18e370de001a */
+static const CrosscomUuid IID_IApplication = {{0xfd, 0x2f, 0x7f, 0x28, 0xc3, 0xea, 0x44, 0x2c, 0xa6, 0xdc, 0x18, 0xe3, 0x70, 0xde, 0x00, 0x1a}};
+
+/* Class Application: IApplication */
+static const CrosscomUuid CLSID_Application = {{0xbd, 0x5a, 0xd7, 0xe2, 0x63, 0x5d, 0x4b, 0xf2, 0x94, 0xda, 0xa8, 0x14, 0xd0, 0x01, 0xc8, 0x11}};
+
+/* Interface IApplicationLoaderComponent */
+
+typedef struct IApplicationLoaderComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IApplicationLoaderComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IApplicationLoaderComponent *self);
+    long (CROSSCOM_CALL *release)(IApplicationLoaderComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IApplicationLoaderComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IApplicationLoaderComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IApplicationLoaderComponent *self);
+} IApplicationLoaderComponentVtbl;
+
+struct IApplicationLoaderComponent {
+    const IApplicationLoaderComponentVtbl *vtbl;
+};
+
+/* 3afe8052-b675-4939-aafb-2a4fca8f2cf2 */
+static const CrosscomUuid IID_IApplicationLoaderComponent = {{0x3a, 0xfe, 0x80, 0x52, 0xb6, 0x75, 0x49, 0x39, 0xaa, 0xfb, 0x2a, 0x4f, 0xca, 0x8f, 0x2c, 0xf2}};
+
+/* Interface IScene */
+
+typedef struct ISceneVtbl {
+    long (CROSSCOM_CALL *query_interface)(IScene *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IScene *self);
+    long (CROSSCOM_CALL *release)(IScene *self);
+    void (CROSSCOM_CALL *add_component)(IScene *self, CrosscomUuid uuid, IComponent *component);
+    IComponent *(CROSSCOM_CALL *get_component)(IScene *self, CrosscomUuid uuid);
+    IComponent *(CROSSCOM_CALL *remove_component)(IScene *self, CrosscomUuid uuid);
+    void (CROSSCOM_CALL *load)(IScene *self);
+    int (CROSSCOM_CALL *visible)(IScene *self);
+    void (CROSSCOM_CALL *update)(IScene *self, float delta_sec);
+    void (CROSSCOM_CALL *unload)(IScene *self);
+    void (CROSSCOM_CALL *add_entity)(IScene *self, IEntity *entity);
+} ISceneVtbl;
+
+struct IScene {
+    const ISceneVtbl *vtbl;
+};
+
+/* 27e705f1-d035-4e91-8735-3a006fab870d */
+static const CrosscomUuid IID_IScene = {{0x27, 0xe7, 0x05, 0xf1, 0xd0, 0x35, 0x4e, 0x91, 0x87, 0x35, 0x3a, 0x00, 0x6f, 0xab, 0x87, 0x0d}};
+
+/* Class Scene: IScene */
+static const CrosscomUuid CLSID_Scene = {{0xce, 0x8f, 0xc8, 0x0c, 0xce, 0x9e, 0x4a, 0x44, 0x92, 0xe5, 0x04, 0x3c, 0x06, 0xfe, 0xf7, 0x76}};
+
+/* Interface IEntity */
+
+typedef struct IEntityVtbl {
+    long (CROSSCOM_CALL *query_interface)(IEntity *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IEntity *self);
+    long (CROSSCOM_CALL *release)(IEntity *self);
+    void (CROSSCOM_CALL *add_component)(IEntity *self, CrosscomUuid uuid, IComponent *component);
+    IComponent *(CROSSCOM_CALL *get_component)(IEntity *self, CrosscomUuid uuid);
+    IComponent *(CROSSCOM_CALL *remove_component)(IEntity *self, CrosscomUuid uuid);
+    void (CROSSCOM_CALL *load)(IEntity *self);
+    void (CROSSCOM_CALL *unload)(IEntity *self);
+    void (CROSSCOM_CALL *update)(IEntity *self, float delta_sec);
+    int (CROSSCOM_CALL *visible)(IEntity *self);
+    void (CROSSCOM_CALL *set_visible)(IEntity *self, int visible);
+    int (CROSSCOM_CALL *enabled)(IEntity *self);
+    void (CROSSCOM_CALL *set_enabled)(IEntity *self, int enabled);
+    void (CROSSCOM_CALL *attach)(IEntity *self, IEntity *child);
+    float (CROSSCOM_CALL *position_x)(IEntity *self);
+    float (CROSSCOM_CALL *position_y)(IEntity *self);
+    float (CROSSCOM_CALL *position_z)(IEntity *self);
+    void (CROSSCOM_CALL *set_position)(IEntity *self, float x, float y, float z);
+    void (CROSSCOM_CALL *look_at)(IEntity *self, float x, float y, float z);
+    void (CROSSCOM_CALL *set_position_and_look_at)(IEntity *self, float px, float py, float pz, float lx, float ly, float lz);
+} IEntityVtbl;
+
+struct IEntity {
+    const IEntityVtbl *vtbl;
+};
+
+/* 95099190-580e-439f-be36-8d1345cf4dec */
+static const CrosscomUuid IID_IEntity = {{0x95, 0x09, 0x91, 0x90, 0x58, 0x0e, 0x43, 0x9f, 0xbe, 0x36, 0x8d, 0x13, 0x45, 0xcf, 0x4d, 0xec}};
+
+/* Class Entity: IEntity */
+static const CrosscomUuid CLSID_Entity = {{0x66, 0xd5, 0x1e, 0x1e, 0x3f, 0x46, 0x4f, 0xe2, 0xa0, 0xdc, 0x61, 0x83, 0xed, 0xf7, 0xc0, 0x63}};
+
+/* Interface IStaticMeshComponent */
+
+typedef struct IStaticMeshComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IStaticMeshComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IStaticMeshComponent *self);
+    long (CROSSCOM_CALL *release)(IStaticMeshComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IStaticMeshComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IStaticMeshComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IStaticMeshComponent *self);
+} IStaticMeshComponentVtbl;
+
+struct IStaticMeshComponent {
+    const IStaticMeshComponentVtbl *vtbl;
+};
+
+/* 8dd91852-476b-401b-8668-ba9cc331b7a1 */
+static const CrosscomUuid IID_IStaticMeshComponent = {{0x8d, 0xd9, 0x18, 0x52, 0x47, 0x6b, 0x40, 0x1b, 0x86, 0x68, 0xba, 0x9c, 0xc3, 0x31, 0xb7, 0xa1}};
+
+/* Class StaticMeshComponent: IStaticMeshComponent */
+static const CrosscomUuid CLSID_StaticMeshComponent = {{0xaa, 0x9c, 0xfb, 0xdc, 0x59, 0xa2, 0x4e, 0x9e, 0x92, 0x80, 0xf7, 0x7d, 0x52, 0xe7, 0x94, 0x94}};
+
+/* Interface IUvAnimationComponent */
+
+typedef struct IUvAnimationComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IUvAnimationComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IUvAnimationComponent *self);
+    long (CROSSCOM_CALL *release)(IUvAnimationComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IUvAnimationComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IUvAnimationComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IUvAnimationComponent *self);
+} IUvAnimationComponentVtbl;
+
+struct IUvAnimationComponent {
+    const IUvAnimationComponentVtbl *vtbl;
+};
+
+/* 4cf87859-1f99-48d1-8476-6f24d5ec3164 */
+static const CrosscomUuid IID_IUvAnimationComponent = {{0x4c, 0xf8, 0x78, 0x59, 0x1f, 0x99, 0x48, 0xd1, 0x84, 0x76, 0x6f, 0x24, 0xd5, 0xec, 0x31, 0x64}};
+
+/* Class UvAnimationComponent: IUvAnimationComponent */
+static const CrosscomUuid CLSID_UvAnimationComponent = {{0x00, 0x8a, 0xe9, 0x5a, 0x9b, 0x01, 0x43, 0xd3, 0x98, 0xfb, 0x63, 0xd5, 0xb2, 0x3b, 0xe6, 0xb5}};
+
+/* Interface IFrameAnimationComponent */
+
+typedef struct IFrameAnimationComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IFrameAnimationComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IFrameAnimationComponent *self);
+    long (CROSSCOM_CALL *release)(IFrameAnimationComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IFrameAnimationComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IFrameAnimationComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IFrameAnimationComponent *self);
+} IFrameAnimationComponentVtbl;
+
+struct IFrameAnimationComponent {
+    const IFrameAnimationComponentVtbl *vtbl;
+};
+
+/* 7b3e2c10-9d44-4a1e-bf21-1c2d3e4f5a60 */
+static const CrosscomUuid IID_IFrameAnimationComponent = {{0x7b, 0x3e, 0x2c, 0x10, 0x9d, 0x44, 0x4a, 0x1e, 0xbf, 0x21, 0x1c, 0x2d, 0x3e, 0x4f, 0x5a, 0x60}};
+
+/* Class FrameAnimationComponent: IFrameAnimationComponent */
+static const CrosscomUuid CLSID_FrameAnimationComponent = {{0x7b, 0x3e, 0x2c, 0x11, 0x9d, 0x44, 0x4a, 0x1e, 0xbf, 0x21, 0x1c, 0x2d, 0x3e, 0x4f, 0x5a, 0x61}};
+
+/* Interface IBillboardComponent */
+
+typedef struct IBillboardComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IBillboardComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IBillboardComponent *self);
+    long (CROSSCOM_CALL *release)(IBillboardComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IBillboardComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IBillboardComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IBillboardComponent *self);
+} IBillboardComponentVtbl;
+
+struct IBillboardComponent {
+    const IBillboardComponentVtbl *vtbl;
+};
+
+/* 9daba808-6c33-4c93-b16a-c801b6fac55a */
+static const CrosscomUuid IID_IBillboardComponent = {{0x9d, 0xab, 0xa8, 0x08, 0x6c, 0x33, 0x4c, 0x93, 0xb1, 0x6a, 0xc8, 0x01, 0xb6, 0xfa, 0xc5, 0x5a}};
+
+/* Class BillboardComponent: IBillboardComponent */
+static const CrosscomUuid CLSID_BillboardComponent = {{0xe7, 0x18, 0xa5, 0xe7, 0x5d, 0x56, 0x48, 0xaf, 0x98, 0x60, 0x90, 0xdb, 0x57, 0xdf, 0xe6, 0x4c}};
+
+/* Interface ISkyboxComponent */
+
+typedef struct ISkyboxComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(ISkyboxComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ISkyboxComponent *self);
+    long (CROSSCOM_CALL *release)(ISkyboxComponent *self);
+    void (CROSSCOM_CALL *on_loading)(ISkyboxComponent *self);
+    void (CROSSCOM_CALL *on_updating)(ISkyboxComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(ISkyboxComponent *self);
+} ISkyboxComponentVtbl;
+
+struct ISkyboxComponent {
+    const ISkyboxComponentVtbl *vtbl;
+};
+
+/* 2455b52c-ca9f-438f-b105-8539ba7d604f */
+static const CrosscomUuid IID_ISkyboxComponent = {{0x24, 0x55, 0xb5, 0x2c, 0xca, 0x9f, 0x43, 0x8f, 0xb1, 0x05, 0x85, 0x39, 0xba, 0x7d, 0x60, 0x4f}};
+
+/* Class SkyboxComponent: ISkyboxComponent */
+static const CrosscomUuid CLSID_SkyboxComponent = {{0x74, 0x98, 0x1b, 0x78, 0x3b, 0xf1, 0x42, 0x1e, 0xa7, 0xaf, 0xed, 0xf2, 0x78, 0x56, 0x40, 0xc0}};
+
+/* Interface IDistanceCullComponent */
+
+typedef struct IDistanceCullComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IDistanceCullComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IDistanceCullComponent *self);
+    long (CROSSCOM_CALL *release)(IDistanceCullComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IDistanceCullComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IDistanceCullComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IDistanceCullComponent *self);
+} IDistanceCullComponentVtbl;
+
+struct IDistanceCullComponent {
+    const IDistanceCullComponentVtbl *vtbl;
+};
+
+/* 0f9a2d41-6c2e-4f1b-9c3a-2d7b8e4a1f60 */
+static const CrosscomUuid IID_IDistanceCullComponent = {{0x0f, 0x9a, 0x2d, 0x41, 0x6c, 0x2e, 0x4f, 0x1b, 0x9c, 0x3a, 0x2d, 0x7b, 0x8e, 0x4a, 0x1f, 0x60}};
+
+/* Class DistanceCullComponent: IDistanceCullComponent */
+static const CrosscomUuid CLSID_DistanceCullComponent = {{0x2b, 0x5e, 0x7c, 0x90, 0x3a, 0x8d, 0x41, 0xe6, 0xbf, 0x52, 0x7c, 0x1d, 0x9e, 0x0a, 0x4b, 0x38}};
+
+/* Interface IAudioSourceComponent */
+
+typedef struct IAudioSourceComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IAudioSourceComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IAudioSourceComponent *self);
+    long (CROSSCOM_CALL *release)(IAudioSourceComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IAudioSourceComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IAudioSourceComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IAudioSourceComponent *self);
+    void (CROSSCOM_CALL *play)(IAudioSourceComponent *self);
+    void (CROSSCOM_CALL *stop)(IAudioSourceComponent *self);
+    void (CROSSCOM_CALL *set_gain)(IAudioSourceComponent *self, float gain);
+} IAudioSourceComponentVtbl;
+
+struct IAudioSourceComponent {
+    const IAudioSourceComponentVtbl *vtbl;
+};
+
+/* 327a838b-b67e-4cf2-8c7b-45daa11964a4 */
+static const CrosscomUuid IID_IAudioSourceComponent = {{0x32, 0x7a, 0x83, 0x8b, 0xb6, 0x7e, 0x4c, 0xf2, 0x8c, 0x7b, 0x45, 0xda, 0xa1, 0x19, 0x64, 0xa4}};
+
+/* Class AudioSourceComponent: IAudioSourceComponent */
+static const CrosscomUuid CLSID_AudioSourceComponent = {{0xac, 0x55, 0xc3, 0x2a, 0x7c, 0xf0, 0x44, 0xca, 0x8c, 0xe6, 0x7b, 0x40, 0x91, 0x1f, 0x31, 0x99}};
+
+/* Interface IAnimatedMeshComponent */
+
+typedef struct IAnimatedMeshComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IAnimatedMeshComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IAnimatedMeshComponent *self);
+    long (CROSSCOM_CALL *release)(IAnimatedMeshComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IAnimatedMeshComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IAnimatedMeshComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IAnimatedMeshComponent *self);
+    void (CROSSCOM_CALL *play)(IAnimatedMeshComponent *self, int replay);
+} IAnimatedMeshComponentVtbl;
+
+struct IAnimatedMeshComponent {
+    const IAnimatedMeshComponentVtbl *vtbl;
+};
+
+/* 5c56adbc-bc22-4275-b99a-09973a3ffff0 */
+static const CrosscomUuid IID_IAnimatedMeshComponent = {{0x5c, 0x56, 0xad, 0xbc, 0xbc, 0x22, 0x42, 0x75, 0xb9, 0x9a, 0x09, 0x97, 0x3a, 0x3f, 0xff, 0xf0}};
+
+/* Class AnimatedMeshComponent: IAnimatedMeshComponent */
+static const CrosscomUuid CLSID_AnimatedMeshComponent = {{0xc1, 0x50, 0x28, 0x19, 0x57, 0xd7, 0x45, 0xd2, 0xa1, 0x60, 0x13, 0xd8, 0x03, 0xe4, 0xfc, 0x48}};
+
+/* Interface ICollisionMeshComponent */
+
+typedef struct ICollisionMeshComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(ICollisionMeshComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ICollisionMeshComponent *self);
+    long (CROSSCOM_CALL *release)(ICollisionMeshComponent *self);
+    void (CROSSCOM_CALL *on_loading)(ICollisionMeshComponent *self);
+    void (CROSSCOM_CALL *on_updating)(ICollisionMeshComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(ICollisionMeshComponent *self);
+    float (CROSSCOM_CALL *cast_ray)(ICollisionMeshComponent *self, float ox, float oy, float oz, float dx, float dy, float dz);
+    float (CROSSCOM_CALL *cast_aa_ny)(ICollisionMeshComponent *self, float ox, float oy, float oz);
+} ICollisionMeshComponentVtbl;
+
+struct ICollisionMeshComponent {
+    const ICollisionMeshComponentVtbl *vtbl;
+};
+
+/* 2422a84b-0f94-43b6-836b-ab6ab85b0414 */
+static const CrosscomUuid IID_ICollisionMeshComponent = {{0x24, 0x22, 0xa8, 0x4b, 0x0f, 0x94, 0x43, 0xb6, 0x83, 0x6b, 0xab, 0x6a, 0xb8, 0x5b, 0x04, 0x14}};
+
+/* Class CollisionMeshComponent: ICollisionMeshComponent */
+static const CrosscomUuid CLSID_CollisionMeshComponent = {{0x14, 0x14, 0x69, 0x15, 0x7b, 0x33, 0x4b, 0xa3, 0xa4, 0x1a, 0x91, 0x19, 0x80, 0xf4, 0x8e, 0x7a}};
+
+/* Interface ITriggerVolumeComponent */
+
+typedef struct ITriggerVolumeComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(ITriggerVolumeComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ITriggerVolumeComponent *self);
+    long (CROSSCOM_CALL *release)(ITriggerVolumeComponent *self);
+    void (CROSSCOM_CALL *on_loading)(ITriggerVolumeComponent *self);
+    void (CROSSCOM_CALL *on_updating)(ITriggerVolumeComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(ITriggerVolumeComponent *self);
+    int (CROSSCOM_CALL *triggered)(ITriggerVolumeComponent *self);
+} ITriggerVolumeComponentVtbl;
+
+struct ITriggerVolumeComponent {
+    const ITriggerVolumeComponentVtbl *vtbl;
+};
+
+/* 4b1383a7-6c0f-4f51-b4f8-84641c35aaf0 */
+static const CrosscomUuid IID_ITriggerVolumeComponent = {{0x4b, 0x13, 0x83, 0xa7, 0x6c, 0x0f, 0x4f, 0x51, 0xb4, 0xf8, 0x84, 0x64, 0x1c, 0x35, 0xaa, 0xf0}};
+
+/* Class TriggerVolumeComponent: ITriggerVolumeComponent */
+static const CrosscomUuid CLSID_TriggerVolumeComponent = {{0xd3, 0x50, 0x87, 0xf4, 0xa7, 0x78, 0x4c, 0xb0, 0x97, 0x28, 0xd1, 0x9b, 0x1d, 0xbf, 0xf8, 0x93}};
+
+/* Interface ICollisionWorldComponent */
+
+typedef struct ICollisionWorldComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(ICollisionWorldComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ICollisionWorldComponent *self);
+    long (CROSSCOM_CALL *release)(ICollisionWorldComponent *self);
+    void (CROSSCOM_CALL *on_loading)(ICollisionWorldComponent *self);
+    void (CROSSCOM_CALL *on_updating)(ICollisionWorldComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(ICollisionWorldComponent *self);
+} ICollisionWorldComponentVtbl;
+
+struct ICollisionWorldComponent {
+    const ICollisionWorldComponentVtbl *vtbl;
+};
+
+/* f8614555-9670-4996-86e2-a4a3e1eafe13 */
+static const CrosscomUuid IID_ICollisionWorldComponent = {{0xf8, 0x61, 0x45, 0x55, 0x96, 0x70, 0x49, 0x96, 0x86, 0xe2, 0xa4, 0xa3, 0xe1, 0xea, 0xfe, 0x13}};
+
+/* Class CollisionWorldComponent: ICollisionWorldComponent */
+static const CrosscomUuid CLSID_CollisionWorldComponent = {{0x7f, 0x54, 0xd8, 0x1f, 0x44, 0xee, 0x43, 0x93, 0xb6, 0x27, 0xd7, 0x65, 0x2f, 0x42, 0xe3, 0xd4}};
+
+/* Class WorldRayCaster: IRayCaster */
+static const CrosscomUuid CLSID_WorldRayCaster = {{0x32, 0x14, 0x5f, 0x89, 0x03, 0x7d, 0x41, 0x01, 0x85, 0x7c, 0x1d, 0x9a, 0x22, 0xdb, 0x05, 0x52}};
+
+/* Interface IDirector */
+
+typedef struct IDirectorVtbl {
+    long (CROSSCOM_CALL *query_interface)(IDirector *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IDirector *self);
+    long (CROSSCOM_CALL *release)(IDirector *self);
+    void (CROSSCOM_CALL *activate)(IDirector *self);
+    IDirector *(CROSSCOM_CALL *update)(IDirector *self, float delta_sec);
+    void (CROSSCOM_CALL *deactivate)(IDirector *self);
+} IDirectorVtbl;
+
+struct IDirector {
+    const IDirectorVtbl *vtbl;
+};
+
+/* 6dedae32-8339-482e-9f66-c30d557cacb4 */
+static const CrosscomUuid IID_IDirector = {{0x6d, 0xed, 0xae, 0x32, 0x83, 0x39, 0x48, 0x2e, 0x9f, 0x66, 0xc3, 0x0d, 0x55, 0x7c, 0xac, 0xb4}};
+
+/* Interface ISceneManager */
+
+typedef struct ISceneManagerVtbl {
+    long (CROSSCOM_CALL *query_interface)(ISceneManager *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ISceneManager *self);
+    long (CROSSCOM_CALL *release)(ISceneManager *self);
+    void (CROSSCOM_CALL *update)(ISceneManager *self, float delta_sec);
+    IScene *(CROSSCOM_CALL *scene)(ISceneManager *self);
+    IDirector *(CROSSCOM_CALL *director)(ISceneManager *self);
+    void (CROSSCOM_CALL *set_director)(ISceneManager *self, IDirector *director);
+    void (CROSSCOM_CALL *push_scene)(ISceneManager *self, IScene *scene);
+    IScene *(CROSSCOM_CALL *pop_scene)(ISceneManager *self);
+    void (CROSSCOM_CALL *unload_all_scenes)(ISceneManager *self);
+    void (CROSSCOM_CALL *unset_director)(ISceneManager *self);
+    ICameraControl *(CROSSCOM_CALL *camera)(ISceneManager *self);
+} ISceneManagerVtbl;
+
+struct ISceneManager {
+    const ISceneManagerVtbl *vtbl;
+};
+
+/* a12c44d5-f5bc-4268-bd00-ab3b6270a829 */
+static const CrosscomUuid IID_ISceneManager = {{0xa1, 0x2c, 0x44, 0xd5, 0xf5, 0xbc, 0x42, 0x68, 0xbd, 0x00, 0xab, 0x3b, 0x62, 0x70, 0xa8, 0x29}};
+
+/* Class SceneManager: ISceneManager */
+static const CrosscomUuid CLSID_SceneManager = {{0xc5, 0x98, 0xee, 0xb6, 0x4e, 0xf5, 0x49, 0x8c, 0x95, 0xb7, 0xd0, 0x64, 0x86, 0xb6, 0x9f, 0x32}};
+
+/* Interface IArmatureComponent */
+
+typedef struct IArmatureComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IArmatureComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IArmatureComponent *self);
+    long (CROSSCOM_CALL *release)(IArmatureComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IArmatureComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IArmatureComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IArmatureComponent *self);
+    void (CROSSCOM_CALL *clear_animation)(IArmatureComponent *self);
+    void (CROSSCOM_CALL *set_looping)(IArmatureComponent *self, int looping);
+    void (CROSSCOM_CALL *play)(IArmatureComponent *self);
+    void (CROSSCOM_CALL *pause)(IArmatureComponent *self);
+    void (CROSSCOM_CALL *stop)(IArmatureComponent *self);
+    void (CROSSCOM_CALL *add_animation_event_observer)(IArmatureComponent *self, IAnimationEventObserver *observer);
+} IArmatureComponentVtbl;
+
+struct IArmatureComponent {
+    const IArmatureComponentVtbl *vtbl;
+};
+
+/* cad476ee-990b-4ffe-af1b-02eac152e66e */
+static const CrosscomUuid IID_IArmatureComponent = {{0xca, 0xd4, 0x76, 0xee, 0x99, 0x0b, 0x4f, 0xfe, 0xaf, 0x1b, 0x02, 0xea, 0xc1, 0x52, 0xe6, 0x6e}};
+
+/* Class ArmatureComponent: IArmatureComponent */
+static const CrosscomUuid CLSID_ArmatureComponent = {{0x6c, 0xe4, 0x22, 0xd7, 0x70, 0xd9, 0x49, 0x86, 0x80, 0xee, 0xe6, 0x7a, 0x53, 0x91, 0x9d, 0xb1}};
+
+/* Interface ISkinnedMeshComponent */
+
+typedef struct ISkinnedMeshComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(ISkinnedMeshComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ISkinnedMeshComponent *self);
+    long (CROSSCOM_CALL *release)(ISkinnedMeshComponent *self);
+    void (CROSSCOM_CALL *on_loading)(ISkinnedMeshComponent *self);
+    void (CROSSCOM_CALL *on_updating)(ISkinnedMeshComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(ISkinnedMeshComponent *self);
+} ISkinnedMeshComponentVtbl;
+
+struct ISkinnedMeshComponent {
+    const ISkinnedMeshComponentVtbl *vtbl;
+};
+
+/* 19ff0435-8a22-486c-b16a-69c2e1ffd0ae */
+static const CrosscomUuid IID_ISkinnedMeshComponent = {{0x19, 0xff, 0x04, 0x35, 0x8a, 0x22, 0x48, 0x6c, 0xb1, 0x6a, 0x69, 0xc2, 0xe1, 0xff, 0xd0, 0xae}};
+
+/* Class SkinnedMeshComponent: ISkinnedMeshComponent */
+static const CrosscomUuid CLSID_SkinnedMeshComponent = {{0x9f, 0x53, 0x64, 0x33, 0x8e, 0xb3, 0x41, 0xda, 0xb4, 0xf2, 0x2a, 0x7e, 0x81, 0xc6, 0xa0, 0xcf}};
+
+/* Interface IHAnimBoneComponent */
+
+typedef struct IHAnimBoneComponentVtbl {
+    long (CROSSCOM_CALL *query_interface)(IHAnimBoneComponent *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IHAnimBoneComponent *self);
+    long (CROSSCOM_CALL *release)(IHAnimBoneComponent *self);
+    void (CROSSCOM_CALL *on_loading)(IHAnimBoneComponent *self);
+    void (CROSSCOM_CALL *on_updating)(IHAnimBoneComponent *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IHAnimBoneComponent *self);
+    void (CROSSCOM_CALL *reset_timestamp)(IHAnimBoneComponent *self);
+} IHAnimBoneComponentVtbl;
+
+struct IHAnimBoneComponent {
+    const IHAnimBoneComponentVtbl *vtbl;
+};
+
+/* 1b4b89da-94cb-4dd8-a1e1-493763f14ee3 */
+static const CrosscomUuid IID_IHAnimBoneComponent = {{0x1b, 0x4b, 0x89, 0xda, 0x94, 0xcb, 0x4d, 0xd8, 0xa1, 0xe1, 0x49, 0x37, 0x63, 0xf1, 0x4e, 0xe3}};
+
+/* Class HAnimBoneComponent: IHAnimBoneComponent */
+static const CrosscomUuid CLSID_HAnimBoneComponent = {{0x0f, 0xc7, 0x24, 0xde, 0xae, 0x8c, 0x42, 0xf1, 0x95, 0x43, 0x51, 0x1c, 0x11, 0x56, 0x09, 0x03}};
+
+/* Interface IAnimationEventObserver */
+
+typedef struct IAnimationEventObserverVtbl {
+    long (CROSSCOM_CALL *query_interface)(IAnimationEventObserver *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IAnimationEventObserver *self);
+    long (CROSSCOM_CALL *release)(IAnimationEventObserver *self);
+    void (CROSSCOM_CALL *on_animation_event)(IAnimationEventObserver *self, const char *event_name);
+} IAnimationEventObserverVtbl;
+
+struct IAnimationEventObserver {
+    const IAnimationEventObserverVtbl *vtbl;
+};
+
+/* 55c4060c-91ab-4373-9139-84293c1fc66f */
+static const CrosscomUuid IID_IAnimationEventObserver = {{0x55, 0xc4, 0x06, 0x0c, 0x91, 0xab, 0x43, 0x73, 0x91, 0x39, 0x84, 0x29, 0x3c, 0x1f, 0xc6, 0x6f}};
+
+/* Interface IRayCaster */
+
+typedef struct IRayCasterVtbl {
+    long (CROSSCOM_CALL *query_interface)(IRayCaster *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IRayCaster *self);
+    long (CROSSCOM_CALL *release)(IRayCaster *self);
+    float (CROSSCOM_CALL *cast_aa_ny)(IRayCaster *self, float ox, float oy, float oz);
+} IRayCasterVtbl;
+
+struct IRayCaster {
+    const IRayCasterVtbl *vtbl;
+};
+
+/* 77d1815c-7042-4e4c-b849-440c75363dec */
+static const CrosscomUuid IID_IRayCaster = {{0x77, 0xd1, 0x81, 0x5c, 0x70, 0x42, 0x4e, 0x4c, 0xb8, 0x49, 0x44, 0x0c, 0x75, 0x36, 0x3d, 0xec}};
+
+/* Class ScriptRayCaster: IRayCaster */
+static const CrosscomUuid CLSID_ScriptRayCaster = {{0x50, 0x91, 0x49, 0xf3, 0x51, 0xfc, 0x4f, 0xaa, 0xac, 0x3a, 0xf8, 0x4c, 0xa2, 0x72, 0xc5, 0x06}};
+
+/* Interface ICameraControl */
+
+typedef struct ICameraControlVtbl {
+    long (CROSSCOM_CALL *query_interface)(ICameraControl *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ICameraControl *self);
+    long (CROSSCOM_CALL *release)(ICameraControl *self);
+    void (CROSSCOM_CALL *set_position)(ICameraControl *self, float x, float y, float z);
+    void (CROSSCOM_CALL *look_at)(ICameraControl *self, float x, float y, float z);
+    float (CROSSCOM_CALL *forward_x)(ICameraControl *self);
+    float (CROSSCOM_CALL *forward_y)(ICameraControl *self);
+    float (CROSSCOM_CALL *forward_z)(ICameraControl *self);
+    float (CROSSCOM_CALL *right_x)(ICameraControl *self);
+    float (CROSSCOM_CALL *right_y)(ICameraControl *self);
+    float (CROSSCOM_CALL *right_z)(ICameraControl *self);
+    void (CROSSCOM_CALL *translate_local)(ICameraControl *self, float dx, float dy, float dz);
+    void (CROSSCOM_CALL *translate)(ICameraControl *self, float dx, float dy, float dz);
+    void (CROSSCOM_CALL *rotate_axis_angle_local)(ICameraControl *self, float ax, float ay, float az, float radian);
+    void (CROSSCOM_CALL *set_fov)(ICameraControl *self, float radian);
+} ICameraControlVtbl;
+
+struct ICameraControl {
+    const ICameraControlVtbl *vtbl;
+};
+
+/* 55f53f18-79ee-4ded-8c0d-d0b25387a69b */
+static const CrosscomUuid IID_ICameraControl = {{0x55, 0xf5, 0x3f, 0x18, 0x79, 0xee, 0x4d, 0xed, 0x8c, 0x0d, 0xd0, 0xb2, 0x53, 0x87, 0xa6, 0x9b}};
+
+/* Class SceneCameraControl: ICameraControl */
+static const CrosscomUuid CLSID_SceneCameraControl = {{0xd9, 0xb4, 0xc3, 0xf7, 0xbd, 0x44, 0x4c, 0xde, 0x8f, 0xa8, 0x8e, 0x1a, 0xde, 0xc7, 0x86, 0xb3}};
+
+/* Interface IUiHost */
+
+typedef struct IUiHostVtbl {
+    long (CROSSCOM_CALL *query_interface)(IUiHost *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IUiHost *self);
+    long (CROSSCOM_CALL *release)(IUiHost *self);
+    void (CROSSCOM_CALL *window)(IUiHost *self, const char *title, float w, float h, int flags, IAction *body);
+    void (CROSSCOM_CALL *window_centered)(IUiHost *self, const char *title, float w, float h, IAction *body);
+    void (CROSSCOM_CALL *window_fullscreen)(IUiHost *self, const char *title, int flags, IAction *body);
+    void (CROSSCOM_CALL *child_window)(IUiHost *self, const char *id, float w, float h, IAction *body);
+    void (CROSSCOM_CALL *table)(IUiHost *self, const char *id, int cols, IAction *body);
+    void (CROSSCOM_CALL *tab_bar)(IUiHost *self, const char *id, IAction *body);
+    void (CROSSCOM_CALL *tree_node)(IUiHost *self, const char *label, IAction *body);
+    void (CROSSCOM_CALL *group)(IUiHost *self, IAction *body);
+    void (CROSSCOM_CALL *style_alpha)(IUiHost *self, float alpha, IAction *body);
+    void (CROSSCOM_CALL *style_color)(IUiHost *self, int slot, float r, float g, float b, float a, IAction *body);
+    void (CROSSCOM_CALL *with_font)(IUiHost *self, int font_idx, IAction *body);
+    int (CROSSCOM_CALL *tab_item)(IUiHost *self, const char *label, int closable, IAction *body);
+    void (CROSSCOM_CALL *same_line)(IUiHost *self);
+    void (CROSSCOM_CALL *dummy)(IUiHost *self, float w, float h);
+    void (CROSSCOM_CALL *spacer)(IUiHost *self, float w, float h);
+    void (CROSSCOM_CALL *set_cursor_pos)(IUiHost *self, float x, float y);
+    float (CROSSCOM_CALL *cursor_pos_x)(IUiHost *self);
+    float (CROSSCOM_CALL *cursor_pos_y)(IUiHost *self);
+    int (CROSSCOM_CALL *display_size_x)(IUiHost *self);
+    int (CROSSCOM_CALL *display_size_y)(IUiHost *self);
+    float (CROSSCOM_CALL *calc_text_size_x)(IUiHost *self, const char *s);
+    float (CROSSCOM_CALL *calc_text_size_y)(IUiHost *self, const char *s);
+    void (CROSSCOM_CALL *table_next_column)(IUiHost *self);
+    void (CROSSCOM_CALL *text)(IUiHost *self, const char *s);
+    void (CROSSCOM_CALL *text_with_font)(IUiHost *self, int font_idx, const char *s);
+    int (CROSSCOM_CALL *button)(IUiHost *self, const char *label, float w, float h);
+    int (CROSSCOM_CALL *checkbox)(IUiHost *self, const char *label, int value);
+    int (CROSSCOM_CALL *slider_int)(IUiHost *self, const char *label, int value, int min, int max);
+    void (CROSSCOM_CALL *set_next_item_width)(IUiHost *self, float w);
+    void (CROSSCOM_CALL *image)(IUiHost *self, int texture_com_id, float w, float h);
+    void (CROSSCOM_CALL *image_fit)(IUiHost *self, int texture_com_id, float src_w, float src_h);
+    void (CROSSCOM_CALL *image_uv)(IUiHost *self, int texture_com_id, float w, float h, float u0, float v0, float u1, float v1);
+    void (CROSSCOM_CALL *multiline_text)(IUiHost *self, const char *content, float w, float h);
+    void (CROSSCOM_CALL *set_text_buffer)(IUiHost *self, int key, const char *content);
+    int (CROSSCOM_CALL *show_text_buffer)(IUiHost *self, int key, float w, float h);
+    void (CROSSCOM_CALL *copy_text_buffer)(IUiHost *self, int key);
+    int (CROSSCOM_CALL *tree_leaf)(IUiHost *self, const char *label, int selected);
+    void (CROSSCOM_CALL *list_clipped)(IUiHost *self, int count, IAction *body);
+    int (CROSSCOM_CALL *list_clipped_index)(IUiHost *self);
+    int (CROSSCOM_CALL *tree_node_open)(IUiHost *self, const char *label);
+    void (CROSSCOM_CALL *tree_pop)(IUiHost *self);
+    void (CROSSCOM_CALL *dock_space)(IUiHost *self, const char *root_id, IAction *body);
+    void (CROSSCOM_CALL *window_docked)(IUiHost *self, const char *title, IAction *body);
+    void (CROSSCOM_CALL *dock_layout_once)(IUiHost *self, const char *root_id, const char *left_window, const char *right_window, const char *bottom_window, const char *center_window, float left_ratio, float right_ratio, float bottom_ratio);
+    void (CROSSCOM_CALL *dock_layout_assets_once)(IUiHost *self, const char *root_id, const char *left_window, const char *right_window, float left_ratio);
+    void (CROSSCOM_CALL *dock_layout_scene_once)(IUiHost *self, const char *root_id, const char *inspector_window, const char *hierarchy_window, const char *scene_view_window, const char *resource_window, const char *preview_window, float right_ratio, float bottom_ratio, float hierarchy_ratio, float resource_ratio);
+    int (CROSSCOM_CALL *is_item_hovered)(IUiHost *self);
+    int (CROSSCOM_CALL *mouse_down)(IUiHost *self, int button);
+    float (CROSSCOM_CALL *mouse_drag_delta_x)(IUiHost *self, int button);
+    float (CROSSCOM_CALL *mouse_drag_delta_y)(IUiHost *self, int button);
+    void (CROSSCOM_CALL *reset_mouse_drag_delta)(IUiHost *self, int button);
+    float (CROSSCOM_CALL *mouse_wheel)(IUiHost *self);
+    float (CROSSCOM_CALL *mouse_pos_x)(IUiHost *self);
+    float (CROSSCOM_CALL *mouse_pos_y)(IUiHost *self);
+    int (CROSSCOM_CALL *any_key_or_mouse_down)(IUiHost *self);
+    void (CROSSCOM_CALL *main_menu_bar)(IUiHost *self, IAction *body);
+    void (CROSSCOM_CALL *menu)(IUiHost *self, const char *label, IAction *body);
+    int (CROSSCOM_CALL *menu_item)(IUiHost *self, const char *label, int selected);
+    int (CROSSCOM_CALL *content_region_avail_x)(IUiHost *self);
+    int (CROSSCOM_CALL *content_region_avail_y)(IUiHost *self);
+    void (CROSSCOM_CALL *fill_rect)(IUiHost *self, float x0, float y0, float x1, float y1, float r, float g, float b, float a);
+    void (CROSSCOM_CALL *image_rect)(IUiHost *self, int texture_com_id, float x0, float y0, float x1, float y1, float u0, float v0, float u1, float v1);
+    void (CROSSCOM_CALL *text_at)(IUiHost *self, float x, float y, float r, float g, float b, float a, const char *s);
+    float (CROSSCOM_CALL *game_font_size)(IUiHost *self);
+    void (CROSSCOM_CALL *text_at_small)(IUiHost *self, float x, float y, float r, float g, float b, float a, const char *s);
+    float (CROSSCOM_CALL *game_font_size_small)(IUiHost *self);
+} IUiHostVtbl;
+
+struct IUiHost {
+    const IUiHostVtbl *vtbl;
+};
+
+/* 7d3c8a44-58f1-4d12-9c4b-1a2f9c0e0d01 */
+static const CrosscomUuid IID_IUiHost = {{0x7d, 0x3c, 0x8a, 0x44, 0x58, 0xf1, 0x4d, 0x12, 0x9c, 0x4b, 0x1a, 0x2f, 0x9c, 0x0e, 0x0d, 0x01}};
+
+/* Interface IUiLayer */
+
+typedef struct IUiLayerVtbl {
+    long (CROSSCOM_CALL *query_interface)(IUiLayer *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IUiLayer *self);
+    long (CROSSCOM_CALL *release)(IUiLayer *self);
+    void (CROSSCOM_CALL *render)(IUiLayer *self, IUiHost *ui, float dt);
+} IUiLayerVtbl;
+
+struct IUiLayer {
+    const IUiLayerVtbl *vtbl;
+};
+
+/* 3cc6ab2c-0d00-499f-b59f-7e9d63abb136 */
+static const CrosscomUuid IID_IUiLayer = {{0x3c, 0xc6, 0xab, 0x2c, 0x0d, 0x00, 0x49, 0x9f, 0xb5, 0x9f, 0x7e, 0x9d, 0x63, 0xab, 0xb1, 0x36}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_RADIANCE_H */
@@ -0,0 +1,534 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: radiance_scripting::comdef::services */
+
+#ifndef CROSSCOM_SCRIPTING_SERVICES_H
+#define CROSSCOM_SCRIPTING_SERVICES_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IAudioSource IAudioSource;
+typedef struct IAudioService IAudioService;
+typedef struct ITexture ITexture;
+typedef struct IRenderTarget IRenderTarget;
+typedef struct IVideoHandle IVideoHandle;
+typedef struct IGifAnimation IGifAnimation;
+typedef struct ITextureService ITextureService;
+typedef struct ISprite ISprite;
+typedef struct IAtlasPage IAtlasPage;
+typedef struct ISpriteService ISpriteService;
+typedef struct IVfsService IVfsService;
+typedef struct IInputService IInputService;
+typedef struct IGameRegistry IGameRegistry;
+typedef struct IAppService IAppService;
+typedef struct IConfigService IConfigService;
+typedef struct IRandomService IRandomService;
+typedef struct IHostContext IHostContext;
+typedef struct IUiLayoutHandle IUiLayoutHandle;
+typedef struct IScriptHotReloader IScriptHotReloader;
+
+/* Interface IAudioSource */
+
+typedef struct IAudioSourceVtbl {
+    long (CROSSCOM_CALL *query_interface)(IAudioSource *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IAudioSource *self);
+    long (CROSSCOM_CALL *release)(IAudioSource *self);
+    void (CROSSCOM_CALL *play)(IAudioSource *self, int looped);
+    void (CROSSCOM_CALL *pause)(IAudioSource *self);
+    void (CROSSCOM_CALL *stop)(IAudioSource *self);
+    void (CROSSCOM_CALL *update)(IAudioSource *self);
+    int (CROSSCOM_CALL *state)(IAudioSource *self);
+} IAudioSourceVtbl;
+
+struct IAudioSource {
+    const IAudioSourceVtbl *vtbl;
+};
+
+/* 831d0c9e-f26f-4b2a-aadc-0e8b47584487 */
+static const CrosscomUuid IID_IAudioSource = {{0x83, 0x1d, 0x0c, 0x9e, 0xf2, 0x6f, 0x4b, 0x2a, 0xaa, 0xdc, 0x0e, 0x8b, 0x47, 0x58, 0x44, 0x87}};
+
+/* Class AudioSource: IAudioSource */
+static const CrosscomUuid CLSID_AudioSource = {{0xc2, 0x7b, 0x7e, 0x01, 0x4b, 0x90, 0x43, 0x92, 0xb6, 0x6f, 0x3a, 0x96, 0xf4, 0x8f, 0x2e, 0x99}};
+
+/* Interface IAudioService */
+
+typedef struct IAudioServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(IAudioService *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IAudioService *self);
+    long (CROSSCOM_CALL *release)(IAudioService *self);
+    IAudioSource *(CROSSCOM_CALL *load)(IAudioService *self, const char *vfs_path, int codec);
+} IAudioServiceVtbl;
+
+struct IAudioService {
+    const IAudioServiceVtbl *vtbl;
+};
+
+/* bd0f4f5a-15bf-4ebd-93de-12a24f2ff8c7 */
+static const CrosscomUuid IID_IAudioService = {{0xbd, 0x0f, 0x4f, 0x5a, 0x15, 0xbf, 0x4e, 0xbd, 0x93, 0xde, 0x12, 0xa2, 0x4f, 0x2f, 0xf8, 0xc7}};
+
+/* Class AudioService: IAudioService */
+static const CrosscomUuid CLSID_AudioService = {{0x9a, 0x86, 0x22, 0x4e, 0x8e, 0x50, 0x45, 0xbc, 0xb7, 0xe3, 0x96, 0xda, 0x10, 0x49, 0x72, 0x04}};
+
+/* Interface ITexture */
+
+typedef struct ITextureVtbl {
+    long (CROSSCOM_CALL *query_interface)(ITexture *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ITexture *self);
+    long (CROSSCOM_CALL *release)(ITexture *self);
+    int (CROSSCOM_CALL *width)(ITexture *self);
+    int (CROSSCOM_CALL *height)(ITexture *self);
+    int (CROSSCOM_CALL *imgui_id)(ITexture *self);
+} ITextureVtbl;
+
+struct ITexture {
+    const ITextureVtbl *vtbl;
+};
+
+/* eb3b4e35-0a69-4e1f-8d34-12a7a93869e4 */
+static const CrosscomUuid IID_ITexture = {{0xeb, 0x3b, 0x4e, 0x35, 0x0a, 0x69, 0x4e, 0x1f, 0x8d, 0x34, 0x12, 0xa7, 0xa9, 0x38, 0x69, 0xe4}};
+
+/* Class Texture: ITexture */
+static const CrosscomUuid CLSID_Texture = {{0x25, 0xab, 0x34, 0x0f, 0xcb, 0xc1, 0x4e, 0x4e, 0xa9, 0xf6, 0x54, 0x5b, 0xf0, 0x90, 0xd7, 0x41}};
+
+/* Interface IRenderTarget */
+
+typedef struct IRenderTargetVtbl {
+    long (CROSSCOM_CALL *query_interface)(IRenderTarget *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IRenderTarget *self);
+    long (CROSSCOM_CALL *release)(IRenderTarget *self);
+    int (CROSSCOM_CALL *width)(IRenderTarget *self);
+    int (CROSSCOM_CALL *height)(IRenderTarget *self);
+    void (CROSSCOM_CALL *resize)(IRenderTarget *self, int w, int h);
+    int (CROSSCOM_CALL *texture_id)(IRenderTarget *self);
+} IRenderTargetVtbl;
+
+struct IRenderTarget {
+    const IRenderTargetVtbl *vtbl;
+};
+
+/* d6f4f7a1-7c4a-4f1f-9c2e-1b3a4d5e6f70 */
+static const CrosscomUuid IID_IRenderTarget = {{0xd6, 0xf4, 0xf7, 0xa1, 0x7c, 0x4a, 0x4f, 0x1f, 0x9c, 0x2e, 0x1b, 0x3a, 0x4d, 0x5e, 0x6f, 0x70}};
+
+/* Class RenderTarget: IRenderTarget */
+static const CrosscomUuid CLSID_RenderTarget = {{0xd6, 0xf4, 0xf7, 0xa1, 0x7c, 0x4a, 0x4f, 0x1f, 0x9c, 0x2e, 0x1b, 0x3a, 0x4d, 0x5e, 0x6f, 0x71}};
+
+/* Interface IVideoHandle */
+
+typedef struct IVideoHandleVtbl {
+    long (CROSSCOM_CALL *query_interface)(IVideoHandle *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IVideoHandle *self);
+    long (CROSSCOM_CALL *release)(IVideoHandle *self);
+    void (CROSSCOM_CALL *toggle)(IVideoHandle *self);
+    int (CROSSCOM_CALL *state)(IVideoHandle *self);
+    int (CROSSCOM_CALL *width)(IVideoHandle *self);
+    int (CROSSCOM_CALL *height)(IVideoHandle *self);
+    int (CROSSCOM_CALL *texture_com_id)(IVideoHandle *self);
+    int (CROSSCOM_CALL *duration_ms)(IVideoHandle *self);
+    int (CROSSCOM_CALL *position_ms)(IVideoHandle *self);
+    void (CROSSCOM_CALL *seek_ms)(IVideoHandle *self, int ms);
+    void (CROSSCOM_CALL *restart)(IVideoHandle *self);
+    void (CROSSCOM_CALL *stop)(IVideoHandle *self);
+    void (CROSSCOM_CALL *set_looping)(IVideoHandle *self, int looping);
+    int (CROSSCOM_CALL *looping)(IVideoHandle *self);
+} IVideoHandleVtbl;
+
+struct IVideoHandle {
+    const IVideoHandleVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c05 */
+static const CrosscomUuid IID_IVideoHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x05}};
+
+/* Interface IGifAnimation */
+
+typedef struct IGifAnimationVtbl {
+    long (CROSSCOM_CALL *query_interface)(IGifAnimation *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IGifAnimation *self);
+    long (CROSSCOM_CALL *release)(IGifAnimation *self);
+    int (CROSSCOM_CALL *frame_count)(IGifAnimation *self);
+    ITexture *(CROSSCOM_CALL *frame_at)(IGifAnimation *self, int i);
+    int (CROSSCOM_CALL *delay_ms)(IGifAnimation *self, int i);
+} IGifAnimationVtbl;
+
+struct IGifAnimation {
+    const IGifAnimationVtbl *vtbl;
+};
+
+/* 15a50631-5098-45ca-ac41-1036b48df399 */
+static const CrosscomUuid IID_IGifAnimation = {{0x15, 0xa5, 0x06, 0x31, 0x50, 0x98, 0x45, 0xca, 0xac, 0x41, 0x10, 0x36, 0xb4, 0x8d, 0xf3, 0x99}};
+
+/* Class GifAnimation: IGifAnimation */
+static const CrosscomUuid CLSID_GifAnimation = {{0x15, 0xa5, 0x06, 0x31, 0x50, 0x98, 0x45, 0xca, 0xac, 0x41, 0x10, 0x36, 0xb4, 0x8d, 0xf3, 0xa0}};
+
+/* Interface ITextureService */
+
+typedef struct ITextureServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(ITextureService *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ITextureService *self);
+    long (CROSSCOM_CALL *release)(ITextureService *self);
+    ITexture *(CROSSCOM_CALL *load_png)(ITextureService *self, const char *vfs_path);
+    IObjectArray *(CROSSCOM_CALL *load_gif_frames)(ITextureService *self, const char *vfs_path);
+    IGifAnimation *(CROSSCOM_CALL *load_gif_animation)(ITextureService *self, const char *vfs_path);
+} ITextureServiceVtbl;
+
+struct ITextureService {
+    const ITextureServiceVtbl *vtbl;
+};
+
+/* 15a50631-5098-45ca-ac41-1036b48df398 */
+static const CrosscomUuid IID_ITextureService = {{0x15, 0xa5, 0x06, 0x31, 0x50, 0x98, 0x45, 0xca, 0xac, 0x41, 0x10, 0x36, 0xb4, 0x8d, 0xf3, 0x98}};
+
+/* Class TextureService: ITextureService */
+static const CrosscomUuid CLSID_TextureService = {{0x78, 0x07, 0xf4, 0x0d, 0x87, 0xf5, 0x46, 0xf9, 0xa4, 0x79, 0x32, 0x44, 0x9f, 0xb4, 0xbb, 0x74}};
+
+/* Interface ISprite */
+
+typedef struct ISpriteVtbl {
+    long (CROSSCOM_CALL *query_interface)(ISprite *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ISprite *self);
+    long (CROSSCOM_CALL *release)(ISprite *self);
+    int (CROSSCOM_CALL *com_id)(ISprite *self);
+    int (CROSSCOM_CALL *width)(ISprite *self);
+    int (CROSSCOM_CALL *height)(ISprite *self);
+    float (CROSSCOM_CALL *u0)(ISprite *self);
+    float (CROSSCOM_CALL *v0)(ISprite *self);
+    float (CROSSCOM_CALL *u1)(ISprite *self);
+    float (CROSSCOM_CALL *v1)(ISprite *self);
+} ISpriteVtbl;
+
+struct ISprite {
+    const ISpriteVtbl *vtbl;
+};
+
+/* 48874160-ffd0-443c-855a-f689eb2ddc60 */
+static const CrosscomUuid IID_ISprite = {{0x48, 0x87, 0x41, 0x60, 0xff, 0xd0, 0x44, 0x3c, 0x85, 0x5a, 0xf6, 0x89, 0xeb, 0x2d, 0xdc, 0x60}};
+
+/* Class Sprite: ISprite */
+static const CrosscomUuid CLSID_Sprite = {{0x24, 0xe1, 0x4d, 0x91, 0x6a, 0xd5, 0x4a, 0x86, 0xa7, 0xd8, 0xac, 0x47, 0xdd, 0xc9, 0xc5, 0xcc}};
+
+/* Interface IAtlasPage */
+
+typedef struct IAtlasPageVtbl {
+    long (CROSSCOM_CALL *query_interface)(IAtlasPage *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IAtlasPage *self);
+    long (CROSSCOM_CALL *release)(IAtlasPage *self);
+    int (CROSSCOM_CALL *width)(IAtlasPage *self);
+    int (CROSSCOM_CALL *height)(IAtlasPage *self);
+    ISprite *(CROSSCOM_CALL *sprite)(IAtlasPage *self, int x, int y, int w, int h);
+    ISprite *(CROSSCOM_CALL *whole)(IAtlasPage *self);
+} IAtlasPageVtbl;
+
+struct IAtlasPage {
+    const IAtlasPageVtbl *vtbl;
+};
+
+/* 396e0261-1097-4750-9be8-d29a3029a88e */
+static const CrosscomUuid IID_IAtlasPage = {{0x39, 0x6e, 0x02, 0x61, 0x10, 0x97, 0x47, 0x50, 0x9b, 0xe8, 0xd2, 0x9a, 0x30, 0x29, 0xa8, 0x8e}};
+
+/* Class AtlasPage: IAtlasPage */
+static const CrosscomUuid CLSID_AtlasPage = {{0x8b, 0x8f, 0x8b, 0x3d, 0xfc, 0xfe, 0x4b, 0x84, 0xa0, 0x64, 0x7e, 0x6f, 0x84, 0x62, 0x73, 0x5b}};
+
+/* Interface ISpriteService */
+
+typedef struct ISpriteServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(ISpriteService *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ISpriteService *self);
+    long (CROSSCOM_CALL *release)(ISpriteService *self);
+    ISprite *(CROSSCOM_CALL *load_sprite)(ISpriteService *self, const char *vfs_path);
+    IAtlasPage *(CROSSCOM_CALL *load_atlas_page)(ISpriteService *self, const char *vfs_path);
+} ISpriteServiceVtbl;
+
+struct ISpriteService {
+    const ISpriteServiceVtbl *vtbl;
+};
+
+/* d3c7d03e-9099-4ea6-8f1f-c091e615c855 */
+static const CrosscomUuid IID_ISpriteService = {{0xd3, 0xc7, 0xd0, 0x3e, 0x90, 0x99, 0x4e, 0xa6, 0x8f, 0x1f, 0xc0, 0x91, 0xe6, 0x15, 0xc8, 0x55}};
+
+/* Class SpriteService: ISpriteService */
+static const CrosscomUuid CLSID_SpriteService = {{0xcb, 0x25, 0xa8, 0x2b, 0x18, 0x48, 0x47, 0x08, 0xb6, 0xae, 0xa6, 0x16, 0x1d, 0xcb, 0x57, 0xf1}};
+
+/* Interface IVfsService */
+
+typedef struct IVfsServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(IVfsService *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IVfsService *self);
+    long (CROSSCOM_CALL *release)(IVfsService *self);
+    int (CROSSCOM_CALL *exists)(IVfsService *self, const char *vfs_path);
+    int (CROSSCOM_CALL *byte_len)(IVfsService *self, const char *vfs_path);
+    int (CROSSCOM_CALL *entry_count)(IVfsService *self, const char *vfs_path);
+    int (CROSSCOM_CALL *subdir_count)(IVfsService *self, const char *vfs_path);
+    const char *(CROSSCOM_CALL *entry_name)(IVfsService *self, const char *vfs_path, int index);
+    int (CROSSCOM_CALL *entry_is_dir)(IVfsService *self, const char *vfs_path, int index);
+    int (CROSSCOM_CALL *is_dir)(IVfsService *self, const char *vfs_path);
+    int (CROSSCOM_CALL *is_expanded)(IVfsService *self, const char *vfs_path);
+    void (CROSSCOM_CALL *toggle_expanded)(IVfsService *self, const char *vfs_path);
+    int (CROSSCOM_CALL *command_id)(IVfsService *self, const char *vfs_path);
+    const char *(CROSSCOM_CALL *command_path)(IVfsService *self, int command_id);
+} IVfsServiceVtbl;
+
+struct IVfsService {
+    const IVfsServiceVtbl *vtbl;
+};
+
+/* fa71af91-765f-4fc4-a36d-63fcbe27c4f5 */
+static const CrosscomUuid IID_IVfsService = {{0xfa, 0x71, 0xaf, 0x91, 0x76, 0x5f, 0x4f, 0xc4, 0xa3, 0x6d, 0x63, 0xfc, 0xbe, 0x27, 0xc4, 0xf5}};
+
+/* Class VfsService: IVfsService */
+static const CrosscomUuid CLSID_VfsService = {{0xf1, 0x9e, 0x19, 0xaa, 0x95, 0x68, 0x40, 0x21, 0xae, 0x47, 0x76, 0x58, 0x42, 0x67, 0x80, 0xf2}};
+
+/* Interface IInputService */
+
+typedef struct IInputServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(IInputService *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IInputService *self);
+    long (CROSSCOM_CALL *release)(IInputService *self);
+    int (CROSSCOM_CALL *key_down)(IInputService *self, int keycode);
+    int (CROSSCOM_CALL *key_pressed)(IInputService *self, int keycode);
+    float (CROSSCOM_CALL *mouse_x)(IInputService *self);
+    float (CROSSCOM_CALL *mouse_y)(IInputService *self);
+    float (CROSSCOM_CALL *axis)(IInputService *self, int axis_code);
+    int (CROSSCOM_CALL *mouse_button_down)(IInputService *self, int button);
+    int (CROSSCOM_CALL *mouse_button_pressed)(IInputService *self, int button);
+    float (CROSSCOM_CALL *mouse_delta_x)(IInputService *self);
+    float (CROSSCOM_CALL *mouse_delta_y)(IInputService *self);
+    float (CROSSCOM_CALL *mouse_wheel)(IInputService *self);
+} IInputServiceVtbl;
+
+struct IInputService {
+    const IInputServiceVtbl *vtbl;
+};
+
+/* c0d98698-33e4-4503-a403-eb3573716ed0 */
+static const CrosscomUuid IID_IInputService = {{0xc0, 0xd9, 0x86, 0x98, 0x33, 0xe4, 0x45, 0x03, 0xa4, 0x03, 0xeb, 0x35, 0x73, 0x71, 0x6e, 0xd0}};
+
+/* Class InputService: IInputService */
+static const CrosscomUuid CLSID_InputService = {{0x09, 0x93, 0xf2, 0xd2, 0x6b, 0x91, 0x4b, 0xbb, 0x92, 0xac, 0xac, 0xd5, 0xe3, 0xee, 0x94, 0xc4}};
+
+/* Interface IGameRegistry */
+
+typedef struct IGameRegistryVtbl {
+    long (CROSSCOM_CALL *query_interface)(IGameRegistry *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IGameRegistry *self);
+    long (CROSSCOM_CALL *release)(IGameRegistry *self);
+    int (CROSSCOM_CALL *count)(IGameRegistry *self);
+    int (CROSSCOM_CALL *game_at)(IGameRegistry *self, int index);
+    const char *(CROSSCOM_CALL *full_name)(IGameRegistry *self, int game);
+    const char *(CROSSCOM_CALL *config_key)(IGameRegistry *self, int game);
+    const char *(CROSSCOM_CALL *default_asset_path)(IGameRegistry *self, int game);
+} IGameRegistryVtbl;
+
+struct IGameRegistry {
+    const IGameRegistryVtbl *vtbl;
+};
+
+/* 5ad2bb0f-3e0c-4282-ab44-3d503ce44443 */
+static const CrosscomUuid IID_IGameRegistry = {{0x5a, 0xd2, 0xbb, 0x0f, 0x3e, 0x0c, 0x42, 0x82, 0xab, 0x44, 0x3d, 0x50, 0x3c, 0xe4, 0x44, 0x43}};
+
+/* Class GameRegistry: IGameRegistry */
+static const CrosscomUuid CLSID_GameRegistry = {{0x41, 0x92, 0xe6, 0xb8, 0x79, 0x85, 0x4d, 0x18, 0x8d, 0x28, 0x03, 0x2f, 0x1f, 0xc6, 0x3d, 0xe6}};
+
+/* Interface IAppService */
+
+typedef struct IAppServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(IAppService *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IAppService *self);
+    long (CROSSCOM_CALL *release)(IAppService *self);
+    IDirector *(CROSSCOM_CALL *open_game)(IAppService *self, int ordinal);
+    void (CROSSCOM_CALL *exit)(IAppService *self);
+    void (CROSSCOM_CALL *set_title)(IAppService *self, const char *title);
+} IAppServiceVtbl;
+
+struct IAppService {
+    const IAppServiceVtbl *vtbl;
+};
+
+/* 302fd52d-e93d-45e3-bfb3-719b94f2b215 */
+static const CrosscomUuid IID_IAppService = {{0x30, 0x2f, 0xd5, 0x2d, 0xe9, 0x3d, 0x45, 0xe3, 0xbf, 0xb3, 0x71, 0x9b, 0x94, 0xf2, 0xb2, 0x15}};
+
+/* Class AppService: IAppService */
+static const CrosscomUuid CLSID_AppService = {{0x8c, 0x4d, 0x50, 0xaa, 0x3b, 0x07, 0x4a, 0x32, 0x9d, 0x20, 0x2e, 0x0b, 0x2f, 0x7c, 0x00, 0x03}};
+
+/* Interface IConfigService */
+
+typedef struct IConfigServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(IConfigService *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IConfigService *self);
+    long (CROSSCOM_CALL *release)(IConfigService *self);
+    const char *(CROSSCOM_CALL *get_asset_path)(IConfigService *self, const char *config_key);
+    void (CROSSCOM_CALL *set_asset_path)(IConfigService *self, const char *config_key, const char *path);
+    int (CROSSCOM_CALL *save)(IConfigService *self);
+    void (CROSSCOM_CALL *reload)(IConfigService *self);
+    const char *(CROSSCOM_CALL *pick_folder)(IConfigService *self, const char *initial);
+    const char *(CROSSCOM_CALL *pick_save_file)(IConfigService *self, const char *initial, const char *default_name, const char *ext_filter);
+    const char *(CROSSCOM_CALL *get_theme)(IConfigService *self, const char *config_key);
+    void (CROSSCOM_CALL *set_theme)(IConfigService *self, const char *config_key, const char *name);
+    void (CROSSCOM_CALL *apply_theme)(IConfigService *self, const char *name);
+    int (CROSSCOM_CALL *available_theme_count)(IConfigService *self);
+    const char *(CROSSCOM_CALL *available_theme_at)(IConfigService *self, int index);
+    const char *(CROSSCOM_CALL *get_scene_scale_mode)(IConfigService *self);
+    void (CROSSCOM_CALL *set_scene_scale_mode)(IConfigService *self, const char *mode);
+} IConfigServiceVtbl;
+
+struct IConfigService {
+    const IConfigServiceVtbl *vtbl;
+};
+
+/* 8c4d50aa-3b07-4a32-9d20-2e0b2f7c0001 */
+static const CrosscomUuid IID_IConfigService = {{0x8c, 0x4d, 0x50, 0xaa, 0x3b, 0x07, 0x4a, 0x32, 0x9d, 0x20, 0x2e, 0x0b, 0x2f, 0x7c, 0x00, 0x01}};
+
+/* Interface IRandomService */
+
+typedef struct IRandomServiceVtbl {
+    long (CROSSCOM_CALL *query_interface)(IRandomService *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IRandomService *self);
+    long (CROSSCOM_CALL *release)(IRandomService *self);
+    int (CROSSCOM_CALL *next_int)(IRandomService *self, int max);
+} IRandomServiceVtbl;
+
+struct IRandomService {
+    const IRandomServiceVtbl *vtbl;
+};
+
+/* 8c4d50aa-3b07-4a32-9d20-2e0b2f7c0010 */
+static const CrosscomUuid IID_IRandomService = {{0x8c, 0x4d, 0x50, 0xaa, 0x3b, 0x07, 0x4a, 0x32, 0x9d, 0x20, 0x2e, 0x0b, 0x2f, 0x7c, 0x00, 0x10}};
+
+/* Class RandomService: IRandomService */
+static const CrosscomUuid CLSID_RandomService = {{0x8c, 0x4d, 0x50, 0xaa, 0x3b, 0x07, 0x4a, 0x32, 0x9d, 0x20, 0x2e, 0x0b, 0x2f, 0x7c, 0x00, 0x11}};
+
+/* Interface IHostContext */
+
+typedef struct IHostContextVtbl {
+    long (CROSSCOM_CALL *query_interface)(IHostContext *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IHostContext *self);
+    long (CROSSCOM_CALL *release)(IHostContext *self);
+    ISceneManager *(CROSSCOM_CALL *scene_manager)(IHostContext *self);
+    IAudioService *(CROSSCOM_CALL *audio)(IHostContext *self);
+    ITextureService *(CROSSCOM_CALL *textures)(IHostContext *self);
+    IVfsService *(CROSSCOM_CALL *vfs)(IHostContext *self);
+    IInputService *(CROSSCOM_CALL *input)(IHostContext *self);
+    IGameRegistry *(CROSSCOM_CALL *games)(IHostContext *self);
+    IAppService *(CROSSCOM_CALL *app)(IHostContext *self);
+    IRandomService *(CROSSCOM_CALL *random)(IHostContext *self);
+    IConfigService *(CROSSCOM_CALL *config)(IHostContext *self);
+} IHostContextVtbl;
+
+struct IHostContext {
+    const IHostContextVtbl *vtbl;
+};
+
+/* b6fd26a0-e028-427c-994c-e18310c1cd34 */
+static const CrosscomUuid IID_IHostContext = {{0xb6, 0xfd, 0x26, 0xa0, 0xe0, 0x28, 0x42, 0x7c, 0x99, 0x4c, 0xe1, 0x83, 0x10, 0xc1, 0xcd, 0x34}};
+
+/* Class HostContext: IHostContext */
+static const CrosscomUuid CLSID_HostContext = {{0xe7, 0xc2, 0x64, 0xf9, 0xfe, 0xa1, 0x47, 0x2f, 0x9d, 0x47, 0x46, 0x8a, 0x6d, 0xec, 0x93, 0x31}};
+
+/* Class UiHost: IUiHost */
+static const CrosscomUuid CLSID_UiHost = {{0x7d, 0x3c, 0x8a, 0x44, 0x58, 0xf1, 0x4d, 0x12, 0x9c, 0x4b, 0x1a, 0x2f, 0x9c, 0x0e, 0x0d, 0x02}};
+
+/* Interface IUiLayoutHandle */
+
+typedef struct IUiLayoutHandleVtbl {
+    long (CROSSCOM_CALL *query_interface)(IUiLayoutHandle *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IUiLayoutHandle *self);
+    long (CROSSCOM_CALL *release)(IUiLayoutHandle *self);
+    int (CROSSCOM_CALL *native_width)(IUiLayoutHandle *self);
+    int (CROSSCOM_CALL *native_height)(IUiLayoutHandle *self);
+    const char *(CROSSCOM_CALL *text_dump)(IUiLayoutHandle *self);
+    int (CROSSCOM_CALL *draw_count)(IUiLayoutHandle *self);
+    int (CROSSCOM_CALL *draw_window_id)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_x)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_y)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_w)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_h)(IUiLayoutHandle *self, int i);
+    int (CROSSCOM_CALL *draw_texture_com_id)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_u0)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_v0)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_u1)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_v1)(IUiLayoutHandle *self, int i);
+    int (CROSSCOM_CALL *draw_hover_texture_com_id)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_hover_u0)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_hover_v0)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_hover_u1)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_hover_v1)(IUiLayoutHandle *self, int i);
+    int (CROSSCOM_CALL *draw_pressed_texture_com_id)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_pressed_u0)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_pressed_v0)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_pressed_u1)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_pressed_v1)(IUiLayoutHandle *self, int i);
+    int (CROSSCOM_CALL *draw_disabled_texture_com_id)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_disabled_u0)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_disabled_v0)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_disabled_u1)(IUiLayoutHandle *self, int i);
+    float (CROSSCOM_CALL *draw_disabled_v1)(IUiLayoutHandle *self, int i);
+    int (CROSSCOM_CALL *draw_is_interactive)(IUiLayoutHandle *self, int i);
+    const char *(CROSSCOM_CALL *draw_text)(IUiLayoutHandle *self, int i);
+    int (CROSSCOM_CALL *draw_has_text)(IUiLayoutHandle *self, int i);
+    int (CROSSCOM_CALL *draw_frame_count)(IUiLayoutHandle *self, int i);
+    int (CROSSCOM_CALL *draw_frame_texture_com_id)(IUiLayoutHandle *self, int i, int frame);
+    float (CROSSCOM_CALL *draw_frame_u0)(IUiLayoutHandle *self, int i, int frame);
+    float (CROSSCOM_CALL *draw_frame_v0)(IUiLayoutHandle *self, int i, int frame);
+    float (CROSSCOM_CALL *draw_frame_u1)(IUiLayoutHandle *self, int i, int frame);
+    float (CROSSCOM_CALL *draw_frame_v1)(IUiLayoutHandle *self, int i, int frame);
+    int (CROSSCOM_CALL *window_count)(IUiLayoutHandle *self);
+    const char *(CROSSCOM_CALL *window_name)(IUiLayoutHandle *self, int i);
+    const char *(CROSSCOM_CALL *window_type)(IUiLayoutHandle *self, int i);
+} IUiLayoutHandleVtbl;
+
+struct IUiLayoutHandle {
+    const IUiLayoutHandleVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c19 */
+static const CrosscomUuid IID_IUiLayoutHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x19}};
+
+/* Interface IScriptHotReloader */
+
+typedef struct IScriptHotReloaderVtbl {
+    long (CROSSCOM_CALL *query_interface)(IScriptHotReloader *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IScriptHotReloader *self);
+    long (CROSSCOM_CALL *release)(IScriptHotReloader *self);
+    void (CROSSCOM_CALL *on_loading)(IScriptHotReloader *self);
+    void (CROSSCOM_CALL *on_updating)(IScriptHotReloader *self, float delta_sec);
+    void (CROSSCOM_CALL *on_unloading)(IScriptHotReloader *self);
+} IScriptHotReloaderVtbl;
+
+struct IScriptHotReloader {
+    const IScriptHotReloaderVtbl *vtbl;
+};
+
+/* dd46b4e6-7f0c-4614-bba7-ec3116b367ea */
+static const CrosscomUuid IID_IScriptHotReloader = {{0xdd, 0x46, 0xb4, 0xe6, 0x7f, 0x0c, 0x46, 0x14, 0xbb, 0xa7, 0xec, 0x31, 0x16, 0xb3, 0x67, 0xea}};
+
+/* Class ScriptHotReloader: IScriptHotReloader */
+static const CrosscomUuid CLSID_ScriptHotReloader = {{0x76, 0x63, 0x51, 0x67, 0x99, 0xbc, 0x4f, 0x53, 0xb8, 0x01, 0x66, 0xbb, 0x7c, 0xd2, 0x07, 0x8f}};
+
+/* Class ScriptConsole: IUiLayer */
+static const CrosscomUuid CLSID_ScriptConsole = {{0x25, 0xef, 0x5a, 0x3f, 0x47, 0x77, 0x4f, 0x94, 0x8c, 0xd4, 0xa9, 0xd5, 0x7b, 0xd2, 0x80, 0xaf}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_SCRIPTING_SERVICES_H */
@@ -0,0 +1,44 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: shared::comdef::shared_services */
+
+#ifndef CROSSCOM_SHARED_SERVICES_H
+#define CROSSCOM_SHARED_SERVICES_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "scripting_services.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Class ConfigService: IConfigService */
+static const CrosscomUuid CLSID_ConfigService = {{0x8c, 0x4d, 0x50, 0xaa, 0x3b, 0x07, 0x4a, 0x32, 0x9d, 0x20, 0x2e, 0x0b, 0x2f, 0x7c, 0x00, 0x20}};
+
+/* Class UiLayoutHandle: IUiLayoutHandle */
+static const CrosscomUuid CLSID_UiLayoutHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x1a}};
+
+/* Class VideoHandle: IVideoHandle */
+static const CrosscomUuid CLSID_VideoHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x1b}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_SHARED_SERVICES_H */
@@ -0,0 +1,107 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: test::crosscom_gen */
+
+#ifndef CROSSCOM_TEST_H
+#define CROSSCOM_TEST_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct ITest ITest;
+typedef struct ITest2 ITest2;
+typedef struct ITest3 ITest3;
+typedef struct ITest4 ITest4;
+
+/* Interface ITest */
+
+typedef struct ITestVtbl {
+    long (CROSSCOM_CALL *query_interface)(ITest *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ITest *self);
+    long (CROSSCOM_CALL *release)(ITest *self);
+    int (CROSSCOM_CALL *test)(ITest *self);
+} ITestVtbl;
+
+struct ITest {
+    const ITestVtbl *vtbl;
+};
+
+/* 6ac46481-7efa-45ff-a279-687b4603c746 */
+static const CrosscomUuid IID_ITest = {{0x6a, 0xc4, 0x64, 0x81, 0x7e, 0xfa, 0x45, 0xff, 0xa2, 0x79, 0x68, 0x7b, 0x46, 0x03, 0xc7, 0x46}};
+
+/* Interface ITest2 */
+
+typedef struct ITest2Vtbl {
+    long (CROSSCOM_CALL *query_interface)(ITest2 *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ITest2 *self);
+    long (CROSSCOM_CALL *release)(ITest2 *self);
+    int (CROSSCOM_CALL *test)(ITest2 *self);
+    float (CROSSCOM_CALL *mul)(ITest2 *self, int a, float b);
+} ITest2Vtbl;
+
+struct ITest2 {
+    const ITest2Vtbl *vtbl;
+};
+
+/* de3d989d-2b1d-42a3-b085-a23e40840126 */
+static const CrosscomUuid IID_ITest2 = {{0xde, 0x3d, 0x98, 0x9d, 0x2b, 0x1d, 0x42, 0xa3, 0xb0, 0x85, 0xa2, 0x3e, 0x40, 0x84, 0x01, 0x26}};
+
+/* Interface ITest3 */
+
+typedef struct ITest3Vtbl {
+    long (CROSSCOM_CALL *query_interface)(ITest3 *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ITest3 *self);
+    long (CROSSCOM_CALL *release)(ITest3 *self);
+    int (CROSSCOM_CALL *echo)(ITest3 *self, int a);
+} ITest3Vtbl;
+
+struct ITest3 {
+    const ITest3Vtbl *vtbl;
+};
+
+/* de3d989d-2b1d-42a3-b085-a23e40840128 */
+static const CrosscomUuid IID_ITest3 = {{0xde, 0x3d, 0x98, 0x9d, 0x2b, 0x1d, 0x42, 0xa3, 0xb0, 0x85, 0xa2, 0x3e, 0x40, 0x84, 0x01, 0x28}};
+
+/* Interface ITest4 */
+
+typedef struct ITest4Vtbl {
+    long (CROSSCOM_CALL *query_interface)(ITest4 *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ITest4 *self);
+    long (CROSSCOM_CALL *release)(ITest4 *self);
+    ITest3 *(CROSSCOM_CALL *get)(ITest4 *self);
+} ITest4Vtbl;
+
+struct ITest4 {
+    const ITest4Vtbl *vtbl;
+};
+
+/* de3d989d-2b1d-42a3-b085-a23e40840129 */
+static const CrosscomUuid IID_ITest4 = {{0xde, 0x3d, 0x98, 0x9d, 0x2b, 0x1d, 0x42, 0xa3, 0xb0, 0x85, 0xa2, 0x3e, 0x40, 0x84, 0x01, 0x29}};
+
+/* Class Test: ITest2, ITest, ITest3, ITest4 */
+static const CrosscomUuid CLSID_Test = {{0x50, 0x20, 0x3e, 0xe6, 0xe4, 0x72, 0x42, 0xba, 0xbd, 0x57, 0xa2, 0x02, 0xe9, 0xca, 0xfe, 0x7f}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_TEST_H */
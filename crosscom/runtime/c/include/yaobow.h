@@ -0,0 +1,77 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: yaobow::comdef */
+
+#ifndef CROSSCOM_YAOBOW_H
+#define CROSSCOM_YAOBOW_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+#include "scripting_services.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IPal5StoryDirector IPal5StoryDirector;
+
+/* Class OpenPal3ApplicationLoaderComponent: IApplicationLoaderComponent */
+static const CrosscomUuid CLSID_OpenPal3ApplicationLoaderComponent = {{0xe0, 0x61, 0x81, 0x13, 0x5c, 0xf2, 0x45, 0x62, 0xba, 0x93, 0xff, 0xd3, 0x5d, 0x36, 0xc7, 0xf3}};
+
+/* Class OpenPal4ApplicationLoaderComponent: IApplicationLoaderComponent */
+static const CrosscomUuid CLSID_OpenPal4ApplicationLoaderComponent = {{0x41, 0x20, 0x87, 0xff, 0x19, 0xbe, 0x4f, 0xa1, 0x86, 0x5a, 0x9d, 0xbc, 0x24, 0x3b, 0xd4, 0x34}};
+
+/* Class OpenPal5ApplicationLoaderComponent: IApplicationLoaderComponent */
+static const CrosscomUuid CLSID_OpenPal5ApplicationLoaderComponent = {{0xce, 0x22, 0x43, 0xb2, 0x1f, 0xdf, 0x42, 0xe4, 0x96, 0xfc, 0x19, 0x41, 0x99, 0x72, 0x93, 0x50}};
+
+/* Class OpenSwd5ApplicationLoaderComponent: IApplicationLoaderComponent */
+static const CrosscomUuid CLSID_OpenSwd5ApplicationLoaderComponent = {{0x21, 0xab, 0x77, 0x96, 0x95, 0xb3, 0x4c, 0xf9, 0xb6, 0x8d, 0x71, 0x5e, 0x1d, 0x72, 0x87, 0xac}};
+
+/* Class YaobowApplicationLoader: IApplicationLoaderComponent */
+static const CrosscomUuid CLSID_YaobowApplicationLoader = {{0x94, 0x92, 0xea, 0x12, 0xf9, 0x0d, 0x49, 0x09, 0x9d, 0xbb, 0x7d, 0x16, 0xa0, 0xdf, 0x71, 0x6d}};
+
+/* Interface IPal5StoryDirector */
+
+typedef struct IPal5StoryDirectorVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPal5StoryDirector *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPal5StoryDirector *self);
+    long (CROSSCOM_CALL *release)(IPal5StoryDirector *self);
+    void (CROSSCOM_CALL *activate)(IPal5StoryDirector *self);
+    IDirector *(CROSSCOM_CALL *update)(IPal5StoryDirector *self, float delta_sec);
+    void (CROSSCOM_CALL *deactivate)(IPal5StoryDirector *self);
+} IPal5StoryDirectorVtbl;
+
+struct IPal5StoryDirector {
+    const IPal5StoryDirectorVtbl *vtbl;
+};
+
+/* 2b9f7d1a-4c6e-4a2b-9f3d-7e1c0a5b8d42 */
+static const CrosscomUuid IID_IPal5StoryDirector = {{0x2b, 0x9f, 0x7d, 0x1a, 0x4c, 0x6e, 0x4a, 0x2b, 0x9f, 0x3d, 0x7e, 0x1c, 0x0a, 0x5b, 0x8d, 0x42}};
+
+/* Class Pal5StoryDirector: IPal5StoryDirector, IUiLayer */
+static const CrosscomUuid CLSID_Pal5StoryDirector = {{0x7c, 0x4e, 0x9a, 0x05, 0x3b, 0x1d, 0x4f, 0x8a, 0x8c, 0x2e, 0x6d, 0x9b, 0x0f, 0x1a, 0x2e, 0x34}};
+
+/* Class OpenPal3DebugLayer: IUiLayer */
+static const CrosscomUuid CLSID_OpenPal3DebugLayer = {{0x4b, 0x39, 0xdc, 0xfa, 0x84, 0x2e, 0x44, 0xf4, 0x8e, 0x1a, 0x29, 0x4a, 0xb2, 0x30, 0xce, 0xde}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_YAOBOW_H */
@@ -0,0 +1,283 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: yaobow_editor::comdef::editor_services */
+
+#ifndef CROSSCOM_YAOBOW_EDITOR_SERVICES_H
+#define CROSSCOM_YAOBOW_EDITOR_SERVICES_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+#include "scripting_services.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IImageHandle IImageHandle;
+typedef struct IAudioHandle IAudioHandle;
+typedef struct IPreviewSession IPreviewSession;
+typedef struct IModelHandle IModelHandle;
+typedef struct IPreviewerHub IPreviewerHub;
+typedef struct IResourceManager IResourceManager;
+typedef struct ISceneHandle ISceneHandle;
+typedef struct IScenePreviewSession IScenePreviewSession;
+typedef struct IInspectorView IInspectorView;
+typedef struct IEditorHostContext IEditorHostContext;
+
+/* Interface IImageHandle */
+
+typedef struct IImageHandleVtbl {
+    long (CROSSCOM_CALL *query_interface)(IImageHandle *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IImageHandle *self);
+    long (CROSSCOM_CALL *release)(IImageHandle *self);
+    int (CROSSCOM_CALL *width)(IImageHandle *self);
+    int (CROSSCOM_CALL *height)(IImageHandle *self);
+    int (CROSSCOM_CALL *texture_com_id)(IImageHandle *self);
+} IImageHandleVtbl;
+
+struct IImageHandle {
+    const IImageHandleVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c01 */
+static const CrosscomUuid IID_IImageHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x01}};
+
+/* Class ImageHandle: IImageHandle */
+static const CrosscomUuid CLSID_ImageHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x02}};
+
+/* Interface IAudioHandle */
+
+typedef struct IAudioHandleVtbl {
+    long (CROSSCOM_CALL *query_interface)(IAudioHandle *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IAudioHandle *self);
+    long (CROSSCOM_CALL *release)(IAudioHandle *self);
+    void (CROSSCOM_CALL *toggle)(IAudioHandle *self);
+    int (CROSSCOM_CALL *state)(IAudioHandle *self);
+    void (CROSSCOM_CALL *update)(IAudioHandle *self);
+} IAudioHandleVtbl;
+
+struct IAudioHandle {
+    const IAudioHandleVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c03 */
+static const CrosscomUuid IID_IAudioHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x03}};
+
+/* Class AudioHandle: IAudioHandle */
+static const CrosscomUuid CLSID_AudioHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x04}};
+
+/* Interface IPreviewSession */
+
+typedef struct IPreviewSessionVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPreviewSession *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPreviewSession *self);
+    long (CROSSCOM_CALL *release)(IPreviewSession *self);
+    void (CROSSCOM_CALL *close)(IPreviewSession *self);
+    IRenderTarget *(CROSSCOM_CALL *target)(IPreviewSession *self);
+    void (CROSSCOM_CALL *tick_camera)(IPreviewSession *self, float dx, float dy, float wheel, int buttons);
+} IPreviewSessionVtbl;
+
+struct IPreviewSession {
+    const IPreviewSessionVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c07 */
+static const CrosscomUuid IID_IPreviewSession = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x07}};
+
+/* Class PreviewSession: IPreviewSession */
+static const CrosscomUuid CLSID_PreviewSession = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x08}};
+
+/* Interface IModelHandle */
+
+typedef struct IModelHandleVtbl {
+    long (CROSSCOM_CALL *query_interface)(IModelHandle *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IModelHandle *self);
+    long (CROSSCOM_CALL *release)(IModelHandle *self);
+    const char *(CROSSCOM_CALL *text_dump)(IModelHandle *self);
+    IPreviewSession *(CROSSCOM_CALL *open_preview)(IModelHandle *self);
+    int (CROSSCOM_CALL *export_glb)(IModelHandle *self, const char *output_path);
+} IModelHandleVtbl;
+
+struct IModelHandle {
+    const IModelHandleVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c09 */
+static const CrosscomUuid IID_IModelHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x09}};
+
+/* Class ModelHandle: IModelHandle */
+static const CrosscomUuid CLSID_ModelHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x0a}};
+
+/* Interface IPreviewerHub */
+
+typedef struct IPreviewerHubVtbl {
+    long (CROSSCOM_CALL *query_interface)(IPreviewerHub *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IPreviewerHub *self);
+    long (CROSSCOM_CALL *release)(IPreviewerHub *self);
+    int (CROSSCOM_CALL *classify)(IPreviewerHub *self, const char *vfs_path);
+    const char *(CROSSCOM_CALL *open_text)(IPreviewerHub *self, const char *vfs_path);
+    const char *(CROSSCOM_CALL *dump_structured)(IPreviewerHub *self, const char *vfs_path);
+    IImageHandle *(CROSSCOM_CALL *open_image)(IPreviewerHub *self, const char *vfs_path);
+    IAudioHandle *(CROSSCOM_CALL *open_audio)(IPreviewerHub *self, const char *vfs_path);
+    IVideoHandle *(CROSSCOM_CALL *open_video)(IPreviewerHub *self, const char *vfs_path);
+    IModelHandle *(CROSSCOM_CALL *open_model)(IPreviewerHub *self, const char *vfs_path);
+    ISceneHandle *(CROSSCOM_CALL *open_scene)(IPreviewerHub *self, const char *vfs_path);
+    IUiLayoutHandle *(CROSSCOM_CALL *open_ui_layout)(IPreviewerHub *self, const char *vfs_path);
+    IResourceManager *(CROSSCOM_CALL *resources)(IPreviewerHub *self);
+} IPreviewerHubVtbl;
+
+struct IPreviewerHub {
+    const IPreviewerHubVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c0b */
+static const CrosscomUuid IID_IPreviewerHub = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x0b}};
+
+/* Class PreviewerHub: IPreviewerHub */
+static const CrosscomUuid CLSID_PreviewerHub = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x0c}};
+
+/* Interface IResourceManager */
+
+typedef struct IResourceManagerVtbl {
+    long (CROSSCOM_CALL *query_interface)(IResourceManager *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IResourceManager *self);
+    long (CROSSCOM_CALL *release)(IResourceManager *self);
+    int (CROSSCOM_CALL *category_count)(IResourceManager *self);
+    const char *(CROSSCOM_CALL *category_name)(IResourceManager *self, int idx);
+    int (CROSSCOM_CALL *category_entry_count)(IResourceManager *self, int idx, const char *filter);
+    const char *(CROSSCOM_CALL *category_entry_path)(IResourceManager *self, int idx, const char *filter, int row);
+} IResourceManagerVtbl;
+
+struct IResourceManager {
+    const IResourceManagerVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c10 */
+static const CrosscomUuid IID_IResourceManager = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x10}};
+
+/* Class ResourceManager: IResourceManager */
+static const CrosscomUuid CLSID_ResourceManager = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x11}};
+
+/* Interface ISceneHandle */
+
+typedef struct ISceneHandleVtbl {
+    long (CROSSCOM_CALL *query_interface)(ISceneHandle *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(ISceneHandle *self);
+    long (CROSSCOM_CALL *release)(ISceneHandle *self);
+    IScenePreviewSession *(CROSSCOM_CALL *open_preview)(ISceneHandle *self);
+    int (CROSSCOM_CALL *node_count)(ISceneHandle *self);
+    int (CROSSCOM_CALL *node_parent)(ISceneHandle *self, int id);
+    const char *(CROSSCOM_CALL *node_name)(ISceneHandle *self, int id);
+    int (CROSSCOM_CALL *node_kind)(ISceneHandle *self, int id);
+} ISceneHandleVtbl;
+
+struct ISceneHandle {
+    const ISceneHandleVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c12 */
+static const CrosscomUuid IID_ISceneHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x12}};
+
+/* Class SceneHandle: ISceneHandle */
+static const CrosscomUuid CLSID_SceneHandle = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x13}};
+
+/* Interface IScenePreviewSession */
+
+typedef struct IScenePreviewSessionVtbl {
+    long (CROSSCOM_CALL *query_interface)(IScenePreviewSession *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IScenePreviewSession *self);
+    long (CROSSCOM_CALL *release)(IScenePreviewSession *self);
+    void (CROSSCOM_CALL *close)(IScenePreviewSession *self);
+    IRenderTarget *(CROSSCOM_CALL *target)(IScenePreviewSession *self);
+    void (CROSSCOM_CALL *tick_camera)(IScenePreviewSession *self, float dx, float dy, float wheel, int buttons);
+    void (CROSSCOM_CALL *set_selection)(IScenePreviewSession *self, int node_id);
+    int (CROSSCOM_CALL *selection)(IScenePreviewSession *self);
+    void (CROSSCOM_CALL *set_gizmo_visible)(IScenePreviewSession *self, int axes, int aabb, int pivot);
+    IInspectorView *(CROSSCOM_CALL *inspector_for)(IScenePreviewSession *self, int node_id);
+} IScenePreviewSessionVtbl;
+
+struct IScenePreviewSession {
+    const IScenePreviewSessionVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c14 */
+static const CrosscomUuid IID_IScenePreviewSession = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x14}};
+
+/* Class ScenePreviewSession: IScenePreviewSession */
+static const CrosscomUuid CLSID_ScenePreviewSession = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x15}};
+
+/* Interface IInspectorView */
+
+typedef struct IInspectorViewVtbl {
+    long (CROSSCOM_CALL *query_interface)(IInspectorView *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IInspectorView *self);
+    long (CROSSCOM_CALL *release)(IInspectorView *self);
+    int (CROSSCOM_CALL *field_count)(IInspectorView *self);
+    const char *(CROSSCOM_CALL *field_key)(IInspectorView *self, int i);
+    const char *(CROSSCOM_CALL *field_value)(IInspectorView *self, int i);
+} IInspectorViewVtbl;
+
+struct IInspectorView {
+    const IInspectorViewVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c16 */
+static const CrosscomUuid IID_IInspectorView = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x16}};
+
+/* Class InspectorView: IInspectorView */
+static const CrosscomUuid CLSID_InspectorView = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x17}};
+
+/* Interface IEditorHostContext */
+
+typedef struct IEditorHostContextVtbl {
+    long (CROSSCOM_CALL *query_interface)(IEditorHostContext *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IEditorHostContext *self);
+    long (CROSSCOM_CALL *release)(IEditorHostContext *self);
+    ISceneManager *(CROSSCOM_CALL *scene_manager)(IEditorHostContext *self);
+    IAudioService *(CROSSCOM_CALL *audio)(IEditorHostContext *self);
+    ITextureService *(CROSSCOM_CALL *textures)(IEditorHostContext *self);
+    IVfsService *(CROSSCOM_CALL *vfs)(IEditorHostContext *self);
+    IInputService *(CROSSCOM_CALL *input)(IEditorHostContext *self);
+    IGameRegistry *(CROSSCOM_CALL *games)(IEditorHostContext *self);
+    IAppService *(CROSSCOM_CALL *app)(IEditorHostContext *self);
+    IRandomService *(CROSSCOM_CALL *random)(IEditorHostContext *self);
+    IConfigService *(CROSSCOM_CALL *config)(IEditorHostContext *self);
+    IPreviewerHub *(CROSSCOM_CALL *previewers)(IEditorHostContext *self);
+    IRenderTarget *(CROSSCOM_CALL *new_render_target)(IEditorHostContext *self, int w, int h);
+    void (CROSSCOM_CALL *render_pending_previews)(IEditorHostContext *self);
+} IEditorHostContextVtbl;
+
+struct IEditorHostContext {
+    const IEditorHostContextVtbl *vtbl;
+};
+
+/* da12dcb1-4f74-4f51-9bff-1d5d3d3e1c0d */
+static const CrosscomUuid IID_IEditorHostContext = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x0d}};
+
+/* Class EditorHostContext: IEditorHostContext */
+static const CrosscomUuid CLSID_EditorHostContext = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x0e}};
+
+/* Class StubDirector: IDirector */
+static const CrosscomUuid CLSID_StubDirector = {{0xda, 0x12, 0xdc, 0xb1, 0x4f, 0x74, 0x4f, 0x51, 0x9b, 0xff, 0x1d, 0x5d, 0x3d, 0x3e, 0x1c, 0x18}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_YAOBOW_EDITOR_SERVICES_H */
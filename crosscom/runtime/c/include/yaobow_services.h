@@ -0,0 +1,103 @@
+/* Auto-generated from IDL by crosscom-ccidl. Do not edit. */
+/* Module: yaobow::comdef::yaobow_services */
+
+#ifndef CROSSCOM_YAOBOW_SERVICES_H
+#define CROSSCOM_YAOBOW_SERVICES_H
+
+#ifndef CROSSCOM_C_COMMON
+#define CROSSCOM_C_COMMON
+
+/* Matches Rust's `extern "system"`. */
+#if defined(_WIN32)
+#define CROSSCOM_CALL __stdcall
+#else
+#define CROSSCOM_CALL
+#endif
+
+/* Same bytes as `uuid::Uuid::as_bytes`; passed by value. */
+typedef struct CrosscomUuid {
+    unsigned char bytes[16];
+} CrosscomUuid;
+
+#endif /* CROSSCOM_C_COMMON */
+
+#include "crosscom.h"
+#include "radiance.h"
+#include "scripting_services.h"
+#include "pal4_debug.h"
+#include "openpal3.h"
+#include "openpal4.h"
+#include "openpal5.h"
+#include "openswd5.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct IYaobowHostContext IYaobowHostContext;
+typedef struct IYaobowScriptApp IYaobowScriptApp;
+
+/* Interface IYaobowHostContext */
+
+typedef struct IYaobowHostContextVtbl {
+    long (CROSSCOM_CALL *query_interface)(IYaobowHostContext *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IYaobowHostContext *self);
+    long (CROSSCOM_CALL *release)(IYaobowHostContext *self);
+    ISceneManager *(CROSSCOM_CALL *scene_manager)(IYaobowHostContext *self);
+    IAudioService *(CROSSCOM_CALL *audio)(IYaobowHostContext *self);
+    ITextureService *(CROSSCOM_CALL *textures)(IYaobowHostContext *self);
+    IVfsService *(CROSSCOM_CALL *vfs)(IYaobowHostContext *self);
+    IInputService *(CROSSCOM_CALL *input)(IYaobowHostContext *self);
+    IGameRegistry *(CROSSCOM_CALL *games)(IYaobowHostContext *self);
+    IAppService *(CROSSCOM_CALL *app)(IYaobowHostContext *self);
+    IRandomService *(CROSSCOM_CALL *random)(IYaobowHostContext *self);
+    IConfigService *(CROSSCOM_CALL *config)(IYaobowHostContext *self);
+    IPal3Service *(CROSSCOM_CALL *pal3)(IYaobowHostContext *self);
+    IPal4Service *(CROSSCOM_CALL *pal4)(IYaobowHostContext *self);
+    IPal5Service *(CROSSCOM_CALL *pal5)(IYaobowHostContext *self);
+    ISwd5Service *(CROSSCOM_CALL *swd5)(IYaobowHostContext *self);
+} IYaobowHostContextVtbl;
+
+struct IYaobowHostContext {
+    const IYaobowHostContextVtbl *vtbl;
+};
+
+/* f5a0434b-8f1e-411b-9392-073e3db56785 */
+static const CrosscomUuid IID_IYaobowHostContext = {{0xf5, 0xa0, 0x43, 0x4b, 0x8f, 0x1e, 0x41, 0x1b, 0x93, 0x92, 0x07, 0x3e, 0x3d, 0xb5, 0x67, 0x85}};
+
+/* Class YaobowHostContext: IYaobowHostContext */
+static const CrosscomUuid CLSID_YaobowHostContext = {{0xbf, 0x7e, 0x30, 0xe9, 0xe6, 0xb6, 0x40, 0x02, 0xb4, 0xd8, 0xce, 0x2e, 0x22, 0xd5, 0x2c, 0x1b}};
+
+/* Class Pal3Service: IPal3Service */
+static const CrosscomUuid CLSID_Pal3Service = {{0x8e, 0x9d, 0x29, 0xc2, 0xe6, 0x0b, 0x4a, 0x03, 0xb8, 0x16, 0x27, 0xf1, 0xf6, 0xa0, 0x2d, 0x63}};
+
+/* Class Pal5Service: IPal5Service */
+static const CrosscomUuid CLSID_Pal5Service = {{0x23, 0xce, 0xc3, 0x75, 0x5a, 0xd3, 0x42, 0xe9, 0x8f, 0x0e, 0x71, 0x47, 0xc5, 0x32, 0x98, 0x04}};
+
+/* Interface IYaobowScriptApp */
+
+typedef struct IYaobowScriptAppVtbl {
+    long (CROSSCOM_CALL *query_interface)(IYaobowScriptApp *self, CrosscomUuid guid, IUnknown **retval);
+    long (CROSSCOM_CALL *add_ref)(IYaobowScriptApp *self);
+    long (CROSSCOM_CALL *release)(IYaobowScriptApp *self);
+    IDirector *(CROSSCOM_CALL *make_title_director)(IYaobowScriptApp *self);
+} IYaobowScriptAppVtbl;
+
+struct IYaobowScriptApp {
+    const IYaobowScriptAppVtbl *vtbl;
+};
+
+/* 31f1b8d2-9b50-4c0e-9bd3-1c3d2e3f4a04 */
+static const CrosscomUuid IID_IYaobowScriptApp = {{0x31, 0xf1, 0xb8, 0xd2, 0x9b, 0x50, 0x4c, 0x0e, 0x9b, 0xd3, 0x1c, 0x3d, 0x2e, 0x3f, 0x4a, 0x04}};
+
+/* Class YaobowScriptApp: IYaobowScriptApp */
+static const CrosscomUuid CLSID_YaobowScriptApp = {{0x31, 0xf1, 0xb8, 0xd2, 0x9b, 0x50, 0x4c, 0x0e, 0x9b, 0xd3, 0x1c, 0x3d, 0x2e, 0x3f, 0x4a, 0x05}};
+
+/* Class YaobowAppContext: IHostContext */
+static const CrosscomUuid CLSID_YaobowAppContext = {{0x31, 0xf1, 0xb8, 0xd2, 0x9b, 0x50, 0x4c, 0x0e, 0x9b, 0xd3, 0x1c, 0x3d, 0x2e, 0x3f, 0x4a, 0x06}};
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CROSSCOM_YAOBOW_SERVICES_H */
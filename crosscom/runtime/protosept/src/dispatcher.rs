@@ -11,6 +11,18 @@
 //!   marshal a C-ABI virtual call against the COM vtable.
 //! - `com.release` — the finalizer, called when an owned `box<I>` is
 //!   collected. Releases the strong reference held by `ComObjectTable`.
+//! - `com.downgrade` / `com.upgrade` / `com.drop_weak` — weak
+//!   references. Scripts declare them per interface, e.g.
+//!
+//!   ```text
+//!   @intrinsic(name="com.downgrade")
+//!   fn downgrade_scene(s: box<radiance.IScene>) -> int;
+//!   @intrinsic(name="com.upgrade")
+//!   fn upgrade_scene(w: int) -> ?box<radiance.IScene>;
+//!   ```
+//!
+//!   The weak id is a plain `int` that does not keep the object alive;
+//!   `com.upgrade` yields `null` once the object has been destroyed.
 //!
 //! No per-IDL Rust code is generated. Adding a new crosscom IDL only
 //! requires running `crosscom-ccidl --protosept` to produce its `.p7`
@@ -60,6 +72,9 @@ use p7::semantic::HostReturnTy;
 pub fn install_com_dispatcher(ctx: &mut Context) {
     ctx.register_host_function("com.invoke".to_string(), com_invoke);
     ctx.register_host_function("com.release".to_string(), com_release);
+    ctx.register_host_function("com.downgrade".to_string(), com_downgrade);
+    ctx.register_host_function("com.upgrade".to_string(), com_upgrade);
+    ctx.register_host_function("com.drop_weak".to_string(), com_drop_weak);
     crate::proto_ccw::register_crosscom_iaction();
 }
 
@@ -214,6 +229,74 @@ fn com_release(ctx: &mut Context) -> Result<(), RuntimeError> {
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// com.downgrade / com.upgrade / com.drop_weak
+// ---------------------------------------------------------------------------
+
+/// `fn(b: box<I>) -> int`: weak id for the object behind `b`.
+fn com_downgrade(ctx: &mut Context) -> Result<(), RuntimeError> {
+    let data = ctx
+        .stack_frame_mut()?
+        .stack
+        .pop()
+        .ok_or(RuntimeError::StackUnderflow)?;
+    let (box_idx, generation) = match data {
+        Data::BoxRef { idx, generation } => (idx, generation),
+        Data::ProtoBoxRef {
+            box_idx,
+            generation,
+            ..
+        } => (box_idx, generation),
+        other => {
+            return Err(RuntimeError::Other(format!(
+                "com.downgrade: expected a foreign box, got {:?}",
+                other
+            )));
+        }
+    };
+    let Data::Foreign {
+        type_tag, handle, ..
+    } = ctx.box_heap.get(box_idx, generation)?.clone()
+    else {
+        return Err(RuntimeError::Other(
+            "com.downgrade: box did not contain a Foreign value".into(),
+        ));
+    };
+    let weak_id = with_services(|s| s.com_table_mut().downgrade(handle, &type_tag))
+        .map_err(|e| RuntimeError::Other(format!("com.downgrade: with_services: {}", e)))?
+        .ok_or_else(|| {
+            RuntimeError::Other(format!(
+                "com.downgrade: invalid COM object handle {}",
+                handle
+            ))
+        })?;
+    ctx.stack_frame_mut()?.stack.push(Data::Int(weak_id));
+    Ok(())
+}
+
+/// `fn(w: int) -> ?box<I>`: a new strong box, or `null` once the
+/// object is gone. Unknown or dropped weak ids also yield `null`.
+fn com_upgrade(ctx: &mut Context) -> Result<(), RuntimeError> {
+    let weak_id = pop_int(ctx, "com.upgrade: weak id")?;
+    let upgraded = with_services(|s| s.com_table_mut().upgrade(weak_id))
+        .map_err(|e| RuntimeError::Other(format!("com.upgrade: with_services: {}", e)))?;
+    match upgraded {
+        Some((handle, type_tag)) => ctx.push_foreign_optional(&type_tag, Some(handle)),
+        None => {
+            ctx.stack_frame_mut()?.stack.push(Data::Null);
+            Ok(())
+        }
+    }
+}
+
+/// `fn(w: int)`: forget a weak id.
+fn com_drop_weak(ctx: &mut Context) -> Result<(), RuntimeError> {
+    let weak_id = pop_int(ctx, "com.drop_weak: weak id")?;
+    with_services(|s| s.com_table_mut().drop_weak(weak_id))
+        .map_err(|e| RuntimeError::Other(format!("com.drop_weak: with_services: {}", e)))?;
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Argument classification
 // ---------------------------------------------------------------------------
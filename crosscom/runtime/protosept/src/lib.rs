@@ -13,9 +13,10 @@
 //!   reach the active services bundle without taking on a static dep on the
 //!   protosept workspace.
 //! - The generic, AST-free `@foreign` proto dispatcher
-//!   ([`install_com_dispatcher`]) that wires `com.invoke` and `com.release`
-//!   onto a freshly-created [`p7::interpreter::context::Context`]. Adding a
-//!   new crosscom IDL only requires running
+//!   ([`install_com_dispatcher`]) that wires `com.invoke`, `com.release`
+//!   and the weak-reference intrinsics onto a freshly-created
+//!   [`p7::interpreter::context::Context`]. Adding a new crosscom IDL
+//!   only requires running
 //!   `crosscom-ccidl --protosept` to produce its `.p7` source — no per-IDL
 //!   Rust code is generated.
 //! - A default adapter ([`P7HostContext`]) that implements [`HostContext`]
@@ -30,7 +31,7 @@ use std::collections::HashMap;
 use std::rc::{Rc, Weak};
 
 pub use crosscom;
-use crosscom::{ComInterface, ComRc, IUnknown};
+use crosscom::{ComInterface, ComRc, ComWeak, IUnknown};
 
 pub mod adapter;
 pub mod dispatcher;
@@ -39,8 +40,8 @@ pub mod proto_ccw;
 pub use adapter::{MinimalServices, P7HostContext};
 pub use dispatcher::install_com_dispatcher;
 pub use proto_ccw::{
-    ArgKind, MethodSpec, ProtoSpec, RetKind, is_proto_registered, register_crosscom_iaction,
-    register_proto_ccw, wrap_proto, wrap_proto_unknown,
+    ArgKind, MethodSpec, ProtoSpec, RetKind, is_proto_registered, proto_ccw_data,
+    register_crosscom_iaction, register_proto_ccw, wrap_proto, wrap_proto_unknown,
 };
 
 // ---------------------------------------------------------------------------
@@ -84,6 +85,8 @@ impl std::error::Error for HostError {}
 pub struct ComObjectTable {
     slots: Vec<Slot>,
     free: Vec<usize>,
+    weak_slots: Vec<WeakSlot>,
+    weak_free: Vec<usize>,
 }
 
 struct Slot {
@@ -103,6 +106,16 @@ struct Slot {
     refs: u32,
 }
 
+/// A script-held weak reference ([`ComObjectTable::downgrade`]). Ids
+/// share the [`ComObjectId`] encoding but live in their own slot space.
+struct WeakSlot {
+    weak: Option<ComWeak<IUnknown>>,
+    /// Foreign type tag of the box that was downgraded, so
+    /// `com.upgrade` can hand back a box of the same type.
+    type_tag: String,
+    generation: u32,
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
 pub struct ComObjectId(u64);
 
@@ -137,6 +150,8 @@ impl ComObjectTable {
         Self {
             slots: Vec::new(),
             free: Vec::new(),
+            weak_slots: Vec::new(),
+            weak_free: Vec::new(),
         }
     }
 
@@ -242,6 +257,71 @@ impl ComObjectTable {
     pub fn live(&self) -> usize {
         self.slots.iter().filter(|s| s.rc.is_some()).count()
     }
+
+    /// Create a weak reference to the object behind `id` and return
+    /// its encoded weak id. The weak id does not keep the object alive;
+    /// `type_tag` is remembered for [`Self::upgrade`]. `None` if `id`
+    /// is invalid.
+    pub fn downgrade(&mut self, id: i64, type_tag: &str) -> Option<i64> {
+        let (slot, generation) = ComObjectId::decode(id)?;
+        let s = self.slots.get(slot)?;
+        if s.generation != generation {
+            return None;
+        }
+        let weak = s.rc.as_ref()?.downgrade();
+        if let Some(idx) = self.weak_free.pop() {
+            let w = &mut self.weak_slots[idx];
+            w.weak = Some(weak);
+            w.type_tag = type_tag.to_string();
+            return Some(ComObjectId::encode(idx, w.generation));
+        }
+        let idx = self.weak_slots.len();
+        self.weak_slots.push(WeakSlot {
+            weak: Some(weak),
+            type_tag: type_tag.to_string(),
+            generation: 0,
+        });
+        Some(ComObjectId::encode(idx, 0))
+    }
+
+    /// Intern a fresh strong handle for the object behind `weak_id`.
+    /// Returns the handle and the remembered type tag, or `None` once
+    /// the object is gone or the weak id is invalid. The new handle
+    /// needs its own [`Self::release`].
+    pub fn upgrade(&mut self, weak_id: i64) -> Option<(i64, String)> {
+        let (slot, generation) = ComObjectId::decode(weak_id)?;
+        let w = self.weak_slots.get(slot)?;
+        if w.generation != generation {
+            return None;
+        }
+        let rc = w.weak.as_ref()?.upgrade()?;
+        let type_tag = w.type_tag.clone();
+        Some((self.intern_unknown(rc), type_tag))
+    }
+
+    /// Forget `weak_id`. Returns `false` if it was already invalid.
+    pub fn drop_weak(&mut self, weak_id: i64) -> bool {
+        let Some((slot, generation)) = ComObjectId::decode(weak_id) else {
+            return false;
+        };
+        let Some(w) = self.weak_slots.get_mut(slot) else {
+            return false;
+        };
+        if w.generation != generation || w.weak.is_none() {
+            return false;
+        }
+        w.weak = None;
+        w.type_tag.clear();
+        w.generation = w.generation.wrapping_add(1);
+        self.weak_free.push(slot);
+        true
+    }
+
+    /// Number of weak ids not yet dropped, dead targets included (for
+    /// diagnostics).
+    pub fn live_weak(&self) -> usize {
+        self.weak_slots.iter().filter(|w| w.weak.is_some()).count()
+    }
     /// Look up `id`, then `query_interface` to the runtime-specified UUID.
     /// Returns the raw COM pointer (`this`) that the host dispatcher can
     /// use to read the vtable. `None` when the id is invalid or the held
@@ -350,6 +430,15 @@ pub trait HostServices: Any {
 /// transparently see the same context.
 pub trait RuntimeAccess: Any {
     fn with_ctx(&self, body: &mut dyn FnMut(&mut p7::interpreter::context::Context));
+
+    /// Identifies the interpreter instance currently behind
+    /// [`with_ctx`](Self::with_ctx). Runtimes that can discard and
+    /// rebuild their interpreter (`ScriptHost::reload`) bump it on
+    /// every rebuild so CCWs rooted in the old instance can tell their
+    /// root index no longer means anything.
+    fn generation(&self) -> u64 {
+        0
+    }
 }
 
 /// Weak, cheaply-cloneable handle to a [`RuntimeAccess`]. Reverse-wrap
@@ -387,6 +476,12 @@ impl RuntimeHandle {
         self.weak.upgrade().is_none()
     }
 
+    /// Current [`RuntimeAccess::generation`] of the runtime, or `None`
+    /// if the underlying `Rc` is gone.
+    pub fn generation(&self) -> Option<u64> {
+        self.weak.upgrade().map(|rc| rc.generation())
+    }
+
     /// Attempt to re-enter the runtime. Returns `None` if the
     /// underlying `Rc` is gone.
     pub fn try_with_ctx<R>(
@@ -576,4 +671,13 @@ mod tests {
         assert!(!t.release(-1));
         assert_eq!(t.live(), 0);
     }
+
+    #[test]
+    fn weak_ids_of_invalid_handles_are_rejected() {
+        let mut t = ComObjectTable::new();
+        assert!(t.downgrade(ComObjectId::encode(0, 0), "a.IFoo").is_none());
+        assert!(t.upgrade(ComObjectId::encode(0, 0)).is_none());
+        assert!(!t.drop_weak(-1));
+        assert_eq!(t.live_weak(), 0);
+    }
 }
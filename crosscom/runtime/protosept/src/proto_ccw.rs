@@ -208,6 +208,34 @@ pub fn wrap_proto_unknown(
     Ok(unsafe { ComRc::<IUnknown>::from_raw_pointer(raw) })
 }
 
+/// Inverse of [`wrap_proto`]: the script-side value a reverse-wrapped
+/// `ComRc` dispatches to. `None` when `rc` is not a proto CCW (a Rust
+/// `ComObject`, say), when its runtime is gone, or when it was rooted
+/// in an interpreter that has since been rebuilt.
+pub fn proto_ccw_data<I: ComInterface>(rc: &ComRc<I>) -> Option<Data> {
+    let this = rc.ptr_value() as *const *const c_void;
+    if this.is_null() {
+        return None;
+    }
+    unsafe {
+        // Every proto CCW slot shares `proto_query_interface`, which is
+        // how a foreign vtable is told apart before trusting the fat
+        // layout behind it.
+        let query_interface = *(*this as *const *const c_void);
+        if query_interface != proto_query_interface as *const c_void {
+            return None;
+        }
+        let payload = &recover_header(this).payload;
+        if !payload.is_current() {
+            return None;
+        }
+        payload
+            .handle
+            .try_with_ctx(|ctx| ctx.external_root(payload.root_idx))
+            .flatten()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Registry internals
 // ---------------------------------------------------------------------------
@@ -425,11 +453,23 @@ struct CcwHeader {
 struct ProtoCcwPayload {
     root_idx: usize,
     handle: RuntimeHandle,
+    /// [`RuntimeHandle::generation`] at wrap time. `root_idx` only
+    /// indexes the interpreter of that generation.
+    generation: u64,
     /// Per-slot interface metadata, in declaration order. `slots[k]`
     /// describes the interface backing slot K of the CCW.
     slots: Box<[SlotInfo]>,
 }
 
+impl ProtoCcwPayload {
+    /// False once the runtime rebuilt its interpreter (or went away):
+    /// the root this CCW holds died with the old instance and must be
+    /// neither dispatched to nor unrooted.
+    fn is_current(&self) -> bool {
+        self.handle.generation() == Some(self.generation)
+    }
+}
+
 struct SlotInfo {
     uuid: [u8; 16],
     additional_query_uuids: &'static [[u8; 16]],
@@ -544,6 +584,7 @@ unsafe extern "system" fn proto_release(this: *const *const c_void) -> c_long {
         let header_addr = recover_header_addr(this);
         let prev = (*header_addr).ref_count.fetch_sub(1, Ordering::SeqCst);
         if prev == 1 {
+            crosscom::on_final_release(recover_slot0_addr(this) as *const c_void);
             // Drop the CCW: read out num_slots + payload, unroot the
             // script handle, drop the payload (handle + slots Box), and
             // dealloc the entire buffer with the same Layout used at
@@ -555,9 +596,11 @@ unsafe extern "system" fn proto_release(this: *const *const c_void) -> c_long {
             // the header survives past this point — ref_count just hit
             // zero.
             let payload = std::ptr::read(&(*header_addr).payload);
-            let _ = payload
-                .handle
-                .try_with_ctx(|ctx| ctx.remove_external_root(payload.root_idx));
+            if payload.is_current() {
+                let _ = payload
+                    .handle
+                    .try_with_ctx(|ctx| ctx.remove_external_root(payload.root_idx));
+            }
             drop(payload);
             dealloc(header_addr as *mut u8, layout);
         }
@@ -698,6 +741,7 @@ fn wrap_proto_raw(
             unsafe { dealloc(alloc_ptr, layout) };
             HostError::message("wrap_proto: runtime gone before rooting")
         })?;
+    let generation = handle.generation().unwrap_or_default();
 
     // Build the per-slot metadata Box before writing into the
     // buffer; that way a panic during allocation surfaces as a
@@ -722,6 +766,7 @@ fn wrap_proto_raw(
                 payload: ProtoCcwPayload {
                     root_idx,
                     handle: handle.clone(),
+                    generation,
                     slots: slots_box,
                 },
             },
@@ -736,6 +781,15 @@ fn wrap_proto_raw(
             );
         }
 
+        // Slot 0 is the object's IUnknown identity; only the slot
+        // array is ours to scan, the script data lives in the heap.
+        crosscom::leak_tracker::track(
+            slot_array_base as *const c_void,
+            "script proto",
+            plans[0].uuid,
+            num_slots * size_of::<*const c_void>(),
+        );
+
         Ok(ccw_slot_ptr(slot_array_base, selected_slot))
     }
 }
@@ -920,6 +974,11 @@ unsafe fn dispatch_method(
         // installs `scope` + `scope_context` for the duration of the
         // closure, which is exactly what marshalling and the subsequent
         // `push_proto_method`/`resume` both need.
+        if !header.payload.is_current() {
+            return DispatchOutcome::Error(HostError::message(
+                "CCW outlived a script reload; its script object is gone",
+            ));
+        }
         let root_idx = header.payload.root_idx;
         let method_name = userdata.method_name.clone();
         let ret_kind = userdata.ret.clone();
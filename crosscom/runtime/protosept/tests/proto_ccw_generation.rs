@@ -0,0 +1,169 @@
+//! CCWs against a runtime that rebuilds its interpreter.
+//!
+//! `ScriptHost::reload` swaps the `Context` behind a live
+//! `RuntimeHandle` and bumps `RuntimeAccess::generation`. A CCW rooted
+//! before the swap must stop resolving its script value
+//! (`proto_ccw_data`) and must not unroot whatever now sits at its old
+//! root index in the fresh interpreter.
+
+use std::cell::{Cell, UnsafeCell};
+use std::ffi::c_void;
+use std::os::raw::c_int;
+use std::rc::Rc;
+
+use crosscom::{ComInterface, ComRc, IUnknownVirtualTable};
+use crosscom_protosept::{
+    MethodSpec, ProtoSpec, RetKind, RuntimeAccess, RuntimeHandle, proto_ccw_data,
+    register_proto_ccw, wrap_proto,
+};
+use p7::interpreter::context::{Context, Data};
+
+struct ReloadableRuntime {
+    ctx: UnsafeCell<Context>,
+    generation: Cell<u64>,
+}
+
+impl ReloadableRuntime {
+    fn new(ctx: Context) -> Rc<Self> {
+        Rc::new(Self {
+            ctx: UnsafeCell::new(ctx),
+            generation: Cell::new(0),
+        })
+    }
+
+    fn replace_ctx(&self, ctx: Context) {
+        unsafe { *self.ctx.get() = ctx };
+        self.generation.set(self.generation.get() + 1);
+    }
+
+    fn with_ctx_mut<R>(&self, body: impl FnOnce(&mut Context) -> R) -> R {
+        unsafe { body(&mut *self.ctx.get()) }
+    }
+}
+
+impl RuntimeAccess for ReloadableRuntime {
+    fn with_ctx(&self, body: &mut dyn FnMut(&mut Context)) {
+        let ctx = unsafe { &mut *self.ctx.get() };
+        crosscom_protosept::scope_context(ctx, || {
+            let _ = crosscom_protosept::with_context(|c| {
+                body(c);
+            });
+        });
+    }
+
+    fn generation(&self) -> u64 {
+        self.generation.get()
+    }
+}
+
+const IVALUE_UUID: [u8; 16] = [
+    0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55,
+];
+
+#[repr(C)]
+struct IValueVtbl {
+    iunk: IUnknownVirtualTable,
+    get: unsafe extern "system" fn(this: *const *const c_void) -> c_int,
+}
+
+#[repr(C)]
+struct IValueInst {
+    vtable: *const IValueVtbl,
+}
+
+impl ComInterface for IValueInst {
+    const INTERFACE_ID: [u8; 16] = IVALUE_UUID;
+}
+
+const VALUE_SCRIPT: &str = r#"
+@foreign(dispatcher="com.invoke", finalizer="com.release",
+         type_tag="test.IValue",
+         uuid="55555555-5555-5555-5555-555555555555")
+pub proto IValue {
+    fn get(self: ref<IValue>) -> int;
+}
+
+struct[IValue] Value(
+    value: int,
+) {
+    pub fn get(self: ref<Self>) -> int { self.value }
+}
+
+pub fn make_value(value: int) -> box<IValue> {
+    let v = box(Value(value));
+    v as box<IValue>
+}
+"#;
+
+fn register_value() {
+    let _ = register_proto_ccw(ProtoSpec {
+        uuid: IVALUE_UUID,
+        type_tag: "test.IValue".into(),
+        methods: vec![MethodSpec {
+            name: "get".into(),
+            args: vec![],
+            ret: RetKind::Int,
+        }],
+        additional_query_uuids: vec![],
+    });
+}
+
+fn make_value(value: i64) -> (Rc<ReloadableRuntime>, Data) {
+    let mut ctx = Context::new();
+    ctx.load_module(p7::compile(VALUE_SCRIPT.to_string()).expect("compile"));
+    ctx.push_function("make_value", vec![Data::Int(value)]);
+    ctx.resume().expect("make_value ran");
+    let data = ctx.stack[0].stack.pop().expect("returned box");
+    (ReloadableRuntime::new(ctx), data)
+}
+
+fn invoke_get(value: &IValueInst) -> c_int {
+    unsafe {
+        let this = value as *const IValueInst as *const *const c_void;
+        ((*value.vtable).get)(this)
+    }
+}
+
+#[test]
+fn proto_ccw_data_recovers_the_wrapped_script_value() {
+    register_value();
+    let (runtime, data) = make_value(11);
+    let handle = RuntimeHandle::from_rc(&runtime);
+
+    let value: ComRc<IValueInst> = wrap_proto(&handle, data).expect("wrap_proto IValue");
+    assert_eq!(invoke_get(&value), 11);
+
+    let recovered = proto_ccw_data(&value).expect("proto CCW exposes its script value");
+    assert!(
+        matches!(recovered, Data::ProtoBoxRef { .. } | Data::BoxRef { .. }),
+        "recovered value is the wrapped box, got {:?}",
+        recovered
+    );
+}
+
+#[test]
+fn ccw_from_an_older_generation_is_inert() {
+    register_value();
+    let (runtime, data) = make_value(3);
+    let handle = RuntimeHandle::from_rc(&runtime);
+    let value: ComRc<IValueInst> = wrap_proto(&handle, data).expect("wrap_proto IValue");
+    assert!(proto_ccw_data(&value).is_some());
+
+    // Rebuild the interpreter and occupy the root index the CCW used.
+    runtime.replace_ctx(Context::new());
+    let idx = runtime.with_ctx_mut(|ctx| ctx.add_external_root(Data::Int(42)));
+    assert_eq!(handle.generation(), Some(1));
+
+    assert!(
+        proto_ccw_data(&value).is_none(),
+        "stale CCW must not resolve against the new interpreter"
+    );
+
+    drop(value);
+    runtime.with_ctx_mut(|ctx| {
+        assert!(
+            ctx.external_root(idx).is_some(),
+            "releasing a stale CCW must leave the new interpreter's roots alone"
+        );
+    });
+}
@@ -0,0 +1,166 @@
+//! End-to-end test for the `com.downgrade` / `com.upgrade` weak
+//! reference intrinsics.
+//!
+//! A script creates a counter, keeps only a weak id to it and lets the
+//! box go. The id upgrades while the box is still uncollected and
+//! yields `null` once the GC has released the object.
+
+use std::ffi::c_void;
+use std::os::raw::c_long;
+
+use crosscom::{ComInterface, IUnknown, IUnknownVirtualTable};
+use crosscom_protosept::{ComObjectTable, HostServices, install_com_dispatcher, scope};
+use p7::interpreter::context::{Context, Data};
+
+const COUNTER_UUID_BYTES: [u8; 16] = [
+    0xde, 0xad, 0xbe, 0xef, 0xca, 0xfe, 0xba, 0xbe, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+];
+
+#[repr(C)]
+struct ICounterVtbl {
+    iunk: IUnknownVirtualTable,
+    add: unsafe extern "system" fn(this: *const *const c_void, delta: c_long) -> c_long,
+}
+
+unsafe extern "system" fn fake_qi(
+    this: *const c_void,
+    guid: uuid::Uuid,
+    retval: &mut *const *const c_void,
+) -> c_long {
+    let bytes = *guid.as_bytes();
+    if bytes == IUnknown::INTERFACE_ID || bytes == COUNTER_UUID_BYTES {
+        *retval = this as *const *const c_void;
+        unsafe { fake_addref(*retval) };
+        0
+    } else {
+        *retval = std::ptr::null();
+        -1
+    }
+}
+
+unsafe extern "system" fn fake_addref(this: *const *const c_void) -> c_long {
+    let header = unsafe { &mut *(this as *mut FakeCounter) };
+    header.refcount += 1;
+    header.refcount as c_long
+}
+
+unsafe extern "system" fn fake_release(this: *const *const c_void) -> c_long {
+    let header = unsafe { &mut *(this as *mut FakeCounter) };
+    header.refcount -= 1;
+    let remaining = header.refcount as c_long;
+    if remaining == 0 {
+        crosscom::on_final_release(this as *const c_void);
+        drop(unsafe { Box::from_raw(this as *mut FakeCounter) });
+    }
+    remaining
+}
+
+unsafe extern "system" fn fake_add(this: *const *const c_void, delta: c_long) -> c_long {
+    let header = unsafe { &mut *(this as *mut FakeCounter) };
+    let prev = header.value;
+    header.value += delta;
+    prev
+}
+
+#[repr(C)]
+struct FakeCounter {
+    vtable: *const ICounterVtbl,
+    refcount: usize,
+    value: c_long,
+}
+
+static COUNTER_VTBL: ICounterVtbl = ICounterVtbl {
+    iunk: IUnknownVirtualTable {
+        query_interface: fake_qi,
+        add_ref: fake_addref,
+        release: fake_release,
+    },
+    add: fake_add,
+};
+
+struct TestServices {
+    com: ComObjectTable,
+}
+
+impl HostServices for TestServices {
+    fn com_table_mut(&mut self) -> &mut ComObjectTable {
+        &mut self.com
+    }
+}
+
+const SOURCE: &str = r#"
+@foreign(dispatcher="com.invoke", finalizer="com.release",
+         type_tag="test.ICounter",
+         uuid="deadbeef-cafe-babe-0000-000000000002")
+pub proto ICounter {
+    fn add(self: ref<ICounter>, delta: int) -> int;
+}
+
+@intrinsic(name="test.make_counter")
+pub fn make_counter() -> box<ICounter>;
+
+@intrinsic(name="com.downgrade")
+pub fn downgrade_counter(c: box<ICounter>) -> int;
+
+@intrinsic(name="com.upgrade")
+pub fn upgrade_counter(w: int) -> ?box<ICounter>;
+
+pub fn make_weak() -> int {
+    let c: box<ICounter> = make_counter();
+    let _x: int = c.add(3);
+    downgrade_counter(c)
+}
+
+pub fn poke(w: int) -> int {
+    let c = upgrade_counter(w);
+    if c != null {
+        return c!.add(1);
+    }
+    -1
+}
+"#;
+
+fn host_make_counter(ctx: &mut Context) -> Result<(), p7::errors::RuntimeError> {
+    let counter = Box::new(FakeCounter {
+        vtable: &COUNTER_VTBL as *const ICounterVtbl,
+        refcount: 1,
+        value: 0,
+    });
+    let raw = Box::into_raw(counter) as *const *const c_void;
+    let unk = unsafe { crosscom::ComRc::<IUnknown>::from_raw_pointer(raw) };
+    let handle = crosscom_protosept::with_services(|s| s.com_table_mut().intern_unknown(unk))
+        .map_err(|e| p7::errors::RuntimeError::Other(format!("with_services: {}", e)))?;
+    ctx.push_foreign("test.ICounter", handle)
+}
+
+fn call_int(ctx: &mut Context, name: &str, args: Vec<Data>) -> i64 {
+    ctx.push_function(name, args);
+    ctx.resume().expect(name);
+    match ctx.stack[0].stack.pop().expect("result") {
+        Data::Int(i) => i,
+        other => panic!("{}: expected int, got {:?}", name, other),
+    }
+}
+
+#[test]
+fn weak_id_upgrades_until_the_object_is_released() {
+    let module = p7::compile(SOURCE.to_string()).expect("compile");
+    let mut ctx = Context::new();
+    install_com_dispatcher(&mut ctx);
+    ctx.register_host_function("test.make_counter".to_string(), host_make_counter);
+
+    ctx.load_module(module);
+
+    let mut services = TestServices {
+        com: ComObjectTable::new(),
+    };
+    scope(&mut services, || {
+        let weak = call_int(&mut ctx, "make_weak", Vec::new());
+        assert_eq!(call_int(&mut ctx, "poke", vec![Data::Int(weak)]), 3);
+
+        ctx.collect_garbage().expect("collect");
+        assert_eq!(call_int(&mut ctx, "poke", vec![Data::Int(weak)]), -1);
+    });
+    assert_eq!(services.com.live(), 0);
+    assert_eq!(services.com.live_weak(), 1);
+}
@@ -94,6 +94,30 @@ pub fn get(&self, index: std::os::raw::c_int) -> crosscom::ComRc<crosscom::IUnkn
         use crosscom::ComInterface;
         uuid::Uuid::from_bytes(IObjectArray::INTERFACE_ID)
     }
+
+    #[doc(hidden)]
+    pub fn reflect_invoke(
+        this: &crosscom::ComRc<crosscom::IUnknown>,
+        method: usize,
+        args: &[crosscom::reflect::Variant],
+    ) -> Result<crosscom::reflect::Variant, crosscom::reflect::InvokeError> {
+        let this = this
+            .query_interface::<IObjectArray>()
+            .ok_or(crosscom::reflect::InvokeError::NoInterface(IObjectArray::uuid()))?;
+        match method {
+            0 => {
+                crosscom::reflect::check_arity(args, 0)?;
+                Ok(crosscom::reflect::Variant::from(this.len()))
+            }
+            1 => {
+                crosscom::reflect::check_arity(args, 1)?;
+                Ok(crosscom::reflect::Variant::from(this.get(args[0].to_int()?)))
+            }
+            _ => Err(crosscom::reflect::InvokeError::NotInvokable {
+                name: format!("IObjectArray method #{method}"),
+            }),
+        }
+    }
 }
 pub trait IObjectArrayImpl {
     fn len(&self, ) -> std::os::raw::c_int;
@@ -103,6 +127,17 @@ pub trait IObjectArrayImpl {
 impl crosscom::ComInterface for IObjectArray {
     // 928e03ea-0017-4741-80f9-c70a93b16702
     const INTERFACE_ID: [u8; 16] = [146u8,142u8,3u8,234u8,0u8,23u8,71u8,65u8,128u8,249u8,199u8,10u8,147u8,177u8,103u8,2u8];
+
+    const REFLECTION: Option<&'static crosscom::reflect::InterfaceDesc> = Some(&crosscom::reflect::InterfaceDesc {
+        name: "IObjectArray",
+        module: "crosscom",
+        iid: Self::INTERFACE_ID,
+        base: Some(crosscom::reflect::InterfaceRef { name: "IUnknown", iid: <crosscom::IUnknown as crosscom::ComInterface>::INTERFACE_ID }),
+        methods: &[crosscom::reflect::MethodDesc { name: "len", slot: 3, params: &[], ret: crosscom::reflect::TypeDesc::Int, internal: false, property: false },
+crosscom::reflect::MethodDesc { name: "get", slot: 4, params: &[crosscom::reflect::ParamDesc { name: "index", ty: crosscom::reflect::TypeDesc::Int, out: false },], ret: crosscom::reflect::TypeDesc::Interface(crosscom::reflect::InterfaceRef { name: "IUnknown", iid: <crosscom::IUnknown as crosscom::ComInterface>::INTERFACE_ID }), internal: false, property: false },
+],
+        invoke: IObjectArray::reflect_invoke,
+    });
 }
 
 
@@ -170,6 +205,7 @@ mod ObjectArray_crosscom_impl {
 
         let previous = (*object).ref_count.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
         if previous - 1 == 0 {
+            crosscom::on_final_release(object as *const std::os::raw::c_void);
             let _ = Box::from_raw(object as *mut ObjectArrayCcw);
         }
 
@@ -211,6 +247,8 @@ pub const GLOBAL_IObjectArrayVirtualTable_CCW_FOR_ObjectArray: crosscom::IObject
 
     impl crosscom::ComObject for $impl_type {
         type CcwType = ObjectArrayCcw;
+        const CLASS_NAME: &'static str = "ObjectArray";
+        const CLASS_ID: [u8; 16] = [176u8,211u8,3u8,186u8,145u8,143u8,68u8,130u8,143u8,191u8,74u8,126u8,138u8,189u8,77u8,53u8];
 
         fn create_ccw(self) -> Self::CcwType {
             Self::CcwType {
@@ -326,6 +364,27 @@ pub fn invoke(&self, ) -> () {
         use crosscom::ComInterface;
         uuid::Uuid::from_bytes(IAction::INTERFACE_ID)
     }
+
+    #[doc(hidden)]
+    pub fn reflect_invoke(
+        this: &crosscom::ComRc<crosscom::IUnknown>,
+        method: usize,
+        args: &[crosscom::reflect::Variant],
+    ) -> Result<crosscom::reflect::Variant, crosscom::reflect::InvokeError> {
+        let this = this
+            .query_interface::<IAction>()
+            .ok_or(crosscom::reflect::InvokeError::NoInterface(IAction::uuid()))?;
+        match method {
+            0 => {
+                crosscom::reflect::check_arity(args, 0)?;
+                this.invoke();
+                Ok(crosscom::reflect::Variant::Void)
+            }
+            _ => Err(crosscom::reflect::InvokeError::NotInvokable {
+                name: format!("IAction method #{method}"),
+            }),
+        }
+    }
 }
 pub trait IActionImpl {
     fn invoke(&self, ) -> ();
@@ -334,6 +393,16 @@ pub trait IActionImpl {
 impl crosscom::ComInterface for IAction {
     // 5a8b1d3f-7a26-4d4b-9c41-1e9b4f0a0c01
     const INTERFACE_ID: [u8; 16] = [90u8,139u8,29u8,63u8,122u8,38u8,77u8,75u8,156u8,65u8,30u8,155u8,79u8,10u8,12u8,1u8];
+
+    const REFLECTION: Option<&'static crosscom::reflect::InterfaceDesc> = Some(&crosscom::reflect::InterfaceDesc {
+        name: "IAction",
+        module: "crosscom",
+        iid: Self::INTERFACE_ID,
+        base: Some(crosscom::reflect::InterfaceRef { name: "IUnknown", iid: <crosscom::IUnknown as crosscom::ComInterface>::INTERFACE_ID }),
+        methods: &[crosscom::reflect::MethodDesc { name: "invoke", slot: 3, params: &[], ret: crosscom::reflect::TypeDesc::Void, internal: false, property: false },
+],
+        invoke: IAction::reflect_invoke,
+    });
 }
 
 
@@ -401,6 +470,7 @@ mod Action_crosscom_impl {
 
         let previous = (*object).ref_count.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
         if previous - 1 == 0 {
+            crosscom::on_final_release(object as *const std::os::raw::c_void);
             let _ = Box::from_raw(object as *mut ActionCcw);
         }
 
@@ -433,6 +503,8 @@ pub const GLOBAL_IActionVirtualTable_CCW_FOR_Action: crosscom::IActionVirtualTab
 
     impl crosscom::ComObject for $impl_type {
         type CcwType = ActionCcw;
+        const CLASS_NAME: &'static str = "Action";
+        const CLASS_ID: [u8; 16] = [90u8,139u8,29u8,63u8,122u8,38u8,77u8,75u8,156u8,65u8,30u8,155u8,79u8,10u8,12u8,2u8];
 
         fn create_ccw(self) -> Self::CcwType {
             Self::CcwType {
@@ -548,6 +620,27 @@ pub fn invoke(&self, value: std::os::raw::c_int) -> () {
         use crosscom::ComInterface;
         uuid::Uuid::from_bytes(IIntAction::INTERFACE_ID)
     }
+
+    #[doc(hidden)]
+    pub fn reflect_invoke(
+        this: &crosscom::ComRc<crosscom::IUnknown>,
+        method: usize,
+        args: &[crosscom::reflect::Variant],
+    ) -> Result<crosscom::reflect::Variant, crosscom::reflect::InvokeError> {
+        let this = this
+            .query_interface::<IIntAction>()
+            .ok_or(crosscom::reflect::InvokeError::NoInterface(IIntAction::uuid()))?;
+        match method {
+            0 => {
+                crosscom::reflect::check_arity(args, 1)?;
+                this.invoke(args[0].to_int()?);
+                Ok(crosscom::reflect::Variant::Void)
+            }
+            _ => Err(crosscom::reflect::InvokeError::NotInvokable {
+                name: format!("IIntAction method #{method}"),
+            }),
+        }
+    }
 }
 pub trait IIntActionImpl {
     fn invoke(&self, value: std::os::raw::c_int) -> ();
@@ -556,6 +649,16 @@ pub trait IIntActionImpl {
 impl crosscom::ComInterface for IIntAction {
     // 5a8b1d3f-7a26-4d4b-9c41-1e9b4f0a0c03
     const INTERFACE_ID: [u8; 16] = [90u8,139u8,29u8,63u8,122u8,38u8,77u8,75u8,156u8,65u8,30u8,155u8,79u8,10u8,12u8,3u8];
+
+    const REFLECTION: Option<&'static crosscom::reflect::InterfaceDesc> = Some(&crosscom::reflect::InterfaceDesc {
+        name: "IIntAction",
+        module: "crosscom",
+        iid: Self::INTERFACE_ID,
+        base: Some(crosscom::reflect::InterfaceRef { name: "IUnknown", iid: <crosscom::IUnknown as crosscom::ComInterface>::INTERFACE_ID }),
+        methods: &[crosscom::reflect::MethodDesc { name: "invoke", slot: 3, params: &[crosscom::reflect::ParamDesc { name: "value", ty: crosscom::reflect::TypeDesc::Int, out: false },], ret: crosscom::reflect::TypeDesc::Void, internal: false, property: false },
+],
+        invoke: IIntAction::reflect_invoke,
+    });
 }
 
 
@@ -623,6 +726,7 @@ mod IntAction_crosscom_impl {
 
         let previous = (*object).ref_count.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
         if previous - 1 == 0 {
+            crosscom::on_final_release(object as *const std::os::raw::c_void);
             let _ = Box::from_raw(object as *mut IntActionCcw);
         }
 
@@ -656,6 +760,8 @@ pub const GLOBAL_IIntActionVirtualTable_CCW_FOR_IntAction: crosscom::IIntActionV
 
     impl crosscom::ComObject for $impl_type {
         type CcwType = IntActionCcw;
+        const CLASS_NAME: &'static str = "IntAction";
+        const CLASS_ID: [u8; 16] = [90u8,139u8,29u8,63u8,122u8,38u8,77u8,75u8,156u8,65u8,30u8,155u8,79u8,10u8,12u8,4u8];
 
         fn create_ccw(self) -> Self::CcwType {
             Self::CcwType {
@@ -771,6 +877,27 @@ pub fn invoke(&self, value: f32) -> () {
         use crosscom::ComInterface;
         uuid::Uuid::from_bytes(IFloatAction::INTERFACE_ID)
     }
+
+    #[doc(hidden)]
+    pub fn reflect_invoke(
+        this: &crosscom::ComRc<crosscom::IUnknown>,
+        method: usize,
+        args: &[crosscom::reflect::Variant],
+    ) -> Result<crosscom::reflect::Variant, crosscom::reflect::InvokeError> {
+        let this = this
+            .query_interface::<IFloatAction>()
+            .ok_or(crosscom::reflect::InvokeError::NoInterface(IFloatAction::uuid()))?;
+        match method {
+            0 => {
+                crosscom::reflect::check_arity(args, 1)?;
+                this.invoke(args[0].to_float()?);
+                Ok(crosscom::reflect::Variant::Void)
+            }
+            _ => Err(crosscom::reflect::InvokeError::NotInvokable {
+                name: format!("IFloatAction method #{method}"),
+            }),
+        }
+    }
 }
 pub trait IFloatActionImpl {
     fn invoke(&self, value: f32) -> ();
@@ -779,6 +906,16 @@ pub trait IFloatActionImpl {
 impl crosscom::ComInterface for IFloatAction {
     // 5a8b1d3f-7a26-4d4b-9c41-1e9b4f0a0c05
     const INTERFACE_ID: [u8; 16] = [90u8,139u8,29u8,63u8,122u8,38u8,77u8,75u8,156u8,65u8,30u8,155u8,79u8,10u8,12u8,5u8];
+
+    const REFLECTION: Option<&'static crosscom::reflect::InterfaceDesc> = Some(&crosscom::reflect::InterfaceDesc {
+        name: "IFloatAction",
+        module: "crosscom",
+        iid: Self::INTERFACE_ID,
+        base: Some(crosscom::reflect::InterfaceRef { name: "IUnknown", iid: <crosscom::IUnknown as crosscom::ComInterface>::INTERFACE_ID }),
+        methods: &[crosscom::reflect::MethodDesc { name: "invoke", slot: 3, params: &[crosscom::reflect::ParamDesc { name: "value", ty: crosscom::reflect::TypeDesc::Float, out: false },], ret: crosscom::reflect::TypeDesc::Void, internal: false, property: false },
+],
+        invoke: IFloatAction::reflect_invoke,
+    });
 }
 
 
@@ -846,6 +983,7 @@ mod FloatAction_crosscom_impl {
 
         let previous = (*object).ref_count.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
         if previous - 1 == 0 {
+            crosscom::on_final_release(object as *const std::os::raw::c_void);
             let _ = Box::from_raw(object as *mut FloatActionCcw);
         }
 
@@ -879,6 +1017,8 @@ pub const GLOBAL_IFloatActionVirtualTable_CCW_FOR_FloatAction: crosscom::IFloatA
 
     impl crosscom::ComObject for $impl_type {
         type CcwType = FloatActionCcw;
+        const CLASS_NAME: &'static str = "FloatAction";
+        const CLASS_ID: [u8; 16] = [90u8,139u8,29u8,63u8,122u8,38u8,77u8,75u8,156u8,65u8,30u8,155u8,79u8,10u8,12u8,6u8];
 
         fn create_ccw(self) -> Self::CcwType {
             Self::CcwType {
@@ -994,6 +1134,27 @@ pub fn invoke(&self, value: &str) -> () {
         use crosscom::ComInterface;
         uuid::Uuid::from_bytes(IStrAction::INTERFACE_ID)
     }
+
+    #[doc(hidden)]
+    pub fn reflect_invoke(
+        this: &crosscom::ComRc<crosscom::IUnknown>,
+        method: usize,
+        args: &[crosscom::reflect::Variant],
+    ) -> Result<crosscom::reflect::Variant, crosscom::reflect::InvokeError> {
+        let this = this
+            .query_interface::<IStrAction>()
+            .ok_or(crosscom::reflect::InvokeError::NoInterface(IStrAction::uuid()))?;
+        match method {
+            0 => {
+                crosscom::reflect::check_arity(args, 1)?;
+                this.invoke(args[0].to_str()?);
+                Ok(crosscom::reflect::Variant::Void)
+            }
+            _ => Err(crosscom::reflect::InvokeError::NotInvokable {
+                name: format!("IStrAction method #{method}"),
+            }),
+        }
+    }
 }
 pub trait IStrActionImpl {
     fn invoke(&self, value: &str) -> ();
@@ -1002,6 +1163,16 @@ pub trait IStrActionImpl {
 impl crosscom::ComInterface for IStrAction {
     // 5a8b1d3f-7a26-4d4b-9c41-1e9b4f0a0c07
     const INTERFACE_ID: [u8; 16] = [90u8,139u8,29u8,63u8,122u8,38u8,77u8,75u8,156u8,65u8,30u8,155u8,79u8,10u8,12u8,7u8];
+
+    const REFLECTION: Option<&'static crosscom::reflect::InterfaceDesc> = Some(&crosscom::reflect::InterfaceDesc {
+        name: "IStrAction",
+        module: "crosscom",
+        iid: Self::INTERFACE_ID,
+        base: Some(crosscom::reflect::InterfaceRef { name: "IUnknown", iid: <crosscom::IUnknown as crosscom::ComInterface>::INTERFACE_ID }),
+        methods: &[crosscom::reflect::MethodDesc { name: "invoke", slot: 3, params: &[crosscom::reflect::ParamDesc { name: "value", ty: crosscom::reflect::TypeDesc::Str, out: false },], ret: crosscom::reflect::TypeDesc::Void, internal: false, property: false },
+],
+        invoke: IStrAction::reflect_invoke,
+    });
 }
 
 
@@ -1069,6 +1240,7 @@ mod StrAction_crosscom_impl {
 
         let previous = (*object).ref_count.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
         if previous - 1 == 0 {
+            crosscom::on_final_release(object as *const std::os::raw::c_void);
             let _ = Box::from_raw(object as *mut StrActionCcw);
         }
 
@@ -1102,6 +1274,8 @@ pub const GLOBAL_IStrActionVirtualTable_CCW_FOR_StrAction: crosscom::IStrActionV
 
     impl crosscom::ComObject for $impl_type {
         type CcwType = StrActionCcw;
+        const CLASS_NAME: &'static str = "StrAction";
+        const CLASS_ID: [u8; 16] = [90u8,139u8,29u8,63u8,122u8,38u8,77u8,75u8,156u8,65u8,30u8,155u8,79u8,10u8,12u8,8u8];
 
         fn create_ccw(self) -> Self::CcwType {
             Self::CcwType {
@@ -1133,3 +1307,13 @@ IStrAction: unsafe { crosscom::IStrAction::from_raw_vtable(
 }
 
 // pub use ComObject_StrAction;
+
+/// Register this module's interfaces with `crosscom::reflect`.
+#[allow(dead_code)]
+pub fn register_reflection() {
+    crosscom::reflect::register::<IObjectArray>();
+    crosscom::reflect::register::<IAction>();
+    crosscom::reflect::register::<IIntAction>();
+    crosscom::reflect::register::<IFloatAction>();
+    crosscom::reflect::register::<IStrAction>();
+}
@@ -0,0 +1,355 @@
+//! Debug-build registry of live crosscom objects.
+//!
+//! Every object created through [`ComRc::from_object`](crate::ComRc)
+//! is recorded with its class name, class UUID and CCW size until its
+//! final `release`. In release builds ([`ENABLED`] is false) tracking
+//! compiles to nothing and every query comes back empty.
+//!
+//! Besides per-class counts, [`suspected_cycles`] looks for reference
+//! cycles among the live objects. crosscom objects do not enumerate
+//! what they hold, so edges are found conservatively: each live CCW's
+//! own memory is scanned for pointers into another live CCW. That sees
+//! `ComRc` fields stored inline in the object (including inside
+//! `RefCell` / `Option`) but not ones behind a heap allocation such as
+//! a `Vec`; a cycle through a collection shows up as an object that is
+//! alive with no live owner instead.
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::sync::{Mutex, OnceLock};
+
+use uuid::Uuid;
+
+use crate::IUnknownVirtualTable;
+
+pub const ENABLED: bool = cfg!(debug_assertions);
+
+struct TrackedObject {
+    class_name: &'static str,
+    class_id: [u8; 16],
+    size: usize,
+    seq: u64,
+}
+
+#[derive(Default)]
+struct Tracker {
+    objects: HashMap<usize, TrackedObject>,
+    next_seq: u64,
+}
+
+fn tracker() -> &'static Mutex<Tracker> {
+    static TRACKER: OnceLock<Mutex<Tracker>> = OnceLock::new();
+    TRACKER.get_or_init(|| Mutex::new(Tracker::default()))
+}
+
+/// Record a new object. `identity` is the pointer it answers
+/// `IUnknown` with and `size` the byte size of its CCW allocation
+/// starting there. `ComRc::from_object` does this for generated CCWs;
+/// hand-written CCWs call it themselves and report their destruction
+/// through [`crate::on_final_release`].
+pub fn track(identity: *const c_void, class_name: &'static str, class_id: [u8; 16], size: usize) {
+    if !ENABLED {
+        return;
+    }
+    let mut tracker = tracker().lock().unwrap();
+    let seq = tracker.next_seq;
+    tracker.next_seq += 1;
+    tracker.objects.insert(
+        identity as usize,
+        TrackedObject {
+            class_name,
+            class_id,
+            size,
+            seq,
+        },
+    );
+}
+
+pub(crate) fn untrack(identity: *const c_void) {
+    if !ENABLED {
+        return;
+    }
+    tracker()
+        .lock()
+        .unwrap()
+        .objects
+        .remove(&(identity as usize));
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassCount {
+    pub class_id: Uuid,
+    pub class_name: &'static str,
+    pub live: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct LiveObject {
+    pub identity: usize,
+    pub class_id: Uuid,
+    pub class_name: &'static str,
+    pub strong_count: u32,
+}
+
+/// A strongly connected group of live objects that reference each
+/// other through inline fields.
+#[derive(Clone, Debug)]
+pub struct SuspectedCycle {
+    pub members: Vec<LiveObject>,
+    /// Every strong reference to every member comes from inside the
+    /// group: nothing else can ever release it.
+    pub unreachable: bool,
+}
+
+/// Marks a point in time; see [`live_since`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeakCheckpoint(u64);
+
+pub fn checkpoint() -> LeakCheckpoint {
+    LeakCheckpoint(tracker().lock().unwrap().next_seq)
+}
+
+/// Live objects per class, most numerous first.
+pub fn live_counts() -> Vec<ClassCount> {
+    counts_where(|_| true)
+}
+
+/// Live objects created after `checkpoint`, per class. Taken right
+/// before a level is built and read after it is torn down, anything
+/// listed here outlived it.
+pub fn live_since(checkpoint: LeakCheckpoint) -> Vec<ClassCount> {
+    counts_where(|object| object.seq >= checkpoint.0)
+}
+
+fn counts_where(filter: impl Fn(&TrackedObject) -> bool) -> Vec<ClassCount> {
+    let tracker = tracker().lock().unwrap();
+    let mut counts: HashMap<([u8; 16], &'static str), usize> = HashMap::new();
+    for object in tracker.objects.values().filter(|o| filter(o)) {
+        *counts
+            .entry((object.class_id, object.class_name))
+            .or_default() += 1;
+    }
+    let mut counts: Vec<ClassCount> = counts
+        .into_iter()
+        .map(|((class_id, class_name), live)| ClassCount {
+            class_id: Uuid::from_bytes(class_id),
+            class_name,
+            live,
+        })
+        .collect();
+    counts.sort_by(|a, b| b.live.cmp(&a.live).then(a.class_name.cmp(b.class_name)));
+    counts
+}
+
+pub fn live_total() -> usize {
+    tracker().lock().unwrap().objects.len()
+}
+
+/// Reference cycles among the live objects; see the module docs for
+/// what the scan can and cannot see.
+pub fn suspected_cycles() -> Vec<SuspectedCycle> {
+    // Snapshot first: probing refcounts calls into the objects.
+    let mut objects: Vec<(usize, usize, &'static str, [u8; 16])> = {
+        let tracker = tracker().lock().unwrap();
+        tracker
+            .objects
+            .iter()
+            .map(|(identity, o)| (*identity, o.size, o.class_name, o.class_id))
+            .collect()
+    };
+    objects.sort_by_key(|o| o.0);
+
+    // edges[i] lists j once per inline reference from i to j.
+    let edges: Vec<Vec<usize>> = objects
+        .iter()
+        .enumerate()
+        .map(|(i, &(base, size, _, _))| {
+            scan_words(base, size)
+                .filter_map(|word| owner_of(&objects, word))
+                .filter(|&j| j != i)
+                .collect()
+        })
+        .collect();
+
+    strongly_connected(&edges)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|component| {
+            let members: Vec<LiveObject> = component
+                .iter()
+                .map(|&i| {
+                    let (identity, _, class_name, class_id) = objects[i];
+                    LiveObject {
+                        identity,
+                        class_id: Uuid::from_bytes(class_id),
+                        class_name,
+                        strong_count: strong_count(identity),
+                    }
+                })
+                .collect();
+            let unreachable = component.iter().zip(&members).all(|(&i, member)| {
+                let internal = component
+                    .iter()
+                    .flat_map(|&from| edges[from].iter())
+                    .filter(|&&to| to == i)
+                    .count();
+                internal as u32 >= member.strong_count
+            });
+            SuspectedCycle {
+                members,
+                unreachable,
+            }
+        })
+        .collect()
+}
+
+/// Human-readable summary of live objects and suspected cycles, for
+/// logging at shutdown. Empty when nothing is alive.
+pub fn report() -> String {
+    let counts = live_counts();
+    if counts.is_empty() {
+        return String::new();
+    }
+    let total: usize = counts.iter().map(|c| c.live).sum();
+    let mut out = format!(
+        "crosscom: {} live objects in {} classes\n",
+        total,
+        counts.len()
+    );
+    for count in &counts {
+        out.push_str(&format!(
+            "  {:>6}  {} {{{}}}\n",
+            count.live, count.class_name, count.class_id
+        ));
+    }
+    for cycle in suspected_cycles() {
+        out.push_str(&format!(
+            "crosscom: suspected cycle of {} objects{}\n",
+            cycle.members.len(),
+            if cycle.unreachable {
+                " (no outside owner)"
+            } else {
+                ""
+            }
+        ));
+        for member in &cycle.members {
+            out.push_str(&format!(
+                "    {} @{:#x} ({} strong)\n",
+                member.class_name, member.identity, member.strong_count
+            ));
+        }
+    }
+    out
+}
+
+/// Pointer-sized words of the CCW at `base`. Reads raw memory of a
+/// live allocation, padding included; only meant for diagnostics.
+fn scan_words(base: usize, size: usize) -> impl Iterator<Item = usize> {
+    let word = std::mem::size_of::<usize>();
+    (0..size / word)
+        .map(move |i| unsafe { std::ptr::read_volatile((base + i * word) as *const usize) })
+}
+
+/// Index of the object whose CCW contains `address`, if any.
+fn owner_of(objects: &[(usize, usize, &'static str, [u8; 16])], address: usize) -> Option<usize> {
+    let i = objects.partition_point(|o| o.0 <= address).checked_sub(1)?;
+    let (base, size, _, _) = objects[i];
+    (address < base + size).then_some(i)
+}
+
+fn strong_count(identity: usize) -> u32 {
+    unsafe {
+        let this = identity as *const *const c_void;
+        let vtable = *(this as *const *const IUnknownVirtualTable);
+        let after_add = ((*vtable).add_ref)(this);
+        ((*vtable).release)(this);
+        (after_add - 1) as u32
+    }
+}
+
+/// Tarjan's algorithm, iterative so deep object graphs cannot overflow
+/// the stack.
+fn strongly_connected(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+    let n = edges.len();
+    let mut index = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut next_index = 0;
+
+    for root in 0..n {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        // (node, next edge to look at)
+        let mut work = vec![(root, 0usize)];
+        while let Some(frame) = work.last_mut() {
+            let (node, edge) = *frame;
+            if edge == 0 && index[node] == UNVISITED {
+                index[node] = next_index;
+                low[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+            if let Some(&next) = edges[node].get(edge) {
+                frame.1 += 1;
+                if index[next] == UNVISITED {
+                    work.push((next, 0));
+                } else if on_stack[next] {
+                    low[node] = low[node].min(index[next]);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+            if low[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tarjan_groups_cycles_and_leaves_chains_alone() {
+        // 0 → 1 → 2 → 0, 3 → 0, 4 ↔ 5
+        let edges = vec![vec![1], vec![2], vec![0], vec![0], vec![5], vec![4]];
+        let mut components: Vec<Vec<usize>> = strongly_connected(&edges)
+            .into_iter()
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .filter(|c| c.len() > 1)
+            .collect();
+        components.sort();
+        assert_eq!(components, vec![vec![0, 1, 2], vec![4, 5]]);
+    }
+
+    #[test]
+    fn owner_lookup_respects_ccw_bounds() {
+        let objects = vec![(0x1000, 0x20, "A", [0; 16]), (0x2000, 0x10, "B", [0; 16])];
+        assert_eq!(owner_of(&objects, 0x1008), Some(0));
+        assert_eq!(owner_of(&objects, 0x1020), None);
+        assert_eq!(owner_of(&objects, 0x2000), Some(1));
+        assert_eq!(owner_of(&objects, 0x0fff), None);
+    }
+}
@@ -4,6 +4,12 @@ use uuid::Uuid;
 
 pub use memoffset::offset_of;
 
+pub mod leak_tracker;
+pub mod reflect;
+mod weak;
+
+pub use weak::{ComWeak, on_final_release};
+
 pub type Void = ();
 pub type StaticStr = &'static str;
 
@@ -28,6 +34,12 @@ impl<TComInterface: ComInterface> ComRc<TComInterface> {
     pub fn from_object<TComObject: ComObject>(obj: TComObject) -> ComRc<TComInterface> {
         let p = Box::new(TComObject::create_ccw(obj));
         let raw = Box::into_raw(p);
+        leak_tracker::track(
+            raw as *const c_void,
+            TComObject::CLASS_NAME,
+            TComObject::CLASS_ID,
+            std::mem::size_of::<TComObject::CcwType>(),
+        );
         Self::query_interface_raw(raw as *const c_void)
     }
 
@@ -168,10 +180,20 @@ impl<TComInterface: ComInterface> Drop for ComRc<TComInterface> {
 
 pub trait ComInterface {
     const INTERFACE_ID: [u8; 16];
+
+    /// Runtime type information emitted by ccidl; `None` for
+    /// hand-written interfaces. See [`reflect`].
+    const REFLECTION: Option<&'static reflect::InterfaceDesc> = None;
 }
 
 pub trait ComObject: Sized {
     type CcwType;
+
+    /// IDL class name and UUID, reported by [`leak_tracker`]. Generated
+    /// CCWs override both.
+    const CLASS_NAME: &'static str = "<unnamed>";
+    const CLASS_ID: [u8; 16] = [0; 16];
+
     fn create_ccw(self) -> Self::CcwType;
     fn get_ccw(&self) -> &Self::CcwType;
 
@@ -0,0 +1,564 @@
+//! Runtime type information and dynamic invocation.
+//!
+//! ccidl attaches an [`InterfaceDesc`] to every generated interface
+//! (`<IFoo as ComInterface>::REFLECTION`): its UUID, base, and every
+//! method in vtable order with parameter and return types. The
+//! descriptor also carries a generated invoke thunk, so a method can be
+//! called by name with [`Variant`] arguments — the equivalent of COM's
+//! `IDispatch::Invoke`, without a separate dispatch interface on every
+//! object.
+//!
+//! Descriptors only become discoverable once registered. Each
+//! generated IDL module has a `register_reflection()` that registers
+//! all of its interfaces; hosts call the ones they care about at
+//! startup. [`interfaces_of`] then finds which registered interfaces an
+//! object implements by querying for each of them.
+//!
+//! Dynamic calls support primitives, strings, `UUID`, `float?` and
+//! interface pointers (bare or `?`). Methods taking or returning arrays,
+//! `byte*`, `[out]` parameters, and `[internal(), rust()]` methods are
+//! described but not invokable.
+//!
+//! Methods tagged `[property]` in the IDL take no arguments and have no
+//! side effects; [`properties`] reads all of them, which is what
+//! generic inspectors display.
+
+use std::fmt;
+use std::sync::RwLock;
+
+use uuid::Uuid;
+
+use crate::{ComInterface, ComRc, IUnknown};
+
+/// The type of a parameter or return value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeDesc {
+    Void,
+    Bool,
+    Int,
+    Long,
+    LongLong,
+    Byte,
+    Float,
+    /// `float?`: NaN on the C ABI means "none".
+    OptionalFloat,
+    Uuid,
+    /// `&str` and `string`.
+    Str,
+    /// `byte*`.
+    Bytes,
+    Interface(InterfaceRef),
+    OptionalInterface(InterfaceRef),
+    /// `IFoo[]`.
+    Array(InterfaceRef),
+    /// A Rust type on an `[internal(), rust()]` method, spelled as in
+    /// the IDL.
+    Rust(&'static str),
+}
+
+impl TypeDesc {
+    /// Whether [`Variant`] can carry this type through a dynamic call.
+    pub fn is_dynamic(&self) -> bool {
+        !matches!(self, TypeDesc::Bytes | TypeDesc::Array(_) | TypeDesc::Rust(_))
+    }
+}
+
+impl fmt::Display for TypeDesc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeDesc::Void => write!(f, "void"),
+            TypeDesc::Bool => write!(f, "bool"),
+            TypeDesc::Int => write!(f, "int"),
+            TypeDesc::Long => write!(f, "long"),
+            TypeDesc::LongLong => write!(f, "longlong"),
+            TypeDesc::Byte => write!(f, "byte"),
+            TypeDesc::Float => write!(f, "float"),
+            TypeDesc::OptionalFloat => write!(f, "float?"),
+            TypeDesc::Uuid => write!(f, "UUID"),
+            TypeDesc::Str => write!(f, "string"),
+            TypeDesc::Bytes => write!(f, "byte*"),
+            TypeDesc::Interface(interface) => write!(f, "{}", interface.name),
+            TypeDesc::OptionalInterface(interface) => write!(f, "{}?", interface.name),
+            TypeDesc::Array(interface) => write!(f, "{}[]", interface.name),
+            TypeDesc::Rust(ty) => write!(f, "{ty}"),
+        }
+    }
+}
+
+/// A reference to an interface from a type position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterfaceRef {
+    pub name: &'static str,
+    pub iid: [u8; 16],
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParamDesc {
+    pub name: &'static str,
+    pub ty: TypeDesc,
+    /// `[out]` parameter.
+    pub out: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MethodDesc {
+    pub name: &'static str,
+    /// Index into the vtable, counting `IUnknown`'s three methods.
+    pub slot: usize,
+    pub params: &'static [ParamDesc],
+    pub ret: TypeDesc,
+    /// `[internal(), rust()]`: Rust-only signature.
+    pub internal: bool,
+    /// `[property]`: a side-effect-free getter.
+    pub property: bool,
+}
+
+impl MethodDesc {
+    pub fn is_invokable(&self) -> bool {
+        !self.internal
+            && self.ret.is_dynamic()
+            && self.params.iter().all(|p| !p.out && p.ty.is_dynamic())
+    }
+}
+
+/// Generated per interface; calls `methods[method]` on `this`.
+pub type InvokeFn =
+    fn(this: &ComRc<IUnknown>, method: usize, args: &[Variant]) -> Result<Variant, InvokeError>;
+
+#[derive(Debug)]
+pub struct InterfaceDesc {
+    pub name: &'static str,
+    /// Rust module the interface is generated into.
+    pub module: &'static str,
+    pub iid: [u8; 16],
+    pub base: Option<InterfaceRef>,
+    /// Every method in vtable order, inherited ones first. `IUnknown`'s
+    /// methods are left out; `slot` still counts them.
+    pub methods: &'static [MethodDesc],
+    pub invoke: InvokeFn,
+}
+
+impl InterfaceDesc {
+    pub fn uuid(&self) -> Uuid {
+        Uuid::from_bytes(self.iid)
+    }
+
+    pub fn method(&self, name: &str) -> Option<(usize, &'static MethodDesc)> {
+        self.methods
+            .iter()
+            .enumerate()
+            .find(|(_, method)| method.name == name)
+    }
+
+    /// Call `name` on `object`, which must implement this interface.
+    pub fn invoke(
+        &self,
+        object: &ComRc<IUnknown>,
+        name: &str,
+        args: &[Variant],
+    ) -> Result<Variant, InvokeError> {
+        let (index, method) = self.method(name).ok_or_else(|| InvokeError::NoSuchMethod {
+            name: format!("{}.{name}", self.name),
+        })?;
+        if !method.is_invokable() {
+            return Err(InvokeError::NotInvokable {
+                name: format!("{}.{name}", self.name),
+            });
+        }
+        (self.invoke)(object, index, args)
+    }
+}
+
+/// A dynamically typed argument or return value.
+#[derive(Clone)]
+pub enum Variant {
+    /// Returned by `void` methods.
+    Void,
+    /// A `float?` or `IFoo?` with no value.
+    Null,
+    Bool(bool),
+    /// Every integer type.
+    Int(i64),
+    Float(f32),
+    Str(String),
+    Uuid(Uuid),
+    Object(ComRc<IUnknown>),
+}
+
+impl Variant {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Variant::Void => "void",
+            Variant::Null => "null",
+            Variant::Bool(_) => "bool",
+            Variant::Int(_) => "int",
+            Variant::Float(_) => "float",
+            Variant::Str(_) => "string",
+            Variant::Uuid(_) => "UUID",
+            Variant::Object(_) => "object",
+        }
+    }
+
+    fn mismatch(&self, expected: &'static str) -> InvokeError {
+        InvokeError::TypeMismatch {
+            expected,
+            found: self.type_name(),
+        }
+    }
+
+    pub fn to_bool(&self) -> Result<bool, InvokeError> {
+        match self {
+            Variant::Bool(value) => Ok(*value),
+            Variant::Int(value) => Ok(*value != 0),
+            other => Err(other.mismatch("bool")),
+        }
+    }
+
+    /// Integer in the range of `T`.
+    pub fn to_int<T: TryFrom<i64>>(&self) -> Result<T, InvokeError> {
+        match self {
+            Variant::Int(value) => T::try_from(*value).map_err(|_| InvokeError::OutOfRange),
+            Variant::Bool(value) => T::try_from(*value as i64).map_err(|_| InvokeError::OutOfRange),
+            other => Err(other.mismatch("int")),
+        }
+    }
+
+    pub fn to_float(&self) -> Result<f32, InvokeError> {
+        match self {
+            Variant::Float(value) => Ok(*value),
+            Variant::Int(value) => Ok(*value as f32),
+            other => Err(other.mismatch("float")),
+        }
+    }
+
+    pub fn to_optional_float(&self) -> Result<Option<f32>, InvokeError> {
+        match self {
+            Variant::Null => Ok(None),
+            other => other.to_float().map(Some),
+        }
+    }
+
+    pub fn to_str(&self) -> Result<&str, InvokeError> {
+        match self {
+            Variant::Str(value) => Ok(value),
+            other => Err(other.mismatch("string")),
+        }
+    }
+
+    pub fn to_uuid(&self) -> Result<Uuid, InvokeError> {
+        match self {
+            Variant::Uuid(value) => Ok(*value),
+            Variant::Str(value) => Uuid::parse_str(value).map_err(|_| self.mismatch("UUID")),
+            other => Err(other.mismatch("UUID")),
+        }
+    }
+
+    pub fn to_object<T: ComInterface>(&self) -> Result<ComRc<T>, InvokeError> {
+        match self {
+            Variant::Object(object) => object
+                .query_interface::<T>()
+                .ok_or(InvokeError::NoInterface(Uuid::from_bytes(T::INTERFACE_ID))),
+            other => Err(other.mismatch("object")),
+        }
+    }
+
+    pub fn to_optional_object<T: ComInterface>(&self) -> Result<Option<ComRc<T>>, InvokeError> {
+        match self {
+            Variant::Null => Ok(None),
+            other => other.to_object().map(Some),
+        }
+    }
+}
+
+impl fmt::Debug for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variant::Object(object) => write!(f, "Object({:p})", object.ptr_value()),
+            other => write!(f, "{other}"),
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variant::Void => write!(f, "void"),
+            Variant::Null => write!(f, "null"),
+            Variant::Bool(value) => write!(f, "{value}"),
+            Variant::Int(value) => write!(f, "{value}"),
+            Variant::Float(value) => write!(f, "{value}"),
+            Variant::Str(value) => write!(f, "{value:?}"),
+            Variant::Uuid(value) => write!(f, "{value}"),
+            Variant::Object(object) => {
+                let names: Vec<&str> = interfaces_of(object).iter().map(|i| i.name).collect();
+                if names.is_empty() {
+                    write!(f, "<object>")
+                } else {
+                    write!(f, "<{}>", names.join(", "))
+                }
+            }
+        }
+    }
+}
+
+impl From<()> for Variant {
+    fn from(_: ()) -> Self {
+        Variant::Void
+    }
+}
+
+impl From<bool> for Variant {
+    fn from(value: bool) -> Self {
+        Variant::Bool(value)
+    }
+}
+
+impl From<u8> for Variant {
+    fn from(value: u8) -> Self {
+        Variant::Int(value as i64)
+    }
+}
+
+impl From<i32> for Variant {
+    fn from(value: i32) -> Self {
+        Variant::Int(value as i64)
+    }
+}
+
+impl From<i64> for Variant {
+    fn from(value: i64) -> Self {
+        Variant::Int(value)
+    }
+}
+
+impl From<f32> for Variant {
+    fn from(value: f32) -> Self {
+        Variant::Float(value)
+    }
+}
+
+impl From<Option<f32>> for Variant {
+    fn from(value: Option<f32>) -> Self {
+        value.map_or(Variant::Null, Variant::Float)
+    }
+}
+
+impl From<&str> for Variant {
+    fn from(value: &str) -> Self {
+        Variant::Str(value.to_string())
+    }
+}
+
+impl From<String> for Variant {
+    fn from(value: String) -> Self {
+        Variant::Str(value)
+    }
+}
+
+impl From<Uuid> for Variant {
+    fn from(value: Uuid) -> Self {
+        Variant::Uuid(value)
+    }
+}
+
+impl<T: ComInterface> From<ComRc<T>> for Variant {
+    fn from(value: ComRc<T>) -> Self {
+        // Every interface pointer is also a valid `IUnknown` pointer;
+        // the reference moves over as is.
+        Variant::Object(unsafe { ComRc::from_raw_pointer(value.into_raw()) })
+    }
+}
+
+impl<T: ComInterface> From<Option<ComRc<T>>> for Variant {
+    fn from(value: Option<ComRc<T>>) -> Self {
+        value.map_or(Variant::Null, Variant::from)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvokeError {
+    /// The object does not implement the interface.
+    NoInterface(Uuid),
+    NoSuchMethod {
+        name: String,
+    },
+    /// The method's signature cannot be expressed with [`Variant`].
+    NotInvokable {
+        name: String,
+    },
+    WrongArgCount {
+        expected: usize,
+        found: usize,
+    },
+    TypeMismatch {
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// An integer argument does not fit the parameter type.
+    OutOfRange,
+}
+
+impl fmt::Display for InvokeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvokeError::NoInterface(iid) => write!(f, "object does not implement {iid}"),
+            InvokeError::NoSuchMethod { name } => write!(f, "no method {name}"),
+            InvokeError::NotInvokable { name } => {
+                write!(f, "{name} cannot be called dynamically")
+            }
+            InvokeError::WrongArgCount { expected, found } => {
+                write!(f, "expected {expected} argument(s), got {found}")
+            }
+            InvokeError::TypeMismatch { expected, found } => {
+                write!(f, "expected {expected}, got {found}")
+            }
+            InvokeError::OutOfRange => write!(f, "integer argument out of range"),
+        }
+    }
+}
+
+impl std::error::Error for InvokeError {}
+
+/// Used by generated invoke thunks.
+#[doc(hidden)]
+pub fn check_arity(args: &[Variant], expected: usize) -> Result<(), InvokeError> {
+    if args.len() == expected {
+        Ok(())
+    } else {
+        Err(InvokeError::WrongArgCount {
+            expected,
+            found: args.len(),
+        })
+    }
+}
+
+static REGISTRY: RwLock<Vec<&'static InterfaceDesc>> = RwLock::new(Vec::new());
+
+/// Register `T`'s descriptor. Interfaces without one (hand-written
+/// ones such as `IUnknown`) are ignored; registering twice is a no-op.
+pub fn register<T: ComInterface>() {
+    if let Some(desc) = T::REFLECTION {
+        register_desc(desc);
+    }
+}
+
+pub fn register_desc(desc: &'static InterfaceDesc) {
+    let mut registry = REGISTRY.write().unwrap();
+    if !registry.iter().any(|known| known.iid == desc.iid) {
+        registry.push(desc);
+    }
+}
+
+/// Every registered interface, in registration order.
+pub fn interfaces() -> Vec<&'static InterfaceDesc> {
+    REGISTRY.read().unwrap().clone()
+}
+
+pub fn find_interface(iid: &[u8; 16]) -> Option<&'static InterfaceDesc> {
+    REGISTRY
+        .read()
+        .unwrap()
+        .iter()
+        .copied()
+        .find(|desc| &desc.iid == iid)
+}
+
+/// Look up by bare (`IFoo`) or module-qualified
+/// (`radiance::comdef::IFoo`) name.
+pub fn find_interface_by_name(name: &str) -> Option<&'static InterfaceDesc> {
+    REGISTRY.read().unwrap().iter().copied().find(|desc| {
+        desc.name == name
+            || name
+                .strip_suffix(desc.name)
+                .and_then(|prefix| prefix.strip_suffix("::"))
+                == Some(desc.module)
+    })
+}
+
+/// The registered interfaces `object` implements, in registration
+/// order.
+pub fn interfaces_of(object: &ComRc<IUnknown>) -> Vec<&'static InterfaceDesc> {
+    interfaces()
+        .into_iter()
+        .filter(|desc| implements(object, desc))
+        .collect()
+}
+
+fn implements(object: &ComRc<IUnknown>, desc: &InterfaceDesc) -> bool {
+    // The IID is only known at runtime, so `IUnknown::query_interface`
+    // (generic over the interface type) does not apply.
+    let mut raw = std::ptr::null();
+    let hr = unsafe {
+        let this = object.ptr_value() as *const IUnknown;
+        ((*(*this).vtable).query_interface)(
+            this as *const std::ffi::c_void,
+            Uuid::from_bytes(desc.iid),
+            &mut raw,
+        )
+    };
+    if hr != 0 || raw.is_null() {
+        return false;
+    }
+    drop(unsafe { ComRc::<IUnknown>::from_raw_pointer(raw) });
+    true
+}
+
+/// Call a method by name. `method` is either `name`, resolved against
+/// every registered interface `object` implements (the first match
+/// wins), or `IFoo.name`.
+pub fn invoke(
+    object: &ComRc<IUnknown>,
+    method: &str,
+    args: &[Variant],
+) -> Result<Variant, InvokeError> {
+    if let Some((interface, name)) = method.rsplit_once('.') {
+        let desc = find_interface_by_name(interface).ok_or_else(|| InvokeError::NoSuchMethod {
+            name: method.to_string(),
+        })?;
+        if !implements(object, desc) {
+            return Err(InvokeError::NoInterface(desc.uuid()));
+        }
+        return desc.invoke(object, name, args);
+    }
+
+    for desc in interfaces_of(object) {
+        if desc.method(method).is_some() {
+            return desc.invoke(object, method, args);
+        }
+    }
+    Err(InvokeError::NoSuchMethod {
+        name: method.to_string(),
+    })
+}
+
+/// One `[property]` read by [`properties`].
+#[derive(Debug)]
+pub struct Property {
+    pub interface: &'static str,
+    pub name: &'static str,
+    pub value: Result<Variant, InvokeError>,
+}
+
+/// Read every `[property]` method of every registered interface
+/// `object` implements. Inherited properties are reported once, under
+/// the interface that declares them.
+pub fn properties(object: &ComRc<IUnknown>) -> Vec<Property> {
+    let implemented = interfaces_of(object);
+    let mut properties = Vec::new();
+    for desc in &implemented {
+        let inherited = desc
+            .base
+            .and_then(|base| find_interface(&base.iid))
+            .map_or(0, |base| base.methods.len());
+        for (index, method) in desc.methods.iter().enumerate().skip(inherited) {
+            if method.property && method.params.is_empty() && method.is_invokable() {
+                properties.push(Property {
+                    interface: desc.name,
+                    name: method.name,
+                    value: (desc.invoke)(object, index, &[]),
+                });
+            }
+        }
+    }
+    properties
+}
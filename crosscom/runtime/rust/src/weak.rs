@@ -0,0 +1,130 @@
+//! Weak references to crosscom objects.
+//!
+//! A [`ComWeak<T>`] names an object without keeping it alive. Every
+//! object that has ever been downgraded gets one shared "alive" flag in
+//! a process-wide registry keyed by its COM identity (the `IUnknown`
+//! pointer). Generated CCWs call [`on_final_release`] when their
+//! refcount reaches zero — *before* the inner value is dropped — which
+//! clears the flag, so [`ComWeak::upgrade`] fails from then on, even
+//! when called from the dying object's own `Drop`.
+//!
+//! Objects that were never downgraded only pay an atomic load in
+//! `release`.
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::{ComInterface, ComRc, IUnknown, IUnknownVirtualTable};
+
+/// Identity → alive flag for every object with outstanding weak refs.
+fn registry() -> &'static Mutex<HashMap<usize, Arc<AtomicBool>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<usize, Arc<AtomicBool>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Registry size, read without the lock on the `release` fast path.
+static WEAK_TARGETS: AtomicUsize = AtomicUsize::new(0);
+
+pub struct ComWeak<TComInterface: ComInterface> {
+    this: *const TComInterface,
+    alive: Arc<AtomicBool>,
+    /// `!Send` / `!Sync` for the same reason as [`ComRc`].
+    _not_send_sync: PhantomData<*const ()>,
+}
+
+impl<TComInterface: ComInterface> ComWeak<TComInterface> {
+    pub fn new(rc: &ComRc<TComInterface>) -> Self {
+        let identity = identity_of(rc.ptr_value());
+        let alive = {
+            let mut registry = registry().lock().unwrap();
+            let alive = registry.entry(identity).or_insert_with(|| {
+                WEAK_TARGETS.fetch_add(1, Ordering::SeqCst);
+                Arc::new(AtomicBool::new(true))
+            });
+            alive.clone()
+        };
+        Self {
+            this: rc.ptr_value() as *const TComInterface,
+            alive,
+            _not_send_sync: PhantomData,
+        }
+    }
+
+    /// A strong reference if the object is still alive.
+    pub fn upgrade(&self) -> Option<ComRc<TComInterface>> {
+        if !self.is_alive() {
+            return None;
+        }
+        unsafe {
+            let vtable = *(self.this as *const *const IUnknownVirtualTable);
+            ((*vtable).add_ref)(self.this as *const *const c_void);
+            Some(ComRc::from_raw_pointer(self.this as *const *const c_void))
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::SeqCst)
+    }
+
+    /// Whether `self` and `rc` point at the same interface of the same
+    /// object. Meaningful only while `self` is alive.
+    pub fn ptr_eq(&self, rc: &ComRc<TComInterface>) -> bool {
+        self.this as *const c_void == rc.ptr_value()
+    }
+}
+
+impl<TComInterface: ComInterface> Clone for ComWeak<TComInterface> {
+    fn clone(&self) -> Self {
+        Self {
+            this: self.this,
+            alive: self.alive.clone(),
+            _not_send_sync: PhantomData,
+        }
+    }
+}
+
+impl<TComInterface: ComInterface> std::fmt::Debug for ComWeak<TComInterface> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComWeak")
+            .field("this", &self.this)
+            .field("alive", &self.is_alive())
+            .finish()
+    }
+}
+
+impl<TComInterface: ComInterface> ComRc<TComInterface> {
+    pub fn downgrade(&self) -> ComWeak<TComInterface> {
+        ComWeak::new(self)
+    }
+}
+
+/// The `IUnknown` pointer of the object behind `this`. COM guarantees
+/// every interface of one object answers `IUnknown` with the same
+/// pointer; generated CCWs hand out their base address.
+fn identity_of(this: *const c_void) -> usize {
+    unsafe {
+        let unknown = (*(this as *const IUnknown))
+            .query_interface::<IUnknown>()
+            .expect("every crosscom object exposes IUnknown");
+        unknown.ptr_value() as usize
+    }
+}
+
+/// Called by a CCW's `release` once its refcount has reached zero and
+/// before its inner value is dropped. `identity` is the pointer the
+/// object answers `IUnknown` with. Generated code calls this; hand-
+/// written CCWs must too.
+pub fn on_final_release(identity: *const c_void) {
+    crate::leak_tracker::untrack(identity);
+    if WEAK_TARGETS.load(Ordering::SeqCst) == 0 {
+        return;
+    }
+    let removed = registry().lock().unwrap().remove(&(identity as usize));
+    if let Some(alive) = removed {
+        WEAK_TARGETS.fetch_sub(1, Ordering::SeqCst);
+        alive.store(false, Ordering::SeqCst);
+    }
+}
@@ -89,6 +89,26 @@ impl ITest {
         use crosscom::ComInterface;
         uuid::Uuid::from_bytes(ITest::INTERFACE_ID)
     }
+
+    #[doc(hidden)]
+    pub fn reflect_invoke(
+        this: &crosscom::ComRc<crosscom::IUnknown>,
+        method: usize,
+        args: &[crosscom::reflect::Variant],
+    ) -> Result<crosscom::reflect::Variant, crosscom::reflect::InvokeError> {
+        let this = this
+            .query_interface::<ITest>()
+            .ok_or(crosscom::reflect::InvokeError::NoInterface(ITest::uuid()))?;
+        match method {
+            0 => {
+                crosscom::reflect::check_arity(args, 0)?;
+                Ok(crosscom::reflect::Variant::from(this.test()))
+            }
+            _ => Err(crosscom::reflect::InvokeError::NotInvokable {
+                name: format!("ITest method #{method}"),
+            }),
+        }
+    }
 }
 
 pub trait ITestImpl {
@@ -101,6 +121,26 @@ impl crosscom::ComInterface for ITest {
         106u8, 196u8, 100u8, 129u8, 126u8, 250u8, 69u8, 255u8, 162u8, 121u8, 104u8, 123u8, 70u8,
         3u8, 199u8, 70u8,
     ];
+
+    const REFLECTION: Option<&'static crosscom::reflect::InterfaceDesc> =
+        Some(&crosscom::reflect::InterfaceDesc {
+            name: "ITest",
+            module: "test::crosscom_gen",
+            iid: Self::INTERFACE_ID,
+            base: Some(crosscom::reflect::InterfaceRef {
+                name: "IUnknown",
+                iid: <crosscom::IUnknown as crosscom::ComInterface>::INTERFACE_ID,
+            }),
+            methods: &[crosscom::reflect::MethodDesc {
+                name: "test",
+                slot: 3,
+                params: &[],
+                ret: crosscom::reflect::TypeDesc::Int,
+                internal: false,
+                property: false,
+            }],
+            invoke: ITest::reflect_invoke,
+        });
 }
 
 // Interface ITest2
@@ -209,6 +249,32 @@ impl ITest2 {
         use crosscom::ComInterface;
         uuid::Uuid::from_bytes(ITest2::INTERFACE_ID)
     }
+
+    #[doc(hidden)]
+    pub fn reflect_invoke(
+        this: &crosscom::ComRc<crosscom::IUnknown>,
+        method: usize,
+        args: &[crosscom::reflect::Variant],
+    ) -> Result<crosscom::reflect::Variant, crosscom::reflect::InvokeError> {
+        let this = this
+            .query_interface::<ITest2>()
+            .ok_or(crosscom::reflect::InvokeError::NoInterface(ITest2::uuid()))?;
+        match method {
+            0 => {
+                crosscom::reflect::check_arity(args, 0)?;
+                Ok(crosscom::reflect::Variant::from(this.test()))
+            }
+            1 => {
+                crosscom::reflect::check_arity(args, 2)?;
+                Ok(crosscom::reflect::Variant::from(
+                    this.mul(args[0].to_int()?, args[1].to_float()?),
+                ))
+            }
+            _ => Err(crosscom::reflect::InvokeError::NotInvokable {
+                name: format!("ITest2 method #{method}"),
+            }),
+        }
+    }
 }
 
 pub trait ITest2Impl {
@@ -221,6 +287,47 @@ impl crosscom::ComInterface for ITest2 {
         222u8, 61u8, 152u8, 157u8, 43u8, 29u8, 66u8, 163u8, 176u8, 133u8, 162u8, 62u8, 64u8, 132u8,
         1u8, 38u8,
     ];
+
+    const REFLECTION: Option<&'static crosscom::reflect::InterfaceDesc> =
+        Some(&crosscom::reflect::InterfaceDesc {
+            name: "ITest2",
+            module: "test::crosscom_gen",
+            iid: Self::INTERFACE_ID,
+            base: Some(crosscom::reflect::InterfaceRef {
+                name: "ITest",
+                iid: <ITest as crosscom::ComInterface>::INTERFACE_ID,
+            }),
+            methods: &[
+                crosscom::reflect::MethodDesc {
+                    name: "test",
+                    slot: 3,
+                    params: &[],
+                    ret: crosscom::reflect::TypeDesc::Int,
+                    internal: false,
+                    property: false,
+                },
+                crosscom::reflect::MethodDesc {
+                    name: "mul",
+                    slot: 4,
+                    params: &[
+                        crosscom::reflect::ParamDesc {
+                            name: "a",
+                            ty: crosscom::reflect::TypeDesc::Int,
+                            out: false,
+                        },
+                        crosscom::reflect::ParamDesc {
+                            name: "b",
+                            ty: crosscom::reflect::TypeDesc::Float,
+                            out: false,
+                        },
+                    ],
+                    ret: crosscom::reflect::TypeDesc::Float,
+                    internal: false,
+                    property: false,
+                },
+            ],
+            invoke: ITest2::reflect_invoke,
+        });
 }
 
 // Interface ITest3
@@ -316,6 +423,28 @@ impl ITest3 {
         use crosscom::ComInterface;
         uuid::Uuid::from_bytes(ITest3::INTERFACE_ID)
     }
+
+    #[doc(hidden)]
+    pub fn reflect_invoke(
+        this: &crosscom::ComRc<crosscom::IUnknown>,
+        method: usize,
+        args: &[crosscom::reflect::Variant],
+    ) -> Result<crosscom::reflect::Variant, crosscom::reflect::InvokeError> {
+        let this = this
+            .query_interface::<ITest3>()
+            .ok_or(crosscom::reflect::InvokeError::NoInterface(ITest3::uuid()))?;
+        match method {
+            0 => {
+                crosscom::reflect::check_arity(args, 1)?;
+                Ok(crosscom::reflect::Variant::from(
+                    this.echo(args[0].to_int()?),
+                ))
+            }
+            _ => Err(crosscom::reflect::InvokeError::NotInvokable {
+                name: format!("ITest3 method #{method}"),
+            }),
+        }
+    }
 }
 
 pub trait ITest3Impl {
@@ -328,6 +457,30 @@ impl crosscom::ComInterface for ITest3 {
         222u8, 61u8, 152u8, 157u8, 43u8, 29u8, 66u8, 163u8, 176u8, 133u8, 162u8, 62u8, 64u8, 132u8,
         1u8, 40u8,
     ];
+
+    const REFLECTION: Option<&'static crosscom::reflect::InterfaceDesc> =
+        Some(&crosscom::reflect::InterfaceDesc {
+            name: "ITest3",
+            module: "test::crosscom_gen",
+            iid: Self::INTERFACE_ID,
+            base: Some(crosscom::reflect::InterfaceRef {
+                name: "IUnknown",
+                iid: <crosscom::IUnknown as crosscom::ComInterface>::INTERFACE_ID,
+            }),
+            methods: &[crosscom::reflect::MethodDesc {
+                name: "echo",
+                slot: 3,
+                params: &[crosscom::reflect::ParamDesc {
+                    name: "a",
+                    ty: crosscom::reflect::TypeDesc::Int,
+                    out: false,
+                }],
+                ret: crosscom::reflect::TypeDesc::Int,
+                internal: false,
+                property: false,
+            }],
+            invoke: ITest3::reflect_invoke,
+        });
 }
 
 // Interface ITest4
@@ -422,6 +575,26 @@ impl ITest4 {
         use crosscom::ComInterface;
         uuid::Uuid::from_bytes(ITest4::INTERFACE_ID)
     }
+
+    #[doc(hidden)]
+    pub fn reflect_invoke(
+        this: &crosscom::ComRc<crosscom::IUnknown>,
+        method: usize,
+        args: &[crosscom::reflect::Variant],
+    ) -> Result<crosscom::reflect::Variant, crosscom::reflect::InvokeError> {
+        let this = this
+            .query_interface::<ITest4>()
+            .ok_or(crosscom::reflect::InvokeError::NoInterface(ITest4::uuid()))?;
+        match method {
+            0 => {
+                crosscom::reflect::check_arity(args, 0)?;
+                Ok(crosscom::reflect::Variant::from(this.get()))
+            }
+            _ => Err(crosscom::reflect::InvokeError::NotInvokable {
+                name: format!("ITest4 method #{method}"),
+            }),
+        }
+    }
 }
 
 pub trait ITest4Impl {
@@ -434,6 +607,29 @@ impl crosscom::ComInterface for ITest4 {
         222u8, 61u8, 152u8, 157u8, 43u8, 29u8, 66u8, 163u8, 176u8, 133u8, 162u8, 62u8, 64u8, 132u8,
         1u8, 41u8,
     ];
+
+    const REFLECTION: Option<&'static crosscom::reflect::InterfaceDesc> =
+        Some(&crosscom::reflect::InterfaceDesc {
+            name: "ITest4",
+            module: "test::crosscom_gen",
+            iid: Self::INTERFACE_ID,
+            base: Some(crosscom::reflect::InterfaceRef {
+                name: "IUnknown",
+                iid: <crosscom::IUnknown as crosscom::ComInterface>::INTERFACE_ID,
+            }),
+            methods: &[crosscom::reflect::MethodDesc {
+                name: "get",
+                slot: 3,
+                params: &[],
+                ret: crosscom::reflect::TypeDesc::Interface(crosscom::reflect::InterfaceRef {
+                    name: "ITest3",
+                    iid: <ITest3 as crosscom::ComInterface>::INTERFACE_ID,
+                }),
+                internal: false,
+                property: false,
+            }],
+            invoke: ITest4::reflect_invoke,
+        });
 }
 
 // Class Test
@@ -531,6 +727,7 @@ macro_rules! ComObject_Test {
                         .ref_count
                         .fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
                     if previous - 1 == 0 {
+                        crosscom::on_final_release(object as *const std::os::raw::c_void);
                         Box::from_raw(object as *mut TestCcw);
                     }
 
@@ -637,6 +834,7 @@ macro_rules! ComObject_Test {
 
             impl crosscom::ComObject for $impl_type {
                 type CcwType = TestCcw;
+                const CLASS_NAME: &'static str = "Test";
 
                 fn create_ccw(self) -> Self::CcwType {
                     Self::CcwType {
@@ -690,3 +888,12 @@ macro_rules! ComObject_Test {
 }
 
 // pub use ComObject_Test;
+
+/// Register this module's interfaces with `crosscom::reflect`.
+#[allow(dead_code)]
+pub fn register_reflection() {
+    crosscom::reflect::register::<ITest>();
+    crosscom::reflect::register::<ITest2>();
+    crosscom::reflect::register::<ITest3>();
+    crosscom::reflect::register::<ITest4>();
+}
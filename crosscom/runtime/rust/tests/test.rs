@@ -7,6 +7,8 @@ use crate::crosscom_gen::ITest4;
 use crate::crosscom_gen::ITest4Impl;
 use crate::crosscom_gen::ITestImpl;
 use crosscom::ComRc;
+use crosscom::leak_tracker;
+use crosscom::reflect::{self, InvokeError, Variant};
 
 #[macro_use]
 mod crosscom_gen;
@@ -73,3 +75,106 @@ fn test_return_obj() {
     let itest3 = com_object.get();
     assert_eq!(300, itest3.echo(300));
 }
+
+#[test]
+fn test_weak_upgrade() {
+    let com_object = ComRc::<ITest>::from_object(Test { test: None });
+    let weak = com_object.downgrade();
+    assert!(weak.is_alive());
+    assert_eq!(42, weak.upgrade().unwrap().test());
+
+    let test3 = com_object.query_interface::<ITest3>().unwrap();
+    let weak3 = test3.downgrade();
+    drop(com_object);
+    assert_eq!(7, weak3.upgrade().unwrap().echo(7));
+    assert!(weak.upgrade().is_some());
+
+    drop(test3);
+    assert!(!weak.is_alive());
+    assert!(weak.upgrade().is_none());
+    assert!(weak3.clone().upgrade().is_none());
+}
+
+#[test]
+fn test_leak_tracker_counts_live_objects() {
+    if !leak_tracker::ENABLED {
+        return;
+    }
+
+    let checkpoint = leak_tracker::checkpoint();
+    let inner = ComRc::<ITest3>::from_object(Test { test: None });
+    let outer = ComRc::<ITest4>::from_object(Test { test: Some(inner) });
+    let live = leak_tracker::live_since(checkpoint);
+    let test = live.iter().find(|c| c.class_name == "Test").unwrap();
+    assert!(test.live >= 2);
+    assert!(leak_tracker::report().contains("Test"));
+
+    // A chain is not a cycle.
+    let identity = outer
+        .query_interface::<crosscom::IUnknown>()
+        .unwrap()
+        .ptr_value() as usize;
+    assert!(
+        leak_tracker::suspected_cycles()
+            .iter()
+            .all(|cycle| cycle.members.iter().all(|m| m.identity != identity))
+    );
+}
+
+#[test]
+fn test_reflection_invoke() {
+    crosscom_gen::register_reflection();
+
+    let desc = <ITest2 as crosscom::ComInterface>::REFLECTION.unwrap();
+    assert_eq!(desc.base.unwrap().name, "ITest");
+    let names: Vec<_> = desc.methods.iter().map(|m| (m.name, m.slot)).collect();
+    assert_eq!(names, vec![("test", 3), ("mul", 4)]);
+
+    let inner = ComRc::<ITest3>::from_object(Test { test: None });
+    let object = ComRc::<ITest4>::from_object(Test { test: Some(inner) })
+        .query_interface::<crosscom::IUnknown>()
+        .unwrap();
+    let implemented: Vec<_> = reflect::interfaces_of(&object)
+        .iter()
+        .map(|i| i.name)
+        .collect();
+    assert_eq!(implemented, vec!["ITest", "ITest2", "ITest3", "ITest4"]);
+
+    let product = reflect::invoke(&object, "mul", &[Variant::Int(3), Variant::Float(2.)]);
+    assert!(matches!(product, Ok(Variant::Float(v)) if v == 6.));
+    assert!(matches!(
+        reflect::invoke(&object, "ITest2.test", &[]),
+        Ok(Variant::Int(42))
+    ));
+
+    let Ok(Variant::Object(echo)) = reflect::invoke(&object, "get", &[]) else {
+        panic!("get should return an object");
+    };
+    assert!(matches!(
+        reflect::invoke(&echo, "ITest3.echo", &[Variant::Int(5)]),
+        Ok(Variant::Int(5))
+    ));
+
+    assert_eq!(
+        reflect::invoke(&object, "mul", &[Variant::Int(3)]).unwrap_err(),
+        InvokeError::WrongArgCount {
+            expected: 2,
+            found: 1
+        }
+    );
+    assert_eq!(
+        reflect::invoke(&object, "echo", &[Variant::from("x")]).unwrap_err(),
+        InvokeError::TypeMismatch {
+            expected: "int",
+            found: "string"
+        }
+    );
+    assert_eq!(
+        reflect::invoke(&object, "echo", &[Variant::Int(i64::MAX)]).unwrap_err(),
+        InvokeError::OutOfRange
+    );
+    assert!(matches!(
+        reflect::invoke(&object, "missing", &[]),
+        Err(InvokeError::NoSuchMethod { .. })
+    ));
+}
@@ -60,6 +60,30 @@ pub struct Application {
     /// Native-mode all-zero struct for the `Application::new()` path.
     #[cfg_attr(any(linux, macos, android), allow(dead_code))]
     engine_options: crate::rendering::RenderingEngineOptions,
+    /// Declared last so it drops after the engine and every component:
+    /// whatever crosscom objects are still alive by then have leaked.
+    _leak_report: LeakReportGuard,
+}
+
+/// Env var that turns on the shutdown leak report in debug builds.
+const LEAK_REPORT_ENV: &str = "RADIANCE_LEAK_REPORT";
+
+/// Logs [`crosscom::leak_tracker::report`] on drop when built with
+/// debug assertions and `RADIANCE_LEAK_REPORT` is set.
+struct LeakReportGuard;
+
+impl Drop for LeakReportGuard {
+    fn drop(&mut self) {
+        if !crosscom::leak_tracker::ENABLED || std::env::var_os(LEAK_REPORT_ENV).is_none() {
+            return;
+        }
+        let report = crosscom::leak_tracker::report();
+        if report.is_empty() {
+            log::info!("crosscom: no live objects at shutdown");
+        } else {
+            log::warn!("{}", report);
+        }
+    }
 }
 
 ComObject_Application!(super::Application);
@@ -402,6 +426,7 @@ impl Application {
             engine_ready_callbacks,
             engine_ready,
             engine_options: options,
+            _leak_report: LeakReportGuard,
         }
     }
 
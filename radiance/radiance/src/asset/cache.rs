@@ -0,0 +1,501 @@
+//! On-disk cache for processed assets.
+//!
+//! Decoding DDS/TGA into RGBA and rebuilding terrain atlases dominates
+//! cold start, and the result only changes when the source file or the
+//! code that processed it does. [`AssetCache`] stores those results on
+//! disk keyed by a [`CacheKey`]: an xxh3-128 over the entry kind, the
+//! producing loader's version and the source bytes. Bump the loader
+//! version whenever its output changes and old entries simply stop
+//! being hit; eviction cleans them up.
+//!
+//! Entries live at `<root>/<kind>/<key>` as a small header followed by
+//! the zstd-compressed payload. Writes go through a temp file and a
+//! rename, so a crash or a concurrent reader never sees a torn entry.
+//! A hit bumps the entry's mtime; once the cache grows past its size
+//! limit the least recently used entries are removed.
+//!
+//! Loaders reach the cache through [`global`], which stays `None`
+//! until the host calls [`install`]. Every helper treats "no cache" and
+//! "cache I/O failed" the same way: decode as if the cache wasn't
+//! there.
+
+use std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Mutex, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::SystemTime,
+};
+
+use image::RgbaImage;
+use xxhash_rust::xxh3::Xxh3;
+
+const MAGIC: &[u8; 4] = b"YAC1";
+const HEADER_LEN: usize = 4 + 8 + 8;
+
+/// Fraction of the size limit eviction trims down to, so a full cache
+/// doesn't evict again on every write.
+const EVICT_TARGET_PERCENT: u64 = 90;
+
+static GLOBAL: OnceLock<AssetCache> = OnceLock::new();
+
+/// Make `cache` the process-wide cache returned by [`global`]. Only the
+/// first call takes effect.
+pub fn install(cache: AssetCache) {
+    if GLOBAL.set(cache).is_err() {
+        log::warn!("AssetCache: already installed, ignoring");
+    }
+}
+
+pub fn global() -> Option<&'static AssetCache> {
+    GLOBAL.get()
+}
+
+/// Decode `source` into an image through the global cache: on a hit
+/// the cached pixels are returned without calling `decode`; on a miss
+/// `decode`'s result is stored for next time.
+pub fn cached_image(
+    kind: &str,
+    version: u32,
+    source: &[u8],
+    decode: impl FnOnce() -> Option<RgbaImage>,
+) -> Option<RgbaImage> {
+    let Some(cache) = global() else {
+        return decode();
+    };
+
+    let key = CacheKey::new(kind, version, &[source]);
+    cache.get_or_insert_image(kind, &key, decode)
+}
+
+/// Identifies one processed asset. Built from everything that affects
+/// the processed output; two keys are equal only if all parts are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CacheKey(u128);
+
+impl CacheKey {
+    pub fn new(kind: &str, version: u32, parts: &[&[u8]]) -> Self {
+        let mut hasher = Xxh3::new();
+        hasher.update(kind.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(&version.to_le_bytes());
+        for part in parts {
+            // Length-prefixed so ["ab", "c"] and ["a", "bc"] differ.
+            hasher.update(&(part.len() as u64).to_le_bytes());
+            hasher.update(part);
+        }
+        Self(hasher.digest128())
+    }
+
+    /// Raw digest, for folding this key into a derived one.
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.0.to_le_bytes()
+    }
+
+    fn file_name(&self) -> String {
+        format!("{:032x}", self.0)
+    }
+}
+
+pub struct AssetCache {
+    root: PathBuf,
+    max_bytes: u64,
+    /// Running estimate of the bytes on disk; recounted by every
+    /// eviction pass.
+    size: AtomicU64,
+    evict_lock: Mutex<()>,
+    tmp_counter: AtomicU64,
+    compress_textures: bool,
+}
+
+impl AssetCache {
+    /// Open (or create) a cache under `root`, holding at most
+    /// `max_bytes` of entries. `0` disables the size limit.
+    pub fn new(root: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        let root = root.into();
+        if let Err(e) = fs::create_dir_all(&root) {
+            log::warn!("AssetCache: cannot create {}: {}", root.display(), e);
+        }
+
+        let cache = Self {
+            size: AtomicU64::new(0),
+            root,
+            max_bytes,
+            evict_lock: Mutex::new(()),
+            tmp_counter: AtomicU64::new(0),
+            compress_textures: false,
+        };
+        let size = cache.entries().iter().map(|e| e.len).sum();
+        cache.size.store(size, Ordering::Relaxed);
+        cache.evict_if_needed();
+        cache
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Store decoded non-DDS textures BC-compressed rather than as
+    /// RGBA; see `rendering::material::decode_texture_data`.
+    pub fn with_compressed_textures(mut self, compress: bool) -> Self {
+        self.compress_textures = compress;
+        self
+    }
+
+    pub fn compresses_textures(&self) -> bool {
+        self.compress_textures
+    }
+
+    /// Bytes currently on disk, as of the last write or eviction.
+    pub fn size(&self) -> u64 {
+        self.size.load(Ordering::Relaxed)
+    }
+
+    /// Cached payload for `key`, or `None` on a miss or a corrupt
+    /// entry (which is removed).
+    pub fn get(&self, kind: &str, key: &CacheKey) -> Option<Vec<u8>> {
+        let path = self.entry_path(kind, key);
+        let mut file = File::open(&path).ok()?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data).ok()?;
+        drop(file);
+
+        match decode_entry(&data) {
+            Some(payload) => {
+                touch(&path);
+                Some(payload)
+            }
+            None => {
+                log::warn!("AssetCache: dropping corrupt entry {}", path.display());
+                if fs::remove_file(&path).is_ok() {
+                    self.size
+                        .fetch_sub((data.len() as u64).min(self.size()), Ordering::Relaxed);
+                }
+                None
+            }
+        }
+    }
+
+    /// Store `payload` under `key`, replacing any previous entry.
+    /// Failures are logged and otherwise ignored.
+    pub fn put(&self, kind: &str, key: &CacheKey, payload: &[u8]) {
+        if let Err(e) = self.try_put(kind, key, payload) {
+            log::warn!("AssetCache: cannot write {} entry: {}", kind, e);
+        }
+    }
+
+    pub fn get_image(&self, kind: &str, key: &CacheKey) -> Option<RgbaImage> {
+        let data = self.get(kind, key)?;
+        if data.len() < 8 {
+            return None;
+        }
+        let width = u32::from_le_bytes(data[0..4].try_into().unwrap());
+        let height = u32::from_le_bytes(data[4..8].try_into().unwrap());
+        RgbaImage::from_raw(width, height, data[8..].to_vec())
+    }
+
+    pub fn put_image(&self, kind: &str, key: &CacheKey, image: &RgbaImage) {
+        let mut data = Vec::with_capacity(8 + image.as_raw().len());
+        data.extend_from_slice(&image.width().to_le_bytes());
+        data.extend_from_slice(&image.height().to_le_bytes());
+        data.extend_from_slice(image.as_raw());
+        self.put(kind, key, &data);
+    }
+
+    /// Cached image for `key`, or `decode`'s result, which is then
+    /// cached. A failed decode isn't cached.
+    pub fn get_or_insert_image(
+        &self,
+        kind: &str,
+        key: &CacheKey,
+        decode: impl FnOnce() -> Option<RgbaImage>,
+    ) -> Option<RgbaImage> {
+        if let Some(image) = self.get_image(kind, key) {
+            return Some(image);
+        }
+
+        let image = decode()?;
+        self.put_image(kind, key, &image);
+        Some(image)
+    }
+
+    /// Remove every entry.
+    pub fn clear(&self) -> io::Result<()> {
+        let _guard = self.evict_lock.lock().unwrap();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+        }
+        self.size.store(0, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn try_put(&self, kind: &str, key: &CacheKey, payload: &[u8]) -> io::Result<()> {
+        let path = self.entry_path(kind, key);
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir)?;
+
+        let data = encode_entry(payload)?;
+        let tmp = dir.join(format!(
+            ".{}.{}.{}.tmp",
+            key.file_name(),
+            std::process::id(),
+            self.tmp_counter.fetch_add(1, Ordering::Relaxed)
+        ));
+        let written = File::create(&tmp)
+            .and_then(|mut file| file.write_all(&data))
+            .and_then(|_| fs::rename(&tmp, &path));
+        if written.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        written?;
+
+        self.size.fetch_add(data.len() as u64, Ordering::Relaxed);
+        self.evict_if_needed();
+        Ok(())
+    }
+
+    fn entry_path(&self, kind: &str, key: &CacheKey) -> PathBuf {
+        self.root.join(kind).join(key.file_name())
+    }
+
+    fn evict_if_needed(&self) {
+        if self.max_bytes == 0 || self.size() <= self.max_bytes {
+            return;
+        }
+        let Ok(_guard) = self.evict_lock.try_lock() else {
+            // Another thread is already evicting.
+            return;
+        };
+
+        let mut entries = self.entries();
+        entries.sort_by_key(|e| e.modified);
+        let mut size: u64 = entries.iter().map(|e| e.len).sum();
+        let target = self.max_bytes / 100 * EVICT_TARGET_PERCENT;
+        let mut removed = 0;
+        for entry in entries {
+            if size <= target {
+                break;
+            }
+            if fs::remove_file(&entry.path).is_ok() {
+                size -= entry.len;
+                removed += 1;
+            }
+        }
+
+        log::info!(
+            "AssetCache: evicted {} entries, {} bytes remain",
+            removed,
+            size
+        );
+        self.size.store(size, Ordering::Relaxed);
+    }
+
+    /// Every entry file under the root. Temp files of in-flight
+    /// writes are skipped.
+    fn entries(&self) -> Vec<EntryInfo> {
+        let mut entries = Vec::new();
+        let Ok(kinds) = fs::read_dir(&self.root) else {
+            return entries;
+        };
+        for kind in kinds.flatten() {
+            let Ok(files) = fs::read_dir(kind.path()) else {
+                continue;
+            };
+            for file in files.flatten() {
+                if file.file_name().to_string_lossy().starts_with('.') {
+                    continue;
+                }
+                let Ok(meta) = file.metadata() else {
+                    continue;
+                };
+                entries.push(EntryInfo {
+                    path: file.path(),
+                    len: meta.len(),
+                    modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                });
+            }
+        }
+        entries
+    }
+}
+
+struct EntryInfo {
+    path: PathBuf,
+    len: u64,
+    modified: SystemTime,
+}
+
+/// `MAGIC`, payload length, xxh3-64 of the payload, then the
+/// zstd-compressed payload.
+fn encode_entry(payload: &[u8]) -> io::Result<Vec<u8>> {
+    let compressed = zstd::stream::encode_all(payload, 0)?;
+    let mut data = Vec::with_capacity(HEADER_LEN + compressed.len());
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    data.extend_from_slice(&xxhash_rust::xxh3::xxh3_64(payload).to_le_bytes());
+    data.extend_from_slice(&compressed);
+    Ok(data)
+}
+
+fn decode_entry(data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() < HEADER_LEN || &data[0..4] != MAGIC {
+        return None;
+    }
+    let len = u64::from_le_bytes(data[4..12].try_into().unwrap());
+    let hash = u64::from_le_bytes(data[12..20].try_into().unwrap());
+    let payload = zstd::stream::decode_all(&data[HEADER_LEN..]).ok()?;
+    if payload.len() as u64 != len || xxhash_rust::xxh3::xxh3_64(&payload) != hash {
+        return None;
+    }
+    Some(payload)
+}
+
+/// Mark an entry as recently used for LRU eviction.
+fn touch(path: &Path) {
+    let touched = File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+    if let Err(e) = touched {
+        log::debug!("AssetCache: cannot touch {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "radiance-asset-cache-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn key_covers_kind_version_and_parts() {
+        let key = CacheKey::new("texture", 1, &[b"abc"]);
+        assert_eq!(key, CacheKey::new("texture", 1, &[b"abc"]));
+        assert_ne!(key, CacheKey::new("texture", 2, &[b"abc"]));
+        assert_ne!(key, CacheKey::new("atlas", 1, &[b"abc"]));
+        assert_ne!(key, CacheKey::new("texture", 1, &[b"abd"]));
+        assert_ne!(
+            CacheKey::new("texture", 1, &[b"ab", b"c"]),
+            CacheKey::new("texture", 1, &[b"a", b"bc"])
+        );
+    }
+
+    #[test]
+    fn image_roundtrip_and_decode_skipped_on_hit() {
+        let root = temp_root("image");
+        let cache = AssetCache::new(&root, 0);
+        let key = CacheKey::new("texture", 1, &[b"source"]);
+        let image = RgbaImage::from_fn(3, 2, |x, y| image::Rgba([x as u8, y as u8, 7, 255]));
+
+        let first = cache.get_or_insert_image("texture", &key, || Some(image.clone()));
+        assert_eq!(first.as_ref(), Some(&image));
+        let second = cache.get_or_insert_image("texture", &key, || panic!("decoded on a hit"));
+        assert_eq!(second, Some(image));
+
+        // A fresh instance over the same root sees the entry too.
+        let reopened = AssetCache::new(&root, 0);
+        assert!(reopened.size() > 0);
+        assert!(reopened.get_image("texture", &key).is_some());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn corrupt_entry_is_a_miss() {
+        let root = temp_root("corrupt");
+        let cache = AssetCache::new(&root, 0);
+        let key = CacheKey::new("texture", 1, &[b"source"]);
+        cache.put("texture", &key, b"payload");
+        let path = cache.entry_path("texture", &key);
+        let mut data = fs::read(&path).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 0xff;
+        fs::write(&path, data).unwrap();
+
+        assert_eq!(cache.get("texture", &key), None);
+        assert!(!path.exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn eviction_drops_least_recently_used() {
+        let root = temp_root("evict");
+        let payload = |seed: u64| -> Vec<u8> {
+            // Incompressible, so the on-disk size tracks the payload.
+            let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+            (0..4096)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u8
+                })
+                .collect()
+        };
+        let keys: Vec<CacheKey> = (0..3u8)
+            .map(|i| CacheKey::new("blob", 1, &[&[i]]))
+            .collect();
+
+        let unlimited = AssetCache::new(&root, 0);
+        for (i, key) in keys.iter().enumerate() {
+            unlimited.put("blob", key, &payload(i as u64));
+        }
+        // Age the entries so their order doesn't depend on timer
+        // resolution, then use the oldest one so it becomes the newest.
+        let now = SystemTime::now();
+        for (i, key) in keys.iter().enumerate() {
+            let file = File::options()
+                .write(true)
+                .open(unlimited.entry_path("blob", key))
+                .unwrap();
+            file.set_modified(now - Duration::from_secs(100 - i as u64))
+                .unwrap();
+        }
+        assert!(unlimited.get("blob", &keys[0]).is_some());
+
+        let entry_len = unlimited.size() / 3;
+        let limited = AssetCache::new(&root, entry_len * 2 + entry_len / 2);
+        assert!(limited.get("blob", &keys[0]).is_some());
+        assert!(limited.get("blob", &keys[1]).is_none());
+        assert!(limited.get("blob", &keys[2]).is_some());
+        assert!(limited.size() <= limited.max_bytes());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn clear_removes_everything() {
+        let root = temp_root("clear");
+        let cache = AssetCache::new(&root, 0);
+        let key = CacheKey::new("texture", 1, &[b"source"]);
+        cache.put("texture", &key, b"payload");
+        cache.clear().unwrap();
+        assert_eq!(cache.size(), 0);
+        assert_eq!(cache.get("texture", &key), None);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -29,12 +29,18 @@
 //!
 //! ## What this *doesn't* do
 //!
-//! - No asset caching, no hot-reload, no type-aware decoders. Those
-//!   would belong in a future asset-pipeline pass; right now this is
-//!   just a thin facade over `mini_fs::MiniFs` + the file helpers that
-//!   used to live in `yaobow/common::store_ext`.
+//! - No in-memory asset caching, no hot-reload, no type-aware
+//!   decoders; right now this is just a thin facade over
+//!   `mini_fs::MiniFs` + the file helpers that used to live in
+//!   `yaobow/common::store_ext`. (Script hot reload
+//!   only needs a local dir overlaid on a ypk —
+//!   [`AssetManager::mount_ypk_bytes_with_local_overlay`] — and does
+//!   its own change detection.)
 //! - No `packfs::init_virtual_fs` replacement — that walker still owns
 //!   game-format mounting.
+//!
+//! Processed (decoded) assets can persist across launches in the
+//! on-disk [`cache::AssetCache`]; loaders opt in per asset type.
 
 use std::cell::{Ref, RefCell};
 use std::error::Error;
@@ -45,12 +51,15 @@ use std::rc::Rc;
 use encoding::{DecoderTrap, Encoding};
 use mini_fs::{LocalFs, MiniFs, Store, StoreExt, ZipFs};
 
+pub mod cache;
 pub mod file;
+pub mod overlay;
 pub mod seek_traits;
 pub mod ypk;
 
 pub use mini_fs::{Entries, Entry, EntryKind, File};
 
+use self::overlay::OverlayFs;
 use self::ypk::YpkFs;
 
 pub struct AssetManager {
@@ -196,6 +205,26 @@ impl AssetManager {
         Ok(())
     }
 
+    /// [`AssetManager::mount_ypk_bytes`] with the local directory
+    /// `local_path` laid over the bundle: files present on disk are
+    /// read from disk (fresh on every open), everything else from the
+    /// ypk. Script hot reload mounts each crate's `scripts/` source
+    /// dir this way so edits are visible without a rebuild.
+    pub fn mount_ypk_bytes_with_local_overlay<P, V>(
+        &self,
+        vfs_path: V,
+        bytes: &'static [u8],
+        local_path: P,
+    ) -> anyhow::Result<()>
+    where
+        P: AsRef<Path>,
+        V: Into<PathBuf>,
+    {
+        let store = OverlayFs::new(LocalFs::new(local_path.as_ref()), YpkFs::from_bytes(bytes)?);
+        self.mutate_vfs(|vfs| vfs.mount(vfs_path.into(), store));
+        Ok(())
+    }
+
     pub fn mount_store<S, T, V>(&self, vfs_path: V, store: S)
     where
         S: Store<File = T> + 'static,
@@ -500,4 +529,48 @@ mod tests {
 
         let _ = fs::remove_file(&ypk_path);
     }
+
+    #[test]
+    fn local_overlay_shadows_ypk_bytes_and_falls_back() {
+        let ypk_path = unique_tmp("overlay.ypk");
+        let _ = fs::remove_file(&ypk_path);
+        {
+            let f = fs::File::create(&ypk_path).unwrap();
+            let writer: Box<dyn SeekWrite> = Box::new(f);
+            let mut ypk = YpkWriter::new(writer).unwrap();
+            ypk.write_file("main.p7", b"packed").unwrap();
+            ypk.write_file("generated.p7", b"codegen").unwrap();
+            ypk.finish().unwrap();
+        }
+        let bytes: &'static [u8] = Box::leak(fs::read(&ypk_path).unwrap().into_boxed_slice());
+        let _ = fs::remove_file(&ypk_path);
+
+        let root = unique_tmp("overlay_root");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("main.p7"), b"edited").unwrap();
+
+        let assets = AssetManager::new();
+        assets
+            .mount_ypk_bytes_with_local_overlay("/app", bytes, &root)
+            .unwrap();
+
+        assert_eq!(assets.read_to_end("/app/main.p7").unwrap(), b"edited");
+        assert_eq!(assets.read_to_end("/app/generated.p7").unwrap(), b"codegen");
+
+        // Later edits are picked up without remounting.
+        fs::write(root.join("main.p7"), b"edited again").unwrap();
+        assert_eq!(assets.read_to_end("/app/main.p7").unwrap(), b"edited again");
+
+        let mut names: Vec<_> = assets
+            .entries("/app")
+            .unwrap()
+            .filter_map(Result::ok)
+            .map(|e| e.name.to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["generated.p7", "main.p7"]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
 }
@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+
+use mini_fs::{Entries, Entry, EntryKind, File, Store};
+
+/// Two stores mounted at one VFS path: `upper` wins on every lookup,
+/// `lower` serves whatever `upper` doesn't have. Used to lay a
+/// crate's on-disk `scripts/` dir over the ypk packed from it, so
+/// edited sources are read fresh while build-generated entries
+/// (codegen `.p7` bindings) still come from the bundle.
+pub struct OverlayFs<U, L> {
+    upper: U,
+    lower: L,
+}
+
+impl<U, L> OverlayFs<U, L> {
+    pub fn new(upper: U, lower: L) -> Self {
+        Self { upper, lower }
+    }
+}
+
+impl<U, L> Store for OverlayFs<U, L>
+where
+    U: Store,
+    L: Store,
+    U::File: Into<File>,
+    L::File: Into<File>,
+{
+    type File = File;
+
+    fn open_path(&self, path: &Path) -> io::Result<File> {
+        match self.upper.open_path(path) {
+            Ok(file) => Ok(file.into()),
+            Err(_) => self.lower.open_path(path).map(Into::into),
+        }
+    }
+
+    fn entries_path(&self, path: &Path) -> io::Result<Entries<'_>> {
+        let upper = self.upper.entries_path(path);
+        let lower = self.lower.entries_path(path);
+        if upper.is_err() && lower.is_err() {
+            return lower;
+        }
+
+        // Union of both listings; an upper entry shadows a lower one
+        // of the same name. `LocalFs` names entries relative to its
+        // root while ypk listings use bare leaves, so merge on the leaf.
+        let mut children: BTreeMap<OsString, EntryKind> = BTreeMap::new();
+        for entries in [lower, upper].into_iter().flatten() {
+            for entry in entries.flatten() {
+                let name = Path::new(&entry.name)
+                    .file_name()
+                    .map(|leaf| leaf.to_os_string())
+                    .unwrap_or_else(|| entry.name.clone());
+                children.insert(name, entry.kind);
+            }
+        }
+
+        let collected: Vec<io::Result<Entry>> = children
+            .into_iter()
+            .map(|(name, kind)| Ok(Entry { name, kind }))
+            .collect();
+        Ok(Entries::new(collected.into_iter()))
+    }
+}
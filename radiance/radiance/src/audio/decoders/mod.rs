@@ -6,6 +6,8 @@ pub use self::symphonia::SymphoniaDecoder;
 pub use ogg::OggDecoder;
 pub use wav::WavDecoder;
 
+use super::Codec;
+
 pub trait Decoder: Send + Sync {
     fn fetch_samples(&mut self) -> anyhow::Result<Option<Samples>>;
     fn reset(&mut self);
@@ -16,3 +18,14 @@ pub struct Samples {
     pub sample_rate: i32,
     pub channels: usize,
 }
+
+/// Build the stock decoder for an in-memory clip. Shared by every
+/// `AudioEngine` backend so `AudioMemorySource::set_data` decodes the
+/// same way regardless of where the samples end up.
+pub(crate) fn create_decoder(data: Vec<u8>, codec: Codec) -> Box<dyn Decoder> {
+    match codec {
+        Codec::Mp3 => Box::new(SymphoniaDecoder::new(data)),
+        Codec::Ogg => Box::new(OggDecoder::new(data)),
+        Codec::Wav => Box::new(WavDecoder::new(data)),
+    }
+}
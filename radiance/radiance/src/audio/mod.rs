@@ -1,10 +1,12 @@
 mod decoders;
 mod openal;
+mod software;
 
 pub use decoders::{Decoder, Samples};
 pub use openal::OpenAlAudioEngine;
+pub use software::{SOFTWARE_MIX_CHANNELS, SoftwareAudioEngine, SoftwareSourceInfo};
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Codec {
     Wav,
     Mp3,
@@ -19,7 +21,7 @@ pub trait AudioEngine {
     /// linear gain in `[0.0, 1.0]` (`1.0` = unattenuated full scale).
     /// The production OpenAL backend forwards this to the AL listener
     /// gain so it scales BGM, SFX, voice, and video audio uniformly;
-    /// stub / test backends leave it as a no-op. Typically called once
+    /// `SoftwareAudioEngine` scales its mixed output. Typically called once
     /// at startup from the persisted user config.
     fn set_master_volume(&self, _volume: f32) {}
 
@@ -28,15 +30,16 @@ pub trait AudioEngine {
     /// drained OpenAL streaming buffers, feeds fresh decoded samples,
     /// honours looping at EOF). Default impl is a no-op so stub /
     /// test backends don't need to be aware of it; the production
-    /// `OpenAlAudioEngine` overrides it. `CoreRadianceEngine::update`
+    /// `OpenAlAudioEngine` overrides it, and `SoftwareAudioEngine`
+    /// mixes `delta_sec` worth of output frames. `CoreRadianceEngine::update`
     /// drives this once per frame.
     fn update(&self, _delta_sec: f32) {}
 
     /// Update the global listener pose used for 3D spatialization.
     /// `position` is the listener's world position; `forward` and `up`
     /// are its orientation basis vectors (need not be normalized). The
-    /// production OpenAL backend forwards this to the AL listener;
-    /// stub / test backends leave it as a no-op. `CoreRadianceEngine`
+    /// production OpenAL backend forwards this to the AL listener and
+    /// `SoftwareAudioEngine` uses it for attenuation and panning. `CoreRadianceEngine`
     /// drives this once per frame from the active scene's camera.
     fn set_listener(&self, _position: [f32; 3], _forward: [f32; 3], _up: [f32; 3]) {}
 }
@@ -1,6 +1,6 @@
 use super::{
     AudioCustomDecoderSource, AudioMemorySource, Codec,
-    decoders::{Decoder, OggDecoder, Samples, SymphoniaDecoder, WavDecoder},
+    decoders::{Decoder, Samples, create_decoder},
 };
 use super::{AudioEngine, AudioSource, AudioSourceState};
 use alto::{Alto, AltoResult, Context, Mono, Source, Stereo};
@@ -76,9 +76,24 @@ impl AudioEngine for OpenAlAudioEngine {
 
 impl OpenAlAudioEngine {
     pub fn new() -> Self {
-        let alto = Alto::load_default().unwrap();
-        let device = alto.open(None).unwrap();
-        let context = Arc::new(device.new_context(None).unwrap());
+        Self::try_new().unwrap()
+    }
+
+    /// Fallible constructor: returns an error instead of panicking when
+    /// the OpenAL library cannot be loaded or no output device can be
+    /// opened (CI runners, containers, headless servers). Callers can
+    /// then fall back to [`super::SoftwareAudioEngine`].
+    pub fn try_new() -> anyhow::Result<Self> {
+        let alto =
+            Alto::load_default().map_err(|e| anyhow::anyhow!("Cannot load OpenAL: {:?}", e))?;
+        let device = alto
+            .open(None)
+            .map_err(|e| anyhow::anyhow!("Cannot open OpenAL device: {:?}", e))?;
+        let context = Arc::new(
+            device
+                .new_context(None)
+                .map_err(|e| anyhow::anyhow!("Cannot create OpenAL context: {:?}", e))?,
+        );
 
         // Use the clamped linear distance model so a source's
         // `max_distance` is an audible cutoff: gain falls linearly from
@@ -87,10 +102,10 @@ impl OpenAlAudioEngine {
         // reference distance and so keep full gain — the BGM/UI case.
         context.set_distance_model(alto::DistanceModel::LinearClamped);
 
-        Self {
+        Ok(Self {
             context,
             sources: Mutex::new(Vec::new()),
-        }
+        })
     }
 }
 
@@ -429,11 +444,3 @@ fn create_buffer_from_samples(
         _ => None,
     }
 }
-
-fn create_decoder(data: Vec<u8>, codec: Codec) -> Box<dyn Decoder> {
-    match codec {
-        Codec::Mp3 => Box::new(SymphoniaDecoder::new(data)),
-        Codec::Ogg => Box::new(OggDecoder::new(data)),
-        Codec::Wav => Box::new(WavDecoder::new(data)),
-    }
-}
@@ -0,0 +1,645 @@
+//! Software-mixed audio backend.
+//!
+//! [`SoftwareAudioEngine`] implements [`AudioEngine`] without touching
+//! any audio device: every source it mints is decoded and mixed on the
+//! CPU into a stereo 16-bit PCM stream at a fixed output rate. The mix
+//! honours per-source gain, looping, pause/stop, and the same clamped
+//! linear distance model the OpenAL backend configures
+//! (`reference_distance` / `rolloff_factor` / `max_distance`), plus a
+//! simple left/right balance for mono sources placed in 3D.
+//!
+//! The mixed stream is discarded unless capture is enabled; with
+//! capture on it accumulates in memory and can be taken as raw samples
+//! ([`SoftwareAudioEngine::take_captured`]) or written out as a WAV file
+//! ([`SoftwareAudioEngine::write_wav`]). Tests can additionally inspect
+//! which clips are live via [`SoftwareAudioEngine::sources`] — e.g. to
+//! assert that the expected BGM started — and drive the mixer
+//! deterministically with [`SoftwareAudioEngine::render`] instead of
+//! relying on wall-clock `update` deltas.
+//!
+//! Resampling is nearest-neighbour: the backend exists for headless runs
+//! and CI assertions, not for listening fidelity.
+
+use std::cell::{Cell, RefCell};
+use std::path::Path;
+use std::sync::{Arc, Mutex, Weak};
+
+use super::decoders::{Decoder, create_decoder};
+use super::{
+    AudioCustomDecoderSource, AudioEngine, AudioMemorySource, AudioSource, AudioSourceState, Codec,
+};
+
+/// Output channel count of the mix. The stream is always interleaved
+/// stereo (`L R L R ...`).
+pub const SOFTWARE_MIX_CHANNELS: usize = 2;
+
+const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+/// Upper bound on back-to-back empty decoder fetches before a looping
+/// source is considered broken and stopped. Guards against spinning
+/// forever on a clip that decodes to zero samples.
+const MAX_EMPTY_FETCHES: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Listener {
+    position: [f32; 3],
+    forward: [f32; 3],
+    up: [f32; 3],
+}
+
+impl Default for Listener {
+    fn default() -> Self {
+        Self {
+            position: [0., 0., 0.],
+            forward: [0., 0., -1.],
+            up: [0., 1., 0.],
+        }
+    }
+}
+
+/// Snapshot of one live source minted by a [`SoftwareAudioEngine`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SoftwareSourceInfo {
+    pub state: AudioSourceState,
+    pub looping: bool,
+    pub gain: f32,
+    pub position: [f32; 3],
+    pub relative: bool,
+    /// Codec passed to `AudioMemorySource::set_data`; `None` for
+    /// custom-decoder sources or sources that never received data.
+    pub codec: Option<Codec>,
+    /// xxh3 hash of the bytes passed to `AudioMemorySource::set_data`,
+    /// so tests can identify a clip without keeping its payload around.
+    pub data_hash: Option<u64>,
+    /// Total source frames (per-channel samples) mixed so far, across
+    /// loops and restarts.
+    pub frames_played: u64,
+}
+
+pub struct SoftwareAudioEngine {
+    sample_rate: u32,
+    master_volume: Cell<f32>,
+    listener: Cell<Listener>,
+    /// Every source minted by this engine, ticked by `update` / `render`
+    /// the same way `OpenAlAudioEngine` pumps its streaming sources.
+    /// `Arc<Mutex<_>>` because `AudioSource` must be `Send + Sync`.
+    sources: Mutex<Vec<Weak<Mutex<SoftwareSource>>>>,
+    /// Fractional output frames carried between `update` calls so
+    /// uneven frame deltas don't drift the mixed stream length.
+    frame_remainder: Cell<f64>,
+    captured: RefCell<Option<Vec<i16>>>,
+}
+
+impl SoftwareAudioEngine {
+    /// A null engine: sources are mixed (so playback state advances and
+    /// clips reach EOF on schedule) but the output is discarded.
+    pub fn new() -> Self {
+        Self::with_sample_rate(DEFAULT_SAMPLE_RATE)
+    }
+
+    pub fn with_sample_rate(sample_rate: u32) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1),
+            master_volume: Cell::new(1.0),
+            listener: Cell::new(Listener::default()),
+            sources: Mutex::new(Vec::new()),
+            frame_remainder: Cell::new(0.),
+            captured: RefCell::new(None),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Start accumulating the mixed output. Any samples captured by a
+    /// previous session are discarded.
+    pub fn start_capture(&self) {
+        self.captured.replace(Some(Vec::new()));
+    }
+
+    /// Stop accumulating and return what was captured so far.
+    pub fn stop_capture(&self) -> Vec<i16> {
+        self.captured.replace(None).unwrap_or_default()
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.captured.borrow().is_some()
+    }
+
+    /// Drain the samples captured so far (interleaved stereo) while
+    /// leaving capture enabled.
+    pub fn take_captured(&self) -> Vec<i16> {
+        self.captured
+            .borrow_mut()
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Write the samples captured so far to a 16-bit stereo WAV file.
+    /// The capture buffer is left untouched.
+    pub fn write_wav<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
+        let spec = hound::WavSpec {
+            channels: SOFTWARE_MIX_CHANNELS as u16,
+            sample_rate: self.sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+
+        let mut writer = hound::WavWriter::create(path, spec)?;
+        if let Some(samples) = self.captured.borrow().as_ref() {
+            for s in samples {
+                writer.write_sample(*s)?;
+            }
+        }
+
+        writer.finalize()?;
+        Ok(())
+    }
+
+    /// Snapshots of every source still alive, in creation order.
+    pub fn sources(&self) -> Vec<SoftwareSourceInfo> {
+        let mut sources = self.sources.lock().unwrap();
+        sources.retain(|weak| weak.strong_count() > 0);
+        sources
+            .iter()
+            .filter_map(|weak| weak.upgrade())
+            .map(|s| s.lock().unwrap().info())
+            .collect()
+    }
+
+    /// Mix exactly `frames` output frames and return them (interleaved
+    /// stereo). The block is also appended to the capture buffer when
+    /// capture is enabled.
+    pub fn render(&self, frames: usize) -> Vec<i16> {
+        let mut mix = vec![0f32; frames * SOFTWARE_MIX_CHANNELS];
+        let listener = self.listener.get();
+
+        {
+            let mut sources = self.sources.lock().unwrap();
+            sources.retain(|weak| {
+                if let Some(strong) = weak.upgrade() {
+                    strong
+                        .lock()
+                        .unwrap()
+                        .mix_into(&mut mix, self.sample_rate, &listener);
+                    true
+                } else {
+                    false
+                }
+            });
+        }
+
+        let master = self.master_volume.get();
+        let out: Vec<i16> = mix
+            .into_iter()
+            .map(|s| (s * master).clamp(-1., 1.))
+            .map(|s| (s * i16::MAX as f32) as i16)
+            .collect();
+
+        if let Some(captured) = self.captured.borrow_mut().as_mut() {
+            captured.extend_from_slice(&out);
+        }
+
+        out
+    }
+
+    fn register(&self) -> Arc<Mutex<SoftwareSource>> {
+        let inner = Arc::new(Mutex::new(SoftwareSource::new()));
+        self.sources.lock().unwrap().push(Arc::downgrade(&inner));
+        inner
+    }
+}
+
+impl Default for SoftwareAudioEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioEngine for SoftwareAudioEngine {
+    fn create_source(&self) -> Box<dyn AudioMemorySource> {
+        Box::new(SoftwareSourceHandle {
+            inner: self.register(),
+        })
+    }
+
+    fn create_custom_decoder_source(&self) -> Box<dyn AudioCustomDecoderSource> {
+        Box::new(SoftwareSourceHandle {
+            inner: self.register(),
+        })
+    }
+
+    fn set_master_volume(&self, volume: f32) {
+        let gain = if volume.is_finite() {
+            volume.clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        self.master_volume.set(gain);
+    }
+
+    fn update(&self, delta_sec: f32) {
+        if !delta_sec.is_finite() || delta_sec <= 0. {
+            return;
+        }
+
+        let frames = self.frame_remainder.get() + delta_sec as f64 * self.sample_rate as f64;
+        let whole = frames.floor();
+        self.frame_remainder.set(frames - whole);
+        self.render(whole as usize);
+    }
+
+    fn set_listener(&self, position: [f32; 3], forward: [f32; 3], up: [f32; 3]) {
+        self.listener.set(Listener {
+            position,
+            forward,
+            up,
+        });
+    }
+}
+
+/// Decoded chunk currently being consumed by a [`SoftwareSource`].
+struct Chunk {
+    data: Vec<i16>,
+    channels: usize,
+    sample_rate: i32,
+}
+
+impl Chunk {
+    fn frames(&self) -> usize {
+        self.data.len().checked_div(self.channels).unwrap_or(0)
+    }
+
+    /// Stereo frame `index`, normalized to `[-1, 1]`. Mono is duplicated
+    /// to both channels; channels beyond the first two are dropped.
+    fn frame(&self, index: usize) -> (f32, f32) {
+        let base = index * self.channels;
+        let l = self.data[base] as f32 / i16::MAX as f32;
+        let r = if self.channels > 1 {
+            self.data[base + 1] as f32 / i16::MAX as f32
+        } else {
+            l
+        };
+        (l, r)
+    }
+}
+
+struct SoftwareSource {
+    decoder: Option<Box<dyn Decoder>>,
+    state: AudioSourceState,
+    looping: bool,
+    chunk: Option<Chunk>,
+    /// Read cursor into `chunk`, in source frames.
+    cursor: f64,
+    frames_played: u64,
+
+    gain: f32,
+    position: [f32; 3],
+    relative: bool,
+    reference_distance: f32,
+    rolloff_factor: f32,
+    max_distance: f32,
+
+    codec: Option<Codec>,
+    data_hash: Option<u64>,
+}
+
+impl SoftwareSource {
+    fn new() -> Self {
+        // Spatial defaults mirror OpenAL's per-source defaults.
+        Self {
+            decoder: None,
+            state: AudioSourceState::Stopped,
+            looping: false,
+            chunk: None,
+            cursor: 0.,
+            frames_played: 0,
+            gain: 1.,
+            position: [0., 0., 0.],
+            relative: false,
+            reference_distance: 1.,
+            rolloff_factor: 1.,
+            max_distance: f32::MAX,
+            codec: None,
+            data_hash: None,
+        }
+    }
+
+    fn info(&self) -> SoftwareSourceInfo {
+        SoftwareSourceInfo {
+            state: self.state,
+            looping: self.looping,
+            gain: self.gain,
+            position: self.position,
+            relative: self.relative,
+            codec: self.codec,
+            data_hash: self.data_hash,
+            frames_played: self.frames_played,
+        }
+    }
+
+    fn rewind(&mut self) {
+        if let Some(decoder) = self.decoder.as_mut() {
+            decoder.reset();
+        }
+
+        self.chunk = None;
+        self.cursor = 0.;
+    }
+
+    /// Make sure `chunk` holds the frame under `cursor`, pulling (and
+    /// looping) the decoder as needed. Returns `false` once the clip is
+    /// exhausted, at which point the source has been stopped.
+    fn ensure_chunk(&mut self) -> bool {
+        let mut empty_fetches = 0;
+        loop {
+            if let Some(chunk) = self.chunk.as_ref() {
+                let frames = chunk.frames() as f64;
+                if self.cursor < frames {
+                    return true;
+                }
+
+                self.cursor -= frames;
+                self.chunk = None;
+            }
+
+            let Some(decoder) = self.decoder.as_mut() else {
+                self.state = AudioSourceState::Stopped;
+                return false;
+            };
+
+            match decoder.fetch_samples() {
+                Ok(Some(samples))
+                    if samples.channels > 0 && samples.data.len() >= samples.channels =>
+                {
+                    self.chunk = Some(Chunk {
+                        data: samples.data,
+                        channels: samples.channels,
+                        sample_rate: samples.sample_rate,
+                    });
+                    empty_fetches = 0;
+                }
+                Ok(Some(_)) => {
+                    empty_fetches += 1;
+                }
+                Ok(None) => {
+                    empty_fetches += 1;
+                    if !self.looping {
+                        self.state = AudioSourceState::Stopped;
+                        return false;
+                    }
+
+                    decoder.reset();
+                    self.cursor = 0.;
+                }
+                Err(e) => {
+                    log::warn!("Audio: software mixer decode error: {:?}", e);
+                    self.state = AudioSourceState::Stopped;
+                    return false;
+                }
+            }
+
+            if empty_fetches > MAX_EMPTY_FETCHES {
+                self.state = AudioSourceState::Stopped;
+                return false;
+            }
+        }
+    }
+
+    /// Per-channel gain for this source under the clamped linear
+    /// distance model, with a balance pan for mono sources.
+    fn spatial_gain(&self, listener: &Listener, mono: bool) -> (f32, f32) {
+        let offset = if self.relative {
+            self.position
+        } else {
+            sub(self.position, listener.position)
+        };
+
+        let distance = length(offset);
+        let attenuation = if self.max_distance > self.reference_distance {
+            let d = distance.clamp(self.reference_distance, self.max_distance);
+            1. - self.rolloff_factor * (d - self.reference_distance)
+                / (self.max_distance - self.reference_distance)
+        } else {
+            1.
+        }
+        .clamp(0., 1.);
+
+        let gain = self.gain.max(0.) * attenuation;
+        if !mono || distance <= f32::EPSILON {
+            return (gain, gain);
+        }
+
+        // Listener-relative sources are expressed in listener space
+        // (OpenAL: +X right), world sources are projected onto the
+        // listener's right vector.
+        let right = if self.relative {
+            [1., 0., 0.]
+        } else {
+            normalize(cross(listener.forward, listener.up))
+        };
+        let pan = (dot(offset, right) / distance).clamp(-1., 1.);
+        (gain * (1. - pan).min(1.), gain * (1. + pan).min(1.))
+    }
+
+    fn mix_into(&mut self, out: &mut [f32], output_rate: u32, listener: &Listener) {
+        if self.state != AudioSourceState::Playing {
+            return;
+        }
+
+        let frames = out.len() / SOFTWARE_MIX_CHANNELS;
+        let mut cached_gain: Option<(bool, (f32, f32))> = None;
+        for i in 0..frames {
+            if !self.ensure_chunk() {
+                return;
+            }
+
+            let chunk = self.chunk.as_ref().unwrap();
+            let mono = chunk.channels == 1;
+            let (gl, gr) = match cached_gain {
+                Some((m, g)) if m == mono => g,
+                _ => {
+                    let g = self.spatial_gain(listener, mono);
+                    cached_gain = Some((mono, g));
+                    g
+                }
+            };
+
+            let (l, r) = chunk.frame(self.cursor as usize);
+            out[i * SOFTWARE_MIX_CHANNELS] += l * gl;
+            out[i * SOFTWARE_MIX_CHANNELS + 1] += r * gr;
+
+            let step = chunk.sample_rate.max(1) as f64 / output_rate as f64;
+            let before = self.cursor as u64;
+            self.cursor += step;
+            self.frames_played += self.cursor as u64 - before;
+        }
+    }
+}
+
+impl AudioSource for SoftwareSource {
+    fn update(&mut self) {}
+
+    fn play(&mut self, looping: bool) {
+        self.stop();
+        self.looping = looping;
+        if self.decoder.is_some() {
+            self.state = AudioSourceState::Playing;
+        }
+    }
+
+    fn restart(&mut self) {
+        if self.decoder.is_none() {
+            return;
+        }
+
+        self.rewind();
+        self.state = AudioSourceState::Playing;
+    }
+
+    fn pause(&mut self) {
+        if self.state == AudioSourceState::Playing {
+            self.state = AudioSourceState::Paused;
+        }
+    }
+
+    fn resume(&mut self) {
+        if self.state == AudioSourceState::Paused {
+            self.state = AudioSourceState::Playing;
+        }
+    }
+
+    fn stop(&mut self) {
+        self.state = AudioSourceState::Stopped;
+        self.rewind();
+    }
+
+    fn state(&self) -> AudioSourceState {
+        self.state
+    }
+
+    fn set_position(&mut self, position: [f32; 3]) {
+        self.position = position;
+    }
+
+    fn set_gain(&mut self, gain: f32) {
+        self.gain = gain;
+    }
+
+    fn set_relative(&mut self, relative: bool) {
+        self.relative = relative;
+    }
+
+    fn set_reference_distance(&mut self, distance: f32) {
+        self.reference_distance = distance;
+    }
+
+    fn set_rolloff_factor(&mut self, factor: f32) {
+        self.rolloff_factor = factor;
+    }
+
+    fn set_max_distance(&mut self, distance: f32) {
+        self.max_distance = distance;
+    }
+}
+
+/// Caller-visible handle returned from the `SoftwareAudioEngine`
+/// factories. Dropping it drops the last strong reference, so the
+/// engine's `Weak` stops mixing the source on the next tick — the same
+/// ownership shape as the OpenAL backend's engine-owned handles.
+struct SoftwareSourceHandle {
+    inner: Arc<Mutex<SoftwareSource>>,
+}
+
+impl AudioSource for SoftwareSourceHandle {
+    fn update(&mut self) {
+        self.inner.lock().unwrap().update();
+    }
+    fn play(&mut self, looping: bool) {
+        self.inner.lock().unwrap().play(looping);
+    }
+    fn restart(&mut self) {
+        self.inner.lock().unwrap().restart();
+    }
+    fn pause(&mut self) {
+        self.inner.lock().unwrap().pause();
+    }
+    fn resume(&mut self) {
+        self.inner.lock().unwrap().resume();
+    }
+    fn stop(&mut self) {
+        self.inner.lock().unwrap().stop();
+    }
+    fn state(&self) -> AudioSourceState {
+        self.inner.lock().unwrap().state()
+    }
+    fn set_position(&mut self, position: [f32; 3]) {
+        self.inner.lock().unwrap().set_position(position);
+    }
+    fn set_gain(&mut self, gain: f32) {
+        self.inner.lock().unwrap().set_gain(gain);
+    }
+    fn set_relative(&mut self, relative: bool) {
+        self.inner.lock().unwrap().set_relative(relative);
+    }
+    fn set_reference_distance(&mut self, distance: f32) {
+        self.inner.lock().unwrap().set_reference_distance(distance);
+    }
+    fn set_rolloff_factor(&mut self, factor: f32) {
+        self.inner.lock().unwrap().set_rolloff_factor(factor);
+    }
+    fn set_max_distance(&mut self, distance: f32) {
+        self.inner.lock().unwrap().set_max_distance(distance);
+    }
+}
+
+impl AudioMemorySource for SoftwareSourceHandle {
+    fn set_data(&mut self, data: Vec<u8>, codec_hint: Codec) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.stop();
+        inner.data_hash = Some(xxhash_rust::xxh3::xxh3_64(&data));
+        inner.codec = Some(codec_hint);
+        inner.decoder = Some(create_decoder(data, codec_hint));
+    }
+}
+
+impl AudioCustomDecoderSource for SoftwareSourceHandle {
+    fn set_decoder(&mut self, reader: Box<dyn Decoder>) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.stop();
+        inner.codec = None;
+        inner.data_hash = None;
+        inner.decoder = Some(reader);
+    }
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(a: [f32; 3]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn normalize(a: [f32; 3]) -> [f32; 3] {
+    let len = length(a);
+    if len <= f32::EPSILON {
+        [1., 0., 0.]
+    } else {
+        [a[0] / len, a[1] / len, a[2] / len]
+    }
+}
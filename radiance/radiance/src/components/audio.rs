@@ -23,6 +23,14 @@
 //!   own previous instance is still audible so it never stacks copies of
 //!   itself.
 //!
+//! Spatial nodes can also opt into **distance culling**
+//! ([`AudioNodeConfig::cull_distance`]): while the listener is farther
+//! away than the cull radius the source is stopped (looping beds) or its
+//! countdown is held (random one-shots), so a block full of out-of-range
+//! emitters doesn't keep decoding inaudible audio. The listener position
+//! is the camera position published once per frame by
+//! `CoreRadianceEngine::update` (shared with [`super::billboard`]).
+//!
 //! The component **self-ticks** via [`IComponent::on_updating`]; the
 //! owning container dispatches it every frame while the entity is
 //! active, so callers attach once and do nothing else. On
@@ -37,6 +45,7 @@ use crosscom::ComRc;
 
 use crate::audio::{AudioEngine, AudioMemorySource, AudioSourceState, Codec};
 use crate::comdef::{IAudioSourceComponentImpl, IComponentImpl, IEntity, IEntityExt};
+use crate::components::billboard::camera_position;
 use crate::math::Vec3;
 
 ComObject_AudioSourceComponent!(super::AudioSourceComponent);
@@ -80,6 +89,11 @@ pub struct AudioNodeConfig {
     /// OpenAL max distance: attenuation stops decreasing past this
     /// distance (an effective audible cutoff for the clamped models).
     pub max_distance: f32,
+    /// Spatial nodes only: when set, the node goes dormant while the
+    /// listener is farther than this from the entity — a looping bed is
+    /// stopped and restarted on re-entry, a random-interval emitter holds
+    /// its countdown. `None` keeps the node live at any distance.
+    pub cull_distance: Option<f32>,
 }
 
 impl Default for AudioNodeConfig {
@@ -91,6 +105,7 @@ impl Default for AudioNodeConfig {
             reference_distance: 1.0,
             rolloff_factor: 1.0,
             max_distance: f32::MAX,
+            cull_distance: None,
         }
     }
 }
@@ -145,6 +160,9 @@ pub struct AudioSourceComponent {
     /// Seconds until the next random-interval fire. Unused for
     /// `Loop` / `OneShot`.
     next_play_in_sec: Cell<f32>,
+    /// Whether the node is currently dormant because the listener is
+    /// beyond [`AudioNodeConfig::cull_distance`].
+    culled: Cell<bool>,
 }
 
 impl AudioSourceComponent {
@@ -185,6 +203,7 @@ impl AudioSourceComponent {
             source: RefCell::new(source),
             config,
             next_play_in_sec: Cell::new(next_play_in_sec),
+            culled: Cell::new(false),
         })
     }
 
@@ -195,6 +214,36 @@ impl AudioSourceComponent {
         let pos = self.entity.world_transform().position();
         self.source.borrow_mut().set_position(vec3_to_array(pos));
     }
+
+    fn listener_in_range(&self) -> bool {
+        let Some(cull_distance) = self.config.cull_distance else {
+            return true;
+        };
+        if !self.config.spatial {
+            return true;
+        }
+        let pos = self.entity.world_transform().position();
+        let d = Vec3::sub(&camera_position(), &pos);
+        d.norm2() <= cull_distance * cull_distance
+    }
+
+    /// Re-evaluate distance culling. Returns `true` while the node is
+    /// dormant. Looping beds are stopped on leaving range and restarted
+    /// on re-entry; other modes only report the state.
+    fn update_culling(&self) -> bool {
+        let culled = !self.listener_in_range();
+        if culled != self.culled.get() {
+            self.culled.set(culled);
+            if self.config.mode == PlaybackMode::Loop {
+                if culled {
+                    self.source.borrow_mut().stop();
+                } else {
+                    self.source.borrow_mut().play(true);
+                }
+            }
+        }
+        culled
+    }
 }
 
 impl IAudioSourceComponentImpl for AudioSourceComponent {
@@ -215,8 +264,14 @@ impl IAudioSourceComponentImpl for AudioSourceComponent {
 impl IComponentImpl for AudioSourceComponent {
     fn on_loading(&self) -> crosscom::Void {
         self.sync_position();
+        self.culled.set(!self.listener_in_range());
         match self.config.mode {
-            PlaybackMode::Loop => self.source.borrow_mut().play(true),
+            PlaybackMode::Loop => {
+                // A culled bed starts once the listener walks into range.
+                if !self.culled.get() {
+                    self.source.borrow_mut().play(true);
+                }
+            }
             PlaybackMode::OneShot | PlaybackMode::RandomInterval { .. } => {
                 // RandomInterval waits for its first countdown in
                 // on_updating; OneShot fires immediately.
@@ -229,6 +284,7 @@ impl IComponentImpl for AudioSourceComponent {
 
     fn on_updating(&self, delta_sec: f32) -> crosscom::Void {
         self.sync_position();
+        let culled = self.update_culling();
 
         let (min, max) = match self.config.mode {
             PlaybackMode::RandomInterval { min, max } => (min, max),
@@ -237,6 +293,12 @@ impl IComponentImpl for AudioSourceComponent {
             _ => return,
         };
 
+        // Out of range: hold the countdown so the emitter neither fires
+        // inaudibly nor bursts the moment the listener returns.
+        if culled {
+            return;
+        }
+
         // Freeze the countdown while this node's own previous instance
         // is still audible, so it never stacks overlapping copies.
         if self.source.borrow().state() != AudioSourceState::Stopped {
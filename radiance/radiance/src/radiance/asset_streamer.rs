@@ -0,0 +1,348 @@
+//! Worker-pool loader for scene streaming.
+//!
+//! Scene loads split into two halves: decoding (file formats, pixel
+//! and vertex data) which only touches plain `Send` data, and
+//! finalizing (entities, components, GPU resources) which must run on
+//! the main thread because the engine's handles are `Rc`. The
+//! [`AssetStreamer`] owns a small pool of worker threads for the
+//! first half; a [`StreamingBatch`] tracks one load's jobs and runs
+//! their finalizers on the main thread, a frame-time budget at a
+//! time, so the window keeps painting while a map streams in.
+//!
+//! The vfs isn't `Send` either, so callers read file bytes on the main
+//! thread and hand the bytes to the decode closure.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Receiver, Sender},
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+type Decoded = Result<Box<dyn Any + Send>, String>;
+type Finalizer = Box<dyn FnOnce(Box<dyn Any + Send>)>;
+
+/// Upper bound on worker threads. Decoding is mostly memory-bound and
+/// the main thread still has to keep up with finalizing, so more
+/// workers than this rarely helps.
+const MAX_WORKERS: usize = 4;
+
+/// Dropping the streamer closes its queue; workers exit once every
+/// batch that can still submit to them is gone.
+pub struct AssetStreamer {
+    sender: Option<Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl AssetStreamer {
+    /// Pool sized to the machine: one worker per available core, up to
+    /// [`MAX_WORKERS`].
+    pub fn new() -> Self {
+        let workers = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(MAX_WORKERS);
+        Self::with_workers(workers)
+    }
+
+    /// Pool with exactly `count` workers. `0` decodes inline on the
+    /// submitting thread, which keeps the same batch API usable on
+    /// targets without threads and in deterministic tests.
+    pub fn with_workers(count: usize) -> Self {
+        if count == 0 {
+            return Self {
+                sender: None,
+                workers: Vec::new(),
+            };
+        }
+
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let mut workers = Vec::with_capacity(count);
+        for i in 0..count {
+            let receiver = receiver.clone();
+            let spawned = std::thread::Builder::new()
+                .name(format!("asset-streamer-{}", i))
+                .spawn(move || {
+                    loop {
+                        // The guard is dropped before the job runs so
+                        // the other workers can pick up work meanwhile.
+                        let job = receiver.lock().unwrap().recv();
+                        let Ok(job) = job else {
+                            break;
+                        };
+                        job();
+                    }
+                });
+            match spawned {
+                Ok(handle) => workers.push(handle),
+                Err(e) => log::warn!("AssetStreamer: failed to spawn worker {}: {}", i, e),
+            }
+        }
+
+        if workers.is_empty() {
+            log::warn!("AssetStreamer: no workers available, decoding inline");
+            return Self::with_workers(0);
+        }
+
+        Self {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Start a new batch of jobs on this pool.
+    pub fn batch(&self) -> StreamingBatch {
+        let (results, ready) = mpsc::channel();
+        StreamingBatch {
+            jobs: self.sender.clone(),
+            results,
+            ready,
+            finalizers: RefCell::new(HashMap::new()),
+            next_id: RefCell::new(0),
+            finalized: RefCell::new(0),
+        }
+    }
+}
+
+impl Default for AssetStreamer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One load's worth of off-thread jobs and their main-thread
+/// finalizers. Finalizers run in completion order, not submit order.
+pub struct StreamingBatch {
+    jobs: Option<Sender<Job>>,
+    results: Sender<(u64, Decoded)>,
+    ready: Receiver<(u64, Decoded)>,
+    finalizers: RefCell<HashMap<u64, Finalizer>>,
+    next_id: RefCell<u64>,
+    finalized: RefCell<u64>,
+}
+
+impl StreamingBatch {
+    /// Run `decode` on a worker, then `finalize` with its output on
+    /// the main thread during a later [`Self::pump`]. A panicking
+    /// decode is logged and its finalizer dropped; it still counts as
+    /// done so the batch can complete.
+    pub fn submit<D: Send + 'static>(
+        &self,
+        decode: impl FnOnce() -> D + Send + 'static,
+        finalize: impl FnOnce(D) + 'static,
+    ) {
+        let id = {
+            let mut next_id = self.next_id.borrow_mut();
+            *next_id += 1;
+            *next_id
+        };
+        self.finalizers.borrow_mut().insert(
+            id,
+            Box::new(move |data: Box<dyn Any + Send>| {
+                finalize(*data.downcast::<D>().unwrap());
+            }),
+        );
+
+        let results = self.results.clone();
+        let job: Job = Box::new(move || {
+            let decoded = catch_unwind(AssertUnwindSafe(decode))
+                .map(|data| Box::new(data) as Box<dyn Any + Send>)
+                .map_err(|panic| panic_message(&*panic));
+            let _ = results.send((id, decoded));
+        });
+
+        let job = match &self.jobs {
+            Some(jobs) => match jobs.send(job) {
+                Ok(()) => return,
+                Err(mpsc::SendError(job)) => job,
+            },
+            None => job,
+        };
+        job();
+    }
+
+    /// Run ready finalizers until `budget` is spent. At least one ready
+    /// finalizer runs per call so a tight budget still makes progress.
+    /// Returns `true` once every submitted job has been finalized.
+    pub fn pump(&self, budget: Duration) -> bool {
+        let start = Instant::now();
+        while !self.is_finished() {
+            let Ok((id, decoded)) = self.ready.try_recv() else {
+                break;
+            };
+            self.finalize(id, decoded);
+            if start.elapsed() >= budget {
+                break;
+            }
+        }
+
+        self.is_finished()
+    }
+
+    /// Block until every submitted job has been decoded and finalized.
+    pub fn finish_blocking(&self) {
+        while !self.is_finished() {
+            let Ok((id, decoded)) = self.ready.recv() else {
+                break;
+            };
+            self.finalize(id, decoded);
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        *self.finalized.borrow() == *self.next_id.borrow()
+    }
+
+    pub fn submitted(&self) -> u64 {
+        *self.next_id.borrow()
+    }
+
+    pub fn finalized(&self) -> u64 {
+        *self.finalized.borrow()
+    }
+
+    /// Fraction of submitted jobs that have been finalized; `1.0` for
+    /// an empty batch.
+    pub fn progress(&self) -> f32 {
+        let submitted = self.submitted();
+        if submitted == 0 {
+            1.0
+        } else {
+            self.finalized() as f32 / submitted as f32
+        }
+    }
+
+    fn finalize(&self, id: u64, decoded: Decoded) {
+        let finalizer = self.finalizers.borrow_mut().remove(&id);
+        *self.finalized.borrow_mut() += 1;
+        let Some(finalizer) = finalizer else {
+            return;
+        };
+        match decoded {
+            Ok(data) => finalizer(data),
+            Err(message) => log::error!("AssetStreamer: decode job panicked: {}", message),
+        }
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(s) = panic.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = panic.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    fn collect(streamer: &AssetStreamer) -> Vec<u32> {
+        let out = Rc::new(RefCell::new(Vec::new()));
+        let batch = streamer.batch();
+        for i in 0..32u32 {
+            let out = out.clone();
+            batch.submit(move || i * i, move |sq| out.borrow_mut().push(sq));
+        }
+        batch.finish_blocking();
+        assert!(batch.is_finished());
+        assert_eq!(batch.progress(), 1.0);
+
+        let mut out = out.borrow().clone();
+        out.sort();
+        out
+    }
+
+    #[test]
+    fn pooled_and_inline_agree() {
+        let expected: Vec<u32> = (0..32).map(|i| i * i).collect();
+        assert_eq!(collect(&AssetStreamer::with_workers(3)), expected);
+        assert_eq!(collect(&AssetStreamer::with_workers(0)), expected);
+    }
+
+    #[test]
+    fn finalizers_run_on_the_pumping_thread() {
+        let streamer = AssetStreamer::with_workers(2);
+        let batch = streamer.batch();
+        let main = std::thread::current().id();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        for _ in 0..4 {
+            let seen = seen.clone();
+            batch.submit(
+                || std::thread::current().id(),
+                move |worker| {
+                    seen.borrow_mut()
+                        .push((worker, std::thread::current().id()))
+                },
+            );
+        }
+        assert_eq!(batch.submitted(), 4);
+
+        while !batch.pump(Duration::ZERO) {
+            std::thread::yield_now();
+        }
+        for (worker, finalizer) in seen.borrow().iter() {
+            assert_ne!(*worker, main);
+            assert_eq!(*finalizer, main);
+        }
+    }
+
+    #[test]
+    fn zero_budget_still_makes_progress() {
+        let streamer = AssetStreamer::with_workers(0);
+        let batch = streamer.batch();
+        for i in 0..3 {
+            batch.submit(move || i, |_| {});
+        }
+        assert!(!batch.pump(Duration::ZERO));
+        assert_eq!(batch.finalized(), 1);
+        assert!((batch.progress() - 1.0 / 3.0).abs() < 1e-6);
+        assert!(!batch.pump(Duration::ZERO));
+        assert!(batch.pump(Duration::ZERO));
+    }
+
+    #[test]
+    fn panicking_decode_completes_the_batch() {
+        let streamer = AssetStreamer::with_workers(1);
+        let batch = streamer.batch();
+        let ran = Rc::new(RefCell::new(0));
+        {
+            let ran = ran.clone();
+            batch.submit(
+                || -> u32 { panic!("corrupt block") },
+                move |_| *ran.borrow_mut() += 1,
+            );
+        }
+        {
+            let ran = ran.clone();
+            batch.submit(|| 1u32, move |_| *ran.borrow_mut() += 1);
+        }
+        batch.finish_blocking();
+        assert_eq!(*ran.borrow(), 1);
+        assert_eq!(batch.finalized(), 2);
+    }
+
+    #[test]
+    fn empty_batch_is_finished() {
+        let streamer = AssetStreamer::with_workers(1);
+        let batch = streamer.batch();
+        assert!(batch.pump(Duration::from_millis(1)));
+        assert_eq!(batch.progress(), 1.0);
+    }
+}
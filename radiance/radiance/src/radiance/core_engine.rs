@@ -1,8 +1,8 @@
 use crosscom::ComRc;
 
-use super::TaskManager;
 use super::ui_frame::UiFrameRenderer;
 use super::ui_manager::UiManager;
+use super::{AssetStreamer, TaskManager};
 use crate::asset::AssetManager;
 use crate::comdef::{ISceneExt, ISceneManager, IUiLayer};
 use crate::rendering::{self, RenderingEngine};
@@ -31,6 +31,8 @@ pub struct CoreRadianceEngine {
     /// `assets()` always has something safe to return.
     assets: Rc<AssetManager>,
     task_manager: Rc<TaskManager>,
+    /// Worker pool for off-thread asset decoding. See [`AssetStreamer`].
+    asset_streamer: Rc<AssetStreamer>,
     services: RefCell<HashMap<TypeId, Rc<dyn Any>>>,
     /// Per-frame UI renderer that drives the registered UI layers inside
     /// the imgui frame scope. See [`UiFrameRenderer`].
@@ -53,6 +55,7 @@ impl CoreRadianceEngine {
             scene_manager,
             assets: AssetManager::new(),
             task_manager: Rc::new(TaskManager::new()),
+            asset_streamer: Rc::new(AssetStreamer::new()),
             services: RefCell::new(HashMap::new()),
             ui_renderer: RefCell::new(None),
         }
@@ -104,6 +107,10 @@ impl CoreRadianceEngine {
         self.task_manager.clone()
     }
 
+    pub fn asset_streamer(&self) -> Rc<AssetStreamer> {
+        self.asset_streamer.clone()
+    }
+
     /// Install the per-frame UI renderer that drives the registered UI
     /// layers inside the imgui frame scope (see [`UiFrameRenderer`]).
     /// Replaces any previously-installed renderer.
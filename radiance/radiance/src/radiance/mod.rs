@@ -1,9 +1,11 @@
+mod asset_streamer;
 mod core_engine;
 mod task_manager;
 mod ui_frame;
 mod ui_layer;
 mod ui_manager;
 
+pub use asset_streamer::{AssetStreamer, StreamingBatch};
 pub use core_engine::CoreRadianceEngine;
 pub use task_manager::{TaskHandle, TaskManager};
 pub use ui_frame::UiFrameRenderer;
@@ -12,7 +14,11 @@ pub use ui_manager::UiManager;
 
 use crosscom::ComRc;
 
-use crate::{application::Platform, audio::OpenAlAudioEngine, scene::DefaultSceneManager};
+use crate::{
+    application::Platform,
+    audio::{AudioEngine, OpenAlAudioEngine, SoftwareAudioEngine},
+    scene::DefaultSceneManager,
+};
 use std::{cell::RefCell, error::Error, rc::Rc};
 
 pub fn create_radiance_engine(
@@ -98,7 +104,15 @@ pub fn create_radiance_engine(
         }));
     }
 
-    let audio_engine = Rc::new(OpenAlAudioEngine::new());
+    // Without a usable OpenAL device (CI runners, containers) keep the
+    // game running on the software mixer instead of panicking at boot.
+    let audio_engine: Rc<dyn AudioEngine> = match OpenAlAudioEngine::try_new() {
+        Ok(engine) => Rc::new(engine),
+        Err(e) => {
+            log::warn!("{:?}; falling back to the software audio mixer", e);
+            Rc::new(SoftwareAudioEngine::new())
+        }
+    };
     let input_engine = crate::input::CoreInputEngine::new(platform);
     let scene_manager = ComRc::from_object(DefaultSceneManager::new());
 
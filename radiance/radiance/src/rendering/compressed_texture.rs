@@ -0,0 +1,724 @@
+//! Block-compressed (BCn / DXTn) texture payloads.
+//!
+//! Most PAL5 and SWD5 textures ship as DXT1/DXT3/DXT5 `.dds`. Expanding
+//! them to RGBA costs 4–8× the VRAM of the source, so [`CompressedImage`]
+//! keeps the blocks as they are for backends that can sample them
+//! directly (Vulkan with `textureCompressionBC`). Backends that can't
+//! call [`CompressedImage::decode`] instead.
+//!
+//! A BCn image can't be mip-mapped on the GPU with blits the way the
+//! RGBA path does, so the chain comes from the file when it has one,
+//! and [`CompressedImage::with_full_mip_chain`] builds the rest on the
+//! CPU: decode the smallest level, box-filter it down, and re-encode
+//! with a simple range-fit encoder. Lower mips are small and viewed
+//! from afar, so the encoder favours speed over quality.
+
+use image::{Rgba, RgbaImage};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BcFormat {
+    /// DXT1: RGB565 endpoints, optional 1-bit alpha.
+    Bc1,
+    /// DXT3: BC1 color plus explicit 4-bit alpha.
+    Bc2,
+    /// DXT5: BC1 color plus interpolated 8-bit alpha.
+    Bc3,
+}
+
+impl BcFormat {
+    pub fn block_bytes(&self) -> usize {
+        match self {
+            BcFormat::Bc1 => 8,
+            BcFormat::Bc2 | BcFormat::Bc3 => 16,
+        }
+    }
+
+    fn from_four_cc(four_cc: &[u8]) -> Option<Self> {
+        match four_cc {
+            b"DXT1" => Some(BcFormat::Bc1),
+            b"DXT2" | b"DXT3" => Some(BcFormat::Bc2),
+            b"DXT4" | b"DXT5" => Some(BcFormat::Bc3),
+            _ => None,
+        }
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            BcFormat::Bc1 => 1,
+            BcFormat::Bc2 => 2,
+            BcFormat::Bc3 => 3,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            1 => Some(BcFormat::Bc1),
+            2 => Some(BcFormat::Bc2),
+            3 => Some(BcFormat::Bc3),
+            _ => None,
+        }
+    }
+}
+
+/// Byte size of one `width`×`height` level in `format`.
+pub fn level_size(format: BcFormat, width: u32, height: u32) -> usize {
+    let blocks_x = width.div_ceil(4).max(1) as usize;
+    let blocks_y = height.div_ceil(4).max(1) as usize;
+    blocks_x * blocks_y * format.block_bytes()
+}
+
+fn level_extent(width: u32, height: u32, level: usize) -> (u32, u32) {
+    ((width >> level).max(1), (height >> level).max(1))
+}
+
+pub struct CompressedImage {
+    format: BcFormat,
+    width: u32,
+    height: u32,
+    /// Mip 0 first; each level half the size of the previous one.
+    levels: Vec<Vec<u8>>,
+    /// Sample alpha as 1.0 regardless of the blocks' alpha. Set for
+    /// textures loaded opaque whose alpha channel holds unrelated data.
+    opaque: bool,
+}
+
+impl CompressedImage {
+    /// Parse a DXT1/DXT3/DXT5 `.dds`, keeping every mip level the file
+    /// carries in full. `None` for anything else (uncompressed or DX10
+    /// `.dds`, other formats), which callers decode to RGBA instead.
+    pub fn from_dds(data: &[u8]) -> Option<Self> {
+        const HEADER_LEN: usize = 128;
+        const DDPF_FOURCC: u32 = 0x4;
+
+        if data.len() < HEADER_LEN || &data[0..4] != b"DDS " {
+            return None;
+        }
+        let u32_at =
+            |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+        if u32_at(4) != 124 || u32_at(80) & DDPF_FOURCC == 0 {
+            return None;
+        }
+        let format = BcFormat::from_four_cc(&data[84..88])?;
+        let height = u32_at(12);
+        let width = u32_at(16);
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let max_levels = full_mip_levels(width, height) as usize;
+        let declared = (u32_at(28) as usize).clamp(1, max_levels);
+        let mut levels = Vec::with_capacity(declared);
+        let mut offset = HEADER_LEN;
+        for level in 0..declared {
+            let (w, h) = level_extent(width, height, level);
+            let size = level_size(format, w, h);
+            let Some(bytes) = data.get(offset..offset + size) else {
+                break;
+            };
+            levels.push(bytes.to_vec());
+            offset += size;
+        }
+        if levels.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            format,
+            width,
+            height,
+            levels,
+            opaque: false,
+        })
+    }
+
+    /// Block-compress a decoded image: BC1 when every texel is opaque,
+    /// BC3 otherwise. Each mip is box-filtered from the level above in
+    /// RGBA before encoding, so lower levels don't compound the
+    /// encoder's error.
+    pub fn from_rgba(image: &RgbaImage) -> Option<Self> {
+        let (width, height) = image.dimensions();
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let format = if image.pixels().all(|px| px.0[3] == 255) {
+            BcFormat::Bc1
+        } else {
+            BcFormat::Bc3
+        };
+
+        let full = full_mip_levels(width, height) as usize;
+        let mut levels = Vec::with_capacity(full);
+        levels.push(encode(format, image));
+        let mut source: Option<RgbaImage> = None;
+        for level in 1..full {
+            let (w, h) = level_extent(width, height, level);
+            let smaller = downsample(source.as_ref().unwrap_or(image), w, h);
+            levels.push(encode(format, &smaller));
+            source = Some(smaller);
+        }
+
+        Some(Self {
+            format,
+            width,
+            height,
+            levels,
+            opaque: false,
+        })
+    }
+
+    pub fn format(&self) -> BcFormat {
+        self.format
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn levels(&self) -> &[Vec<u8>] {
+        &self.levels
+    }
+
+    pub fn mip_levels(&self) -> u32 {
+        self.levels.len() as u32
+    }
+
+    pub fn is_opaque(&self) -> bool {
+        self.opaque
+    }
+
+    pub fn set_opaque(&mut self, opaque: bool) {
+        self.opaque = opaque;
+    }
+
+    pub fn has_full_mip_chain(&self) -> bool {
+        self.levels.len() == full_mip_levels(self.width, self.height) as usize
+    }
+
+    /// Extend the chain down to 1×1 (see the module docs). A chain the
+    /// file already completed is returned unchanged.
+    pub fn with_full_mip_chain(mut self) -> Self {
+        let full = full_mip_levels(self.width, self.height) as usize;
+        while self.levels.len() < full {
+            let last = self.levels.len() - 1;
+            let source = self.decode_level(last);
+            let (w, h) = level_extent(self.width, self.height, last + 1);
+            let smaller = downsample(&source, w, h);
+            self.levels.push(encode(self.format, &smaller));
+        }
+        self
+    }
+
+    /// Mip 0 expanded to RGBA, for backends without BCn support.
+    pub fn decode(&self) -> RgbaImage {
+        self.decode_level(0)
+    }
+
+    pub fn decode_level(&self, level: usize) -> RgbaImage {
+        let (w, h) = level_extent(self.width, self.height, level);
+        let mut image = RgbaImage::new(w, h);
+        for_each_block(self.format, &self.levels[level], w, h, |bx, by, block| {
+            let texels = decode_block(self.format, block);
+            for (i, texel) in texels.iter().enumerate() {
+                let (x, y) = (bx * 4 + (i as u32 % 4), by * 4 + (i as u32 / 4));
+                if x < w && y < h {
+                    let mut texel = *texel;
+                    if self.opaque {
+                        texel[3] = 255;
+                    }
+                    image.put_pixel(x, y, Rgba(texel));
+                }
+            }
+        });
+        image
+    }
+
+    /// Mip 0's alpha channel in row-major order, without decoding color.
+    /// Used to classify transparency cheaply.
+    pub fn alpha_values(&self) -> Vec<u8> {
+        let (w, h) = (self.width, self.height);
+        let mut alpha = vec![255u8; (w * h) as usize];
+        if self.opaque {
+            return alpha;
+        }
+        for_each_block(self.format, &self.levels[0], w, h, |bx, by, block| {
+            let values = decode_block_alpha(self.format, block);
+            for (i, a) in values.iter().enumerate() {
+                let (x, y) = (bx * 4 + (i as u32 % 4), by * 4 + (i as u32 / 4));
+                if x < w && y < h {
+                    alpha[(y * w + x) as usize] = *a;
+                }
+            }
+        });
+        alpha
+    }
+
+    /// Flatten into bytes for the on-disk asset cache.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.push(self.format.tag());
+        data.push(self.opaque as u8);
+        data.extend_from_slice(&self.width.to_le_bytes());
+        data.extend_from_slice(&self.height.to_le_bytes());
+        data.extend_from_slice(&(self.levels.len() as u32).to_le_bytes());
+        for level in &self.levels {
+            data.extend_from_slice(level);
+        }
+        data
+    }
+
+    /// Inverse of [`Self::to_bytes`]; `None` if `data` is malformed.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let format = BcFormat::from_tag(*data.first()?)?;
+        let opaque = *data.get(1)? != 0;
+        let u32_at = |offset: usize| -> Option<u32> {
+            Some(u32::from_le_bytes(
+                data.get(offset..offset + 4)?.try_into().ok()?,
+            ))
+        };
+        let width = u32_at(2)?;
+        let height = u32_at(6)?;
+        let count = u32_at(10)? as usize;
+        if width == 0
+            || height == 0
+            || count == 0
+            || count > full_mip_levels(width, height) as usize
+        {
+            return None;
+        }
+
+        let mut levels = Vec::with_capacity(count);
+        let mut offset = 14;
+        for level in 0..count {
+            let (w, h) = level_extent(width, height, level);
+            let size = level_size(format, w, h);
+            levels.push(data.get(offset..offset + size)?.to_vec());
+            offset += size;
+        }
+        if offset != data.len() {
+            return None;
+        }
+
+        Some(Self {
+            format,
+            width,
+            height,
+            levels,
+            opaque,
+        })
+    }
+}
+
+fn full_mip_levels(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+fn for_each_block(
+    format: BcFormat,
+    data: &[u8],
+    width: u32,
+    height: u32,
+    mut f: impl FnMut(u32, u32, &[u8]),
+) {
+    let blocks_x = width.div_ceil(4).max(1);
+    let blocks_y = height.div_ceil(4).max(1);
+    let block_bytes = format.block_bytes();
+    for by in 0..blocks_y {
+        for bx in 0..blocks_x {
+            let start = ((by * blocks_x + bx) as usize) * block_bytes;
+            if let Some(block) = data.get(start..start + block_bytes) {
+                f(bx, by, block);
+            }
+        }
+    }
+}
+
+fn expand_565(c: u16) -> [u8; 3] {
+    let r = ((c >> 11) & 0x1f) as u8;
+    let g = ((c >> 5) & 0x3f) as u8;
+    let b = (c & 0x1f) as u8;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+}
+
+fn pack_565(rgb: [u8; 3]) -> u16 {
+    let r = (rgb[0] as u16 * 31 + 127) / 255;
+    let g = (rgb[1] as u16 * 63 + 127) / 255;
+    let b = (rgb[2] as u16 * 31 + 127) / 255;
+    (r << 11) | (g << 5) | b
+}
+
+/// The four palette entries of a color block. `punch_through` enables
+/// BC1's 3-color mode (transparent black) when `c0 <= c1`; BC2/BC3
+/// color blocks always interpolate four colors.
+fn color_palette(block: &[u8], punch_through: bool) -> [[u8; 4]; 4] {
+    let c0 = u16::from_le_bytes([block[0], block[1]]);
+    let c1 = u16::from_le_bytes([block[2], block[3]]);
+    let (a, b) = (expand_565(c0), expand_565(c1));
+    let mix = |wa: u16, wb: u16, div: u16| -> [u8; 4] {
+        [
+            ((a[0] as u16 * wa + b[0] as u16 * wb) / div) as u8,
+            ((a[1] as u16 * wa + b[1] as u16 * wb) / div) as u8,
+            ((a[2] as u16 * wa + b[2] as u16 * wb) / div) as u8,
+            255,
+        ]
+    };
+    let p0 = [a[0], a[1], a[2], 255];
+    let p1 = [b[0], b[1], b[2], 255];
+    if c0 > c1 || !punch_through {
+        [p0, p1, mix(2, 1, 3), mix(1, 2, 3)]
+    } else {
+        [p0, p1, mix(1, 1, 2), [0, 0, 0, 0]]
+    }
+}
+
+fn decode_color(block: &[u8], punch_through: bool) -> [[u8; 4]; 16] {
+    let palette = color_palette(block, punch_through);
+    let indices = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+    std::array::from_fn(|i| palette[((indices >> (i * 2)) & 3) as usize])
+}
+
+fn bc3_alpha_palette(a0: u8, a1: u8) -> [u8; 8] {
+    let (a0w, a1w) = (a0 as u16, a1 as u16);
+    let mut palette = [a0, a1, 0, 0, 0, 0, 0, 255];
+    if a0 > a1 {
+        for i in 1..7u16 {
+            palette[i as usize + 1] = (((7 - i) * a0w + i * a1w) / 7) as u8;
+        }
+    } else {
+        for i in 1..5u16 {
+            palette[i as usize + 1] = (((5 - i) * a0w + i * a1w) / 5) as u8;
+        }
+    }
+    palette
+}
+
+fn decode_block_alpha(format: BcFormat, block: &[u8]) -> [u8; 16] {
+    match format {
+        BcFormat::Bc1 => decode_color(block, true).map(|texel| texel[3]),
+        BcFormat::Bc2 => std::array::from_fn(|i| ((block[i / 2] >> ((i % 2) * 4)) & 0xf) * 17),
+        BcFormat::Bc3 => {
+            let palette = bc3_alpha_palette(block[0], block[1]);
+            let mut bits = [0u8; 8];
+            bits[..6].copy_from_slice(&block[2..8]);
+            let indices = u64::from_le_bytes(bits);
+            std::array::from_fn(|i| palette[((indices >> (i * 3)) & 7) as usize])
+        }
+    }
+}
+
+fn decode_block(format: BcFormat, block: &[u8]) -> [[u8; 4]; 16] {
+    match format {
+        BcFormat::Bc1 => decode_color(block, true),
+        BcFormat::Bc2 | BcFormat::Bc3 => {
+            let mut texels = decode_color(&block[8..], false);
+            let alpha = decode_block_alpha(format, block);
+            for (texel, a) in texels.iter_mut().zip(alpha) {
+                texel[3] = a;
+            }
+            texels
+        }
+    }
+}
+
+/// Box-filter `source` down to `width`×`height` (each at most half the
+/// source extent, at least 1).
+fn downsample(source: &RgbaImage, width: u32, height: u32) -> RgbaImage {
+    let sx = (source.width() / width).max(1);
+    let sy = (source.height() / height).max(1);
+    RgbaImage::from_fn(width, height, |x, y| {
+        let mut sum = [0u32; 4];
+        for dy in 0..sy {
+            for dx in 0..sx {
+                let px = source.get_pixel(
+                    (x * sx + dx).min(source.width() - 1),
+                    (y * sy + dy).min(source.height() - 1),
+                );
+                for (sum, value) in sum.iter_mut().zip(px.0) {
+                    *sum += value as u32;
+                }
+            }
+        }
+        let n = sx * sy;
+        Rgba(sum.map(|s| ((s + n / 2) / n) as u8))
+    })
+}
+
+fn encode(format: BcFormat, image: &RgbaImage) -> Vec<u8> {
+    let (w, h) = image.dimensions();
+    let mut out = Vec::with_capacity(level_size(format, w, h));
+    for by in 0..h.div_ceil(4).max(1) {
+        for bx in 0..w.div_ceil(4).max(1) {
+            // Edge blocks repeat the last row/column.
+            let texels: [[u8; 4]; 16] = std::array::from_fn(|i| {
+                let x = (bx * 4 + i as u32 % 4).min(w - 1);
+                let y = (by * 4 + i as u32 / 4).min(h - 1);
+                image.get_pixel(x, y).0
+            });
+            match format {
+                BcFormat::Bc1 => out.extend_from_slice(&encode_color(&texels)),
+                BcFormat::Bc2 => {
+                    let mut alpha = [0u8; 8];
+                    for (i, texel) in texels.iter().enumerate() {
+                        let a = ((texel[3] as u16 * 15 + 127) / 255) as u8;
+                        alpha[i / 2] |= a << ((i % 2) * 4);
+                    }
+                    out.extend_from_slice(&alpha);
+                    out.extend_from_slice(&encode_color(&texels));
+                }
+                BcFormat::Bc3 => {
+                    out.extend_from_slice(&encode_bc3_alpha(&texels));
+                    out.extend_from_slice(&encode_color(&texels));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Range-fit color block in 4-color mode: endpoints at the corners of
+/// the texels' bounding box along the diagonal that follows their
+/// correlation, each texel snapped to the nearest palette entry.
+fn encode_color(texels: &[[u8; 4]; 16]) -> [u8; 8] {
+    let mut lo = [255u8; 3];
+    let mut hi = [0u8; 3];
+    let mut mean = [0i32; 3];
+    for texel in texels {
+        for c in 0..3 {
+            lo[c] = lo[c].min(texel[c]);
+            hi[c] = hi[c].max(texel[c]);
+            mean[c] += texel[c] as i32;
+        }
+    }
+    let mean = mean.map(|m| m / 16);
+
+    // Flip every channel that runs against the widest one, so the
+    // endpoints span the anti-diagonal when the texels do.
+    let principal = (0..3).max_by_key(|&c| hi[c] - lo[c]).unwrap();
+    for c in (0..3).filter(|&c| c != principal) {
+        let covariance: i32 = texels
+            .iter()
+            .map(|t| (t[c] as i32 - mean[c]) * (t[principal] as i32 - mean[principal]))
+            .sum();
+        if covariance < 0 {
+            std::mem::swap(&mut lo[c], &mut hi[c]);
+        }
+    }
+
+    let (mut c0, mut c1) = (pack_565(hi), pack_565(lo));
+    let mut block = [0u8; 8];
+    if c0 == c1 {
+        block[0..2].copy_from_slice(&c0.to_le_bytes());
+        block[2..4].copy_from_slice(&c1.to_le_bytes());
+        return block;
+    }
+    if c0 < c1 {
+        std::mem::swap(&mut c0, &mut c1);
+    }
+    block[0..2].copy_from_slice(&c0.to_le_bytes());
+    block[2..4].copy_from_slice(&c1.to_le_bytes());
+
+    let palette = color_palette(&block, false);
+    let mut indices = 0u32;
+    for (i, texel) in texels.iter().enumerate() {
+        let best = (0..4)
+            .min_by_key(|&p| {
+                (0..3)
+                    .map(|c| (palette[p][c] as i32 - texel[c] as i32).pow(2))
+                    .sum::<i32>()
+            })
+            .unwrap();
+        indices |= (best as u32) << (i * 2);
+    }
+    block[4..8].copy_from_slice(&indices.to_le_bytes());
+    block
+}
+
+fn encode_bc3_alpha(texels: &[[u8; 4]; 16]) -> [u8; 8] {
+    let lo = texels.iter().map(|t| t[3]).min().unwrap();
+    let hi = texels.iter().map(|t| t[3]).max().unwrap();
+    let mut block = [0u8; 8];
+    block[0] = hi;
+    block[1] = lo;
+    if hi == lo {
+        return block;
+    }
+
+    let palette = bc3_alpha_palette(hi, lo);
+    let mut indices = 0u64;
+    for (i, texel) in texels.iter().enumerate() {
+        let best = (0..8)
+            .min_by_key(|&p| (palette[p] as i32 - texel[3] as i32).abs())
+            .unwrap();
+        indices |= (best as u64) << (i * 3);
+    }
+    block[2..8].copy_from_slice(&indices.to_le_bytes()[..6]);
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dds(four_cc: &[u8; 4], width: u32, height: u32, levels: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = vec![0u8; 128];
+        data[0..4].copy_from_slice(b"DDS ");
+        data[4..8].copy_from_slice(&124u32.to_le_bytes());
+        data[12..16].copy_from_slice(&height.to_le_bytes());
+        data[16..20].copy_from_slice(&width.to_le_bytes());
+        data[28..32].copy_from_slice(&(levels.len() as u32).to_le_bytes());
+        data[80..84].copy_from_slice(&4u32.to_le_bytes());
+        data[84..88].copy_from_slice(four_cc);
+        for level in levels {
+            data.extend_from_slice(level);
+        }
+        data
+    }
+
+    /// Colors along one line through RGB space, which is what BCn
+    /// represents well within a block.
+    fn gradient(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            let v = ((x * 4 + y * 8) % 256) as u8;
+            Rgba([v, 255 - v, v / 2, 255])
+        })
+    }
+
+    #[test]
+    fn bc1_solid_block_decodes_exactly() {
+        // Pure red in both endpoints; every index picks c0.
+        let red = 0xf800u16.to_le_bytes();
+        let block = vec![red[0], red[1], red[0], red[1], 0, 0, 0, 0];
+        let image = CompressedImage::from_dds(&dds(b"DXT1", 4, 4, &[block]))
+            .unwrap()
+            .decode();
+        assert!(image.pixels().all(|px| px.0 == [255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn bc1_punch_through_is_transparent() {
+        // c0 <= c1 selects 3-color mode; index 3 is transparent black.
+        let block = vec![0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+        let image = CompressedImage::from_dds(&dds(b"DXT1", 4, 4, &[block])).unwrap();
+        assert!(image.alpha_values().iter().all(|&a| a == 0));
+        assert!(image.decode().pixels().all(|px| px.0 == [0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn bc3_alpha_interpolates() {
+        let mut block = vec![255, 0];
+        // Index 0 for the first texel (a0), index 1 for the rest (a1).
+        let indices: u64 = (1..16).fold(0, |acc, i| acc | (1u64 << (i * 3)));
+        block.extend_from_slice(&indices.to_le_bytes()[..6]);
+        block.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]);
+        let alpha = CompressedImage::from_dds(&dds(b"DXT5", 4, 4, &[block]))
+            .unwrap()
+            .alpha_values();
+        assert_eq!(alpha[0], 255);
+        assert!(alpha[1..].iter().all(|&a| a == 0));
+    }
+
+    #[test]
+    fn encoder_roundtrips_within_tolerance() {
+        let source = gradient(16, 16);
+        for format in [BcFormat::Bc1, BcFormat::Bc2, BcFormat::Bc3] {
+            let image = CompressedImage {
+                format,
+                width: 16,
+                height: 16,
+                levels: vec![encode(format, &source)],
+                opaque: false,
+            };
+            let decoded = image.decode();
+            for (a, b) in decoded.pixels().zip(source.pixels()) {
+                for c in 0..4 {
+                    assert!(
+                        (a.0[c] as i32 - b.0[c] as i32).abs() <= 24,
+                        "{:?}: {:?} vs {:?}",
+                        format,
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn missing_mips_are_generated() {
+        let level0 = encode(BcFormat::Bc3, &gradient(16, 8));
+        let image = CompressedImage::from_dds(&dds(b"DXT5", 16, 8, &[level0]))
+            .unwrap()
+            .with_full_mip_chain();
+        assert_eq!(image.mip_levels(), 5);
+        for (level, data) in image.levels().iter().enumerate() {
+            let (w, h) = level_extent(16, 8, level);
+            assert_eq!(data.len(), level_size(BcFormat::Bc3, w, h));
+        }
+    }
+
+    #[test]
+    fn truncated_chain_keeps_whole_levels() {
+        let level0 = encode(BcFormat::Bc1, &gradient(8, 8));
+        let mut data = dds(b"DXT1", 8, 8, &[level0]);
+        data[28..32].copy_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(&[0; 3]);
+        let image = CompressedImage::from_dds(&data).unwrap();
+        assert_eq!(image.mip_levels(), 1);
+    }
+
+    #[test]
+    fn opaque_flag_forces_alpha() {
+        let block = vec![0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+        let mut image = CompressedImage::from_dds(&dds(b"DXT1", 4, 4, &[block])).unwrap();
+        image.set_opaque(true);
+        assert!(image.alpha_values().iter().all(|&a| a == 255));
+        assert!(image.decode().pixels().all(|px| px.0[3] == 255));
+    }
+
+    #[test]
+    fn rgba_picks_format_by_alpha() {
+        let opaque = CompressedImage::from_rgba(&gradient(16, 8)).unwrap();
+        assert_eq!(opaque.format(), BcFormat::Bc1);
+        assert!(opaque.has_full_mip_chain());
+        for (level, data) in opaque.levels().iter().enumerate() {
+            let (w, h) = level_extent(16, 8, level);
+            assert_eq!(data.len(), level_size(BcFormat::Bc1, w, h));
+        }
+
+        let mut translucent = gradient(6, 6);
+        translucent.put_pixel(2, 3, Rgba([10, 20, 30, 128]));
+        let image = CompressedImage::from_rgba(&translucent).unwrap();
+        assert_eq!(image.format(), BcFormat::Bc3);
+        assert_eq!(image.decode().dimensions(), (6, 6));
+        assert!(CompressedImage::from_rgba(&RgbaImage::new(0, 0)).is_none());
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let image = CompressedImage::from_dds(&dds(
+            b"DXT5",
+            8,
+            8,
+            &[encode(BcFormat::Bc3, &gradient(8, 8))],
+        ))
+        .unwrap()
+        .with_full_mip_chain();
+        let restored = CompressedImage::from_bytes(&image.to_bytes()).unwrap();
+        assert_eq!(restored.format(), BcFormat::Bc3);
+        assert_eq!(restored.levels(), image.levels());
+        assert!(CompressedImage::from_bytes(&image.to_bytes()[..20]).is_none());
+    }
+
+    #[test]
+    fn non_bc_dds_is_rejected() {
+        assert!(CompressedImage::from_dds(&dds(b"DX10", 4, 4, &[vec![0; 16]])).is_none());
+        assert!(CompressedImage::from_dds(b"not a dds").is_none());
+    }
+}
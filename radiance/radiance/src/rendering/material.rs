@@ -1,8 +1,14 @@
 use image::ImageFormat;
 
+use crate::asset::cache;
 use crate::rendering::texture::TextureStore;
 
-use super::{ShaderProgram, sampler::SamplerDef, texture::TextureDef};
+use super::{
+    ShaderProgram,
+    compressed_texture::CompressedImage,
+    sampler::SamplerDef,
+    texture::{TextureData, TextureDef},
+};
 use std::{io::Read, sync::Arc};
 
 /// Color-blend mode for a material. Today every variant maps to a distinct
@@ -470,18 +476,9 @@ impl SimpleMaterialDef {
         get_reader: impl FnOnce(&str) -> Option<R>,
         sampler: SamplerDef,
     ) -> MaterialDef {
-        let texture =
-            TextureStore::get_or_update(texture_name, || match get_reader(texture_name) {
-                Some(mut r) => {
-                    let mut buf = Vec::new();
-                    r.read_to_end(&mut buf).unwrap();
-                    image::load_from_memory(&buf)
-                        .or_else(|_| image::load_from_memory_with_format(&buf, ImageFormat::Tga))
-                        .and_then(|img| Ok(img.to_rgba8()))
-                        .ok()
-                }
-                _ => None,
-            });
+        let texture = TextureStore::get_or_update(texture_name, || {
+            read_texture_data(get_reader(texture_name))
+        });
 
         Self::create_internal(texture, sampler)
     }
@@ -539,18 +536,100 @@ impl SimpleMaterialDef {
     }
 }
 
+/// Version of [`decode_texture`]'s output, part of its
+/// [`cache::CacheKey`]. Bump it when decoding changes so stale cache
+/// entries stop matching.
+const TEXTURE_DECODE_VERSION: u32 = 1;
+
 /// Decode raw texture bytes (DDS / TGA / PNG / …) into an `RgbaImage`,
-/// trying the auto-detected format first and falling back to TGA. Shared
-/// by [`SimpleMaterialDef::create2`] and
-/// [`SimpleMaterialDef::create2_opaque`].
-fn decode_texture_data(data: Option<Vec<u8>>) -> Option<image::RgbaImage> {
-    let data = data?;
-    image::load_from_memory(&data)
-        .or_else(|_| image::load_from_memory_with_format(&data, ImageFormat::Tga))
+/// trying the auto-detected format first and falling back to TGA. The
+/// result goes through the on-disk asset cache when one is installed.
+/// Loaders that post-process a texture before building its material
+/// (e.g. mask compositing) should decode through here too.
+pub fn decode_texture(data: &[u8]) -> Option<image::RgbaImage> {
+    cache::cached_image("texture", TEXTURE_DECODE_VERSION, data, || {
+        decode_texture_uncached(data)
+    })
+}
+
+fn decode_texture_uncached(data: &[u8]) -> Option<image::RgbaImage> {
+    image::load_from_memory(data)
+        .or_else(|_| image::load_from_memory_with_format(data, ImageFormat::Tga))
         .map(|img| img.to_rgba8())
         .ok()
 }
 
+/// Version of the mip chains [`decode_compressed_texture`] generates.
+const COMPRESSED_MIP_VERSION: u32 = 1;
+
+/// Parse DXTn `.dds` bytes into a [`CompressedImage`] with a full mip
+/// chain. Chains the file leaves short are completed on the CPU, which
+/// is slow enough to be worth keeping in the asset cache.
+fn decode_compressed_texture(data: &[u8]) -> Option<CompressedImage> {
+    let image = CompressedImage::from_dds(data)?;
+    if image.has_full_mip_chain() {
+        return Some(image);
+    }
+
+    let Some(cache) = cache::global() else {
+        return Some(image.with_full_mip_chain());
+    };
+
+    let key = cache::CacheKey::new("bc_texture", COMPRESSED_MIP_VERSION, &[data]);
+    if let Some(cached) = cache
+        .get("bc_texture", &key)
+        .and_then(|bytes| CompressedImage::from_bytes(&bytes))
+    {
+        return Some(cached);
+    }
+
+    let image = image.with_full_mip_chain();
+    cache.put("bc_texture", &key, &image.to_bytes());
+    Some(image)
+}
+
+/// Version of the blocks [`encode_compressed_texture`] produces.
+const BC_ENCODE_VERSION: u32 = 1;
+
+/// Block-compress a non-DDS texture when the installed cache is set to
+/// store textures compressed. The encode is slow, so it runs once per
+/// source file and the blocks are served from the cache afterwards;
+/// the RGBA decode it starts from is not cached separately. `None`
+/// when the option is off or the data doesn't decode.
+fn encode_compressed_texture(data: &[u8]) -> Option<CompressedImage> {
+    let cache = cache::global().filter(|cache| cache.compresses_textures())?;
+    let key = cache::CacheKey::new("bc_encoded", BC_ENCODE_VERSION, &[data]);
+    if let Some(cached) = cache
+        .get("bc_encoded", &key)
+        .and_then(|bytes| CompressedImage::from_bytes(&bytes))
+    {
+        return Some(cached);
+    }
+
+    let image = CompressedImage::from_rgba(&decode_texture_uncached(data)?)?;
+    cache.put("bc_encoded", &key, &image.to_bytes());
+    Some(image)
+}
+
+/// Decode texture bytes, keeping DXTn `.dds` block-compressed so the
+/// backend can upload the blocks as-is. Other formats are
+/// block-compressed too when the cache's `compress_textures` option is
+/// on, and expanded to RGBA otherwise.
+fn decode_texture_data(data: Option<Vec<u8>>) -> Option<TextureData> {
+    let data = data?;
+    decode_compressed_texture(&data)
+        .or_else(|| encode_compressed_texture(&data))
+        .map(TextureData::Compressed)
+        .or_else(|| decode_texture(&data).map(TextureData::Rgba))
+}
+
+/// [`decode_texture_data`] over everything `reader` yields.
+fn read_texture_data<R: Read>(reader: Option<R>) -> Option<TextureData> {
+    let mut buf = Vec::new();
+    reader?.read_to_end(&mut buf).unwrap();
+    decode_texture_data(Some(buf))
+}
+
 /// Builds a PAL5 grass-wind material ([`ShaderProgram::GrassWind`]). The vertex
 /// stage sways geometry by the second texcoord set (`inTexCoord2.x`: 0 root
 /// pinned, 1 tip moves) over the per-frame `time` uniform. `wind_strength` is
@@ -709,18 +788,9 @@ impl LitMaterialDef {
         get_reader: impl FnOnce(&str) -> Option<R>,
         sampler: SamplerDef,
     ) -> MaterialDef {
-        let texture =
-            TextureStore::get_or_update(texture_name, || match get_reader(texture_name) {
-                Some(mut r) => {
-                    let mut buf = Vec::new();
-                    r.read_to_end(&mut buf).unwrap();
-                    image::load_from_memory(&buf)
-                        .or_else(|_| image::load_from_memory_with_format(&buf, ImageFormat::Tga))
-                        .and_then(|img| Ok(img.to_rgba8()))
-                        .ok()
-                }
-                _ => None,
-            });
+        let texture = TextureStore::get_or_update(texture_name, || {
+            read_texture_data(get_reader(texture_name))
+        });
 
         MaterialDef::builder(ShaderProgram::TexturedDynamicLit)
             .debug_name("lit_material")
@@ -764,18 +834,9 @@ impl Pal3ActorMaterialDef {
         get_reader: impl FnOnce(&str) -> Option<R>,
         sampler: SamplerDef,
     ) -> MaterialDef {
-        let texture =
-            TextureStore::get_or_update(texture_name, || match get_reader(texture_name) {
-                Some(mut r) => {
-                    let mut buf = Vec::new();
-                    r.read_to_end(&mut buf).unwrap();
-                    image::load_from_memory(&buf)
-                        .or_else(|_| image::load_from_memory_with_format(&buf, ImageFormat::Tga))
-                        .and_then(|img| Ok(img.to_rgba8()))
-                        .ok()
-                }
-                _ => None,
-            });
+        let texture = TextureStore::get_or_update(texture_name, || {
+            read_texture_data(get_reader(texture_name))
+        });
 
         MaterialDef::builder(ShaderProgram::Pal3Actor)
             .debug_name("pal3_actor_material")
@@ -794,18 +855,9 @@ impl Pal3GeomMaterialDef {
         texture_name: &str,
         get_reader: impl FnOnce(&str) -> Option<R>,
     ) -> MaterialDef {
-        let texture =
-            TextureStore::get_or_update(texture_name, || match get_reader(texture_name) {
-                Some(mut r) => {
-                    let mut buf = Vec::new();
-                    r.read_to_end(&mut buf).unwrap();
-                    image::load_from_memory(&buf)
-                        .or_else(|_| image::load_from_memory_with_format(&buf, ImageFormat::Tga))
-                        .and_then(|img| Ok(img.to_rgba8()))
-                        .ok()
-                }
-                _ => None,
-            });
+        let texture = TextureStore::get_or_update(texture_name, || {
+            read_texture_data(get_reader(texture_name))
+        });
 
         MaterialDef::builder(ShaderProgram::Pal3Geom)
             .debug_name("pal3_geom_material")
@@ -823,18 +875,9 @@ impl Pal3PropMaterialDef {
         texture_name: &str,
         get_reader: impl FnOnce(&str) -> Option<R>,
     ) -> MaterialDef {
-        let texture =
-            TextureStore::get_or_update(texture_name, || match get_reader(texture_name) {
-                Some(mut r) => {
-                    let mut buf = Vec::new();
-                    r.read_to_end(&mut buf).unwrap();
-                    image::load_from_memory(&buf)
-                        .or_else(|_| image::load_from_memory_with_format(&buf, ImageFormat::Tga))
-                        .and_then(|img| Ok(img.to_rgba8()))
-                        .ok()
-                }
-                _ => None,
-            });
+        let texture = TextureStore::get_or_update(texture_name, || {
+            read_texture_data(get_reader(texture_name))
+        });
 
         MaterialDef::builder(ShaderProgram::Pal3Prop)
             .debug_name("pal3_prop_material")
@@ -1,3 +1,4 @@
+mod compressed_texture;
 mod engine;
 mod factory;
 mod material;
@@ -18,13 +19,14 @@ mod vulkan;
 #[cfg(vitagl)]
 mod vitagl;
 
+pub use compressed_texture::{BcFormat, CompressedImage};
 pub use engine::{CapturedFrame, RenderingEngine};
 pub use factory::ComponentFactory;
 pub use material::{
     BlendMode, CullMode, DepthMode, GradientYMaterialDef, GrassMaterialDef, LightMapMaterialDef,
     LitMaterialDef, MaterialDef, MaterialDefBuilder, MaterialKey, MaterialParams,
     Pal3ActorMaterialDef, Pal3GeomMaterialDef, Pal3PropMaterialDef, SimpleMaterialDef,
-    TerrainLayer, TerrainSplatMaterialDef,
+    TerrainLayer, TerrainSplatMaterialDef, decode_texture,
 };
 pub use platform::Window;
 pub use render_object::{RenderObject, RenderObjectHandle};
@@ -33,7 +35,7 @@ pub use rendering_component::RenderingComponent;
 pub use sampler::{AddressMode, FilterMode, MipmapMode, SamplerDef};
 pub use shader::{Shader, ShaderProgram};
 pub use sprite::Sprite;
-pub use texture::{AlphaKind, Texture, TextureDef, TextureStore};
+pub use texture::{AlphaKind, Texture, TextureData, TextureDef, TextureStore};
 pub use vertex_buffer::{VertexBuffer, VertexComponents};
 pub use video_player::VideoPlayer;
 
@@ -15,12 +15,11 @@
 //! (LINEAR + REPEAT in all axes), so any caller that doesn't supply a
 //! `SamplerDef` keeps the legacy behavior bit-for-bit.
 //!
-//! Mipmap support is intentionally minimal: the field is carried but
-//! the Vulkan texture pipeline does not generate mip levels yet, so
-//! the chosen `MipmapMode` only takes effect once mip generation is
-//! wired up. `From<rwbs filter_mode>` collapses RW's MIP_* values to
-//! the matching non-mipped filter to avoid wrongly sampling a
-//! single-level image with a mip-aware sampler.
+//! Every texture is bound with a full mip chain (blitted on upload for
+//! RGBA, taken from the file or built on the CPU for block-compressed
+//! data), so `MipmapMode::Linear` gives trilinear filtering. On top of
+//! that `max_anisotropy` requests anisotropic filtering; the backend
+//! clamps it to what the device supports.
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
 pub enum FilterMode {
@@ -50,6 +49,8 @@ pub struct SamplerDef {
     pub address_u: AddressMode,
     pub address_v: AddressMode,
     pub address_w: AddressMode,
+    /// Requested anisotropy level; 1 disables anisotropic filtering.
+    pub max_anisotropy: u8,
 }
 
 impl SamplerDef {
@@ -60,8 +61,12 @@ impl SamplerDef {
         address_u: AddressMode::Repeat,
         address_v: AddressMode::Repeat,
         address_w: AddressMode::Repeat,
+        max_anisotropy: Self::DEFAULT_ANISOTROPY,
     };
 
+    /// Anisotropy level used unless a caller asks otherwise.
+    pub const DEFAULT_ANISOTROPY: u8 = 16;
+
     /// Sampler for UI / imgui textures (sprites, 9-slice chrome, video,
     /// render-target previews): LINEAR filtering with CLAMP_TO_EDGE on
     /// every axis. UI sprites are drawn edge-to-edge with `[0,1]` UVs, so
@@ -79,6 +84,7 @@ impl SamplerDef {
             address_u: address,
             address_v: address,
             address_w: address,
+            max_anisotropy: Self::DEFAULT_ANISOTROPY,
         }
     }
 
@@ -94,15 +100,28 @@ impl SamplerDef {
             address_u,
             address_v,
             address_w: AddressMode::Repeat,
+            max_anisotropy: Self::DEFAULT_ANISOTROPY,
         }
     }
 
+    pub const fn with_mipmap_mode(mut self, mipmap_mode: MipmapMode) -> Self {
+        self.mipmap_mode = mipmap_mode;
+        self
+    }
+
+    /// Request `max_anisotropy` (1 turns anisotropic filtering off).
+    pub const fn with_anisotropy(mut self, max_anisotropy: u8) -> Self {
+        self.max_anisotropy = max_anisotropy;
+        self
+    }
+
     pub fn uses_anisotropy(&self) -> bool {
         // Per Vulkan spec validation, samplerAnisotropy must be disabled
         // when either mag or min filter is NEAREST. Pixel-art-style
         // assets that explicitly request NEAREST also look better
         // without anisotropic filtering.
-        matches!(self.mag_filter, FilterMode::Linear)
+        self.max_anisotropy > 1
+            && matches!(self.mag_filter, FilterMode::Linear)
             && matches!(self.min_filter, FilterMode::Linear)
     }
 }
@@ -4,6 +4,8 @@ use std::sync::{Arc, Mutex, RwLock};
 use image::RgbaImage;
 use lru::LruCache;
 
+use super::compressed_texture::CompressedImage;
+
 pub trait Texture {
     fn width(&self) -> u32;
     fn height(&self) -> u32;
@@ -59,6 +61,49 @@ const BLEND_GRADED_FRACTION_MIN: f32 = 0.05;
 /// guards the classification from both directions.
 const BLEND_OPAQUE_FRACTION_MAX: f32 = 0.5;
 
+/// CPU-side pixels of a [`TextureDef`]: expanded RGBA, or BCn blocks
+/// the backend can upload as-is (see [`CompressedImage`]).
+pub enum TextureData {
+    Rgba(RgbaImage),
+    Compressed(CompressedImage),
+}
+
+impl TextureData {
+    pub fn width(&self) -> u32 {
+        match self {
+            TextureData::Rgba(image) => image.width(),
+            TextureData::Compressed(image) => image.width(),
+        }
+    }
+
+    pub fn height(&self) -> u32 {
+        match self {
+            TextureData::Rgba(image) => image.height(),
+            TextureData::Compressed(image) => image.height(),
+        }
+    }
+
+    /// Expand to RGBA, decoding compressed blocks on the CPU.
+    pub fn into_rgba(self) -> RgbaImage {
+        match self {
+            TextureData::Rgba(image) => image,
+            TextureData::Compressed(image) => image.decode(),
+        }
+    }
+}
+
+impl From<RgbaImage> for TextureData {
+    fn from(image: RgbaImage) -> Self {
+        TextureData::Rgba(image)
+    }
+}
+
+impl From<CompressedImage> for TextureData {
+    fn from(image: CompressedImage) -> Self {
+        TextureData::Compressed(image)
+    }
+}
+
 pub struct TextureDef {
     name: String,
     /// The decoded CPU-side image. Backends consume this exactly once,
@@ -75,7 +120,11 @@ pub struct TextureDef {
     /// is safe to drain because `VulkanTextureStore` already caches
     /// `Rc<VulkanTexture>` by name, so the upload runs at most once
     /// per `TextureDef`.
-    image: Mutex<Option<RgbaImage>>,
+    ///
+    /// Compressed textures stay compressed here; [`with_image`] and
+    /// [`take_image`] decode them for backends that want RGBA, and
+    /// [`take_data`] hands them over as-is.
+    image: Mutex<Option<TextureData>>,
     alpha_kind: AlphaKind,
 }
 
@@ -89,13 +138,23 @@ impl TextureDef {
     /// (see [`take_image`]) or was never populated.
     pub fn with_image<R>(&self, f: impl FnOnce(Option<&RgbaImage>) -> R) -> R {
         let guard = self.image.lock().unwrap();
-        f(guard.as_ref())
+        match guard.as_ref() {
+            Some(TextureData::Rgba(image)) => f(Some(image)),
+            Some(TextureData::Compressed(image)) => f(Some(&image.decode())),
+            None => f(None),
+        }
     }
 
     /// Drain the cached `RgbaImage` out of the `TextureDef`, freeing
     /// its memory. Returns `None` on the second call (or if no image
     /// was ever set).
     pub fn take_image(&self) -> Option<RgbaImage> {
+        self.take_data().map(TextureData::into_rgba)
+    }
+
+    /// Like [`take_image`], but leaves compressed textures compressed
+    /// for backends that can sample BCn directly.
+    pub fn take_data(&self) -> Option<TextureData> {
         self.image.lock().unwrap().take()
     }
 
@@ -109,12 +168,15 @@ fn classify_alpha(image: Option<&RgbaImage>) -> AlphaKind {
         return AlphaKind::Opaque;
     };
 
+    classify_alpha_values(img.pixels().map(|px| px.0[3]))
+}
+
+fn classify_alpha_values(alpha: impl Iterator<Item = u8>) -> AlphaKind {
     let mut graded_count: u64 = 0; // alpha in 32..=223
     let mut non_opaque_count: u64 = 0; // alpha < 255
     let mut total: u64 = 0;
-    for px in img.pixels() {
+    for a in alpha {
         total += 1;
-        let a = px.0[3];
         if a == 255 {
             continue;
         }
@@ -184,9 +246,9 @@ lazy_static::lazy_static! {
 
 pub struct TextureStore;
 impl TextureStore {
-    pub fn get_or_update(
+    pub fn get_or_update<T: Into<TextureData>>(
         name: &str,
-        update: impl FnOnce() -> Option<RgbaImage>,
+        update: impl FnOnce() -> Option<T>,
     ) -> Arc<TextureDef> {
         Self::get_or_update_inner(name, update, AlphaHandling::Classify)
     }
@@ -202,9 +264,9 @@ impl TextureStore {
     /// tag them `Blend`/`Cutout` and `premultiply_alpha` would darken
     /// their RGB (often to near-black), even though the material renders
     /// opaque.
-    pub fn get_or_update_opaque(
+    pub fn get_or_update_opaque<T: Into<TextureData>>(
         name: &str,
-        update: impl FnOnce() -> Option<RgbaImage>,
+        update: impl FnOnce() -> Option<T>,
     ) -> Arc<TextureDef> {
         Self::get_or_update_inner(name, update, AlphaHandling::ForceOpaque)
     }
@@ -218,16 +280,16 @@ impl TextureStore {
     /// R/G/B/A each hold a layer's blend weight. Both `get_or_update`
     /// (premultiply) and `get_or_update_opaque` (force alpha = 255) would
     /// corrupt such data; this path preserves every channel.
-    pub fn get_or_update_raw(
+    pub fn get_or_update_raw<T: Into<TextureData>>(
         name: &str,
-        update: impl FnOnce() -> Option<RgbaImage>,
+        update: impl FnOnce() -> Option<T>,
     ) -> Arc<TextureDef> {
         Self::get_or_update_inner(name, update, AlphaHandling::Raw)
     }
 
-    fn get_or_update_inner(
+    fn get_or_update_inner<T: Into<TextureData>>(
         name: &str,
-        update: impl FnOnce() -> Option<RgbaImage>,
+        update: impl FnOnce() -> Option<T>,
         alpha: AlphaHandling,
     ) -> Arc<TextureDef> {
         let mut store = TEXTURE_STORE.write().unwrap();
@@ -235,27 +297,13 @@ impl TextureStore {
         if let Some(t) = store.get(name) {
             t.clone()
         } else {
-            let mut image = update();
-            let alpha_kind = match alpha {
-                // Keep channels verbatim, classify Opaque (no premultiply).
-                AlphaHandling::Raw => AlphaKind::Opaque,
-                AlphaHandling::ForceOpaque => {
-                    if let Some(img) = image.as_mut() {
-                        for px in img.pixels_mut() {
-                            px.0[3] = 255;
-                        }
-                    }
-                    AlphaKind::Opaque
-                }
-                AlphaHandling::Classify => {
-                    let kind = classify_alpha(image.as_ref());
-                    if kind != AlphaKind::Opaque {
-                        if let Some(img) = image.as_mut() {
-                            premultiply_alpha(img);
-                        }
-                    }
-                    kind
+            let (image, alpha_kind) = match update().map(Into::into) {
+                Some(TextureData::Compressed(image)) => prepare_compressed(image, alpha),
+                Some(TextureData::Rgba(image)) => {
+                    let (image, kind) = prepare_rgba(Some(image), alpha);
+                    (image.map(TextureData::Rgba), kind)
                 }
+                None => (None, prepare_rgba(None, alpha).1),
             };
             let t = Arc::new(TextureDef {
                 name: name.to_string(),
@@ -268,6 +316,60 @@ impl TextureStore {
     }
 }
 
+fn prepare_rgba(
+    mut image: Option<RgbaImage>,
+    alpha: AlphaHandling,
+) -> (Option<RgbaImage>, AlphaKind) {
+    let alpha_kind = match alpha {
+        // Keep channels verbatim, classify Opaque (no premultiply).
+        AlphaHandling::Raw => AlphaKind::Opaque,
+        AlphaHandling::ForceOpaque => {
+            if let Some(img) = image.as_mut() {
+                for px in img.pixels_mut() {
+                    px.0[3] = 255;
+                }
+            }
+            AlphaKind::Opaque
+        }
+        AlphaHandling::Classify => {
+            let kind = classify_alpha(image.as_ref());
+            if kind != AlphaKind::Opaque {
+                if let Some(img) = image.as_mut() {
+                    premultiply_alpha(img);
+                }
+            }
+            kind
+        }
+    };
+    (image, alpha_kind)
+}
+
+/// Compressed counterpart of [`prepare_rgba`]. BCn blocks can't be
+/// premultiplied in place, so a texture that turns out to carry
+/// transparency is decoded and takes the RGBA path instead. Forcing
+/// alpha opaque is a flag on the image that backends honour when
+/// sampling.
+fn prepare_compressed(
+    mut image: CompressedImage,
+    alpha: AlphaHandling,
+) -> (Option<TextureData>, AlphaKind) {
+    match alpha {
+        AlphaHandling::ForceOpaque => {
+            image.set_opaque(true);
+            (Some(TextureData::Compressed(image)), AlphaKind::Opaque)
+        }
+        AlphaHandling::Classify | AlphaHandling::Raw => {
+            let kind = classify_alpha_values(image.alpha_values().into_iter());
+            if kind == AlphaKind::Opaque {
+                (Some(TextureData::Compressed(image)), AlphaKind::Opaque)
+            } else {
+                let (image, kind) = prepare_rgba(Some(image.decode()), alpha);
+                (image.map(TextureData::Rgba), kind)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -343,10 +445,53 @@ mod tests {
             Some(img)
         });
         assert_eq!(def.alpha_kind(), AlphaKind::Opaque);
-        let img = def.image.lock().unwrap();
-        let img = img.as_ref().expect("image present");
-        let px = img.get_pixel(0, 0);
+        let px = def.with_image(|img| *img.expect("image present").get_pixel(0, 0));
         // RGB preserved (not premultiplied down toward black), alpha forced.
         assert_eq!(px.0, [200, 180, 120, 255]);
     }
+
+    /// A 4×4 DXT5 `.dds` of one solid color and alpha.
+    fn solid_dxt5(rgb565: u16, alpha: u8) -> CompressedImage {
+        let mut data = vec![0u8; 128];
+        data[0..4].copy_from_slice(b"DDS ");
+        data[4..8].copy_from_slice(&124u32.to_le_bytes());
+        data[12..16].copy_from_slice(&4u32.to_le_bytes());
+        data[16..20].copy_from_slice(&4u32.to_le_bytes());
+        data[80..84].copy_from_slice(&4u32.to_le_bytes());
+        data[84..88].copy_from_slice(b"DXT5");
+        data.extend_from_slice(&[alpha, alpha, 0, 0, 0, 0, 0, 0]);
+        let c = rgb565.to_le_bytes();
+        data.extend_from_slice(&[c[0], c[1], c[0], c[1], 0, 0, 0, 0]);
+        CompressedImage::from_dds(&data).unwrap()
+    }
+
+    #[test]
+    fn opaque_compressed_texture_stays_compressed() {
+        let def = TextureStore::get_or_update("test::bc_opaque", || Some(solid_dxt5(0xf800, 255)));
+        assert_eq!(def.alpha_kind(), AlphaKind::Opaque);
+        assert!(matches!(def.take_data(), Some(TextureData::Compressed(_))));
+    }
+
+    #[test]
+    fn translucent_compressed_texture_is_premultiplied_rgba() {
+        let def = TextureStore::get_or_update("test::bc_blend", || Some(solid_dxt5(0xf800, 128)));
+        assert_eq!(def.alpha_kind(), AlphaKind::Blend);
+        let Some(TextureData::Rgba(img)) = def.take_data() else {
+            panic!("expected an RGBA fallback");
+        };
+        assert_eq!(img.get_pixel(0, 0).0, [128, 0, 0, 128]);
+    }
+
+    #[test]
+    fn force_opaque_compressed_texture_samples_opaque() {
+        let def = TextureStore::get_or_update_opaque("test::bc_force_opaque", || {
+            Some(solid_dxt5(0xf800, 42))
+        });
+        assert_eq!(def.alpha_kind(), AlphaKind::Opaque);
+        let Some(TextureData::Compressed(img)) = def.take_data() else {
+            panic!("expected compressed data");
+        };
+        assert!(img.is_opaque());
+        assert_eq!(img.decode().get_pixel(0, 0).0, [255, 0, 0, 255]);
+    }
 }
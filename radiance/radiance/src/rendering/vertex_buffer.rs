@@ -131,6 +131,23 @@ impl VertexBuffer {
         }
     }
 
+    /// Like [`Self::new_with_data_blob`], but `None` instead of a panic
+    /// when `data` isn't a whole number of vertices. For blobs read back
+    /// from disk.
+    pub fn try_new_with_data_blob(components: VertexComponents, data: Vec<u8>) -> Option<Self> {
+        let layout = VertexComponentsLayout::from_components(components);
+        if layout.size == 0 || data.len() % layout.size != 0 {
+            return None;
+        }
+
+        let count = data.len() / layout.size;
+        Some(Self {
+            layout,
+            data,
+            count,
+        })
+    }
+
     pub fn set_data(
         &mut self,
         index: usize,
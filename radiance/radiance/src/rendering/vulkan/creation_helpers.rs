@@ -108,6 +108,7 @@ pub fn create_device(
     instance: &Instance,
     physical_device: PhysicalDevice,
     graphic_queue_family_index: u32,
+    features: &vk::PhysicalDeviceFeatures,
 ) -> VkResult<Device> {
     let priorities = [0.5 as f32];
     let queue_create_info = vk::DeviceQueueCreateInfo::default()
@@ -115,7 +116,9 @@ pub fn create_device(
         .queue_priorities(&priorities);
     let extension_names = helpers::device_extension_names();
     let queue_create_info = [queue_create_info];
-    let physical_device_features = vk::PhysicalDeviceFeatures::default().sampler_anisotropy(true);
+    let physical_device_features = vk::PhysicalDeviceFeatures::default()
+        .sampler_anisotropy(features.sampler_anisotropy == vk::TRUE)
+        .texture_compression_bc(features.texture_compression_bc == vk::TRUE);
     let create_info = vk::DeviceCreateInfo::default()
         .queue_create_infos(&queue_create_info)
         .enabled_extension_names(&extension_names)
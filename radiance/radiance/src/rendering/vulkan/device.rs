@@ -30,6 +30,8 @@ pub struct Device {
     device: ash::Device,
     pipeline_cache: Cell<PipelineCache>,
     pipeline_cache_path: Option<PathBuf>,
+    supports_bc: bool,
+    max_sampler_anisotropy: f32,
 }
 
 impl Device {
@@ -38,12 +40,33 @@ impl Device {
         physical_device: PhysicalDevice,
         graphics_queue_family_index: u32,
     ) -> Self {
+        let (features, limits) = unsafe {
+            let vk_instance = instance.vk_instance();
+            (
+                vk_instance.get_physical_device_features(physical_device),
+                vk_instance
+                    .get_physical_device_properties(physical_device)
+                    .limits,
+            )
+        };
         let device = creation_helpers::create_device(
             &instance.vk_instance(),
             physical_device,
             graphics_queue_family_index,
+            &features,
         )
         .unwrap();
+        let supports_bc = features.texture_compression_bc == ash::vk::TRUE;
+        let max_sampler_anisotropy = if features.sampler_anisotropy == ash::vk::TRUE {
+            limits.max_sampler_anisotropy
+        } else {
+            1.
+        };
+        log::info!(
+            "Vulkan device: textureCompressionBC={}, maxSamplerAnisotropy={}",
+            supports_bc,
+            max_sampler_anisotropy
+        );
 
         let pipeline_cache_path = pipeline_cache_disk_path();
         let initial_data = pipeline_cache_path
@@ -76,6 +99,8 @@ impl Device {
             device,
             pipeline_cache: Cell::new(pipeline_cache),
             pipeline_cache_path,
+            supports_bc,
+            max_sampler_anisotropy,
         }
     }
 
@@ -83,6 +108,18 @@ impl Device {
         &self.device
     }
 
+    /// Whether BC1–BC3 images can be sampled directly. Without it,
+    /// compressed textures are decoded to RGBA before upload.
+    pub fn supports_bc(&self) -> bool {
+        self.supports_bc
+    }
+
+    /// Largest `max_anisotropy` a sampler may request; 1 when
+    /// anisotropic filtering is unavailable.
+    pub fn max_sampler_anisotropy(&self) -> f32 {
+        self.max_sampler_anisotropy
+    }
+
     pub fn get_device_queue(&self, queue_family_index: u32, queue_index: u32) -> Queue {
         unsafe {
             self.device
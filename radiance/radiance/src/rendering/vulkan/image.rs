@@ -48,6 +48,25 @@ impl Image {
         )
     }
 
+    /// Block-compressed texture image (`BC1`–`BC3`). The whole mip
+    /// chain is uploaded from the source, so no blit usage is needed.
+    pub fn new_compressed_image(
+        allocator: &Rc<vk_mem::Allocator>,
+        tex_width: u32,
+        tex_height: u32,
+        format: vk::Format,
+        mip_levels: u32,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::new(
+            allocator,
+            tex_width,
+            tex_height,
+            format,
+            vk::ImageUsageFlags::TRANSFER_DST | vk::ImageUsageFlags::SAMPLED,
+            mip_levels,
+        )
+    }
+
     /// Color image suitable as an offscreen render target: usable as a
     /// `COLOR_ATTACHMENT` and as a `SAMPLED` texture in subsequent passes
     /// (e.g. imgui sampling the result inside the editor preview).
@@ -451,6 +470,44 @@ impl Image {
         )
     }
 
+    /// Record a copy of one tightly packed mip level starting at
+    /// `buffer_offset` into `buffer`. The level must already be in
+    /// `TRANSFER_DST_OPTIMAL`.
+    pub fn record_copy_level_from(
+        &mut self,
+        buffer: &Buffer,
+        buffer_offset: u64,
+        level: u32,
+        device: &Rc<super::device::Device>,
+        command_buffer: &vk::CommandBuffer,
+    ) {
+        let region = vk::BufferImageCopy::default()
+            .image_extent(
+                vk::Extent3D::default()
+                    .width((self.width >> level).max(1))
+                    .height((self.height >> level).max(1))
+                    .depth(1),
+            )
+            .image_offset(vk::Offset3D::default().x(0).y(0).z(0))
+            .buffer_offset(buffer_offset)
+            .buffer_row_length(0)
+            .buffer_image_height(0)
+            .image_subresource(
+                vk::ImageSubresourceLayers::default()
+                    .layer_count(1)
+                    .base_array_layer(0)
+                    .mip_level(level)
+                    .aspect_mask(vk::ImageAspectFlags::COLOR),
+            );
+        device.cmd_copy_buffer_to_image(
+            *command_buffer,
+            buffer.vk_buffer(),
+            self.vk_image(),
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[region],
+        )
+    }
+
     fn new(
         allocator: &Rc<vk_mem::Allocator>,
         tex_width: u32,
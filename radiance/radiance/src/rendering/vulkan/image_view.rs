@@ -25,6 +25,24 @@ impl ImageView {
         )
     }
 
+    /// Color view that reads alpha as 1 regardless of the stored
+    /// value, for textures loaded with their alpha channel ignored.
+    pub fn new_opaque_color_image_view(
+        device: Rc<Device>,
+        image: vk::Image,
+        format: vk::Format,
+        mip_levels: u32,
+    ) -> VkResult<Self> {
+        Self::new_with_components(
+            device,
+            image,
+            format,
+            vk::ImageAspectFlags::COLOR,
+            mip_levels,
+            vk::ComponentMapping::default().a(vk::ComponentSwizzle::ONE),
+        )
+    }
+
     pub fn new_depth_image_view(
         device: Rc<Device>,
         image: vk::Image,
@@ -87,6 +105,24 @@ impl ImageView {
             .r(vk::ComponentSwizzle::IDENTITY)
             .g(vk::ComponentSwizzle::IDENTITY)
             .b(vk::ComponentSwizzle::IDENTITY);
+        Self::new_with_components(
+            device,
+            image,
+            format,
+            aspect_mask,
+            mip_levels,
+            component_mapping,
+        )
+    }
+
+    fn new_with_components(
+        device: Rc<Device>,
+        image: vk::Image,
+        format: vk::Format,
+        aspect_mask: vk::ImageAspectFlags,
+        mip_levels: u32,
+        component_mapping: vk::ComponentMapping,
+    ) -> VkResult<Self> {
         let subres_range = vk::ImageSubresourceRange::default()
             .aspect_mask(aspect_mask)
             .base_array_layer(0)
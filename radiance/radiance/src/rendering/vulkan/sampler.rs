@@ -38,14 +38,16 @@ impl Sampler {
     /// Create a Vulkan sampler matching the cross-backend `SamplerDef`,
     /// with a `max_lod` derived from the bound texture's mip count.
     ///
-    /// Anisotropy is enabled (max 16) only when both filters are
-    /// `Linear` — Vulkan validation requires `samplerAnisotropy` to be
-    /// disabled when either filter is `NEAREST`. `min_lod` is pinned
+    /// Anisotropy is enabled only when `def` asks for it and both
+    /// filters are `Linear` — Vulkan validation requires
+    /// `samplerAnisotropy` to be disabled when either filter is
+    /// `NEAREST`. The level is clamped to the device limit. `min_lod` is pinned
     /// to 0; `max_lod` is `(mip_levels - 1) as f32` so single-level
     /// textures (`mip_levels = 1`) keep the legacy `max_lod = 0`
     /// behaviour and multi-level textures get the full chain.
     pub fn new(device: Rc<Device>, def: &SamplerDef, mip_levels: u32) -> VkResult<Self> {
-        let anisotropy = def.uses_anisotropy();
+        let max_anisotropy = (def.max_anisotropy as f32).min(device.max_sampler_anisotropy());
+        let anisotropy = def.uses_anisotropy() && max_anisotropy > 1.;
         let mip_levels = mip_levels.max(1);
         let max_lod = (mip_levels - 1) as f32;
         let sampler_info = vk::SamplerCreateInfo::default()
@@ -55,7 +57,7 @@ impl Sampler {
             .address_mode_v(vk_address(def.address_v))
             .address_mode_w(vk_address(def.address_w))
             .anisotropy_enable(anisotropy)
-            .max_anisotropy(if anisotropy { 16. } else { 1. })
+            .max_anisotropy(if anisotropy { max_anisotropy } else { 1. })
             .border_color(vk::BorderColor::INT_OPAQUE_BLACK)
             .unnormalized_coordinates(false)
             .compare_enable(false)
@@ -2,7 +2,8 @@ use super::{
     adhoc_command_runner::AdhocCommandRunner, buffer::Buffer, device::Device, image::Image,
     image_view::ImageView,
 };
-use crate::rendering::texture::{Texture, TextureDef};
+use crate::rendering::compressed_texture::{BcFormat, CompressedImage};
+use crate::rendering::texture::{Texture, TextureData, TextureDef};
 use ash::vk;
 use lru::LruCache;
 use std::error::Error;
@@ -31,35 +32,106 @@ impl VulkanTexture {
         allocator: &Rc<vk_mem::Allocator>,
         command_runner: &Rc<AdhocCommandRunner>,
     ) -> Result<Self, Box<dyn Error>> {
-        // Drain the CPU-side `RgbaImage` out of the `TextureDef` here:
+        // Drain the CPU-side pixels out of the `TextureDef` here:
         // after this upload the GPU copy is the source of truth and the
         // CPU bytes are dead weight that would otherwise linger in
         // `TEXTURE_STORE` for the process lifetime. `VulkanTextureStore`
         // caches `Rc<VulkanTexture>` by texture name so a repeat
         // `create_texture` call for the same `TextureDef` short-circuits
-        // before reaching here — `take_image` returning `None` on a
+        // before reaching here — `take_data` returning `None` on a
         // re-entry path only happens if the same `TextureDef` is wired
         // through two different `VulkanTextureStore` instances, in
         // which case falling back to the missing-texture sentinel keeps
         // rendering correct (just visually wrong for that one texture).
-        let owned_image = def.take_image();
-        let texture_missing =
-            image::load_from_memory(radiance_assets::TEXTURE_MISSING_TEXTURE_FILE)
+        let data = match def.take_data() {
+            Some(TextureData::Compressed(image)) if device.supports_bc() => {
+                return Self::from_compressed(&image, device, allocator, command_runner);
+            }
+            Some(data) => data.into_rgba(),
+            None => image::load_from_memory(radiance_assets::TEXTURE_MISSING_TEXTURE_FILE)
                 .unwrap()
-                .to_rgba8();
-        let rgba_image = owned_image.as_ref().unwrap_or(&texture_missing);
+                .to_rgba8(),
+        };
 
         Self::from_buffer(
-            rgba_image.as_raw(),
+            data.as_raw(),
             0,
-            rgba_image.width(),
-            rgba_image.height(),
+            data.width(),
+            data.height(),
             device,
             allocator,
             command_runner,
         )
     }
 
+    /// Upload a BCn image with its whole mip chain as-is. Every level
+    /// comes from `image` (see [`CompressedImage::with_full_mip_chain`]),
+    /// so unlike [`Self::from_buffer`] there is no blit ladder. An
+    /// opaque image gets a view that reads alpha as 1.
+    pub fn from_compressed(
+        image: &CompressedImage,
+        device: &Rc<Device>,
+        allocator: &Rc<vk_mem::Allocator>,
+        command_runner: &Rc<AdhocCommandRunner>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let format = match image.format() {
+            BcFormat::Bc1 => vk::Format::BC1_RGBA_UNORM_BLOCK,
+            BcFormat::Bc2 => vk::Format::BC2_UNORM_BLOCK,
+            BcFormat::Bc3 => vk::Format::BC3_UNORM_BLOCK,
+        };
+        let payload = image.levels().concat();
+        let buffer = Buffer::new_staging_buffer_with_data(allocator, &payload)?;
+        let mip_levels = image.mip_levels();
+        let mut vk_image = Image::new_compressed_image(
+            allocator,
+            image.width(),
+            image.height(),
+            format,
+            mip_levels,
+        )?;
+
+        command_runner.run_commands_one_shot(|dev, cb| {
+            vk_image.record_transit_layout(
+                vk::ImageLayout::UNDEFINED,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                dev,
+                cb,
+            );
+            let mut offset = 0;
+            for (level, data) in image.levels().iter().enumerate() {
+                vk_image.record_copy_level_from(&buffer, offset, level as u32, dev, cb);
+                offset += data.len() as u64;
+            }
+            vk_image.record_transit_layout(
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                dev,
+                cb,
+            );
+        })?;
+
+        let image_view = if image.is_opaque() {
+            ImageView::new_opaque_color_image_view(
+                device.clone(),
+                vk_image.vk_image(),
+                format,
+                mip_levels,
+            )?
+        } else {
+            ImageView::new_color_image_view(
+                device.clone(),
+                vk_image.vk_image(),
+                format,
+                mip_levels,
+            )?
+        };
+
+        Ok(Self {
+            image: vk_image,
+            image_view,
+        })
+    }
+
     /// Upload `image_buffer` (`R8G8B8A8_UNORM`) into a Vulkan image
     /// with a full mipmap chain generated via a `vkCmdBlitImage` ladder
     /// inside a single one-shot command buffer (no extra submits).
@@ -219,6 +219,11 @@ impl CoreEntity {
         self.props().children.clone()
     }
 
+    /// Attached components keyed by the UUID they were added under.
+    pub fn components(&self) -> Vec<(Uuid, ComRc<IComponent>)> {
+        self.components.snapshot()
+    }
+
     pub fn get_rendering_component(&self) -> Option<Rc<RenderingComponent>> {
         self.props().rendering_component.clone()
     }
@@ -243,6 +248,7 @@ pub trait IEntityExt {
     fn world_transform(&self) -> Transform;
     fn update_world_transform(&self, parent_transform: &Transform);
     fn children(&self) -> Vec<ComRc<IEntity>>;
+    fn components(&self) -> Vec<(Uuid, ComRc<IComponent>)>;
     fn get_rendering_component(&self) -> Option<Rc<RenderingComponent>>;
     fn set_rendering_component(&self, component: Option<Rc<RenderingComponent>>);
 }
@@ -279,6 +285,9 @@ impl IEntityExt for ComRc<IEntity> {
     fn children(&self) -> Vec<ComRc<IEntity>> {
         self.inner::<CoreEntity>().children()
     }
+    fn components(&self) -> Vec<(Uuid, ComRc<IComponent>)> {
+        self.inner::<CoreEntity>().components()
+    }
     fn get_rendering_component(&self) -> Option<Rc<RenderingComponent>> {
         self.inner::<CoreEntity>().get_rendering_component()
     }
@@ -111,6 +111,17 @@ impl ComponentBag {
             .collect()
     }
 
+    /// Attached components in insertion order, skipping ones pending
+    /// removal.
+    pub(crate) fn snapshot(&self) -> Vec<(Uuid, ComRc<IComponent>)> {
+        self.components
+            .borrow()
+            .iter()
+            .filter(|(_, entry)| !entry.pending_removal)
+            .map(|(uuid, entry)| (*uuid, entry.component.clone()))
+            .collect()
+    }
+
     pub(crate) fn dispatch_each<F: FnMut(ComRc<IComponent>)>(&self, mut f: F) {
         let len = self.len();
         for i in 0..len {
@@ -428,6 +439,23 @@ mod tests {
         assert_eq!(snapshot(&events), vec![Event::Updating("keep")]);
     }
 
+    #[test]
+    fn component_bag_snapshot_skips_pending_removal_in_order() {
+        let bag = ComponentBag::new();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        for tag in [3, 1, 2] {
+            bag.insert(
+                test_uuid(tag),
+                make_recording_component("c", events.clone()),
+                false,
+            );
+        }
+        bag.mark_pending_removal(test_uuid(1));
+
+        let uuids: Vec<Uuid> = bag.snapshot().into_iter().map(|(uuid, _)| uuid).collect();
+        assert_eq!(uuids, vec![test_uuid(3), test_uuid(2)]);
+    }
+
     #[test]
     fn component_bag_load_all_fires_on_loading_once_per_unloaded_entry() {
         let bag = ComponentBag::new();
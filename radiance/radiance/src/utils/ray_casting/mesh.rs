@@ -124,6 +124,8 @@ impl Triangle {
 pub(crate) struct Mesh {
     vertices: Vec<Vec3>,
     triangles: Vec<Triangle>,
+    aabb_min: Vec3,
+    aabb_max: Vec3,
 }
 
 impl Mesh {
@@ -136,13 +138,76 @@ impl Mesh {
             ));
         }
 
+        let mut aabb_min = Vec3::new(f32::MAX, f32::MAX, f32::MAX);
+        let mut aabb_max = Vec3::new(f32::MIN, f32::MIN, f32::MIN);
+        for t in &triangles {
+            aabb_min = Vec3::new(
+                aabb_min.x.min(t.aabb_min.x),
+                aabb_min.y.min(t.aabb_min.y),
+                aabb_min.z.min(t.aabb_min.z),
+            );
+            aabb_max = Vec3::new(
+                aabb_max.x.max(t.aabb_max.x),
+                aabb_max.y.max(t.aabb_max.y),
+                aabb_max.z.max(t.aabb_max.z),
+            );
+        }
+
         Self {
             vertices,
             triangles,
+            aabb_min,
+            aabb_max,
         }
     }
 
+    /// Slab test against the mesh bounds, so a scene made of many
+    /// small colliders (e.g. every PAL5 map object) only pays for the
+    /// per-triangle test on the meshes a ray can actually reach.
+    fn ray_hits_bounds(&self, ray_origin: &Vec3, ray_direction: &Vec3) -> bool {
+        let mut t_min = f32::NEG_INFINITY;
+        let mut t_max = f32::INFINITY;
+        for (o, d, lo, hi) in [
+            (
+                ray_origin.x,
+                ray_direction.x,
+                self.aabb_min.x,
+                self.aabb_max.x,
+            ),
+            (
+                ray_origin.y,
+                ray_direction.y,
+                self.aabb_min.y,
+                self.aabb_max.y,
+            ),
+            (
+                ray_origin.z,
+                ray_direction.z,
+                self.aabb_min.z,
+                self.aabb_max.z,
+            ),
+        ] {
+            if d == 0. {
+                if o < lo || o > hi {
+                    return false;
+                }
+                continue;
+            }
+
+            let t0 = (lo - o) / d;
+            let t1 = (hi - o) / d;
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+        }
+
+        t_max >= t_min.max(0.)
+    }
+
     pub fn cast_aaray(&self, ray_origin: &Vec3, aaray: AARayDirection) -> Option<f32> {
+        if !self.ray_hits_bounds(ray_origin, &aaray.get_direction()) {
+            return None;
+        }
+
         let mut min_distance = None;
         for triangle in &self.triangles {
             if let Some(distance) = triangle.cast_aaray(ray_origin, aaray, &self.vertices) {
@@ -160,6 +225,10 @@ impl Mesh {
     }
 
     pub fn cast_ray(&self, ray_origin: &Vec3, ray_direction: &Vec3) -> Option<f32> {
+        if !self.ray_hits_bounds(ray_origin, ray_direction) {
+            return None;
+        }
+
         let mut min_distance = None;
         for triangle in &self.triangles {
             if let Some(distance) = triangle.cast_ray(ray_origin, ray_direction, &self.vertices) {
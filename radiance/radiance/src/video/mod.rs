@@ -12,13 +12,26 @@ pub enum VideoStreamState {
     Paused,
 }
 
-#[derive(Copy, Clone, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
 pub enum Codec {
     Bik,
     Webm,
     Theora,
 }
 
+impl Codec {
+    /// Map a file extension (case-insensitive, without the dot) to the
+    /// codec its container carries.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "bik" => Some(Codec::Bik),
+            "webm" => Some(Codec::Webm),
+            "ogv" => Some(Codec::Theora),
+            _ => None,
+        }
+    }
+}
+
 pub trait VideoStream {
     fn set_reader(&mut self, reader: Box<dyn SeekRead>);
 
@@ -73,6 +86,10 @@ pub fn register_video_decoder(codec: Codec, constructor: DecoderConstructor) {
     VIDEO_DECODER_MAP.entry(codec).or_insert(constructor);
 }
 
+pub fn has_video_decoder(codec: Codec) -> bool {
+    VIDEO_DECODER_MAP.contains_key(&codec)
+}
+
 pub(crate) fn create_stream(
     factory: Rc<dyn ComponentFactory>,
     audio_engine: Rc<dyn AudioEngine>,
@@ -0,0 +1,77 @@
+//! `AudioSourceComponent` distance culling, driven against the
+//! software mixer so no audio device is needed.
+
+use std::io::Cursor;
+use std::rc::Rc;
+
+use radiance::audio::{AudioEngine, AudioSourceState, Codec, SoftwareAudioEngine};
+use radiance::comdef::IComponent;
+use radiance::components::audio::{AudioNodeConfig, AudioSourceComponent, PlaybackMode};
+use radiance::components::billboard::set_camera_position;
+use radiance::math::{Transform, Vec3};
+use radiance::scene::{CoreEntity, IEntityExt};
+
+fn wav_bytes(frames: usize) -> Vec<u8> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 8000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut cursor = Cursor::new(Vec::new());
+    {
+        let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+        for _ in 0..frames {
+            writer.write_sample(4000i16).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    cursor.into_inner()
+}
+
+#[test]
+fn looping_bed_sleeps_outside_cull_distance() {
+    let engine = Rc::new(SoftwareAudioEngine::with_sample_rate(8000));
+    let entity = CoreEntity::create("sound:test".to_string(), false);
+    entity
+        .transform()
+        .borrow_mut()
+        .set_position(&Vec3::new(100., 0., 0.));
+    entity.update_world_transform(&Transform::new());
+
+    let component = AudioSourceComponent::create(
+        entity,
+        engine.clone() as Rc<dyn AudioEngine>,
+        wav_bytes(800),
+        Codec::Wav,
+        AudioNodeConfig {
+            mode: PlaybackMode::Loop,
+            max_distance: 50.,
+            cull_distance: Some(50.),
+            ..Default::default()
+        },
+    );
+    let component = component.query_interface::<IComponent>().unwrap();
+
+    // Listener far away: the bed is not started.
+    set_camera_position(Vec3::new(0., 0., 0.));
+    component.on_loading();
+    assert_eq!(engine.sources()[0].state, AudioSourceState::Stopped);
+
+    // Walking into range starts it.
+    set_camera_position(Vec3::new(90., 0., 0.));
+    component.on_updating(0.1);
+    let info = &engine.sources()[0];
+    assert_eq!(info.state, AudioSourceState::Playing);
+    assert!(info.looping);
+    assert_eq!(info.position, [100., 0., 0.]);
+
+    // Leaving range stops it again; unloading keeps it stopped.
+    set_camera_position(Vec3::new(-100., 0., 0.));
+    component.on_updating(0.1);
+    assert_eq!(engine.sources()[0].state, AudioSourceState::Stopped);
+    component.on_unloading();
+    assert_eq!(engine.sources()[0].state, AudioSourceState::Stopped);
+}
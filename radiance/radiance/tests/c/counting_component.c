@@ -0,0 +1,93 @@
+/* An `IComponent` written against the generated crosscom C headers.
+ * Built and loaded by `tests/c_component.rs`; counts every lifecycle
+ * call into a caller-owned struct so the Rust side can check them. */
+
+#include <stdlib.h>
+#include <string.h>
+
+#include "radiance.h"
+
+#if defined(_WIN32)
+#define EXPORT __declspec(dllexport)
+#else
+#define EXPORT __attribute__((visibility("default")))
+#endif
+
+/* Mirrored by `Counters` in c_component.rs. */
+typedef struct Counters {
+    int loading;
+    int updating;
+    int unloading;
+    int destroyed;
+    float total_delta;
+} Counters;
+
+typedef struct CountingComponent {
+    IComponent iface;
+    long ref_count;
+    Counters *counters;
+} CountingComponent;
+
+static int uuid_eq(const CrosscomUuid *a, const CrosscomUuid *b) {
+    return memcmp(a->bytes, b->bytes, sizeof(a->bytes)) == 0;
+}
+
+static long CROSSCOM_CALL add_ref(IComponent *self) {
+    CountingComponent *c = (CountingComponent *)self;
+    return ++c->ref_count;
+}
+
+static long CROSSCOM_CALL release(IComponent *self) {
+    CountingComponent *c = (CountingComponent *)self;
+    long remaining = --c->ref_count;
+    if (remaining == 0) {
+        c->counters->destroyed++;
+        free(c);
+    }
+    return remaining;
+}
+
+static long CROSSCOM_CALL query_interface(IComponent *self, CrosscomUuid guid, IUnknown **retval) {
+    if (uuid_eq(&guid, &IID_IUnknown) || uuid_eq(&guid, &IID_IComponent)) {
+        add_ref(self);
+        *retval = (IUnknown *)self;
+        return 0;
+    }
+    *retval = NULL;
+    return -1;
+}
+
+static void CROSSCOM_CALL on_loading(IComponent *self) {
+    ((CountingComponent *)self)->counters->loading++;
+}
+
+static void CROSSCOM_CALL on_updating(IComponent *self, float delta_sec) {
+    Counters *counters = ((CountingComponent *)self)->counters;
+    counters->updating++;
+    counters->total_delta += delta_sec;
+}
+
+static void CROSSCOM_CALL on_unloading(IComponent *self) {
+    ((CountingComponent *)self)->counters->unloading++;
+}
+
+static const IComponentVtbl COUNTING_COMPONENT_VTBL = {
+    query_interface,
+    add_ref,
+    release,
+    on_loading,
+    on_updating,
+    on_unloading,
+};
+
+/* Returns a new component holding one reference. */
+EXPORT IComponent *counting_component_create(Counters *counters) {
+    CountingComponent *c = (CountingComponent *)calloc(1, sizeof(CountingComponent));
+    if (c == NULL) {
+        return NULL;
+    }
+    c->iface.vtbl = &COUNTING_COMPONENT_VTBL;
+    c->ref_count = 1;
+    c->counters = counters;
+    return &c->iface;
+}
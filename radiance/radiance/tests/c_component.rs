@@ -0,0 +1,102 @@
+//! A component implemented in C against the generated crosscom headers
+//! (`crosscom/runtime/c/include`), attached to a `CoreEntity` from Rust.
+//!
+//! The test compiles `tests/c/counting_component.c` into a shared
+//! library with the system C compiler (`$CC`, default `cc`) and loads it
+//! at runtime; it is skipped when no compiler can be run.
+
+#![cfg(unix)]
+
+use std::ffi::c_void;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crosscom::ComRc;
+use radiance::comdef::{IComponent, IEntity};
+use radiance::scene::CoreEntity;
+use uuid::Uuid;
+
+/// Mirrors `Counters` in counting_component.c.
+#[repr(C)]
+#[derive(Default, Debug)]
+struct Counters {
+    loading: i32,
+    updating: i32,
+    unloading: i32,
+    destroyed: i32,
+    total_delta: f32,
+}
+
+type CreateFn = unsafe extern "C" fn(counters: *mut Counters) -> *const *const c_void;
+
+fn build_component_library() -> Option<PathBuf> {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let include_dir = manifest_dir.join("../../crosscom/runtime/c/include");
+    let source = manifest_dir.join("tests/c/counting_component.c");
+    let out_dir = std::env::temp_dir().join(format!("radiance_c_component_{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let library = out_dir.join("libcounting_component.so");
+
+    let cc = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(&cc)
+        .args(["-shared", "-fPIC", "-o"])
+        .arg(&library)
+        .arg("-I")
+        .arg(&include_dir)
+        .arg(&source)
+        .status();
+    match status {
+        Ok(status) => {
+            assert!(status.success(), "failed to compile {}", source.display());
+            Some(library)
+        }
+        Err(err) => {
+            eprintln!("skipping: cannot run {cc}: {err}");
+            None
+        }
+    }
+}
+
+#[test]
+fn c_component_follows_entity_lifecycle() {
+    let Some(library_path) = build_component_library() else {
+        return;
+    };
+    let library = unsafe { libloading::Library::new(&library_path) }.unwrap();
+    let create: libloading::Symbol<CreateFn> =
+        unsafe { library.get(b"counting_component_create\0") }.unwrap();
+
+    let mut counters = Box::new(Counters::default());
+    let raw = unsafe { create(&mut *counters) };
+    assert!(!raw.is_null());
+    let component = unsafe { ComRc::<IComponent>::from_raw_pointer(raw) };
+
+    // Rust-side QI through the C vtable.
+    assert!(component.query_interface::<IComponent>().is_some());
+    assert!(component.query_interface::<IEntity>().is_none());
+
+    let entity: ComRc<IEntity> = CoreEntity::create("c-component".into(), true);
+    let uuid = Uuid::from_bytes([0xc0; 16]);
+    entity.add_component(uuid, component);
+    assert_eq!(counters.loading, 0, "entity is not loaded yet");
+
+    entity.load();
+    entity.update(0.25);
+    entity.update(0.5);
+    assert_eq!(counters.loading, 1);
+    assert_eq!(counters.updating, 2);
+    assert_eq!(counters.total_delta, 0.75);
+    assert!(entity.get_component(uuid).is_some());
+
+    entity.unload();
+    assert_eq!(counters.unloading, 1);
+
+    drop(entity);
+    assert_eq!(
+        counters.destroyed, 1,
+        "last release should free the C object"
+    );
+
+    drop(library);
+    let _ = std::fs::remove_dir_all(library_path.parent().unwrap());
+}
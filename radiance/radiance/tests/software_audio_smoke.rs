@@ -0,0 +1,196 @@
+//! Smoke tests for `SoftwareAudioEngine`: the device-free mixer backend
+//! used for headless runs and CI audio assertions.
+
+use std::io::Cursor;
+
+use radiance::audio::{
+    AudioEngine, AudioSourceState, Codec, Decoder, SOFTWARE_MIX_CHANNELS, Samples,
+    SoftwareAudioEngine,
+};
+
+const RATE: u32 = 8000;
+
+/// Decoder emitting `chunks` chunks of a constant mono level.
+struct ConstantDecoder {
+    level: i16,
+    chunk_frames: usize,
+    chunks: usize,
+    emitted: usize,
+}
+
+impl ConstantDecoder {
+    fn new(level: i16, chunk_frames: usize, chunks: usize) -> Self {
+        Self {
+            level,
+            chunk_frames,
+            chunks,
+            emitted: 0,
+        }
+    }
+}
+
+impl Decoder for ConstantDecoder {
+    fn fetch_samples(&mut self) -> anyhow::Result<Option<Samples>> {
+        if self.emitted >= self.chunks {
+            return Ok(None);
+        }
+
+        self.emitted += 1;
+        Ok(Some(Samples {
+            data: vec![self.level; self.chunk_frames],
+            sample_rate: RATE as i32,
+            channels: 1,
+        }))
+    }
+
+    fn reset(&mut self) {
+        self.emitted = 0;
+    }
+}
+
+fn wav_bytes(level: i16, frames: usize) -> Vec<u8> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut cursor = Cursor::new(Vec::new());
+    {
+        let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+        for _ in 0..frames {
+            writer.write_sample(level).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    cursor.into_inner()
+}
+
+fn peak(samples: &[i16], channel: usize) -> i16 {
+    samples
+        .chunks(SOFTWARE_MIX_CHANNELS)
+        .map(|frame| frame[channel].abs())
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn memory_source_plays_to_completion_and_is_identifiable() {
+    let engine = SoftwareAudioEngine::with_sample_rate(RATE);
+    let bgm = wav_bytes(8000, 4000);
+    let mut source = engine.create_source();
+    source.set_data(bgm.clone(), Codec::Wav);
+    source.play(false);
+
+    let first = engine.render(2000);
+    assert_eq!(first.len(), 2000 * SOFTWARE_MIX_CHANNELS);
+    assert!(peak(&first, 0) > 7000);
+    assert_eq!(source.state(), AudioSourceState::Playing);
+
+    let info = engine.sources();
+    assert_eq!(info.len(), 1);
+    assert_eq!(info[0].codec, Some(Codec::Wav));
+    assert!(!info[0].looping);
+
+    let mut other = engine.create_source();
+    other.set_data(bgm, Codec::Wav);
+    let info = engine.sources();
+    assert_eq!(info.len(), 2);
+    assert!(info[0].data_hash.is_some());
+    assert_eq!(info[0].data_hash, info[1].data_hash);
+    drop(other);
+
+    engine.render(4000);
+    assert_eq!(source.state(), AudioSourceState::Stopped);
+    assert_eq!(engine.sources().len(), 1);
+    assert_eq!(engine.sources()[0].frames_played, 4000);
+}
+
+#[test]
+fn looping_source_keeps_playing_and_pause_silences() {
+    let engine = SoftwareAudioEngine::with_sample_rate(RATE);
+    let mut source = engine.create_custom_decoder_source();
+    source.set_decoder(Box::new(ConstantDecoder::new(10000, 100, 3)));
+    source.play(true);
+
+    let out = engine.render(1000);
+    assert_eq!(source.state(), AudioSourceState::Playing);
+    assert!(peak(&out[out.len() - 2..], 0) > 9000);
+
+    source.pause();
+    assert_eq!(peak(&engine.render(100), 0), 0);
+    source.resume();
+    assert!(peak(&engine.render(100), 0) > 9000);
+}
+
+#[test]
+fn gain_distance_and_master_volume_attenuate() {
+    let engine = SoftwareAudioEngine::with_sample_rate(RATE);
+    let mut source = engine.create_custom_decoder_source();
+    source.set_decoder(Box::new(ConstantDecoder::new(16000, 1000, 1)));
+    source.set_reference_distance(1.);
+    source.set_max_distance(11.);
+    source.set_position([0., 0., -6.]);
+    source.set_gain(0.5);
+    source.play(false);
+
+    // Halfway between reference and max distance, at half gain.
+    let level = peak(&engine.render(10), 0) as f32;
+    assert!((level - 16000. * 0.5 * 0.5).abs() < 200., "{}", level);
+
+    engine.set_listener([0., 0., 20.], [0., 0., -1.], [0., 1., 0.]);
+    assert_eq!(peak(&engine.render(10), 0), 0);
+
+    engine.set_listener([0., 0., -6.], [0., 0., -1.], [0., 1., 0.]);
+    engine.set_master_volume(0.5);
+    let level = peak(&engine.render(10), 0) as f32;
+    assert!((level - 16000. * 0.5 * 0.5).abs() < 200., "{}", level);
+}
+
+#[test]
+fn mono_sources_pan_towards_their_side() {
+    let engine = SoftwareAudioEngine::with_sample_rate(RATE);
+    let mut source = engine.create_custom_decoder_source();
+    source.set_decoder(Box::new(ConstantDecoder::new(16000, 1000, 1)));
+    source.set_position([3., 0., 0.]);
+    source.play(false);
+
+    let out = engine.render(10);
+    assert_eq!(peak(&out, 0), 0);
+    assert!(peak(&out, 1) > 15000);
+
+    source.set_relative(true);
+    source.set_position([0., 0., 0.]);
+    let out = engine.render(10);
+    assert_eq!(peak(&out, 0), peak(&out, 1));
+}
+
+#[test]
+fn capture_follows_update_and_writes_wav() {
+    let engine = SoftwareAudioEngine::with_sample_rate(RATE);
+    let mut source = engine.create_custom_decoder_source();
+    source.set_decoder(Box::new(ConstantDecoder::new(12000, 100, 1)));
+    source.play(true);
+
+    engine.update(0.5);
+    assert!(!engine.is_capturing());
+
+    engine.start_capture();
+    for _ in 0..4 {
+        engine.update(0.25);
+    }
+
+    let path = std::env::temp_dir().join("radiance_software_audio_smoke.wav");
+    engine.write_wav(&path).unwrap();
+    let captured = engine.stop_capture();
+    assert_eq!(captured.len(), RATE as usize * SOFTWARE_MIX_CHANNELS);
+
+    let mut reader = hound::WavReader::open(&path).unwrap();
+    assert_eq!(reader.spec().channels as usize, SOFTWARE_MIX_CHANNELS);
+    assert_eq!(reader.spec().sample_rate, RATE);
+    let written: Vec<i16> = reader.samples::<i16>().map(|s| s.unwrap()).collect();
+    assert_eq!(written, captured);
+    let _ = std::fs::remove_file(&path);
+}
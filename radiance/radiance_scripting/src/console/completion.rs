@@ -0,0 +1,246 @@
+//! Identifier completion for the script console.
+//!
+//! p7 does not expose a symbol table for loaded modules, so the index
+//! is built from module *sources*, resolved through the same provider
+//! chain `import` uses ([`ScriptHost::module_source`]). A light scan
+//! picks up the declarations a console user can name:
+//!
+//! * top-level `pub fn` / `pub let` / `pub struct` / `pub proto` /
+//!   `pub enum` — completed after `<module>.`;
+//! * methods declared inside protos and struct bodies — completed
+//!   after any other `<expr>.`, which is how `host.vfs().` reaches the
+//!   `IVfsService` methods from the codegen bindings.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::runtime::ScriptHost;
+
+/// Words offered at the start of an identifier besides module names.
+const KEYWORDS: &[&str] = &[
+    "box", "else", "false", "fn", "for", "host", "if", "import", "let", "match", "null", "return",
+    "true", "while",
+];
+
+#[derive(Default)]
+pub struct CompletionIndex {
+    /// Module alias (last path segment) → public top-level names.
+    modules: BTreeMap<String, BTreeSet<String>>,
+    /// Every method name declared in an indexed module.
+    methods: BTreeSet<String>,
+}
+
+impl CompletionIndex {
+    /// Index `imports` (dotted module paths). Modules that fail to
+    /// resolve are skipped; the console reports those at import time.
+    pub fn build(host: &ScriptHost, imports: &[String]) -> Self {
+        let mut index = Self::default();
+        for module_path in imports {
+            if let Some(source) = host.module_source(module_path) {
+                index.add_module(module_path, &source);
+            }
+        }
+        index
+    }
+
+    pub fn add_module(&mut self, module_path: &str, source: &str) {
+        let alias = module_path
+            .rsplit('.')
+            .next()
+            .unwrap_or(module_path)
+            .to_string();
+        let declared = scan_declarations(source);
+        self.modules
+            .entry(alias)
+            .or_default()
+            .extend(declared.top_level);
+        self.methods.extend(declared.methods);
+    }
+
+    /// Candidates for the identifier ending at the end of `line`, as
+    /// `(replace_from, candidates)`: the byte offset where the partial
+    /// word starts and the full words that extend it.
+    pub fn complete(&self, line: &str) -> (usize, Vec<String>) {
+        let word_start = line
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_ident_char(*c))
+            .last()
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        let partial = &line[word_start..];
+        let before = &line[..word_start];
+
+        let pool: Vec<&str> = match before.strip_suffix('.') {
+            Some(receiver) => {
+                let receiver_start = receiver
+                    .char_indices()
+                    .rev()
+                    .take_while(|(_, c)| is_ident_char(*c))
+                    .last()
+                    .map(|(i, _)| i)
+                    .unwrap_or(receiver.len());
+                let receiver_word = &receiver[receiver_start..];
+                let qualified =
+                    receiver_start == 0 || !receiver[..receiver_start].ends_with(['.', ')']);
+                match self.modules.get(receiver_word) {
+                    Some(names) if qualified => names.iter().map(String::as_str).collect(),
+                    _ => self.methods.iter().map(String::as_str).collect(),
+                }
+            }
+            None => KEYWORDS
+                .iter()
+                .copied()
+                .chain(self.modules.keys().map(String::as_str))
+                .collect(),
+        };
+
+        let mut candidates: Vec<String> = pool
+            .into_iter()
+            .filter(|name| name.starts_with(partial) && *name != partial)
+            .map(str::to_string)
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        (word_start, candidates)
+    }
+}
+
+/// Longest prefix every candidate shares; what Tab inserts when the
+/// match is ambiguous.
+pub fn common_prefix(candidates: &[String]) -> &str {
+    let Some(first) = candidates.first() else {
+        return "";
+    };
+    let mut len = first.len();
+    for other in &candidates[1..] {
+        len = first
+            .char_indices()
+            .zip(other.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map(|((i, c), _)| i + c.len_utf8())
+            .unwrap_or(0)
+            .min(len);
+    }
+    &first[..len]
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[derive(Default)]
+struct Declarations {
+    top_level: Vec<String>,
+    methods: Vec<String>,
+}
+
+fn scan_declarations(source: &str) -> Declarations {
+    let mut declared = Declarations::default();
+    let mut depth = 0usize;
+    for line in source.lines() {
+        let code = line.split("//").next().unwrap_or("").trim();
+        if depth == 0 {
+            if let Some(name) = top_level_name(code) {
+                declared.top_level.push(name);
+            }
+        } else if let Some(name) = method_name(code) {
+            declared.methods.push(name);
+        }
+        for c in code.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+    declared
+}
+
+fn top_level_name(code: &str) -> Option<String> {
+    let rest = code.strip_prefix("pub ")?.trim_start();
+    let rest = ["fn ", "let ", "proto ", "enum ", "struct"]
+        .iter()
+        .find_map(|kw| rest.strip_prefix(kw))?;
+    // `struct[IFoo, IBar] Name(` carries its conformance list first.
+    let rest = match rest.trim_start().strip_prefix('[') {
+        Some(after) => after.split_once(']').map(|(_, name)| name)?,
+        None => rest,
+    };
+    leading_ident(rest)
+}
+
+fn method_name(code: &str) -> Option<String> {
+    let rest = code.strip_prefix("pub ").unwrap_or(code);
+    leading_ident(rest.strip_prefix("fn ")?)
+}
+
+fn leading_ident(text: &str) -> Option<String> {
+    let name: String = text
+        .trim_start()
+        .chars()
+        .take_while(|c| is_ident_char(*c))
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+import radiance;
+
+pub let SPEED: float = 500.0;
+
+pub proto IVfsService {
+    fn read_text(self: ref<IVfsService>, path: string) -> string;
+    fn exists(self: ref<IVfsService>, path: string) -> bool;
+}
+
+struct[IVfsService] Local(root: string) {
+    pub fn read_text(self: ref<Self>, path: string) -> string { "" }
+}
+
+pub fn make_local(root: string) -> box<IVfsService> {
+    // fn not_a_method() {}
+    box(Local(root)) as box<IVfsService>
+}
+"#;
+
+    fn index() -> CompletionIndex {
+        let mut index = CompletionIndex::default();
+        index.add_module("scripting_services", SOURCE);
+        index
+    }
+
+    #[test]
+    fn module_members_complete_after_the_module_alias() {
+        let (start, candidates) = index().complete("scripting_services.ma");
+        assert_eq!(start, "scripting_services.".len());
+        assert_eq!(candidates, vec!["make_local"]);
+
+        let (_, candidates) = index().complete("scripting_services.");
+        assert_eq!(candidates, vec!["IVfsService", "SPEED", "make_local"]);
+    }
+
+    #[test]
+    fn methods_complete_after_an_expression() {
+        let (_, candidates) = index().complete("host.vfs().");
+        assert_eq!(candidates, vec!["exists", "read_text"]);
+        let (_, candidates) = index().complete("let x = host.e");
+        assert_eq!(candidates, vec!["exists"]);
+    }
+
+    #[test]
+    fn bare_words_complete_keywords_and_modules() {
+        let (start, candidates) = index().complete("let v = scr");
+        assert_eq!(start, "let v = ".len());
+        assert_eq!(candidates, vec!["scripting_services"]);
+        assert_eq!(
+            common_prefix(&["read_text".into(), "read_bytes".into()]),
+            "read_"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! In-game protosept console.
+//!
+//! A dockable imgui window (toggled with F1) that evaluates p7 snippets
+//! against the live [`ScriptHost`], with `host` bound to the app's
+//! `IHostContext` — so `host.vfs()`, `host.audio()`, `host.config()`
+//! and `host.scene_manager()` are one line away. See
+//! [`session`] for how snippets are compiled and typed,
+//! [`completion`] for Tab completion and [`pretty`] for value
+//! printing.
+//!
+//! The console is a Rust `IUiLayer` drawn with raw imgui in the
+//! `DebugOverlay` band, next to the per-game debug overlays (which
+//! keep `~`). Evaluation happens inside the layer's `render`, outside
+//! any script call, so snippets may call anything a script director
+//! could.
+
+pub mod completion;
+pub mod pretty;
+pub mod session;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crosscom::ComRc;
+use imgui::{
+    Condition, HistoryDirection, InputTextCallback, InputTextCallbackHandler, TextCallbackData,
+};
+use radiance::comdef::{IApplication, IApplicationExt, IUiHost, IUiLayer, IUiLayerImpl};
+use radiance::radiance::{UiLayerBand, UiLayerHandle, UiManager};
+
+use crate::comdef::services::IHostContext;
+use crate::runtime::ScriptHost;
+
+pub use session::{ConsoleLine, ConsoleSession};
+
+const TOGGLE_KEY: imgui::Key = imgui::Key::F1;
+const ERROR_COLOR: [f32; 4] = [1.0, 0.45, 0.45, 1.0];
+const INPUT_COLOR: [f32; 4] = [0.6, 0.8, 1.0, 1.0];
+
+pub struct ScriptConsole {
+    ui: Rc<UiManager>,
+    session: RefCell<ConsoleSession>,
+    input: RefCell<String>,
+    visible: RefCell<bool>,
+    /// Set after a submit so the log scrolls to the new output.
+    scroll_to_bottom: RefCell<bool>,
+}
+
+ComObject_ScriptConsole!(super::ScriptConsole);
+
+impl ScriptConsole {
+    pub fn new(
+        ui: Rc<UiManager>,
+        host: Rc<ScriptHost>,
+        host_context: ComRc<IHostContext>,
+    ) -> ScriptConsole {
+        ScriptConsole {
+            ui,
+            session: RefCell::new(ConsoleSession::new(host, Some(host_context))),
+            input: RefCell::new(String::new()),
+            visible: RefCell::new(false),
+            scroll_to_bottom: RefCell::new(false),
+        }
+    }
+
+    fn render_window(&self) {
+        let ui = self.ui.ui();
+        let mut opened = true;
+        ui.window("Script Console")
+            .size([640.0, 360.0], Condition::FirstUseEver)
+            .opened(&mut opened)
+            .build(|| {
+                let footer = ui.frame_height_with_spacing();
+                ui.child_window("##console_log")
+                    .size([0.0, -footer])
+                    .build(|| {
+                        for line in self.session.borrow().log() {
+                            match line {
+                                ConsoleLine::Input(text) => {
+                                    ui.text_colored(INPUT_COLOR, format!("> {}", text));
+                                }
+                                ConsoleLine::Output(text) => ui.text_wrapped(text),
+                                ConsoleLine::Error(text) => ui.text_colored(ERROR_COLOR, text),
+                            }
+                        }
+                        if self.scroll_to_bottom.replace(false) {
+                            ui.set_scroll_here_y_with_ratio(1.0);
+                        }
+                    });
+
+                let mut input = self.input.borrow_mut();
+                let submitted = ui
+                    .input_text("##console_input", &mut *input)
+                    .enter_returns_true(true)
+                    .callback(
+                        InputTextCallback::COMPLETION | InputTextCallback::HISTORY,
+                        LineEditor {
+                            session: &self.session,
+                        },
+                    )
+                    .build();
+                if submitted {
+                    let line = std::mem::take(&mut *input);
+                    self.session.borrow_mut().submit(&line);
+                    self.scroll_to_bottom.replace(true);
+                    // Enter drops focus; keep typing in the same box.
+                    ui.set_keyboard_focus_here_with_offset(imgui::FocusedWidget::Previous);
+                }
+            });
+        if !opened {
+            self.visible.replace(false);
+        }
+    }
+}
+
+impl IUiLayerImpl for ScriptConsole {
+    // Raw imgui, like the per-game debug overlays; `ui_host` is unused.
+    fn render(&self, _ui_host: ComRc<IUiHost>, _delta_sec: f32) {
+        let ui = self.ui.ui();
+        if ui.is_key_pressed_no_repeat(TOGGLE_KEY) {
+            let visible = *self.visible.borrow();
+            self.visible.replace(!visible);
+        }
+
+        if *self.visible.borrow() {
+            self.render_window();
+        }
+    }
+}
+
+/// Routes the input box's Tab / Up / Down to the session.
+struct LineEditor<'a> {
+    session: &'a RefCell<ConsoleSession>,
+}
+
+impl InputTextCallbackHandler for LineEditor<'_> {
+    fn on_completion(&mut self, mut data: TextCallbackData) {
+        let completed = self.session.borrow_mut().complete(data.str());
+        if let Some(line) = completed {
+            data.clear();
+            data.push_str(&line);
+        }
+    }
+
+    fn on_history(&mut self, direction: HistoryDirection, mut data: TextCallbackData) {
+        let older = matches!(direction, HistoryDirection::Up);
+        let line = self.session.borrow_mut().browse_history(older);
+        if let Some(line) = line {
+            data.clear();
+            data.push_str(&line);
+        }
+    }
+}
+
+/// Registers the console on `app`'s UI manager. The console lives as
+/// long as the returned handle.
+pub fn install_script_console(
+    app: &ComRc<IApplication>,
+    host: Rc<ScriptHost>,
+    host_context: ComRc<IHostContext>,
+) -> UiLayerHandle {
+    let ui = app.engine().borrow().ui_manager();
+    let layer: ComRc<IUiLayer> =
+        ComRc::from_object(ScriptConsole::new(ui.clone(), host, host_context));
+    ui.register_ui_layer(UiLayerBand::DebugOverlay, layer)
+}
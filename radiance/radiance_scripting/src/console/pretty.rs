@@ -0,0 +1,148 @@
+//! Pretty printer for script values.
+//!
+//! Walks a `Data` the way the interpreter sees it: boxes are followed
+//! into the box heap, foreign carriers print their IDL type tag and
+//! COM table handle, and proto boxes list the `@foreign` protos their
+//! concrete struct conforms to. Anything the printer has no shape for
+//! falls back to the `Debug` form, so nothing prints as blank.
+
+use p7::interpreter::context::{Context, Data};
+
+/// Nesting beyond this prints as `…` — box graphs can be cyclic.
+const MAX_DEPTH: usize = 6;
+/// Array elements shown before the rest is summarised.
+const MAX_ITEMS: usize = 32;
+
+/// How the console asked for the value. p7 has no distinct runtime
+/// shape for `bool`, so the requested return type is the only way to
+/// print `true` rather than `1`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ValueHint {
+    Any,
+    Bool,
+}
+
+pub fn format_data(ctx: &Context, data: &Data, hint: ValueHint) -> String {
+    let mut out = String::new();
+    write_data(ctx, data, hint, 0, &mut out);
+    out
+}
+
+fn write_data(ctx: &Context, data: &Data, hint: ValueHint, depth: usize, out: &mut String) {
+    if depth > MAX_DEPTH {
+        out.push('…');
+        return;
+    }
+
+    match data {
+        Data::Int(v) if hint == ValueHint::Bool => {
+            out.push_str(if *v != 0 { "true" } else { "false" });
+        }
+        Data::Int(v) => out.push_str(&v.to_string()),
+        Data::Float(v) => out.push_str(&format!("{:?}", v)),
+        Data::String(s) => out.push_str(&format!("{:?}", &**s)),
+        Data::Null => out.push_str("null"),
+        Data::Some(inner) => {
+            out.push_str("some(");
+            write_data(ctx, inner, hint, depth + 1, out);
+            out.push(')');
+        }
+        Data::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().take(MAX_ITEMS).enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_data(ctx, item, hint, depth + 1, out);
+            }
+            if items.len() > MAX_ITEMS {
+                out.push_str(&format!(", … {} more", items.len() - MAX_ITEMS));
+            }
+            out.push(']');
+        }
+        Data::Foreign {
+            type_tag, handle, ..
+        } => out.push_str(&format!("<{} #{}>", type_tag, handle)),
+        Data::BoxRef { idx, generation } => {
+            write_box(ctx, *idx, *generation, None, depth, out);
+        }
+        Data::ProtoBoxRef {
+            box_idx,
+            generation,
+            concrete_type_id,
+            origin_module_idx,
+        } => {
+            let conforms = ctx
+                .struct_foreign_proto_tags(*origin_module_idx as usize, *concrete_type_id)
+                .into_iter()
+                .map(|tag| tag.to_string())
+                .collect::<Vec<_>>();
+            write_box(ctx, *box_idx, *generation, Some(conforms), depth, out);
+        }
+        other => out.push_str(&format!("{:?}", other)),
+    }
+}
+
+fn write_box(
+    ctx: &Context,
+    idx: u32,
+    generation: u32,
+    conforms: Option<Vec<String>>,
+    depth: usize,
+    out: &mut String,
+) {
+    let payload = match ctx.box_heap.get(idx, generation) {
+        Ok(payload) => payload,
+        Err(_) => {
+            out.push_str(&format!("box(<collected #{}>)", idx));
+            return;
+        }
+    };
+
+    // A foreign carrier is just a COM handle in a box; print it bare
+    // so `host.vfs()` reads as the interface, not as a box of one.
+    if let Data::Foreign { .. } = payload {
+        write_data(ctx, payload, ValueHint::Any, depth + 1, out);
+        return;
+    }
+
+    out.push_str("box");
+    if let Some(conforms) = conforms {
+        if !conforms.is_empty() {
+            out.push_str(&format!("[{}]", conforms.join(", ")));
+        }
+    }
+    out.push('(');
+    write_data(ctx, payload, ValueHint::Any, depth + 1, out);
+    out.push(')');
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn scalars_and_arrays_print_as_literals() {
+        let ctx = Context::new();
+        let array = Data::Array(Rc::new(vec![
+            Data::Int(1),
+            Data::Float(0.5),
+            Data::String(Rc::from("a\"b")),
+            Data::Null,
+        ]));
+        assert_eq!(
+            format_data(&ctx, &array, ValueHint::Any),
+            r#"[1, 0.5, "a\"b", null]"#
+        );
+        assert_eq!(format_data(&ctx, &Data::Int(0), ValueHint::Bool), "false");
+    }
+
+    #[test]
+    fn long_arrays_are_summarised() {
+        let ctx = Context::new();
+        let array = Data::Array(Rc::new((0..40).map(Data::Int).collect()));
+        assert!(format_data(&ctx, &array, ValueHint::Any).ends_with(", … 8 more]"));
+    }
+}
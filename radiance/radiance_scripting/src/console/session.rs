@@ -0,0 +1,353 @@
+//! Console state and snippet evaluation, independent of imgui.
+//!
+//! Each submitted snippet is compiled as its own module:
+//!
+//! ```text
+//! import scripting_services;
+//! import <every `import` the session has accepted>;
+//!
+//! pub fn __console_eval_<n>(host: box<scripting_services.IHostContext>) -> <T> {
+//!     <snippet>
+//! }
+//! ```
+//!
+//! p7 has no inferred return types, so `<T>` is found by trial: the
+//! snippet is type-checked as `int`, `float`, `bool` and `string` in
+//! turn and, failing all of those, run as a statement with no result.
+//! `:as <type> <expr>` names the type explicitly (for boxes and
+//! structs). Only the winning wrapper is loaded; `load_module` is
+//! append-only, so a long session grows the interpreter by one small
+//! module per evaluation until the next `ScriptHost::reload`.
+
+use std::rc::Rc;
+
+use crosscom::ComRc;
+use crosscom_protosept::{HostError, RuntimeAccess};
+use p7::interpreter::context::Data;
+
+use super::completion::{CompletionIndex, common_prefix};
+use super::pretty::{ValueHint, format_data};
+use crate::comdef::services::IHostContext;
+use crate::runtime::ScriptHost;
+use crate::script_vfs::HOST_CONTEXT_TYPE_TAG;
+
+/// Modules every snippet imports; `host` is typed against the first.
+const DEFAULT_IMPORTS: &[&str] = &["scripting_services", "radiance"];
+const MAX_HISTORY: usize = 200;
+const MAX_LOG_LINES: usize = 1000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConsoleLine {
+    Input(String),
+    Output(String),
+    Error(String),
+}
+
+/// Return types tried, in order, before falling back to a statement.
+const TRIAL_RETURNS: &[(&str, ValueHint)] = &[
+    ("int", ValueHint::Any),
+    ("float", ValueHint::Any),
+    ("bool", ValueHint::Bool),
+    ("string", ValueHint::Any),
+];
+
+pub struct ConsoleSession {
+    host: Rc<ScriptHost>,
+    host_context: Option<ComRc<IHostContext>>,
+    imports: Vec<String>,
+    log: Vec<ConsoleLine>,
+    history: Vec<String>,
+    /// Position while browsing history with Up/Down; `None` when
+    /// editing a fresh line.
+    history_cursor: Option<usize>,
+    completion: Option<CompletionIndex>,
+    next_eval: u32,
+    /// `host` interned into the COM table, tagged with the interpreter
+    /// generation it was interned under (a reload clears the table).
+    interned_host: Option<(u64, i64)>,
+}
+
+impl ConsoleSession {
+    /// `host_context` is bound to `host` in every snippet. Without one
+    /// (headless tests) snippets take no parameters.
+    pub fn new(host: Rc<ScriptHost>, host_context: Option<ComRc<IHostContext>>) -> Self {
+        Self {
+            host,
+            host_context,
+            imports: DEFAULT_IMPORTS.iter().map(|m| m.to_string()).collect(),
+            log: Vec::new(),
+            history: Vec::new(),
+            history_cursor: None,
+            completion: None,
+            next_eval: 0,
+            interned_host: None,
+        }
+    }
+
+    pub fn log(&self) -> &[ConsoleLine] {
+        &self.log
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Evaluate one console line, recording it and its result in the
+    /// log and history.
+    pub fn submit(&mut self, input: &str) {
+        let input = input.trim();
+        if input.is_empty() {
+            return;
+        }
+        self.push_log(ConsoleLine::Input(input.to_string()));
+        if self.history.last().map(String::as_str) != Some(input) {
+            self.history.push(input.to_string());
+            if self.history.len() > MAX_HISTORY {
+                self.history.remove(0);
+            }
+        }
+        self.history_cursor = None;
+
+        match self.eval(input) {
+            Ok(Some(output)) => self.push_log(ConsoleLine::Output(output)),
+            Ok(None) => {}
+            Err(err) => self.push_log(ConsoleLine::Error(err)),
+        }
+    }
+
+    /// Evaluate `input` without touching the log: `Ok(Some(text))` for
+    /// a value or command reply, `Ok(None)` for a statement.
+    pub fn eval(&mut self, input: &str) -> Result<Option<String>, String> {
+        if let Some(command) = input.strip_prefix(':') {
+            return self.run_command(command.trim());
+        }
+        if let Some(rest) = input.strip_prefix("import ") {
+            return self.import(rest.trim().trim_end_matches(';').trim());
+        }
+
+        let statement = input.ends_with(';') || input.ends_with('}');
+        if !statement {
+            for (ret, hint) in TRIAL_RETURNS {
+                let source = self.wrapper(self.next_eval, Some(ret), input);
+                if self.host.check_source(&source).is_ok() {
+                    return self.run(source, true, *hint);
+                }
+            }
+        }
+
+        let body = if statement {
+            input.to_string()
+        } else {
+            format!("{};", input)
+        };
+        let source = self.wrapper(self.next_eval, None, &body);
+        self.run(source, false, ValueHint::Any)
+    }
+
+    /// Up/Down history browsing. Returns the line to show in the input
+    /// box, or `None` to leave it as is.
+    pub fn browse_history(&mut self, older: bool) -> Option<String> {
+        if self.history.is_empty() {
+            return None;
+        }
+        let cursor = match (self.history_cursor, older) {
+            (None, true) => self.history.len() - 1,
+            (None, false) => return None,
+            (Some(0), true) => 0,
+            (Some(i), true) => i - 1,
+            (Some(i), false) if i + 1 >= self.history.len() => {
+                self.history_cursor = None;
+                return Some(String::new());
+            }
+            (Some(i), false) => i + 1,
+        };
+        self.history_cursor = Some(cursor);
+        Some(self.history[cursor].clone())
+    }
+
+    /// Tab completion for `line`. Returns the completed line when there
+    /// is something to insert; ambiguous matches also list every
+    /// candidate in the log.
+    pub fn complete(&mut self, line: &str) -> Option<String> {
+        if self.completion.is_none() {
+            self.completion = Some(CompletionIndex::build(&self.host, &self.imports));
+        }
+        let (start, candidates) = self.completion.as_ref()?.complete(line);
+        match candidates.len() {
+            0 => None,
+            1 => Some(format!("{}{}", &line[..start], candidates[0])),
+            _ => {
+                let prefix = common_prefix(&candidates).to_string();
+                self.push_log(ConsoleLine::Output(candidates.join("  ")));
+                (prefix.len() > line.len() - start).then(|| format!("{}{}", &line[..start], prefix))
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.log.clear();
+    }
+
+    fn run_command(&mut self, command: &str) -> Result<Option<String>, String> {
+        let (name, rest) = command.split_once(' ').unwrap_or((command, ""));
+        match name {
+            "help" => Ok(Some(
+                [
+                    "<expr>            evaluate and print (int / float / bool / string)",
+                    "<stmt>;           run for side effects",
+                    "import a.b;       make module a.b visible to later lines",
+                    ":as <type> <expr> evaluate as an explicit p7 type, e.g. box<radiance.IScene>",
+                    ":imports          list imported modules",
+                    ":clear            clear the console",
+                    "Tab completes, Up/Down walks history. `host` is the IHostContext.",
+                ]
+                .join("\n"),
+            )),
+            "clear" => {
+                self.clear();
+                Ok(None)
+            }
+            "imports" => Ok(Some(self.imports.join("\n"))),
+            "as" => {
+                let (ty, expr) = split_type(rest.trim())
+                    .ok_or_else(|| "usage: :as <type> <expr>".to_string())?;
+                let source = self.wrapper(self.next_eval, Some(ty), expr);
+                self.run(source, true, ValueHint::Any)
+            }
+            _ => Err(format!("unknown command :{} (try :help)", name)),
+        }
+    }
+
+    fn import(&mut self, module_path: &str) -> Result<Option<String>, String> {
+        if module_path.is_empty() {
+            return Err("usage: import <module>;".to_string());
+        }
+        if self.imports.iter().any(|m| m == module_path) {
+            return Ok(Some(format!("{} already imported", module_path)));
+        }
+        if self.host.module_source(module_path).is_none() {
+            return Err(format!("no module {}", module_path));
+        }
+        self.imports.push(module_path.to_string());
+        self.completion = None;
+        Ok(Some(format!("imported {}", module_path)))
+    }
+
+    fn wrapper(&self, n: u32, ret: Option<&str>, body: &str) -> String {
+        let mut source = String::new();
+        for module in &self.imports {
+            source.push_str(&format!("import {};\n", module));
+        }
+        let param = if self.host_context.is_some() {
+            "host: box<scripting_services.IHostContext>"
+        } else {
+            ""
+        };
+        let ret = ret.map(|ty| format!(" -> {}", ty)).unwrap_or_default();
+        source.push_str(&format!(
+            "\npub fn {}({}){} {{\n{}\n}}\n",
+            eval_fn_name(n),
+            param,
+            ret,
+            body
+        ));
+        source
+    }
+
+    fn run(
+        &mut self,
+        source: String,
+        returns: bool,
+        hint: ValueHint,
+    ) -> Result<Option<String>, String> {
+        let name = eval_fn_name(self.next_eval);
+        self.host
+            .load_source(&source)
+            .map_err(|err| err.to_string())?;
+        self.next_eval += 1;
+
+        let args = match self.host_box().map_err(|err| err.to_string())? {
+            Some(host_box) => vec![host_box],
+            None => Vec::new(),
+        };
+        if !returns {
+            self.host
+                .call_void(&name, args)
+                .map_err(|err| err.to_string())?;
+            return Ok(None);
+        }
+        let value = self
+            .host
+            .call_returning_data(&name, args)
+            .map_err(|err| err.to_string())?;
+        Ok(Some(
+            self.host.with_ctx(|ctx| format_data(ctx, &value, hint)),
+        ))
+    }
+
+    fn host_box(&mut self) -> Result<Option<Data>, HostError> {
+        let Some(host_context) = self.host_context.as_ref() else {
+            return Ok(None);
+        };
+        let generation = self.host.generation();
+        let handle = match self.interned_host {
+            Some((interned_at, handle)) if interned_at == generation => handle,
+            _ => {
+                let handle = self.host.intern(host_context.clone());
+                self.interned_host = Some((generation, handle));
+                handle
+            }
+        };
+        self.host
+            .foreign_box(HOST_CONTEXT_TYPE_TAG, handle)
+            .map(Some)
+    }
+
+    fn push_log(&mut self, line: ConsoleLine) {
+        self.log.push(line);
+        if self.log.len() > MAX_LOG_LINES {
+            let excess = self.log.len() - MAX_LOG_LINES;
+            self.log.drain(..excess);
+        }
+    }
+}
+
+fn eval_fn_name(n: u32) -> String {
+    format!("__console_eval_{}", n)
+}
+
+/// Split `box<radiance.IScene> host.scene_manager().scene()` into the
+/// type and the expression, honouring `<...>` nesting in the type.
+fn split_type(text: &str) -> Option<(&str, &str)> {
+    let mut depth = 0i32;
+    for (i, c) in text.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                let expr = text[i..].trim();
+                return (!expr.is_empty()).then_some((&text[..i], expr));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_command_splits_nested_generic_types() {
+        assert_eq!(
+            split_type("?box<radiance.IScene> host.scene()"),
+            Some(("?box<radiance.IScene>", "host.scene()"))
+        );
+        assert_eq!(
+            split_type("array<box<a.B>>  xs"),
+            Some(("array<box<a.B>>", "xs"))
+        );
+        assert_eq!(split_type("int"), None);
+    }
+}
@@ -0,0 +1,538 @@
+//! Between-frame hot reload of `.p7` script packages.
+//!
+//! Opt-in via `RADIANCE_SCRIPT_HOT_RELOAD=1`. When enabled:
+//!
+//! * [`mount_script_bundle`] lays each crate's on-disk `scripts/` dir
+//!   over its build-time ypk (see
+//!   `AssetManager::mount_ypk_bytes_with_local_overlay`), so the
+//!   script `AssetManager` serves the sources as they are on disk, and
+//!   remembers the dir for the watcher.
+//! * [`install_hot_reloader`] adds a [`ScriptHotReloader`] component to
+//!   the application. Its `on_updating` runs before `engine.update`,
+//!   i.e. between frames and outside any script call — the only place
+//!   [`ScriptHost::reload`] is safe.
+//!
+//! A reload is a restart of the script package, not a patch: p7's
+//! `load_module` is append-only, so the whole interpreter is rebuilt
+//! and the package's root re-bootstrapped through the caller's
+//! [`Rebootstrap`] closure, which returns the new root director
+//! (re-wrapped via `wrap_director`).
+//!
+//! The active director is then rebuilt on the new interpreter when its
+//! struct names the factory that made it, and its state carried over,
+//! through opt-in hooks:
+//!
+//! ```text
+//! pub fn reload_factory(self: ref<Self>) -> string;  // old interpreter
+//! pub fn serialize(self: ref<Self>) -> string;       // old interpreter
+//! pub fn restore(self: ref<Self>, state: string);    // rebuilt director
+//! ```
+//!
+//! `reload_factory` returns the module-qualified factory, e.g.
+//! `"yaobow.title.title.make_settings_director"`, which is called on
+//! the new interpreter with the host context (like a mod's director
+//! factory) and wrapped via `wrap_director`. Without the hook, or when
+//! the factory no longer exists or fails, the new root director takes
+//! over instead. `restore` runs before the director's `activate`.
+//! [`CarriedDirector`] is that capture/rebuild step on its own; the
+//! shipped title, start-menu and editor directors implement the hooks.
+//!
+//! Reloads are applied only while the active director is
+//! script-implemented. While a Rust director (a running game, a
+//! previewer) is on top it may hold script objects of its own —
+//! controllers, factories — that a rebuild would strand, so changes
+//! stay pending until a script director is active again.
+
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use crosscom::ComRc;
+use crosscom_protosept::{HostError, proto_ccw_data};
+use p7::interpreter::context::Data;
+use radiance::asset::AssetManager;
+use radiance::comdef::{IApplication, IApplicationExt, IComponentImpl, IDirector};
+
+use crate::comdef::services::IScriptHotReloader;
+use crate::mods::ModHostArg;
+use crate::runtime::ScriptHost;
+use crate::wrap_director;
+
+/// Environment variable enabling hot reload (any value but `0`).
+pub const HOT_RELOAD_ENV: &str = "RADIANCE_SCRIPT_HOT_RELOAD";
+
+/// Seconds between two scans of the watched dirs.
+const SCAN_INTERVAL_SEC: f32 = 0.5;
+
+pub fn hot_reload_enabled() -> bool {
+    match std::env::var(HOT_RELOAD_ENV) {
+        Ok(value) => !value.is_empty() && value != "0",
+        Err(_) => false,
+    }
+}
+
+thread_local! {
+    static SOURCE_DIRS: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Mounts a crate's script ypk at `vfs_path`. With hot reload enabled
+/// and `source_dir` (the crate's `scripts/` dir, baked in at compile
+/// time) present, the dir is overlaid on the bundle and registered
+/// for [`ScriptWatcher::watched_sources`]; otherwise this is plain
+/// `mount_ypk_bytes`.
+pub fn mount_script_bundle(
+    assets: &AssetManager,
+    vfs_path: &str,
+    bundle: &'static [u8],
+    source_dir: &str,
+) -> anyhow::Result<()> {
+    let source_dir = Path::new(source_dir);
+    if !hot_reload_enabled() || !source_dir.is_dir() {
+        return assets.mount_ypk_bytes(vfs_path, bundle);
+    }
+
+    assets.mount_ypk_bytes_with_local_overlay(vfs_path, bundle, source_dir)?;
+    SOURCE_DIRS.with(|dirs| {
+        let mut dirs = dirs.borrow_mut();
+        if !dirs.iter().any(|dir| dir == source_dir) {
+            dirs.push(source_dir.to_path_buf());
+        }
+    });
+    log::info!(
+        "script hot reload: serving {} from {}",
+        vfs_path,
+        source_dir.display()
+    );
+    Ok(())
+}
+
+/// Modification-time snapshot of every file under a set of dirs.
+/// Polling keeps this dependency-free and is cheap at the size of
+/// our script trees.
+pub struct ScriptWatcher {
+    dirs: Vec<PathBuf>,
+    stamps: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl ScriptWatcher {
+    pub fn new(dirs: Vec<PathBuf>) -> Self {
+        let stamps = snapshot(&dirs);
+        Self { dirs, stamps }
+    }
+
+    /// Watches every source dir registered by [`mount_script_bundle`].
+    pub fn watched_sources() -> Self {
+        Self::new(SOURCE_DIRS.with(|dirs| dirs.borrow().clone()))
+    }
+
+    pub fn dirs(&self) -> &[PathBuf] {
+        &self.dirs
+    }
+
+    /// Files modified, added or removed since the previous call (or
+    /// since construction).
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let current = snapshot(&self.dirs);
+        let mut changed: Vec<PathBuf> = current
+            .iter()
+            .filter(|(path, stamp)| self.stamps.get(*path) != Some(*stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.stamps
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned(),
+        );
+        self.stamps = current;
+        changed
+    }
+}
+
+fn snapshot(dirs: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    let mut stamps = BTreeMap::new();
+    for dir in dirs {
+        scan(dir, &mut stamps);
+    }
+    stamps
+}
+
+fn scan(dir: &Path, stamps: &mut BTreeMap<PathBuf, SystemTime>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            scan(&path, stamps);
+        } else if let Ok(modified) = metadata.modified() {
+            stamps.insert(path, modified);
+        }
+    }
+}
+
+/// Rebuilds the package root on a freshly reloaded [`ScriptHost`] and
+/// returns the director to install — typically
+/// `bootstrap_script_root_from_path` followed by `wrap_director`, plus
+/// whatever the app re-wires from the new root (factories handed to
+/// services, ...).
+pub type Rebootstrap = Box<dyn FnMut(&ScriptHost) -> Result<ComRc<IDirector>, HostError>>;
+
+pub struct ScriptHotReloader {
+    app: ComRc<IApplication>,
+    host: Rc<ScriptHost>,
+    /// Package root, compiled ahead of each reload so a broken edit
+    /// never tears down the running interpreter.
+    root_path: String,
+    watcher: RefCell<ScriptWatcher>,
+    rebootstrap: RefCell<Rebootstrap>,
+    /// Argument handed to the active director's `reload_factory`.
+    host_arg: ModHostArg,
+    since_scan: Cell<f32>,
+    /// Changes seen but not applied yet (no script director active).
+    pending: Cell<bool>,
+    /// The last reload tore down the old interpreter but the
+    /// rebootstrap failed, so no script director is running. The next
+    /// change reloads unconditionally.
+    orphaned: Cell<bool>,
+}
+
+ComObject_ScriptHotReloader!(super::ScriptHotReloader);
+
+impl ScriptHotReloader {
+    fn tick(&self, delta_sec: f32) {
+        let since_scan = self.since_scan.get() + delta_sec;
+        if since_scan < SCAN_INTERVAL_SEC {
+            self.since_scan.set(since_scan);
+            return;
+        }
+        self.since_scan.set(0.);
+
+        let changed = self.watcher.borrow_mut().poll();
+        if !changed.is_empty() {
+            for path in &changed {
+                log::info!("script hot reload: {} changed", path.display());
+            }
+            self.pending.set(true);
+        }
+        if self.pending.get() {
+            self.reload();
+        }
+    }
+
+    fn reload(&self) {
+        let scene_manager = self.app.engine().borrow().scene_manager().clone();
+        let active = scene_manager.director();
+        let active_data = active.as_ref().and_then(proto_ccw_data);
+        if active_data.is_none() && !self.orphaned.get() {
+            return;
+        }
+
+        self.pending.set(false);
+        if let Err(err) = self.host.check_source_from_path(&self.root_path) {
+            log::error!(
+                "script hot reload: {} does not compile, keeping the running scripts: {}",
+                self.root_path,
+                err
+            );
+            return;
+        }
+
+        let carried = active_data.map(|data| CarriedDirector::capture(&self.host, data));
+
+        // Deactivate and release the old director while its
+        // interpreter is still alive; everything else still holding a
+        // CCW into it goes inert on `reload`.
+        scene_manager.unset_director();
+        drop(active);
+        self.host.reload();
+
+        let root = match (self.rebootstrap.borrow_mut())(&self.host) {
+            Ok(director) => director,
+            Err(err) => {
+                log::error!(
+                    "script hot reload: {} failed to start: {}",
+                    self.root_path,
+                    err
+                );
+                self.orphaned.set(true);
+                return;
+            }
+        };
+        self.orphaned.set(false);
+
+        let director = match carried {
+            Some(carried) => carried.rebuild(&self.host, &self.host_arg, root),
+            None => root,
+        };
+        scene_manager.set_director(director);
+        log::info!("script hot reload: reloaded {}", self.root_path);
+    }
+}
+
+/// What a reload carries over from the active script director: the
+/// factory its `reload_factory` names and the state its `serialize`
+/// returns. Captured on the old interpreter, applied to the new one
+/// by [`CarriedDirector::rebuild`].
+pub struct CarriedDirector {
+    factory: Option<String>,
+    state: Option<Data>,
+}
+
+impl CarriedDirector {
+    /// Runs the `reload_factory` and `serialize` hooks on `director`,
+    /// the script object behind the active director. Missing hooks
+    /// just leave the matching half empty.
+    pub fn capture(host: &ScriptHost, director: Data) -> Self {
+        let factory = reload_factory(host, director.clone());
+        let state = serialize(host, director);
+        Self { factory, state }
+    }
+
+    pub fn factory(&self) -> Option<&str> {
+        self.factory.as_deref()
+    }
+
+    /// Builds the director to install on the reloaded `host`: the one
+    /// the captured factory makes, or `root` when there was none or it
+    /// no longer builds. The captured state is handed to `restore`
+    /// on whichever director it came from — it is dropped when a named
+    /// factory failed, since the root does not know another
+    /// director's state.
+    pub fn rebuild(
+        self,
+        host: &ScriptHost,
+        host_arg: &ModHostArg,
+        root: ComRc<IDirector>,
+    ) -> ComRc<IDirector> {
+        let (director, own_state) = match self.factory {
+            Some(factory) => match rebuild(host, host_arg, &factory) {
+                Some(director) => (director, true),
+                None => (root, false),
+            },
+            None => (root, true),
+        };
+        if let (Some(state), true) = (self.state, own_state) {
+            restore(host, &director, state);
+        }
+        director
+    }
+}
+
+/// The factory the active director's `reload_factory` names, if it
+/// implements the hook.
+fn reload_factory(host: &ScriptHost, director: Data) -> Option<String> {
+    match host.call_method_returning_data(director, "reload_factory", vec![]) {
+        Ok(Data::String(factory)) => Some(factory.to_string()),
+        Ok(other) => {
+            log::warn!(
+                "script hot reload: reload_factory returned {:?}, expected a string",
+                other
+            );
+            None
+        }
+        Err(err) => {
+            log::debug!("script hot reload: active director not rebuilt: {}", err);
+            None
+        }
+    }
+}
+
+/// Builds the director `factory` names on the new interpreter.
+/// `None` (logged) when it no longer exists or fails.
+fn rebuild(host: &ScriptHost, host_arg: &ModHostArg, factory: &str) -> Option<ComRc<IDirector>> {
+    let Some((module, function)) = split_factory_path(factory) else {
+        log::warn!("script hot reload: bad reload_factory '{}'", factory);
+        return None;
+    };
+    if !host.has_module_function(module, function) {
+        log::info!(
+            "script hot reload: {} is gone, starting from the root director",
+            factory
+        );
+        return None;
+    }
+
+    let made = host_arg(host).and_then(|arg| {
+        let data = host.call_module_returning_data(module, function, vec![arg])?;
+        wrap_director(&host.runtime_handle(), data)
+    });
+    match made {
+        Ok(director) => Some(director),
+        Err(err) => {
+            log::error!(
+                "script hot reload: {} failed, starting from the root director: {}",
+                factory,
+                err
+            );
+            None
+        }
+    }
+}
+
+fn serialize(host: &ScriptHost, director: Data) -> Option<Data> {
+    match host.call_method_returning_data(director, "serialize", vec![]) {
+        Ok(state @ Data::String(_)) => Some(state),
+        Ok(other) => {
+            log::warn!(
+                "script hot reload: serialize returned {:?}, expected a string; state dropped",
+                other
+            );
+            None
+        }
+        // Most directors simply don't implement the hook.
+        Err(err) => {
+            log::debug!("script hot reload: no state carried over: {}", err);
+            None
+        }
+    }
+}
+
+fn restore(host: &ScriptHost, director: &ComRc<IDirector>, state: Data) {
+    let Some(data) = proto_ccw_data(director) else {
+        return;
+    };
+    if let Err(err) = host.call_method_void(data, "restore", vec![state]) {
+        log::warn!("script hot reload: restore failed: {}", err);
+    }
+}
+
+/// Splits `"a.b.make"` into the module `"a.b"` and function `"make"`.
+fn split_factory_path(path: &str) -> Option<(&str, &str)> {
+    let (module, function) = path.rsplit_once('.')?;
+    (!module.is_empty() && !function.is_empty()).then_some((module, function))
+}
+
+impl IComponentImpl for ScriptHotReloader {
+    fn on_loading(&self) {}
+
+    fn on_updating(&self, delta_sec: f32) {
+        self.tick(delta_sec);
+    }
+
+    fn on_unloading(&self) {}
+}
+
+/// Installs a [`ScriptHotReloader`] on `app` when hot reload is
+/// enabled and at least one script source dir was mounted through
+/// [`mount_script_bundle`]. `root_path` is the package root passed to
+/// `bootstrap_script_root_from_path` (e.g. `/yaobow_editor/main.p7`);
+/// `host_arg` builds the argument a rebuilt director's factory gets.
+/// Returns whether a reloader was installed.
+pub fn install_hot_reloader(
+    app: &ComRc<IApplication>,
+    host: Rc<ScriptHost>,
+    root_path: &str,
+    rebootstrap: Rebootstrap,
+    host_arg: ModHostArg,
+) -> bool {
+    if !hot_reload_enabled() {
+        return false;
+    }
+
+    let watcher = ScriptWatcher::watched_sources();
+    if watcher.dirs().is_empty() {
+        log::warn!(
+            "script hot reload: {} is set but no script source dir exists; running the packed bundles",
+            HOT_RELOAD_ENV
+        );
+        return false;
+    }
+
+    log::info!(
+        "script hot reload: watching {} dir(s) for {}",
+        watcher.dirs().len(),
+        root_path
+    );
+    app.add_component(
+        IScriptHotReloader::uuid(),
+        ComRc::from_object(ScriptHotReloader {
+            app: app.clone(),
+            host,
+            root_path: root_path.to_string(),
+            watcher: RefCell::new(watcher),
+            rebootstrap: RefCell::new(rebootstrap),
+            host_arg,
+            since_scan: Cell::new(0.),
+            pending: Cell::new(false),
+            orphaned: Cell::new(false),
+        }),
+    );
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::time::Duration;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("script_hot_reload_{}_{}", std::process::id(), name))
+    }
+
+    fn touch(path: &Path, content: &str, modified: SystemTime) {
+        fs::write(path, content).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    #[test]
+    fn watcher_reports_modified_added_and_removed_files() {
+        let root = unique_tmp("watcher");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("panels")).unwrap();
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        touch(&root.join("main.p7"), "// main", t0);
+        touch(&root.join("panels/log.p7"), "// log", t0);
+
+        let mut watcher = ScriptWatcher::new(vec![root.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        touch(
+            &root.join("panels/log.p7"),
+            "// log v2",
+            t0 + Duration::from_secs(5),
+        );
+        assert_eq!(watcher.poll(), vec![root.join("panels/log.p7")]);
+        assert!(watcher.poll().is_empty(), "a change is reported once");
+
+        touch(&root.join("panels/new.p7"), "// new", t0);
+        fs::remove_file(root.join("main.p7")).unwrap();
+        let mut changed = watcher.poll();
+        changed.sort();
+        assert_eq!(
+            changed,
+            vec![root.join("main.p7"), root.join("panels/new.p7")]
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn factory_path_splits_at_the_last_dot() {
+        assert_eq!(
+            split_factory_path("yaobow.title.title.make_title"),
+            Some(("yaobow.title.title", "make_title"))
+        );
+        assert_eq!(split_factory_path("make_title"), None);
+        assert_eq!(split_factory_path("yaobow."), None);
+        assert_eq!(split_factory_path(".make_title"), None);
+    }
+
+    #[test]
+    fn missing_dir_watches_nothing() {
+        let mut watcher = ScriptWatcher::new(vec![unique_tmp("does_not_exist")]);
+        assert!(watcher.poll().is_empty());
+    }
+}
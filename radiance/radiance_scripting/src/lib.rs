@@ -20,6 +20,9 @@ pub mod script_bridges {
     }
 }
 
+pub mod console;
+pub mod hot_reload;
+pub mod mods;
 pub mod proxies;
 pub mod runtime;
 pub mod script_vfs;
@@ -67,9 +70,13 @@ pub fn mount_engine_bindings(assets: &radiance::asset::AssetManager) {
 /// `AssetManager`, so per-game launchers can
 /// `import radiance_scripting.freeview;`.
 pub fn mount_scripts(assets: &radiance::asset::AssetManager) {
-    assets
-        .mount_ypk_bytes("/radiance_scripting", SCRIPT_BUNDLE_YPK)
-        .expect("radiance_scripting.ypk must mount");
+    hot_reload::mount_script_bundle(
+        assets,
+        "/radiance_scripting",
+        SCRIPT_BUNDLE_YPK,
+        concat!(env!("CARGO_MANIFEST_DIR"), "/scripts"),
+    )
+    .expect("radiance_scripting.ypk must mount");
 }
 
 /// Re-export of the radiance-side `wrap_ray_caster` so callers
@@ -83,6 +90,6 @@ pub use radiance::utils::ray_casting::wrap_ray_caster;
 // dependency surface for `yaobow_editor` minimal — it can pull
 // everything it needs from this crate.
 pub use crosscom_protosept::{
-    ArgKind, MethodSpec, ProtoSpec, RetKind, RuntimeAccess, RuntimeHandle, register_proto_ccw,
-    with_services,
+    ArgKind, HostError, MethodSpec, ProtoSpec, RetKind, RuntimeAccess, RuntimeHandle,
+    proto_ccw_data, register_proto_ccw, with_services,
 };
@@ -0,0 +1,502 @@
+//! User script mods: extra `.ypk` script packages discovered at
+//! runtime.
+//!
+//! A mod is a ypk packed like the built-in bundles (`script-package`
+//! `pack`, with `mod.toml` passed as an extra file) carrying this
+//! manifest at its root:
+//!
+//! ```toml
+//! name = "clock_hud"           # [a-z0-9_]; also its import path
+//! version = "1.2.0"
+//! games = ["pal4"]             # optional; empty = every game
+//! requires = ["IVfsService"]   # host interfaces it calls through `host`
+//! entry = "main"               # module holding the factories (default)
+//!
+//! [[ui_layers]]
+//! factory = "make_clock"       # fn(host) -> box<radiance.IUiLayer>
+//! band = "hud"                 # scene / hud / dialog / debug_overlay
+//!
+//! [directors]
+//! title = "make_title"         # fn(host) -> box<radiance.IDirector>
+//! ```
+//!
+//! [`discover_mods`] reads and validates every manifest in the mods
+//! dir against a [`ModEnvironment`] — unknown games, missing host
+//! interfaces, unknown director slots and name clashes reject the mod
+//! with a report line instead of failing the boot. [`mount_mods`]
+//! mounts the accepted packages at `/mods/<name>/` on the script
+//! `AssetManager`, so a mod imports built-in modules as usual and is
+//! itself reachable as `mods.<name>.<module>`.
+//!
+//! [`load_mods`] runs after the built-in package has bootstrapped: it
+//! loads each mod's entry module, registers its UI layers on the
+//! engine `UiManager` and records its director overrides, which the
+//! app consults through [`LoadedMods::make_director`] wherever it
+//! would otherwise build the stock director for that slot.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crosscom::{ComInterface, ComRc};
+use crosscom_protosept::HostError;
+use p7::interpreter::context::Data;
+use radiance::asset::AssetManager;
+use radiance::comdef::IDirector;
+use radiance::radiance::{UiLayerBand, UiLayerHandle, UiManager};
+use serde::Deserialize;
+
+use crate::runtime::ScriptHost;
+use crate::script_bridges::radiance::{wrap_director, wrap_ui_layer};
+
+pub const MANIFEST_FILE: &str = "mod.toml";
+pub const MODS_VFS_ROOT: &str = "/mods";
+
+/// Host interfaces every `IHostContext` exposes; apps extend this with
+/// their own context's accessors in [`ModEnvironment::host_interfaces`].
+pub const HOST_CONTEXT_INTERFACES: &[&str] = &[
+    "ISceneManager",
+    "IAudioService",
+    "ITextureService",
+    "IVfsService",
+    "IInputService",
+    "IGameRegistry",
+    "IAppService",
+    "IRandomService",
+    "IConfigService",
+];
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ModManifest {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub games: Vec<String>,
+    #[serde(default)]
+    pub requires: Vec<String>,
+    #[serde(default = "default_entry")]
+    pub entry: String,
+    #[serde(default)]
+    pub ui_layers: Vec<ModUiLayer>,
+    /// Director slot (e.g. `title`) → factory function in `entry`.
+    #[serde(default)]
+    pub directors: BTreeMap<String, String>,
+}
+
+fn default_entry() -> String {
+    "main".to_string()
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ModUiLayer {
+    pub factory: String,
+    #[serde(default)]
+    pub band: ModUiBand,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModUiBand {
+    Scene,
+    #[default]
+    Hud,
+    Dialog,
+    DebugOverlay,
+}
+
+impl From<ModUiBand> for UiLayerBand {
+    fn from(band: ModUiBand) -> Self {
+        match band {
+            ModUiBand::Scene => UiLayerBand::Scene,
+            ModUiBand::Hud => UiLayerBand::Hud,
+            ModUiBand::Dialog => UiLayerBand::Dialog,
+            ModUiBand::DebugOverlay => UiLayerBand::DebugOverlay,
+        }
+    }
+}
+
+/// What the running app offers mods.
+pub struct ModEnvironment<'a> {
+    /// Game the app booted straight into, if any. Mods whose `games`
+    /// list leaves it out are skipped; in launcher mode (`None`) every
+    /// compatible mod loads.
+    pub game: Option<&'a str>,
+    /// Every game key a manifest may name.
+    pub known_games: &'a [&'a str],
+    /// Interfaces reachable through the `host` handed to factories.
+    pub host_interfaces: &'a [&'a str],
+    /// Director slots the app lets mods override.
+    pub director_slots: &'a [&'a str],
+}
+
+impl ModManifest {
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Reads `mod.toml` out of the ypk at `path` without mounting it
+    /// anywhere shared.
+    pub fn read_from_ypk(path: &Path) -> anyhow::Result<Self> {
+        let assets = AssetManager::new();
+        assets.mount_ypk("/", path)?;
+        let bytes = assets
+            .read_to_end(Path::new("/").join(MANIFEST_FILE))
+            .map_err(|err| anyhow::anyhow!("no {MANIFEST_FILE}: {err}"))?;
+        Self::parse(std::str::from_utf8(&bytes)?)
+    }
+
+    /// Every reason the manifest cannot load in `env`; empty when it
+    /// can.
+    pub fn problems(&self, env: &ModEnvironment) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !is_module_segment(&self.name) {
+            problems.push(format!(
+                "name '{}' must be lowercase letters, digits and '_'",
+                self.name
+            ));
+        }
+        if !is_version(&self.version) {
+            problems.push(format!(
+                "version '{}' is not of the form MAJOR.MINOR.PATCH",
+                self.version
+            ));
+        }
+        if !self.entry.split('.').all(is_module_segment) {
+            problems.push(format!("entry '{}' is not a module path", self.entry));
+        }
+        for game in &self.games {
+            if !env.known_games.contains(&game.as_str()) {
+                problems.push(format!("unknown game '{}'", game));
+            }
+        }
+        for interface in &self.requires {
+            if !env.host_interfaces.contains(&interface.as_str()) {
+                problems.push(format!("host does not provide {}", interface));
+            }
+        }
+        for slot in self.directors.keys() {
+            if !env.director_slots.contains(&slot.as_str()) {
+                problems.push(format!("no director slot '{}'", slot));
+            }
+        }
+        problems
+    }
+
+    pub fn supports_game(&self, game: &str) -> bool {
+        self.games.is_empty() || self.games.iter().any(|g| g == game)
+    }
+
+    /// Import path of the entry module once mounted by [`mount_mods`].
+    pub fn entry_module(&self) -> String {
+        format!("mods.{}.{}", self.name, self.entry)
+    }
+}
+
+fn is_module_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && !segment.starts_with(|c: char| c.is_ascii_digit())
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn is_version(version: &str) -> bool {
+    let parts: Vec<&str> = version.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// A validated mod package on disk.
+#[derive(Debug, Clone)]
+pub struct ModPackage {
+    pub manifest: ModManifest,
+    pub path: PathBuf,
+}
+
+/// Result of scanning the mods dir: accepted packages in load order
+/// (file name order) and one line per package that was not.
+#[derive(Debug, Default)]
+pub struct ModDiscovery {
+    pub packages: Vec<ModPackage>,
+    pub rejected: Vec<String>,
+}
+
+/// Scans `dir` for `*.ypk` mods. A missing dir is simply no mods.
+pub fn discover_mods(dir: &Path, env: &ModEnvironment) -> ModDiscovery {
+    let mut discovery = ModDiscovery::default();
+    let mut paths: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ypk"))
+            .collect(),
+        Err(_) => return discovery,
+    };
+    paths.sort();
+
+    let mut names = BTreeSet::new();
+    for path in paths {
+        let manifest = match ModManifest::read_from_ypk(&path) {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                discovery
+                    .rejected
+                    .push(format!("{}: {}", path.display(), err));
+                continue;
+            }
+        };
+
+        let mut problems = manifest.problems(env);
+        if !names.insert(manifest.name.clone()) {
+            problems.push(format!("another mod is already named '{}'", manifest.name));
+        }
+        if !problems.is_empty() {
+            discovery
+                .rejected
+                .push(format!("{}: {}", path.display(), problems.join("; ")));
+            continue;
+        }
+        if let Some(game) = env.game {
+            if !manifest.supports_game(game) {
+                discovery.rejected.push(format!(
+                    "{}: skipped, supports {} but running {}",
+                    path.display(),
+                    manifest.games.join(", "),
+                    game
+                ));
+                continue;
+            }
+        }
+        discovery.packages.push(ModPackage { manifest, path });
+    }
+    discovery
+}
+
+/// Mounts each package at `/mods/<name>/` on the script
+/// `AssetManager`. Packages that fail to mount are dropped from the
+/// returned list (and logged).
+pub fn mount_mods(assets: &AssetManager, packages: Vec<ModPackage>) -> Vec<ModPackage> {
+    packages
+        .into_iter()
+        .filter(|package| {
+            let vfs_path = format!("{}/{}", MODS_VFS_ROOT, package.manifest.name);
+            match assets.mount_ypk(vfs_path, &package.path) {
+                Ok(()) => true,
+                Err(err) => {
+                    log::error!("mod {}: mount failed: {err}", package.path.display());
+                    false
+                }
+            }
+        })
+        .collect()
+}
+
+/// Produces the `host` argument mod factories are called with.
+pub type ModHostArg = Rc<dyn Fn(&ScriptHost) -> Result<Data, HostError>>;
+
+/// Builds a [`ModHostArg`] that hands factories `host_context` as a
+/// foreign box tagged `type_tag`.
+pub fn mod_host_arg<I: ComInterface + 'static>(
+    host_context: ComRc<I>,
+    type_tag: &str,
+) -> ModHostArg {
+    let type_tag = type_tag.to_string();
+    Rc::new(move |host: &ScriptHost| {
+        let handle = host.intern(host_context.clone());
+        host.foreign_box(&type_tag, handle)
+    })
+}
+
+struct DirectorOverride {
+    mod_name: String,
+    module: String,
+    factory: String,
+}
+
+/// Mods loaded into the current interpreter. Their UI layers stay
+/// registered as long as this value lives; rebuild it after a
+/// `ScriptHost::reload`.
+pub struct LoadedMods {
+    host_arg: ModHostArg,
+    layers: Vec<UiLayerHandle>,
+    directors: BTreeMap<String, DirectorOverride>,
+}
+
+impl LoadedMods {
+    pub fn empty(host_arg: ModHostArg) -> Self {
+        Self {
+            host_arg,
+            layers: Vec::new(),
+            directors: BTreeMap::new(),
+        }
+    }
+
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Builds the director a mod registered for `slot`, or `None` if
+    /// no mod overrides it. When several do, the last in load order
+    /// wins.
+    pub fn make_director(
+        &self,
+        host: &ScriptHost,
+        slot: &str,
+    ) -> Option<Result<ComRc<IDirector>, HostError>> {
+        let entry = self.directors.get(slot)?;
+        let made = (self.host_arg)(host).and_then(|arg| {
+            let data = host.call_module_returning_data(&entry.module, &entry.factory, vec![arg])?;
+            wrap_director(&host.runtime_handle(), data)
+        });
+        Some(made.map_err(|err| {
+            HostError::message(format!(
+                "mod {} director '{}': {}",
+                entry.mod_name, slot, err
+            ))
+        }))
+    }
+}
+
+/// Loads each mounted package's entry module into `host` (after the
+/// built-in package, so mods see everything it defines), registers
+/// its UI layers on `ui` and records its director overrides. A mod
+/// that fails to compile or whose factory errors is logged and skipped
+/// without affecting the others.
+pub fn load_mods(
+    host: &ScriptHost,
+    ui: &UiManager,
+    packages: &[ModPackage],
+    host_arg: ModHostArg,
+) -> LoadedMods {
+    let mut loaded = LoadedMods::empty(host_arg);
+    for package in packages {
+        let manifest = &package.manifest;
+        if let Err(err) = load_mod(host, ui, manifest, &mut loaded) {
+            log::error!("mod {} {}: {}", manifest.name, manifest.version, err);
+            continue;
+        }
+        log::info!(
+            "mod {} {} loaded from {}",
+            manifest.name,
+            manifest.version,
+            package.path.display()
+        );
+    }
+    loaded
+}
+
+fn load_mod(
+    host: &ScriptHost,
+    ui: &UiManager,
+    manifest: &ModManifest,
+    loaded: &mut LoadedMods,
+) -> Result<(), HostError> {
+    let module = manifest.entry_module();
+    host.load_source(&format!("import {};\n", module))?;
+
+    // Build every layer before registering any, so a failing factory
+    // leaves no half-installed mod behind.
+    let mut layers = Vec::new();
+    for layer in &manifest.ui_layers {
+        let arg = (loaded.host_arg)(host)?;
+        let data = host.call_module_returning_data(&module, &layer.factory, vec![arg])?;
+        layers.push((layer.band, wrap_ui_layer(&host.runtime_handle(), data)?));
+    }
+    for (slot, factory) in &manifest.directors {
+        if !host.has_module_function(&module, factory) {
+            return Err(HostError::message(format!(
+                "director '{}': {} has no function {}",
+                slot, module, factory
+            )));
+        }
+    }
+
+    for (band, layer) in layers {
+        loaded.layers.push(ui.register_ui_layer(band.into(), layer));
+    }
+    for (slot, factory) in &manifest.directors {
+        loaded.directors.insert(
+            slot.clone(),
+            DirectorOverride {
+                mod_name: manifest.name.clone(),
+                module: module.clone(),
+                factory: factory.clone(),
+            },
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+name = "clock_hud"
+version = "1.2.0"
+games = ["pal4"]
+requires = ["IVfsService"]
+
+[[ui_layers]]
+factory = "make_clock"
+
+[directors]
+title = "make_title"
+"#;
+
+    fn env(game: Option<&'static str>) -> ModEnvironment<'static> {
+        ModEnvironment {
+            game,
+            known_games: &["pal3", "pal4"],
+            host_interfaces: HOST_CONTEXT_INTERFACES,
+            director_slots: &["title"],
+        }
+    }
+
+    #[test]
+    fn manifest_parses_with_defaults() {
+        let manifest = ModManifest::parse(MANIFEST).unwrap();
+        assert_eq!(manifest.entry, "main");
+        assert_eq!(manifest.entry_module(), "mods.clock_hud.main");
+        assert_eq!(manifest.ui_layers[0].band, ModUiBand::Hud);
+        assert_eq!(manifest.directors["title"], "make_title");
+        assert!(manifest.problems(&env(None)).is_empty());
+        assert!(manifest.supports_game("pal4"));
+        assert!(!manifest.supports_game("pal3"));
+    }
+
+    #[test]
+    fn manifest_problems_are_all_reported() {
+        let manifest = ModManifest::parse(
+            r#"
+name = "Clock-HUD"
+version = "1.2"
+games = ["pal9"]
+requires = ["IPal4Service"]
+
+[directors]
+pause_menu = "make_pause"
+"#,
+        )
+        .unwrap();
+        let problems = manifest.problems(&env(None));
+        assert_eq!(problems.len(), 5, "{:?}", problems);
+    }
+
+    #[test]
+    fn unknown_manifest_keys_are_rejected() {
+        assert!(ModManifest::parse("name = \"a\"\nversion = \"1.0.0\"\nhooks = 1\n").is_err());
+    }
+
+    #[test]
+    fn missing_mods_dir_discovers_nothing() {
+        let dir = std::env::temp_dir().join(format!("radiance-mods-none-{}", std::process::id()));
+        let discovery = discover_mods(&dir, &env(Some("pal4")));
+        assert!(discovery.packages.is_empty());
+        assert!(discovery.rejected.is_empty());
+    }
+}
@@ -248,6 +248,35 @@ impl ScriptHost {
     /// source via [`load_source`]. Typical use: pass the absolute
     /// path of an app-root p7 source — e.g. `/yaobow/app.p7`.
     pub fn load_source_from_path(&self, path: &str) -> Result<(), HostError> {
+        let source = self.read_source(path)?;
+        self.load_source(&source)
+    }
+
+    /// Compile `path` (and everything it imports) against the installed
+    /// script `AssetManager` without loading it. Hot reload runs this
+    /// before [`reload`](Self::reload) so a typo leaves the running
+    /// interpreter untouched.
+    pub fn check_source_from_path(&self, path: &str) -> Result<(), HostError> {
+        let source = self.read_source(path)?;
+        self.check_source(&source)
+    }
+
+    /// Compile `source` against the current bindings without loading
+    /// it. The script console uses this to pick the wrapper shape a
+    /// snippet type-checks under before committing a module for it.
+    pub fn check_source(&self, source: &str) -> Result<(), HostError> {
+        p7::compile_with_provider(source.to_string(), self.module_provider())
+            .map(|_| ())
+            .map_err(|err| HostError::message(format!("p7 compile failed: {:?}", err)))
+    }
+
+    /// Source text `import <module_path>;` would resolve to, through
+    /// the same provider chain [`load_source`](Self::load_source) uses.
+    pub fn module_source(&self, module_path: &str) -> Option<String> {
+        self.module_provider().load_module(module_path)
+    }
+
+    fn read_source(&self, path: &str) -> Result<String, HostError> {
         let assets = self
             .with_inner(|inner| inner.script_assets.clone())
             .ok_or_else(|| {
@@ -258,21 +287,23 @@ impl ScriptHost {
         let bytes = assets
             .read_to_end(std::path::Path::new(path))
             .map_err(|err| HostError::message(format!("read {path}: {err}")))?;
-        let source = String::from_utf8(bytes)
-            .map_err(|err| HostError::message(format!("invalid utf-8 in {path}: {err}")))?;
-        self.load_source(&source)
+        String::from_utf8(bytes)
+            .map_err(|err| HostError::message(format!("invalid utf-8 in {path}: {err}")))
     }
 
     pub fn load_source(&self, source: &str) -> Result<(), HostError> {
-        let (extra, script_assets) =
-            self.with_inner(|inner| (inner.extra_bindings.clone(), inner.script_assets.clone()));
-        let provider = binding_provider(&extra, script_assets);
-        let module = p7::compile_with_provider(source.to_string(), provider)
+        let module = p7::compile_with_provider(source.to_string(), self.module_provider())
             .map_err(|err| HostError::message(format!("p7 compile failed: {:?}", err)))?;
         self.with_inner(|inner| inner.host.ctx.load_module(module));
         Ok(())
     }
 
+    fn module_provider(&self) -> Box<dyn ModuleProvider> {
+        let (extra, script_assets) =
+            self.with_inner(|inner| (inner.extra_bindings.clone(), inner.script_assets.clone()));
+        binding_provider(&extra, script_assets)
+    }
+
     /// Discards every loaded module, every rooted handle, and every interned
     /// ComObject, then re-initialises a fresh interpreter. Any
     /// `ScriptDirectorHandle` outstanding from before the call is silently
@@ -619,6 +650,13 @@ impl RuntimeAccess for ScriptHost {
             });
         });
     }
+
+    /// The reload epoch: CCWs wrapped before a [`reload`](Self::reload)
+    /// see a different value and stop dispatching into the new
+    /// interpreter.
+    fn generation(&self) -> u64 {
+        self.with_inner(|inner| inner.epoch)
+    }
 }
 
 // Push/pop helpers that target the *current* top stack frame rather than the
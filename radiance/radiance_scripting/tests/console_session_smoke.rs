@@ -0,0 +1,41 @@
+use radiance_scripting::ScriptHost;
+use radiance_scripting::console::{ConsoleLine, ConsoleSession};
+
+#[test]
+fn expressions_print_through_the_first_return_type_that_checks() {
+    let mut session = ConsoleSession::new(ScriptHost::new(), None);
+    assert_eq!(session.eval("1 + 2"), Ok(Some("3".to_string())));
+    assert_eq!(session.eval("0.5 * 3.0"), Ok(Some("1.5".to_string())));
+    assert_eq!(session.eval("\"pal\""), Ok(Some("\"pal\"".to_string())));
+    assert_eq!(
+        session.eval(":as box<int> box(5)"),
+        Ok(Some("box(5)".to_string()))
+    );
+}
+
+#[test]
+fn statements_run_without_a_value_and_errors_are_reported() {
+    let mut session = ConsoleSession::new(ScriptHost::new(), None);
+    assert_eq!(session.eval("let x = 1;"), Ok(None));
+    assert!(session.eval("undefined_name + 1").is_err());
+    assert!(session.eval(":nope").is_err());
+    assert!(session.eval("import no_such_module;").is_err());
+}
+
+#[test]
+fn submit_logs_results_and_keeps_history() {
+    let mut session = ConsoleSession::new(ScriptHost::new(), None);
+    session.submit("40 + 2");
+    session.submit("40 + 2");
+    session.submit("1 +");
+    assert_eq!(session.history(), ["40 + 2", "1 +"]);
+    assert_eq!(session.log()[0], ConsoleLine::Input("40 + 2".to_string()));
+    assert_eq!(session.log()[1], ConsoleLine::Output("42".to_string()));
+    assert!(matches!(session.log().last(), Some(ConsoleLine::Error(_))));
+
+    assert_eq!(session.browse_history(true).as_deref(), Some("1 +"));
+    assert_eq!(session.browse_history(true).as_deref(), Some("40 + 2"));
+    assert_eq!(session.browse_history(true).as_deref(), Some("40 + 2"));
+    assert_eq!(session.browse_history(false).as_deref(), Some("1 +"));
+    assert_eq!(session.browse_history(false).as_deref(), Some(""));
+}
@@ -0,0 +1,178 @@
+//! Hot reload of a non-root director: the director a reload finds
+//! active names its factory through `reload_factory`, is rebuilt on the
+//! reloaded interpreter instead of falling back to the root director,
+//! and gets the state its `serialize` captured handed to `restore`.
+
+use std::rc::Rc;
+use std::sync::Mutex;
+
+use crosscom::ComRc;
+use crosscom_protosept::proto_ccw_data;
+use p7::errors::RuntimeError;
+use p7::interpreter::context::{Context, Data};
+use radiance::comdef::IDirector;
+use radiance_scripting::hot_reload::CarriedDirector;
+use radiance_scripting::mods::ModHostArg;
+use radiance_scripting::{ScriptHost, wrap_director};
+
+const MENUS: &str = r#"
+import radiance;
+
+@intrinsic(name="hr_test.record_event")
+fn record_event(seed: int, event: int);
+
+struct[radiance.IDirector] RootDir(
+    seed: int,
+) {
+    pub fn activate(self: refmut<Self>) -> int {
+        record_event(self.seed, 1);
+        0
+    }
+    pub fn update(self: refmut<Self>, dt: float) -> ?box<radiance.IDirector> {
+        return null;
+    }
+    pub fn deactivate(self: refmut<Self>) -> int {
+        0
+    }
+}
+
+struct[radiance.IDirector] PageDir(
+    seed: int,
+    page: string,
+) {
+    pub fn activate(self: refmut<Self>) -> int {
+        record_event(self.seed, 2);
+        0
+    }
+    pub fn update(self: refmut<Self>, dt: float) -> ?box<radiance.IDirector> {
+        return null;
+    }
+    pub fn deactivate(self: refmut<Self>) -> int {
+        0
+    }
+
+    pub fn go(self: refmut<Self>, page: string) {
+        self.page = page;
+    }
+
+    pub fn reload_factory(self: ref<Self>) -> string {
+        "reload_test.make_page"
+    }
+    pub fn serialize(self: ref<Self>) -> string {
+        self.page
+    }
+    pub fn restore(self: refmut<Self>, state: string) {
+        record_event(self.seed, 3);
+        self.page = state;
+    }
+}
+
+pub fn make_root(seed: int) -> box<radiance.IDirector> {
+    box(RootDir(seed)) as box<radiance.IDirector>
+}
+
+pub fn make_page(seed: int) -> box<radiance.IDirector> {
+    box(PageDir(seed, "first")) as box<radiance.IDirector>
+}
+"#;
+
+const APP: &str = r#"
+import reload_test;
+"#;
+
+static EVENTS: Mutex<Vec<(i64, i64)>> = Mutex::new(Vec::new());
+
+fn record_event_host_fn(ctx: &mut Context) -> Result<(), RuntimeError> {
+    let frame = ctx.stack_frame_mut()?;
+    let event = frame.stack.pop().expect("event arg");
+    let seed = frame.stack.pop().expect("seed arg");
+    match (seed, event) {
+        (Data::Int(s), Data::Int(e)) => {
+            EVENTS.lock().unwrap().push((s, e));
+            Ok(())
+        }
+        other => panic!("expected (int, int) record_event args, got {:?}", other),
+    }
+}
+
+/// Loads the package the way a (re)bootstrap does and returns its root
+/// director.
+fn bootstrap(host: &ScriptHost) -> ComRc<IDirector> {
+    host.with_ctx_mut(|ctx| {
+        ctx.register_host_function("hr_test.record_event".to_string(), record_event_host_fn);
+    });
+    host.load_source(APP).expect("load_source");
+    let root = host
+        .call_module_returning_data("reload_test", "make_root", vec![Data::Int(1)])
+        .expect("make_root");
+    wrap_director(&host.runtime_handle(), root).expect("wrap root")
+}
+
+fn page_of(host: &ScriptHost, director: &ComRc<IDirector>) -> Data {
+    let data = proto_ccw_data(director).expect("script director");
+    host.call_method_returning_data(data, "serialize", vec![])
+        .expect("serialize")
+}
+
+#[test]
+fn reload_keeps_the_active_non_root_director() {
+    EVENTS.lock().unwrap().clear();
+
+    let host = ScriptHost::new();
+    host.add_binding("reload_test", MENUS);
+    let root = bootstrap(&host);
+
+    // The player has navigated away from the root.
+    let page = host
+        .call_module_returning_data("reload_test", "make_page", vec![Data::Int(7)])
+        .expect("make_page");
+    let active = wrap_director(&host.runtime_handle(), page).expect("wrap page");
+    active.activate();
+    host.call_method_void(
+        proto_ccw_data(&active).unwrap(),
+        "go",
+        vec![Data::String(Rc::from("second"))],
+    )
+    .expect("go");
+
+    let carried = CarriedDirector::capture(&host, proto_ccw_data(&active).unwrap());
+    assert_eq!(carried.factory(), Some("reload_test.make_page"));
+
+    drop(active);
+    drop(root);
+    host.reload();
+    let root = bootstrap(&host);
+
+    let host_arg: ModHostArg = Rc::new(|_: &ScriptHost| Ok(Data::Int(7)));
+    let director = carried.rebuild(&host, &host_arg, root);
+    director.activate();
+
+    assert_eq!(
+        page_of(&host, &director),
+        Data::String(Rc::from("second")),
+        "the rebuilt director is the page, with its state restored"
+    );
+    assert_eq!(
+        *EVENTS.lock().unwrap(),
+        vec![(7, 2), (7, 3), (7, 2)],
+        "restore runs on the rebuilt page before its activate; the root never activates"
+    );
+}
+
+#[test]
+fn reload_without_hooks_falls_back_to_the_root_director() {
+    let host = ScriptHost::new();
+    host.add_binding("reload_test", MENUS);
+    let root = bootstrap(&host);
+
+    let carried = CarriedDirector::capture(&host, proto_ccw_data(&root).unwrap());
+    assert_eq!(carried.factory(), None);
+
+    drop(root);
+    host.reload();
+    let root = bootstrap(&host);
+    let host_arg: ModHostArg = Rc::new(|_: &ScriptHost| Ok(Data::Int(1)));
+    let director = carried.rebuild(&host, &host_arg, root.clone());
+
+    assert_eq!(director.ptr_value(), root.ptr_value());
+}
@@ -0,0 +1,104 @@
+//! User mod packages end to end: pack a ypk with `script-package`,
+//! discover it through its `mod.toml`, mount it on a script
+//! `AssetManager` and import its entry module.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use p7::interpreter::context::Data;
+use radiance::asset::AssetManager;
+use radiance_scripting::ScriptHost;
+use radiance_scripting::mods::{
+    HOST_CONTEXT_INTERFACES, ModEnvironment, discover_mods, mount_mods,
+};
+use script_package::{ExtraFile, PackInput, pack};
+
+fn unique_tmp(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "radiance_script_mods_{}_{}",
+        std::process::id(),
+        name
+    ))
+}
+
+fn pack_mod(mods_dir: &Path, file_name: &str, manifest: &str, main: &str) {
+    let src = unique_tmp(&format!("src_{file_name}"));
+    let _ = fs::remove_dir_all(&src);
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("main.p7"), main).unwrap();
+    let manifest_path = src.join("manifest.toml");
+    fs::write(&manifest_path, manifest).unwrap();
+
+    pack(
+        &PackInput {
+            scripts_dir: Some(&src),
+            extra_files: &[ExtraFile {
+                source_path: &manifest_path,
+                virtual_entry: "mod.toml",
+            }],
+        },
+        &mods_dir.join(file_name),
+    )
+    .unwrap();
+}
+
+fn env(game: Option<&'static str>) -> ModEnvironment<'static> {
+    ModEnvironment {
+        game,
+        known_games: &["pal3", "pal4"],
+        host_interfaces: HOST_CONTEXT_INTERFACES,
+        director_slots: &["title"],
+    }
+}
+
+#[test]
+fn discovered_mods_mount_under_their_name_and_import() {
+    let mods_dir = unique_tmp("mods");
+    let _ = fs::remove_dir_all(&mods_dir);
+    fs::create_dir_all(&mods_dir).unwrap();
+
+    pack_mod(
+        &mods_dir,
+        "a_answer.ypk",
+        "name = \"answer\"\nversion = \"0.1.0\"\ngames = [\"pal4\"]\n",
+        "pub fn answer() -> int { 42 }",
+    );
+    pack_mod(
+        &mods_dir,
+        "b_greedy.ypk",
+        "name = \"greedy\"\nversion = \"0.1.0\"\nrequires = [\"IPal9Service\"]\n",
+        "pub fn answer() -> int { 0 }",
+    );
+    fs::write(mods_dir.join("c_broken.ypk"), b"not a ypk").unwrap();
+
+    let discovery = discover_mods(&mods_dir, &env(None));
+    let names: Vec<&str> = discovery
+        .packages
+        .iter()
+        .map(|p| p.manifest.name.as_str())
+        .collect();
+    assert_eq!(names, ["answer"]);
+    assert_eq!(discovery.rejected.len(), 2, "{:?}", discovery.rejected);
+    assert!(discovery.rejected[0].contains("IPal9Service"));
+
+    // Direct boot into a game the mod doesn't list skips it.
+    assert!(
+        discover_mods(&mods_dir, &env(Some("pal3")))
+            .packages
+            .is_empty()
+    );
+
+    let assets = AssetManager::new();
+    let mounted = mount_mods(&assets, discovery.packages);
+    assert_eq!(mounted.len(), 1);
+
+    let host = ScriptHost::new();
+    host.set_script_assets(assets);
+    let module = mounted[0].manifest.entry_module();
+    host.load_source(&format!("import {};\n", module))
+        .expect("mod entry module compiles");
+    let value = host
+        .call_module_returning_data(&module, "answer", Vec::new())
+        .expect("mod function callable");
+    assert!(matches!(value, Data::Int(42)), "{:?}", value);
+}
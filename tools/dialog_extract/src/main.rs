@@ -0,0 +1,208 @@
+//! Dialog string extractor for localization catalogs.
+//!
+//! Mounts a game install through the same vfs the engine uses and
+//! collects every dialog string into a catalog (gettext `.po` or JSON,
+//! picked by the output extension):
+//!
+//! - PAL3 / PAL3A: `Dlg`/`DlgSel`/`DlgTime`/`Caption` operands of every
+//!   scene `.sce` plus `init.sce`.
+//! - PAL4: non-ASCII entries of every `.csb` string table.
+//! - PAL5 / PAL5Q: string literals passed to `ui.Dialog`/`ui.Message`
+//!   in every script listed in `scriptlist.ini`.
+//! - SWD5 family: string arguments of `storymsg`/`storymsgpos`/`talkmsg`
+//!   in the main Lua chunk.
+//!
+//! Re-running against an existing output keeps its translations for
+//! every source string that still exists, so translators can refresh a
+//! catalog after an engine or script change.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+use common::store_ext::StoreExt2;
+use mini_fs::MiniFs;
+use packfs::init_virtual_fs;
+use shared::{
+    GameType,
+    localization::{
+        Catalog,
+        extract::{
+            PAL5_DIALOG_FUNCTIONS, SWD5_DIALOG_FUNCTIONS, extract_csb, extract_lua, extract_sce,
+        },
+    },
+    openpal3::loaders::sce_loader::sce_load_from_file,
+    openpal5::script::ScriptIndex,
+    openswd5::asset_loader::AssetLoader,
+    scripting::angelscript::ScriptModule,
+};
+
+#[derive(Parser)]
+#[command(about = "Extract dialog text into a localization catalog")]
+struct Cli {
+    /// Game config key: pal3, pal3a, pal4, pal5, pal5q, swd5, swdhc, swdcf.
+    #[arg(long)]
+    game: String,
+
+    /// Game install root, as configured in `yaobow.toml`.
+    #[arg(long)]
+    root: PathBuf,
+
+    /// Output catalog. `.po` writes gettext, anything else JSON. An
+    /// existing file's translations are carried over.
+    #[arg(long)]
+    out: PathBuf,
+
+    /// Target language tag recorded in the catalog header (e.g. `en`).
+    #[arg(long, default_value = "")]
+    language: String,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let game = GameType::from_config_key(&cli.game)
+        .with_context(|| format!("unknown game key '{}'", cli.game))?;
+    let root = cli
+        .root
+        .canonicalize()
+        .with_context(|| format!("resolve --root {}", cli.root.display()))?;
+    eprintln!("Mounting {} vfs from {}", game.app_name(), root.display());
+    let vfs = init_virtual_fs(&root, game.pkg_key());
+
+    let mut catalog = Catalog::new(game.config_key(), &cli.language);
+    match game {
+        GameType::PAL3 | GameType::PAL3A => extract_pal3(&mut catalog, &vfs, game)?,
+        GameType::PAL4 => extract_pal4(&mut catalog, &vfs)?,
+        GameType::PAL5 | GameType::PAL5Q => extract_pal5(&mut catalog, &vfs)?,
+        GameType::SWD5 | GameType::SWDHC | GameType::SWDCF => {
+            let src = AssetLoader::read_main_script(&vfs, game)?;
+            extract_lua(
+                &mut catalog,
+                &src,
+                "main",
+                SWD5_DIALOG_FUNCTIONS,
+                encoding::all::BIG5_2003,
+            );
+        }
+        _ => bail!("dialog extraction is not supported for {}", game.app_name()),
+    }
+
+    if cli.out.exists() {
+        let previous = Catalog::load(&cli.out)
+            .with_context(|| format!("load existing {}", cli.out.display()))?;
+        catalog.merge_translations(&previous);
+    }
+
+    catalog.save(&cli.out)?;
+    eprintln!(
+        "Wrote {} strings ({} translated) to {}",
+        catalog.len(),
+        catalog.translated_len(),
+        cli.out.display()
+    );
+    Ok(())
+}
+
+fn extract_pal3(catalog: &mut Catalog, vfs: &MiniFs, game: GameType) -> Result<()> {
+    let mut sce_paths = vec![PathBuf::from("/basedata/basedata/init.sce")];
+    match game {
+        GameType::PAL3A => {
+            for name in list_files(vfs, Path::new("/scene/sce/Sce"), "sce")? {
+                sce_paths.push(Path::new("/scene/sce/Sce").join(name));
+            }
+        }
+        _ => {
+            for cpk in list_dirs(vfs, Path::new("/scene"))? {
+                sce_paths.push(Path::new("/scene").join(&cpk).join(format!("{}.sce", cpk)));
+            }
+        }
+    }
+
+    for path in sce_paths {
+        if !vfs.exists(&path) {
+            continue;
+        }
+
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let sce = sce_load_from_file(vfs, &path);
+        extract_sce(catalog, &sce, &name);
+    }
+
+    Ok(())
+}
+
+fn extract_pal4(catalog: &mut Catalog, vfs: &MiniFs) -> Result<()> {
+    let script_dir = Path::new("/gamedata/script");
+    for name in list_files(vfs, script_dir, "csb")? {
+        let bytes = vfs.read_to_end(script_dir.join(&name))?;
+        match ScriptModule::read_from_buffer(&bytes) {
+            Ok(module) => {
+                let stem = Path::new(&name).file_stem().unwrap().to_string_lossy();
+                extract_csb(catalog, &module, &stem)
+            }
+            Err(e) => eprintln!("skipping {}: {:#}", name, e),
+        }
+    }
+
+    Ok(())
+}
+
+fn extract_pal5(catalog: &mut Catalog, vfs: &MiniFs) -> Result<()> {
+    let index = ScriptIndex::load(vfs)?;
+    let mut ids: Vec<u32> = index.entries().map(|(id, _)| id).collect();
+    ids.sort();
+
+    for id in ids {
+        match index.load_source(vfs, id) {
+            Ok((name, src)) => extract_lua(
+                catalog,
+                &src,
+                &name,
+                PAL5_DIALOG_FUNCTIONS,
+                encoding::all::GBK,
+            ),
+            Err(e) => eprintln!("skipping script {}: {:#}", id, e),
+        }
+    }
+
+    Ok(())
+}
+
+fn list_entries(vfs: &MiniFs, dir: &Path, want_dirs: bool) -> Result<Vec<String>> {
+    let entries = <MiniFs as mini_fs::Store>::entries_path(vfs, dir)
+        .with_context(|| format!("list {}", dir.display()))?;
+
+    let mut names = vec![];
+    for entry in entries {
+        let entry = entry?;
+        if matches!(entry.kind, mini_fs::EntryKind::Dir) != want_dirs {
+            continue;
+        }
+
+        // CpkFs returns basenames, LocalFs relative paths; normalise.
+        let name = Path::new(&entry.name)
+            .file_name()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| entry.name.to_string_lossy().into_owned());
+        names.push(name);
+    }
+
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+fn list_dirs(vfs: &MiniFs, dir: &Path) -> Result<Vec<String>> {
+    list_entries(vfs, dir, true)
+}
+
+fn list_files(vfs: &MiniFs, dir: &Path, ext: &str) -> Result<Vec<String>> {
+    Ok(list_entries(vfs, dir, false)?
+        .into_iter()
+        .filter(|name| {
+            Path::new(name)
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case(ext))
+        })
+        .collect())
+}
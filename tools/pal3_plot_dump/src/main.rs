@@ -0,0 +1,1165 @@
+//! PAL3 plot catalog generator.
+//!
+//! Walks every scene's SCE bytecode (`sce_loader` + the static SCE
+//! disassembler) plus each sub-scene's `.scn` roles/nodes and `.nav`
+//! layer triggers, and emits a JSON map in the same shape
+//! `tools/pal4_plot_dump` produces:
+//!
+//! ```text
+//! scenes.<scene>.blocks.<block> = {
+//!     entry_fn, triggers, npcs, objects
+//! }
+//! ```
+//!
+//! so `tools/pal4_planner`'s catalog loader reads PAL3 dumps unchanged.
+//! The PAL3-specific mapping is:
+//!
+//! * `scene` is the scene cpk (`q01`), `block` the sub-scene (`q01y`) —
+//!   the same pair `/v1/state` reports as `scene` / `block` for PAL3.
+//!   Both are lower-cased.
+//! * `fns` are SCE procs keyed by name; `id` carries the proc id that
+//!   `.scn` bindings and `Call` refer to.
+//! * A global is an SCE variable with a negative index. Its slot is the
+//!   variable's bit pattern as `u16`, matching the `slot` of the
+//!   `global_read` / `global_write` trace events.
+//! * `transitions` come from `LoadScene(scene, block)`.
+//! * `cmp_literals` is keyed by the offset of each `TestGoto`, which is
+//!   the `pc` of that command's `call_sys` trace event.
+//!
+//! Like the PAL4 walker this is a purely static pass: it records what
+//! each proc *may* do, not what a particular playthrough reaches.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fs::File,
+    io::BufWriter,
+    panic::{AssertUnwindSafe, catch_unwind},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+use common::store_ext::StoreExt2;
+use mini_fs::MiniFs;
+use packfs::init_virtual_fs;
+use serde::Serialize;
+use shared::{
+    GameType,
+    openpal3::loaders::{
+        nav_loader::{NavFile, NavMap, nav_load_from_file},
+        sce_loader::{SceFile, sce_load_from_file},
+        scn_loader::{ScnFile, ScnNode, scn_load_from_file},
+    },
+    scripting::sce::disassembler::{SceInstInstance, SceOperand, disasm_partial, opcode_layout},
+};
+
+#[derive(Parser)]
+#[command(about = "Generate a PAL3 plot catalog (JSON)")]
+struct Cli {
+    /// Game config key: pal3 or pal3a.
+    #[arg(long, default_value = "pal3")]
+    game: String,
+
+    /// PAL3 install root, as configured in `yaobow.toml`.
+    #[arg(long)]
+    root: PathBuf,
+
+    /// Output JSON path.
+    #[arg(long, default_value = "pal3_plot.json")]
+    out: PathBuf,
+
+    /// Pretty-print the catalog instead of the compact one-line form.
+    #[arg(long)]
+    pretty: bool,
+
+    /// Debug: print the disassembly of every proc whose name contains
+    /// this string, then exit.
+    #[arg(long)]
+    debug_proc: Option<String>,
+}
+
+// ---- output schema ------------------------------------------------------
+
+/// Schema version recorded in the catalog's top-level `version` field.
+/// Tracks the PAL3 dump independently of `pal4_plot_dump`; bump on
+/// every shape-breaking change. See `docs/pal3_plot_catalog.md`.
+const CATALOG_VERSION: u32 = 1;
+
+#[derive(Serialize, Default)]
+struct Catalog {
+    version: u32,
+    /// `"pal3"` or `"pal3a"`.
+    game: String,
+    /// Number of SCE opcodes the disassembler knows.
+    sysfn_count: usize,
+    /// Distinct global slots referenced by any proc.
+    global_count: usize,
+    scenes: BTreeMap<String, Scene>,
+    /// Reverse index "to advance global[slot] to value V, fire one of
+    /// these". Same shape as the PAL4 catalog's `plot_index`.
+    plot_index: BTreeMap<String, Vec<PlotIndexEntry>>,
+}
+
+#[derive(Serialize, Default)]
+struct Scene {
+    blocks: BTreeMap<String, Block>,
+    /// Every proc in the scene's `.sce`. Kept at scene level because
+    /// one `.sce` serves every sub-scene of the cpk.
+    fns: BTreeMap<String, FunctionSummary>,
+    /// All `LoadScene` transitions recovered from the scene's procs.
+    transitions: Vec<Transition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+#[derive(Serialize, Default)]
+struct Block {
+    /// `_<scene>_<block>`, the proc `LoadScene` runs on arrival, when
+    /// the `.sce` defines it.
+    entry_fn: Option<String>,
+    triggers: Vec<Trigger>,
+    npcs: Vec<Npc>,
+    /// PAL3 has no GOB-style examine objects; always empty. Kept so the
+    /// planner's loader sees the PAL4 shape.
+    objects: [(); 0],
+    /// `true` when the block is only known from a `LoadScene` and its
+    /// `.scn` couldn't be read.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    synthesized: bool,
+}
+
+#[derive(Serialize)]
+struct Trigger {
+    /// `node_<index>` for `.scn` nodes, `layer_<map>_<index>` for
+    /// `.nav` layer triggers.
+    name: String,
+    /// Bound proc name; empty for layer triggers.
+    function: String,
+    center: [f32; 3],
+    half_size: [f32; 3],
+    /// `"plane"` for nav-grid rectangles (y is 0), `"box"` for AABB
+    /// triggers, `"point"` for item / ladder triggers.
+    shape: &'static str,
+    /// `"trigger"` for proc-bound entries, `"layer"` for nav layer
+    /// switches, which run no script.
+    kind: &'static str,
+    /// Which scene test fires it: `"nav"`, `"aabb"`, `"item"`,
+    /// `"ladder"` or `"layer"`.
+    source: &'static str,
+    #[serde(flatten)]
+    summary: TriggerSummary,
+}
+
+#[derive(Serialize)]
+struct Npc {
+    /// Entity name in the live scene (`ROLE_<index>`).
+    name: String,
+    /// Model name from the `.scn`.
+    model: String,
+    position: [f32; 3],
+    /// Talk proc; empty when the role has none.
+    function: String,
+    #[serde(flatten)]
+    summary: TriggerSummary,
+}
+
+#[derive(Serialize, Clone)]
+struct Transition {
+    to: [String; 2],
+    via_fn: String,
+}
+
+#[derive(Serialize, Default)]
+struct FunctionSummary {
+    /// Proc id.
+    id: u32,
+    /// Global slots read by compares.
+    reads: Vec<u32>,
+    /// Global writes. `value` is set for `Let`, `None` for computed
+    /// writes (`Rnd`, `GetAppr`).
+    writes: Vec<ValueWrite>,
+    /// Distinct command names, in encounter order. Named `sysfns` so
+    /// the planner's `fn_calls_sysfn` lookup works on PAL3 dumps.
+    sysfns: Vec<String>,
+    /// Procs called via `Call`.
+    calls: Vec<String>,
+    /// Targets of `Goto` / `TestGoto`, as offsets within the proc.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    jumps: Vec<u32>,
+    /// `TestGoto` offset → the literal its condition compared a global
+    /// against, or `None` when the condition was a combination or a
+    /// range.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    cmp_literals: BTreeMap<u32, Option<i32>>,
+    /// Set when the disassembler stopped early; the summary covers the
+    /// instructions before the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl FunctionSummary {
+    fn read(&mut self, var: i16) {
+        let Some(slot) = global_slot(var) else {
+            return;
+        };
+        if !self.reads.contains(&slot) {
+            self.reads.push(slot);
+        }
+    }
+
+    fn write(&mut self, var: i16, value: Option<i32>) {
+        let Some(global) = global_slot(var) else {
+            return;
+        };
+        let entry = ValueWrite { global, value };
+        if !self.writes.contains(&entry) {
+            self.writes.push(entry);
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+struct ValueWrite {
+    global: u32,
+    value: Option<i32>,
+}
+
+/// Call-graph closure from a trigger's proc. See the PAL4 dumper's
+/// type of the same name.
+#[derive(Serialize, Default)]
+struct TriggerSummary {
+    called_fns: Vec<String>,
+    reads: Vec<u32>,
+    writes: Vec<ValueWrite>,
+    transitions: Vec<[String; 2]>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+struct PlotIndexEntry {
+    value: Option<i32>,
+    scene: String,
+    block: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trigger: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    npc: Option<String>,
+    r#fn: String,
+}
+
+/// Cap on procs visited per closure. SCE procs chain through `Call`
+/// only a few levels deep.
+const CALL_CLOSURE_DEPTH: usize = 16;
+
+/// `.scn` node types the scene tests bind procs to. Mirrors the private
+/// `ScnNodeTypes` table in `shared/src/openpal3/scene/scene.rs`.
+mod node_types {
+    pub const NAV: u16 = 0;
+    pub const NAV_ANY_LAYER: u16 = 14;
+    pub const LADDER: u16 = 15;
+    pub const ITEM_TRIGGER2: u16 = 11;
+    pub const ITEM_TRIGGER: u16 = 16;
+    pub const AABB_TRIGGER: u16 = 20;
+    pub const TRIGGER_SOURCE: u16 = 23;
+    pub const ITEM_TRIGGER3: u16 = 33;
+    pub const LADDER2: u16 = 40;
+}
+
+/// Radius `ScnScene::test_item_trigger` uses.
+const ITEM_TRIGGER_RADIUS: f32 = 100.;
+
+// ---- main ---------------------------------------------------------------
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let game = match GameType::from_config_key(&cli.game) {
+        Some(game @ (GameType::PAL3 | GameType::PAL3A)) => game,
+        _ => bail!("--game must be pal3 or pal3a, got '{}'", cli.game),
+    };
+    let root = cli
+        .root
+        .canonicalize()
+        .with_context(|| format!("resolve root: {}", cli.root.display()))?;
+    eprintln!("Mounting {} vfs from {}", game.app_name(), root.display());
+    let vfs = init_virtual_fs(&root, game.pkg_key());
+    let layout = Layout { game };
+
+    let scene_names = layout.scene_names(&vfs)?;
+    eprintln!("Found {} scenes", scene_names.len());
+
+    let mut catalog = Catalog {
+        version: CATALOG_VERSION,
+        game: cli.game.clone(),
+        sysfn_count: (0..=i16::MAX)
+            .filter(|op| opcode_layout(*op, 0).is_some())
+            .count(),
+        ..Catalog::default()
+    };
+
+    for cpk in &scene_names {
+        let sce_path = layout.sce_path(cpk);
+        let Some(sce) = load(&sce_path, || sce_load_from_file(&vfs, &sce_path)) else {
+            continue;
+        };
+
+        if let Some(needle) = cli.debug_proc.as_deref() {
+            debug_procs(cpk, &sce, needle);
+            continue;
+        }
+
+        let scene_name = cpk.to_ascii_lowercase();
+        let mut scene_out = Scene::default();
+        let proc_keys = proc_keys(&sce);
+        for header in &sce.proc_headers {
+            let Some(proc) = sce.procs.get(&header.id) else {
+                continue;
+            };
+            let (insts, error) = disasm_partial(&proc.inst);
+            let error = error.map(|e| {
+                eprintln!("    WARN: {}:{}: {:#}", cpk, proc.name, e);
+                format!("{:#}", e)
+            });
+            let key = &proc_keys[&header.id];
+            let mut summary = walk_proc(&insts, &proc_keys, key, &mut scene_out.transitions);
+            summary.id = header.id;
+            summary.error = error;
+            scene_out.fns.insert(key.clone(), summary);
+        }
+
+        // Sub-scenes on disk, plus any this scene's `LoadScene`s enter.
+        let mut blocks = layout.block_names(&vfs, cpk);
+        for tr in &scene_out.transitions {
+            if tr.to[0] == scene_name {
+                blocks.push(tr.to[1].clone());
+            }
+        }
+        blocks.sort();
+        blocks.dedup();
+
+        let transitions = scene_out.transitions.clone();
+        for block in blocks {
+            let built = build_block(&vfs, &layout, cpk, &block, &scene_out.fns, &transitions);
+            scene_out.blocks.insert(block, built);
+        }
+        if scene_out.blocks.is_empty() {
+            scene_out.note = Some("no .scn sub-scenes found".to_string());
+        }
+        eprintln!(
+            "  {}: {} procs, {} blocks",
+            scene_name,
+            scene_out.fns.len(),
+            scene_out.blocks.len()
+        );
+        catalog.scenes.insert(scene_name, scene_out);
+    }
+
+    if cli.debug_proc.is_some() {
+        return Ok(());
+    }
+
+    catalog.global_count = count_globals(&catalog);
+    synthesise_missing_blocks(&mut catalog);
+    build_plot_index(&mut catalog);
+    eprintln!(
+        "Catalog: {} scenes / {} blocks / {} globals / {} plot_index entries",
+        catalog.scenes.len(),
+        catalog
+            .scenes
+            .values()
+            .map(|s| s.blocks.len())
+            .sum::<usize>(),
+        catalog.global_count,
+        catalog.plot_index.values().map(|v| v.len()).sum::<usize>(),
+    );
+
+    let file = File::create(&cli.out).with_context(|| format!("create {}", cli.out.display()))?;
+    let mut writer = BufWriter::new(file);
+    if cli.pretty {
+        serde_json::to_writer_pretty(&mut writer, &catalog)?;
+    } else {
+        serde_json::to_writer(&mut writer, &catalog)?;
+    }
+    eprintln!("Wrote {}", cli.out.display());
+    Ok(())
+}
+
+fn debug_procs(cpk: &str, sce: &SceFile, needle: &str) {
+    for header in &sce.proc_headers {
+        let Some(proc) = sce.procs.get(&header.id) else {
+            continue;
+        };
+        if !proc.name.contains(needle) {
+            continue;
+        }
+        eprintln!("--- {} :: {} (id {}) ---", cpk, proc.name, proc.id);
+        let (insts, error) = disasm_partial(&proc.inst);
+        for inst in insts {
+            eprintln!("{:04x}: {} {:?}", inst.addr, inst.name, inst.operands);
+        }
+        if let Some(e) = error {
+            eprintln!("error: {:#}", e);
+        }
+    }
+}
+
+/// The scene's loaders panic on malformed files; skip those with a
+/// warning instead of aborting the whole dump.
+fn load<T>(path: &Path, f: impl FnOnce() -> T) -> Option<T> {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => Some(value),
+        Err(_) => {
+            eprintln!("    WARN: failed to load {}", path.display());
+            None
+        }
+    }
+}
+
+/// Catalog key for every proc: its name, or `name#id` when the `.sce`
+/// has several procs of that name.
+fn proc_keys(sce: &SceFile) -> HashMap<u32, String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for header in &sce.proc_headers {
+        *counts.entry(header.name.as_str()).or_default() += 1;
+    }
+    sce.proc_headers
+        .iter()
+        .map(|header| {
+            let key = if counts[header.name.as_str()] > 1 {
+                format!("{}#{}", header.name, header.id)
+            } else {
+                header.name.clone()
+            };
+            (header.id, key)
+        })
+        .collect()
+}
+
+// ---- file layout --------------------------------------------------------
+
+/// Where PAL3 and PAL3A keep their scene files. Mirrors the path logic
+/// in `openpal3::asset_manager::AssetManager`.
+struct Layout {
+    game: GameType,
+}
+
+impl Layout {
+    fn is_pal3a(&self) -> bool {
+        matches!(self.game, GameType::PAL3A)
+    }
+
+    fn scene_names(&self, vfs: &MiniFs) -> Result<Vec<String>> {
+        if self.is_pal3a() {
+            return Ok(list_files(vfs, Path::new("/scene/sce/Sce"), "sce")?
+                .into_iter()
+                .map(|name| file_stem(&name))
+                .collect());
+        }
+
+        Ok(list_dirs(vfs, Path::new("/scene"))?
+            .into_iter()
+            .filter(|cpk| vfs.exists(&self.sce_path(cpk)))
+            .collect())
+    }
+
+    fn sce_path(&self, cpk: &str) -> PathBuf {
+        if self.is_pal3a() {
+            Path::new("/scene/sce/Sce").join(format!("{}.sce", cpk))
+        } else {
+            Path::new("/scene").join(cpk).join(format!("{}.sce", cpk))
+        }
+    }
+
+    fn block_names(&self, vfs: &MiniFs, cpk: &str) -> Vec<String> {
+        let (dir, prefix) = if self.is_pal3a() {
+            (
+                Path::new("/scene/scn/Scn").join(cpk),
+                format!("{}_", cpk.to_ascii_lowercase()),
+            )
+        } else {
+            (Path::new("/scene").join(cpk), String::new())
+        };
+        list_files(vfs, &dir, "scn")
+            .unwrap_or_default()
+            .into_iter()
+            .map(|name| {
+                let stem = file_stem(&name).to_ascii_lowercase();
+                match stem.strip_prefix(&prefix) {
+                    Some(block) if !prefix.is_empty() => block.to_string(),
+                    _ => stem,
+                }
+            })
+            .collect()
+    }
+
+    fn scn_path(&self, cpk: &str, block: &str) -> PathBuf {
+        if self.is_pal3a() {
+            Path::new("/scene/scn/Scn")
+                .join(cpk)
+                .join(format!("{}_{}.scn", cpk, block))
+        } else {
+            Path::new("/scene").join(cpk).join(format!("{}.scn", block))
+        }
+    }
+
+    fn nav_path(&self, cpk: &str, scn_base: &str) -> PathBuf {
+        let dir = if self.is_pal3a() {
+            Path::new("/scene/scn/Scn").join(cpk)
+        } else {
+            Path::new("/scene").join(cpk)
+        };
+        dir.join(scn_base).join(format!("{}.nav", scn_base))
+    }
+}
+
+fn file_stem(name: &str) -> String {
+    Path::new(name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| name.to_string())
+}
+
+fn list_entries(vfs: &MiniFs, dir: &Path, want_dirs: bool) -> Result<Vec<String>> {
+    let entries = <MiniFs as mini_fs::Store>::entries_path(vfs, dir)
+        .with_context(|| format!("list {}", dir.display()))?;
+
+    let mut names = vec![];
+    for entry in entries {
+        let entry = entry?;
+        if matches!(entry.kind, mini_fs::EntryKind::Dir) != want_dirs {
+            continue;
+        }
+
+        // CpkFs returns basenames, LocalFs relative paths; normalise.
+        let name = Path::new(&entry.name)
+            .file_name()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| entry.name.to_string_lossy().into_owned());
+        names.push(name);
+    }
+
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+fn list_dirs(vfs: &MiniFs, dir: &Path) -> Result<Vec<String>> {
+    list_entries(vfs, dir, true)
+}
+
+fn list_files(vfs: &MiniFs, dir: &Path, ext: &str) -> Result<Vec<String>> {
+    Ok(list_entries(vfs, dir, false)?
+        .into_iter()
+        .filter(|name| {
+            Path::new(name)
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case(ext))
+        })
+        .collect())
+}
+
+// ---- per-block assembly -------------------------------------------------
+
+fn build_block(
+    vfs: &MiniFs,
+    layout: &Layout,
+    cpk: &str,
+    block: &str,
+    fns: &BTreeMap<String, FunctionSummary>,
+    scene_transitions: &[Transition],
+) -> Block {
+    let entry_name = format!("_{}_{}", cpk, block);
+    let entry_fn = fns
+        .keys()
+        .find(|name| name.eq_ignore_ascii_case(&entry_name))
+        .cloned();
+
+    let scn_path = layout.scn_path(cpk, block);
+    let scn = if vfs.exists(&scn_path) {
+        load(&scn_path, || {
+            scn_load_from_file(vfs, &scn_path, layout.game)
+        })
+    } else {
+        None
+    };
+    let Some(scn) = scn else {
+        return Block {
+            entry_fn,
+            synthesized: true,
+            ..Block::default()
+        };
+    };
+
+    let nav_path = layout.nav_path(cpk, &scn.scn_base_name);
+    let nav = if vfs.exists(&nav_path) {
+        load(&nav_path, || nav_load_from_file(vfs, &nav_path))
+    } else {
+        eprintln!("    nav missing for {}/{}", cpk, block);
+        None
+    };
+
+    let by_id: HashMap<u32, &str> = fns.iter().map(|(k, f)| (f.id, k.as_str())).collect();
+    let mut triggers = build_node_triggers(&scn, nav.as_ref(), &by_id, fns, scene_transitions);
+    if let Some(nav) = &nav {
+        triggers.extend(build_layer_triggers(nav));
+    }
+
+    Block {
+        entry_fn,
+        triggers,
+        npcs: build_npcs(&scn, &by_id, fns, scene_transitions),
+        objects: [],
+        synthesized: false,
+    }
+}
+
+fn build_node_triggers(
+    scn: &ScnFile,
+    nav: Option<&NavFile>,
+    by_id: &HashMap<u32, &str>,
+    fns: &BTreeMap<String, FunctionSummary>,
+    scene_transitions: &[Transition],
+) -> Vec<Trigger> {
+    use node_types::*;
+
+    let mut triggers = vec![];
+    for node in &scn.nodes {
+        if node.sce_proc_id == 0 {
+            continue;
+        }
+
+        let has_nav_rect =
+            node.nav_trigger_coord_min != (0, 0) || node.nav_trigger_coord_max != (0, 0);
+        let (source, shape, center, half_size) = match node.node_type {
+            NAV | NAV_ANY_LAYER if has_nav_rect => {
+                let map = nav.and_then(|nav| nav.maps.get(node.nav_layer as usize));
+                let (center, half_size) = match map {
+                    Some(map) => {
+                        nav_rect_bounds(map, node.nav_trigger_coord_min, node.nav_trigger_coord_max)
+                    }
+                    None => (node_position(node), [0.; 3]),
+                };
+                ("nav", "plane", center, half_size)
+            }
+            AABB_TRIGGER => {
+                let (a, b) = (&node.aabb_trigger_coord1, &node.aabb_trigger_coord2);
+                let center = [(a.x + b.x) * 0.5, (a.y + b.y) * 0.5, (a.z + b.z) * 0.5];
+                let half_size = [
+                    (a.x - b.x).abs() * 0.5,
+                    (a.y - b.y).abs() * 0.5,
+                    (a.z - b.z).abs() * 0.5,
+                ];
+                ("aabb", "box", center, half_size)
+            }
+            ITEM_TRIGGER | ITEM_TRIGGER2 | ITEM_TRIGGER3 | TRIGGER_SOURCE => (
+                "item",
+                "point",
+                node_position(node),
+                [ITEM_TRIGGER_RADIUS, 0., ITEM_TRIGGER_RADIUS],
+            ),
+            LADDER | LADDER2 => ("ladder", "point", node_position(node), [0.; 3]),
+            _ => continue,
+        };
+
+        let function = by_id
+            .get(&node.sce_proc_id)
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+        triggers.push(Trigger {
+            name: format!("node_{}", node.index),
+            summary: build_trigger_summary(&function, fns, scene_transitions),
+            function,
+            center,
+            half_size,
+            shape,
+            kind: "trigger",
+            source,
+        });
+    }
+
+    triggers
+}
+
+fn build_layer_triggers(nav: &NavFile) -> Vec<Trigger> {
+    let mut triggers = vec![];
+    for (layer, map) in nav.maps.iter().enumerate() {
+        let Some(layer_triggers) = &map.layer_triggers else {
+            continue;
+        };
+        for (i, trigger) in layer_triggers.iter().enumerate() {
+            if trigger.nav_coord_min == (0, 0) && trigger.nav_coord_max == (0, 0) {
+                continue;
+            }
+            let (center, half_size) =
+                nav_rect_bounds(map, trigger.nav_coord_min, trigger.nav_coord_max);
+            triggers.push(Trigger {
+                name: format!("layer_{}_{}", layer, i),
+                function: String::new(),
+                center,
+                half_size,
+                shape: "plane",
+                kind: "layer",
+                source: "layer",
+                summary: TriggerSummary::default(),
+            });
+        }
+    }
+
+    triggers
+}
+
+fn build_npcs(
+    scn: &ScnFile,
+    by_id: &HashMap<u32, &str>,
+    fns: &BTreeMap<String, FunctionSummary>,
+    scene_transitions: &[Transition],
+) -> Vec<Npc> {
+    scn.roles
+        .iter()
+        .map(|role| {
+            let function = by_id
+                .get(&role.sce_proc_id)
+                .filter(|_| role.sce_proc_id != 0)
+                .map(|name| name.to_string())
+                .unwrap_or_default();
+            Npc {
+                name: format!("ROLE_{}", role.index),
+                model: role.name.clone(),
+                position: [role.position_x, 0., role.position_z],
+                summary: build_trigger_summary(&function, fns, scene_transitions),
+                function,
+            }
+        })
+        .collect()
+}
+
+fn node_position(node: &ScnNode) -> [f32; 3] {
+    [node.position.x, node.position.y, node.position.z]
+}
+
+/// World-space center / half-size of an inclusive nav-grid rectangle.
+/// Uses the cell size `scene::Nav` derives; y is left at 0 since the
+/// scene tests only compare x/z.
+fn nav_rect_bounds(map: &NavMap, min: (i32, i32), max: (i32, i32)) -> ([f32; 3], [f32; 3]) {
+    let cell_x = (map.max_coord.x - map.min_coord.x) / (map.width + 1) as f32;
+    let cell_z = (map.max_coord.z - map.min_coord.z) / (map.height + 1) as f32;
+    let x0 = map.min_coord.x + min.0 as f32 * cell_x;
+    let x1 = map.min_coord.x + (max.0 + 1) as f32 * cell_x;
+    let z0 = map.min_coord.z + min.1 as f32 * cell_z;
+    let z1 = map.min_coord.z + (max.1 + 1) as f32 * cell_z;
+    (
+        [(x0 + x1) * 0.5, 0., (z0 + z1) * 0.5],
+        [(x1 - x0).abs() * 0.5, 0., (z1 - z0).abs() * 0.5],
+    )
+}
+
+// ---- proc walker --------------------------------------------------------
+
+/// Global slot for an SCE variable index; `None` for proc locals.
+fn global_slot(var: i16) -> Option<u32> {
+    (var < 0).then_some(var as u16 as u32)
+}
+
+fn operand_i16(inst: &SceInstInstance, i: usize) -> Option<i16> {
+    match inst.operands.get(i) {
+        Some(SceOperand::I16(v)) => Some(*v),
+        _ => None,
+    }
+}
+
+fn operand_i32(inst: &SceInstInstance, i: usize) -> Option<i32> {
+    match inst.operands.get(i) {
+        Some(SceOperand::I32(v)) => Some(*v),
+        _ => None,
+    }
+}
+
+fn operand_u32(inst: &SceInstInstance, i: usize) -> Option<u32> {
+    match inst.operands.get(i) {
+        Some(SceOperand::U32(v)) => Some(*v),
+        _ => None,
+    }
+}
+
+fn operand_str(inst: &SceInstInstance, i: usize) -> Option<&str> {
+    match inst.operands.get(i) {
+        Some(SceOperand::String(v)) => Some(v.as_str()),
+        _ => None,
+    }
+}
+
+fn walk_proc(
+    insts: &[SceInstInstance],
+    proc_keys: &HashMap<u32, String>,
+    fn_name: &str,
+    transitions: &mut Vec<Transition>,
+) -> FunctionSummary {
+    /// Condition feeding the next `TestGoto`. SCE compares push a bool
+    /// onto the FOP stack; `FOP` combines them, `TestGoto` pops one.
+    enum Gate {
+        Idle,
+        /// One compare against a global; the literal when it has one.
+        Single(Option<i32>),
+        /// Anything else (combined, local or var-vs-var).
+        Mixed,
+    }
+
+    let mut summary = FunctionSummary::default();
+    let mut gate = Gate::Idle;
+
+    for inst in insts {
+        if !summary.sysfns.iter().any(|name| name == inst.name) {
+            summary.sysfns.push(inst.name.to_string());
+        }
+
+        match inst.name {
+            "GT" | "LS" | "EQ" | "NEQ" | "LEQ" | "GEQ" => {
+                let var = operand_i16(inst, 0).unwrap_or(0);
+                summary.read(var);
+                // `GEQ` with `access_local_var == 3` compares two vars.
+                let rhs_var = operand_i16(inst, 1);
+                if let Some(rhs) = rhs_var {
+                    summary.read(rhs);
+                }
+                gate = match (&gate, global_slot(var), rhs_var) {
+                    (Gate::Idle, Some(_), None) => Gate::Single(operand_i32(inst, 1)),
+                    _ => Gate::Mixed,
+                };
+            }
+            "Between" => {
+                summary.read(operand_i16(inst, 0).unwrap_or(0));
+                gate = match gate {
+                    Gate::Idle => Gate::Single(None),
+                    _ => Gate::Mixed,
+                };
+            }
+            "FOP" => gate = Gate::Mixed,
+            "TestGoto" => {
+                match gate {
+                    Gate::Idle => {}
+                    Gate::Single(value) => {
+                        summary.cmp_literals.insert(inst.addr, value);
+                    }
+                    Gate::Mixed => {
+                        summary.cmp_literals.insert(inst.addr, None);
+                    }
+                }
+                gate = Gate::Idle;
+                if let Some(target) = operand_u32(inst, 0) {
+                    summary.jumps.push(target);
+                }
+            }
+            "Goto" => {
+                if let Some(target) = operand_u32(inst, 0) {
+                    summary.jumps.push(target);
+                }
+            }
+            "Let" => {
+                let var = operand_i16(inst, 0).unwrap_or(0);
+                summary.write(var, operand_i32(inst, 1));
+            }
+            "Rnd" | "GetAppr" => {
+                summary.write(operand_i16(inst, 0).unwrap_or(0), None);
+            }
+            "Call" => {
+                if let Some(id) = operand_u32(inst, 0) {
+                    let name = proc_keys
+                        .get(&id)
+                        .cloned()
+                        .unwrap_or_else(|| format!("?proc{}", id));
+                    if !summary.calls.contains(&name) {
+                        summary.calls.push(name);
+                    }
+                }
+            }
+            "LoadScene" => {
+                if let (Some(scene), Some(block)) = (operand_str(inst, 0), operand_str(inst, 1)) {
+                    transitions.push(Transition {
+                        to: [scene.to_ascii_lowercase(), block.to_ascii_lowercase()],
+                        via_fn: fn_name.to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    summary.jumps.sort();
+    summary.jumps.dedup();
+    summary
+}
+
+// ---- per-trigger closure ------------------------------------------------
+
+/// BFS the intra-scene `Call` graph from `entry_fn`, unioning reads,
+/// writes and `LoadScene` destinations. Same rules as the PAL4 dumper.
+fn build_trigger_summary(
+    entry_fn: &str,
+    fns: &BTreeMap<String, FunctionSummary>,
+    scene_transitions: &[Transition],
+) -> TriggerSummary {
+    let mut summary = TriggerSummary::default();
+    if entry_fn.is_empty() || !fns.contains_key(entry_fn) {
+        return summary;
+    }
+
+    let mut visited: Vec<String> = vec![entry_fn.to_string()];
+    let mut queue: VecDeque<String> = VecDeque::from([entry_fn.to_string()]);
+    while let Some(name) = queue.pop_front() {
+        if summary.called_fns.len() >= CALL_CLOSURE_DEPTH {
+            break;
+        }
+        summary.called_fns.push(name.clone());
+        let Some(fs) = fns.get(&name) else { continue };
+
+        for r in &fs.reads {
+            if !summary.reads.contains(r) {
+                summary.reads.push(*r);
+            }
+        }
+        for w in &fs.writes {
+            if !summary.writes.contains(w) {
+                summary.writes.push(w.clone());
+            }
+        }
+        for callee in &fs.calls {
+            if !visited.contains(callee) {
+                visited.push(callee.clone());
+                queue.push_back(callee.clone());
+            }
+        }
+        for t in scene_transitions {
+            if t.via_fn == name && !summary.transitions.contains(&t.to) {
+                summary.transitions.push(t.to.clone());
+            }
+        }
+    }
+
+    summary
+}
+
+// ---- catalog-wide passes ------------------------------------------------
+
+fn count_globals(catalog: &Catalog) -> usize {
+    let mut slots: Vec<u32> = catalog
+        .scenes
+        .values()
+        .flat_map(|scene| scene.fns.values())
+        .flat_map(|f| {
+            f.reads
+                .iter()
+                .copied()
+                .chain(f.writes.iter().map(|w| w.global))
+        })
+        .collect();
+    slots.sort();
+    slots.dedup();
+    slots.len()
+}
+
+/// Add stub blocks for `LoadScene` destinations in scenes the dump has
+/// no `.sce` for (or no matching sub-scene in).
+fn synthesise_missing_blocks(catalog: &mut Catalog) {
+    let needed: Vec<[String; 2]> = catalog
+        .scenes
+        .values()
+        .flat_map(|scene| scene.transitions.iter().map(|t| t.to.clone()))
+        .collect();
+    let mut added = 0usize;
+    for [scene, block] in needed {
+        let scene = catalog.scenes.entry(scene).or_default();
+        if scene.blocks.contains_key(&block) {
+            continue;
+        }
+        scene.blocks.insert(
+            block,
+            Block {
+                synthesized: true,
+                ..Block::default()
+            },
+        );
+        added += 1;
+    }
+    if added > 0 {
+        eprintln!("Synthesised {} stub block(s) from transitions", added);
+    }
+}
+
+/// Build `Catalog.plot_index` from the trigger and NPC closures. Rows
+/// are sorted and deduplicated so successive dumps diff cleanly.
+fn build_plot_index(catalog: &mut Catalog) {
+    let mut index: BTreeMap<String, Vec<PlotIndexEntry>> = BTreeMap::new();
+    for (scene_name, scene) in &catalog.scenes {
+        for (block_name, block) in &scene.blocks {
+            let sources = block
+                .triggers
+                .iter()
+                .map(|t| (Some(&t.name), None, &t.function, &t.summary))
+                .chain(
+                    block
+                        .npcs
+                        .iter()
+                        .map(|n| (None, Some(&n.name), &n.function, &n.summary)),
+                );
+            for (trigger, npc, function, summary) in sources {
+                if function.is_empty() {
+                    continue;
+                }
+                for w in &summary.writes {
+                    index
+                        .entry(w.global.to_string())
+                        .or_default()
+                        .push(PlotIndexEntry {
+                            value: w.value,
+                            scene: scene_name.clone(),
+                            block: block_name.clone(),
+                            trigger: trigger.cloned(),
+                            npc: npc.cloned(),
+                            r#fn: function.clone(),
+                        });
+                }
+            }
+        }
+    }
+
+    for entries in index.values_mut() {
+        let key = |e: &PlotIndexEntry| {
+            (
+                e.scene.clone(),
+                e.block.clone(),
+                e.trigger.clone().unwrap_or_default(),
+                e.npc.clone().unwrap_or_default(),
+                e.r#fn.clone(),
+                e.value,
+            )
+        };
+        entries.sort_by_key(key);
+        entries.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    catalog.plot_index = index;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inst(addr: u32, name: &'static str, operands: Vec<SceOperand>) -> SceInstInstance {
+        SceInstInstance {
+            addr,
+            opcode: 0,
+            access_local_var: 0,
+            name,
+            operands,
+        }
+    }
+
+    fn walk(insts: &[SceInstInstance]) -> (FunctionSummary, Vec<Transition>) {
+        let keys = HashMap::from([(7, "helper".to_string())]);
+        let mut transitions = vec![];
+        let summary = walk_proc(insts, &keys, "main", &mut transitions);
+        (summary, transitions)
+    }
+
+    #[test]
+    fn walker_records_globals_calls_and_load_scene() {
+        let (summary, transitions) = walk(&[
+            inst(0, "Let", vec![SceOperand::I16(-3), SceOperand::I32(5)]),
+            // Locals are not part of the plot vector.
+            inst(8, "Let", vec![SceOperand::I16(2), SceOperand::I32(1)]),
+            inst(16, "Rnd", vec![SceOperand::I16(-4), SceOperand::I32(10)]),
+            inst(24, "EQ", vec![SceOperand::I16(-3), SceOperand::I32(5)]),
+            inst(32, "Call", vec![SceOperand::U32(7)]),
+            inst(
+                40,
+                "LoadScene",
+                vec![
+                    SceOperand::String("Q01".to_string()),
+                    SceOperand::String("Q01Y".to_string()),
+                ],
+            ),
+        ]);
+
+        assert_eq!(summary.reads, vec![(-3i16) as u16 as u32]);
+        assert_eq!(
+            summary.writes,
+            vec![
+                ValueWrite {
+                    global: 0xfffd,
+                    value: Some(5)
+                },
+                ValueWrite {
+                    global: 0xfffc,
+                    value: None
+                },
+            ]
+        );
+        assert_eq!(summary.calls, vec!["helper"]);
+        assert_eq!(
+            summary.sysfns,
+            vec!["Let", "Rnd", "EQ", "Call", "LoadScene"]
+        );
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].to, ["q01".to_string(), "q01y".to_string()]);
+        assert_eq!(transitions[0].via_fn, "main");
+    }
+
+    #[test]
+    fn walker_keys_cmp_literals_by_test_goto_offset() {
+        let (summary, _) = walk(&[
+            // Single compare against a global.
+            inst(0, "GT", vec![SceOperand::I16(-1), SceOperand::I32(100)]),
+            inst(8, "TestGoto", vec![SceOperand::U32(64)]),
+            // Two compares combined by FOP: no single literal.
+            inst(16, "EQ", vec![SceOperand::I16(-1), SceOperand::I32(1)]),
+            inst(24, "EQ", vec![SceOperand::I16(-2), SceOperand::I32(2)]),
+            inst(32, "FOP", vec![SceOperand::I32(0)]),
+            inst(40, "TestGoto", vec![SceOperand::U32(80)]),
+            // A compare on a local isn't a gate literal either.
+            inst(48, "EQ", vec![SceOperand::I16(1), SceOperand::I32(3)]),
+            inst(56, "TestGoto", vec![SceOperand::U32(96)]),
+            inst(64, "Goto", vec![SceOperand::U32(0)]),
+        ]);
+
+        assert_eq!(
+            summary.cmp_literals,
+            BTreeMap::from([(8, Some(100)), (40, None), (56, None)])
+        );
+        assert_eq!(summary.jumps, vec![0, 64, 80, 96]);
+    }
+
+    #[test]
+    fn closure_follows_calls_and_transitions() {
+        let mut fns = BTreeMap::new();
+        fns.insert(
+            "a".to_string(),
+            FunctionSummary {
+                calls: vec!["b".to_string()],
+                ..FunctionSummary::default()
+            },
+        );
+        fns.insert(
+            "b".to_string(),
+            FunctionSummary {
+                calls: vec!["a".to_string()],
+                writes: vec![ValueWrite {
+                    global: 1,
+                    value: Some(2),
+                }],
+                ..FunctionSummary::default()
+            },
+        );
+        let transitions = vec![Transition {
+            to: ["q02".to_string(), "q02".to_string()],
+            via_fn: "b".to_string(),
+        }];
+
+        let s = build_trigger_summary("a", &fns, &transitions);
+        assert_eq!(s.called_fns, vec!["a", "b"]);
+        assert_eq!(s.writes.len(), 1);
+        assert_eq!(s.transitions, vec![["q02".to_string(), "q02".to_string()]]);
+        assert!(
+            build_trigger_summary("", &fns, &transitions)
+                .called_fns
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,357 @@
+//! Save-slot browser and maintenance tool for the yaobow games.
+//!
+//! Works directly on the slot files under the yaobow save dir (or
+//! `--dir`), using the same schema, migrations and validator the games
+//! load with:
+//!
+//! - `list`: every slot with its version, timestamp, playtime, scene and
+//!   validation status.
+//! - `validate`: unknown / missing fields and load errors of one slot.
+//! - `diff`: field-level differences between two slots (or files),
+//!   after migrating both to the current schema.
+//! - `repair`: migrate, drop unknown fields, fill missing and reset
+//!   malformed ones. The original is kept as `<slot>.json.bak`.
+//! - `import`: convert a retail save into a slot, guided by a layout
+//!   file, and report what could not be mapped.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand};
+use serde_json::Value;
+use shared::saves::{
+    self, SaveKind, SaveMetadata, SlotFiles,
+    diff::diff,
+    import::{ImportReport, RetailLayout, import_pal3, import_pal4},
+    validate::{ValidationReport, repair, validate},
+};
+
+#[derive(Parser)]
+#[command(about = "List, validate, diff, repair and import yaobow save slots")]
+struct Cli {
+    /// Save root holding `OpenPAL3/`, `OpenPAL4/`, `OpenPAL5/`,
+    /// `OpenPAL5Q/` and `OpenSWD5/` (`OpenSWDHC/`, `OpenSWDCF/`).
+    /// Defaults to the yaobow save dir.
+    #[arg(long, global = true)]
+    dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List slots of one game, or of every game when `--game` is omitted.
+    List {
+        #[arg(long)]
+        game: Option<String>,
+    },
+
+    /// Report unknown / missing fields and load errors of a slot.
+    Validate {
+        #[arg(long)]
+        game: String,
+        slot: String,
+    },
+
+    /// Show field differences between two slots. Either side may be a
+    /// slot number or a path to a save file.
+    Diff {
+        #[arg(long)]
+        game: String,
+        left: String,
+        right: String,
+    },
+
+    /// Rewrite a slot so the game can load it.
+    Repair {
+        #[arg(long)]
+        game: String,
+        slot: String,
+
+        /// Print the planned changes without writing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Convert a retail save into a slot. The game comes from the layout.
+    Import {
+        /// Retail save layout (TOML), see docs/saves.md.
+        #[arg(long)]
+        layout: PathBuf,
+
+        /// Destination slot number.
+        #[arg(long)]
+        slot: i32,
+
+        /// Overwrite an existing slot.
+        #[arg(long)]
+        force: bool,
+
+        /// Print the import report without writing anything.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Retail save file.
+        file: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let root = cli.dir.clone().unwrap_or_else(shared::ydirs::save_dir);
+
+    match cli.command {
+        Command::List { game } => {
+            let kinds = match game {
+                Some(game) => vec![parse_kind(&game)?],
+                None => SaveKind::ALL.to_vec(),
+            };
+            for kind in kinds {
+                list(&root, kind);
+            }
+        }
+        Command::Validate { game, slot } => {
+            let kind = parse_kind(&game)?;
+            let path = resolve(&root, kind, &slot);
+            let report = validate(kind, &read_raw(&path)?);
+            print_report(&path, &report);
+            if !report.is_loadable() {
+                std::process::exit(1);
+            }
+        }
+        Command::Diff { game, left, right } => {
+            let kind = parse_kind(&game)?;
+            let left_path = resolve(&root, kind, &left);
+            let right_path = resolve(&root, kind, &right);
+            let left = saves::read_state_value(kind, &left_path)
+                .with_context(|| format!("read {}", left_path.display()))?;
+            let right = saves::read_state_value(kind, &right_path)
+                .with_context(|| format!("read {}", right_path.display()))?;
+
+            let diffs = diff(&left, &right);
+            if diffs.is_empty() {
+                println!("no differences");
+            }
+            for d in diffs {
+                println!(
+                    "{}: {} -> {}",
+                    d.path,
+                    show(d.left.as_ref()),
+                    show(d.right.as_ref())
+                );
+            }
+        }
+        Command::Repair {
+            game,
+            slot,
+            dry_run,
+        } => {
+            let kind = parse_kind(&game)?;
+            let path = resolve(&root, kind, &slot);
+            let (repaired, actions) = repair(kind, &read_raw(&path)?)?;
+            if actions.is_empty() {
+                println!("{}: nothing to repair", path.display());
+                return Ok(());
+            }
+
+            for action in &actions {
+                println!("{}", action);
+            }
+
+            if !dry_run {
+                let backup = path.with_extension("json.bak");
+                std::fs::copy(&path, &backup)
+                    .with_context(|| format!("back up to {}", backup.display()))?;
+                saves::write_json_atomic(&path, &repaired)?;
+                println!(
+                    "repaired {} (original kept as {})",
+                    path.display(),
+                    backup.display()
+                );
+            }
+        }
+        Command::Import {
+            layout,
+            slot,
+            force,
+            dry_run,
+            file,
+        } => {
+            let layout = RetailLayout::load(&layout)
+                .with_context(|| format!("load layout {}", layout.display()))?;
+            let data = std::fs::read(&file).with_context(|| format!("read {}", file.display()))?;
+            let kind = layout.kind()?;
+            let dir = saves::slot_dir(&root, kind.app_name());
+            let target = SlotFiles::new(&dir, slot).state;
+            if target.exists() && !force && !dry_run {
+                bail!("{} exists, pass --force to overwrite", target.display());
+            }
+
+            let report = match kind {
+                SaveKind::Pal3 => {
+                    let (state, report) = import_pal3(&data, &layout)?;
+                    if !dry_run {
+                        let metadata = SaveMetadata::now(0., state.location());
+                        saves::write_slot_in(kind, &dir, slot, &state, metadata)?;
+                    }
+                    report
+                }
+                SaveKind::Pal4 => {
+                    let (state, report) = import_pal4(&data, &layout)?;
+                    if !dry_run {
+                        let metadata = SaveMetadata::now(0., state.location());
+                        saves::write_slot_in(kind, &dir, slot, &state, metadata)?;
+                    }
+                    report
+                }
+                SaveKind::Pal5
+                | SaveKind::Pal5Q
+                | SaveKind::Swd5
+                | SaveKind::SwdHc
+                | SaveKind::SwdCf => {
+                    bail!("retail save import is not supported for {}", kind.key())
+                }
+            };
+
+            print_import_report(&file, &report);
+            if !dry_run {
+                println!("wrote {}", target.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_kind(game: &str) -> Result<SaveKind> {
+    match SaveKind::from_key(game) {
+        Some(kind) => Ok(kind),
+        None => bail!(
+            "unknown game '{}', expected pal3, pal4, pal5, pal5q, swd5, swdhc or swdcf",
+            game
+        ),
+    }
+}
+
+/// A slot number maps to the game's slot file; anything else is a path.
+fn resolve(root: &Path, kind: SaveKind, slot: &str) -> PathBuf {
+    match slot.parse::<i32>() {
+        Ok(slot) => SlotFiles::new(&saves::slot_dir(root, kind.app_name()), slot).state,
+        Err(_) => PathBuf::from(slot),
+    }
+}
+
+fn read_raw(path: &Path) -> Result<Value> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("parse {}", path.display()))
+}
+
+fn list(root: &Path, kind: SaveKind) {
+    let dir = saves::slot_dir(root, kind.app_name());
+    let slots = saves::list_slots(&dir);
+    println!("{} ({})", kind.app_name(), dir.display());
+    if slots.is_empty() {
+        println!("  no saves");
+        return;
+    }
+
+    for slot in slots {
+        let path = SlotFiles::new(&dir, slot).state;
+        let status = match read_raw(&path) {
+            Ok(value) => status(&validate(kind, &value)),
+            Err(e) => format!("unreadable: {:#}", e),
+        };
+
+        match saves::read_metadata(&dir, slot) {
+            Some(meta) => println!(
+                "  {:>3}  v{}  {}  {}  {:<16}  {}{}",
+                slot,
+                meta.save_version,
+                saves::format_timestamp(meta.timestamp),
+                format_playtime(meta.playtime),
+                meta.scene,
+                status,
+                if meta.thumbnail.is_some() {
+                    "  [thumbnail]"
+                } else {
+                    ""
+                }
+            ),
+            None => println!("  {:>3}  (no metadata)  {}", slot, status),
+        }
+    }
+}
+
+fn status(report: &ValidationReport) -> String {
+    if report.is_clean() {
+        "ok".to_string()
+    } else if report.is_loadable() {
+        format!(
+            "loadable ({} unknown, {} missing)",
+            report.unknown_fields.len(),
+            report.missing_fields.len()
+        )
+    } else {
+        format!("broken: {}", report.errors.join("; "))
+    }
+}
+
+fn print_report(path: &Path, report: &ValidationReport) {
+    println!("{}", path.display());
+    match report.version {
+        Some(version) => println!("  version: {}", version),
+        None => println!("  version: unknown"),
+    }
+    for field in &report.unknown_fields {
+        println!("  unknown field: {}", field);
+    }
+    for field in &report.missing_fields {
+        println!("  missing field: {}", field);
+    }
+    for error in &report.errors {
+        println!("  error: {}", error);
+    }
+    println!("  status: {}", status(report));
+}
+
+fn format_playtime(seconds: f64) -> String {
+    let seconds = seconds.max(0.) as u64;
+    format!(
+        "{:>3}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn show(value: Option<&Value>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "(absent)".to_string(),
+    }
+}
+
+fn print_import_report(path: &Path, report: &ImportReport) {
+    println!("{}", path.display());
+    for field in &report.mapped {
+        println!("  mapped: {}", field);
+    }
+    for field in &report.unmapped {
+        println!("  unmapped: {}", field);
+    }
+    for warning in &report.warnings {
+        println!("  warning: {}", warning);
+    }
+    if !report.unread.is_empty() {
+        println!(
+            "  {} bytes in {} ranges not read by the layout:",
+            report.unread_bytes(),
+            report.unread.len()
+        );
+        for range in &report.unread {
+            println!("    0x{:06x}..0x{:06x}", range.start, range.end);
+        }
+    }
+}
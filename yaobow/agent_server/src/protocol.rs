@@ -56,7 +56,8 @@ pub enum AgentCommand {
     LogTail(LogTailParams),
     /// Capture a PNG screenshot of the current framebuffer.
     Screenshot,
-    /// Invoke a whitelisted `gi*` script function with literal args.
+    /// Evaluate a side-effect-free script expression, or call one
+    /// allow-listed query `gi*` sysfn with literal args.
     ScriptEval(ScriptEvalParams),
     /// List the EVF event triggers for the currently loaded block,
     /// with their handler function names and world-space bounding
@@ -343,21 +344,23 @@ pub struct ScreenshotResponse {
     pub rgba: Vec<u8>,
 }
 
-/// Whitelisted script invocation.
+/// Read-only script evaluation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScriptEvalParams {
-    /// Function name, e.g. `"giAddMoney"`. Must match the
-    /// session's allow-list.
+    /// Expression to evaluate, e.g. `"g[12] == 3 &&
+    /// giCheckEquipInInventory(1203)"`, or a bare sysfn name such as
+    /// `"giGetMoney"` when `args` is given. Sysfns must be on the
+    /// session's query allow-list.
     pub function: String,
-    /// Literal positional arguments. Each value must be a JSON
-    /// `number` or `string`; the session adapter converts to the
-    /// AngelScript stack types.
+    /// Literal positional arguments for the bare-name form. Each
+    /// value must be a JSON `number`, `bool` or `string`; the session
+    /// adapter converts to the AngelScript stack types.
     #[serde(default)]
     pub args: Vec<serde_json::Value>,
 }
 
-/// Script invocation reply: the function name and any returned value
-/// (the legacy `gi*` ABI has at most one return).
+/// Script evaluation reply: the submitted `function` and the value it
+/// evaluated to (the legacy `gi*` ABI has at most one return).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScriptEvalResponse {
     pub function: String,
@@ -438,11 +441,16 @@ pub struct InventoryEntry {
 pub struct PartyMember {
     /// Slot index (0..PLAYER_COUNT).
     pub slot: usize,
-    pub level: i32,
+    /// `level`, `max_hp` and `max_mp` are omitted when the game doesn't
+    /// know them (PAL5 reads no role data yet).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<i32>,
     pub hp: i32,
-    pub max_hp: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_hp: Option<i32>,
     pub mp: i32,
-    pub max_mp: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_mp: Option<i32>,
     pub in_team: bool,
 }
 
@@ -109,11 +109,11 @@ fn every_response_roundtrips() {
             leader_pos: [1.0, 2.0, 3.0],
             party: vec![PartyMember {
                 slot: 0,
-                level: 10,
+                level: Some(10),
                 hp: 320,
-                max_hp: 400,
+                max_hp: Some(400),
                 mp: 80,
-                max_mp: 100,
+                max_mp: Some(100),
                 in_team: true,
             }],
             money: 1234,
@@ -0,0 +1,140 @@
+//! PAL5 scripted camera path decoder (`camera.ChangeCameraPath`).
+//!
+//! A camera path is a list of timed keys, each a camera eye position and
+//! the point it looks at. The script addresses the path by its
+//! `role_*.bin` asset id; the runtime turns the keys into a spline track
+//! (`shared::openpal5::camera::CameraTrack`).
+//!
+//! ## Layout
+//! ```text
+//! 0x00  u32  key_count
+//! 0x04  key_count × 28 bytes:
+//!         f32  time        seconds from the start of the path
+//!         f32  eye[3]      world-space camera position
+//!         f32  look[3]     world-space look-at point
+//! ```
+//! Trailing bytes past the last key are ignored. This layout is the
+//! working reading the player is built against and has not yet been
+//! checked against every shipped path; keys are sorted by `time` on
+//! load so an out-of-order file still plays forwards, and a non-finite
+//! field rejects the whole file rather than flying the camera off into
+//! nowhere.
+
+/// Size of one serialized key.
+const KEY_LEN: usize = 28;
+
+/// Upper bound on `key_count`; guards against garbage headers asking
+/// for a multi-gigabyte allocation.
+const MAX_KEYS: usize = 4096;
+
+/// One camera path key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraKey {
+    /// Seconds from the start of the path.
+    pub time: f32,
+    /// World-space camera position.
+    pub eye: [f32; 3],
+    /// World-space look-at point.
+    pub look: [f32; 3],
+}
+
+#[derive(Debug, Clone)]
+pub struct CameraPathFile {
+    /// Keys in ascending `time` order.
+    pub keys: Vec<CameraKey>,
+}
+
+impl CameraPathFile {
+    pub fn read(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 4 {
+            anyhow::bail!("camera path too small: {} bytes", data.len());
+        }
+        let count = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+        if count == 0 || count > MAX_KEYS {
+            anyhow::bail!("camera path bad key count: {}", count);
+        }
+        if data.len() < 4 + count * KEY_LEN {
+            anyhow::bail!(
+                "camera path truncated: {} keys need {} bytes, got {}",
+                count,
+                4 + count * KEY_LEN,
+                data.len()
+            );
+        }
+
+        let f = |off: usize| -> f32 {
+            f32::from_le_bytes([data[off], data[off + 1], data[off + 2], data[off + 3]])
+        };
+        let mut keys: Vec<CameraKey> = (0..count)
+            .map(|i| {
+                let b = 4 + i * KEY_LEN;
+                CameraKey {
+                    time: f(b),
+                    eye: [f(b + 4), f(b + 8), f(b + 12)],
+                    look: [f(b + 16), f(b + 20), f(b + 24)],
+                }
+            })
+            .collect();
+        if let Some(i) = keys.iter().position(|k| {
+            !k.time.is_finite() || k.eye.iter().chain(k.look.iter()).any(|c| !c.is_finite())
+        }) {
+            anyhow::bail!("camera path key {} is not finite", i);
+        }
+
+        keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Ok(Self { keys })
+    }
+
+    /// Time of the last key, i.e. how long the path plays.
+    pub fn duration(&self) -> f32 {
+        self.keys.last().map_or(0.0, |k| k.time.max(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_path(keys: &[(f32, [f32; 3], [f32; 3])]) -> Vec<u8> {
+        let mut v = Vec::new();
+        v.extend_from_slice(&(keys.len() as u32).to_le_bytes());
+        for (time, eye, look) in keys {
+            v.extend_from_slice(&time.to_le_bytes());
+            for c in eye.iter().chain(look.iter()) {
+                v.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        v
+    }
+
+    #[test]
+    fn decodes_keys_in_time_order() {
+        let raw = make_path(&[
+            (2.0, [10.0, 20.0, 30.0], [0.0, 0.0, 0.0]),
+            (0.0, [1.0, 2.0, 3.0], [4.0, 5.0, 6.0]),
+        ]);
+        let path = CameraPathFile::read(&raw).unwrap();
+        assert_eq!(path.keys.len(), 2);
+        assert_eq!(path.keys[0].eye, [1.0, 2.0, 3.0]);
+        assert_eq!(path.keys[0].look, [4.0, 5.0, 6.0]);
+        assert_eq!(path.keys[1].time, 2.0);
+        assert_eq!(path.duration(), 2.0);
+    }
+
+    #[test]
+    fn rejects_truncated_and_empty_files() {
+        let mut raw = make_path(&[(0.0, [0.0; 3], [0.0; 3]), (1.0, [0.0; 3], [0.0; 3])]);
+        raw.truncate(raw.len() - 1);
+        assert!(CameraPathFile::read(&raw).is_err());
+        assert!(CameraPathFile::read(&make_path(&[])).is_err());
+        assert!(CameraPathFile::read(&[1, 0]).is_err());
+    }
+
+    #[test]
+    fn rejects_non_finite_keys() {
+        let raw = make_path(&[(0.0, [0.0; 3], [0.0; 3]), (f32::NAN, [0.0; 3], [0.0; 3])]);
+        assert!(CameraPathFile::read(&raw).is_err());
+        let raw = make_path(&[(0.0, [0.0, f32::INFINITY, 0.0], [0.0; 3])]);
+        assert!(CameraPathFile::read(&raw).is_err());
+    }
+}
@@ -2,10 +2,126 @@ use binrw::{BinRead, BinWrite};
 
 use super::Sized32Big5String;
 
+/// Upper bound on the field trigger count; guards against reading
+/// garbage as a trigger table.
+const MAX_TRIGGERS: u32 = 4096;
+
 #[derive(Debug, BinRead, BinWrite)]
 #[brw(little)]
 pub struct Fld {
     pub name: Sized32Big5String,
     pub map_file: Sized32Big5String,
+
+    /// Field trigger regions following the map file name. `None` when
+    /// the tail doesn't decode as a trigger table.
+    #[br(try)]
+    pub triggers: Option<FldTriggers>,
     // TODO: parse all the fields
 }
+
+/// Field trigger table.
+///
+/// ## Layout
+/// ```text
+/// u32  count
+/// count × trigger:
+///   i32  id
+///   u32  kind         0 = on entering, 1 = on the action key
+///   f32  min_x, min_z, max_x, max_z
+///   u32 + Big5  function    Lua global the trigger calls with `id`
+/// ```
+/// This is the working reading the SWD5 field runtime is built against
+/// and has not been checked against every shipped map.
+#[derive(Debug, BinRead, BinWrite)]
+#[brw(little)]
+pub struct FldTriggers {
+    #[br(assert(count <= MAX_TRIGGERS))]
+    pub count: u32,
+
+    #[br(count = count)]
+    pub triggers: Vec<FldTrigger>,
+}
+
+#[derive(Debug, Clone, BinRead, BinWrite)]
+#[brw(little)]
+pub struct FldTrigger {
+    pub id: i32,
+    pub kind: u32,
+    pub min_x: f32,
+    pub min_z: f32,
+    pub max_x: f32,
+    pub max_z: f32,
+    pub function: Sized32Big5String,
+}
+
+impl FldTrigger {
+    /// Fires when the player walks into the region.
+    pub const KIND_ENTER: u32 = 0;
+    /// Fires when the player presses the action key inside the region.
+    pub const KIND_ACTION: u32 = 1;
+
+    pub fn contains(&self, x: f32, z: f32) -> bool {
+        (self.min_x..=self.max_x).contains(&x) && (self.min_z..=self.max_z).contains(&z)
+    }
+}
+
+impl Fld {
+    /// The decoded trigger regions, or none.
+    pub fn triggers(&self) -> &[FldTrigger] {
+        self.triggers.as_ref().map_or(&[], |t| &t.triggers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn string(s: &str) -> Vec<u8> {
+        let mut v = (s.len() as u32).to_le_bytes().to_vec();
+        v.extend_from_slice(s.as_bytes());
+        v
+    }
+
+    fn header() -> Vec<u8> {
+        let mut v = string("field");
+        v.extend(string("0001.map"));
+        v
+    }
+
+    #[test]
+    fn reads_trigger_table() {
+        let mut raw = header();
+        raw.extend_from_slice(&1u32.to_le_bytes());
+        raw.extend_from_slice(&7i32.to_le_bytes());
+        raw.extend_from_slice(&FldTrigger::KIND_ACTION.to_le_bytes());
+        for c in [-10f32, -20., 10., 20.] {
+            raw.extend_from_slice(&c.to_le_bytes());
+        }
+        raw.extend(string("event_0001"));
+
+        let fld = Fld::read(&mut Cursor::new(raw)).unwrap();
+        assert_eq!(fld.map_file.to_string(), "0001.map");
+        let triggers = fld.triggers();
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(
+            (triggers[0].id, triggers[0].kind),
+            (7, FldTrigger::KIND_ACTION)
+        );
+        assert_eq!(triggers[0].function.to_string(), "event_0001");
+        assert!(triggers[0].contains(0., 20.));
+        assert!(!triggers[0].contains(11., 0.));
+    }
+
+    #[test]
+    fn missing_or_bad_trigger_table_is_none() {
+        let fld = Fld::read(&mut Cursor::new(header())).unwrap();
+        assert!(fld.triggers.is_none());
+
+        let mut raw = header();
+        raw.extend_from_slice(&u32::MAX.to_le_bytes());
+        let fld = Fld::read(&mut Cursor::new(raw)).unwrap();
+        assert!(fld.triggers().is_empty());
+    }
+}
@@ -65,3 +65,193 @@ pub struct MapModelChunk {
     pub unknown5: u32,
     pub model_file: Sized32Big5String,
 }
+
+/// Floor cell flag: the player can't stand on this cell.
+pub const FLOOR_BLOCKED: u32 = 1;
+
+/// Upper bound on the floor grid size; guards against garbage headers
+/// asking for a huge allocation.
+const MAX_FLOOR_CELLS: usize = 1 << 22;
+
+/// Size of the floor grid header and of one cell.
+const FLOOR_HEADER_LEN: usize = 20;
+const FLOOR_CELL_LEN: usize = 8;
+
+impl MapTerrainChunk {
+    /// Decode the floor grid carried in the chunk data.
+    pub fn floor(&self) -> anyhow::Result<MapFloor> {
+        MapFloor::read(&self.data)
+    }
+}
+
+/// Walkable floor of a map: a regular XZ grid of cells, each with the
+/// ground height and whether it can be walked on.
+///
+/// ## Layout (terrain chunk data)
+/// ```text
+/// 0x00  u32  columns
+/// 0x04  u32  rows
+/// 0x08  f32  origin_x     world X of the grid's first column edge
+/// 0x0C  f32  origin_z     world Z of the grid's first row edge
+/// 0x10  f32  cell_size    world units per cell edge
+/// 0x14  columns × rows cells, row-major (+X across a row, +Z down):
+///         f32  height
+///         u32  flags      bit 0 = blocked
+/// ```
+/// Trailing bytes past the last cell are ignored. This is the working
+/// reading the SWD5 field runtime is built against; it has not been
+/// checked against every shipped map, so callers treat a decode failure
+/// as "no floor data" (and log it) rather than a load error.
+#[derive(Debug, Clone)]
+pub struct MapFloor {
+    pub columns: u32,
+    pub rows: u32,
+    pub origin_x: f32,
+    pub origin_z: f32,
+    pub cell_size: f32,
+    pub cells: Vec<FloorCell>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloorCell {
+    pub height: f32,
+    pub flags: u32,
+}
+
+impl FloorCell {
+    pub fn is_blocked(&self) -> bool {
+        self.flags & FLOOR_BLOCKED != 0
+    }
+}
+
+impl MapFloor {
+    pub fn read(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < FLOOR_HEADER_LEN {
+            anyhow::bail!("floor data too small: {} bytes", data.len());
+        }
+
+        let u = |off: usize| {
+            u32::from_le_bytes([data[off], data[off + 1], data[off + 2], data[off + 3]])
+        };
+        let f = |off: usize| f32::from_bits(u(off));
+
+        let columns = u(0);
+        let rows = u(4);
+        let cell_size = f(16);
+        let count = columns as usize * rows as usize;
+        if count == 0 || count > MAX_FLOOR_CELLS {
+            anyhow::bail!("floor bad grid size: {}x{}", columns, rows);
+        }
+        if !cell_size.is_finite() || cell_size <= 0. {
+            anyhow::bail!("floor bad cell size: {}", cell_size);
+        }
+        let (origin_x, origin_z) = (f(8), f(12));
+        if !origin_x.is_finite() || !origin_z.is_finite() {
+            anyhow::bail!("floor bad origin: ({}, {})", origin_x, origin_z);
+        }
+        let needed = FLOOR_HEADER_LEN + count * FLOOR_CELL_LEN;
+        if data.len() < needed {
+            anyhow::bail!(
+                "floor data truncated: {}x{} cells need {} bytes, got {}",
+                columns,
+                rows,
+                needed,
+                data.len()
+            );
+        }
+
+        let cells = (0..count)
+            .map(|i| {
+                let b = FLOOR_HEADER_LEN + i * FLOOR_CELL_LEN;
+                FloorCell {
+                    height: f(b),
+                    flags: u(b + 4),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            columns,
+            rows,
+            origin_x,
+            origin_z,
+            cell_size,
+            cells,
+        })
+    }
+
+    /// The cell under the world position `(x, z)`, if it lies on the grid.
+    pub fn cell_at(&self, x: f32, z: f32) -> Option<&FloorCell> {
+        let column = ((x - self.origin_x) / self.cell_size).floor();
+        let row = ((z - self.origin_z) / self.cell_size).floor();
+        if column < 0. || row < 0. || column >= self.columns as f32 || row >= self.rows as f32 {
+            return None;
+        }
+
+        self.cells
+            .get(row as usize * self.columns as usize + column as usize)
+    }
+
+    /// Ground height at `(x, z)`, or `None` off the grid or on a blocked
+    /// cell.
+    pub fn height_at(&self, x: f32, z: f32) -> Option<f32> {
+        self.cell_at(x, z)
+            .filter(|cell| !cell.is_blocked())
+            .map(|cell| cell.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_floor(columns: u32, rows: u32, cells: &[(f32, u32)]) -> Vec<u8> {
+        let mut v = Vec::new();
+        v.extend_from_slice(&columns.to_le_bytes());
+        v.extend_from_slice(&rows.to_le_bytes());
+        for header in [-100f32, 50., 10.] {
+            v.extend_from_slice(&header.to_le_bytes());
+        }
+        for (height, flags) in cells {
+            v.extend_from_slice(&height.to_le_bytes());
+            v.extend_from_slice(&flags.to_le_bytes());
+        }
+        v
+    }
+
+    #[test]
+    fn floor_cells_map_to_world_positions() {
+        let raw = make_floor(2, 2, &[(1., 0), (2., 0), (3., FLOOR_BLOCKED), (4., 0)]);
+        let floor = MapFloor::read(&raw).unwrap();
+        assert_eq!((floor.columns, floor.rows, floor.cell_size), (2, 2, 10.));
+
+        assert_eq!(floor.height_at(-95., 55.), Some(1.));
+        assert_eq!(floor.height_at(-85., 55.), Some(2.));
+        assert_eq!(floor.height_at(-85., 65.), Some(4.));
+        // Blocked, then off the grid on either side.
+        assert_eq!(floor.height_at(-95., 65.), None);
+        assert!(floor.cell_at(-95., 65.).unwrap().is_blocked());
+        assert!(floor.cell_at(-101., 55.).is_none());
+        assert!(floor.cell_at(-95., 70.).is_none());
+    }
+
+    #[test]
+    fn rejects_truncated_and_empty_floors() {
+        let mut raw = make_floor(2, 1, &[(0., 0), (0., 0)]);
+        raw.truncate(raw.len() - 1);
+        assert!(MapFloor::read(&raw).is_err());
+        assert!(MapFloor::read(&make_floor(0, 4, &[])).is_err());
+        assert!(MapFloor::read(&[0; 8]).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_cell_size_and_origin() {
+        let mut raw = make_floor(1, 1, &[(0., 0)]);
+        raw[16..20].copy_from_slice(&0f32.to_le_bytes());
+        assert!(MapFloor::read(&raw).is_err());
+
+        let mut raw = make_floor(1, 1, &[(0., 0)]);
+        raw[8..12].copy_from_slice(&f32::NAN.to_le_bytes());
+        assert!(MapFloor::read(&raw).is_err());
+    }
+}
@@ -18,6 +18,12 @@ fn main() {
     generate_script_bridge("openswd5.idl", "shared_openswd5_bridge.rs");
     generate_comdef("shared_services.idl", "shared_services_comdef.rs");
 
+    // Game-neutral loading overlay, implemented in p7 and driven by the
+    // PAL4 transition and the PAL5 streamed map loads.
+    generate_comdef("loading.idl", "shared_loading_comdef.rs");
+    generate_p7("loading.idl", "shared_loading.p7");
+    generate_script_bridge("loading.idl", "shared_loading_bridge.rs");
+
     // PAL4 debug overlay bridge: emit both the Rust ComObject scaffolding
     // and the p7 binding source consumed by `ScriptHost::add_binding`.
     generate_comdef("pal4_debug.idl", "shared_pal4_debug_comdef.rs");
@@ -75,8 +81,9 @@ fn generate_script_bridge(idl_file: &str, out_file: &str) {
     }
 }
 
-/// Pack the five codegen-derived IDL p7s (`shared_openpal{3,4,5}.p7`,
-/// `shared_openswd5.p7`, `shared_pal4_debug.p7` from `OUT_DIR`) into
+/// Pack the six codegen-derived IDL p7s (`shared_openpal{3,4,5}.p7`,
+/// `shared_openswd5.p7`, `shared_pal4_debug.p7`, `shared_loading.p7`
+/// from `OUT_DIR`) into
 /// `OUT_DIR/shared_scripts.ypk`. The codegen files are stored under
 /// flat names (`openpal3.p7`, `openpal4.p7`, etc.) so that, once the
 /// ypk is mounted at `/shared/` on the script `AssetManager`, scripts
@@ -101,6 +108,7 @@ fn pack_script_bundle() {
         ("shared_openpal5.p7", "openpal5.p7"),
         ("shared_openswd5.p7", "openswd5.p7"),
         ("shared_pal4_debug.p7", "pal4_debug.p7"),
+        ("shared_loading.p7", "loading.p7"),
     ];
 
     let extra_paths: Vec<PathBuf> = extras.iter().map(|(file, _)| out_path(file)).collect();
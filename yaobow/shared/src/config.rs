@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use crate::GameType;
+use crate::localization::LocalizationConfig;
 
 const CONFIG_FILE_NAME: &str = "yaobow.toml";
 const ENV_OVERRIDE: &str = "YAOBOW_CONFIG";
@@ -102,6 +103,60 @@ impl Default for AudioConfig {
     }
 }
 
+/// User script mods. Stored under `[mods]` in `yaobow.toml`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ModsConfig {
+    /// Directory scanned for mod `.ypk` packages. Empty means `mods/`
+    /// under the yaobow config dir; see [`YaobowConfig::mods_dir`].
+    #[serde(default)]
+    pub dir: String,
+    /// Mod names (as declared in their `mod.toml`) to leave unloaded.
+    #[serde(default)]
+    pub disabled: Vec<String>,
+}
+
+/// Default processed-asset cache size limit, in MiB.
+fn default_cache_max_size_mb() -> u64 {
+    2048
+}
+
+fn default_cache_enabled() -> bool {
+    true
+}
+
+/// Processed-asset cache (decoded textures, DFF geometry buffers,
+/// terrain atlases). Stored
+/// under `[cache]` in `yaobow.toml`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CacheConfig {
+    #[serde(default = "default_cache_enabled")]
+    pub enabled: bool,
+    /// Cache directory. Empty means the OS cache dir; see
+    /// [`YaobowConfig::cache_dir`].
+    #[serde(default)]
+    pub dir: String,
+    /// Size limit in MiB; least recently used entries are evicted past
+    /// it. `0` means unlimited.
+    #[serde(default = "default_cache_max_size_mb")]
+    pub max_size_mb: u64,
+    /// Store decoded non-DDS textures (TGA, BMP, …) BC-compressed
+    /// instead of as RGBA. Smaller on disk and in VRAM, but the encode
+    /// is lossy, so it is off by default.
+    #[serde(default)]
+    pub compress_textures: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_cache_enabled(),
+            dir: String::new(),
+            max_size_mb: default_cache_max_size_mb(),
+            compress_textures: false,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct YaobowConfig {
     #[serde(default)]
@@ -124,6 +179,19 @@ pub struct YaobowConfig {
     /// runtime and the editor.
     #[serde(default)]
     pub audio: AudioConfig,
+
+    /// Dialog language pack and Chinese script conversion. See
+    /// [`crate::localization`].
+    #[serde(default)]
+    pub localization: LocalizationConfig,
+
+    /// Where user script mods live and which are switched off.
+    #[serde(default)]
+    pub mods: ModsConfig,
+
+    /// On-disk cache of decoded assets.
+    #[serde(default)]
+    pub cache: CacheConfig,
 }
 
 impl YaobowConfig {
@@ -189,6 +257,29 @@ impl YaobowConfig {
         Ok(())
     }
 
+    /// Directory scanned for user script mods.
+    pub fn mods_dir(&self) -> PathBuf {
+        if self.mods.dir.is_empty() {
+            crate::ydirs::config_dir().join("mods")
+        } else {
+            PathBuf::from(&self.mods.dir)
+        }
+    }
+
+    /// Directory of the processed-asset cache.
+    pub fn cache_dir(&self) -> PathBuf {
+        if self.cache.dir.is_empty() {
+            crate::ydirs::cache_dir()
+        } else {
+            PathBuf::from(&self.cache.dir)
+        }
+    }
+
+    /// Asset cache size limit in bytes; `0` means unlimited.
+    pub fn cache_max_bytes(&self) -> u64 {
+        self.cache.max_size_mb.saturating_mul(1024 * 1024)
+    }
+
     pub fn asset_path_for(&self, game: GameType) -> &str {
         self.game
             .get(game.config_key())
@@ -430,4 +521,22 @@ mod tests {
         cfg.audio.master_volume = f32::NAN;
         assert_eq!(cfg.master_volume(), 0.7);
     }
+
+    #[test]
+    fn cache_defaults_when_section_absent_or_partial() {
+        let cfg: YaobowConfig = toml::from_str("").unwrap();
+        assert!(cfg.cache.enabled);
+        assert_eq!(cfg.cache_max_bytes(), 2048 * 1024 * 1024);
+        assert!(!cfg.cache.compress_textures);
+
+        let cfg: YaobowConfig = toml::from_str("[cache]\nmax_size_mb = 0\n").unwrap();
+        assert!(cfg.cache.enabled);
+        assert_eq!(cfg.cache_max_bytes(), 0);
+
+        let cfg: YaobowConfig = toml::from_str("[cache]\ndir = \"/tmp/yc\"\n").unwrap();
+        assert_eq!(cfg.cache_dir(), PathBuf::from("/tmp/yc"));
+
+        let cfg: YaobowConfig = toml::from_str("[cache]\ncompress_textures = true\n").unwrap();
+        assert!(cfg.cache.compress_textures);
+    }
 }
@@ -7,14 +7,21 @@ pub mod config;
 #[macro_use]
 pub mod comdef {
     include!(concat!(env!("OUT_DIR"), "/shared_services_comdef.rs"));
+
+    #[macro_use]
+    pub mod loading {
+        include!(concat!(env!("OUT_DIR"), "/shared_loading_comdef.rs"));
+    }
 }
 pub mod config_service;
 pub mod exporters;
 pub mod loaders;
+pub mod localization;
 pub mod openpal3;
 pub mod openpal4;
 pub mod openpal5;
 pub mod openswd5;
+pub mod saves;
 pub mod scripting;
 pub mod theme_runtime;
 /// Auto-generated script bridges from `[protosept(scriptable)]` IDLs.
@@ -22,6 +29,9 @@ pub mod script_bridges {
     pub mod pal4_debug {
         include!(concat!(env!("OUT_DIR"), "/shared_pal4_debug_bridge.rs"));
     }
+    pub mod loading {
+        include!(concat!(env!("OUT_DIR"), "/shared_loading_bridge.rs"));
+    }
     pub mod openpal4 {
         include!(concat!(env!("OUT_DIR"), "/shared_openpal4_bridge.rs"));
     }
@@ -55,7 +65,8 @@ const SCRIPT_BUNDLE_YPK: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/share
 
 /// Mounts this crate's `shared_scripts.ypk` at `/shared/` on the
 /// script `AssetManager`, so scripts can `import shared.openpal4;`,
-/// `import shared.openpal3;`, `import shared.pal4_debug;`, etc.
+/// `import shared.openpal3;`, `import shared.pal4_debug;`,
+/// `import shared.loading;`, etc.
 pub fn mount_scripts(assets: &radiance::asset::AssetManager) {
     assets
         .mount_ypk_bytes("/shared", SCRIPT_BUNDLE_YPK)
@@ -6,6 +6,7 @@ use fileformats::rwbs::{
     material::Material,
     read_bsp,
     sector::{AtomicSector, Sector},
+    world::World,
 };
 use mini_fs::{MiniFs, StoreExt};
 use radiance::{comdef::IEntity, rendering::ComponentFactory, scene::CoreEntity};
@@ -40,18 +41,45 @@ fn load_bsp_model<P: AsRef<Path>>(
         .with_context(|| format!("reading BSP {}", path.as_ref().display()))?;
     let chunks =
         read_bsp(&data).with_context(|| format!("parsing BSP {}", path.as_ref().display()))?;
-    if !chunks.is_empty() {
+    load_bsp_worlds(entity, component_factory, vfs, path, chunks, config);
+    Ok(())
+}
+
+/// Build the entity for already-parsed BSP worlds. Like
+/// [`super::dff::create_entity_from_dff_clumps`], this lets streaming
+/// loaders run `read_bsp` on a worker; `path` still resolves textures.
+pub fn create_entity_from_bsp_worlds<P: AsRef<Path>>(
+    component_factory: &Rc<dyn ComponentFactory>,
+    vfs: &MiniFs,
+    path: P,
+    name: String,
+    worlds: Vec<World>,
+    config: &DffLoaderConfig,
+) -> ComRc<IEntity> {
+    let entity = CoreEntity::create(name, true);
+    load_bsp_worlds(entity.clone(), component_factory, vfs, path, worlds, config);
+    entity
+}
+
+fn load_bsp_worlds<P: AsRef<Path>>(
+    entity: ComRc<IEntity>,
+    component_factory: &Rc<dyn ComponentFactory>,
+    vfs: &MiniFs,
+    path: P,
+    worlds: Vec<World>,
+    config: &DffLoaderConfig,
+) {
+    if !worlds.is_empty() {
         create_geometries(
             entity,
             component_factory,
-            &chunks[0].sector,
-            &chunks[0].materials,
+            &worlds[0].sector,
+            &worlds[0].materials,
             vfs,
             path,
             config,
         );
     }
-    Ok(())
 }
 
 fn create_geometries<P: AsRef<Path>>(
@@ -141,5 +169,6 @@ fn create_geometry_from_atomic_sector<P: AsRef<Path>>(
         false,
         config.fog_exempt,
         false,
+        None,
     );
 }
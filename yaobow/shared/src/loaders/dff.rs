@@ -8,6 +8,7 @@ use fileformats::rwbs::{
 };
 use mini_fs::{MiniFs, StoreExt};
 use radiance::{
+    asset::cache::{self, CacheKey},
     comdef::{
         IArmatureComponent, IBillboardComponent, IComponent, IEntity, IHAnimBoneComponent,
         ISkinnedMeshComponent, IStaticMeshComponent,
@@ -20,7 +21,7 @@ use radiance::{
     math::{Mat44, Vec3},
     rendering::{
         AddressMode, AlphaKind, BlendMode, ComponentFactory, CullMode, FilterMode, MaterialDef,
-        SamplerDef,
+        MipmapMode, SamplerDef, VertexBuffer, VertexComponents,
     },
     scene::CoreEntity,
 };
@@ -118,8 +119,6 @@ pub fn create_entity_from_dff_model<P: AsRef<Path>>(
     visible: bool,
     config: &DffLoaderConfig,
 ) -> anyhow::Result<ComRc<IEntity>> {
-    let entity = CoreEntity::create(name, visible);
-
     let mut data = vec![];
     vfs.open(&path)
         .with_context(|| format!("opening DFF {}", path.as_ref().display()))?
@@ -127,7 +126,40 @@ pub fn create_entity_from_dff_model<P: AsRef<Path>>(
         .with_context(|| format!("reading DFF {}", path.as_ref().display()))?;
     let chunks =
         read_dff(&data).with_context(|| format!("parsing DFF {}", path.as_ref().display()))?;
-    for chunk in chunks {
+
+    Ok(create_entity_from_dff_clumps(
+        component_factory,
+        vfs,
+        path,
+        name,
+        visible,
+        chunks,
+        Some(dff_cache_key(&data)),
+        config,
+    ))
+}
+
+/// Build the entity for already-parsed DFF clumps. Parsing
+/// (`read_dff`) only touches plain data, so streaming loaders run it
+/// on a worker and call this on the main thread. `path` is still
+/// needed to resolve the model's textures. `source_key` (see
+/// [`dff_cache_key`]) lets the processed vertex/index buffers be served
+/// from the asset cache; `None` always rebuilds them.
+pub fn create_entity_from_dff_clumps<P: AsRef<Path>>(
+    component_factory: &Rc<dyn ComponentFactory>,
+    vfs: &MiniFs,
+    path: P,
+    name: String,
+    visible: bool,
+    chunks: Vec<Clump>,
+    source_key: Option<CacheKey>,
+    config: &DffLoaderConfig,
+) -> ComRc<IEntity> {
+    let entity = CoreEntity::create(name, visible);
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        let clump_key = source_key
+            .as_ref()
+            .map(|key| derive_cache_key(key, &[&(i as u32).to_le_bytes()]));
         load_clump(
             chunk,
             entity.clone(),
@@ -135,10 +167,11 @@ pub fn create_entity_from_dff_model<P: AsRef<Path>>(
             vfs,
             path.as_ref(),
             config,
+            clump_key,
         );
     }
 
-    Ok(entity)
+    entity
 }
 
 struct HAnimBone {
@@ -190,6 +223,7 @@ fn load_clump(
     vfs: &MiniFs,
     path: &Path,
     config: &DffLoaderConfig,
+    cache_key: Option<CacheKey>,
 ) {
     let mut root_bone = None;
     let mut bone_id_map: HashMap<u32, ComRc<IEntity>> = HashMap::new();
@@ -447,6 +481,18 @@ fn load_clump(
             two_sided,
             config.dynamic_lighting,
             config.fog_exempt,
+            cache_key.as_ref().map(|key| {
+                derive_cache_key(
+                    key,
+                    &[
+                        &atomic.geometry.to_le_bytes(),
+                        &[
+                            config.dynamic_lighting as u8,
+                            config.bsp_lightmap_tint.is_some() as u8,
+                        ],
+                    ],
+                )
+            }),
         );
 
         if billboard {
@@ -730,6 +776,7 @@ fn create_foliage_geometry(
         dynamic_lighting,
         fog_exempt,
         true, // force_alpha_test: leaf cards cast cutout shadows
+        None,
     );
 }
 
@@ -747,6 +794,7 @@ fn create_geometry(
     two_sided: bool,
     dynamic_lighting: bool,
     fog_exempt: bool,
+    cache_key: Option<CacheKey>,
 ) {
     if geometry.morph_targets.len() == 0 {
         return;
@@ -900,6 +948,7 @@ fn create_geometry(
         dynamic_lighting,
         fog_exempt,
         false,
+        cache_key,
     );
 }
 
@@ -921,21 +970,223 @@ pub(crate) fn create_geometry_internal(
     dynamic_lighting: bool,
     fog_exempt: bool,
     force_alpha_test: bool,
+    cache_key: Option<CacheKey>,
 ) {
-    let mut r_vertices = vec![];
     // Forward per-vertex normals only when the geometry actually ships them
     // *and* the caller opts into dynamic lighting; otherwise the mesh stays
     // on the unlit/baked path and the normal attribute is omitted.
     let has_normals = dynamic_lighting && normals.map_or(false, |n| n.len() == vertices.len());
+
+    // RenderWare DFF carries blend info implicitly: the material's RGBA
+    // `color` alpha byte signals translucency and the texture's own
+    // alpha channel separates alpha-cutout (binary) from alpha-blended
+    // (graded). See `detect_blend` below.
+    let build_material = |slot: u16| -> MaterialDef {
+        let material = &materials[slot as usize];
+        let md = if let Some(texture) = material.texture.as_ref() {
+            if let Some(tint) = bsp_lightmap_tint {
+                match load_lightmap_material_pair(
+                    material,
+                    tint,
+                    vfs,
+                    path.as_ref(),
+                    texture_resolver,
+                ) {
+                    Some(md) => md,
+                    _ => {
+                        // BSP material with a diffuse but no
+                        // `LightMapPlugin` (rare PAL4 case). PAL4
+                        // BSP diffuses ship with scene-generic
+                        // names (`s01`, `s01b`, …) that would
+                        // otherwise collide in the process-wide
+                        // `TextureStore` LRU across scenes; use
+                        // the scoped loader so each scene's
+                        // diffuse atlas gets its own
+                        // `TextureDef`. See
+                        // `load_lightmap_material_pair` for the
+                        // same rationale on the lightmap key.
+                        load_bsp_material_texture_scoped(
+                            texture,
+                            vfs,
+                            path.as_ref(),
+                            texture_resolver,
+                        )
+                    }
+                }
+            } else {
+                load_material_texture(texture, vfs, path.as_ref(), texture_resolver, has_normals)
+            }
+        } else if let (Some(tint), Some(_)) = (bsp_lightmap_tint, material.lightmap.as_ref()) {
+            // PAL4 BSPs occasionally ship materials with NO
+            // primary diffuse `texture` chunk, only a
+            // `LightMapPlugin` (`*LightingMap`) — i.e. the
+            // baked lightmap atlas is the surface's only color
+            // source (typical for cave/wall sectors where the
+            // material was authored as lightmap-only). Build a
+            // `LightMapMaterialDef` with a white dummy diffuse
+            // so `(lightMap * 1.5 * intensity + 0.3) * white *
+            // tint` renders the lightmap straight through.
+            // Without this branch we fell to the `missing`-
+            // texture placeholder below and rendered 1+
+            // fan-shaped pure-black triangle clusters on cave
+            // walls.
+            load_lightmap_only_material(material, tint, vfs, path.as_ref(), texture_resolver)
+        } else {
+            log::debug!("no texture info for material {:?}", path);
+            radiance::rendering::SimpleMaterialDef::create2("missing", None)
+        };
+
+        let blend = detect_blend(material, &md);
+        let mut md = md.with_blend(blend);
+        // PAL5 tree leaves/leaf billboards are graded-alpha (→ AlphaBlend)
+        // but must cast leaf-shaped shadows; flag them so the engine routes
+        // them through the alpha-clip cutout depth pass. Foliage cards
+        // (force_alpha_test) and dynamically-lit blended meshes (PAL5 trees)
+        // opt in; opaque/cutout already cast unconditionally.
+        if (force_alpha_test || dynamic_lighting) && blend == BlendMode::AlphaBlend {
+            let mut p = *md.params();
+            p.casts_shadow = true;
+            md = md.with_params(p);
+        }
+        // PAL5 foliage cards are single quads; render them
+        // two-sided so the back-facing half is not culled.
+        if two_sided && matches!(blend, BlendMode::AlphaTest | BlendMode::AlphaBlend) {
+            md = md.with_cull(CullMode::None);
+        }
+        if let Some(name) = material.userdata_name.as_deref() {
+            md = md.with_debug_name(name);
+        }
+        if force_unique_materials {
+            md = md.make_unique();
+        }
+
+        // Stamp per-material fog exemption (skybox etc.). Preserves
+        // every other param already set on the MaterialDef (lightmap
+        // tint/intensity, blend-derived alpha_ref, …).
+        if fog_exempt {
+            let mut p = *md.params();
+            p.fog_exempt = true;
+            md = md.with_params(p);
+        }
+
+        md
+    };
+
+    let cached = cache_key.as_ref().and_then(|key| {
+        cached_geometries(
+            key,
+            vertices.len(),
+            has_normals,
+            texcoord_sets.len(),
+            &build_material,
+        )
+    });
+    let r_geometries = match cached {
+        Some(geometries) => geometries,
+        None => {
+            let geometries = build_geometries(
+                vertices,
+                normals.filter(|_| has_normals),
+                triangles,
+                texcoord_sets,
+                path,
+                &build_material,
+            );
+            if let (Some(key), Some(cache)) = (cache_key.as_ref(), cache::global()) {
+                cache.put(DFF_GEOMETRY_KIND, key, &encode_geometries(&geometries));
+            }
+            geometries.into_iter().map(|(_, g)| g).collect()
+        }
+    };
+
+    match skin_info {
+        None => {
+            let mesh_component =
+                StaticMeshComponent::new(entity.clone(), r_geometries, component_factory.clone());
+            entity.add_component(
+                IStaticMeshComponent::uuid(),
+                crosscom::ComRc::from_object(mesh_component),
+            );
+        }
+        Some(skin_info) => {
+            let bone_id: Vec<[usize; 4]> = skin_info
+                .v_bone_indices
+                .iter()
+                .map(|id| {
+                    [
+                        id[0] as usize,
+                        id[1] as usize,
+                        id[2] as usize,
+                        id[3] as usize,
+                    ]
+                })
+                .collect();
+
+            for r_geometry in r_geometries {
+                let child = CoreEntity::create(format!("{}_geom", entity.name()), true);
+
+                let mesh_component = SkinnedMeshComponent::new(
+                    child.clone(),
+                    component_factory.clone(),
+                    r_geometry,
+                    skin_info.armature.clone(),
+                    bone_id.clone(),
+                    skin_info.v_weights.clone(),
+                );
+
+                child.add_component(
+                    ISkinnedMeshComponent::uuid(),
+                    ComRc::from_object(mesh_component),
+                );
+
+                entity.attach(child);
+            }
+        }
+    }
+}
+
+/// Cache kind for processed DFF vertex/index buffers.
+const DFF_GEOMETRY_KIND: &str = "dff_geometry";
+/// Bump when [`encode_geometries`]' payload layout or the way
+/// [`build_geometries`] lays out vertices changes, so stale entries miss.
+const DFF_GEOMETRY_VERSION: u32 = 1;
+
+/// Cache key for the processed geometry of a DFF, derived from its raw
+/// bytes. Loaders that parse the DFF off-thread compute it alongside
+/// `read_dff` and hand it to [`create_entity_from_dff_clumps`].
+pub fn dff_cache_key(data: &[u8]) -> CacheKey {
+    CacheKey::new(DFF_GEOMETRY_KIND, DFF_GEOMETRY_VERSION, &[data])
+}
+
+/// Derive a child key (clump, then geometry within the clump) from a
+/// parent key plus the parts that change the processed buffers.
+fn derive_cache_key(parent: &CacheKey, parts: &[&[u8]]) -> CacheKey {
+    let parent = parent.to_bytes();
+    let mut all: Vec<&[u8]> = vec![&parent];
+    all.extend_from_slice(parts);
+    CacheKey::new(DFF_GEOMETRY_KIND, DFF_GEOMETRY_VERSION, &all)
+}
+
+/// Build one `Geometry` per material group, paired with the material slot
+/// it was grouped by so the cache can rebuild the material on a hit.
+fn build_geometries(
+    vertices: &[Vec3f],
+    normals: Option<&Vec<Vec3f>>,
+    triangles: &[Triangle],
+    texcoord_sets: &[Vec<TexCoord>],
+    path: &Path,
+    build_material: &dyn Fn(u16) -> MaterialDef,
+) -> Vec<(u16, radiance::components::mesh::Geometry)> {
+    let mut r_vertices = vec![];
     let mut r_normals: Vec<Vec3> = vec![];
     for i in 0..vertices.len() {
         r_vertices.push(Vec3::new(vertices[i].x, vertices[i].y, vertices[i].z));
-        if has_normals {
-            let n = &normals.unwrap()[i];
+        if let Some(normals) = normals {
+            let n = &normals[i];
             r_normals.push(Vec3::new(n.x, n.y, n.z));
         }
     }
-    let r_normals: Option<&[Vec3]> = if has_normals { Some(&r_normals) } else { None };
+    let r_normals: Option<&[Vec3]> = normals.map(|_| r_normals.as_slice());
 
     let r_texcoords: Vec<Vec<radiance::components::mesh::TexCoord>> = texcoord_sets
         .iter()
@@ -960,116 +1211,10 @@ pub(crate) fn create_geometry_internal(
         {
             Some(idx) => idx,
             None => {
-                let material = &materials[t.material as usize];
-                // RenderWare DFF carries blend info implicitly: the
-                // material's RGBA `color` alpha byte signals translucency
-                // and the texture's own alpha channel separates
-                // alpha-cutout (binary) from alpha-blended (graded). See
-                // `detect_blend` below.
-                let md = if let Some(texture) = material.texture.as_ref() {
-                    if let Some(tint) = bsp_lightmap_tint {
-                        match load_lightmap_material_pair(
-                            material,
-                            tint,
-                            vfs,
-                            path.as_ref(),
-                            texture_resolver,
-                        ) {
-                            Some(md) => md,
-                            _ => {
-                                // BSP material with a diffuse but no
-                                // `LightMapPlugin` (rare PAL4 case). PAL4
-                                // BSP diffuses ship with scene-generic
-                                // names (`s01`, `s01b`, …) that would
-                                // otherwise collide in the process-wide
-                                // `TextureStore` LRU across scenes; use
-                                // the scoped loader so each scene's
-                                // diffuse atlas gets its own
-                                // `TextureDef`. See
-                                // `load_lightmap_material_pair` for the
-                                // same rationale on the lightmap key.
-                                load_bsp_material_texture_scoped(
-                                    texture,
-                                    vfs,
-                                    path.as_ref(),
-                                    texture_resolver,
-                                )
-                            }
-                        }
-                    } else {
-                        load_material_texture(
-                            texture,
-                            vfs,
-                            path.as_ref(),
-                            texture_resolver,
-                            has_normals,
-                        )
-                    }
-                } else if let (Some(tint), Some(_)) =
-                    (bsp_lightmap_tint, material.lightmap.as_ref())
-                {
-                    // PAL4 BSPs occasionally ship materials with NO
-                    // primary diffuse `texture` chunk, only a
-                    // `LightMapPlugin` (`*LightingMap`) — i.e. the
-                    // baked lightmap atlas is the surface's only color
-                    // source (typical for cave/wall sectors where the
-                    // material was authored as lightmap-only). Build a
-                    // `LightMapMaterialDef` with a white dummy diffuse
-                    // so `(lightMap * 1.5 * intensity + 0.3) * white *
-                    // tint` renders the lightmap straight through.
-                    // Without this branch we fell to the `missing`-
-                    // texture placeholder below and rendered 1+
-                    // fan-shaped pure-black triangle clusters on cave
-                    // walls.
-                    load_lightmap_only_material(
-                        material,
-                        tint,
-                        vfs,
-                        path.as_ref(),
-                        texture_resolver,
-                    )
-                } else {
-                    log::debug!("no texture info for material {:?}", path);
-                    radiance::rendering::SimpleMaterialDef::create2("missing", None)
-                };
-
-                let blend = detect_blend(material, &md);
-                let mut md = md.with_blend(blend);
-                // PAL5 tree leaves/leaf billboards are graded-alpha (→ AlphaBlend)
-                // but must cast leaf-shaped shadows; flag them so the engine routes
-                // them through the alpha-clip cutout depth pass. Foliage cards
-                // (force_alpha_test) and dynamically-lit blended meshes (PAL5 trees)
-                // opt in; opaque/cutout already cast unconditionally.
-                if (force_alpha_test || dynamic_lighting) && blend == BlendMode::AlphaBlend {
-                    let mut p = *md.params();
-                    p.casts_shadow = true;
-                    md = md.with_params(p);
-                }
-                // PAL5 foliage cards are single quads; render them
-                // two-sided so the back-facing half is not culled.
-                if two_sided && matches!(blend, BlendMode::AlphaTest | BlendMode::AlphaBlend) {
-                    md = md.with_cull(CullMode::None);
-                }
-                if let Some(name) = material.userdata_name.as_deref() {
-                    md = md.with_debug_name(name);
-                }
-                if force_unique_materials {
-                    md = md.make_unique();
-                }
-
-                // Stamp per-material fog exemption (skybox etc.). Preserves
-                // every other param already set on the MaterialDef (lightmap
-                // tint/intensity, blend-derived alpha_ref, …).
-                if fog_exempt {
-                    let mut p = *md.params();
-                    p.fog_exempt = true;
-                    md = md.with_params(p);
-                }
-
                 material_to_indices.push((
                     t.material,
                     MaterialGroupedIndices {
-                        material: md,
+                        material: build_material(t.material),
                         indices: vec![],
                     },
                 ));
@@ -1083,9 +1228,9 @@ pub(crate) fn create_geometry_internal(
         group.indices.push(t.index[2] as u32);
     }
 
-    let r_geometries = material_to_indices
+    material_to_indices
         .into_iter()
-        .map(|(_, v)| {
+        .map(|(slot, v)| {
             // Per-material vertex layout: a material's shader expects a
             // specific subset of vertex components, and the buffer
             // stride is derived from the texcoord-set count passed to
@@ -1122,60 +1267,125 @@ pub(crate) fn create_geometry_internal(
             } else {
                 &single
             };
-            radiance::components::mesh::Geometry::new(
-                &r_vertices,
-                r_normals,
-                geom_texcoords,
-                v.indices,
-                v.material,
+            (
+                slot,
+                radiance::components::mesh::Geometry::new(
+                    &r_vertices,
+                    r_normals,
+                    geom_texcoords,
+                    v.indices,
+                    v.material,
+                ),
             )
         })
-        .collect();
+        .collect()
+}
 
-    match skin_info {
-        None => {
-            let mesh_component =
-                StaticMeshComponent::new(entity.clone(), r_geometries, component_factory.clone());
-            entity.add_component(
-                IStaticMeshComponent::uuid(),
-                crosscom::ComRc::from_object(mesh_component),
-            );
-        }
-        Some(skin_info) => {
-            let bone_id: Vec<[usize; 4]> = skin_info
-                .v_bone_indices
-                .iter()
-                .map(|id| {
-                    [
-                        id[0] as usize,
-                        id[1] as usize,
-                        id[2] as usize,
-                        id[3] as usize,
-                    ]
-                })
-                .collect();
+/// One material group of a cached geometry: material slot, vertex layout,
+/// raw vertex blob and triangle indices.
+struct CachedGroup {
+    slot: u16,
+    components: u32,
+    vertices: Vec<u8>,
+    indices: Vec<u32>,
+}
 
-            for r_geometry in r_geometries {
-                let child = CoreEntity::create(format!("{}_geom", entity.name()), true);
+fn encode_geometries(geometries: &[(u16, radiance::components::mesh::Geometry)]) -> Vec<u8> {
+    let mut out = vec![];
+    out.extend_from_slice(&(geometries.len() as u32).to_le_bytes());
+    for (slot, geometry) in geometries {
+        let data = geometry.vertices.data();
+        out.extend_from_slice(&slot.to_le_bytes());
+        out.extend_from_slice(&geometry.vertices.components().bits().to_le_bytes());
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(data);
+        out.extend_from_slice(&(geometry.indices.len() as u32).to_le_bytes());
+        for index in &geometry.indices {
+            out.extend_from_slice(&index.to_le_bytes());
+        }
+    }
+    out
+}
 
-                let mesh_component = SkinnedMeshComponent::new(
-                    child.clone(),
-                    component_factory.clone(),
-                    r_geometry,
-                    skin_info.armature.clone(),
-                    bone_id.clone(),
-                    skin_info.v_weights.clone(),
-                );
+fn decode_geometries(mut payload: &[u8]) -> Option<Vec<CachedGroup>> {
+    fn take<'a>(payload: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+        if payload.len() < len {
+            return None;
+        }
+        let (head, tail) = payload.split_at(len);
+        *payload = tail;
+        Some(head)
+    }
+    fn take_u32(payload: &mut &[u8]) -> Option<u32> {
+        take(payload, 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
 
-                child.add_component(
-                    ISkinnedMeshComponent::uuid(),
-                    ComRc::from_object(mesh_component),
-                );
+    let count = take_u32(&mut payload)? as usize;
+    let mut groups = vec![];
+    for _ in 0..count {
+        let slot = take(&mut payload, 2).map(|b| u16::from_le_bytes([b[0], b[1]]))?;
+        let components = take_u32(&mut payload)?;
+        let len = take_u32(&mut payload)? as usize;
+        let vertices = take(&mut payload, len)?.to_vec();
+        let index_count = take_u32(&mut payload)? as usize;
+        let indices = take(&mut payload, index_count.checked_mul(4)?)?
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        groups.push(CachedGroup {
+            slot,
+            components,
+            vertices,
+            indices,
+        });
+    }
+    payload.is_empty().then_some(groups)
+}
 
-                entity.attach(child);
-            }
+/// Rebuild the geometry from a cached entry, re-resolving each group's
+/// material live. Returns `None` (falling back to a rebuild) when the
+/// entry is missing, malformed, or its vertex layout no longer matches
+/// what the group's material would be built with today.
+fn cached_geometries(
+    key: &CacheKey,
+    vertex_count: usize,
+    has_normals: bool,
+    texcoord_set_count: usize,
+    build_material: &dyn Fn(u16) -> MaterialDef,
+) -> Option<Vec<radiance::components::mesh::Geometry>> {
+    let payload = cache::global()?.get(DFF_GEOMETRY_KIND, key)?;
+    let groups = decode_geometries(&payload)?;
+    let mut geometries = vec![];
+    for group in groups {
+        let material = build_material(group.slot);
+        let mut expected = VertexComponents::POSITION;
+        if has_normals {
+            expected |= VertexComponents::NORMAL;
+        }
+        if matches!(
+            material.program(),
+            radiance::rendering::ShaderProgram::TexturedLightmap
+        ) {
+            expected |= VertexComponents::TEXCOORD | VertexComponents::TEXCOORD2;
+        } else if texcoord_set_count > 0 {
+            expected |= VertexComponents::TEXCOORD;
+        }
+        if group.components != expected.bits() {
+            return None;
+        }
+        let vertices = VertexBuffer::try_new_with_data_blob(expected, group.vertices)?;
+        if vertices.count() != vertex_count
+            || group.indices.iter().any(|&i| i as usize >= vertex_count)
+        {
+            return None;
         }
+        geometries.push(radiance::components::mesh::Geometry {
+            material,
+            vertices,
+            indices: group.indices,
+        });
     }
+    Some(geometries)
 }
 
 fn create_matrix(frame: &Frame) -> Mat44 {
@@ -1452,30 +1662,6 @@ fn load_bsp_material_texture_scoped(
     )
 }
 
-/// Build a cross-backend `SamplerDef` from the raw RenderWare
-/// `Texture::filter_mode / address_mode_u / address_mode_v` fields
-/// parsed in `fileformats/src/rwbs/material.rs`.
-///
-/// Filter mode mapping (RW values; see RW SDK `RwTextureFilterMode`):
-///   0 = NAFILTERMODE              -> Linear (today's default)
-///   1 = NEAREST                   -> Nearest
-///   2 = LINEAR                    -> Linear
-///   3 = MIPNEAREST                -> Nearest (mip levels not generated)
-///   4 = MIPLINEAR                 -> Linear  (mip levels not generated)
-///   5 = LINEARMIPNEAREST          -> Linear
-///   6 = LINEARMIPLINEAR           -> Linear
-///
-/// Address mode mapping (RW `RwTextureAddressMode`):
-///   0 = NATEXTUREADDRESS          -> Repeat (today's default)
-///   1 = WRAP                      -> Repeat
-///   2 = MIRROR                    -> Mirror
-///   3 = CLAMP                     -> Clamp
-///   4 = BORDER                    -> Border
-///
-/// Mip-map generation is intentionally skipped for now; once
-/// `VulkanTexture` learns to build mip chains the `mipmap_mode` carried
-/// on `SamplerDef` already takes effect (see
-/// `radiance/.../vulkan/sampler.rs`).
 /// Build a PAL4 BSP lightmap material: textures = `[lightmap,
 /// diffuse]`, shader = `TexturedLightmap`, sampler taken from the BSP
 /// material's RW Texture metadata, `MaterialParams.tint` stamped with
@@ -1646,12 +1832,35 @@ fn load_lightmap_only_material(
     md.with_params(params)
 }
 
+/// Build a cross-backend `SamplerDef` from the raw RenderWare
+/// `Texture::filter_mode / address_mode_u / address_mode_v` fields
+/// parsed in `fileformats/src/rwbs/material.rs`.
+///
+/// Filter mode mapping (RW values; see RW SDK `RwTextureFilterMode`):
+///   0 = NAFILTERMODE              -> Linear, mip Linear (today's default)
+///   1 = NEAREST                   -> Nearest, mip Linear
+///   2 = LINEAR                    -> Linear, mip Linear
+///   3 = MIPNEAREST                -> Nearest, mip Nearest
+///   4 = MIPLINEAR                 -> Linear, mip Linear
+///   5 = LINEARMIPNEAREST          -> Linear, mip Nearest
+///   6 = LINEARMIPLINEAR           -> Linear, mip Linear (trilinear)
+///
+/// Address mode mapping (RW `RwTextureAddressMode`):
+///   0 = NATEXTUREADDRESS          -> Repeat (today's default)
+///   1 = WRAP                      -> Repeat
+///   2 = MIRROR                    -> Mirror
+///   3 = CLAMP                     -> Clamp
+///   4 = BORDER                    -> Border
+///
+/// Every texture carries a full mip chain (generated on upload or
+/// supplied by the `.dds`), so the mip mode always takes effect.
 fn rw_sampler_def(texture: &fileformats::rwbs::material::Texture) -> SamplerDef {
     SamplerDef::with_address_uv(
         rw_filter_mode(texture.filter_mode),
         rw_address_mode(texture.address_mode_u),
         rw_address_mode(texture.address_mode_v),
     )
+    .with_mipmap_mode(rw_mipmap_mode(texture.filter_mode))
 }
 
 fn rw_filter_mode(mode: u32) -> FilterMode {
@@ -1662,6 +1871,13 @@ fn rw_filter_mode(mode: u32) -> FilterMode {
     }
 }
 
+fn rw_mipmap_mode(mode: u32) -> MipmapMode {
+    match mode {
+        3 | 5 => MipmapMode::Nearest,
+        _ => MipmapMode::Linear,
+    }
+}
+
 fn rw_address_mode(mode: u32) -> AddressMode {
     match mode {
         1 => AddressMode::Repeat,
@@ -1679,10 +1895,7 @@ fn decode_texture(
     texture_resolver: &dyn TextureResolver,
 ) -> Option<image::RgbaImage> {
     let data = texture_resolver.resolve_texture(vfs, model_path, name)?;
-    image::load_from_memory(&data)
-        .or_else(|_| image::load_from_memory_with_format(&data, image::ImageFormat::Tga))
-        .ok()
-        .map(|img| img.to_rgba8())
+    radiance::rendering::decode_texture(&data)
 }
 
 /// Composite a RenderWare mask texture onto `main`'s alpha channel.
@@ -1946,3 +2159,70 @@ mod foliage_tag_tests {
         assert_eq!(parse_prt_texture_id("[w]{t}"), None);
     }
 }
+
+#[cfg(test)]
+mod geometry_cache_tests {
+    use super::*;
+    use radiance::components::mesh::{Geometry, TexCoord as MeshTexCoord};
+
+    fn quad() -> (u16, Geometry) {
+        let vertices = [
+            Vec3::new(0., 0., 0.),
+            Vec3::new(1., 0., 0.),
+            Vec3::new(1., 1., 0.),
+            Vec3::new(0., 1., 0.),
+        ];
+        let texcoords = vec![
+            vertices
+                .iter()
+                .map(|v| MeshTexCoord::new(v.x, v.y))
+                .collect(),
+        ];
+        let material = radiance::rendering::SimpleMaterialDef::create2("missing", None);
+        let geometry = Geometry::new(
+            &vertices,
+            None,
+            &texcoords,
+            vec![0, 1, 2, 0, 2, 3],
+            material,
+        );
+        (3, geometry)
+    }
+
+    #[test]
+    fn geometry_payload_round_trips() {
+        let geometries = vec![quad()];
+        let payload = encode_geometries(&geometries);
+        let groups = decode_geometries(&payload).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].slot, 3);
+        assert_eq!(
+            groups[0].components,
+            (VertexComponents::POSITION | VertexComponents::TEXCOORD).bits()
+        );
+        assert_eq!(groups[0].vertices, geometries[0].1.vertices.data());
+        assert_eq!(groups[0].indices, vec![0, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn truncated_or_padded_payload_is_rejected() {
+        let payload = encode_geometries(&[quad()]);
+        assert!(decode_geometries(&payload[..payload.len() - 1]).is_none());
+        assert!(decode_geometries(&payload[..3]).is_none());
+        let mut padded = payload.clone();
+        padded.push(0);
+        assert!(decode_geometries(&padded).is_none());
+    }
+
+    #[test]
+    fn derived_keys_differ_per_part() {
+        let source = dff_cache_key(b"dff bytes");
+        let a = derive_cache_key(&source, &[&0u32.to_le_bytes()]);
+        let b = derive_cache_key(&source, &[&1u32.to_le_bytes()]);
+        assert_ne!(a.to_bytes(), b.to_bytes());
+        assert_eq!(
+            a.to_bytes(),
+            derive_cache_key(&source, &[&0u32.to_le_bytes()]).to_bytes()
+        );
+    }
+}
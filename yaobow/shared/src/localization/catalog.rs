@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// One translatable string. `source` is the text exactly as the game
+/// script hands it to the dialog layer (after codec decoding, before
+/// any markup processing), and doubles as the lookup key at runtime.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct CatalogEntry {
+    pub source: String,
+
+    /// Replacement text. Empty means "not translated yet"; the runtime
+    /// then falls back to the source (optionally script-converted).
+    #[serde(default)]
+    pub translation: String,
+
+    /// Where the string was found, e.g. `q01:q01_1001@0x2c` for an SCE
+    /// proc or `M01#strings[12]` for a CSB module. Informational only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+}
+
+/// A per-game string catalog, keyed by source text (gettext-style
+/// msgids). Serialized as JSON or as a gettext PO file; both formats
+/// carry the same data so a translator can round-trip either.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Catalog {
+    /// Config key of the game the catalog belongs to (`pal3`, `swd5`…).
+    #[serde(default)]
+    pub game: String,
+
+    /// Free-form target language tag, e.g. `en` or `zh-Hant`.
+    #[serde(default)]
+    pub language: String,
+
+    #[serde(default)]
+    pub entries: Vec<CatalogEntry>,
+
+    #[serde(skip)]
+    index: HashMap<String, usize>,
+}
+
+impl Catalog {
+    pub fn new(game: &str, language: &str) -> Self {
+        Self {
+            game: game.to_string(),
+            language: language.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Record `source` as seen at `reference`. Repeated sources keep a
+    /// single entry and accumulate references.
+    pub fn add(&mut self, source: &str, reference: &str) {
+        if source.is_empty() {
+            return;
+        }
+
+        let index = match self.index.get(source) {
+            Some(index) => *index,
+            None => {
+                self.entries.push(CatalogEntry {
+                    source: source.to_string(),
+                    ..Default::default()
+                });
+                let index = self.entries.len() - 1;
+                self.index.insert(source.to_string(), index);
+                index
+            }
+        };
+
+        let references = &mut self.entries[index].references;
+        if !reference.is_empty() && !references.iter().any(|r| r == reference) {
+            references.push(reference.to_string());
+        }
+    }
+
+    pub fn get(&self, source: &str) -> Option<&CatalogEntry> {
+        self.index.get(source).map(|index| &self.entries[*index])
+    }
+
+    /// Non-empty translation for `source`, if any.
+    pub fn translation(&self, source: &str) -> Option<&str> {
+        self.get(source)
+            .map(|e| e.translation.as_str())
+            .filter(|t| !t.is_empty())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of entries carrying a translation.
+    pub fn translated_len(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| !e.translation.is_empty())
+            .count()
+    }
+
+    /// Carry translations from `previous` into this (freshly extracted)
+    /// catalog. Entries whose source no longer exists are dropped, so
+    /// re-extracting after a script change never resurrects stale lines.
+    pub fn merge_translations(&mut self, previous: &Catalog) {
+        for entry in &mut self.entries {
+            if entry.translation.is_empty() {
+                if let Some(t) = previous.translation(&entry.source) {
+                    entry.translation = t.to_string();
+                }
+            }
+        }
+
+        if self.language.is_empty() {
+            self.language = previous.language.clone();
+        }
+    }
+
+    /// Load a catalog, picking the format from the file extension
+    /// (`.po` for gettext, anything else as JSON).
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        if is_po(path) {
+            Self::from_po(&text)
+        } else {
+            Self::from_json(&text)
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let text = if is_po(path) {
+            self.to_po()
+        } else {
+            self.to_json()?
+        };
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    pub fn from_json(text: &str) -> anyhow::Result<Self> {
+        let mut catalog: Catalog = serde_json::from_str(text)?;
+        catalog.reindex();
+        Ok(catalog)
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn to_po(&self) -> String {
+        let mut out = String::new();
+        out.push_str("msgid \"\"\nmsgstr \"\"\n");
+        out.push_str(&format!("\"Language: {}\\n\"\n", po_escape(&self.language)));
+        out.push_str("\"Content-Type: text/plain; charset=UTF-8\\n\"\n");
+        out.push_str(&format!(
+            "\"X-Yaobow-Game: {}\\n\"\n",
+            po_escape(&self.game)
+        ));
+
+        for entry in &self.entries {
+            out.push('\n');
+            for reference in &entry.references {
+                out.push_str(&format!("#: {}\n", reference));
+            }
+            out.push_str(&format!("msgid \"{}\"\n", po_escape(&entry.source)));
+            out.push_str(&format!("msgstr \"{}\"\n", po_escape(&entry.translation)));
+        }
+
+        out
+    }
+
+    /// Parse the subset of gettext PO that [`Self::to_po`] emits:
+    /// `#:` references, `msgid`/`msgstr` with continuation lines, and
+    /// the header entry. Other comments and `msgctxt` are ignored.
+    pub fn from_po(text: &str) -> anyhow::Result<Self> {
+        #[derive(PartialEq)]
+        enum Field {
+            None,
+            Id,
+            Str,
+        }
+
+        let mut catalog = Catalog::default();
+        let mut references = vec![];
+        let mut msgid: Option<String> = None;
+        let mut msgstr = String::new();
+        let mut field = Field::None;
+
+        let flush = |catalog: &mut Catalog,
+                     references: &mut Vec<String>,
+                     msgid: &mut Option<String>,
+                     msgstr: &mut String| {
+            if let Some(id) = msgid.take() {
+                if id.is_empty() {
+                    for line in msgstr.lines() {
+                        if let Some((key, value)) = line.split_once(':') {
+                            match key.trim() {
+                                "Language" => catalog.language = value.trim().to_string(),
+                                "X-Yaobow-Game" => catalog.game = value.trim().to_string(),
+                                _ => {}
+                            }
+                        }
+                    }
+                } else {
+                    catalog.entries.push(CatalogEntry {
+                        source: id,
+                        translation: std::mem::take(msgstr),
+                        references: std::mem::take(references),
+                    });
+                }
+            }
+            references.clear();
+            msgstr.clear();
+        };
+
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if let Some(reference) = line.strip_prefix("#:") {
+                if msgid.is_some() {
+                    flush(&mut catalog, &mut references, &mut msgid, &mut msgstr);
+                }
+                references.push(reference.trim().to_string());
+            } else if line.is_empty() || line.starts_with('#') {
+                continue;
+            } else if let Some(rest) = line.strip_prefix("msgid ") {
+                if msgid.is_some() {
+                    flush(&mut catalog, &mut references, &mut msgid, &mut msgstr);
+                }
+                msgid = Some(po_unquote(rest, line_no)?);
+                field = Field::Id;
+            } else if let Some(rest) = line.strip_prefix("msgstr ") {
+                msgstr = po_unquote(rest, line_no)?;
+                field = Field::Str;
+            } else if line.starts_with('"') {
+                let part = po_unquote(line, line_no)?;
+                match field {
+                    Field::Id => msgid.get_or_insert_with(String::new).push_str(&part),
+                    Field::Str => msgstr.push_str(&part),
+                    Field::None => anyhow::bail!("line {}: stray string", line_no + 1),
+                }
+            } else if line.starts_with("msgctxt ") {
+                field = Field::None;
+            } else {
+                anyhow::bail!("line {}: unrecognized PO syntax", line_no + 1);
+            }
+        }
+        flush(&mut catalog, &mut references, &mut msgid, &mut msgstr);
+
+        catalog.reindex();
+        Ok(catalog)
+    }
+
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (i, entry) in self.entries.iter().enumerate() {
+            self.index.entry(entry.source.clone()).or_insert(i);
+        }
+    }
+}
+
+fn is_po(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.eq_ignore_ascii_case("po"))
+        .unwrap_or(false)
+}
+
+fn po_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn po_unquote(quoted: &str, line_no: usize) -> anyhow::Result<String> {
+    let inner = quoted
+        .trim()
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .ok_or_else(|| anyhow::anyhow!("line {}: expected a quoted string", line_no + 1))?;
+
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some(c) => out.push(c),
+            None => anyhow::bail!("line {}: dangling escape", line_no + 1),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn po_round_trip_keeps_entries_and_header() {
+        let mut catalog = Catalog::new("pal3", "en");
+        catalog.add("景天：\\n你好", "q01:q01_1@0x0");
+        catalog.add("景天：\\n你好", "q01:q01_2@0x10");
+        catalog.add("\"引号\"", "");
+        catalog.entries[0].translation = "Jingtian:\\nHello".to_string();
+
+        let parsed = Catalog::from_po(&catalog.to_po()).unwrap();
+        assert_eq!(parsed.game, "pal3");
+        assert_eq!(parsed.language, "en");
+        assert_eq!(parsed.entries, catalog.entries);
+        assert_eq!(
+            parsed.translation("景天：\\n你好"),
+            Some("Jingtian:\\nHello")
+        );
+        assert_eq!(parsed.translation("\"引号\""), None);
+    }
+
+    #[test]
+    fn merge_carries_translations_for_surviving_sources() {
+        let mut old = Catalog::new("pal4", "en");
+        old.add("甲", "");
+        old.add("乙", "");
+        old.entries[0].translation = "A".to_string();
+        old.entries[1].translation = "B".to_string();
+        let old = Catalog::from_json(&old.to_json().unwrap()).unwrap();
+
+        let mut fresh = Catalog::new("pal4", "");
+        fresh.add("甲", "M01#strings[0]");
+        fresh.add("丙", "M01#strings[1]");
+        fresh.merge_translations(&old);
+
+        assert_eq!(fresh.language, "en");
+        assert_eq!(fresh.translation("甲"), Some("A"));
+        assert_eq!(fresh.translation("丙"), None);
+        assert_eq!(fresh.len(), 2);
+        assert_eq!(fresh.translated_len(), 1);
+    }
+}
@@ -0,0 +1,444 @@
+//! Offline dialog-string extraction. Each extractor returns the strings
+//! exactly as the runtime hands them to the dialog layer, so the
+//! resulting catalog keys match what [`super::localize`] sees.
+
+use encoding::{DecoderTrap, EncodingRef};
+
+use super::Catalog;
+use crate::openpal3::loaders::sce_loader::SceFile;
+use crate::scripting::angelscript::ScriptModule;
+use crate::scripting::sce::disassembler::disasm;
+
+/// SCE instructions whose string operands reach the player: dialog
+/// lines, choice lists, timed choices and captions.
+const SCE_DIALOG_OPCODES: &[&str] = &["Dlg", "DlgSel", "DlgTime", "Caption"];
+
+/// PAL5 Lua functions whose string arguments are dialog text.
+pub const PAL5_DIALOG_FUNCTIONS: &[&str] = &["ui.Dialog", "ui.Message"];
+
+/// SWD5-family Lua functions whose string arguments are dialog text
+/// (`talkmsg` also carries the speaker name).
+pub const SWD5_DIALOG_FUNCTIONS: &[&str] = &["storymsg", "storymsgpos", "talkmsg"];
+
+/// Add every dialog string of a PAL3 `.sce` to `catalog`. References
+/// are `<sce_name>:<proc name>@<addr>`. A proc the static walker can't
+/// decode is skipped and reported through the log.
+pub fn extract_sce(catalog: &mut Catalog, sce: &SceFile, sce_name: &str) {
+    for header in &sce.proc_headers {
+        let Some(proc) = sce.procs.get(&header.id) else {
+            continue;
+        };
+
+        let insts = match disasm(&proc.inst) {
+            Ok(insts) => insts,
+            Err(e) => {
+                log::warn!("{}:{}: {:#}", sce_name, proc.name, e);
+                continue;
+            }
+        };
+
+        for inst in insts
+            .iter()
+            .filter(|i| SCE_DIALOG_OPCODES.contains(&i.name))
+        {
+            let reference = format!("{}:{}@0x{:x}", sce_name, proc.name, inst.addr);
+            for text in inst.strings() {
+                catalog.add(text, &reference);
+            }
+        }
+    }
+}
+
+/// Add the dialog strings of a PAL4 `.csb` module to `catalog`. CSB
+/// string tables mix dialog with asset names and identifiers; only the
+/// entries containing non-ASCII text are taken. References are
+/// `<module_name>#strings[<index>]`.
+pub fn extract_csb(catalog: &mut Catalog, module: &ScriptModule, module_name: &str) {
+    for (i, text) in module.strings.iter().enumerate() {
+        if text.is_ascii() {
+            continue;
+        }
+
+        catalog.add(text, &format!("{}#strings[{}]", module_name, i));
+    }
+}
+
+/// Add the string-literal arguments of calls to `functions` in a Lua
+/// source chunk. Scanning is byte-wise and unescapes literals the way
+/// the Lua lexer does, so a GBK/Big5 trail byte of `0x5C` behaves the
+/// same as at runtime; the unescaped bytes are then decoded with
+/// `encoding`. References are `<chunk_name>:<line>`.
+///
+/// Precompiled chunks (starting with `ESC "Lua"`) carry no call sites;
+/// for those every non-ASCII string constant is taken instead.
+pub fn extract_lua(
+    catalog: &mut Catalog,
+    src: &[u8],
+    chunk_name: &str,
+    functions: &[&str],
+    encoding: EncodingRef,
+) {
+    if src.starts_with(b"\x1bLua") {
+        for text in lua_binary_strings(src) {
+            let text = decode(&text, encoding);
+            if !text.is_ascii() {
+                catalog.add(&text, chunk_name);
+            }
+        }
+        return;
+    }
+
+    for (line, literal) in LuaScanner::new(src).dialog_literals(functions) {
+        catalog.add(
+            &decode(&literal, encoding),
+            &format!("{}:{}", chunk_name, line),
+        );
+    }
+}
+
+fn decode(bytes: &[u8], encoding: EncodingRef) -> String {
+    encoding
+        .decode(bytes, DecoderTrap::Replace)
+        .unwrap_or_else(|_| String::from_utf8_lossy(bytes).into_owned())
+}
+
+struct LuaScanner<'a> {
+    src: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> LuaScanner<'a> {
+    fn new(src: &'a [u8]) -> Self {
+        Self {
+            src,
+            pos: 0,
+            line: 1,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<u8> {
+        self.src.get(self.pos + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == b'\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    /// Skip whitespace and comments.
+    fn skip_trivia(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_ascii_whitespace() {
+                self.bump();
+            } else if c == b'-' && self.peek_at(1) == Some(b'-') {
+                self.pos += 2;
+                if self.long_bracket_level().is_some() {
+                    let _ = self.long_string();
+                } else {
+                    while let Some(c) = self.peek() {
+                        if c == b'\n' {
+                            break;
+                        }
+                        self.bump();
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Level of a `[[` / `[==[` opener at the cursor, if any.
+    fn long_bracket_level(&self) -> Option<usize> {
+        if self.peek() != Some(b'[') {
+            return None;
+        }
+        let mut level = 0;
+        while self.peek_at(1 + level) == Some(b'=') {
+            level += 1;
+        }
+        (self.peek_at(1 + level) == Some(b'[')).then_some(level)
+    }
+
+    fn long_string(&mut self) -> Option<Vec<u8>> {
+        let level = self.long_bracket_level()?;
+        self.pos += level + 2;
+        // A newline right after the opener is skipped, as in Lua.
+        if self.peek() == Some(b'\n') {
+            self.bump();
+        }
+
+        let mut out = vec![];
+        while let Some(c) = self.bump() {
+            if c == b']' {
+                let closes = (0..level).all(|i| self.peek_at(i) == Some(b'='))
+                    && self.peek_at(level) == Some(b']');
+                if closes {
+                    self.pos += level + 1;
+                    return Some(out);
+                }
+            }
+            out.push(c);
+        }
+        Some(out)
+    }
+
+    fn quoted_string(&mut self) -> Vec<u8> {
+        let quote = self.bump().unwrap();
+        let mut out = vec![];
+        while let Some(c) = self.bump() {
+            match c {
+                c if c == quote => break,
+                b'\n' => break,
+                b'\\' => match self.bump() {
+                    Some(b'n') => out.push(b'\n'),
+                    Some(b't') => out.push(b'\t'),
+                    Some(b'r') => out.push(b'\r'),
+                    Some(b'a') => out.push(0x07),
+                    Some(b'b') => out.push(0x08),
+                    Some(b'f') => out.push(0x0c),
+                    Some(b'v') => out.push(0x0b),
+                    Some(d) if d.is_ascii_digit() => {
+                        let mut value = (d - b'0') as u32;
+                        for _ in 0..2 {
+                            match self.peek() {
+                                Some(d) if d.is_ascii_digit() => {
+                                    value = value * 10 + (d - b'0') as u32;
+                                    self.bump();
+                                }
+                                _ => break,
+                            }
+                        }
+                        out.push(value as u8);
+                    }
+                    Some(c) => out.push(c),
+                    None => break,
+                },
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    fn string_literal(&mut self) -> Option<Vec<u8>> {
+        match self.peek()? {
+            b'"' | b'\'' => Some(self.quoted_string()),
+            b'[' => self.long_string(),
+            _ => None,
+        }
+    }
+
+    /// Dotted/colon-separated name at the cursor, normalized to dots.
+    fn name(&mut self) -> String {
+        let mut out = String::new();
+        loop {
+            while let Some(c) = self.peek() {
+                if c.is_ascii_alphanumeric() || c == b'_' {
+                    out.push(c as char);
+                    self.bump();
+                } else {
+                    break;
+                }
+            }
+
+            match (self.peek(), self.peek_at(1)) {
+                (Some(b'.' | b':'), Some(c)) if c.is_ascii_alphabetic() || c == b'_' => {
+                    out.push('.');
+                    self.bump();
+                }
+                _ => return out,
+            }
+        }
+    }
+
+    /// Skip one argument expression up to the next top-level `,` or the
+    /// closing `)`, collecting nothing.
+    fn skip_expression(&mut self) {
+        let mut depth = 0usize;
+        loop {
+            self.skip_trivia();
+            match self.peek() {
+                None => return,
+                Some(b'"' | b'\'') => {
+                    self.quoted_string();
+                }
+                Some(b'[') if self.long_bracket_level().is_some() => {
+                    self.long_string();
+                }
+                Some(b'(' | b'{' | b'[') => {
+                    depth += 1;
+                    self.bump();
+                }
+                Some(b')' | b'}' | b']') => {
+                    if depth == 0 {
+                        return;
+                    }
+                    depth -= 1;
+                    self.bump();
+                }
+                Some(b',') if depth == 0 => return,
+                Some(_) => {
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    /// `(line, bytes)` for every string-literal argument of a call to
+    /// one of `functions`.
+    fn dialog_literals(&mut self, functions: &[&str]) -> Vec<(usize, Vec<u8>)> {
+        let mut ret = vec![];
+        loop {
+            self.skip_trivia();
+            let Some(c) = self.peek() else {
+                break;
+            };
+
+            if c == b'"' || c == b'\'' || self.long_bracket_level().is_some() {
+                self.string_literal();
+                continue;
+            }
+
+            if !(c.is_ascii_alphabetic() || c == b'_') {
+                self.bump();
+                continue;
+            }
+
+            let name = self.name();
+            self.skip_trivia();
+            if self.peek() != Some(b'(') || !functions.contains(&name.as_str()) {
+                continue;
+            }
+
+            self.bump();
+            loop {
+                self.skip_trivia();
+                let line = self.line;
+                let is_literal = matches!(self.peek(), Some(b'"' | b'\''))
+                    || self.long_bracket_level().is_some();
+                if is_literal {
+                    let literal = self.string_literal().unwrap_or_default();
+                    self.skip_trivia();
+                    // Only a bare literal argument is a fixed dialog
+                    // string; `"a" .. x` is built at runtime.
+                    if matches!(self.peek(), Some(b',' | b')')) {
+                        ret.push((line, literal));
+                    } else {
+                        self.skip_expression();
+                    }
+                } else {
+                    self.skip_expression();
+                }
+
+                match self.bump() {
+                    Some(b',') => continue,
+                    _ => break,
+                }
+            }
+        }
+
+        ret
+    }
+}
+
+/// String constants of a precompiled Lua 5.0 chunk, found by scanning
+/// for the on-disk `u32 size` + bytes + NUL encoding (32-bit little
+/// endian `size_t`, as the SWD5 runtime is built). The header carries
+/// no index of its constants, so this is a heuristic: a candidate must
+/// be NUL-terminated with no interior NUL.
+fn lua_binary_strings(src: &[u8]) -> Vec<Vec<u8>> {
+    let mut ret = vec![];
+    let mut pos = 0;
+    while pos + 4 < src.len() {
+        let size =
+            u32::from_le_bytes([src[pos], src[pos + 1], src[pos + 2], src[pos + 3]]) as usize;
+        let start = pos + 4;
+        let end = start + size;
+        let plausible = size > 1
+            && size < 0x10000
+            && end <= src.len()
+            && src[end - 1] == 0
+            && !src[start..end - 1].contains(&0);
+        if plausible {
+            ret.push(src[start..end - 1].to_vec());
+            pos = end;
+        } else {
+            pos += 1;
+        }
+    }
+
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(src: &[u8], functions: &[&str]) -> Vec<(String, Vec<String>)> {
+        let mut catalog = Catalog::new("test", "");
+        extract_lua(&mut catalog, src, "t", functions, encoding::all::GBK);
+        catalog
+            .entries
+            .into_iter()
+            .map(|e| (e.source, e.references))
+            .collect()
+    }
+
+    #[test]
+    fn lua_literals_of_dialog_calls_are_extracted() {
+        let src = "-- ui.Dialog(\"commented\")\n\
+                   ui.Dialog(\"first\\nline\")\n\
+                   ui:Message( [[long\nstring]] )\n\
+                   talkmsg('name', \"text\" .. x)\n\
+                   other(\"skip\")\n\
+                   ui.Dialog(name, \"second\")\n";
+        let found = extract(src.as_bytes(), &["ui.Dialog", "ui.Message", "talkmsg"]);
+        assert_eq!(
+            found,
+            vec![
+                ("first\nline".to_string(), vec!["t:2".to_string()]),
+                ("long\nstring".to_string(), vec!["t:3".to_string()]),
+                ("name".to_string(), vec!["t:5".to_string()]),
+                ("second".to_string(), vec!["t:7".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn lua_literals_are_decoded_after_unescaping() {
+        // "你好" in GBK, the second byte of 好 written as a decimal escape.
+        let src = b"ui.Dialog(\"\xc4\xe3\xba\\195\")";
+        let found = extract(src, &["ui.Dialog"]);
+        assert_eq!(found[0].0, "你好");
+    }
+
+    #[test]
+    fn precompiled_chunks_yield_non_ascii_constants() {
+        let mut chunk = b"\x1bLua\x50\x01\x04\x04\x04\x08".to_vec();
+        for s in [&b"storymsg"[..], "测试".as_bytes()] {
+            chunk.extend(((s.len() + 1) as u32).to_le_bytes());
+            chunk.extend(s);
+            chunk.push(0);
+        }
+
+        let mut catalog = Catalog::new("swd5", "");
+        extract_lua(
+            &mut catalog,
+            &chunk,
+            "0000.C01",
+            SWD5_DIALOG_FUNCTIONS,
+            encoding::all::UTF_8,
+        );
+        assert_eq!(catalog.len(), 1);
+        assert_eq!(catalog.entries[0].source, "测试");
+    }
+}
@@ -0,0 +1,207 @@
+//! Dialog localization.
+//!
+//! Dialog text is keyed by its source string: [`extract`] walks SCE
+//! procs, CSB string tables and Lua scripts into a per-game [`Catalog`],
+//! translators fill in the `translation` fields (JSON or PO), and at
+//! runtime every dialog entry point passes its decoded text through
+//! [`localize`] before markup parsing. Untranslated text can optionally
+//! be converted between Simplified and Traditional Chinese.
+//!
+//! Language packs are directories holding one catalog per game, named
+//! after the game's config key (`pal3.po`, `swd5.json`, …). The active
+//! pack and script conversion come from `[localization]` in
+//! `yaobow.toml` and are installed by each game service when it builds
+//! its director ([`install_for_game`]).
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameType;
+use crate::config::YaobowConfig;
+
+mod catalog;
+pub mod extract;
+
+pub use catalog::{Catalog, CatalogEntry};
+
+/// Chinese script applied to dialog text that has no translation.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChineseScript {
+    /// Show the text as the game ships it.
+    #[default]
+    Original,
+    Simplified,
+    Traditional,
+}
+
+impl ChineseScript {
+    /// Convert `text` to this script. `Original` borrows the input.
+    pub fn convert<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let variant = match self {
+            ChineseScript::Original => return Cow::Borrowed(text),
+            ChineseScript::Simplified => zhconv::Variant::ZhHans,
+            ChineseScript::Traditional => zhconv::Variant::ZhHant,
+        };
+
+        if text.is_ascii() {
+            return Cow::Borrowed(text);
+        }
+
+        Cow::Owned(zhconv::zhconv(text, variant))
+    }
+}
+
+/// Runtime lookup built from a language pack catalog.
+#[derive(Default)]
+pub struct Localizer {
+    translations: HashMap<String, String>,
+    script: ChineseScript,
+}
+
+impl Localizer {
+    pub fn new(catalog: Option<&Catalog>, script: ChineseScript) -> Self {
+        let translations = catalog
+            .map(|c| {
+                c.entries
+                    .iter()
+                    .filter(|e| !e.translation.is_empty())
+                    .map(|e| (e.source.clone(), e.translation.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            translations,
+            script,
+        }
+    }
+
+    /// Whether this localizer leaves every string unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.translations.is_empty() && self.script == ChineseScript::Original
+    }
+
+    /// Translation of `text` if the pack has one, otherwise `text`
+    /// converted to the configured Chinese script.
+    pub fn localize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self.translations.get(text) {
+            Some(translation) => Cow::Owned(translation.clone()),
+            None => self.script.convert(text),
+        }
+    }
+}
+
+static ACTIVE: RwLock<Option<Arc<Localizer>>> = RwLock::new(None);
+
+/// Replace the process-wide localizer. `None` (or an identity
+/// localizer) restores the shipped text.
+pub fn install(localizer: Option<Localizer>) {
+    let localizer = localizer.filter(|l| !l.is_identity()).map(Arc::new);
+    *ACTIVE.write().unwrap() = localizer;
+}
+
+/// Localize a dialog string through the installed [`Localizer`].
+/// Borrows `text` unchanged when nothing is installed.
+pub fn localize(text: &str) -> Cow<'_, str> {
+    match ACTIVE.read().unwrap().as_ref() {
+        Some(localizer) => Cow::Owned(localizer.localize(text).into_owned()),
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// Localization preferences. Stored under `[localization]` in
+/// `yaobow.toml`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct LocalizationConfig {
+    /// Directory holding per-game catalogs (`<config_key>.po` or
+    /// `<config_key>.json`). Empty disables translation overrides.
+    #[serde(default)]
+    pub language_pack: String,
+
+    /// Script conversion for untranslated text. See [`ChineseScript`].
+    #[serde(default)]
+    pub chinese_script: ChineseScript,
+}
+
+impl LocalizationConfig {
+    /// Catalog file for `game` inside the configured pack, if present.
+    pub fn catalog_path(&self, game: GameType) -> Option<PathBuf> {
+        if self.language_pack.is_empty() {
+            return None;
+        }
+
+        let dir = shellexpand::tilde(&self.language_pack).into_owned();
+        ["po", "json"]
+            .iter()
+            .map(|ext| Path::new(&dir).join(format!("{}.{}", game.config_key(), ext)))
+            .find(|p| p.is_file())
+    }
+}
+
+/// Build and install the localizer for `game` from the persisted
+/// config. A missing or malformed catalog is logged and skipped; the
+/// script conversion still applies.
+pub fn install_for_game(game: GameType) {
+    let config = YaobowConfig::load().localization;
+    let catalog = config
+        .catalog_path(game)
+        .and_then(|path| match Catalog::load(&path) {
+            Ok(catalog) => {
+                log::info!(
+                    "loaded language pack {} ({}/{} translated)",
+                    path.display(),
+                    catalog.translated_len(),
+                    catalog.len()
+                );
+                Some(catalog)
+            }
+            Err(e) => {
+                log::warn!("failed to load language pack {}: {:#}", path.display(), e);
+                None
+            }
+        });
+
+    if catalog.is_none() && !config.language_pack.is_empty() {
+        log::info!(
+            "language pack {} has no catalog for {}",
+            config.language_pack,
+            game.config_key()
+        );
+    }
+
+    install(Some(Localizer::new(
+        catalog.as_ref(),
+        config.chinese_script,
+    )));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translation_wins_over_script_conversion() {
+        let mut catalog = Catalog::new("pal3", "en");
+        catalog.add("你好", "");
+        catalog.entries[0].translation = "Hello".to_string();
+
+        let localizer = Localizer::new(Some(&catalog), ChineseScript::Traditional);
+        assert_eq!(localizer.localize("你好"), "Hello");
+        assert_eq!(localizer.localize("欢迎"), "歡迎");
+        assert_eq!(localizer.localize("abc"), "abc");
+        assert!(!localizer.is_identity());
+        assert!(Localizer::new(None, ChineseScript::Original).is_identity());
+    }
+
+    #[test]
+    fn script_conversion_both_ways() {
+        assert_eq!(ChineseScript::Simplified.convert("軒轅劍"), "轩辕剑");
+        assert_eq!(ChineseScript::Traditional.convert("轩辕剑"), "軒轅劍");
+        assert_eq!(ChineseScript::Original.convert("轩辕剑"), "轩辕剑");
+    }
+}
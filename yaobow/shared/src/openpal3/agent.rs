@@ -9,7 +9,11 @@
 //!   [`GlobalState`](crate::openpal3::states::global_state::GlobalState),
 //!   [`PersistentState`](crate::openpal3::states::persistent_state::PersistentState)).
 //! * Dispatches [`AgentCommand`]s to the appropriate PAL3 surface
-//!   (synthetic input → bridge, teleport → director, save/load → service).
+//!   (synthetic input → bridge, teleport → director, save/load → service,
+//!   dialog choices → the SCE `DlgSel` buffer).
+//! * Installs PAL4's [`AgentTraceAdapter`] on the SCE VM for
+//!   `/v1/script/trace/*`; see [`crate::scripting::sce::trace`] for how
+//!   SCE events map onto the shared trace format.
 //!
 //! Unsupported endpoints (no clean PAL3 mapping today) return
 //! `AgentResponse::Error { kind: NotImplemented }` so external
@@ -23,9 +27,10 @@
 use std::rc::Rc;
 
 use agent_server::protocol::{
-    AgentCommand, AgentError, AgentResponse, AxisInputParams, DialogSnapshot, KeyAction,
-    KeyInputParams, ScreenshotResponse, ScriptGlobalsParams, ScriptGlobalsResponse, SlotParams,
-    StateSnapshot, StatusMenuParams, StepTimeParams, TeleportParams,
+    AgentCommand, AgentError, AgentResponse, AxisInputParams, DialogChooseParams, DialogSnapshot,
+    KeyAction, KeyInputParams, ScreenshotResponse, ScriptGlobalsParams, ScriptGlobalsResponse,
+    SlotParams, StateSnapshot, StatusMenuParams, StepTimeParams, TeleportParams, TraceDrainParams,
+    TraceDrainResponse, TraceStartParams,
 };
 use crosscom::ComRc;
 use radiance::comdef::ISceneManager;
@@ -34,6 +39,8 @@ use radiance::math::Vec3;
 
 use crate::agent_common::AgentBridge;
 use crate::openpal3::directors::AdventureDirector;
+use crate::openpal4::agent::AgentTraceAdapter;
+use crate::scripting::angelscript::TraceSink;
 
 /// Default size of the dense window returned by `/v1/script/globals`
 /// when the caller omits `limit`. PAL3 globals are written sparsely
@@ -100,9 +107,16 @@ pub fn dispatch_pal3_command(ctx: &Pal3DispatchCtx, command: AgentCommand) -> Ag
             ctx.bridge.input_bridge.borrow().tap(Key::Space);
             AgentResponse::Ok
         }
+        C::ChooseDialog(p) => handle_choose_dialog(ctx, p),
         C::SaveSlot(p) => handle_save_slot(ctx, p),
         C::GetScriptGlobals(p) => handle_get_globals(ctx, p),
         C::SetStatusMenu(p) => handle_set_status_menu(ctx, p),
+        C::TraceStart(p) => handle_trace_start(ctx, p),
+        C::TraceStop => {
+            ctx.bridge.trace_sink.stop();
+            AgentResponse::Ok
+        }
+        C::TraceDrain(p) => handle_trace_drain(ctx.bridge, p),
 
         // --- mode control: routed through the dispatcher in service.rs ----
         // `LoadSlot` (`/v1/load`) is unified with `EnterLoadGame`: PAL3 has
@@ -118,10 +132,6 @@ pub fn dispatch_pal3_command(ctx: &Pal3DispatchCtx, command: AgentCommand) -> Ag
         }
 
         // --- not yet implemented for PAL3 ---------------------------------
-        C::ChooseDialog(_) => AgentResponse::err(AgentError::not_implemented(
-            "PAL3 dialog choice buffering not yet implemented; the SCE dialog system reads the \
-             current selection via SceProcContext::set_dlgsel directly",
-        )),
         C::ChooseWorldMap(_) => AgentResponse::err(AgentError::not_implemented(
             "PAL3 has no world-map prompt analog to PAL4's giShowWorldMap",
         )),
@@ -140,11 +150,6 @@ pub fn dispatch_pal3_command(ctx: &Pal3DispatchCtx, command: AgentCommand) -> Ag
         C::ScriptEval(_) => AgentResponse::err(AgentError::not_implemented(
             "PAL3 script_eval is not supported (no AngelScript VM)",
         )),
-        C::TraceStart(_) | C::TraceStop | C::TraceDrain(_) => {
-            AgentResponse::err(AgentError::not_implemented(
-                "PAL3 SceVm has no trace adapter yet; /v1/script/trace/* unavailable",
-            ))
-        }
 
         // AgentCommand is `#[non_exhaustive]` — surface any future
         // variants as `not_implemented` rather than panicking, so a
@@ -193,12 +198,70 @@ pub fn build_snapshot(ctx: &Pal3DispatchCtx) -> StateSnapshot {
     snap.script_running = script_running;
     snap.current_script_fn = sce_vm.state().context().current_proc_name();
 
-    // PAL3 dialog/movie surfaces aren't readable from outside the
-    // SceVm today; leave them at defaults rather than fabricate data.
-    snap.dialog = DialogSnapshot::default();
+    let dialog = sce_vm.state().dialog();
+    snap.dialog = DialogSnapshot {
+        open: dialog.text.is_some() || !dialog.choices.is_empty(),
+        text: dialog.text.clone().unwrap_or_default(),
+        avatar: match dialog.avatar_side {
+            Some(0) => "left",
+            Some(_) => "right",
+            None => "",
+        }
+        .to_string(),
+        choices: dialog.choices.clone(),
+    };
     snap
 }
 
+/// Install a trace adapter for `bridge` on `director`'s SCE VM. Called
+/// on `/v1/script/trace/start` and whenever a new director picks up a
+/// bridge that is already capturing.
+pub fn install_trace_adapter(bridge: &AgentBridge, director: &AdventureDirector) {
+    let adapter: Rc<dyn TraceSink> = Rc::new(AgentTraceAdapter::new(bridge.trace_sink.clone()));
+    director.sce_vm_mut().set_trace_sink(Some(adapter));
+}
+
+fn handle_choose_dialog(ctx: &Pal3DispatchCtx, params: DialogChooseParams) -> AgentResponse {
+    let Some(director) = ctx.director else {
+        return AgentResponse::err(AgentError::conflict(
+            "no active adventure director — dialog choices need a running playthrough",
+        ));
+    };
+    if params.index < 1 {
+        return AgentResponse::err(AgentError::bad_request(format!(
+            "dialog choice index is 1-based, got {}",
+            params.index
+        )));
+    }
+    // Buffered until the next `DlgSel` reads it, so the choice may be
+    // sent before the options are on screen.
+    director
+        .sce_vm_mut()
+        .state_mut()
+        .buffer_dialog_choice(params.index - 1);
+    AgentResponse::Ok
+}
+
+fn handle_trace_start(ctx: &Pal3DispatchCtx, params: TraceStartParams) -> AgentResponse {
+    ctx.bridge.trace_sink.start(params.reset, params.capacity);
+    if let Some(director) = ctx.director {
+        install_trace_adapter(ctx.bridge, director);
+    }
+    AgentResponse::Ok
+}
+
+fn handle_trace_drain(bridge: &Rc<AgentBridge>, params: TraceDrainParams) -> AgentResponse {
+    let result = bridge
+        .trace_sink
+        .drain(params.after_seq, params.n.unwrap_or(1024));
+    AgentResponse::TraceDrain(TraceDrainResponse {
+        next_seq: result.next_seq,
+        dropped: result.dropped,
+        capturing: bridge.trace_sink.is_capturing(),
+        events: result.events,
+    })
+}
+
 fn handle_key_input(bridge: &Rc<AgentBridge>, params: KeyInputParams) -> AgentResponse {
     let Some(key) = Key::from_name(&params.key) else {
         return AgentResponse::err(AgentError::bad_request(format!(
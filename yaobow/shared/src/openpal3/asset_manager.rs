@@ -1,27 +1,32 @@
 use common::store_ext::StoreExt2;
 use crosscom::ComRc;
 use encoding::{DecoderTrap, types::Encoding};
+use fileformats::pol::{PolFile, read_pol};
 use ini::Ini;
 use mini_fs::MiniFs;
 use mini_fs::prelude::*;
 use radiance::comdef::{IAnimatedMeshComponent, IEntity, IScene};
 use radiance::math::Vec3;
+use radiance::radiance::AssetStreamer;
 use radiance::rendering::ComponentFactory;
 use radiance::scene::{CoreScene, ISceneExt, SceneLight, SceneLighting};
 use radiance::utils::SeekRead;
-use std::io::BufReader;
+use radiance::video::Codec as VideoCodec;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::{io, rc::Rc};
 
 use crate::GameType;
+use crate::video::open_movie;
 
 use super::comdef::IScnSceneComponent;
 use super::loaders::nav_loader::NavFile;
 use super::loaders::nav_loader::nav_load_from_file;
-use super::loaders::pol::create_entity_from_pol_model;
+use super::loaders::pol::{create_entity_from_pol_file, create_entity_from_pol_model};
 use super::loaders::sce_loader::SceFile;
 use super::loaders::sce_loader::sce_load_from_file;
-use super::loaders::scn_loader::scn_load_from_file;
+use super::loaders::scn_loader::{ScnFile, scn_load_from_file};
 use super::scene::ScnScene;
 use super::scene::create_animated_mesh_from_mv3;
 use super::scene::create_entity_from_cvd_model;
@@ -38,6 +43,10 @@ pub struct AssetManager {
     snd_path: PathBuf,
     basedata_path: PathBuf,
     vfs: Rc<MiniFs>,
+    asset_streamer: Rc<AssetStreamer>,
+    /// Scene `.pol`s parsed ahead by [`Self::load_scn`], taken by
+    /// [`Self::load_scn_pol`].
+    prefetched_pols: RefCell<HashMap<PathBuf, PolFile>>,
 }
 
 impl AssetManager {
@@ -64,9 +73,18 @@ impl AssetManager {
             movie_effect_path: PathBuf::from("/movie/movie/2deffect"),
             snd_path: PathBuf::from("/snd"),
             vfs,
+            asset_streamer: Rc::new(AssetStreamer::with_workers(0)),
+            prefetched_pols: RefCell::new(HashMap::new()),
         }
     }
 
+    /// Parse scene models on `asset_streamer`'s workers instead of
+    /// inline on the main thread.
+    pub fn with_asset_streamer(mut self, asset_streamer: Rc<AssetStreamer>) -> Self {
+        self.asset_streamer = asset_streamer;
+        self
+    }
+
     pub fn vfs(&self) -> &MiniFs {
         &self.vfs
     }
@@ -84,6 +102,7 @@ impl AssetManager {
 
         let scn_file = scn_load_from_file(&self.vfs, scene_path, self.game);
         let nav_file = self.load_nav(&scn_file.cpk_name, &scn_file.scn_base_name);
+        self.prefetch_scn_pols(cpk_name, &scn_file);
 
         let scene = CoreScene::create();
         scene.set_lighting(self.load_lgt(cpk_name, &scn_file.scn_base_name, scn_file.is_night));
@@ -296,14 +315,61 @@ impl AssetManager {
             .with_extension("mv3")
     }
 
-    pub fn load_scn_pol(
+    /// Parse the scene's own `.pol`s (the ground and the `_`-prefixed
+    /// nodes `ScnScene` loads) on the asset streamer. Files are read here,
+    /// as the vfs stays on the main thread; a model that fails to parse is
+    /// left for [`Self::load_scn_pol`] to load again and report.
+    fn prefetch_scn_pols(&self, cpk_name: &str, scn_file: &ScnFile) {
+        let ground_pol_name = scn_file.scn_base_name.clone() + ".pol";
+        let node_names = scn_file
+            .nodes
+            .iter()
+            .filter(|node| node.node_type != 37 && node.node_type != 43)
+            .map(|node| &node.name)
+            .filter(|name| name.starts_with('_'));
+        let mut paths: Vec<PathBuf> = std::iter::once(&ground_pol_name)
+            .chain(node_names)
+            .filter_map(|name| {
+                self.scn_pol_path(cpk_name, &scn_file.scn_base_name, name, scn_file.is_night)
+            })
+            .collect();
+        paths.sort();
+        paths.dedup();
+
+        let prefetched = Rc::new(RefCell::new(HashMap::new()));
+        let batch = self.asset_streamer.batch();
+        for path in paths {
+            let data = match self.vfs.read_to_end(&path) {
+                Ok(data) => data,
+                Err(e) => {
+                    log::warn!("Cannot read {:?}: {}", path, e);
+                    continue;
+                }
+            };
+
+            let prefetched = prefetched.clone();
+            batch.submit(
+                move || read_pol(&mut io::Cursor::new(data)),
+                move |pol| match pol {
+                    Ok(pol) => {
+                        prefetched.borrow_mut().insert(path, pol);
+                    }
+                    Err(e) => log::warn!("Cannot parse {:?}: {:#}", path, e),
+                },
+            );
+        }
+
+        batch.finish_blocking();
+        *self.prefetched_pols.borrow_mut() = prefetched.take();
+    }
+
+    fn scn_pol_path(
         &self,
         cpk_name: &str,
         scn_name: &str,
         pol_name: &str,
         is_night: bool,
-        index: u16,
-    ) -> Option<ComRc<IEntity>> {
+    ) -> Option<PathBuf> {
         let folder = self.scene_path.join(cpk_name).join(scn_name);
         let mut paths = vec![];
         if is_night {
@@ -311,20 +377,26 @@ impl AssetManager {
         }
 
         paths.push(folder.join(pol_name).with_extension("pol"));
+        paths.into_iter().find(|path| self.vfs.open(path).is_ok())
+    }
 
-        for path in &paths {
-            if self.vfs.open(path).is_ok() {
-                return Some(create_entity_from_pol_model(
-                    &self.factory,
-                    &self.vfs,
-                    path,
-                    format!("OBJECT_{}", index),
-                    true,
-                ));
+    pub fn load_scn_pol(
+        &self,
+        cpk_name: &str,
+        scn_name: &str,
+        pol_name: &str,
+        is_night: bool,
+        index: u16,
+    ) -> Option<ComRc<IEntity>> {
+        let path = self.scn_pol_path(cpk_name, scn_name, pol_name, is_night)?;
+        let name = format!("OBJECT_{}", index);
+        let prefetched = self.prefetched_pols.borrow_mut().remove(&path);
+        Some(match prefetched {
+            Some(pol) => {
+                create_entity_from_pol_file(&self.factory, &self.vfs, &path, &pol, name, true)
             }
-        }
-
-        None
+            None => create_entity_from_pol_model(&self.factory, &self.vfs, &path, name, true),
+        })
     }
 
     pub fn load_scn_cvd(
@@ -421,20 +493,14 @@ impl AssetManager {
         self.vfs.read_to_end(path).unwrap()
     }
 
-    pub fn load_movie_data(&self, movie_name: &str) -> Box<dyn SeekRead> {
-        let movie = self.movie_path.join(movie_name).with_extension("bik");
-        let end_movie = self.movie_end_path.join(movie_name).with_extension("bik");
-        let effect_movie = self
-            .movie_effect_path
-            .join(movie_name)
-            .with_extension("bik");
-        let file = self.vfs.open(movie).unwrap_or_else(|_| {
-            self.vfs
-                .open(end_movie)
-                .unwrap_or_else(|_| self.vfs.open(effect_movie).unwrap())
-        });
-
-        Box::new(BufReader::new(file))
+    pub fn load_movie_data(&self, movie_name: &str) -> (Box<dyn SeekRead>, VideoCodec) {
+        let movie = self.movie_path.join(movie_name);
+        let end_movie = self.movie_end_path.join(movie_name);
+        let effect_movie = self.movie_effect_path.join(movie_name);
+        open_movie(&self.vfs, movie).unwrap_or_else(|_| {
+            open_movie(&self.vfs, end_movie)
+                .unwrap_or_else(|_| open_movie(&self.vfs, effect_movie).unwrap())
+        })
     }
 
     pub fn load_snd_data(&self, snd_name: &str) -> io::Result<Vec<u8>> {
@@ -89,7 +89,13 @@ impl AdventureDirector {
         dialog_renderer: ComRc<crate::openpal3::comdef::IPal3DialogRenderer>,
         status_renderer: ComRc<crate::openpal3::comdef::IPal3StatusRenderer>,
     ) -> Option<Self> {
-        let p_state = PersistentState::load(app_name, slot);
+        let p_state = match PersistentState::load(app_name, slot) {
+            Ok(p_state) => p_state,
+            Err(e) => {
+                log::error!("Cannot load save {}: {:#}", slot, e);
+                return None;
+            }
+        };
         let scene_name = p_state.scene_name();
         let sub_scene_name = p_state.sub_scene_name();
         if scene_name.is_none() || sub_scene_name.is_none() {
@@ -175,7 +181,13 @@ impl AdventureDirector {
     /// booted (so menus exit and a fresh `AdventureDirector` gets the
     /// bridge before its first tick).
     pub fn set_agent_bridge(&self, bridge: Rc<AgentBridge>) {
-        self.props.borrow_mut().agent_bridge = Some(bridge);
+        let capturing = bridge.trace_sink.is_capturing();
+        self.props.borrow_mut().agent_bridge = Some(bridge.clone());
+        // Keep an in-progress `/v1/script/trace` capture going across
+        // scene changes: the new director's SceVm starts untraced.
+        if capturing {
+            crate::openpal3::agent::install_trace_adapter(&bridge, self);
+        }
     }
 
     /// Currently-installed bridge, if any. Used by `Pal3Service`'s
@@ -352,6 +364,10 @@ impl AdventureDirectorProps {
 
     fn do_update(&mut self, delta_sec: f32) -> Option<ComRc<IDirector>> {
         self.sce_vm.update(delta_sec);
+        self.sce_vm
+            .global_state_mut()
+            .persistent_state_mut()
+            .add_playtime(delta_sec);
 
         // Freeze the whole game world while the full-screen status (状态)
         // menu is open, but only when the player actually has control —
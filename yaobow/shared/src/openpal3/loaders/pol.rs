@@ -1,5 +1,5 @@
 use crosscom::ComRc;
-use fileformats::pol::{PolMaterialInfo, PolTriangle, PolVertex, read_pol};
+use fileformats::pol::{PolFile, PolMaterialInfo, PolTriangle, PolVertex, read_pol};
 use mini_fs::{MiniFs, StoreExt};
 use radiance::comdef::{IEntity, IStaticMeshComponent};
 use radiance::components::mesh::{Geometry, StaticMeshComponent, TexCoord};
@@ -20,9 +20,24 @@ pub fn create_entity_from_pol_model<P: AsRef<Path>>(
     path: P,
     name: String,
     visible: bool,
+) -> ComRc<IEntity> {
+    let mut reader = BufReader::new(vfs.open(&path).unwrap());
+    let pol = read_pol(&mut reader).unwrap();
+    create_entity_from_pol_file(component_factory, vfs, path, &pol, name, visible)
+}
+
+/// Build the entity of an already parsed `.pol`; `path` is where it was
+/// read from and only locates its textures.
+pub fn create_entity_from_pol_file<P: AsRef<Path>>(
+    component_factory: &Rc<dyn ComponentFactory>,
+    vfs: &MiniFs,
+    path: P,
+    pol: &PolFile,
+    name: String,
+    visible: bool,
 ) -> ComRc<IEntity> {
     let entity = CoreEntity::create(name, visible);
-    let geometries = load_pol_model(vfs, path);
+    let geometries = load_pol_model(vfs, path, pol);
     let mesh_component =
         StaticMeshComponent::new(entity.clone(), geometries, component_factory.clone());
     entity.add_component(
@@ -32,9 +47,7 @@ pub fn create_entity_from_pol_model<P: AsRef<Path>>(
     entity
 }
 
-fn load_pol_model<P: AsRef<Path>>(vfs: &MiniFs, path: P) -> Vec<Geometry> {
-    let mut reader = BufReader::new(vfs.open(&path).unwrap());
-    let pol = read_pol(&mut reader).unwrap();
+fn load_pol_model<P: AsRef<Path>>(vfs: &MiniFs, path: P, pol: &PolFile) -> Vec<Geometry> {
     let mut geometries = vec![];
     for mesh in &pol.meshes {
         for material in &mesh.material_info {
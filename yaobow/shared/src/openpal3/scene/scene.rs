@@ -71,6 +71,19 @@ impl ScnScene {
         &self.nav
     }
 
+    /// Position of the scene node whose trigger runs `proc_id`, or
+    /// `None` when no node owns the proc (scene-enter and role procs).
+    pub fn node_position_for_proc(&self, proc_id: u32) -> Option<Vec3> {
+        if proc_id == 0 {
+            return None;
+        }
+        self.scn_file
+            .nodes
+            .iter()
+            .find(|node| node.sce_proc_id == proc_id)
+            .map(|node| node.position)
+    }
+
     pub fn nav_min_coord(&self, layer: usize) -> Vec3 {
         self.nav.nav_file.maps[layer].min_coord
     }
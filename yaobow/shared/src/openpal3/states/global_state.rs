@@ -11,7 +11,6 @@ use common::store_ext::StoreExt2;
 use radiance::{
     audio::{AudioEngine, AudioMemorySource, AudioSource, AudioSourceState, Codec as AudioCodec},
     rendering::VideoPlayer,
-    video::Codec as VideoCodec,
 };
 use regex::Regex;
 
@@ -25,6 +24,9 @@ pub struct GlobalState {
     asset_mgr: Rc<AssetManager>,
     bgm_source: Box<dyn AudioMemorySource>,
     sound_sources: Vec<Rc<RefCell<Box<dyn AudioMemorySource>>>>,
+    /// Positional sources tied to nodes of the current scene; stopped
+    /// when another scene loads.
+    scene_sound_sources: Vec<Rc<RefCell<Box<dyn AudioMemorySource>>>>,
     default_scene_bgm: HashMap<String, String>,
     video_player: Box<VideoPlayer>,
 
@@ -53,6 +55,7 @@ impl GlobalState {
             asset_mgr,
             bgm_source,
             sound_sources,
+            scene_sound_sources: vec![],
             default_scene_bgm,
             video_player,
             pass_through_wall: false,
@@ -117,15 +120,10 @@ impl GlobalState {
     }
 
     pub fn play_movie(&mut self, name: &str) -> Option<(u32, u32)> {
-        let reader = self.asset_mgr.load_movie_data(name);
+        let (reader, codec) = self.asset_mgr.load_movie_data(name);
         let factory = self.asset_mgr.component_factory();
-        self.video_player.play(
-            factory,
-            self.audio_engine.clone(),
-            reader,
-            VideoCodec::Bik,
-            false,
-        )
+        self.video_player
+            .play(factory, self.audio_engine.clone(), reader, codec, false)
     }
 
     pub fn asset_mgr(&self) -> Rc<AssetManager> {
@@ -141,7 +139,25 @@ impl GlobalState {
     }
 
     pub fn add_sound_source(&mut self, source: Rc<RefCell<Box<dyn AudioMemorySource>>>) {
-        self.sound_sources.push(source);
+        if !self.sound_sources.iter().any(|s| Rc::ptr_eq(s, &source)) {
+            self.sound_sources.push(source);
+        }
+    }
+
+    pub fn add_scene_sound_source(&mut self, source: Rc<RefCell<Box<dyn AudioMemorySource>>>) {
+        if !self
+            .scene_sound_sources
+            .iter()
+            .any(|s| Rc::ptr_eq(s, &source))
+        {
+            self.scene_sound_sources.push(source);
+        }
+    }
+
+    pub fn stop_scene_sound_sources(&mut self) {
+        for source in self.scene_sound_sources.drain(..) {
+            source.borrow_mut().stop();
+        }
     }
 
     pub fn remove_sound_source(&mut self, source: Rc<RefCell<Box<dyn AudioMemorySource>>>) {
@@ -161,7 +177,11 @@ impl GlobalState {
         }
 
         self.remove_stopped_sound_sources();
-        for source in &mut self.sound_sources {
+        for source in self
+            .sound_sources
+            .iter()
+            .chain(self.scene_sound_sources.iter())
+        {
             if source.borrow().state() == AudioSourceState::Playing {
                 source.borrow_mut().update();
             }
@@ -171,6 +191,8 @@ impl GlobalState {
     fn remove_stopped_sound_sources(&mut self) {
         self.sound_sources
             .retain(|s| s.borrow().state() != AudioSourceState::Stopped);
+        self.scene_sound_sources
+            .retain(|s| s.borrow().state() != AudioSourceState::Stopped);
     }
 
     pub fn pass_through_wall(&self) -> bool {
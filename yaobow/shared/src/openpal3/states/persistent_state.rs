@@ -1,9 +1,8 @@
 use radiance::math::Vec3;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
 
-use crate::ydirs;
+use crate::saves::{self, SaveKind, SaveMetadata};
 
 pub const PAL3_APP_NAME: &str = "OpenPAL3";
 
@@ -14,6 +13,9 @@ pub struct PersistentState {
     position: Vec3,
     scene: Option<String>,
     sub_scene: Option<String>,
+    /// Accumulated in-game seconds. Added in save version 1.
+    #[serde(default)]
+    playtime: f64,
 }
 
 impl PersistentState {
@@ -24,40 +26,32 @@ impl PersistentState {
             position: Vec3::new(0., 0., 0.),
             scene: None,
             sub_scene: None,
+            playtime: 0.,
         }
     }
 
-    fn get_data_dir(app_name: &str) -> PathBuf {
-        ydirs::save_dir().join(app_name)
-    }
-
-    pub fn load(app_name: &str, slot: i32) -> Self {
-        let path = Self::get_data_dir(app_name)
-            .join("Save")
-            .join(format!("{}.json", slot));
-        let content = std::fs::read_to_string(path).unwrap();
-        serde_json::from_str(&content).unwrap()
+    /// Load and migrate slot `slot`. Errors when the slot is missing,
+    /// malformed or written by a newer build.
+    pub fn load(app_name: &str, slot: i32) -> anyhow::Result<Self> {
+        saves::load_state(SaveKind::Pal3, app_name, slot)
     }
 
     pub fn save(&self, slot: i32) {
         if slot >= 0 {
-            let path = Self::get_data_dir(&self.app_name).join("Save");
-            if let Err(e) = std::fs::create_dir_all(&path) {
-                log::error!("Cannot create save dir: {}", e);
-                return;
+            let metadata = SaveMetadata::now(self.playtime, self.location());
+            match saves::write_slot(SaveKind::Pal3, &self.app_name, slot, self, metadata) {
+                Ok(()) => log::info!("Game saved"),
+                Err(e) => log::error!("Cannot save: {:#}", e),
             }
+        }
+    }
 
-            let result = serde_json::to_string_pretty(self);
-            match result {
-                Ok(content) => {
-                    if let Err(e) = std::fs::write(path.join(format!("{}.json", slot)), content) {
-                        log::error!("Cannot save: {}", e);
-                    } else {
-                        log::info!("Game saved");
-                    }
-                }
-                Err(e) => log::error!("Cannot serialize persistent state: {}", e),
-            };
+    /// `scene/sub_scene` label for save metadata.
+    pub fn location(&self) -> String {
+        match (&self.scene, &self.sub_scene) {
+            (Some(scene), Some(sub_scene)) => format!("{}/{}", scene, sub_scene),
+            (Some(scene), None) => scene.clone(),
+            _ => String::new(),
         }
     }
 
@@ -93,4 +87,12 @@ impl PersistentState {
     pub fn sub_scene_name(&self) -> Option<String> {
         self.sub_scene.clone()
     }
+
+    pub fn playtime(&self) -> f64 {
+        self.playtime
+    }
+
+    pub fn add_playtime(&mut self, delta_sec: f32) {
+        self.playtime += delta_sec as f64;
+    }
 }
@@ -11,28 +11,35 @@ use fileformats::{
     binrw::BinRead,
     npc::NpcInfoFile,
     pal4::{cam::CameraDataFile, evf::EvfFile, gob::GobFile, ltmap::LtMapCfg},
-    rwbs::uva::UvAnimDict,
+    rwbs::{clump::Clump, read_bsp, read_dff, uva::UvAnimDict, world::World},
 };
 use mini_fs::{MiniFs, StoreExt};
 use radiance::{
+    asset::cache::CacheKey,
     audio::AudioEngine,
     comdef::{IArmatureComponent, IComponent, IEntity, IScene},
     components::mesh::{event::AnimationEvent, skinned_mesh::AnimKeyFrame},
     input::InputEngine,
+    radiance::AssetStreamer,
     rendering::{ComponentFactory, Sprite},
     scene::CoreScene,
     utils::SeekRead,
+    video::Codec,
 };
 
 use crate::{
     loaders::{
         Pal4TextureResolver,
         anm::{load_amf, load_anm},
-        bsp::create_entity_from_bsp_model,
-        dff::{DffLoaderConfig, create_entity_from_dff_model},
+        bsp::{create_entity_from_bsp_model, create_entity_from_bsp_worlds},
+        dff::{
+            DffLoaderConfig, create_entity_from_dff_clumps, create_entity_from_dff_model,
+            dff_cache_key,
+        },
         smp::load_smp,
     },
     scripting::angelscript::ScriptModule,
+    video::open_movie,
 };
 
 use super::{
@@ -49,6 +56,65 @@ const ZJM_WATER_UVA: &str = "/gamedata/ui/uiWorld/zjm/ZJM_water.uva";
 const ZJM_TRANS_DFF: &str = "/gamedata/ui/uiWorld/zjm/ZJM_trans.dff";
 const ZJM_TRANS_UVA: &str = "/gamedata/ui/uiWorld/zjm/ZJM_trans.uva";
 
+/// Geometry files of a scene block. The staged scene loader parses
+/// them on the [`AssetStreamer`] and builds their entities on the main
+/// thread.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum SceneModel {
+    /// `<block>.bsp`, the world mesh. Every block has one.
+    Bsp,
+    Clip,
+    ClipNa,
+    Sky,
+    /// Animated water surface. Its folder differs between scene
+    /// series, so several candidate paths are probed.
+    Water,
+    /// Collision floor, hidden unless the debug overlay shows it.
+    Floor,
+    /// Collision walls, hidden like the floor.
+    Wall,
+}
+
+/// A [`SceneModel`] file after parsing.
+pub enum ParsedSceneModel {
+    Bsp(Vec<World>),
+    /// Parsed clumps plus the file's geometry cache key.
+    Dff(Vec<Clump>, CacheKey),
+}
+
+impl SceneModel {
+    pub const ALL: [SceneModel; 7] = [
+        SceneModel::Bsp,
+        SceneModel::Clip,
+        SceneModel::ClipNa,
+        SceneModel::Sky,
+        SceneModel::Water,
+        SceneModel::Floor,
+        SceneModel::Wall,
+    ];
+
+    fn entity_name(self) -> &'static str {
+        match self {
+            SceneModel::Bsp => "world",
+            SceneModel::Clip => "clip",
+            SceneModel::ClipNa => "clipNA",
+            SceneModel::Sky => "sky",
+            SceneModel::Water => "water",
+            SceneModel::Floor => "floor",
+            SceneModel::Wall => "wall",
+        }
+    }
+
+    /// Parse the model's file. Touches no engine state, so it runs on
+    /// a streaming worker.
+    pub fn parse(self, data: &[u8]) -> anyhow::Result<ParsedSceneModel> {
+        match self {
+            SceneModel::Bsp => Ok(ParsedSceneModel::Bsp(read_bsp(data)?)),
+            _ => Ok(ParsedSceneModel::Dff(read_dff(data)?, dff_cache_key(data))),
+        }
+    }
+}
+
 pub struct AssetLoader {
     vfs: Rc<MiniFs>,
     component_factory: Rc<dyn ComponentFactory>,
@@ -61,6 +127,7 @@ pub struct AssetLoader {
     input: Rc<RefCell<dyn InputEngine>>,
     texture_resolver: Pal4TextureResolver,
     portraits: HashMap<String, ImageSetImage>,
+    asset_streamer: Rc<AssetStreamer>,
 }
 
 impl AssetLoader {
@@ -69,6 +136,7 @@ impl AssetLoader {
         audio_engine: Rc<dyn AudioEngine>,
         input: Rc<RefCell<dyn InputEngine>>,
         vfs: MiniFs,
+        asset_streamer: Rc<AssetStreamer>,
     ) -> Rc<Self> {
         let portraits = load_portraits(&component_factory, &vfs);
         let vfs = Rc::new(vfs);
@@ -79,9 +147,15 @@ impl AssetLoader {
             vfs,
             texture_resolver: Pal4TextureResolver {},
             portraits,
+            asset_streamer,
         })
     }
 
+    /// Worker pool scene loads parse their geometry on.
+    pub fn asset_streamer(&self) -> Rc<AssetStreamer> {
+        self.asset_streamer.clone()
+    }
+
     pub fn component_factory(&self) -> Rc<dyn ComponentFactory> {
         self.component_factory.clone()
     }
@@ -400,16 +474,61 @@ impl AssetLoader {
         Ok(GobFile::read(&mut reader)?)
     }
 
-    pub fn load_scene(
+    /// Vfs path of `model` for `(scene_name, block_name)`. `None` when
+    /// the block doesn't ship that (optional) model; the BSP path is
+    /// always returned.
+    pub fn scene_model_path(
         &self,
+        model: SceneModel,
         scene_name: &str,
         block_name: &str,
-    ) -> anyhow::Result<(ComRc<IScene>, ComRc<IEntity>)> {
-        let path = format!(
-            "/gamedata/PALWorld/{}/{}/{}.bsp",
-            scene_name, block_name, block_name,
-        );
+    ) -> Option<String> {
+        let world = |suffix: &str| {
+            format!(
+                "/gamedata/PALWorld/{}/{}/{}{}",
+                scene_name, block_name, block_name, suffix,
+            )
+        };
+        let scenedata = |suffix: &str| {
+            format!(
+                "/gamedata/scenedata/{}/{}/{}{}",
+                scene_name, block_name, block_name, suffix,
+            )
+        };
+        let path = match model {
+            SceneModel::Bsp => return Some(world(".bsp")),
+            SceneModel::Clip => world("_clip.dff"),
+            SceneModel::ClipNa => world("_clipNA.dff"),
+            SceneModel::Sky => world("_sky.dff"),
+            SceneModel::Water => {
+                let candidates = self.water_candidate_paths(scene_name, block_name, "dff");
+                return self.find_first_existing(&candidates);
+            }
+            SceneModel::Floor => scenedata("_floor.dff"),
+            SceneModel::Wall => scenedata("_wall.dff"),
+        };
 
+        if self.vfs.exists(&path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Read a scene model's bytes for [`SceneModel::parse`]. The vfs
+    /// isn't `Send`, so this half stays on the main thread.
+    pub fn read_scene_model(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        Ok(self.vfs.read_to_end(path)?)
+    }
+
+    /// Build a fresh scene around the block's parsed BSP.
+    pub fn build_scene(
+        &self,
+        scene_name: &str,
+        block_name: &str,
+        path: &str,
+        worlds: Vec<World>,
+    ) -> (ComRc<IScene>, ComRc<IEntity>) {
         // Per-scene lightmap modulation (`<block>_ltMap.cfg`). The
         // baked lightmap path itself is *always* engaged on PAL4 BSPs
         // (every BSP material's `texture.name` is a `*LightingMap`; the
@@ -460,11 +579,12 @@ impl AssetLoader {
             Some([ltmap.tint[0], ltmap.tint[1], ltmap.tint[2], ltmap.intensity]);
 
         let scene = CoreScene::create();
-        let entity = create_entity_from_bsp_model(
+        let entity = create_entity_from_bsp_worlds(
             &self.component_factory,
             &self.vfs,
             path,
             "world".to_string(),
+            worlds,
             &DffLoaderConfig {
                 texture_resolver: &self.texture_resolver,
                 keep_right_to_render_only: false,
@@ -475,12 +595,46 @@ impl AssetLoader {
                 fog_exempt: false,
                 foliage_resolver: None,
             },
-        )?;
+        );
 
         scene.add_entity(entity.clone());
 
         println!("Loaded scene: {} {}", scene_name, block_name);
-        Ok((scene, entity))
+        (scene, entity)
+    }
+
+    /// Build the entity of an optional scene model parsed from its
+    /// `.dff`.
+    pub fn build_scene_dff(
+        &self,
+        model: SceneModel,
+        path: &str,
+        clumps: Vec<Clump>,
+        source_key: CacheKey,
+    ) -> ComRc<IEntity> {
+        create_entity_from_dff_clumps(
+            &self.component_factory,
+            &self.vfs,
+            path,
+            model.entity_name().to_string(),
+            true,
+            clumps,
+            Some(source_key),
+            &DffLoaderConfig {
+                texture_resolver: &self.texture_resolver,
+                keep_right_to_render_only: false,
+                // Water materials are mutated per-frame by a UvAnimationComponent.
+                // Opt them out of the shared material cache so the UV
+                // transform doesn't leak onto unrelated geometry that
+                // happens to share the same texture+params.
+                force_unique_materials: model == SceneModel::Water,
+                ignore_root_frame_translation: false,
+                bsp_lightmap_tint: None,
+                dynamic_lighting: false,
+                fog_exempt: false,
+                foliage_resolver: None,
+            },
+        )
     }
 
     /// Try to load the optional `<block>_ltMap.cfg` — a 16-byte
@@ -518,83 +672,6 @@ impl AssetLoader {
         }
     }
 
-    pub fn try_load_scene_sky(&self, scene_name: &str, block_name: &str) -> Option<ComRc<IEntity>> {
-        let path = format!(
-            "/gamedata/PALWorld/{}/{}/{}_sky.dff",
-            scene_name, block_name, block_name,
-        );
-
-        self.try_load_dff(path, "sky".to_string())
-    }
-
-    pub fn try_load_scene_clip(
-        &self,
-        scene_name: &str,
-        block_name: &str,
-    ) -> Option<ComRc<IEntity>> {
-        let path = format!(
-            "/gamedata/PALWorld/{}/{}/{}_clip.dff",
-            scene_name, block_name, block_name,
-        );
-
-        self.try_load_dff(path, "clip".to_string())
-    }
-
-    pub fn try_load_scene_clip_na(
-        &self,
-        scene_name: &str,
-        block_name: &str,
-    ) -> Option<ComRc<IEntity>> {
-        let path = format!(
-            "/gamedata/PALWorld/{}/{}/{}_clipNA.dff",
-            scene_name, block_name, block_name,
-        );
-
-        self.try_load_dff(path, "clipNA".to_string())
-    }
-
-    /// Try to load the optional `<block>_water.dff` (animated water
-    /// surface). Mirrors `try_load_scene_clip` / `try_load_scene_sky` �?    /// returns `None` for scenes that don't ship a water mesh.
-    ///
-    /// The on-disk layout for PAL4 water assets isn't uniform across
-    /// scene categories (M-series �?`/gamedata/scenedata/...`, Q-series
-    /// �?`/gamedata/{scene}/q01/{block}/...`, combat �?`/gamedata/
-    /// PALWorld/CombatWorld/...`), so this method probes several
-    /// plausible candidates and loads the first that exists.
-    pub fn try_load_scene_water(
-        &self,
-        scene_name: &str,
-        block_name: &str,
-    ) -> Option<ComRc<IEntity>> {
-        let candidates = self.water_candidate_paths(scene_name, block_name, "dff");
-        let path = self.find_first_existing(&candidates)?;
-        log::debug!("[uv-anim] loading water DFF from {}", path);
-        let entity = create_entity_from_dff_model(
-            &self.component_factory,
-            &self.vfs,
-            path.clone(),
-            "water".to_string(),
-            true,
-            &DffLoaderConfig {
-                texture_resolver: &self.texture_resolver,
-                keep_right_to_render_only: false,
-                // Water materials are mutated per-frame by a UvAnimationComponent.
-                // Opt them out of the shared material cache so the UV
-                // transform doesn't leak onto unrelated geometry that
-                // happens to share the same texture+params.
-                force_unique_materials: true,
-                ignore_root_frame_translation: false,
-                bsp_lightmap_tint: None,
-                dynamic_lighting: false,
-                fog_exempt: false,
-                foliage_resolver: None,
-            },
-        )
-        .map_err(|e| log::warn!("try_load_scene_water: failed to load {}: {:#}", path, e))
-        .ok()?;
-        Some(entity)
-    }
-
     /// Try to load the UV-animation dictionary sibling of the water mesh
     /// (`<block>_water.uva`). Returns `None` if the file is missing or
     /// fails to parse �?water surfaces without a `.uva` render statically
@@ -682,50 +759,6 @@ impl AssetLoader {
         ]
     }
 
-    fn try_load_dff(&self, path: String, object_name: String) -> Option<ComRc<IEntity>> {
-        if self.vfs.exists(&path) {
-            create_entity_from_dff_model(
-                &self.component_factory,
-                &self.vfs,
-                path.clone(),
-                object_name,
-                true,
-                &DffLoaderConfig {
-                    texture_resolver: &self.texture_resolver,
-                    keep_right_to_render_only: false,
-                    force_unique_materials: false,
-                    ignore_root_frame_translation: false,
-                    bsp_lightmap_tint: None,
-                    dynamic_lighting: false,
-                    fog_exempt: false,
-                    foliage_resolver: None,
-                },
-            )
-            .map_err(|e| log::warn!("try_load_dff: failed to load {}: {:#}", path, e))
-            .ok()
-        } else {
-            None
-        }
-    }
-
-    pub fn load_scene_floor(&self, scene_name: &str, block_name: &str) -> Option<ComRc<IEntity>> {
-        let path = format!(
-            "/gamedata/scenedata/{}/{}/{}_floor.dff",
-            scene_name, block_name, block_name,
-        );
-
-        self.try_load_dff(path, "floor".to_string())
-    }
-
-    pub fn load_scene_wall(&self, scene_name: &str, block_name: &str) -> Option<ComRc<IEntity>> {
-        let path = format!(
-            "/gamedata/scenedata/{}/{}/{}_wall.dff",
-            scene_name, block_name, block_name,
-        );
-
-        self.try_load_dff(path, "wall".to_string())
-    }
-
     pub fn load_npc_info(&self, scene_name: &str, block_name: &str) -> anyhow::Result<NpcInfoFile> {
         let path = format!(
             "/gamedata/scenedata/{}/{}/npcInfo.npc",
@@ -737,7 +770,7 @@ impl AssetLoader {
         Ok(NpcInfoFile::read(&mut cursor)?)
     }
 
-    pub fn load_video(&self, video_name: &str) -> anyhow::Result<Box<dyn SeekRead>> {
+    pub fn load_video(&self, video_name: &str) -> anyhow::Result<(Box<dyn SeekRead>, Codec)> {
         let video_folder = match video_name.to_lowercase().as_str() {
             "1a.bik" | "end2.bik" | "pal4a.bik" => "VideoA",
             _ => "videob",
@@ -745,7 +778,7 @@ impl AssetLoader {
 
         let path = format!("/gamedata/{}/{}", video_folder, video_name);
         println!("Loading video: {}", path);
-        Ok(Box::new(BufReader::new(self.vfs.open(&path)?)))
+        open_movie(&self.vfs, path)
     }
 
     pub fn load_music(&self, music_name: &str) -> anyhow::Result<Vec<u8>> {
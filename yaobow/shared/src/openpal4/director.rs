@@ -6,8 +6,8 @@ use std::{
 use agent_server::protocol::{
     AgentCommand, AgentError, AgentResponse, DialogSnapshot, FastForwardParams, FireTriggerParams,
     NameParams, NpcEntry, ObjectEntry, PartyMember, SceneObjectsResponse, SceneTriggersResponse,
-    ScriptEvalParams, ScriptGlobalsParams, ScriptGlobalsResponse, StateSnapshot, TeleportParams,
-    TriggerEntry,
+    ScriptEvalParams, ScriptEvalResponse, ScriptGlobalsParams, ScriptGlobalsResponse,
+    StateSnapshot, TeleportParams, TriggerEntry,
 };
 use crosscom::ComRc;
 use fileformats::pal4::gob::GobObjectType;
@@ -23,17 +23,18 @@ use radiance::{
     utils::free_view::FreeViewController,
 };
 
-use crate::scripting::angelscript::ScriptVm;
+use crate::comdef::loading::ILoadingOverlay;
+use crate::scripting::angelscript::{EvalError, ScriptVm};
 
 use super::{
     agent::Pal4AgentBridge,
     asset_loader::AssetLoader,
-    comdef::IPal4LoadingOverlay,
     comdef::pal4_debug::{IPal4DebugContext, IPal4DebugOverlay},
     object_component::Pal4ObjectComponent,
     pal4_debug::Pal4DebugState,
     scene::Pal4Scene,
     scene::object_component,
+    script_eval,
     scripting::create_script_vm,
     session::{Pal4Session, RuntimeSnapshot},
     vm_context::{
@@ -97,7 +98,7 @@ pub struct OpenPAL4Director {
     /// (e.g. no-protosept build target / headless test harness); in
     /// that case the in-game transition falls back to a synchronous
     /// `load_scene` (and the player sees the legacy freeze-frame).
-    loading_overlay: RefCell<Option<ComRc<IPal4LoadingOverlay>>>,
+    loading_overlay: RefCell<Option<ComRc<ILoadingOverlay>>>,
 
     /// Scripted `IPal4ActorController` factory template, threaded into
     /// each scene swap (the F-key `load_state` reload, the in-game
@@ -237,15 +238,15 @@ impl OpenPAL4Director {
     /// `Pal4TransitionDirector` this director hands out. Idempotent;
     /// the last call wins. Called by `Pal4Service::build_story_director`
     /// with the overlay minted by
-    /// `IPal4ScriptFactory::make_pal4_loading_overlay`.
-    pub fn set_loading_overlay(&self, overlay: ComRc<IPal4LoadingOverlay>) {
+    /// `IPal4ScriptFactory::make_loading_overlay`.
+    pub fn set_loading_overlay(&self, overlay: ComRc<ILoadingOverlay>) {
         *self.loading_overlay.borrow_mut() = Some(overlay);
     }
 
     /// Clone of the installed overlay template, if any. Consumed by
     /// `transition::build_in_game_transition` so each transition
     /// holds its own `ComRc` to the same overlay object.
-    pub fn loading_overlay_template(&self) -> Option<ComRc<IPal4LoadingOverlay>> {
+    pub fn loading_overlay_template(&self) -> Option<ComRc<ILoadingOverlay>> {
         self.loading_overlay.borrow().clone()
     }
 
@@ -714,6 +715,12 @@ impl IDirectorImpl for OpenPAL4Director {
         self.tick_rotating_entities(motion_dt);
 
         self.vm.borrow_mut().vm_context_mut().update(effective_dt);
+        self.vm
+            .borrow()
+            .vm_context
+            .session_mut()
+            .state_mut()
+            .add_playtime(effective_dt);
 
         self.poll_save_load_hotkeys();
 
@@ -1086,17 +1093,26 @@ impl OpenPAL4Director {
         AgentResponse::Ok
     }
 
+    /// Evaluate a read-only expression (or a single allow-listed
+    /// query sysfn) against the live VM. See [`script_eval`] for the
+    /// language and what counts as a write.
     fn handle_script_eval(&self, params: ScriptEvalParams) -> AgentResponse {
-        // Script eval is a thin shim that returns a "not implemented"
-        // until the per-function whitelist is wired in a follow-up:
-        // implementing it requires marshalling JSON args back to the
-        // AngelScript stack types, which is best done as a separate
-        // patch with its own tests.
-        AgentResponse::err(AgentError::not_implemented(format!(
-            "script_eval for {} not yet implemented",
-            params.function
-        )))
-        .also_record(&params)
+        let result =
+            script_eval::evaluate(&mut self.vm.borrow_mut(), &params.function, &params.args);
+        match result {
+            Ok(result) => AgentResponse::Script(ScriptEvalResponse {
+                function: params.function,
+                result: Some(result),
+            }),
+            Err(EvalError::Rejected(msg)) => AgentResponse::err(AgentError::conflict(format!(
+                "script_eval {}: {}",
+                params.function, msg
+            ))),
+            Err(e) => AgentResponse::err(AgentError::bad_request(format!(
+                "script_eval {}: {}",
+                params.function, e
+            ))),
+        }
     }
 
     /// Snapshot the EVF event triggers for the currently loaded
@@ -1364,11 +1380,11 @@ impl OpenPAL4Director {
             .into_iter()
             .map(|p| PartyMember {
                 slot: p.slot,
-                level: p.level,
+                level: Some(p.level),
                 hp: p.hp,
-                max_hp: p.max_hp,
+                max_hp: Some(p.max_hp),
                 mp: p.mp,
-                max_mp: p.max_mp,
+                max_mp: Some(p.max_mp),
                 in_team: p.in_team,
             })
             .collect();
@@ -1417,20 +1433,6 @@ impl OpenPAL4Director {
     }
 }
 
-/// Tiny `AgentResponse` extension that lets `handle_script_eval` log
-/// without disrupting its `match`-arm composition. Kept local to the
-/// director so it doesn't leak as public API.
-trait AgentResponseExt {
-    fn also_record(self, params: &ScriptEvalParams) -> Self;
-}
-
-impl AgentResponseExt for AgentResponse {
-    fn also_record(self, params: &ScriptEvalParams) -> Self {
-        log::debug!("agent: script_eval unsupported for {}", params.function);
-        self
-    }
-}
-
 /// Compute a `(center, half_size)` bounding pair for an EVF event's
 /// trigger volume by AABB-ing the vertex centers. Mirrors the engine's
 /// own "skip if not 4 or 8 vertices" rule for ray-caster construction
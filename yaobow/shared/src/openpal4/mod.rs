@@ -23,6 +23,7 @@ pub mod object_component;
 pub mod pal4_debug;
 pub mod scene;
 pub mod scene_editor_access;
+pub mod script_eval;
 pub mod scripting;
 pub mod service;
 pub mod session;
@@ -1,5 +1,6 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Duration};
 
+use anyhow::Context;
 use crosscom::ComRc;
 use fileformats::npc::NpcInfoFile;
 use fileformats::pal4::{
@@ -16,6 +17,7 @@ use radiance::{
     components::collision::{CollisionWorldComponent, TriggerVolumeComponent},
     input::InputEngine,
     math::{Mat44, Vec3},
+    radiance::StreamingBatch,
     rendering::GradientYMaterialDef,
     scene::{CoreEntity, CoreScene, wrap_scene_camera},
 };
@@ -24,7 +26,7 @@ use radiance_scripting::services::InputService;
 use crate::scripting::angelscript::ScriptModule;
 
 use super::{
-    asset_loader::{self, AssetLoader},
+    asset_loader::{self, AssetLoader, ParsedSceneModel, SceneModel},
     comdef::{
         IPal4ActorAnimationController, IPal4ActorController, IPal4GameContext,
         IPal4ObjectComponent, IPal4ScriptFactory,
@@ -106,6 +108,14 @@ const DEFAULT_SOUND_TRIGGER_DISTANCE: f32 = 600.0;
 
 const SHOW_TRIGGER_POINT: bool = false;
 
+/// Main-thread time per `step()` spent building scene models the
+/// streamer has parsed, so the loading overlay keeps painting.
+const STREAM_BUDGET: Duration = Duration::from_millis(8);
+
+/// Parsed scene models by role, with the path each was read from.
+/// Filled in by the streaming batch's finalizers.
+type ParsedModels = Rc<RefCell<HashMap<SceneModel, (String, anyhow::Result<ParsedSceneModel>)>>>;
+
 impl Pal4Scene {
     const ID_YUN_TIANHE: usize = 0;
     const ID_HAN_LINGSHA: usize = 1;
@@ -155,6 +165,7 @@ impl Pal4Scene {
             if let Some(result) = step.done {
                 return result;
             }
+            loader.wait_for_models();
         }
     }
 }
@@ -169,13 +180,19 @@ impl Pal4Scene {
 ///
 /// | # | Work                                          | end frac |
 /// |---|-----------------------------------------------|----------|
-/// | 0 | `load_scene` (BSP) + scene root               | 0.20     |
-/// | 1 | sky / clip / water (+ UV anim) + camera fov   | 0.35     |
+/// | 0 | parse scene models, then BSP + scene root     | 0.30     |
+/// | 1 | sky / clip / water (+ UV anim) + camera fov   | 0.40     |
 /// | 2 | floor / wall meshes, ray caster, add to scene | 0.50     |
 /// | 3 | players, events, triggers, actor controller   | 0.70     |
 /// | 4 | NPCs                                          | 0.85     |
 /// | 5 | GOB objects                                   | 0.95     |
 /// | 6 | script module + finalize → `Pal4Scene`        | 1.00     |
+///
+/// Stage 0 reads the block's [`SceneModel`] files and parses them on
+/// the asset loader's [`AssetStreamer`](radiance::radiance::AssetStreamer);
+/// it takes as many `step()`s as the parse needs, building what is
+/// ready within [`STREAM_BUDGET`] each time. Later stages build
+/// entities from the parsed models.
 pub struct Pal4SceneLoader {
     // Inputs cloned in at construction.
     asset_loader: Rc<asset_loader::AssetLoader>,
@@ -188,6 +205,11 @@ pub struct Pal4SceneLoader {
     // 7 = done (the constructed scene was already returned).
     next_stage: u8,
 
+    // Stage 0's in-flight parses, and their results until a stage
+    // builds them.
+    batch: Option<StreamingBatch>,
+    models: ParsedModels,
+
     // Accumulated state. `Option<T>` slots are populated by the
     // stage that produces them and consumed by the finaliser.
     scene: Option<ComRc<IScene>>,
@@ -223,6 +245,8 @@ impl Pal4SceneLoader {
             block_name,
             actor_controller_factory,
             next_stage: 0,
+            batch: None,
+            models: Rc::new(RefCell::new(HashMap::new())),
             scene: None,
             players: None,
             events: Vec::new(),
@@ -245,6 +269,13 @@ impl Pal4SceneLoader {
                 done: None,
             };
         }
+        if stage == 0 && !self.stream_models() {
+            let progress = self.batch.as_ref().map_or(1.0, |b| b.progress());
+            return StageProgress {
+                fraction: 0.30 * progress,
+                done: None,
+            };
+        }
         let result: Option<anyhow::Result<()>> = match stage {
             0 => Some(self.stage_bsp()),
             1 => Some(self.stage_sky_clip_water()),
@@ -265,8 +296,8 @@ impl Pal4SceneLoader {
         };
         self.next_stage = stage + 1;
         let fraction = match stage {
-            0 => 0.20,
-            1 => 0.35,
+            0 => 0.30,
+            1 => 0.40,
             2 => 0.50,
             3 => 0.70,
             4 => 0.85,
@@ -290,10 +321,87 @@ impl Pal4SceneLoader {
         }
     }
 
+    /// Submit the block's scene models to the streamer on the first
+    /// call, then build whatever has been parsed. Returns `true` once
+    /// every model is in.
+    fn stream_models(&mut self) -> bool {
+        if self.batch.is_none() {
+            let batch = self.submit_models();
+            self.batch = Some(batch);
+        }
+        self.batch.as_ref().is_none_or(|b| b.pump(STREAM_BUDGET))
+    }
+
+    fn submit_models(&self) -> StreamingBatch {
+        let batch = self.asset_loader.asset_streamer().batch();
+        for model in SceneModel::ALL {
+            if model == SceneModel::Clip && cfg!(vita) {
+                continue;
+            }
+            let Some(path) =
+                self.asset_loader
+                    .scene_model_path(model, &self.scene_name, &self.block_name)
+            else {
+                continue;
+            };
+
+            let data = self.asset_loader.read_scene_model(&path);
+            let models = self.models.clone();
+            batch.submit(
+                move || data.and_then(|data| model.parse(&data)),
+                move |parsed| {
+                    models.borrow_mut().insert(model, (path, parsed));
+                },
+            );
+        }
+        batch
+    }
+
+    /// Block until stage 0's parses are done. Used by the one-shot
+    /// [`Pal4Scene::load`], which has no frames to spread them over.
+    fn wait_for_models(&self) {
+        if let Some(batch) = &self.batch {
+            batch.finish_blocking();
+        }
+    }
+
+    /// Build an optional model's entity; `None` when the block has no
+    /// such model or it failed to load.
+    fn take_model(&self, model: SceneModel) -> Option<ComRc<IEntity>> {
+        let (path, parsed) = self.models.borrow_mut().remove(&model)?;
+        match parsed {
+            Ok(ParsedSceneModel::Dff(clumps, key)) => {
+                Some(self.asset_loader.build_scene_dff(model, &path, clumps, key))
+            }
+            Ok(ParsedSceneModel::Bsp(_)) => unreachable!("{:?} parses as a DFF", model),
+            Err(e) => {
+                log::warn!("Pal4SceneLoader: failed to load {}: {:#}", path, e);
+                None
+            }
+        }
+    }
+
     fn stage_bsp(&mut self) -> anyhow::Result<()> {
-        let (scene, bsp_entity) = self
-            .asset_loader
-            .load_scene(&self.scene_name, &self.block_name)?;
+        self.batch = None;
+        let (path, parsed) = self
+            .models
+            .borrow_mut()
+            .remove(&SceneModel::Bsp)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "BSP of scene='{}' block='{}' failed to parse",
+                    self.scene_name,
+                    self.block_name
+                )
+            })?;
+        let worlds = match parsed.with_context(|| format!("loading BSP {}", path))? {
+            ParsedSceneModel::Bsp(worlds) => worlds,
+            ParsedSceneModel::Dff(..) => unreachable!("the BSP parses as a BSP"),
+        };
+
+        let (scene, bsp_entity) =
+            self.asset_loader
+                .build_scene(&self.scene_name, &self.block_name, &path, worlds);
         bsp_entity.add_tag(TAG_BSP);
         self.scene = Some(scene);
         Ok(())
@@ -302,26 +410,16 @@ impl Pal4SceneLoader {
     fn stage_sky_clip_water(&mut self) -> anyhow::Result<()> {
         let scene = self.scene.as_ref().expect("stage_bsp must run first");
 
-        if !cfg!(vita) {
-            let clip = self
-                .asset_loader
-                .try_load_scene_clip(&self.scene_name, &self.block_name);
-            if let Some(clip) = clip {
-                scene.add_entity(clip);
-            }
+        // The clip model isn't submitted on vita.
+        if let Some(clip) = self.take_model(SceneModel::Clip) {
+            scene.add_entity(clip);
         }
 
-        let clip_na = self
-            .asset_loader
-            .try_load_scene_clip_na(&self.scene_name, &self.block_name);
-        if let Some(clip_na) = clip_na {
+        if let Some(clip_na) = self.take_model(SceneModel::ClipNa) {
             scene.add_entity(clip_na);
         }
 
-        let skybox = self
-            .asset_loader
-            .try_load_scene_sky(&self.scene_name, &self.block_name);
-        if let Some(skybox) = skybox {
+        if let Some(skybox) = self.take_model(SceneModel::Sky) {
             scene.add_entity(skybox);
         }
 
@@ -329,10 +427,7 @@ impl Pal4SceneLoader {
         // e.g. Q01/q01/Q01, Q01/q01/Q01Y). The sibling `_water.uva`
         // drives per-frame UV animation via a self-ticking
         // `UvAnimationComponent` attached to the water entity.
-        let water = self
-            .asset_loader
-            .try_load_scene_water(&self.scene_name, &self.block_name);
-        if let Some(water) = water {
+        if let Some(water) = self.take_model(SceneModel::Water) {
             scene.add_entity(water.clone());
             if let Some(dict) = self
                 .asset_loader
@@ -355,12 +450,8 @@ impl Pal4SceneLoader {
     fn stage_floor_wall(&mut self) -> anyhow::Result<()> {
         let scene = self.scene.as_ref().expect("stage_bsp must run first");
 
-        let floor = self
-            .asset_loader
-            .load_scene_floor(&self.scene_name, &self.block_name);
-        let wall = self
-            .asset_loader
-            .load_scene_wall(&self.scene_name, &self.block_name);
+        let floor = self.take_model(SceneModel::Floor);
+        let wall = self.take_model(SceneModel::Wall);
         if floor.is_none() {
             log::warn!(
                 "Pal4Scene::load: missing floor mesh for scene='{}' block='{}'. \
@@ -654,6 +745,9 @@ impl Pal4SceneLoader {
                         reference_distance: trigger_distance * 0.25,
                         rolloff_factor: 1.0,
                         max_distance: trigger_distance,
+                        // Silent beyond the trigger distance anyway, so
+                        // let far-away emitters go dormant.
+                        cull_distance: Some(trigger_distance),
                     };
 
                     match self.asset_loader.load_sound(&name, "wav") {
@@ -0,0 +1,271 @@
+//! PAL4 name resolution for the read-only `/v1/script/eval`
+//! evaluator (see [`crate::scripting::angelscript::eval`]).
+//!
+//! Roots an expression can use:
+//!
+//! | Path                      | Value |
+//! | ------------------------- | ----- |
+//! | `g[N]`                    | Shared global `N` (same index as `/v1/script/globals`), as `i32` |
+//! | `m[N]`                    | Global `N` of the loaded block's module; unset slots read 0 like the VM |
+//! | `state.<field>...`        | Any field of the serialized [`Pal4PersistentState`](super::states::persistent_state::Pal4PersistentState), e.g. `state.money`, `state.inventory[1203]`, `state.players[0].level` |
+//! | `object.<name>.<field>`   | GOB object entity: `visible`, `x`, `y`, `z` |
+//! | `npc.<name>.<field>`      | NPC entity: `visible`, `x`, `y`, `z` |
+//! | `giXxx(args...)`          | A sysfn from [`QUERY_SYSFNS`] |
+//!
+//! Sysfn calls run through [`ScriptVm::call_sysfn_sandboxed`], which
+//! checkpoints the VM and rejects any call that suspends or writes a
+//! script global. The persistent state is checkpointed here as well,
+//! so a call that changes it is rolled back and rejected. Scene side
+//! effects can't be rolled back, which is why only the allow-listed
+//! query sysfns are callable at all.
+
+use radiance::comdef::IEntityExt;
+
+use crate::scripting::angelscript::{
+    EvalError, EvalHost, EvalKey, EvalValue, Expr, ScriptVm, eval::eval,
+};
+
+use super::vm_context::Pal4VmContext;
+
+/// How to read a query sysfn's `r1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Returns {
+    Int,
+    Bool,
+    Float,
+}
+
+/// `gi*` sysfns that only read game state, with their arity and
+/// return type. Anything not listed here is rejected before it runs.
+/// `giGetRandnum` is left out on purpose: it's side-effect free but
+/// not repeatable, so it can't answer "would this branch be taken".
+const QUERY_SYSFNS: &[(&str, usize, Returns)] = &[
+    ("giCheckEquipInInventory", 1, Returns::Bool),
+    ("giCheckMagicMastered", 0, Returns::Bool),
+    ("giCheckPackProperty", 2, Returns::Bool),
+    ("giGetGoodsOpenCondition", 1, Returns::Int),
+    ("giGetInnDialogResult", 0, Returns::Int),
+    ("giGetMoney", 0, Returns::Int),
+    ("giGetPalTestResult", 1, Returns::Int),
+    ("giGetPlayerLevel", 1, Returns::Int),
+    ("giGetPropertyNumb", 1, Returns::Int),
+    ("giGetPuzzleGameResult", 0, Returns::Int),
+    ("giGetQuestDialogResult", 0, Returns::Int),
+    ("giGetVisibleMonster", 1, Returns::Bool),
+    ("giGetVisibleObject", 1, Returns::Bool),
+    ("giIsNightTime", 0, Returns::Bool),
+    ("giPlayerCurrentGetPosX", 0, Returns::Float),
+    ("giPlayerCurrentGetPosY", 0, Returns::Float),
+    ("giPlayerCurrentGetPosZ", 0, Returns::Float),
+];
+
+/// Evaluate `source` against the live PAL4 VM without changing it.
+///
+/// When `args` is non-empty, `source` must be a bare sysfn name and
+/// is called with those literal arguments — the `{function, args}`
+/// shape `/v1/script/eval` has always documented.
+pub fn evaluate(
+    vm: &mut ScriptVm<Pal4VmContext>,
+    source: &str,
+    args: &[serde_json::Value],
+) -> Result<serde_json::Value, EvalError> {
+    let expr = if args.is_empty() {
+        Expr::parse(source)?
+    } else {
+        let name = match Expr::parse(source) {
+            Ok(Expr::Path { root, steps }) if steps.is_empty() => root,
+            _ => {
+                return Err(EvalError::Parse(
+                    "`args` requires `function` to be a bare sysfn name".to_string(),
+                ));
+            }
+        };
+        let args = args
+            .iter()
+            .map(|arg| Ok(Expr::Literal(json_to_value(arg)?)))
+            .collect::<Result<Vec<_>, EvalError>>()?;
+        Expr::Call { name, args }
+    };
+
+    let value = eval(&expr, &mut Pal4EvalHost { vm })?;
+    Ok(value_to_json(value))
+}
+
+struct Pal4EvalHost<'a> {
+    vm: &'a mut ScriptVm<Pal4VmContext>,
+}
+
+impl Pal4EvalHost<'_> {
+    fn entity(&self, kind: &str, keys: &[EvalKey]) -> Result<EvalValue, EvalError> {
+        let [EvalKey::Name(name), EvalKey::Name(field)] = keys else {
+            return Err(EvalError::Unknown(format!(
+                "expected {}.<name>.<visible|x|y|z>",
+                kind
+            )));
+        };
+        let scene = self.vm.vm_context().scene.borrow();
+        let entity = match kind {
+            "object" => scene.get_object(name),
+            _ => scene.get_npc(name),
+        };
+        let Some(entity) = entity else {
+            return Err(EvalError::Unknown(format!("no {} named {}", kind, name)));
+        };
+        let position = entity.world_transform().position();
+        match field.as_str() {
+            "visible" => Ok(EvalValue::Bool(entity.visible())),
+            "x" => Ok(EvalValue::Float(position.x as f64)),
+            "y" => Ok(EvalValue::Float(position.y as f64)),
+            "z" => Ok(EvalValue::Float(position.z as f64)),
+            _ => Err(EvalError::Unknown(format!(
+                "{}.{}.{} (fields are visible, x, y, z)",
+                kind, name, field
+            ))),
+        }
+    }
+
+    fn state(&self, keys: &[EvalKey]) -> Result<EvalValue, EvalError> {
+        let state = serde_json::to_value(&*self.vm.vm_context().persistent_state())
+            .map_err(|e| EvalError::Type(format!("state does not serialize: {}", e)))?;
+        let mut current = &state;
+        let mut path = "state".to_string();
+        for (depth, key) in keys.iter().enumerate() {
+            let next = match (current, key) {
+                (serde_json::Value::Object(map), EvalKey::Name(name)) => map.get(name),
+                // Integer-keyed maps (`inventory`, `players`) serialize
+                // with string keys.
+                (serde_json::Value::Object(map), EvalKey::Index(i)) => map.get(&i.to_string()),
+                (serde_json::Value::Array(items), EvalKey::Index(i)) => {
+                    usize::try_from(*i).ok().and_then(|i| items.get(i))
+                }
+                _ => None,
+            };
+            match key {
+                EvalKey::Name(name) => path = format!("{}.{}", path, name),
+                EvalKey::Index(i) => path = format!("{}[{}]", path, i),
+            }
+            let Some(next) = next else {
+                // Missing entries of nested maps read as null so
+                // `state.inventory[1203] == null` can test ownership;
+                // an unknown top-level field is still an error.
+                if depth > 0 && matches!(current, serde_json::Value::Object(_)) {
+                    return Ok(EvalValue::Null);
+                }
+                return Err(EvalError::Unknown(path));
+            };
+            current = next;
+        }
+        json_to_value(current).map_err(|_| {
+            EvalError::Type(format!(
+                "{} is an object or array; index into one of its fields",
+                path
+            ))
+        })
+    }
+
+    fn shared_global(&self, keys: &[EvalKey]) -> Result<EvalValue, EvalError> {
+        let [EvalKey::Index(index)] = keys else {
+            return Err(EvalError::Unknown("expected g[<slot>]".to_string()));
+        };
+        let value = usize::try_from(*index)
+            .ok()
+            .and_then(|i| self.vm.g.borrow().vars.get(i).copied());
+        let Some(value) = value else {
+            return Err(EvalError::Unknown(format!("no shared global g[{}]", index)));
+        };
+        Ok(EvalValue::Int(value as i32 as i64))
+    }
+
+    fn module_global(&self, keys: &[EvalKey]) -> Result<EvalValue, EvalError> {
+        let [EvalKey::Index(index)] = keys else {
+            return Err(EvalError::Unknown("expected m[<slot>]".to_string()));
+        };
+        let Ok(index) = usize::try_from(*index) else {
+            return Err(EvalError::Unknown(format!("no module global m[{}]", index)));
+        };
+        let scene = self.vm.vm_context().scene.borrow();
+        let Some(module) = scene.module.as_ref() else {
+            return Err(EvalError::Unknown(
+                "no scene module is loaded for m[...]".to_string(),
+            ));
+        };
+        let value = module.borrow().globals.get(index).copied().unwrap_or(0);
+        Ok(EvalValue::Int(value as i32 as i64))
+    }
+}
+
+impl EvalHost for Pal4EvalHost<'_> {
+    fn lookup(&mut self, root: &str, keys: &[EvalKey]) -> Result<EvalValue, EvalError> {
+        match root {
+            "g" => self.shared_global(keys),
+            "m" => self.module_global(keys),
+            "state" => self.state(keys),
+            "object" | "npc" => self.entity(root, keys),
+            _ => Err(EvalError::Unknown(format!(
+                "{} (roots are g, m, state, object, npc)",
+                root
+            ))),
+        }
+    }
+
+    fn call(&mut self, name: &str, args: &[EvalValue]) -> Result<EvalValue, EvalError> {
+        let Some(&(_, arity, returns)) = QUERY_SYSFNS.iter().find(|(n, _, _)| *n == name) else {
+            return Err(EvalError::Rejected(format!(
+                "{} is not an allow-listed query sysfn",
+                name
+            )));
+        };
+        if args.len() != arity {
+            return Err(EvalError::Type(format!(
+                "{} takes {} argument(s), got {}",
+                name,
+                arity,
+                args.len()
+            )));
+        }
+
+        let before = self.vm.vm_context().persistent_state().clone();
+        let before_json = serde_json::to_value(&before).ok();
+        let ret = self.vm.call_sysfn_sandboxed(name, args);
+        let after_json = serde_json::to_value(&*self.vm.vm_context().persistent_state()).ok();
+        if before_json != after_json {
+            self.vm.vm_context().set_persistent_state(before);
+            return Err(EvalError::Rejected(format!(
+                "{} modified the persistent state",
+                name
+            )));
+        }
+
+        let ret = ret?;
+        Ok(match returns {
+            Returns::Int => EvalValue::Int(ret as i32 as i64),
+            Returns::Bool => EvalValue::Bool(ret != 0),
+            Returns::Float => EvalValue::Float(f32::from_bits(ret) as f64),
+        })
+    }
+}
+
+fn json_to_value(value: &serde_json::Value) -> Result<EvalValue, EvalError> {
+    match value {
+        serde_json::Value::Null => Ok(EvalValue::Null),
+        serde_json::Value::Bool(b) => Ok(EvalValue::Bool(*b)),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Ok(EvalValue::Int(i)),
+            None => Ok(EvalValue::Float(n.as_f64().unwrap_or(0.0))),
+        },
+        serde_json::Value::String(s) => Ok(EvalValue::Str(s.clone())),
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => Err(EvalError::Type(
+            "arguments must be numbers, booleans or strings".to_string(),
+        )),
+    }
+}
+
+fn value_to_json(value: EvalValue) -> serde_json::Value {
+    match value {
+        EvalValue::Null => serde_json::Value::Null,
+        EvalValue::Bool(b) => serde_json::Value::Bool(b),
+        EvalValue::Int(i) => serde_json::Value::from(i),
+        EvalValue::Float(f) => serde_json::Value::from(f),
+        EvalValue::Str(s) => serde_json::Value::String(s),
+    }
+}
@@ -2234,6 +2234,7 @@ fn talk(_: &str, vm: &mut ScriptVm<Pal4VmContext>) -> Pal4FunctionState {
     }
 
     let text = get_str(vm, str as usize).unwrap();
+    let text = crate::localization::localize(&text);
     vm.vm_context.dialog_box.set_text(&text);
     let presenter = DialogBoxPresenter::new();
 
@@ -45,12 +45,13 @@ use radiance_scripting::comdef::services::{IAudioSource, IUiLayoutHandle};
 use radiance_scripting::services::ImguiTextureCache;
 use radiance_scripting::services::audio::AudioSource as ScriptAudioSource;
 
+use crate::comdef::loading::ILoadingOverlay;
 use crate::loaders::cegui::layout as cegui_layout;
 use crate::loaders::cegui::ui_layout_handle::UiLayoutHandle;
 use crate::openpal4::agent::Pal4AgentBridge;
 use crate::openpal4::asset_loader::AssetLoader;
 use crate::openpal4::comdef::{
-    IOpenPAL4Director, IPal4LoadingOverlay, IPal4ScriptFactory, IPal4Service, IPal4ServiceImpl,
+    IOpenPAL4Director, IPal4ScriptFactory, IPal4Service, IPal4ServiceImpl,
 };
 use crate::openpal4::director::{OpenPAL4Director, Pal4DebugBundle};
 use crate::openpal4::modes::{
@@ -132,9 +133,17 @@ pub struct Pal4Service {
     /// up-front means the very first `Pal4TransitionDirector::activate`
     /// returns immediately and the loading layout paints on the next
     /// frame instead of after a multi-second freeze.
-    loading_overlay: RefCell<Option<ComRc<IPal4LoadingOverlay>>>,
+    loading_overlay: RefCell<Option<ComRc<ILoadingOverlay>>>,
+
+    /// Builds a user mod's start menu in place of the scripted one.
+    /// Installed by `YaobowApplicationLoader`; returns `None` when no
+    /// mod overrides the menu or the override fails to build.
+    start_menu_override: RefCell<Option<StartMenuOverride>>,
 }
 
+/// Hook [`Pal4Service::build_start_menu`] asks for a mod's start menu.
+pub type StartMenuOverride = Box<dyn Fn() -> Option<ComRc<IDirector>>>;
+
 ComObject_Pal4Service!(super::Pal4Service);
 
 impl Pal4Service {
@@ -154,6 +163,7 @@ impl Pal4Service {
             session: Rc::new(RefCell::new(Pal4Session::new())),
             launch_asset_path: RefCell::new(None),
             loading_overlay: RefCell::new(None),
+            start_menu_override: RefCell::new(None),
         })
     }
 
@@ -202,11 +212,18 @@ impl Pal4Service {
         *self.script_factory.borrow_mut() = Some(factory);
     }
 
+    /// Install the hook that builds a mod's start menu. Called by
+    /// `YaobowApplicationLoader::on_loading` after user mods load.
+    pub fn set_start_menu_override(&self, hook: StartMenuOverride) {
+        *self.start_menu_override.borrow_mut() = Some(hook);
+    }
+
     /// Drop the held script factory, breaking the
     /// service↔host-context↔script-CCW reference cycle at teardown.
     /// Called by `YaobowApplicationLoader::on_unloading`.
     pub fn clear_script_factory(&self) {
         *self.script_factory.borrow_mut() = None;
+        *self.start_menu_override.borrow_mut() = None;
         // Drop the cached overlay too — its script-side struct holds
         // a `host_context` ComRc, which keeps the script CCW alive.
         *self.loading_overlay.borrow_mut() = None;
@@ -229,12 +246,12 @@ impl Pal4Service {
     /// the first `Pal4TransitionDirector::activate`. `cancel()`
     /// resets the overlay's internal state to IDLE so the warm-up
     /// is invisible.
-    pub(crate) fn prepare_loading_overlay(&self) -> Option<ComRc<IPal4LoadingOverlay>> {
+    pub(crate) fn prepare_loading_overlay(&self) -> Option<ComRc<ILoadingOverlay>> {
         if let Some(overlay) = self.loading_overlay.borrow().clone() {
             return Some(overlay);
         }
         let factory = self.script_factory.borrow().clone()?;
-        let overlay = factory.make_pal4_loading_overlay();
+        let overlay = factory.make_loading_overlay();
         // Pre-warm: arm the state machine (triggers lazy
         // `open_layout`), then cancel to leave it IDLE.
         overlay.request("", "");
@@ -282,6 +299,7 @@ impl Pal4Service {
         let component_factory = engine.rendering_component_factory();
         let audio_engine = engine.audio_engine();
         let real_input = engine.input_engine();
+        let asset_streamer = engine.asset_streamer();
         drop(engine);
 
         let input_engine: Rc<RefCell<dyn InputEngine>> = match self.agent_bridge.borrow().as_ref() {
@@ -290,7 +308,13 @@ impl Pal4Service {
         };
 
         let vfs = init_virtual_fs(asset_path, None);
-        let loader = AssetLoader::new(component_factory, audio_engine, input_engine, vfs);
+        let loader = AssetLoader::new(
+            component_factory,
+            audio_engine,
+            input_engine,
+            vfs,
+            asset_streamer,
+        );
         *self.launch_loader.borrow_mut() = Some(loader.clone());
         // Remember the launch asset path so app-lifetime mode-control
         // commands (`/v1/menu/new_game` etc.) can rebuild the story
@@ -701,6 +725,14 @@ impl IPal4ServiceImpl for Pal4Service {
                 .add_game_font(&bytes, crate::GameType::PAL4.ui_font_scale());
         }
 
+        // Dialog language pack + script conversion from `[localization]`.
+        crate::localization::install_for_game(crate::GameType::PAL4);
+
+        // Saves capture the last presented frame as their thumbnail.
+        crate::saves::install_thumbnail_source(Some(
+            self.app.engine().borrow().rendering_engine(),
+        ));
+
         modes::route(
             self,
             Pal4ModeIntent::StartMenu {
@@ -831,7 +863,8 @@ impl Pal4Service {
     /// Build the scripted PAL4 start-menu director. Mounts the
     /// per-launch asset loader first so the script-side menu can call
     /// `host.pal4().open_layout("/gamedata/ui/...")`, then asks the
-    /// script project's `make_pal4_start_menu` hook to build it. Falls
+    /// script project's `make_pal4_start_menu` hook to build it, unless
+    /// a user mod overrides the `pal4_start_menu` slot. Falls
     /// back to a fresh story director when the scripted menu can't be
     /// built (e.g. PAL4 assets missing at this path). Called by the
     /// mode router for [`Pal4ModeIntent::StartMenu`].
@@ -855,6 +888,15 @@ impl Pal4Service {
         // before the loading layout finally appears.
         let _ = self.prepare_loading_overlay();
 
+        let modded = self
+            .start_menu_override
+            .borrow()
+            .as_ref()
+            .and_then(|make| make());
+        if let Some(director) = modded {
+            return director;
+        }
+
         // The menu struct conforms to both `IUiLayer` and `IDirector`;
         // the factory hands back the `IDirector` the scene manager
         // expects (the engine auto-bridges its `IUiLayer` slot each
@@ -1,11 +1,10 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
 
 use radiance::math::Transform;
 use radiance::math::Vec3;
 use serde::{Deserialize, Serialize};
 
-use crate::ydirs;
+use crate::saves::{self, SaveKind, SaveMetadata};
 
 /// Number of fixed party slots in PAL4 (YunTianhe / HanLingsha /
 /// LiuMengli / MurongZiying). Mirrors the `Player` enum in
@@ -105,6 +104,9 @@ pub struct Pal4PersistentState {
     /// which hold cross-scene story-plot flags.
     #[serde(default)]
     script_globals: Vec<u32>,
+    /// Accumulated in-game seconds. Added in save version 1.
+    #[serde(default)]
+    playtime: f64,
 }
 
 impl Pal4PersistentState {
@@ -136,23 +138,16 @@ impl Pal4PersistentState {
             players,
             inventory: HashMap::new(),
             script_globals: Vec::new(),
+            playtime: 0.,
         }
     }
 
-    fn get_data_dir(app_name: &str) -> PathBuf {
-        ydirs::save_dir().join(app_name)
-    }
-
-    /// Load the persistent state for `app_name` from the given slot.
-    /// Returns an error if the slot file is missing or malformed; the
-    /// caller decides whether to fall back to a fresh state.
+    /// Load the persistent state for `app_name` from the given slot,
+    /// migrating older save versions. Returns an error if the slot file
+    /// is missing, malformed or from a newer build; the caller decides
+    /// whether to fall back to a fresh state.
     pub fn load(app_name: &str, slot: i32) -> anyhow::Result<Self> {
-        let path = Self::get_data_dir(app_name)
-            .join("Save")
-            .join(format!("{}.json", slot));
-        let content = std::fs::read_to_string(path)?;
-        let state = serde_json::from_str(&content)?;
-        Ok(state)
+        saves::load_state(SaveKind::Pal4, app_name, slot)
     }
 
     /// Read a save slot for display purposes only. Returns `None` when
@@ -179,6 +174,15 @@ impl Pal4PersistentState {
         }
     }
 
+    /// `scene/block` label for save metadata.
+    pub fn location(&self) -> String {
+        if self.block_name.is_empty() {
+            self.scene_name.clone()
+        } else {
+            format!("{}/{}", self.scene_name, self.block_name)
+        }
+    }
+
     /// Persist this state to the given slot. Negative slots are
     /// ignored (matching the OpenPAL3 "no slot selected" sentinel).
     pub fn save(&self, slot: i32) {
@@ -186,21 +190,10 @@ impl Pal4PersistentState {
             return;
         }
 
-        let path = Self::get_data_dir(&self.app_name).join("Save");
-        if let Err(e) = std::fs::create_dir_all(&path) {
-            log::error!("Cannot create save dir: {}", e);
-            return;
-        }
-
-        match serde_json::to_string_pretty(self) {
-            Ok(content) => {
-                if let Err(e) = std::fs::write(path.join(format!("{}.json", slot)), content) {
-                    log::error!("Cannot save: {}", e);
-                } else {
-                    log::info!("Game saved to slot {}", slot);
-                }
-            }
-            Err(e) => log::error!("Cannot serialize persistent state: {}", e),
+        let metadata = SaveMetadata::now(self.playtime, self.location());
+        match saves::write_slot(SaveKind::Pal4, &self.app_name, slot, self, metadata) {
+            Ok(()) => log::info!("Game saved to slot {}", slot),
+            Err(e) => log::error!("Cannot save: {:#}", e),
         }
     }
 
@@ -380,6 +373,16 @@ impl Pal4PersistentState {
     pub fn set_script_globals(&mut self, globals: Vec<u32>) {
         self.script_globals = globals;
     }
+
+    // --- Playtime ------------------------------------------------------
+
+    pub fn playtime(&self) -> f64 {
+        self.playtime
+    }
+
+    pub fn add_playtime(&mut self, delta_sec: f32) {
+        self.playtime += delta_sec as f64;
+    }
 }
 
 #[cfg(test)]
@@ -33,13 +33,14 @@ use std::{
     rc::Rc,
 };
 
-use crosscom::ComRc;
-use radiance::comdef::{IDirector, IDirectorImpl, IUiHost, IUiLayerImpl};
+use crosscom::{ComRc, ComWeak};
+use radiance::comdef::{IDirector, IDirectorImpl, IScene, IUiHost, IUiLayerImpl};
 
+use crate::comdef::loading::ILoadingOverlay;
 use crate::scripting::angelscript::ScriptVm;
 
 use super::{
-    comdef::{IPal4LoadingOverlay, IPal4ScriptFactory},
+    comdef::IPal4ScriptFactory,
     director::OpenPAL4Director,
     scene::{Pal4Scene, Pal4SceneLoader, StageProgress},
     session::RuntimeSnapshot,
@@ -114,12 +115,12 @@ enum TransitionPhase {
 
 pub struct Pal4TransitionDirector {
     /// The loading layout renderer. Built per-launch by
-    /// `IPal4ScriptFactory::make_pal4_loading_overlay`. Its
+    /// `IPal4ScriptFactory::make_loading_overlay`. Its
     /// `request`/`tick`/`notify_load_complete`/`is_active`/`cancel`
     /// methods drive the p7-side cosmetic state machine; this
     /// director owns the *transition* state machine and just asks
     /// the overlay to render.
-    overlay: ComRc<IPal4LoadingOverlay>,
+    overlay: ComRc<ILoadingOverlay>,
 
     /// Shared handle to the next director's `ScriptVm`. For boot
     /// flows this is the freshly-built story director's VM; for
@@ -228,6 +229,10 @@ pub(crate) struct Pal4SceneSwap {
     // `Pal4SceneLoader` so the new scene's player entities attach the
     // same scripted controller. `None` for editor/headless builds.
     factory: Option<ComRc<IPal4ScriptFactory>>,
+    // The popped outgoing scene. Once the new scene replaces it in the
+    // VM context nothing should keep it alive; if something does, the
+    // previous block leaked.
+    outgoing: Option<ComWeak<IScene>>,
 }
 
 impl Pal4SceneSwap {
@@ -246,6 +251,7 @@ impl Pal4SceneSwap {
             had_error: false,
             finished: false,
             factory,
+            outgoing: None,
         }
     }
 
@@ -286,7 +292,7 @@ impl Pal4SceneSwap {
                     app.scene_manager.clone(),
                 )
             };
-            let _ = scene_manager.pop_scene();
+            self.outgoing = scene_manager.pop_scene().map(|scene| scene.downgrade());
             self.loader = Some(Pal4SceneLoader::new(
                 asset_loader,
                 input,
@@ -324,6 +330,7 @@ impl Pal4SceneSwap {
                 };
                 *scene_cell.borrow_mut() = scene;
                 scene_manager.push_scene(scene_root);
+                self.check_outgoing_released();
 
                 session
                     .borrow_mut()
@@ -352,6 +359,23 @@ impl Pal4SceneSwap {
         }
     }
 
+    fn check_outgoing_released(&mut self) {
+        let Some(outgoing) = self.outgoing.take() else {
+            return;
+        };
+        if !outgoing.is_alive() {
+            return;
+        }
+        log::warn!(
+            "Pal4SceneSwap: outgoing scene still alive after loading scene='{}' block='{}'",
+            self.scene_name,
+            self.block_name
+        );
+        if crosscom::leak_tracker::ENABLED {
+            log::debug!("{}", crosscom::leak_tracker::report());
+        }
+    }
+
     /// True iff any stage of the swap returned an `Err`. Inspect
     /// after `finished` is true.
     pub fn had_error(&self) -> bool {
@@ -361,7 +385,7 @@ impl Pal4SceneSwap {
 
 impl Pal4TransitionDirector {
     pub fn new(
-        overlay: ComRc<IPal4LoadingOverlay>,
+        overlay: ComRc<ILoadingOverlay>,
         vm: Rc<RefCell<ScriptVm<Pal4VmContext>>>,
         next: ComRc<IDirector>,
         action: Pal4TransitionAction,
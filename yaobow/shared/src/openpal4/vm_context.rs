@@ -899,12 +899,12 @@ impl Pal4VmContext {
     }
 
     pub fn start_play_movie(&mut self, name: &str) -> Option<(u32, u32)> {
-        let reader = self.loader.load_video(name).unwrap();
+        let (reader, codec) = self.loader.load_video(name).unwrap();
         self.video_player.play(
             self.component_factory.clone(),
             self.audio_engine.clone(),
             reader,
-            radiance::video::Codec::Bik,
+            codec,
             false,
         )
     }
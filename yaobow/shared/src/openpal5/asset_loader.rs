@@ -1,22 +1,27 @@
 use std::{collections::HashMap, io::Cursor, rc::Rc};
 
+use anyhow::Context;
 use common::store_ext::StoreExt2;
 use crosscom::ComRc;
 use fileformats::{
     binrw::BinRead,
     nod::NodFile,
+    pal5::{campath::CameraPathFile, ctr::GrassLeaf},
     role_bin::{AssetItem, RoleBinFile},
+    rwbs::clump::Clump,
 };
 use mini_fs::MiniFs;
 use radiance::{
+    asset::cache::CacheKey,
     comdef::{IComponent, IEntity, ISkyboxComponent},
-    components::skybox::SkyboxComponent,
+    components::{mesh::skinned_mesh::AnimKeyFrame, skybox::SkyboxComponent},
     rendering::ComponentFactory,
 };
 
 use crate::loaders::{
     FoliageCard, FoliageResolver, Pal5TextureResolver,
-    dff::{DffLoaderConfig, create_entity_from_dff_model},
+    anm::load_anm,
+    dff::{DffLoaderConfig, create_entity_from_dff_clumps, create_entity_from_dff_model},
 };
 
 /// PAL5 leaf/sprite-card resolver backed by `Config/uvlist.tb`. Maps a model's
@@ -72,6 +77,82 @@ pub struct MapBlock {
     pub col: u32,
     pub mp: fileformats::pal5::mp::MpFile,
     pub alp: Option<fileformats::pal5::alp::AlpFile>,
+    /// Hash of the raw `.mp` and `.alp` bytes, for keying anything
+    /// cached from this block.
+    pub source: CacheKey,
+}
+
+/// One map block's raw files, read from the vfs on the main thread so
+/// [`MapBlockFiles::decode`] can run on an asset-streamer worker.
+pub struct MapBlockFiles {
+    map_name: String,
+    row: u32,
+    col: u32,
+    mp: Vec<u8>,
+    alp: Option<Vec<u8>>,
+    ctr: Option<Vec<u8>>,
+}
+
+/// A decoded [`MapBlock`] and its grass leaves (empty when the block
+/// ships no `.ctr`).
+pub struct DecodedMapBlock {
+    pub block: MapBlock,
+    pub grass: Vec<GrassLeaf>,
+}
+
+impl MapBlockFiles {
+    /// Decode the block. Touches no engine state, so it is safe to call
+    /// off the main thread. Returns `None` when the `.mp` doesn't decode;
+    /// a bad `.alp` or `.ctr` only drops the splat weights or the grass.
+    pub fn decode(self) -> Option<DecodedMapBlock> {
+        use fileformats::pal5::alp::AlpFile;
+        use fileformats::pal5::mp::MpFile;
+
+        let mp = match MpFile::read(&self.mp) {
+            Ok(mp) => mp,
+            Err(err) => {
+                log::warn!(
+                    "Pal5 terrain block {} failed: {}",
+                    block_path(&self.map_name, self.row, self.col, "mp"),
+                    err
+                );
+                return None;
+            }
+        };
+        let alp = self.alp.as_ref().and_then(|raw| match AlpFile::read(raw) {
+            Ok(alp) => Some(alp),
+            Err(err) => {
+                log::warn!(
+                    "Pal5 alphamap {} failed: {}",
+                    alphamap_path(&self.map_name, self.row, self.col),
+                    err
+                );
+                None
+            }
+        });
+        let grass = self
+            .ctr
+            .as_ref()
+            .map(|raw| decode_block_ctr(&self.map_name, self.row, self.col, raw))
+            .unwrap_or_default();
+
+        let source = CacheKey::new(
+            "pal5_map_block",
+            0,
+            &[&self.mp, self.alp.as_deref().unwrap_or_default()],
+        );
+
+        Some(DecodedMapBlock {
+            block: MapBlock {
+                row: self.row,
+                col: self.col,
+                mp,
+                alp,
+                source,
+            },
+            grass,
+        })
+    }
 }
 
 pub struct AssetLoader {
@@ -114,15 +195,11 @@ impl AssetLoader {
         // so the per-block node lists concatenate directly.
         let blocks = self.map_blocks(map_name, "nod");
         let mut merged: Option<NodFile> = None;
-        for (r, c) in &blocks {
-            let path = format!("/Map/{}/{}_{}_{}.nod", map_name, map_name, r, c);
-            let nod = match self.vfs.read_to_end(&path) {
-                Ok(bytes) => NodFile::read(&mut Cursor::new(bytes))?,
-                Err(err) => {
-                    log::warn!("Pal5 nod block {} unreadable: {}", path, err);
-                    continue;
-                }
+        for (r, c) in blocks {
+            let Some(raw) = self.read_block_nod(map_name, r, c) else {
+                continue;
             };
+            let nod = decode_block_nod(raw)?;
             match &mut merged {
                 Some(acc) => acc.nodes.extend(nod.nodes),
                 None => merged = Some(nod),
@@ -131,42 +208,28 @@ impl AssetLoader {
         merged.ok_or_else(|| anyhow::anyhow!("no .nod blocks found for map '{}'", map_name))
     }
 
+    /// Read one block's `.nod` for [`decode_block_nod`]; `None` (logged)
+    /// when it is unreadable.
+    pub fn read_block_nod(&self, map_name: &str, r: u32, c: u32) -> Option<Vec<u8>> {
+        let path = block_path(map_name, r, c, "nod");
+        match self.vfs.read_to_end(&path) {
+            Ok(raw) => Some(raw),
+            Err(err) => {
+                log::warn!("Pal5 nod block {} unreadable: {}", path, err);
+                None
+            }
+        }
+    }
+
     /// Decode the grass (`<map>_<r>_<c>.ctr`) for one terrain block. Each
     /// `.ctr` describes that block's grass as texture layers over the block's
     /// `16×16` grass grid (see [`fileformats::pal5::ctr`]); the renderer drapes
     /// it on the block's terrain heightfield. Returns an empty `Vec` when the
     /// block ships no grass.
-    pub fn load_block_ctr(
-        &self,
-        map_name: &str,
-        r: u32,
-        c: u32,
-    ) -> Vec<fileformats::pal5::ctr::GrassLeaf> {
-        use fileformats::pal5::ctr::CtrFile;
-
-        let path = format!("/Map/{}/{}_{}_{}.ctr", map_name, map_name, r, c);
-        let raw = match self.vfs.read_to_end(&path) {
-            Ok(raw) => raw,
-            Err(_) => return Vec::new(), // block simply has no grass file
-        };
-        match CtrFile::read(&raw) {
-            Ok(ctr) => {
-                let layers = ctr.leaves.iter().filter(|l| !l.density.is_empty()).count();
-                log::info!(
-                    "Pal5 grass block {} ({},{}): depth {}, {} leaves ({} grid layers)",
-                    map_name,
-                    r,
-                    c,
-                    ctr.depth,
-                    ctr.leaves.len(),
-                    layers,
-                );
-                ctr.leaves
-            }
-            Err(err) => {
-                log::warn!("Pal5 grass block {} decode failed: {}", path, err);
-                Vec::new()
-            }
+    pub fn load_block_ctr(&self, map_name: &str, r: u32, c: u32) -> Vec<GrassLeaf> {
+        match self.vfs.read_to_end(block_path(map_name, r, c, "ctr")) {
+            Ok(raw) => decode_block_ctr(map_name, r, c, &raw),
+            Err(_) => Vec::new(), // block simply has no grass file
         }
     }
 
@@ -175,7 +238,7 @@ impl AssetLoader {
     /// contiguous grid from the origin, so we stop probing a row at its
     /// first gap and stop probing rows once a row's first column is absent.
     /// Single-block maps return just `(0, 0)`.
-    fn map_blocks(&self, map_name: &str, ext: &str) -> Vec<(u32, u32)> {
+    pub fn map_blocks(&self, map_name: &str, ext: &str) -> Vec<(u32, u32)> {
         const MAX_BLOCK: u32 = 16;
         let exists = |r: u32, c: u32| {
             let path = format!("/Map/{}/{}_{}_{}.{}", map_name, map_name, r, c, ext);
@@ -245,45 +308,43 @@ impl AssetLoader {
     /// rasters). Blocks with an unreadable/undecodable `.mp` are skipped;
     /// a missing `.alp` yields `alp = None` (that block renders base-only).
     pub fn load_map_blocks(&self, map_name: &str) -> Vec<MapBlock> {
-        use fileformats::pal5::alp::AlpFile;
-        use fileformats::pal5::mp::MpFile;
+        self.map_blocks(map_name, "mp")
+            .into_iter()
+            .filter_map(|(r, c)| self.read_map_block_files(map_name, r, c))
+            .filter_map(|files| files.decode())
+            .map(|decoded| decoded.block)
+            .collect()
+    }
 
-        let mut out = Vec::new();
-        for (r, c) in self.map_blocks(map_name, "mp") {
-            let mp_path = format!("/Map/{}/{}_{}_{}.mp", map_name, map_name, r, c);
-            let mp = match self
-                .vfs
-                .read_to_end(&mp_path)
-                .map_err(anyhow::Error::from)
-                .and_then(|raw| MpFile::read(&raw).map_err(anyhow::Error::from))
-            {
-                Ok(mp) => mp,
-                Err(err) => {
-                    log::warn!("Pal5 terrain block {} failed: {}", mp_path, err);
-                    continue;
-                }
-            };
-            let alp_path = format!("/Map/{}/alphamap_{}_{}.alp", map_name, r, c);
-            let alp = match self
-                .vfs
-                .read_to_end(&alp_path)
-                .map_err(anyhow::Error::from)
-                .and_then(|raw| AlpFile::read(&raw).map_err(anyhow::Error::from))
-            {
-                Ok(alp) => Some(alp),
-                Err(err) => {
-                    log::warn!("Pal5 alphamap {} failed: {}", alp_path, err);
-                    None
-                }
-            };
-            out.push(MapBlock {
-                row: r,
-                col: c,
-                mp,
-                alp,
-            });
-        }
-        out
+    /// Read one block's `.mp`, `.alp` and `.ctr` without decoding them.
+    /// `None` (logged) when the `.mp` itself is unreadable.
+    pub fn read_map_block_files(&self, map_name: &str, r: u32, c: u32) -> Option<MapBlockFiles> {
+        let mp_path = block_path(map_name, r, c, "mp");
+        let mp = match self.vfs.read_to_end(&mp_path) {
+            Ok(raw) => raw,
+            Err(err) => {
+                log::warn!("Pal5 terrain block {} failed: {}", mp_path, err);
+                return None;
+            }
+        };
+        let alp_path = alphamap_path(map_name, r, c);
+        let alp = match self.vfs.read_to_end(&alp_path) {
+            Ok(raw) => Some(raw),
+            Err(err) => {
+                log::warn!("Pal5 alphamap {} failed: {}", alp_path, err);
+                None
+            }
+        };
+        let ctr = self.vfs.read_to_end(block_path(map_name, r, c, "ctr")).ok();
+
+        Some(MapBlockFiles {
+            map_name: map_name.to_string(),
+            row: r,
+            col: c,
+            mp,
+            alp,
+            ctr,
+        })
     }
 
     /// Read a raw asset file (e.g. a terrain `.dds`) from the vfs.
@@ -331,36 +392,77 @@ impl AssetLoader {
     /// [`load_skybox`](Self::load_skybox): the camera-locked sky dome must
     /// never fade to the fog color.
     fn load_model_ex(&self, model_path: &str, fog_exempt: bool) -> anyhow::Result<ComRc<IEntity>> {
-        // PAL5's `role_*.bin` stores Windows backslash separators in
-        // `file_path`; normalise to forward slashes so downstream log
-        // lines (and `Pal5TextureResolver`'s path math) see a uniform
-        // POSIX path. `packfs::pkg::pkg_archive::open` re-normalises
-        // `/` → `\` internally, so the pkg lookup for the `.dff`
-        // itself keeps working unchanged.
-        let model_path = format!("/Model/{}", model_path.replace('\\', "/"));
+        let model_path = model_vfs_path(model_path);
         create_entity_from_dff_model(
             &self.component_factory,
             &self.vfs,
             model_path.clone(),
             model_path,
             true,
-            &DffLoaderConfig {
-                texture_resolver: &self.texture_resolver,
-                keep_right_to_render_only: false,
-                force_unique_materials: false,
-                ignore_root_frame_translation: false,
-
-                bsp_lightmap_tint: None,
-                dynamic_lighting: true,
-                fog_exempt,
-                foliage_resolver: self
-                    .foliage_resolver
-                    .as_ref()
-                    .map(|r| r as &dyn FoliageResolver),
-            },
+            &self.dff_config(fog_exempt),
+        )
+    }
+
+    /// Read a model's `.dff` bytes. Together with `rwbs::read_dff` and
+    /// [`build_model`](Self::build_model) this is [`load_model`](Self::load_model)
+    /// split so the parse can run off the main thread.
+    pub fn read_model_file(&self, model_path: &str) -> anyhow::Result<Vec<u8>> {
+        let model_path = model_vfs_path(model_path);
+        self.vfs
+            .read_to_end(&model_path)
+            .with_context(|| format!("reading DFF {}", model_path))
+    }
+
+    /// Build a model entity from clumps parsed out of
+    /// [`read_model_file`](Self::read_model_file)'s bytes. `source_key`
+    /// comes from `dff_cache_key` over the same bytes.
+    pub fn build_model(
+        &self,
+        model_path: &str,
+        clumps: Vec<Clump>,
+        source_key: CacheKey,
+    ) -> ComRc<IEntity> {
+        let model_path = model_vfs_path(model_path);
+        create_entity_from_dff_clumps(
+            &self.component_factory,
+            &self.vfs,
+            model_path.clone(),
+            model_path,
+            true,
+            clumps,
+            Some(source_key),
+            &self.dff_config(false),
         )
     }
 
+    /// Load an action (`<act>.anm`) stored next to a character model.
+    pub fn load_model_anm(
+        &self,
+        model_path: &str,
+        act: &str,
+    ) -> anyhow::Result<Vec<Vec<AnimKeyFrame>>> {
+        let model_path = model_vfs_path(model_path);
+        let dir = model_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+        load_anm(&self.vfs, format!("{}/{}.anm", dir, act))
+    }
+
+    fn dff_config(&self, fog_exempt: bool) -> DffLoaderConfig<'_> {
+        DffLoaderConfig {
+            texture_resolver: &self.texture_resolver,
+            keep_right_to_render_only: false,
+            force_unique_materials: false,
+            ignore_root_frame_translation: false,
+
+            bsp_lightmap_tint: None,
+            dynamic_lighting: true,
+            fog_exempt,
+            foliage_resolver: self
+                .foliage_resolver
+                .as_ref()
+                .map(|r| r as &dyn FoliageResolver),
+        }
+    }
+
     /// Load the scene's skybox model by its `role_*.bin` asset id (the
     /// `SkyBoxID` carried in `envinfo.env`) and tag it with a
     /// [`SkyboxComponent`] so it stays centred on the camera every frame.
@@ -401,6 +503,82 @@ impl AssetLoader {
         log::info!("Pal5 skybox loaded: asset {} -> '{}'", asset_id, file_path);
         Some(entity)
     }
+
+    /// Decode a scripted camera path (`camera.ChangeCameraPath`) by its
+    /// `role_*.bin` asset id. Returns `None` when the id is absent from the
+    /// role index or the file is missing/undecodable; the caller keeps the
+    /// current shot.
+    pub fn load_camera_path(&self, asset_id: u32) -> Option<CameraPathFile> {
+        let Some(asset) = self.index.get(&asset_id) else {
+            log::warn!("Pal5 camera path asset {} not in the role index", asset_id);
+            return None;
+        };
+        let path = model_vfs_path(&asset.file_path.to_string());
+        match self
+            .vfs
+            .read_to_end(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|raw| CameraPathFile::read(&raw))
+        {
+            Ok(camera_path) => Some(camera_path),
+            Err(err) => {
+                log::warn!("Pal5 camera path {} failed: {}", path, err);
+                None
+            }
+        }
+    }
+}
+
+/// PAL5's `role_*.bin` stores Windows backslash separators in
+/// `file_path`; normalise to forward slashes so downstream log lines (and
+/// `Pal5TextureResolver`'s path math) see a uniform POSIX path.
+/// `packfs::pkg::pkg_archive::open` re-normalises `/` → `\` internally, so
+/// the pkg lookup for the `.dff` itself keeps working unchanged.
+fn model_vfs_path(model_path: &str) -> String {
+    format!("/Model/{}", model_path.replace('\\', "/"))
+}
+
+fn block_path(map_name: &str, r: u32, c: u32, ext: &str) -> String {
+    format!("/Map/{}/{}_{}_{}.{}", map_name, map_name, r, c, ext)
+}
+
+fn alphamap_path(map_name: &str, r: u32, c: u32) -> String {
+    format!("/Map/{}/alphamap_{}_{}.alp", map_name, r, c)
+}
+
+/// Decode one block's `.nod` object list. Safe off the main thread.
+pub fn decode_block_nod(raw: Vec<u8>) -> anyhow::Result<NodFile> {
+    Ok(NodFile::read(&mut Cursor::new(raw))?)
+}
+
+/// Decode the grass (`.ctr`) of block `(r, c)`, logging its layer count.
+/// An undecodable file yields no grass.
+fn decode_block_ctr(map_name: &str, r: u32, c: u32, raw: &[u8]) -> Vec<GrassLeaf> {
+    use fileformats::pal5::ctr::CtrFile;
+
+    match CtrFile::read(raw) {
+        Ok(ctr) => {
+            let layers = ctr.leaves.iter().filter(|l| !l.density.is_empty()).count();
+            log::info!(
+                "Pal5 grass block {} ({},{}): depth {}, {} leaves ({} grid layers)",
+                map_name,
+                r,
+                c,
+                ctr.depth,
+                ctr.leaves.len(),
+                layers,
+            );
+            ctr.leaves
+        }
+        Err(err) => {
+            log::warn!(
+                "Pal5 grass block {} decode failed: {}",
+                block_path(map_name, r, c, "ctr"),
+                err
+            );
+            Vec::new()
+        }
+    }
 }
 
 fn load_foliage_resolver(vfs: &MiniFs) -> Option<Pal5FoliageResolver> {
@@ -0,0 +1,189 @@
+//! PAL5 scripted camera motion: spline tracks for `camera.ChangeCameraPath`
+//! and the procedural `camera.Shake` jitter.
+//!
+//! Both are pure functions of elapsed time; the story context owns the
+//! clock and applies the sampled pose, so the free-fly debug camera
+//! freezes them simply by not ticking the context.
+
+use std::f32::consts::TAU;
+
+use fileformats::pal5::campath::CameraPathFile;
+use radiance::math::Vec3;
+
+/// Shake frequencies (Hz) per axis. Mutually incommensurate so the
+/// jitter never settles into a visible loop.
+const SHAKE_FREQUENCIES: [[f32; 2]; 3] = [[13.0, 7.3], [17.0, 5.1], [11.0, 8.7]];
+
+/// A camera path as a Catmull-Rom spline through its keys, for both the
+/// eye and the look-at point.
+pub struct CameraTrack {
+    times: Vec<f32>,
+    eyes: Vec<Vec3>,
+    looks: Vec<Vec3>,
+}
+
+impl CameraTrack {
+    /// `None` for a path without keys.
+    pub fn new(path: &CameraPathFile) -> Option<Self> {
+        if path.keys.is_empty() {
+            return None;
+        }
+        let to_vec3 = |v: [f32; 3]| Vec3::new(v[0], v[1], v[2]);
+        Some(Self {
+            times: path.keys.iter().map(|k| k.time).collect(),
+            eyes: path.keys.iter().map(|k| to_vec3(k.eye)).collect(),
+            looks: path.keys.iter().map(|k| to_vec3(k.look)).collect(),
+        })
+    }
+
+    /// Seconds from the start of the path to its last key.
+    pub fn duration(&self) -> f32 {
+        self.times.last().map_or(0.0, |t| t.max(0.0))
+    }
+
+    /// `(eye, look)` at `t` seconds into the path; clamps to the first
+    /// and last keys outside the path's time range.
+    pub fn sample(&self, t: f32) -> (Vec3, Vec3) {
+        let (i, u) = self.segment(t);
+        (spline(&self.eyes, i, u), spline(&self.looks, i, u))
+    }
+
+    /// The key index starting the segment containing `t`, and how far
+    /// (`0..=1`) into that segment `t` is.
+    fn segment(&self, t: f32) -> (usize, f32) {
+        let last = self.times.len().saturating_sub(1);
+        let i = self.times.partition_point(|&k| k <= t).saturating_sub(1);
+        if i >= last {
+            return (last, 0.0);
+        }
+        let span = self.times[i + 1] - self.times[i];
+        let u = if span > 0.0 {
+            ((t - self.times[i]) / span).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        (i, u)
+    }
+}
+
+/// Uniform Catmull-Rom between `points[i]` and `points[i + 1]`, with
+/// the end points duplicated so the curve starts and stops on a key.
+fn spline(points: &[Vec3], i: usize, u: f32) -> Vec3 {
+    let last = points.len() - 1;
+    let p0 = &points[i.saturating_sub(1)];
+    let p1 = &points[i];
+    let p2 = &points[(i + 1).min(last)];
+    let p3 = &points[(i + 2).min(last)];
+    let (u2, u3) = (u * u, u * u * u);
+    let axis = |a: f32, b: f32, c: f32, d: f32| {
+        0.5 * (2.0 * b
+            + (c - a) * u
+            + (2.0 * a - 5.0 * b + 4.0 * c - d) * u2
+            + (3.0 * b - a - 3.0 * c + d) * u3)
+    };
+    Vec3::new(
+        axis(p0.x, p1.x, p2.x, p3.x),
+        axis(p0.y, p1.y, p2.y, p3.y),
+        axis(p0.z, p1.z, p2.z, p3.z),
+    )
+}
+
+/// `camera.Shake`: a jitter added on top of whatever drives the camera,
+/// fading out linearly over its duration.
+pub struct CameraShake {
+    amplitude: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl CameraShake {
+    pub fn new(amplitude: f32, duration: f32) -> Self {
+        Self {
+            amplitude,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advance the shake; returns `false` once it has run out.
+    pub fn update(&mut self, delta_sec: f32) -> bool {
+        self.elapsed += delta_sec;
+        self.elapsed < self.duration
+    }
+
+    /// The offset to add to the camera eye and look-at point this frame.
+    pub fn offset(&self) -> Vec3 {
+        if self.elapsed >= self.duration {
+            return Vec3::new(0.0, 0.0, 0.0);
+        }
+        let strength = self.amplitude * (1.0 - self.elapsed / self.duration);
+        let t = self.elapsed;
+        let axis = |[f0, f1]: [f32; 2]| {
+            strength * 0.5 * ((TAU * f0 * t).sin() + (TAU * f1 * t + 1.0).sin())
+        };
+        Vec3::new(
+            axis(SHAKE_FREQUENCIES[0]),
+            axis(SHAKE_FREQUENCIES[1]),
+            axis(SHAKE_FREQUENCIES[2]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fileformats::pal5::campath::CameraKey;
+
+    use super::*;
+
+    fn track(keys: &[(f32, f32)]) -> CameraTrack {
+        CameraTrack::new(&CameraPathFile {
+            keys: keys
+                .iter()
+                .map(|&(time, x)| CameraKey {
+                    time,
+                    eye: [x, 0.0, 0.0],
+                    look: [x, 0.0, 1.0],
+                })
+                .collect(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn track_passes_through_keys_and_clamps() {
+        let track = track(&[(0.0, 0.0), (1.0, 10.0), (3.0, 30.0)]);
+        assert_eq!(track.duration(), 3.0);
+        for (t, x) in [
+            (-1.0, 0.0),
+            (0.0, 0.0),
+            (1.0, 10.0),
+            (3.0, 30.0),
+            (9.0, 30.0),
+        ] {
+            let (eye, look) = track.sample(t);
+            assert!((eye.x - x).abs() < 1e-4, "t={} eye.x={}", t, eye.x);
+            assert!((look.z - 1.0).abs() < 1e-4);
+        }
+        let (mid, _) = track.sample(2.0);
+        assert!(mid.x > 10.0 && mid.x < 30.0);
+    }
+
+    #[test]
+    fn single_key_track_holds_still() {
+        let track = track(&[(0.0, 5.0)]);
+        assert_eq!(track.duration(), 0.0);
+        assert_eq!(track.sample(1.0).0.x, 5.0);
+        assert!(CameraTrack::new(&CameraPathFile { keys: Vec::new() }).is_none());
+    }
+
+    #[test]
+    fn shake_is_bounded_and_settles() {
+        let mut shake = CameraShake::new(8.0, 0.5);
+        while shake.update(1.0 / 60.0) {
+            let o = shake.offset();
+            assert!(o.x.abs() <= 8.0 && o.y.abs() <= 8.0 && o.z.abs() <= 8.0);
+        }
+        let o = shake.offset();
+        assert_eq!((o.x, o.y, o.z), (0.0, 0.0, 0.0));
+    }
+}
@@ -1,9 +1,13 @@
 pub mod asset_loader;
+pub mod camera;
 #[macro_use]
 pub mod comdef {
     include!(concat!(env!("OUT_DIR"), "/shared_openpal5_comdef.rs"));
 }
 pub mod grass;
+pub mod npc;
+pub mod role;
 pub mod scene;
 pub mod script;
+pub mod states;
 pub mod terrain;
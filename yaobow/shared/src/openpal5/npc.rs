@@ -0,0 +1,536 @@
+//! PAL5 scripted NPC actors.
+//!
+//! [`Pal5Npc`] wraps an NPC entity with everything the `npc.*` commands
+//! drive over time: action playback with a queue of chained actions,
+//! turn and move tweens, and patrol loops over waypoints. The
+//! `global.WaitForNpc*` waits poll [`is_moving`](Pal5Npc::is_moving),
+//! [`is_turning`](Pal5Npc::is_turning) and
+//! [`is_acting`](Pal5Npc::is_acting).
+
+use std::{
+    collections::{HashMap, VecDeque},
+    f32::consts::PI,
+    rc::Rc,
+};
+
+use crosscom::ComRc;
+use radiance::{
+    comdef::{IArmatureComponent, IArmatureComponentExt, IEntity, IEntityExt},
+    components::mesh::skinned_mesh::{AnimKeyFrame, AnimationState},
+    math::Vec3,
+};
+
+use super::{
+    asset_loader::AssetLoader,
+    role::{
+        IDLE_ACTS, RUN_ACTS, RUN_SPEED, RoleMotion, WALK_ACTS, WALK_SPEED, armature_of, load_action,
+    },
+    terrain::TerrainHeights,
+};
+
+/// Radians per second an NPC turns at.
+const TURN_SPEED: f32 = 2. * PI;
+
+/// Distance at which a move counts as arrived.
+const ARRIVE_DISTANCE: f32 = 1.;
+
+/// How an NPC walks its patrol waypoints (`npc.SetPatrolType`).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PatrolType {
+    /// Walk the waypoints once and stop at the last one.
+    Once,
+    /// Walk back to the first waypoint after the last one.
+    Loop,
+    /// Walk the waypoints back and forth.
+    PingPong,
+}
+
+impl PatrolType {
+    pub fn from_i32(v: i32) -> Self {
+        match v {
+            0 => PatrolType::Once,
+            2 => PatrolType::PingPong,
+            _ => PatrolType::Loop,
+        }
+    }
+}
+
+struct Movement {
+    target: Vec3,
+    speed: f32,
+    motion: RoleMotion,
+    /// `npc.FloatTo`: move in 3D instead of following the ground.
+    float: bool,
+    /// Patrol legs don't block `WaitForNpcPos`.
+    patrol: bool,
+}
+
+struct Turn {
+    from: f32,
+    delta: f32,
+    elapsed: f32,
+    duration: f32,
+}
+
+pub struct Pal5Npc {
+    entity: ComRc<IEntity>,
+    armature: Option<ComRc<IArmatureComponent>>,
+    asset_loader: Rc<AssetLoader>,
+    model_path: String,
+    /// Loaded actions by act name; a miss is cached as no keyframes.
+    actions: HashMap<String, Vec<Vec<AnimKeyFrame>>>,
+    idle: Vec<Vec<AnimKeyFrame>>,
+    walk: Vec<Vec<AnimKeyFrame>>,
+    run: Vec<Vec<AnimKeyFrame>>,
+
+    /// The locomotion action playing, `None` while a script action plays.
+    motion: Option<RoleMotion>,
+    /// A one-shot script action is playing.
+    acting: bool,
+    /// Script actions queued behind the current one (`npc.AddAnimChain`).
+    chain: VecDeque<String>,
+
+    yaw: f32,
+    turn: Option<Turn>,
+    movement: Option<Movement>,
+    /// `npc.SetSpeed` walking speed.
+    speed: f32,
+
+    patrol: Vec<Vec3>,
+    patrol_type: PatrolType,
+    patrol_next: usize,
+    patrol_forward: bool,
+    patrol_done: bool,
+}
+
+impl Pal5Npc {
+    pub fn new(asset_loader: Rc<AssetLoader>, entity: ComRc<IEntity>, model_path: &str) -> Self {
+        let armature = armature_of(&entity);
+        let yaw = {
+            let transform = entity.transform();
+            let m = *transform.borrow().matrix();
+            // The model faces away from its local +Z (see `look_at`).
+            (-m[0][2]).atan2(-m[2][2])
+        };
+
+        let mut npc = Self {
+            idle: load_action(&asset_loader, model_path, IDLE_ACTS),
+            walk: load_action(&asset_loader, model_path, WALK_ACTS),
+            run: load_action(&asset_loader, model_path, RUN_ACTS),
+            entity,
+            armature,
+            asset_loader,
+            model_path: model_path.to_string(),
+            actions: HashMap::new(),
+            motion: None,
+            acting: false,
+            chain: VecDeque::new(),
+            yaw,
+            turn: None,
+            movement: None,
+            speed: WALK_SPEED,
+            patrol: Vec::new(),
+            patrol_type: PatrolType::Loop,
+            patrol_next: 0,
+            patrol_forward: true,
+            patrol_done: false,
+        };
+        npc.play_motion(RoleMotion::Idle);
+        npc
+    }
+
+    pub fn entity(&self) -> &ComRc<IEntity> {
+        &self.entity
+    }
+
+    pub fn position(&self) -> Vec3 {
+        self.entity.transform().borrow().position()
+    }
+
+    /// Place the NPC, cancelling any move in progress.
+    pub fn set_position(&mut self, position: &Vec3) {
+        self.movement = None;
+        self.entity.transform().borrow_mut().set_position(position);
+        if !self.acting {
+            self.play_motion(RoleMotion::Idle);
+        }
+    }
+
+    /// Walk (or run) to `target` over the ground.
+    pub fn move_to(&mut self, target: Vec3, motion: RoleMotion) {
+        let speed = match motion {
+            RoleMotion::Run => self.speed * RUN_SPEED / WALK_SPEED,
+            _ => self.speed,
+        };
+        self.start_move(target, speed, motion, false, false);
+    }
+
+    /// Glide straight to `target` in 3D, ignoring the ground.
+    pub fn float_to(&mut self, target: Vec3) {
+        self.start_move(target, self.speed, RoleMotion::Idle, true, false);
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        if speed > 0. {
+            self.speed = speed;
+        }
+    }
+
+    /// Turn to face world yaw `yaw` (radians, 0 = facing +Z).
+    pub fn turn_to(&mut self, yaw: f32) {
+        let delta = shortest_turn(self.yaw, yaw);
+        self.turn = Some(Turn {
+            from: self.yaw,
+            delta,
+            elapsed: 0.,
+            duration: delta.abs() / TURN_SPEED,
+        });
+    }
+
+    /// Turn to face world point `target`.
+    pub fn turn_towards(&mut self, target: &Vec3) {
+        let position = self.position();
+        let (dx, dz) = (target.x - position.x, target.z - position.z);
+        if dx != 0. || dz != 0. {
+            self.turn_to(dx.atan2(dz));
+        }
+    }
+
+    /// Play action `act`, replacing the current one and its chain.
+    /// A looping action holds until the next command; a one-shot action
+    /// returns to idle (or the next chained action) when it ends.
+    pub fn set_action(&mut self, act: &str, looping: bool) {
+        self.chain.clear();
+        self.play_action(act, looping);
+    }
+
+    /// Queue `act` to play once the current action ends.
+    pub fn add_chain(&mut self, act: &str) {
+        if self.acting {
+            self.chain.push_back(act.to_string());
+        } else {
+            self.play_action(act, false);
+        }
+    }
+
+    pub fn add_patrol_point(&mut self, point: Vec3) {
+        self.patrol.push(point);
+        self.patrol_done = false;
+    }
+
+    pub fn set_patrol_type(&mut self, patrol_type: PatrolType) {
+        self.patrol_type = patrol_type;
+        self.patrol_done = false;
+    }
+
+    /// A scripted move is in progress (patrols don't count).
+    pub fn is_moving(&self) -> bool {
+        self.movement.as_ref().is_some_and(|m| !m.patrol)
+    }
+
+    pub fn is_turning(&self) -> bool {
+        self.turn.is_some()
+    }
+
+    /// A one-shot action, or a chain of them, is still playing.
+    pub fn is_acting(&self) -> bool {
+        self.acting
+    }
+
+    /// Jump every scripted tween to its end (agent fast-forward).
+    pub fn finish(&mut self) {
+        if let Some(movement) = self.movement.take_if(|m| !m.patrol) {
+            let mut target = movement.target;
+            if !movement.float {
+                target.y = self.position().y;
+            }
+            self.entity.transform().borrow_mut().set_position(&target);
+        }
+        if let Some(turn) = self.turn.take() {
+            self.yaw = turn.from + turn.delta;
+            self.apply_facing();
+        }
+        if self.acting {
+            self.chain.clear();
+            self.acting = false;
+            self.play_motion(RoleMotion::Idle);
+        }
+    }
+
+    pub fn update(&mut self, heights: &TerrainHeights, delta_sec: f32) {
+        self.update_patrol();
+        self.update_movement(heights, delta_sec);
+        self.update_turn(delta_sec);
+        self.update_action();
+    }
+
+    fn start_move(
+        &mut self,
+        target: Vec3,
+        speed: f32,
+        motion: RoleMotion,
+        float: bool,
+        patrol: bool,
+    ) {
+        self.movement = Some(Movement {
+            target,
+            speed,
+            motion,
+            float,
+            patrol,
+        });
+        self.turn = None;
+        if !float && !self.acting {
+            self.play_motion(motion);
+        }
+    }
+
+    fn update_patrol(&mut self) {
+        if self.movement.is_some() || self.patrol.is_empty() || self.patrol_done {
+            return;
+        }
+        let Some(target) = self.patrol.get(self.patrol_next).copied() else {
+            self.patrol_next = 0;
+            return;
+        };
+        self.start_move(target, self.speed, RoleMotion::Walk, false, true);
+    }
+
+    fn update_movement(&mut self, heights: &TerrainHeights, delta_sec: f32) {
+        let Some(movement) = &self.movement else {
+            return;
+        };
+        let position = self.position();
+        let mut target = movement.target;
+        if !movement.float {
+            target.y = position.y;
+        }
+
+        let (mut next, arrived) = step_towards(&position, &target, movement.speed * delta_sec);
+        if !movement.float {
+            if let Some(y) = heights.height_at(next.x, next.z) {
+                next.y = y;
+            }
+            let (dx, dz) = (target.x - position.x, target.z - position.z);
+            if dx != 0. || dz != 0. {
+                self.yaw = dx.atan2(dz);
+            }
+        }
+        self.entity.transform().borrow_mut().set_position(&next);
+        self.apply_facing();
+
+        if !arrived {
+            return;
+        }
+        let patrol = movement.patrol;
+        self.movement = None;
+        if patrol {
+            match next_waypoint(
+                self.patrol_next,
+                self.patrol.len(),
+                self.patrol_type,
+                &mut self.patrol_forward,
+            ) {
+                Some(next) => self.patrol_next = next,
+                None => self.patrol_done = true,
+            }
+        }
+        if !self.acting && (!patrol || self.patrol_done) {
+            self.play_motion(RoleMotion::Idle);
+        }
+    }
+
+    fn update_turn(&mut self, delta_sec: f32) {
+        let Some(turn) = self.turn.as_mut() else {
+            return;
+        };
+        turn.elapsed += delta_sec;
+        let t = if turn.duration > 0. {
+            (turn.elapsed / turn.duration).min(1.)
+        } else {
+            1.
+        };
+        self.yaw = turn.from + turn.delta * t;
+        if t >= 1. {
+            self.turn = None;
+        }
+        self.apply_facing();
+    }
+
+    fn update_action(&mut self) {
+        if !self.acting {
+            return;
+        }
+        let finished = self.armature.as_ref().is_none_or(|armature| {
+            matches!(
+                armature.animation_state(),
+                AnimationState::Stopped | AnimationState::NoAnimation
+            )
+        });
+        if !finished {
+            return;
+        }
+
+        match self.chain.pop_front() {
+            Some(act) => self.play_action(&act, false),
+            None => {
+                self.acting = false;
+                let motion = self
+                    .movement
+                    .as_ref()
+                    .map_or(RoleMotion::Idle, |m| m.motion);
+                self.play_motion(motion);
+            }
+        }
+    }
+
+    fn apply_facing(&self) {
+        let position = self.position();
+        let facing = Vec3::new(self.yaw.sin(), 0., self.yaw.cos());
+        self.entity
+            .transform()
+            .borrow_mut()
+            .look_at(&Vec3::add(&position, &facing))
+            .set_position(&position);
+    }
+
+    fn play_motion(&mut self, motion: RoleMotion) {
+        if self.motion == Some(motion) {
+            return;
+        }
+        self.motion = Some(motion);
+        let keyframes = match motion {
+            RoleMotion::Idle => &self.idle,
+            RoleMotion::Walk if !self.walk.is_empty() => &self.walk,
+            RoleMotion::Walk | RoleMotion::Run => &self.run,
+        };
+        play(self.armature.as_ref(), keyframes, true);
+    }
+
+    fn play_action(&mut self, act: &str, looping: bool) {
+        if !self.actions.contains_key(act) {
+            let keyframes = load_action(&self.asset_loader, &self.model_path, &[act]);
+            self.actions.insert(act.to_string(), keyframes);
+        }
+        let keyframes = &self.actions[act];
+        if keyframes.is_empty() {
+            return;
+        }
+
+        self.motion = None;
+        self.acting = !looping;
+        play(self.armature.as_ref(), keyframes, looping);
+    }
+}
+
+fn play(
+    armature: Option<&ComRc<IArmatureComponent>>,
+    keyframes: &[Vec<AnimKeyFrame>],
+    looping: bool,
+) {
+    let Some(armature) = armature else {
+        return;
+    };
+    if keyframes.is_empty() {
+        armature.stop();
+        return;
+    }
+    armature.set_animation(keyframes.to_vec(), vec![]);
+    armature.set_looping(looping);
+    armature.play();
+}
+
+/// Move from `position` up to `max` towards `target`; also returns
+/// whether `target` was reached.
+fn step_towards(position: &Vec3, target: &Vec3, max: f32) -> (Vec3, bool) {
+    let offset = Vec3::sub(target, position);
+    let distance = offset.norm();
+    if distance <= max.max(ARRIVE_DISTANCE) {
+        return (*target, true);
+    }
+
+    let step = Vec3::scalar_mul(max / distance, &offset);
+    (Vec3::add(position, &step), false)
+}
+
+/// Signed turn from yaw `from` to yaw `to`, the short way round.
+fn shortest_turn(from: f32, to: f32) -> f32 {
+    let delta = (to - from).rem_euclid(2. * PI);
+    if delta > PI { delta - 2. * PI } else { delta }
+}
+
+/// The waypoint after `current` in a `len`-point patrol, or `None` when
+/// a one-way patrol has reached its end. `forward` tracks the direction
+/// of a ping-pong patrol.
+fn next_waypoint(
+    current: usize,
+    len: usize,
+    patrol_type: PatrolType,
+    forward: &mut bool,
+) -> Option<usize> {
+    match patrol_type {
+        PatrolType::Once => (current + 1 < len).then_some(current + 1),
+        PatrolType::Loop => (len > 1).then(|| (current + 1) % len),
+        PatrolType::PingPong => {
+            if len < 2 {
+                return None;
+            }
+            if (*forward && current + 1 >= len) || (!*forward && current == 0) {
+                *forward = !*forward;
+            }
+            Some(if *forward { current + 1 } else { current - 1 })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_stops_at_the_target() {
+        let from = Vec3::new(0., 0., 0.);
+        let to = Vec3::new(10., 0., 0.);
+        let (next, arrived) = step_towards(&from, &to, 4.);
+        assert_eq!((next.x, arrived), (4., false));
+        let (next, arrived) = step_towards(&from, &to, 40.);
+        assert_eq!((next.x, arrived), (10., true));
+    }
+
+    #[test]
+    fn turns_take_the_short_way() {
+        assert!((shortest_turn(0., PI / 2.) - PI / 2.).abs() < 1e-5);
+        assert!((shortest_turn(0., 3. * PI / 2.) + PI / 2.).abs() < 1e-5);
+        assert!((shortest_turn(3. * PI / 2., 0.) - PI / 2.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn patrol_orders() {
+        let mut forward = true;
+        let walk = |patrol_type, forward: &mut bool| {
+            let mut at = 0;
+            let mut order = vec![at];
+            for _ in 0..5 {
+                match next_waypoint(at, 3, patrol_type, forward) {
+                    Some(next) => at = next,
+                    None => break,
+                }
+                order.push(at);
+            }
+            order
+        };
+        assert_eq!(walk(PatrolType::Once, &mut forward), [0, 1, 2]);
+        assert_eq!(walk(PatrolType::Loop, &mut forward), [0, 1, 2, 0, 1, 2]);
+        assert_eq!(walk(PatrolType::PingPong, &mut forward), [0, 1, 2, 1, 0, 1]);
+    }
+
+    #[test]
+    fn single_point_patrol_stops() {
+        let mut forward = true;
+        assert_eq!(next_waypoint(0, 1, PatrolType::Loop, &mut forward), None);
+        assert_eq!(
+            next_waypoint(0, 1, PatrolType::PingPong, &mut forward),
+            None
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! PAL5 party-leader control.
+//!
+//! [`Pal5RoleController`] walks the leader over the map: the ground
+//! comes from the block heightfields ([`TerrainHeights`]) and the map
+//! objects baked into the scene's collision world, which also stop the
+//! leader at walls. It plays the leader's idle / walk / run actions,
+//! drives a third-person follow camera and reports the `map.AddEvent`
+//! region the leader crossed. Input is the shared arrow-key / D-pad /
+//! left-stick movement and A / D / right-stick camera turning, so the
+//! agent's synthetic key and axis input moves the leader too.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crosscom::ComRc;
+use radiance::{
+    comdef::{IArmatureComponent, IArmatureComponentExt, IEntity, IEntityExt, IScene, ISceneExt},
+    components::{collision::CollisionWorldComponent, mesh::skinned_mesh::AnimKeyFrame},
+    input::{Axis, InputEngine},
+    math::Vec3,
+};
+
+use super::{asset_loader::AssetLoader, terrain::TerrainHeights};
+use crate::utils::{get_camera_rotation, get_moving_direction};
+
+pub(crate) const WALK_SPEED: f32 = 160.;
+pub(crate) const RUN_SPEED: f32 = 420.;
+
+/// Left-stick deflection below which the leader walks. Keys always run.
+const WALK_DEFLECTION: f32 = 0.6;
+
+/// Clearance kept between the leader and an object wall.
+const BODY_RADIUS: f32 = 30.;
+
+/// Wall probes are cast this far above the feet; anything lower is a
+/// step the leader climbs onto.
+const STEP_UP: f32 = 40.;
+
+const CAMERA_DISTANCE: f32 = 600.;
+const CAMERA_HEIGHT: f32 = 350.;
+const CAMERA_LOOK_HEIGHT: f32 = 100.;
+
+/// Action files tried for each motion, in order. The first names follow
+/// the PAL4 actor convention.
+pub(crate) const IDLE_ACTS: &[&str] = &["C01", "stand", "idle"];
+pub(crate) const WALK_ACTS: &[&str] = &["C02", "walk"];
+pub(crate) const RUN_ACTS: &[&str] = &["C03", "run"];
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum RoleMotion {
+    Idle,
+    Walk,
+    Run,
+}
+
+pub struct Pal5RoleController {
+    entity: ComRc<IEntity>,
+    armature: Option<ComRc<IArmatureComponent>>,
+    idle: Vec<Vec<AnimKeyFrame>>,
+    walk: Vec<Vec<AnimKeyFrame>>,
+    run: Vec<Vec<AnimKeyFrame>>,
+    motion: Option<RoleMotion>,
+    camera_rotation: f32,
+    camera_eye: Vec3,
+    camera_look: Vec3,
+}
+
+impl Pal5RoleController {
+    /// Take control of `entity`, loaded from `model_path`. Actions that
+    /// can't be found leave the leader in its bind pose for that motion.
+    pub fn new(asset_loader: &AssetLoader, entity: ComRc<IEntity>, model_path: &str) -> Self {
+        let armature = armature_of(&entity);
+        if armature.is_none() {
+            log::warn!("PAL5: leader model '{}' has no armature", model_path);
+        }
+
+        let mut controller = Self {
+            entity,
+            armature,
+            idle: load_action(asset_loader, model_path, IDLE_ACTS),
+            walk: load_action(asset_loader, model_path, WALK_ACTS),
+            run: load_action(asset_loader, model_path, RUN_ACTS),
+            motion: None,
+            camera_rotation: 0.,
+            camera_eye: Vec3::new_zeros(),
+            camera_look: Vec3::new_zeros(),
+        };
+        controller.set_motion(RoleMotion::Idle);
+        controller
+    }
+
+    pub fn entity(&self) -> &ComRc<IEntity> {
+        &self.entity
+    }
+
+    pub fn motion(&self) -> Option<RoleMotion> {
+        self.motion
+    }
+
+    /// The follow camera's `(eye, look)` as of the last update.
+    pub fn camera_pose(&self) -> (Vec3, Vec3) {
+        (self.camera_eye, self.camera_look)
+    }
+
+    /// Stop moving and go back to the idle action.
+    pub fn stop(&mut self) {
+        self.set_motion(RoleMotion::Idle);
+    }
+
+    /// Turn the follow camera so it starts out looking from `eye`, e.g.
+    /// where the last scripted shot left it.
+    pub fn align_camera(&mut self, eye: &Vec3) {
+        let position = self.entity.transform().borrow().position();
+        self.camera_rotation = camera_rotation_towards(&position, eye);
+    }
+
+    /// Move the leader one frame from the player's input and place the
+    /// follow camera. Returns the id of the segment trigger the leader
+    /// crossed this frame, if any.
+    pub fn update(
+        &mut self,
+        input: Rc<RefCell<dyn InputEngine>>,
+        scene: &ComRc<IScene>,
+        heights: &TerrainHeights,
+        delta_sec: f32,
+    ) -> Option<i64> {
+        let direction = get_moving_direction(input.clone(), scene.clone());
+        let stick = {
+            let input = input.borrow();
+            let x = input.get_axis_state(Axis::LeftStickX).value();
+            let y = input.get_axis_state(Axis::LeftStickY).value();
+            x.hypot(y)
+        };
+        let motion = motion_for(direction.norm() > 0.5, stick);
+        self.set_motion(motion);
+
+        let world_com = scene.collision_world();
+        let world = world_com.inner::<CollisionWorldComponent>();
+        let position = self.entity.transform().borrow().position();
+        let mut target = position;
+        if motion != RoleMotion::Idle {
+            let speed = if motion == RoleMotion::Walk {
+                WALK_SPEED
+            } else {
+                RUN_SPEED
+            };
+            let step = Vec3::scalar_mul(speed * delta_sec, &direction);
+            target = resolve_move(&position, &step, |origin, step| {
+                wall_ahead(world, origin, step)
+            });
+        }
+
+        let terrain = heights.height_at(target.x, target.z);
+        let floor = world
+            .cast_aa_ny(&Vec3::new(target.x, target.y + STEP_UP, target.z))
+            .map(|d| target.y + STEP_UP - d);
+        match ground_height(terrain, floor) {
+            Some(y) => target.y = y,
+            // Walking off the edge of a streamed map: stay put.
+            None if !heights.is_empty() => target = position,
+            None => {}
+        }
+
+        let mut fired = None;
+        if Vec3::sub(&target, &position).norm2() > 0. {
+            self.entity
+                .transform()
+                .borrow_mut()
+                .look_at(&Vec3::new(target.x, position.y, target.z))
+                .set_position(&target);
+
+            let body = Vec3::new(0., STEP_UP, 0.);
+            world.evaluate_segment_triggers(
+                &Vec3::add(&position, &body),
+                &Vec3::add(&target, &body),
+            );
+            fired = world.fired_segment_trigger();
+        }
+
+        self.camera_rotation = get_camera_rotation(input, self.camera_rotation, delta_sec);
+        let look = Vec3::new(target.x, target.y + CAMERA_LOOK_HEIGHT, target.z);
+        let mut camera = scene.camera_mut();
+        let transform = camera
+            .transform_mut()
+            .set_position(&Vec3::new(0., CAMERA_HEIGHT, CAMERA_DISTANCE))
+            .rotate_axis_angle(&Vec3::UP, self.camera_rotation)
+            .translate(&look)
+            .look_at(&look);
+        self.camera_eye = transform.position();
+        self.camera_look = look;
+
+        fired
+    }
+
+    fn set_motion(&mut self, motion: RoleMotion) {
+        if self.motion == Some(motion) {
+            return;
+        }
+        self.motion = Some(motion);
+
+        let Some(armature) = &self.armature else {
+            return;
+        };
+        let keyframes = match motion {
+            RoleMotion::Idle => &self.idle,
+            RoleMotion::Walk if !self.walk.is_empty() => &self.walk,
+            RoleMotion::Walk | RoleMotion::Run => &self.run,
+        };
+        if keyframes.is_empty() {
+            armature.stop();
+            return;
+        }
+        armature.set_animation(keyframes.clone(), vec![]);
+        armature.set_looping(true);
+        armature.play();
+    }
+}
+
+pub(crate) fn armature_of(entity: &ComRc<IEntity>) -> Option<ComRc<IArmatureComponent>> {
+    entity
+        .get_component(IArmatureComponent::uuid())
+        .and_then(|c| c.query_interface::<IArmatureComponent>())
+}
+
+/// The first of `acts` found next to the model, or no keyframes.
+pub(crate) fn load_action(
+    asset_loader: &AssetLoader,
+    model_path: &str,
+    acts: &[&str],
+) -> Vec<Vec<AnimKeyFrame>> {
+    let anm = acts
+        .iter()
+        .find_map(|act| asset_loader.load_model_anm(model_path, act).ok());
+    if anm.is_none() {
+        log::debug!("PAL5: no {:?} action for '{}'", acts, model_path);
+    }
+    anm.unwrap_or_default()
+}
+
+fn motion_for(moving: bool, stick: f32) -> RoleMotion {
+    if !moving {
+        RoleMotion::Idle
+    } else if stick > 0.1 && stick < WALK_DEFLECTION {
+        RoleMotion::Walk
+    } else {
+        RoleMotion::Run
+    }
+}
+
+/// Whether an object wall stands within `step` (plus the body radius)
+/// of the leader at `position`.
+fn wall_ahead(world: &CollisionWorldComponent, position: &Vec3, step: &Vec3) -> bool {
+    let length = step.norm();
+    let origin = Vec3::new(position.x, position.y + STEP_UP, position.z);
+    let direction = Vec3::scalar_mul(1. / length, step);
+    world
+        .cast_ray(&origin, &direction)
+        .is_some_and(|distance| distance < length + BODY_RADIUS)
+}
+
+/// Take `step` from `position`, sliding along a wall when the full step
+/// is `blocked` by keeping whichever axis of it is still free.
+pub(crate) fn resolve_move(
+    position: &Vec3,
+    step: &Vec3,
+    blocked: impl Fn(&Vec3, &Vec3) -> bool,
+) -> Vec3 {
+    let candidates = [*step, Vec3::new(step.x, 0., 0.), Vec3::new(0., 0., step.z)];
+    for candidate in candidates {
+        if candidate.norm2() > 0. && !blocked(position, &candidate) {
+            return Vec3::add(position, &candidate);
+        }
+    }
+
+    *position
+}
+
+/// The higher of the terrain and an object floor under the leader.
+fn ground_height(terrain: Option<f32>, floor: Option<f32>) -> Option<f32> {
+    match (terrain, floor) {
+        (Some(t), Some(f)) => Some(t.max(f)),
+        (t, f) => t.or(f),
+    }
+}
+
+/// Follow-camera rotation that puts the camera on the same side of the
+/// leader at `position` as `eye`.
+fn camera_rotation_towards(position: &Vec3, eye: &Vec3) -> f32 {
+    let dx = eye.x - position.x;
+    let dz = eye.z - position.z;
+    if dx == 0. && dz == 0. {
+        return 0.;
+    }
+
+    dx.atan2(dz).rem_euclid(std::f32::consts::PI * 2.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::openpal5::terrain::BlockHeightField;
+
+    #[test]
+    fn stick_deflection_picks_walk_or_run() {
+        assert_eq!(motion_for(false, 0.3), RoleMotion::Idle);
+        assert_eq!(motion_for(true, 0.3), RoleMotion::Walk);
+        assert_eq!(motion_for(true, 1.0), RoleMotion::Run);
+        // Keys leave the stick centred.
+        assert_eq!(motion_for(true, 0.0), RoleMotion::Run);
+    }
+
+    #[test]
+    fn blocked_step_slides_along_the_free_axis() {
+        let position = Vec3::new(0., 0., 0.);
+        let step = Vec3::new(10., 0., 10.);
+        // A wall along x = 5: anything moving in +X is blocked.
+        let moved = resolve_move(&position, &step, |_, s| s.x > 0.);
+        assert_eq!((moved.x, moved.z), (0., 10.));
+
+        let moved = resolve_move(&position, &step, |_, _| true);
+        assert_eq!((moved.x, moved.z), (0., 0.));
+
+        let moved = resolve_move(&position, &step, |_, _| false);
+        assert_eq!((moved.x, moved.z), (10., 10.));
+    }
+
+    #[test]
+    fn ground_is_the_higher_surface() {
+        assert_eq!(ground_height(Some(10.), Some(25.)), Some(25.));
+        assert_eq!(ground_height(Some(10.), None), Some(10.));
+        assert_eq!(ground_height(None, Some(25.)), Some(25.));
+        assert_eq!(ground_height(None, None), None);
+    }
+
+    #[test]
+    fn terrain_heights_cover_only_loaded_blocks() {
+        let mut heights = TerrainHeights::default();
+        assert!(heights.is_empty());
+        heights.push_for_test(BlockHeightField::flat_for_test(0., 0., 100.));
+        heights.push_for_test(BlockHeightField::flat_for_test(5120., 0., 200.));
+
+        assert_eq!(heights.height_at(100., 100.), Some(100.));
+        assert_eq!(heights.height_at(5200., 100.), Some(200.));
+        assert_eq!(heights.height_at(-10., 100.), None);
+        assert_eq!(heights.height_at(100., 5200.), None);
+    }
+
+    #[test]
+    fn camera_starts_on_the_eye_side() {
+        let position = Vec3::new(100., 0., 100.);
+        let behind = camera_rotation_towards(&position, &Vec3::new(100., 300., 700.));
+        assert!(behind.abs() < 1e-5);
+        let right = camera_rotation_towards(&position, &Vec3::new(700., 300., 100.));
+        assert!((right - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+    }
+}
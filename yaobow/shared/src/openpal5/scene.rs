@@ -1,239 +1,579 @@
+//! PAL5 scene assembly.
+//!
+//! [`Pal5SceneLoader`] streams a map in over several frames: the
+//! atmosphere and skybox are set up immediately, then the block files
+//! (`.mp` / `.alp` / `.ctr`), the object lists (`.nod`) and every
+//! object's `.dff` are read on the main thread, decoded on the engine's
+//! [`AssetStreamer`] workers, and turned into entities on the main
+//! thread a frame budget at a time. The terrain and grass are built one
+//! block per step. The scene is valid (and can be pushed) from the
+//! start; it simply fills in as the load advances.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
 use crosscom::ComRc;
-use radiance::{comdef::IScene, math::Vec3, scene::CoreScene};
+use fileformats::{nod::Node, rwbs::read_dff};
+use radiance::{
+    comdef::IScene,
+    components::{collision::CollisionWorldComponent, mesh::Geometry},
+    math::Vec3,
+    radiance::{AssetStreamer, StreamingBatch},
+    scene::CoreScene,
+};
 
-use super::asset_loader::AssetLoader;
+use super::asset_loader::{AssetLoader, DecodedMapBlock, decode_block_nod};
+use super::terrain::TerrainHeights;
+use crate::loaders::dff::dff_cache_key;
 use radiance::comdef::{IEntityExt, ISceneExt};
 
 pub struct Pal5Scene {
     pub scene: ComRc<IScene>,
+    /// Terrain heights for walking on the map; filled as the terrain
+    /// stage streams in.
+    pub heights: Rc<RefCell<TerrainHeights>>,
 }
 
 impl Pal5Scene {
     pub fn new_empty() -> Self {
         Self {
             scene: CoreScene::create(),
+            heights: Rc::new(RefCell::new(TerrainHeights::default())),
         }
     }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Stage {
+    /// Reading block files; workers decode them.
+    Blocks,
+    /// Building one block's terrain geometry per unit of work.
+    Terrain,
+    /// Reading `.nod` object lists; workers decode them.
+    Nodes,
+    /// Reading object models; workers parse them and the finalizers
+    /// create the entities.
+    Models,
+    /// Building one block's grass per unit of work.
+    Grass,
+    Done,
+}
+
+/// Share of the overall progress bar each stage covers. Models dominate
+/// real load time on every map measured so far.
+const STAGE_WEIGHTS: &[(Stage, f32)] = &[
+    (Stage::Blocks, 0.15),
+    (Stage::Terrain, 0.15),
+    (Stage::Nodes, 0.05),
+    (Stage::Models, 0.5),
+    (Stage::Grass, 0.15),
+];
+
+#[derive(Default)]
+struct ModelStats {
+    loaded: Cell<usize>,
+    skipped: Cell<usize>,
+    failed: Cell<usize>,
+}
+
+pub struct Pal5SceneLoader {
+    asset_loader: Rc<AssetLoader>,
+    streamer: Rc<AssetStreamer>,
+    scene_name: String,
+    scene: ComRc<IScene>,
 
-    pub fn load(asset_loader: &AssetLoader, scene_name: &str) -> anyhow::Result<Self> {
+    stage: Stage,
+    /// Jobs of the current stage; replaced on every stage change.
+    batch: StreamingBatch,
+    /// Work items the current stage hasn't read yet, and how many it
+    /// started with.
+    pending_blocks: VecDeque<(u32, u32)>,
+    pending_nodes: VecDeque<Node>,
+    stage_total: usize,
+    /// Next block for the per-block `Terrain` and `Grass` stages.
+    next_block: usize,
+
+    blocks: Rc<RefCell<Vec<DecodedMapBlock>>>,
+    geometries: Vec<Geometry>,
+    heights: Rc<RefCell<TerrainHeights>>,
+    /// Decoded `.nod` lists keyed by block, flattened in grid order.
+    nodes: Rc<RefCell<Vec<((u32, u32), Vec<Node>)>>>,
+    nod_blocks_read: usize,
+    models: Rc<ModelStats>,
+    grass_layers: usize,
+}
+
+impl Pal5SceneLoader {
+    /// Create the scene with its atmosphere and skybox and queue the
+    /// rest of the map. Nothing else is read until the first
+    /// [`step`](Self::step).
+    pub fn new(
+        asset_loader: Rc<AssetLoader>,
+        streamer: Rc<AssetStreamer>,
+        scene_name: &str,
+    ) -> Self {
         let scene = CoreScene::create();
         scene.camera_mut().set_fov43(45_f32.to_radians());
+        setup_atmosphere(&asset_loader, &scene, scene_name);
 
-        // Per-map atmosphere (`envinfo.env`): a dim ambient fill plus a
-        // directional sun. PAL5 ships no per-scene `.lgt`; its terrain and
-        // (when dynamic lighting is enabled) its buildings are dynamically lit
-        // (Lambert + ambient), so without this the lighting term would
-        // collapse to a flat, dead ambient. The sun is a first-class
-        // directional light in the parsed azimuth/elevation direction. If the
-        // `.env` is missing, fall back to full ambient so geometry never
-        // renders black.
-        match asset_loader.load_map_env(scene_name) {
-            Some(env) => {
-                // PAL5's sun *direction* lives in `Config/Data.pkg::MapInfo.ini`
-                // (`sunX/sunY/sunZ`), not in `envinfo.env`. The two degree
-                // fields at env body 0x18/0x1c are NOT the sun azimuth/elevation
-                // and must not drive lighting (they read low, making maps like
-                // kuangfengzhai look morning/evening). Most maps ship no sun
-                // and use the engine's near-overhead default; `(0.3, 1, 0.3)`
-                // reproduces that high "noon" key. The bright sun *color* still
-                // comes from `envinfo.env`.
-                let dir = asset_loader
-                    .load_map_sun(scene_name)
-                    .unwrap_or([0.3, 1.0, 0.3]);
-                let sun = radiance::scene::DirectionalLight::new(
-                    Vec3::new(dir[0], dir[1], dir[2]),
-                    env.sun_color,
-                );
+        let pending_blocks: VecDeque<_> = asset_loader.map_blocks(scene_name, "mp").into();
+        let batch = streamer.batch();
+        Self {
+            asset_loader,
+            streamer,
+            scene_name: scene_name.to_string(),
+            scene,
+            stage: Stage::Blocks,
+            batch,
+            stage_total: pending_blocks.len(),
+            pending_blocks,
+            pending_nodes: VecDeque::new(),
+            next_block: 0,
+            blocks: Rc::new(RefCell::new(Vec::new())),
+            geometries: Vec::new(),
+            heights: Rc::new(RefCell::new(TerrainHeights::default())),
+            nodes: Rc::new(RefCell::new(Vec::new())),
+            nod_blocks_read: 0,
+            models: Rc::new(ModelStats::default()),
+            grass_layers: 0,
+        }
+    }
 
-                // Per-map linear distance fog. PAL5's original engine uses
-                // fixed-function linear eye-space fog: each vertex shader emits
-                // `oFog = clip-w` (eye depth) and the device blends toward the
-                // fog color between `FOGSTART` and `FOGEND`. We reproduce that
-                // as a first-class radiance `Fog` (linear eye-space). Only
-                // enable it when the range is well-formed (`end > start`); a few
-                // demon-realm battlemaps ship inverted/degenerate values (e.g.
-                // start=1.0,end=0.0) which we treat as "no fog" rather than
-                // washing the whole scene to the fog color.
-                //
-                // The `[0,1]` `envinfo.env` fractions (`fog_param_a/b`) scale by
-                // the main scene camera's far distance to give the eye-space
-                // FOGSTART/FOGEND. PAL5 renders its world at true scale (terrain
-                // blocks span 5120 units; the `far = 1000` projection in
-                // `Pal5.exe` is an aspect=1.0 sub-camera, NOT the world view).
-                // `6000` was calibrated against the original game's density
-                // (kuangfengzhai: gentle haze on the far cliffs, crisp village);
-                // tunable via the `PAL5_FOG_FAR` env var.
-                let fog_far: f32 = std::env::var("PAL5_FOG_FAR")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(6000.0);
-                let fog_start = env.fog_param_a * fog_far;
-                let fog_end = env.fog_param_b * fog_far;
-                let fog = if fog_end > fog_start + 1.0 {
-                    Some(radiance::scene::Fog::new(
-                        [env.fog_color[0], env.fog_color[1], env.fog_color[2]],
-                        fog_start,
-                        fog_end,
-                    ))
-                } else {
-                    None
-                };
+    pub fn scene(&self) -> ComRc<IScene> {
+        self.scene.clone()
+    }
 
-                let mut lighting =
-                    radiance::scene::SceneLighting::with_sun(env.ambient, vec![], sun);
-                lighting.fog = fog;
-                scene.set_lighting(lighting);
-
-                log::info!(
-                    "Pal5Scene '{}': atmosphere ambient {:?} sun {:?} dir {:?} | fog {:?} a={} b={} -> eye[{:.0}..{:.0}] enabled={} skybox={:?}",
-                    scene_name,
-                    env.ambient,
-                    env.sun_color,
-                    dir,
-                    env.fog_color,
-                    env.fog_param_a,
-                    env.fog_param_b,
-                    fog_start,
-                    fog_end,
-                    fog.is_some(),
-                    env.skybox_asset_id(),
-                );
+    /// The map's terrain heights, shared with the loader so they fill
+    /// in block by block.
+    pub fn heights(&self) -> Rc<RefCell<TerrainHeights>> {
+        self.heights.clone()
+    }
+
+    pub fn scene_name(&self) -> &str {
+        &self.scene_name
+    }
 
-                // Skybox: the scene's `SkyBoxID` (carried in `envinfo.env`)
-                // selects a `\BuildingP5\yingdi\_skybox*.dff` model. It is
-                // re-centred on the camera every frame by the attached
-                // `SkyboxComponent`, so a scene with no skybox id simply
-                // renders without one.
-                if let Some(skybox_id) = env.skybox_asset_id() {
-                    if let Some(skybox) = asset_loader.load_skybox(skybox_id) {
-                        scene.add_entity(skybox);
-                    }
+    pub fn is_finished(&self) -> bool {
+        self.stage == Stage::Done
+    }
+
+    /// Advance the load for about `budget` of main-thread time. At least
+    /// one unit of work runs per call. Returns `true` once the scene is
+    /// complete.
+    pub fn step(&mut self, budget: Duration) -> bool {
+        let start = Instant::now();
+        loop {
+            let remaining = budget.saturating_sub(start.elapsed());
+            let busy = match self.stage {
+                Stage::Blocks => self.step_blocks(remaining),
+                Stage::Terrain => self.step_terrain(),
+                Stage::Nodes => self.step_nodes(remaining),
+                Stage::Models => self.step_models(remaining),
+                Stage::Grass => self.step_grass(),
+                Stage::Done => return true,
+            };
+
+            // Not busy means the stage is waiting on workers; give the
+            // frame back rather than spin.
+            if !busy || start.elapsed() >= budget {
+                return self.is_finished();
+            }
+        }
+    }
+
+    /// Overall progress in `[0, 1]`, weighted by [`STAGE_WEIGHTS`].
+    pub fn progress(&self) -> f32 {
+        let mut base = 0.0;
+        for &(stage, weight) in STAGE_WEIGHTS {
+            if stage == self.stage {
+                return (base + weight * self.stage_fraction()).min(1.0);
+            }
+            base += weight;
+        }
+        1.0
+    }
+
+    fn stage_fraction(&self) -> f32 {
+        match self.stage {
+            Stage::Blocks | Stage::Nodes | Stage::Models => {
+                if self.stage_total == 0 {
+                    return 1.0;
                 }
+                let pending = match self.stage {
+                    Stage::Models => self.pending_nodes.len(),
+                    _ => self.pending_blocks.len(),
+                };
+                let in_flight = (self.batch.submitted() - self.batch.finalized()) as usize;
+                1.0 - (pending + in_flight) as f32 / self.stage_total as f32
             }
-            None => {
-                scene.set_lighting(radiance::scene::SceneLighting::new([1.0, 1.0, 1.0], vec![]));
+            Stage::Terrain | Stage::Grass => {
+                let blocks = self.blocks.borrow().len();
+                if blocks == 0 {
+                    1.0
+                } else {
+                    self.next_block as f32 / blocks as f32
+                }
             }
+            Stage::Done => 1.0,
         }
+    }
 
-        // Terrain heightfield + splat textures, loaded per block. A
-        // decode/build failure is non-fatal — the scene's objects still
-        // load.
-        let blocks = asset_loader.load_map_blocks(scene_name);
-        let patch_count: usize = blocks.iter().map(|b| b.mp.patches.len()).sum();
-        if !blocks.is_empty() {
-            if let Some(terrain) =
-                super::terrain::build_terrain_entity(asset_loader, scene_name, &blocks)
+    fn enter(&mut self, stage: Stage) {
+        self.stage = stage;
+        self.batch = self.streamer.batch();
+        self.next_block = 0;
+    }
+
+    fn step_blocks(&mut self, remaining: Duration) -> bool {
+        if let Some((r, c)) = self.pending_blocks.pop_front() {
+            if let Some(files) = self
+                .asset_loader
+                .read_map_block_files(&self.scene_name, r, c)
             {
-                scene.add_entity(terrain);
-                log::info!(
-                    "Pal5Scene '{}': terrain loaded ({} blocks, {} patches)",
-                    scene_name,
-                    blocks.len(),
-                    patch_count,
+                let blocks = self.blocks.clone();
+                self.batch.submit(
+                    move || files.decode(),
+                    move |decoded| {
+                        if let Some(decoded) = decoded {
+                            blocks.borrow_mut().push(decoded);
+                        }
+                    },
                 );
             }
-        } else {
-            log::warn!("Pal5Scene '{}': no terrain blocks found", scene_name);
+            return true;
         }
 
-        let nod = asset_loader.load_map_nod(scene_name)?;
+        if !self.batch.pump(remaining) {
+            return false;
+        }
 
-        let mut loaded = 0usize;
-        let mut skipped = 0usize;
-        let mut failed = 0usize;
+        // Blocks finish in any order; build them in grid order.
+        self.blocks
+            .borrow_mut()
+            .sort_by_key(|decoded| (decoded.block.row, decoded.block.col));
+        self.enter(Stage::Terrain);
+        true
+    }
 
-        for node in &nod.nodes {
-            // Resolve the node's asset entry from the role index. Many
-            // `.nod` nodes reference ids that are absent from the
-            // `role_*.bin` index (gameplay markers, server-only props);
-            // those are skipped, not fatal.
-            let Some(asset) = asset_loader.index.get(&node.asset_id) else {
-                log::debug!(
-                    "Pal5Scene: node '{:?}' asset_id {} not in role index; skipping",
-                    node.name,
-                    node.asset_id,
+    // Terrain heightfield + splat textures, built per block. A
+    // decode/build failure is non-fatal — the scene's objects still
+    // load.
+    fn step_terrain(&mut self) -> bool {
+        let blocks = self.blocks.borrow();
+        if let Some(decoded) = blocks.get(self.next_block) {
+            self.geometries
+                .extend(super::terrain::build_block_geometries(
+                    &self.asset_loader,
+                    &self.scene_name,
+                    &decoded.block,
+                ));
+            self.heights.borrow_mut().add_block(&decoded.block);
+            self.next_block += 1;
+            return true;
+        }
+
+        if blocks.is_empty() {
+            log::warn!("Pal5Scene '{}': no terrain blocks found", self.scene_name);
+        } else if let Some(terrain) = super::terrain::terrain_entity_from_geometries(
+            &self.asset_loader,
+            &self.scene_name,
+            std::mem::take(&mut self.geometries),
+        ) {
+            self.scene.add_entity(terrain);
+            log::info!(
+                "Pal5Scene '{}': terrain loaded ({} blocks, {} patches)",
+                self.scene_name,
+                blocks.len(),
+                blocks
+                    .iter()
+                    .map(|decoded| decoded.block.mp.patches.len())
+                    .sum::<usize>(),
+            );
+        }
+        drop(blocks);
+
+        self.pending_blocks = self.asset_loader.map_blocks(&self.scene_name, "nod").into();
+        self.stage_total = self.pending_blocks.len();
+        self.enter(Stage::Nodes);
+        true
+    }
+
+    fn step_nodes(&mut self, remaining: Duration) -> bool {
+        if let Some((r, c)) = self.pending_blocks.pop_front() {
+            if let Some(raw) = self.asset_loader.read_block_nod(&self.scene_name, r, c) {
+                self.nod_blocks_read += 1;
+                let nodes = self.nodes.clone();
+                self.batch.submit(
+                    move || decode_block_nod(raw),
+                    move |nod| match nod {
+                        Ok(nod) => nodes.borrow_mut().push(((r, c), nod.nodes)),
+                        Err(err) => log::warn!("Pal5 nod block ({},{}) failed: {}", r, c, err),
+                    },
                 );
-                continue;
-            };
+            }
+            return true;
+        }
+
+        if !self.batch.pump(remaining) {
+            return false;
+        }
+
+        if self.nod_blocks_read == 0 {
+            log::warn!("Pal5Scene '{}': no .nod blocks found", self.scene_name);
+        }
+        let mut nodes = std::mem::take(&mut *self.nodes.borrow_mut());
+        nodes.sort_by_key(|(block, _)| *block);
+        self.pending_nodes = nodes.into_iter().flat_map(|(_, nodes)| nodes).collect();
+        self.stage_total = self.pending_nodes.len();
+        self.enter(Stage::Models);
+        true
+    }
+
+    fn step_models(&mut self, remaining: Duration) -> bool {
+        if let Some(node) = self.pending_nodes.pop_front() {
+            self.submit_model(node);
+            // Interleave finalizers with reads so entities appear while
+            // the rest is still being read.
+            self.batch.pump(Duration::ZERO);
+            return true;
+        }
 
-            let file_path = asset.file_path.to_string();
+        if !self.batch.pump(remaining) {
+            return false;
+        }
 
-            // Only `.dff` clumps are renderable scene objects. PAL5 also
-            // stores degenerate `file_path` values (e.g. `"1"`) for
-            // non-model assets — guard against those so a stray entry
-            // never reaches the loader.
-            if !file_path.to_ascii_lowercase().ends_with(".dff") {
-                skipped += 1;
-                continue;
+        log::info!(
+            "Pal5Scene '{}': {} models loaded, {} skipped (non-model/unindexed), {} failed of {} nodes",
+            self.scene_name,
+            self.models.loaded.get(),
+            self.models.skipped.get(),
+            self.models.failed.get(),
+            self.stage_total,
+        );
+        self.enter(Stage::Grass);
+        true
+    }
+
+    fn submit_model(&mut self, node: Node) {
+        // Resolve the node's asset entry from the role index. Many
+        // `.nod` nodes reference ids that are absent from the
+        // `role_*.bin` index (gameplay markers, server-only props);
+        // those are skipped, not fatal.
+        let Some(asset) = self.asset_loader.index.get(&node.asset_id) else {
+            log::debug!(
+                "Pal5Scene: node '{:?}' asset_id {} not in role index; skipping",
+                node.name,
+                node.asset_id,
+            );
+            return;
+        };
+
+        let file_path = asset.file_path.to_string();
+
+        // Only `.dff` clumps are renderable scene objects. PAL5 also
+        // stores degenerate `file_path` values (e.g. `"1"`) for
+        // non-model assets — guard against those so a stray entry
+        // never reaches the loader.
+        if !file_path.to_ascii_lowercase().ends_with(".dff") {
+            self.models.skipped.set(self.models.skipped.get() + 1);
+            return;
+        }
+
+        // Isolate per-node failures: a single unreadable/corrupt
+        // model must not abort the whole scene (foliage is
+        // interleaved with buildings, so an early `?` would hide
+        // everything after the first bad node).
+        let raw = match self.asset_loader.read_model_file(&file_path) {
+            Ok(raw) => raw,
+            Err(err) => {
+                self.models.failed.set(self.models.failed.get() + 1);
+                log::warn!(
+                    "Pal5Scene: failed to load model '{}' (asset_id {}): {}",
+                    file_path,
+                    node.asset_id,
+                    err,
+                );
+                return;
             }
+        };
 
-            // Isolate per-node failures: a single unreadable/corrupt
-            // model must not abort the whole scene (foliage is
-            // interleaved with buildings, so an early `?` would hide
-            // everything after the first bad node).
-            let model = match asset_loader.load_model(&file_path) {
-                Ok(model) => model,
+        let asset_loader = self.asset_loader.clone();
+        let scene = self.scene.clone();
+        let models = self.models.clone();
+        self.batch.submit(
+            move || read_dff(&raw).map(|clumps| (clumps, dff_cache_key(&raw))),
+            move |clumps| match clumps {
+                Ok((clumps, source_key)) => {
+                    let model = asset_loader.build_model(&file_path, clumps, source_key);
+                    model
+                        .transform()
+                        .borrow_mut()
+                        .scale_local(&Vec3::new(node.scale[0], node.scale[1], node.scale[2]))
+                        .rotate_axis_angle_local(&Vec3::BACK, -node.rotation[0].to_radians())
+                        .rotate_axis_angle_local(&Vec3::UP, node.rotation[1].to_radians())
+                        .rotate_axis_angle_local(&Vec3::EAST, -node.rotation[2].to_radians())
+                        .set_position(&Vec3::new(
+                            node.position[0],
+                            node.position[1],
+                            node.position[2],
+                        ));
+                    // Map objects are what the party leader walks on and
+                    // bumps into; the terrain itself is sampled from the
+                    // block heightfields instead.
+                    scene
+                        .collision_world()
+                        .inner::<CollisionWorldComponent>()
+                        .attach_collider(&model);
+                    scene.add_entity(model);
+                    models.loaded.set(models.loaded.get() + 1);
+                }
                 Err(err) => {
-                    failed += 1;
+                    models.failed.set(models.failed.get() + 1);
                     log::warn!(
                         "Pal5Scene: failed to load model '{}' (asset_id {}): {}",
                         file_path,
                         node.asset_id,
                         err,
                     );
-                    continue;
                 }
-            };
-
-            model
-                .transform()
-                .borrow_mut()
-                .scale_local(&Vec3::new(node.scale[0], node.scale[1], node.scale[2]))
-                .rotate_axis_angle_local(&Vec3::BACK, -node.rotation[0].to_radians())
-                .rotate_axis_angle_local(&Vec3::UP, node.rotation[1].to_radians())
-                .rotate_axis_angle_local(&Vec3::EAST, -node.rotation[2].to_radians())
-                .set_position(&Vec3::new(
-                    node.position[0],
-                    node.position[1],
-                    node.position[2],
-                ));
-            scene.add_entity(model);
-            loaded += 1;
-        }
-
-        log::info!(
-            "Pal5Scene '{}': {} models loaded, {} skipped (non-model/unindexed), {} failed of {} nodes",
-            scene_name,
-            loaded,
-            skipped,
-            failed,
-            nod.nodes.len(),
+            },
         );
+    }
 
-        // Grass (`<map>_<r>_<c>.ctr`): a terrain-conformal grass overlay built
-        // from each block's `.ctr` density grid (`cao###`-textured, density-
-        // driven coverage). See `grass::build_block_grass`. Non-fatal — a map
-        // with no grass still renders terrain + objects.
-        let mut grass_layers = 0usize;
-        for block in &blocks {
-            let leaves = asset_loader.load_block_ctr(scene_name, block.row, block.col);
-            if leaves.is_empty() {
-                continue;
-            }
-            let chunks = super::grass::build_block_grass(asset_loader, scene_name, block, &leaves);
-            grass_layers += chunks.len();
-            for chunk in chunks {
-                scene.add_entity(chunk);
+    // Grass (`<map>_<r>_<c>.ctr`): a terrain-conformal grass overlay built
+    // from each block's `.ctr` density grid (`cao###`-textured, density-
+    // driven coverage). See `grass::build_block_grass`. Non-fatal — a map
+    // with no grass still renders terrain + objects.
+    fn step_grass(&mut self) -> bool {
+        let blocks = self.blocks.borrow();
+        if let Some(decoded) = blocks.get(self.next_block) {
+            if !decoded.grass.is_empty() {
+                let chunks = super::grass::build_block_grass(
+                    &self.asset_loader,
+                    &self.scene_name,
+                    &decoded.block,
+                    &decoded.grass,
+                );
+                self.grass_layers += chunks.len();
+                for chunk in chunks {
+                    self.scene.add_entity(chunk);
+                }
             }
+            self.next_block += 1;
+            return true;
         }
-        if grass_layers > 0 {
+        drop(blocks);
+
+        if self.grass_layers > 0 {
             log::info!(
                 "Pal5Scene '{}': grass overlay built ({} layer chunks)",
-                scene_name,
-                grass_layers,
+                self.scene_name,
+                self.grass_layers,
             );
         }
+        self.blocks.borrow_mut().clear();
+        self.enter(Stage::Done);
+        true
+    }
+}
+
+fn setup_atmosphere(asset_loader: &AssetLoader, scene: &ComRc<IScene>, scene_name: &str) {
+    // Per-map atmosphere (`envinfo.env`): a dim ambient fill plus a
+    // directional sun. PAL5 ships no per-scene `.lgt`; its terrain and
+    // (when dynamic lighting is enabled) its buildings are dynamically lit
+    // (Lambert + ambient), so without this the lighting term would
+    // collapse to a flat, dead ambient. The sun is a first-class
+    // directional light in the parsed azimuth/elevation direction. If the
+    // `.env` is missing, fall back to full ambient so geometry never
+    // renders black.
+    match asset_loader.load_map_env(scene_name) {
+        Some(env) => {
+            // PAL5's sun *direction* lives in `Config/Data.pkg::MapInfo.ini`
+            // (`sunX/sunY/sunZ`), not in `envinfo.env`. The two degree
+            // fields at env body 0x18/0x1c are NOT the sun azimuth/elevation
+            // and must not drive lighting (they read low, making maps like
+            // kuangfengzhai look morning/evening). Most maps ship no sun
+            // and use the engine's near-overhead default; `(0.3, 1, 0.3)`
+            // reproduces that high "noon" key. The bright sun *color* still
+            // comes from `envinfo.env`.
+            let dir = asset_loader
+                .load_map_sun(scene_name)
+                .unwrap_or([0.3, 1.0, 0.3]);
+            let sun = radiance::scene::DirectionalLight::new(
+                Vec3::new(dir[0], dir[1], dir[2]),
+                env.sun_color,
+            );
 
-        Ok(Self { scene })
+            // Per-map linear distance fog. PAL5's original engine uses
+            // fixed-function linear eye-space fog: each vertex shader emits
+            // `oFog = clip-w` (eye depth) and the device blends toward the
+            // fog color between `FOGSTART` and `FOGEND`. We reproduce that
+            // as a first-class radiance `Fog` (linear eye-space). Only
+            // enable it when the range is well-formed (`end > start`); a few
+            // demon-realm battlemaps ship inverted/degenerate values (e.g.
+            // start=1.0,end=0.0) which we treat as "no fog" rather than
+            // washing the whole scene to the fog color.
+            //
+            // The `[0,1]` `envinfo.env` fractions (`fog_param_a/b`) scale by
+            // the main scene camera's far distance to give the eye-space
+            // FOGSTART/FOGEND. PAL5 renders its world at true scale (terrain
+            // blocks span 5120 units; the `far = 1000` projection in
+            // `Pal5.exe` is an aspect=1.0 sub-camera, NOT the world view).
+            // `6000` was calibrated against the original game's density
+            // (kuangfengzhai: gentle haze on the far cliffs, crisp village);
+            // tunable via the `PAL5_FOG_FAR` env var.
+            let fog_far: f32 = std::env::var("PAL5_FOG_FAR")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(6000.0);
+            let fog_start = env.fog_param_a * fog_far;
+            let fog_end = env.fog_param_b * fog_far;
+            let fog = if fog_end > fog_start + 1.0 {
+                Some(radiance::scene::Fog::new(
+                    [env.fog_color[0], env.fog_color[1], env.fog_color[2]],
+                    fog_start,
+                    fog_end,
+                ))
+            } else {
+                None
+            };
+
+            let mut lighting = radiance::scene::SceneLighting::with_sun(env.ambient, vec![], sun);
+            lighting.fog = fog;
+            scene.set_lighting(lighting);
+
+            log::info!(
+                "Pal5Scene '{}': atmosphere ambient {:?} sun {:?} dir {:?} | fog {:?} a={} b={} -> eye[{:.0}..{:.0}] enabled={} skybox={:?}",
+                scene_name,
+                env.ambient,
+                env.sun_color,
+                dir,
+                env.fog_color,
+                env.fog_param_a,
+                env.fog_param_b,
+                fog_start,
+                fog_end,
+                fog.is_some(),
+                env.skybox_asset_id(),
+            );
+
+            // Skybox: the scene's `SkyBoxID` (carried in `envinfo.env`)
+            // selects a `\BuildingP5\yingdi\_skybox*.dff` model. It is
+            // re-centred on the camera every frame by the attached
+            // `SkyboxComponent`, so a scene with no skybox id simply
+            // renders without one.
+            if let Some(skybox_id) = env.skybox_asset_id() {
+                if let Some(skybox) = asset_loader.load_skybox(skybox_id) {
+                    scene.add_entity(skybox);
+                }
+            }
+        }
+        None => {
+            scene.set_lighting(radiance::scene::SceneLighting::new([1.0, 1.0, 1.0], vec![]));
+        }
     }
 }
@@ -92,6 +92,11 @@ impl ScriptIndex {
         self.by_id.get(&id)
     }
 
+    /// Every indexed script, in no particular order.
+    pub fn entries(&self) -> impl Iterator<Item = (u32, &ScriptEntry)> {
+        self.by_id.iter().map(|(id, entry)| (*id, entry))
+    }
+
     /// Load the (already SDFA-decrypted) Lua source for `id`.
     pub fn load_source(&self, vfs: &MiniFs, id: u32) -> anyhow::Result<(String, Vec<u8>)> {
         let entry = self
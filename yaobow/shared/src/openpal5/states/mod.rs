@@ -0,0 +1 @@
+pub mod persistent_state;
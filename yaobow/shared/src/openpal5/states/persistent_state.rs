@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+
+use radiance::math::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::saves::{self, SaveKind, SaveMetadata};
+
+/// PAL5 save namespace. Save slots live under
+/// `<save_dir>/<PAL5_APP_NAME>/Save/<slot>.json`.
+pub const PAL5_APP_NAME: &str = "OpenPAL5";
+
+/// PAL5Q (the prequel) shares the PAL5 state layout under its own
+/// namespace.
+pub const PAL5Q_APP_NAME: &str = "OpenPAL5Q";
+
+/// Per-role progression record, keyed by the role id the scripts pass
+/// to `player.*`. All fields default so partially populated states
+/// still deserialize cleanly.
+///
+/// `level`, `max_hp` and `max_mp` are never set: PAL5's role data
+/// tables aren't read yet and the scripts only shift HP/MP. They stay
+/// `None` and are left out of the save, and
+/// [`Pal5PersistentState::change_hp`] then applies no maximum.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Pal5PlayerState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<i32>,
+    #[serde(default)]
+    pub hp: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_hp: Option<i32>,
+    #[serde(default)]
+    pub mp: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_mp: Option<i32>,
+    #[serde(default)]
+    pub in_team: bool,
+    #[serde(default)]
+    pub magic: Vec<i32>,
+    #[serde(default)]
+    pub equipment: Vec<i32>,
+}
+
+/// Serializable snapshot of PAL5 game progress: party, bag, recipes,
+/// quests and the Lua story flags. Saved as slot-based JSON, mirroring
+/// `Pal4PersistentState`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pal5PersistentState {
+    app_name: String,
+    #[serde(default)]
+    money: i32,
+    #[serde(default)]
+    scene_name: String,
+    #[serde(default)]
+    position: Option<Vec3>,
+    #[serde(default)]
+    players: HashMap<i32, Pal5PlayerState>,
+    /// Bag contents as item-id -> count.
+    #[serde(default)]
+    inventory: HashMap<i32, i32>,
+    /// Learned cooking/forging formulas (`player.AddFormula`), in the
+    /// order they were learned.
+    #[serde(default)]
+    formulas: Vec<i32>,
+    /// Quest log (`ui.AddQuest`), in the order quests were added.
+    #[serde(default)]
+    quests: Vec<i32>,
+    /// Lua `flag.SetValue` story flags.
+    #[serde(default)]
+    flags: HashMap<i32, i32>,
+    #[serde(default)]
+    playtime: f64,
+}
+
+impl Pal5PersistentState {
+    pub fn new(app_name: String) -> Self {
+        Self {
+            app_name,
+            money: 0,
+            scene_name: String::new(),
+            position: None,
+            players: HashMap::new(),
+            inventory: HashMap::new(),
+            formulas: Vec::new(),
+            quests: Vec::new(),
+            flags: HashMap::new(),
+            playtime: 0.,
+        }
+    }
+
+    /// Load the persistent state for `app_name` from the given slot.
+    /// Returns an error if the slot file is missing, malformed or from a
+    /// newer build.
+    pub fn load(app_name: &str, slot: i32) -> anyhow::Result<Self> {
+        saves::load_state(Self::kind_of(app_name), app_name, slot)
+    }
+
+    /// Persist this state to the given slot. Negative slots are ignored.
+    pub fn save(&self, slot: i32) {
+        if slot < 0 {
+            return;
+        }
+
+        let metadata = SaveMetadata::now(self.playtime, self.scene_name.clone());
+        let kind = Self::kind_of(&self.app_name);
+        match saves::write_slot(kind, &self.app_name, slot, self, metadata) {
+            Ok(()) => log::info!("Game saved to slot {}", slot),
+            Err(e) => log::error!("Cannot save: {:#}", e),
+        }
+    }
+
+    fn kind_of(app_name: &str) -> SaveKind {
+        if app_name == PAL5Q_APP_NAME {
+            SaveKind::Pal5Q
+        } else {
+            SaveKind::Pal5
+        }
+    }
+
+    pub fn app_name(&self) -> &str {
+        self.app_name.as_str()
+    }
+
+    // --- Money ---------------------------------------------------------
+
+    pub fn money(&self) -> i32 {
+        self.money
+    }
+
+    pub fn add_money(&mut self, amount: i32) {
+        self.money = self.money.saturating_add(amount).max(0);
+    }
+
+    // --- Players -------------------------------------------------------
+
+    pub fn player(&self, role: i32) -> Option<&Pal5PlayerState> {
+        self.players.get(&role)
+    }
+
+    pub fn player_mut(&mut self, role: i32) -> &mut Pal5PlayerState {
+        self.players.entry(role).or_default()
+    }
+
+    /// Party members sorted by role id, for deterministic snapshots.
+    pub fn players(&self) -> Vec<(i32, &Pal5PlayerState)> {
+        let mut players: Vec<_> = self.players.iter().map(|(r, p)| (*r, p)).collect();
+        players.sort_by_key(|(role, _)| *role);
+        players
+    }
+
+    pub fn set_in_team(&mut self, role: i32, in_team: bool) {
+        self.player_mut(role).in_team = in_team;
+    }
+
+    pub fn in_team(&self, role: i32) -> bool {
+        self.players.get(&role).is_some_and(|p| p.in_team)
+    }
+
+    pub fn add_magic(&mut self, role: i32, magic_id: i32) {
+        let player = self.player_mut(role);
+        if !player.magic.contains(&magic_id) {
+            player.magic.push(magic_id);
+        }
+    }
+
+    pub fn add_player_equip(&mut self, role: i32, equip_id: i32) {
+        let player = self.player_mut(role);
+        if !player.equipment.contains(&equip_id) {
+            player.equipment.push(equip_id);
+        }
+    }
+
+    /// Shift HP by `delta`, never below zero nor (once a maximum is
+    /// known) above `max_hp`.
+    pub fn change_hp(&mut self, role: i32, delta: i32) {
+        let player = self.player_mut(role);
+        player.hp = clamp_stat(player.hp.saturating_add(delta), player.max_hp);
+    }
+
+    /// Shift MP by `delta`; see [`Self::change_hp`].
+    pub fn change_mp(&mut self, role: i32, delta: i32) {
+        let player = self.player_mut(role);
+        player.mp = clamp_stat(player.mp.saturating_add(delta), player.max_mp);
+    }
+
+    // --- Inventory -----------------------------------------------------
+
+    /// Add (or, with a negative `count`, take away) items; an item whose
+    /// count drops to zero leaves the bag.
+    pub fn add_item(&mut self, item_id: i32, count: i32) {
+        let entry = self.inventory.entry(item_id).or_insert(0);
+        *entry = entry.saturating_add(count).max(0);
+        if *entry == 0 {
+            self.inventory.remove(&item_id);
+        }
+    }
+
+    pub fn item_count(&self, item_id: i32) -> i32 {
+        self.inventory.get(&item_id).copied().unwrap_or(0)
+    }
+
+    /// Read-only iterator over `(item_id, count)` pairs. Order is
+    /// unspecified; callers that need a stable layout should sort.
+    pub fn inventory_iter(&self) -> impl Iterator<Item = (&i32, &i32)> {
+        self.inventory.iter()
+    }
+
+    // --- Formulas / quests ---------------------------------------------
+
+    pub fn add_formula(&mut self, formula_id: i32) {
+        if !self.formulas.contains(&formula_id) {
+            self.formulas.push(formula_id);
+        }
+    }
+
+    pub fn formulas(&self) -> &[i32] {
+        &self.formulas
+    }
+
+    pub fn add_quest(&mut self, quest_id: i32) {
+        if !self.quests.contains(&quest_id) {
+            self.quests.push(quest_id);
+        }
+    }
+
+    pub fn quests(&self) -> &[i32] {
+        &self.quests
+    }
+
+    // --- Story flags ---------------------------------------------------
+
+    pub fn set_flag(&mut self, flag: i32, value: i32) {
+        self.flags.insert(flag, value);
+    }
+
+    pub fn flag(&self, flag: i32) -> i32 {
+        self.flags.get(&flag).copied().unwrap_or(0)
+    }
+
+    // --- Scene / position ----------------------------------------------
+
+    pub fn scene_name(&self) -> &str {
+        &self.scene_name
+    }
+
+    pub fn set_scene_name(&mut self, scene_name: String) {
+        self.scene_name = scene_name;
+    }
+
+    pub fn position(&self) -> Option<Vec3> {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: Option<Vec3>) {
+        self.position = position;
+    }
+
+    // --- Playtime ------------------------------------------------------
+
+    pub fn playtime(&self) -> f64 {
+        self.playtime
+    }
+
+    pub fn add_playtime(&mut self, delta_sec: f32) {
+        self.playtime += delta_sec as f64;
+    }
+}
+
+fn clamp_stat(value: i32, max: Option<i32>) -> i32 {
+    match max {
+        Some(max) => value.clamp(0, max.max(0)),
+        None => value.max(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_leave_the_bag_at_zero() {
+        let mut state = Pal5PersistentState::new(PAL5_APP_NAME.to_string());
+        state.add_item(101, 3);
+        state.add_item(101, -1);
+        assert_eq!(state.item_count(101), 2);
+        state.add_item(101, -5);
+        assert_eq!(state.item_count(101), 0);
+        assert_eq!(state.inventory_iter().count(), 0);
+    }
+
+    #[test]
+    fn stats_clamp_to_known_maximum() {
+        let mut state = Pal5PersistentState::new(PAL5_APP_NAME.to_string());
+        state.change_hp(1, -10);
+        assert_eq!(state.player(1).unwrap().hp, 0);
+        state.change_hp(1, 500);
+        assert_eq!(state.player(1).unwrap().hp, 500);
+
+        state.player_mut(1).max_hp = Some(300);
+        state.change_hp(1, 1);
+        assert_eq!(state.player(1).unwrap().hp, 300);
+    }
+
+    #[test]
+    fn grants_are_not_duplicated() {
+        let mut state = Pal5PersistentState::new(PAL5_APP_NAME.to_string());
+        for _ in 0..2 {
+            state.add_magic(1, 7);
+            state.add_player_equip(1, 8);
+            state.add_formula(9);
+            state.add_quest(10);
+        }
+        let player = state.player(1).unwrap();
+        assert_eq!((player.magic.len(), player.equipment.len()), (1, 1));
+        assert_eq!((state.formulas(), state.quests()), (&[9][..], &[10][..]));
+    }
+
+    #[test]
+    fn state_survives_json_round_trip() {
+        let mut state = Pal5PersistentState::new(PAL5Q_APP_NAME.to_string());
+        state.set_flag(3, 42);
+        state.add_item(5, 2);
+        state.set_in_team(2, true);
+
+        let json = serde_json::to_string(&state).unwrap();
+        // Unknown stats are left out rather than written as zeros.
+        assert!(!json.contains("level") && !json.contains("max_hp"));
+        let restored: Pal5PersistentState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.app_name(), PAL5Q_APP_NAME);
+        assert_eq!(restored.flag(3), 42);
+        assert_eq!(restored.item_count(5), 2);
+        assert!(restored.in_team(2));
+        assert!(!restored.in_team(1));
+    }
+}
@@ -38,6 +38,7 @@ use crosscom::ComRc;
 use fileformats::pal5::alp::{WEIGHT_EDGE, terrain_texture_name};
 use fileformats::pal5::mp::{CELL_WORLD_SIZE, MpFile, PATCH_WORLD_SIZE};
 use image::{Rgba, RgbaImage};
+use radiance::asset::cache::{self, CacheKey};
 use radiance::comdef::IEntity;
 use radiance::components::mesh::{Geometry, StaticMeshComponent, TexCoord};
 use radiance::math::Vec3;
@@ -70,17 +71,27 @@ pub fn build_terrain_entity(
     map_name: &str,
     blocks: &[MapBlock],
 ) -> Option<ComRc<IEntity>> {
-    let factory = asset_loader.component_factory();
-    let entity = CoreEntity::create(format!("{}_terrain", map_name), true);
-
     let geometries: Vec<Geometry> = blocks
         .iter()
         .flat_map(|block| build_block_geometries(asset_loader, map_name, block))
         .collect();
+    terrain_entity_from_geometries(asset_loader, map_name, geometries)
+}
+
+/// Wrap the geometries of every block (see [`build_block_geometries`]) in
+/// the map's single terrain entity. Split from [`build_terrain_entity`] so
+/// a streaming load can build one block per frame.
+pub fn terrain_entity_from_geometries(
+    asset_loader: &AssetLoader,
+    map_name: &str,
+    geometries: Vec<Geometry>,
+) -> Option<ComRc<IEntity>> {
     if geometries.is_empty() {
         return None;
     }
 
+    let factory = asset_loader.component_factory();
+    let entity = CoreEntity::create(format!("{}_terrain", map_name), true);
     let mesh = StaticMeshComponent::new(entity.clone(), geometries, factory);
     entity.add_component(
         radiance::comdef::IStaticMeshComponent::uuid(),
@@ -125,7 +136,7 @@ fn patch_layer_ids(patch: &fileformats::pal5::mp::MpPatch, footer: [i32; 4]) ->
 /// Build every geometry for one block: one per distinct material group
 /// (overlay palette / default ground). Each patch is drawn exactly once as a
 /// weighted-sum splat — no base-grid vs. road split.
-fn build_block_geometries(
+pub fn build_block_geometries(
     asset_loader: &AssetLoader,
     map_name: &str,
     block: &MapBlock,
@@ -186,29 +197,11 @@ fn build_group_geometry(
     let mut normals = Vec::new();
     let mut texcoords = Vec::new();
     let mut indices: Vec<u32> = Vec::new();
-    let mut atlas =
-        RgbaImage::from_pixel(ATLAS_EDGE as u32, ATLAS_EDGE as u32, Rgba([0, 0, 0, 0]));
-
     for &pi in patch_idxs {
         let patch = &mp.patches[pi];
-        let lx = ((patch.min_x - block_min_x) / PATCH_WORLD_SIZE).round() as i64;
-        let lz = ((patch.min_z - block_min_z) / PATCH_WORLD_SIZE).round() as i64;
-        if !(0..PATCHES_PER_BLOCK as i64).contains(&lx)
-            || !(0..PATCHES_PER_BLOCK as i64).contains(&lz)
-        {
+        if patch_tile(patch, block_min_x, block_min_z).is_none() {
             continue;
         }
-        let (lx, lz) = (lx as usize, lz as usize);
-
-        // Weight tile from the `.alp` RGBA raster (remapped by SLOT_TO_BYTE),
-        // zeroing unused layer slots; fall back to the patch's own per-vertex
-        // layer assignment when the block has no decoded `.alp`.
-        match block.alp.as_ref().and_then(|alp| alp.patch(lx, lz)) {
-            Some(ap) if ap.planes.len() >= 4 => {
-                write_overlay_weights(&mut atlas, ap, layer_ids, lx, lz);
-            }
-            _ => write_vertex_layer_weights(&mut atlas, patch, layer_ids, lx, lz),
-        }
 
         // Emit this patch's 17×17 vertex grid.
         let base_vert = vertices.len() as u32;
@@ -242,6 +235,7 @@ fn build_group_geometry(
         return None;
     }
 
+    let atlas = group_atlas(block, block_min_x, block_min_z, layer_ids, patch_idxs);
     let material = group_material(asset_loader, map_name, block, layer_ids, group_idx, atlas);
     Some(Geometry::new(
         &vertices,
@@ -252,6 +246,75 @@ fn build_group_geometry(
     ))
 }
 
+/// Version of [`group_atlas`]'s output, part of its cache key. Bump it
+/// when the weight packing changes.
+const ATLAS_CACHE_VERSION: u32 = 1;
+
+/// Build (or fetch from the asset cache) a group's weight atlas: one
+/// 64×64 tile per patch from the `.alp` RGBA raster (remapped by
+/// SLOT_TO_BYTE), zeroing unused layer slots, falling back to the
+/// patch's own per-vertex layer assignment when the block has no decoded
+/// `.alp`.
+fn group_atlas(
+    block: &MapBlock,
+    block_min_x: f32,
+    block_min_z: f32,
+    layer_ids: [i32; 4],
+    patch_idxs: &[usize],
+) -> RgbaImage {
+    let build = || {
+        let mut atlas =
+            RgbaImage::from_pixel(ATLAS_EDGE as u32, ATLAS_EDGE as u32, Rgba([0, 0, 0, 0]));
+        for &pi in patch_idxs {
+            let patch = &block.mp.patches[pi];
+            let Some((lx, lz)) = patch_tile(patch, block_min_x, block_min_z) else {
+                continue;
+            };
+            match block.alp.as_ref().and_then(|alp| alp.patch(lx, lz)) {
+                Some(ap) if ap.planes.len() >= 4 => {
+                    write_overlay_weights(&mut atlas, ap, layer_ids, lx, lz);
+                }
+                _ => write_vertex_layer_weights(&mut atlas, patch, layer_ids, lx, lz),
+            }
+        }
+        Some(atlas)
+    };
+
+    let atlas = match cache::global() {
+        Some(cache) => {
+            let layers: Vec<u8> = layer_ids.iter().flat_map(|id| id.to_le_bytes()).collect();
+            let patches: Vec<u8> = patch_idxs
+                .iter()
+                .flat_map(|&pi| (pi as u32).to_le_bytes())
+                .collect();
+            let key = CacheKey::new(
+                "pal5_terrain_atlas",
+                ATLAS_CACHE_VERSION,
+                &[&block.source.to_bytes(), &layers, &patches],
+            );
+            cache.get_or_insert_image("pal5_terrain_atlas", &key, build)
+        }
+        None => build(),
+    };
+    atlas.unwrap()
+}
+
+/// The patch's tile coordinate within its block, or `None` for a patch
+/// that lies outside the block's 16×16 grid.
+fn patch_tile(
+    patch: &fileformats::pal5::mp::MpPatch,
+    block_min_x: f32,
+    block_min_z: f32,
+) -> Option<(usize, usize)> {
+    let lx = ((patch.min_x - block_min_x) / PATCH_WORLD_SIZE).round() as i64;
+    let lz = ((patch.min_z - block_min_z) / PATCH_WORLD_SIZE).round() as i64;
+    if !(0..PATCHES_PER_BLOCK as i64).contains(&lx) || !(0..PATCHES_PER_BLOCK as i64).contains(&lz)
+    {
+        return None;
+    }
+    Some((lx as usize, lz as usize))
+}
+
 /// Write a textured patch's 64×64 atlas tile from its `.alp` RGBA weights,
 /// mapping packed byte `b_{SLOT_TO_BYTE[slot]}` → slot and zeroing any slot
 /// whose overlay id is unused (`-1`).
@@ -396,6 +459,13 @@ impl BlockHeightField {
         (self.origin_x, self.origin_z)
     }
 
+    /// Whether world `(wx, wz)` lies within the block.
+    pub(crate) fn contains(&self, wx: f32, wz: f32) -> bool {
+        let size = (self.n - 1) as f32 * CELL_WORLD_SIZE;
+        (self.origin_x..=self.origin_x + size).contains(&wx)
+            && (self.origin_z..=self.origin_z + size).contains(&wz)
+    }
+
     /// Bilinearly sample the ground height at world `(wx, wz)`, clamped to the
     /// block's extent.
     pub(crate) fn sample(&self, wx: f32, wz: f32) -> f32 {
@@ -428,6 +498,36 @@ pub(crate) fn block_height_field(block: &MapBlock) -> BlockHeightField {
     }
 }
 
+/// Ground heights of a map's blocks, for standing characters on the
+/// terrain. Filled one block at a time while the scene streams in.
+#[derive(Default)]
+pub struct TerrainHeights {
+    blocks: Vec<BlockHeightField>,
+}
+
+impl TerrainHeights {
+    pub fn add_block(&mut self, block: &MapBlock) {
+        self.blocks.push(block_height_field(block));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Terrain height at world `(x, z)`, or `None` off the map.
+    pub fn height_at(&self, x: f32, z: f32) -> Option<f32> {
+        self.blocks
+            .iter()
+            .find(|block| block.contains(x, z))
+            .map(|block| block.sample(x, z))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn push_for_test(&mut self, block: BlockHeightField) {
+        self.blocks.push(block);
+    }
+}
+
 #[cfg(test)]
 impl BlockHeightField {
     /// A constant-height field for tests (grass overlay unit tests).
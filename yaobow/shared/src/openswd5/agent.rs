@@ -8,7 +8,7 @@
 //! * Builds [`StateSnapshot`]s from SWD5 state ([`SWD5Context`]).
 //! * Dispatches [`AgentCommand`]s to the generic bridge surface, and
 //!   returns `NotImplemented` for the per-game gameplay endpoints SWD5
-//!   has no clean mapping for yet (save/load, teleport, dialog choice,
+//!   has no clean mapping for yet (mode control, dialog choice,
 //!   scene triggers/objects, script globals/eval/trace, world map).
 //!
 //! The dispatcher is invoked from `Swd5Service::pump_agent`; it never
@@ -76,15 +76,56 @@ pub fn dispatch_swd5_command(ctx: &Swd5DispatchCtx, command: AgentCommand) -> Ag
             AgentResponse::Ok
         }
 
+        C::TeleportPlayer(p) => match ctx.context.as_ref() {
+            Some(context) => {
+                use radiance::math::Vec3;
+                let pos = Vec3::new(p.pos[0], p.pos[1], p.pos[2]);
+                if context.borrow_mut().teleport_player(pos) {
+                    AgentResponse::Ok
+                } else {
+                    AgentResponse::err(AgentError::conflict(
+                        "SWD5 player role not placed yet — nothing to teleport",
+                    ))
+                }
+            }
+            None => AgentResponse::err(AgentError::conflict(
+                "SWD5 story director not installed yet",
+            )),
+        },
+
+        C::SaveSlot(p) => match ctx.context.as_ref() {
+            Some(_) if p.slot < 0 => AgentResponse::err(AgentError::bad_request(format!(
+                "SWD5 save slot must be non-negative, got {}",
+                p.slot
+            ))),
+            Some(context) => {
+                context.borrow().save_game(p.slot);
+                AgentResponse::Ok
+            }
+            None => AgentResponse::err(AgentError::conflict(
+                "SWD5 story director not installed yet — nothing to save",
+            )),
+        },
+
+        // SWD5 has no start menu, so the fresh-boot intent resolves to
+        // the same in-place restore as `/v1/load`.
+        C::LoadSlot(p) | C::EnterLoadGame(p) => match ctx.context.as_ref() {
+            Some(context) => match context.borrow_mut().load_game(p.slot) {
+                Ok(()) => AgentResponse::Ok,
+                Err(e) => AgentResponse::err(AgentError::bad_request(format!(
+                    "SWD5 cannot load slot {}: {:#}",
+                    p.slot, e
+                ))),
+            },
+            None => AgentResponse::err(AgentError::conflict(
+                "SWD5 story director not installed yet — nothing to load into",
+            )),
+        },
+
         // --- not yet implemented for SWD5 ---------------------------------
-        C::TeleportPlayer(_) => AgentResponse::err(AgentError::not_implemented(
-            "SWD5 has no controlled-role teleport surface yet",
+        C::EnterNewGame => AgentResponse::err(AgentError::not_implemented(
+            "SWD5 mode control is not implemented (single bootstrap script)",
         )),
-        C::SaveSlot(_) | C::EnterNewGame | C::EnterLoadGame(_) | C::LoadSlot(_) => {
-            AgentResponse::err(AgentError::not_implemented(
-                "SWD5 save/load + mode control are not implemented (single bootstrap script)",
-            ))
-        }
         C::ExitGame => AgentResponse::err(AgentError::not_implemented(
             "SWD5 exit-to-menu is not implemented (no menu mode)",
         )),
@@ -139,6 +180,9 @@ pub fn build_snapshot(ctx: &Swd5DispatchCtx) -> StateSnapshot {
         // The VM is "running" whenever it isn't parked in a `sleep`.
         snap.script_running = !context.is_sleeping();
         snap.movie_playing = context.is_movie_playing();
+        if let Some(pos) = context.player_position() {
+            snap.leader_pos = [pos.x, pos.y, pos.z];
+        }
     }
 
     // SWD5 dialog text isn't structured (free-form story/talk boxes);
@@ -1,8 +1,4 @@
-use std::{
-    io::{BufReader, Cursor},
-    path::PathBuf,
-    rc::Rc,
-};
+use std::{io::Cursor, path::PathBuf, rc::Rc};
 
 use common::{read_ext::ReadExt, store_ext::StoreExt2};
 use crosscom::ComRc;
@@ -19,18 +15,22 @@ use fileformats::{
 };
 use mini_fs::{MiniFs, StoreExt};
 use radiance::{
-    comdef::IScene,
+    comdef::{IEntity, IScene, ISceneExt},
+    components::{collision::CollisionWorldComponent, mesh::skinned_mesh::AnimKeyFrame},
     rendering::{ComponentFactory, Sprite},
     scene::CoreScene,
     utils::SeekRead,
+    video::Codec,
 };
 
 use crate::{
     GameType,
     loaders::{
         Swd5TextureResolver,
+        anm::load_anm,
         dff::{DffLoaderConfig, create_entity_from_dff_model},
     },
+    video::open_movie,
 };
 
 pub struct AssetLoader {
@@ -61,6 +61,10 @@ impl AssetLoader {
         })
     }
 
+    pub fn game(&self) -> GameType {
+        self.game
+    }
+
     pub fn component_factory(&self) -> Rc<dyn ComponentFactory> {
         self.component_factory.clone()
     }
@@ -74,7 +78,13 @@ impl AssetLoader {
     }
 
     pub fn load_main_script(&self) -> anyhow::Result<Vec<u8>> {
-        let content = self.vfs.read_to_end(self.main_script_path())?;
+        Self::read_main_script(&self.vfs, self.game)
+    }
+
+    /// Read and decompress the main Lua chunk without a full loader, for
+    /// offline tooling (e.g. dialog extraction).
+    pub fn read_main_script(vfs: &MiniFs, game: GameType) -> anyhow::Result<Vec<u8>> {
+        let content = vfs.read_to_end(Self::main_script_path(game))?;
         let mut reader = Cursor::new(content);
         let c00 = C00::read(&mut reader)?;
 
@@ -108,24 +118,45 @@ impl AssetLoader {
             &path,
             model_name.to_string(),
             true,
-            &DffLoaderConfig {
-                texture_resolver: &self.texture_resolver,
-                keep_right_to_render_only: false,
-                force_unique_materials: false,
-                ignore_root_frame_translation: false,
-
-                bsp_lightmap_tint: None,
-                dynamic_lighting: false,
-                fog_exempt: false,
-                foliage_resolver: None,
-            },
+            &self.dff_config(),
         )?;
 
         let scene = CoreScene::create();
+        // The map model is what the player role walks on and bumps into.
+        scene
+            .collision_world()
+            .inner::<CollisionWorldComponent>()
+            .attach_collider(&dff);
         scene.add_entity(dff);
         Ok(scene)
     }
 
+    /// Load a role's model from `/Role/<role_id>/<role_id>.dff`.
+    pub fn load_role(&self, role_id: i32) -> anyhow::Result<ComRc<IEntity>> {
+        let path = format!("/Role/{}/{}.dff", role_id, role_id);
+        log::debug!("loading role: {}", path);
+
+        create_entity_from_dff_model(
+            &self.component_factory,
+            &self.vfs,
+            &path,
+            format!("role_{}", role_id),
+            true,
+            &self.dff_config(),
+        )
+    }
+
+    /// Load one of a role's motions from `/Role/<role_id>/<motion>.anm`,
+    /// the motion id zero-padded to two digits.
+    pub fn load_role_motion(
+        &self,
+        role_id: i32,
+        motion: i32,
+    ) -> anyhow::Result<Vec<Vec<AnimKeyFrame>>> {
+        let path = format!("/Role/{}/{:0>2}.anm", role_id, motion);
+        load_anm(&self.vfs, path)
+    }
+
     pub fn load_story_pic(&self, pic_id: i32) -> anyhow::Result<Sprite> {
         let atp_entry = self.index[(pic_id + -1) as usize]
             .as_ref()
@@ -163,13 +194,11 @@ impl AssetLoader {
         }
     }
 
-    pub fn load_movie_data(&self, movie_id: u32) -> anyhow::Result<Box<dyn SeekRead>> {
+    pub fn load_movie_data(&self, movie_id: u32) -> anyhow::Result<(Box<dyn SeekRead>, Codec)> {
         let path = format!("/movie/movie{:0>2}.bik", movie_id);
 
         log::debug!("Loading movie: {}", path);
-        let content = self.vfs.open(path)?;
-
-        Ok(Box::new(BufReader::new(content)))
+        open_movie(&self.vfs, path)
     }
 
     pub fn load_fld(&self, map_id: i32) -> anyhow::Result<Fld> {
@@ -196,8 +225,22 @@ impl AssetLoader {
         Ok(map)
     }
 
-    fn main_script_path(&self) -> String {
-        match self.game {
+    fn dff_config(&self) -> DffLoaderConfig<'_> {
+        DffLoaderConfig {
+            texture_resolver: &self.texture_resolver,
+            keep_right_to_render_only: false,
+            force_unique_materials: false,
+            ignore_root_frame_translation: false,
+
+            bsp_lightmap_tint: None,
+            dynamic_lighting: false,
+            fog_exempt: false,
+            foliage_resolver: None,
+        }
+    }
+
+    fn main_script_path(game: GameType) -> String {
+        match game {
             GameType::SWD5 => "/Script/0000.C01".to_string(),
             GameType::SWDHC => "/Text/main/0000.C01".to_string(),
             GameType::SWDCF => "/Text/Off_Line/main/0000.C01".to_string(),
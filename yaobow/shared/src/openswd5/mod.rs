@@ -5,6 +5,8 @@ pub mod comdef {
     include!(concat!(env!("OUT_DIR"), "/shared_openswd5_comdef.rs"));
 }
 pub mod director;
+pub mod role;
 pub mod scene;
 pub mod scripting;
 pub mod service;
+pub mod states;
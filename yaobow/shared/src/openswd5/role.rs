@@ -0,0 +1,281 @@
+//! SWD5 field control.
+//!
+//! [`Swd5RoleController`] walks the player role over a field map. The
+//! ground comes from the map's floor grid ([`MapFloor`]), whose blocked
+//! cells stop the role like walls do; maps without a grid fall back to
+//! the map model baked into the scene's collision world. The role plays
+//! its stand / walk / run motions, the camera follows it keeping
+//! whatever framing the script last set, and crossing into a field
+//! trigger is reported back to the caller. Input is the shared arrow
+//! key / D-pad / left-stick movement, so the agent's synthetic input
+//! moves the role too.
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crosscom::ComRc;
+use fileformats::swd5::map::MapFloor;
+use radiance::{
+    comdef::{IArmatureComponent, IArmatureComponentExt, IEntity, IEntityExt, ISceneExt},
+    components::{collision::CollisionWorldComponent, mesh::skinned_mesh::AnimKeyFrame},
+    input::InputEngine,
+    math::Vec3,
+};
+
+use super::{asset_loader::AssetLoader, scene::Swd5Scene};
+use crate::{openpal5::role::resolve_move, utils::get_moving_direction};
+
+/// Motion ids of the looping field motions, as `set_motion` numbers them.
+pub const STAND_MOTION: i32 = 0;
+pub const WALK_MOTION: i32 = 1;
+pub const RUN_MOTION: i32 = 2;
+
+const WALK_SPEED: f32 = 120.;
+const RUN_SPEED: f32 = 300.;
+
+/// Clearance kept between the role and a wall of the map model.
+const BODY_RADIUS: f32 = 20.;
+
+/// Wall probes are cast this far above the feet; anything lower is a
+/// step the role climbs onto.
+const STEP_UP: f32 = 30.;
+
+pub struct Swd5RoleController {
+    role_id: i32,
+    entity: ComRc<IEntity>,
+    armature: Option<ComRc<IArmatureComponent>>,
+    /// Loaded motions by id. A motion that failed to load is kept as an
+    /// empty entry so it isn't looked up again.
+    motions: HashMap<i32, Vec<Vec<AnimKeyFrame>>>,
+    motion: Option<i32>,
+    /// `set_walks`: walk instead of run while moving.
+    walk_only: bool,
+}
+
+impl Swd5RoleController {
+    pub fn new(asset_loader: &AssetLoader, role_id: i32, entity: ComRc<IEntity>) -> Self {
+        let armature = entity
+            .get_component(IArmatureComponent::uuid())
+            .and_then(|c| c.query_interface::<IArmatureComponent>());
+        if armature.is_none() {
+            log::warn!("SWD5: role {} model has no armature", role_id);
+        }
+
+        let mut controller = Self {
+            role_id,
+            entity,
+            armature,
+            motions: HashMap::new(),
+            motion: None,
+            walk_only: false,
+        };
+        for motion in [STAND_MOTION, WALK_MOTION, RUN_MOTION] {
+            controller.load_motion(asset_loader, motion);
+        }
+        controller.play_motion(asset_loader, STAND_MOTION, true);
+        controller
+    }
+
+    pub fn role_id(&self) -> i32 {
+        self.role_id
+    }
+
+    pub fn entity(&self) -> &ComRc<IEntity> {
+        &self.entity
+    }
+
+    pub fn position(&self) -> Vec3 {
+        self.entity.transform().borrow().position()
+    }
+
+    pub fn set_position(&mut self, position: &Vec3) {
+        self.entity.transform().borrow_mut().set_position(position);
+    }
+
+    pub fn set_walk_only(&mut self, walk_only: bool) {
+        self.walk_only = walk_only;
+    }
+
+    /// Stop moving and go back to standing.
+    pub fn stop(&mut self, asset_loader: &AssetLoader) {
+        self.play_motion(asset_loader, STAND_MOTION, true);
+    }
+
+    /// Play `motion`, loading it on first use. Motions that can't be
+    /// found leave the role in its bind pose.
+    pub fn play_motion(&mut self, asset_loader: &AssetLoader, motion: i32, looping: bool) {
+        if self.motion == Some(motion) {
+            return;
+        }
+        self.motion = Some(motion);
+        self.load_motion(asset_loader, motion);
+
+        let Some(armature) = &self.armature else {
+            return;
+        };
+        let keyframes = &self.motions[&motion];
+        if keyframes.is_empty() {
+            armature.stop();
+            return;
+        }
+        armature.set_animation(keyframes.clone(), vec![]);
+        armature.set_looping(looping);
+        armature.play();
+    }
+
+    /// Move the role one frame from the player's input and pan the
+    /// camera after it. Returns the index (into `scene.triggers`) of the
+    /// trigger the role walked into this frame, if any.
+    pub fn update(
+        &mut self,
+        asset_loader: &AssetLoader,
+        input: Rc<RefCell<dyn InputEngine>>,
+        scene: &mut Swd5Scene,
+        delta_sec: f32,
+    ) -> Option<usize> {
+        let direction = get_moving_direction(input, scene.scene.clone());
+        let moving = direction.norm() > 0.5;
+        let motion = motion_for(moving, self.walk_only);
+        self.play_motion(asset_loader, motion, true);
+        if !moving {
+            return None;
+        }
+
+        let speed = if motion == WALK_MOTION {
+            WALK_SPEED
+        } else {
+            RUN_SPEED
+        };
+        let position = self.position();
+        let step = Vec3::scalar_mul(speed * delta_sec, &direction);
+
+        let world_com = scene.scene.collision_world();
+        let world = world_com.inner::<CollisionWorldComponent>();
+        let floor = scene.floor.as_ref();
+        let mut target = resolve_move(&position, &step, |origin, step| {
+            let end = Vec3::add(origin, step);
+            floor.is_some_and(|f| f.height_at(end.x, end.z).is_none())
+                || wall_ahead(world, origin, step)
+        });
+        let model_floor = || {
+            world
+                .cast_aa_ny(&Vec3::new(target.x, target.y + STEP_UP, target.z))
+                .map(|d| target.y + STEP_UP - d)
+        };
+        target.y = ground_at(floor, &target, model_floor)?;
+
+        let moved = Vec3::sub(&target, &position);
+        if moved.norm2() == 0. {
+            return None;
+        }
+        self.entity
+            .transform()
+            .borrow_mut()
+            .look_at(&Vec3::new(target.x, position.y, target.z))
+            .set_position(&target);
+
+        let body = Vec3::new(0., STEP_UP, 0.);
+        world.evaluate_segment_triggers(&Vec3::add(&position, &body), &Vec3::add(&target, &body));
+        let fired = world.fired_segment_trigger();
+        scene.pan_camera(&moved);
+
+        // A segment trigger fires on the way in and on the way out; only
+        // entering the region counts.
+        fired.map(|index| index as usize).filter(|&index| {
+            scene
+                .triggers
+                .get(index)
+                .is_some_and(|t| t.contains(target.x, target.z))
+        })
+    }
+
+    fn load_motion(&mut self, asset_loader: &AssetLoader, motion: i32) {
+        if self.motions.contains_key(&motion) {
+            return;
+        }
+
+        let keyframes = asset_loader
+            .load_role_motion(self.role_id, motion)
+            .unwrap_or_else(|e| {
+                log::debug!("SWD5: role {} motion {}: {:#}", self.role_id, motion, e);
+                vec![]
+            });
+        self.motions.insert(motion, keyframes);
+    }
+}
+
+fn motion_for(moving: bool, walk_only: bool) -> i32 {
+    match (moving, walk_only) {
+        (false, _) => STAND_MOTION,
+        (true, true) => WALK_MOTION,
+        (true, false) => RUN_MOTION,
+    }
+}
+
+/// Whether a wall of the map model stands within `step` (plus the body
+/// radius) of the role at `position`.
+fn wall_ahead(world: &CollisionWorldComponent, position: &Vec3, step: &Vec3) -> bool {
+    let length = step.norm();
+    let origin = Vec3::new(position.x, position.y + STEP_UP, position.z);
+    let direction = Vec3::scalar_mul(1. / length, step);
+    world
+        .cast_ray(&origin, &direction)
+        .is_some_and(|distance| distance < length + BODY_RADIUS)
+}
+
+/// Height the role stands at on `target`: the floor grid's where the map
+/// has one (`None` off the grid or on a blocked cell), otherwise the
+/// map model's, keeping the current height over a hole in the model.
+fn ground_at(
+    floor: Option<&MapFloor>,
+    target: &Vec3,
+    model_floor: impl FnOnce() -> Option<f32>,
+) -> Option<f32> {
+    match floor {
+        Some(floor) => floor.height_at(target.x, target.z),
+        None => Some(model_floor().unwrap_or(target.y)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fileformats::swd5::map::{FLOOR_BLOCKED, FloorCell};
+
+    use super::*;
+
+    #[test]
+    fn walks_only_after_set_walks() {
+        assert_eq!(motion_for(false, false), STAND_MOTION);
+        assert_eq!(motion_for(false, true), STAND_MOTION);
+        assert_eq!(motion_for(true, false), RUN_MOTION);
+        assert_eq!(motion_for(true, true), WALK_MOTION);
+    }
+
+    #[test]
+    fn floor_grid_wins_over_the_map_model() {
+        let floor = MapFloor {
+            columns: 2,
+            rows: 1,
+            origin_x: 0.,
+            origin_z: 0.,
+            cell_size: 100.,
+            cells: vec![
+                FloorCell {
+                    height: 15.,
+                    flags: 0,
+                },
+                FloorCell {
+                    height: 0.,
+                    flags: FLOOR_BLOCKED,
+                },
+            ],
+        };
+        let on_grid = Vec3::new(50., 0., 50.);
+        let blocked = Vec3::new(150., 0., 50.);
+        assert_eq!(ground_at(Some(&floor), &on_grid, || Some(99.)), Some(15.));
+        assert_eq!(ground_at(Some(&floor), &blocked, || Some(99.)), None);
+
+        let target = Vec3::new(0., 7., 0.);
+        assert_eq!(ground_at(None, &target, || Some(99.)), Some(99.));
+        assert_eq!(ground_at(None, &target, || None), Some(7.));
+    }
+}
@@ -1,13 +1,23 @@
 use crosscom::ComRc;
-use radiance::{comdef::IScene, math::Vec3};
+use fileformats::swd5::{fld::FldTrigger, map::MapFloor};
+use radiance::{comdef::IScene, components::collision::CollisionWorldComponent, math::Vec3};
 
 use super::asset_loader::AssetLoader;
 use radiance::comdef::ISceneExt;
 
+/// Half-height of the trigger box built for a field trigger. Triggers
+/// are 2D; the box only needs to span any height the role walks at.
+const TRIGGER_HALF_HEIGHT: f32 = 100000.;
+
 pub struct Swd5Scene {
     pub scene: ComRc<IScene>,
     pub camera_look_at: Vec3,
     pub camera_position: Vec3,
+    /// The map's floor grid; `None` when the terrain chunk carries none,
+    /// in which case the role stands on the map model instead.
+    pub floor: Option<MapFloor>,
+    /// Field triggers from the `.fld`. Segment-trigger ids index this list.
+    pub triggers: Vec<FldTrigger>,
 }
 
 impl Swd5Scene {
@@ -17,10 +27,55 @@ impl Swd5Scene {
         let scene = asset_loader.load_scene_dff(&map.model_chunk.model_file.to_string())?;
         scene.camera_mut().set_fov43(60_f32.to_radians());
 
+        let floor = match map.terrain_chunk.floor() {
+            Ok(floor) => Some(floor),
+            Err(e) => {
+                log::warn!(
+                    "map {}: floor grid did not decode, the role stands on the map model: {:#}",
+                    map_id,
+                    e
+                );
+                None
+            }
+        };
+
+        if fld.triggers.is_none() {
+            log::warn!("map {}: field trigger table did not decode", map_id);
+        }
+        let triggers = fld.triggers().to_vec();
+        let world_com = scene.collision_world();
+        let world = world_com.inner::<CollisionWorldComponent>();
+        for (index, trigger) in triggers.iter().enumerate() {
+            if trigger.kind != FldTrigger::KIND_ENTER {
+                continue;
+            }
+
+            let ring = [
+                (trigger.min_x, trigger.min_z),
+                (trigger.max_x, trigger.min_z),
+                (trigger.max_x, trigger.max_z),
+                (trigger.min_x, trigger.max_z),
+            ];
+            let corners = [-TRIGGER_HALF_HEIGHT, TRIGGER_HALF_HEIGHT]
+                .iter()
+                .flat_map(|&y| ring.iter().map(move |&(x, z)| Vec3::new(x, y, z)))
+                .collect();
+            world.attach_segment_trigger(corners, index as i64, trigger.function.to_string());
+        }
+
         Ok(Self {
             scene,
             camera_look_at: Vec3::new(0., 0., 0.),
             camera_position: Vec3::new(0., 0., 0.),
+            floor,
+            triggers,
+        })
+    }
+
+    /// The action-key trigger covering `position`, if any.
+    pub fn action_trigger_at(&self, position: &Vec3) -> Option<&FldTrigger> {
+        self.triggers.iter().find(|trigger| {
+            trigger.kind == FldTrigger::KIND_ACTION && trigger.contains(position.x, position.z)
         })
     }
 
@@ -59,4 +114,17 @@ impl Swd5Scene {
                 .look_at(&self.camera_look_at);
         };
     }
+
+    /// Move the camera and its look-at point together, keeping the
+    /// current framing. Used to follow the player role.
+    pub fn pan_camera(&mut self, delta: &Vec3) {
+        self.camera_look_at = Vec3::add(&self.camera_look_at, delta);
+        self.camera_position = Vec3::add(&self.camera_position, delta);
+        {
+            let mut c = self.scene.camera_mut();
+            c.transform_mut()
+                .set_position(&self.camera_position)
+                .look_at(&self.camera_look_at);
+        };
+    }
 }
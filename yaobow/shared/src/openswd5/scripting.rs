@@ -1,4 +1,10 @@
-use std::{cell::RefCell, collections::HashMap, os::raw::c_char, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    ffi::CString,
+    os::raw::c_char,
+    rc::Rc,
+};
 
 use crosscom::ComRc;
 use encoding::{DecoderTrap, Encoding};
@@ -8,6 +14,7 @@ use radiance::{
     audio::{AudioEngine, AudioMemorySource, AudioSourceState, Codec},
     comdef::ISceneManager,
     input::{InputEngine, Key},
+    math::Vec3,
     radiance::UiManager,
     rendering::{ComponentFactory, Sprite, VideoPlayer},
     utils::{act_drop::ActDrop, interp_value::InterpValue},
@@ -15,7 +22,38 @@ use radiance::{
 
 use crate::scripting::lua50_32::Lua5032Vm;
 
-use super::{asset_loader::AssetLoader, scene::Swd5Scene};
+use super::{
+    asset_loader::AssetLoader, role::Swd5RoleController, scene::Swd5Scene,
+    states::persistent_state::Swd5PersistentState,
+};
+
+/// The role the player controls on field maps.
+const PLAYER_ROLE: i32 = 0;
+
+/// Runs the main script, then keeps the VM alive dispatching field
+/// triggers to the Lua functions they name. `sleep(0)` parks the
+/// coroutine until the next frame.
+const HARNESS: &str = r#"
+function __swd5_main()
+  initiatelua()
+  while true do
+    local name, id = __swd5_next_event()
+    if name then
+      local f = _G[name]
+      if f then f(id) end
+    else
+      sleep(0)
+    end
+  end
+end
+"#;
+
+/// A field trigger waiting for the harness: the Lua function to call and
+/// the trigger id it gets.
+struct FieldEvent {
+    function: String,
+    id: i32,
+}
 
 pub struct SWD5Context {
     asset_loader: Rc<AssetLoader>,
@@ -31,6 +69,16 @@ pub struct SWD5Context {
     /// snapshot as the `scene` field. `0` before the first map load.
     current_map_id: i32,
 
+    /// Story flags and the player's whereabouts.
+    persistent_state: Swd5PersistentState,
+    /// The player role, once `chang_role_map` has placed it.
+    player: Option<Swd5RoleController>,
+    /// `lock_player` state; the role only moves while it is off and no
+    /// field event is running.
+    player_locked: bool,
+    pending_events: VecDeque<FieldEvent>,
+    event_running: bool,
+
     bgm_source: Box<dyn AudioMemorySource>,
     sound_sources: HashMap<i32, RefCell<Box<dyn AudioMemorySource>>>,
     story_msg: Option<StoryMsg>,
@@ -53,6 +101,7 @@ impl SWD5Context {
     ) -> Self {
         let bgm_source = audio_engine.create_source();
         let video_player = component_factory.create_video_player();
+        let persistent_state = Swd5PersistentState::new(asset_loader.game().app_name().to_string());
         Self {
             asset_loader,
             audio_engine,
@@ -64,6 +113,11 @@ impl SWD5Context {
             scene: None,
             sleep_sec: 0.,
             current_map_id: 0,
+            persistent_state,
+            player: None,
+            player_locked: false,
+            pending_events: VecDeque::new(),
+            event_running: false,
             bgm_source,
             sound_sources: HashMap::new(),
             story_msg: None,
@@ -89,6 +143,66 @@ impl SWD5Context {
         self.current_map_id
     }
 
+    pub fn persistent_state(&self) -> &Swd5PersistentState {
+        &self.persistent_state
+    }
+
+    /// The player role's position, once it has been placed.
+    pub fn player_position(&self) -> Option<Vec3> {
+        self.player.as_ref().map(|player| player.position())
+    }
+
+    /// Move the player role to `pos` (agent `/v1/player/teleport`).
+    /// Returns `false` when no role has been placed yet.
+    pub fn teleport_player(&mut self, pos: Vec3) -> bool {
+        let Some(player) = self.player.as_mut() else {
+            return false;
+        };
+        player.stop(&self.asset_loader);
+        player.set_position(&pos);
+        self.persistent_state.set_position(Some(pos));
+        true
+    }
+
+    /// Write the persistent state to `slot` (agent `/v1/save`).
+    pub fn save_game(&self, slot: i32) {
+        self.persistent_state.save(slot);
+    }
+
+    /// Replace the persistent state with save `slot` (agent
+    /// `/v1/load`): the saved map is loaded and the player role put
+    /// back where it stood. The running Lua script carries on with the
+    /// restored flags.
+    pub fn load_game(&mut self, slot: i32) -> anyhow::Result<()> {
+        let state = Swd5PersistentState::load(self.persistent_state.app_name(), slot)?;
+        let map_id = state.map_id();
+        let position = state.position();
+        self.persistent_state = state;
+        self.pending_events.clear();
+        if map_id == 0 {
+            // Saved before the first map; only the flags carry over.
+            return Ok(());
+        }
+
+        match position {
+            Some(p) => self.chang_role_map(map_id as f64, p.x as f64, p.y as f64, p.z as f64),
+            None => self.chang_map(map_id as f64, 0., 0., 0.),
+        }
+        if self.current_map_id != map_id {
+            anyhow::bail!("map {} failed to load", map_id);
+        }
+        log::info!("SWD5: loaded save slot {}", slot);
+        Ok(())
+    }
+
+    /// Pop the next field event for the harness, marking an event as
+    /// running until the harness asks again.
+    fn next_event(&mut self) -> Option<FieldEvent> {
+        let next = self.pending_events.pop_front();
+        self.event_running = next.is_some();
+        next
+    }
+
     /// Whether a bik movie is currently playing — surfaced to the
     /// agent snapshot's `movie_playing` flag.
     pub fn is_movie_playing(&self) -> bool {
@@ -107,6 +221,7 @@ impl SWD5Context {
     }
 
     pub fn update(&mut self, delta_sec: f32) {
+        self.persistent_state.add_playtime(delta_sec);
         if self.is_sleeping() {
             self.sleep_sec -= delta_sec;
             self.anykey_down = self.anykey_down || self.anykey_down();
@@ -115,12 +230,61 @@ impl SWD5Context {
         self.actdrop.update(self.ui.ui(), delta_sec);
 
         self.update_audio();
+        self.update_player(delta_sec);
         self.update_story_pic();
         self.update_storymsg();
         self.update_talkmsg();
         self.update_video();
     }
 
+    fn update_player(&mut self, delta_sec: f32) {
+        let controllable = !self.player_locked
+            && !self.event_running
+            && self.story_msg.is_none()
+            && self.talk_msg.is_none()
+            && self.story_pic.is_none()
+            && !self.is_movie_playing();
+        let action = self.action_key_down();
+        let (Some(player), Some(scene)) = (self.player.as_mut(), self.scene.as_mut()) else {
+            return;
+        };
+        if !controllable {
+            return;
+        }
+
+        let entered = player.update(
+            &self.asset_loader,
+            self.input_engine.clone(),
+            scene,
+            delta_sec,
+        );
+        let position = player.position();
+        self.persistent_state.set_position(Some(position));
+
+        let trigger = match entered {
+            Some(index) => scene.triggers.get(index),
+            None if action => scene.action_trigger_at(&position),
+            None => None,
+        };
+        let Some(trigger) = trigger else {
+            return;
+        };
+        if self.pending_events.iter().any(|e| e.id == trigger.id) {
+            return;
+        }
+
+        log::info!(
+            "SWD5: field trigger {} -> {}",
+            trigger.id,
+            trigger.function.to_string()
+        );
+        player.stop(&self.asset_loader);
+        self.pending_events.push_back(FieldEvent {
+            function: trigger.function.to_string(),
+            id: trigger.id,
+        });
+    }
+
     fn update_storymsg(&mut self) {
         if self.anykey_down() {
             self.story_msg = None;
@@ -231,6 +395,12 @@ impl SWD5Context {
         }
     }
 
+    fn action_key_down(&self) -> bool {
+        let input = self.input_engine.borrow();
+        input.get_key_state(Key::Space).pressed()
+            || input.get_key_state(Key::GamePadSouth).pressed()
+    }
+
     fn anykey_down(&mut self) -> bool {
         self.input_engine
             .borrow()
@@ -248,15 +418,27 @@ impl SWD5Context {
                 .pressed()
     }
 
-    fn isfon(&mut self, _f: f64) -> i32 {
-        0
+    fn isfon(&mut self, f: f64) -> i32 {
+        self.persistent_state.flag(f as i32) as i32
     }
 
-    fn fon(&mut self, _f: f64) {}
+    fn fon(&mut self, f: f64) {
+        self.persistent_state.set_flag(f as i32, true);
+    }
 
-    fn foff(&mut self, _f: f64) {}
+    fn foff(&mut self, f: f64) {
+        self.persistent_state.set_flag(f as i32, false);
+    }
 
-    fn lock_player(&mut self, _f: f64) {}
+    fn lock_player(&mut self, f: f64) {
+        self.player_locked = f != 0.;
+        let Some(player) = self.player.as_mut() else {
+            return;
+        };
+        if self.player_locked {
+            player.stop(&self.asset_loader);
+        }
+    }
 
     fn dark(&mut self, speed: f64) {
         self.actdrop
@@ -268,8 +450,15 @@ impl SWD5Context {
             .set_darkness(InterpValue::new(1., 0., 0.1 * speed as f32));
     }
 
+    /// Switch to a story map. The player role stays behind; the next
+    /// `chang_role_map` brings it back.
     fn chang_map(&mut self, map_id: f64, _x: f64, _y: f64, _z: f64) {
-        let map_id = map_id as i32;
+        if self.load_map(map_id as i32) {
+            self.player = None;
+        }
+    }
+
+    fn load_map(&mut self, map_id: i32) -> bool {
         let scene = Swd5Scene::load(&self.asset_loader, map_id);
         match scene {
             Ok(scene) => {
@@ -278,8 +467,13 @@ impl SWD5Context {
 
                 self.scene = Some(scene);
                 self.current_map_id = map_id;
+                self.persistent_state.set_map_id(map_id);
+                true
+            }
+            Err(e) => {
+                log::error!("load map {}: {:?}", map_id, e);
+                false
             }
-            Err(e) => log::error!("chang_map {}: {:?}", map_id, e),
         }
     }
 
@@ -302,11 +496,69 @@ impl SWD5Context {
         }
     }
 
-    fn chang_role_map(&mut self, _map_id: f64, _f2: f64, _f3: f64, _f4: f64) {}
+    /// Put the player role on field map `map_id` at `(x, y, z)`,
+    /// loading the map unless it is the current one.
+    fn chang_role_map(&mut self, map_id: f64, x: f64, y: f64, z: f64) {
+        let map_id = map_id as i32;
+        let reload = self.scene.is_none() || map_id != self.current_map_id;
+        if reload && !self.load_map(map_id) {
+            return;
+        }
+        let Some(scene) = self.scene.as_mut() else {
+            return;
+        };
+
+        let create = self.player.is_none();
+        if create {
+            match self.asset_loader.load_role(PLAYER_ROLE) {
+                Ok(entity) => {
+                    self.player = Some(Swd5RoleController::new(
+                        &self.asset_loader,
+                        PLAYER_ROLE,
+                        entity,
+                    ));
+                }
+                Err(e) => {
+                    log::error!("chang_role_map: role {}: {:?}", PLAYER_ROLE, e);
+                    return;
+                }
+            }
+        }
+        let Some(player) = self.player.as_mut() else {
+            return;
+        };
+        if reload || create {
+            scene.scene.add_entity(player.entity().clone());
+        }
+
+        let mut position = Vec3::new(x as f32, y as f32, z as f32);
+        if let Some(height) = scene
+            .floor
+            .as_ref()
+            .and_then(|floor| floor.height_at(position.x, position.z))
+        {
+            position.y = height;
+        }
+        player.stop(&self.asset_loader);
+        player.set_position(&position);
+        self.persistent_state.set_position(Some(position));
+    }
 
-    fn set_motion(&mut self, _f1: f64, _f2: f64) {}
+    fn set_motion(&mut self, role: f64, motion: f64) {
+        match self.player.as_mut() {
+            Some(player) if player.role_id() == role as i32 => {
+                player.play_motion(&self.asset_loader, motion as i32, false)
+            }
+            _ => log::debug!("set_motion: role {} is not on the map", role),
+        }
+    }
 
-    fn set_walks(&mut self, _f1: f64, _f2: f64) {}
+    fn set_walks(&mut self, role: f64, walks: f64) {
+        match self.player.as_mut() {
+            Some(player) if player.role_id() == role as i32 => player.set_walk_only(walks != 0.),
+            _ => log::debug!("set_walks: role {} is not on the map", role),
+        }
+    }
 
     fn play_sound(&mut self, sound_id: f64, _volume: f64) {
         let sound_id = sound_id as i32;
@@ -331,7 +583,7 @@ impl SWD5Context {
     }
 
     fn storymsg(&mut self, text: *const c_char) {
-        let text = decode_big5(text);
+        let text = localize_big5(text);
         let [width, height] = self.ui.ui().io().display_size;
 
         self.story_msg = Some(StoryMsg {
@@ -341,14 +593,14 @@ impl SWD5Context {
     }
 
     fn talkmsg(&mut self, name: *const c_char, text: *const c_char) {
-        let name = decode_big5(name);
-        let text = decode_big5(text);
+        let name = localize_big5(name);
+        let text = localize_big5(text);
 
         self.talk_msg = Some(TalkMsg { name, text });
     }
 
     fn storymsgpos(&mut self, text: *const c_char, x: f64, y: f64) {
-        let text = decode_big5(text);
+        let text = localize_big5(text);
         let (start, size) = calc_43_box(self.ui.ui());
         let x = x as f32 / 960. * size[0];
         let y = y as f32 / 720. * size[1];
@@ -392,12 +644,12 @@ impl SWD5Context {
     fn play_movie(&mut self, id: f64) {
         let reader = self.asset_loader.load_movie_data(id as u32);
         match reader {
-            Ok(reader) => {
+            Ok((reader, codec)) => {
                 self.video_player.play(
                     self.component_factory.clone(),
                     self.audio_engine.clone(),
                     reader,
-                    radiance::video::Codec::Bik,
+                    codec,
                     false,
                 );
             }
@@ -460,7 +712,9 @@ pub fn create_lua_vm(
     context: Rc<RefCell<SWD5Context>>,
 ) -> anyhow::Result<Lua5032Vm<SWD5Context>> {
     let script = asset_loader.load_main_script()?;
-    let vm = Lua5032Vm::new(script, "initiatelua", context)?;
+    let vm = Lua5032Vm::create(context);
+    vm.load_chunk(&script, "main")?;
+    vm.load_chunk(HARNESS.as_bytes(), "swd5_harness")?;
 
     def_func!(vm, isfon, f: number -> number);
     def_func!(vm, fon, f: number);
@@ -474,9 +728,9 @@ pub fn create_lua_vm(
     def_func!(vm, camera_mode, f: number);
     def_func!(vm, story_music_off, f1: number, f2: number);
     def_func!(vm, story_music, music_id: number, f2: number, f3: number, f4: number, f5: number, f6: number);
-    def_func!(vm, chang_role_map, map_id: number, f2: number, f3: number, f4: number);
-    def_func!(vm, set_motion, f1: number, f2: number);
-    def_func!(vm, set_walks, f1: number, f2: number);
+    def_func!(vm, chang_role_map, map_id: number, x: number, y: number, z: number);
+    def_func!(vm, set_motion, role: number, motion: number);
+    def_func!(vm, set_walks, role: number, walks: number);
     def_func!(vm, play_sound, sound_id: number, volume: number);
     def_func!(vm, storymsg, text: string);
     def_func!(vm, storymsgpos, text: string, x: number, y: number);
@@ -491,10 +745,30 @@ pub fn create_lua_vm(
     def_func!(vm, set_camera_pos, x: number, y: number, z: number);
     def_func!(vm, chang_camera_view, dx: number, dy: number, dis: number, time: number);
     def_func!(vm, set_role_face_motion, role: number, face_motion: number);
+    vm.register("__swd5_next_event", Some(next_event));
+    vm.set_entry("__swd5_main")?;
 
     Ok(vm)
 }
 
+/// `__swd5_next_event()` — the function name and trigger id of the next
+/// field event, or nil when none is pending.
+extern "C" fn next_event(state: *mut lua_State) -> i32 {
+    unsafe {
+        let v = lua50_32_sys::lua_touserdata(state, lua50_32_sys::LUA_GLOBALSINDEX - 1);
+        let context = &*(v as *const RefCell<SWD5Context>);
+        let Some(event) = context.borrow_mut().next_event() else {
+            lua50_32_sys::lua_pushnil(state);
+            return 1;
+        };
+
+        let function = CString::new(event.function).unwrap_or_default();
+        lua50_32_sys::lua_pushstring(state, function.as_ptr());
+        lua50_32_sys::lua_pushnumber(state, event.id as f64);
+    }
+    2
+}
+
 extern "C" fn sleep(state: *mut lua_State) -> i32 {
     unsafe {
         let delay = lua50_32_sys::lua_tonumber(state, 1);
@@ -513,6 +787,11 @@ fn decode_big5(s: *const c_char) -> String {
     }
 }
 
+/// Decode dialog text and pass it through the active language pack.
+fn localize_big5(s: *const c_char) -> String {
+    crate::localization::localize(&decode_big5(s)).into_owned()
+}
+
 struct StoryMsg {
     text: String,
     position: [f32; 2],
@@ -142,6 +142,7 @@ impl ISwd5ServiceImpl for Swd5Service {
             radiance_scripting::services::game_registry::ordinal_to_config_key(game_ordinal as i32)
                 .and_then(GameType::from_config_key)
                 .unwrap_or(GameType::SWDHC);
+        crate::localization::install_for_game(game);
 
         let engine_rc = self.app.engine();
         let engine = engine_rc.borrow();
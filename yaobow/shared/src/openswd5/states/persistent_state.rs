@@ -0,0 +1,169 @@
+use std::collections::BTreeSet;
+
+use radiance::math::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::saves::{self, SaveKind, SaveMetadata};
+
+/// SWD5 save namespace. Save slots live under
+/// `<save_dir>/<SWD5_APP_NAME>/Save/<slot>.json`.
+pub const SWD5_APP_NAME: &str = "OpenSWD5";
+
+/// SWDHC and SWDCF share the SWD5 state layout under their own
+/// namespaces.
+pub const SWDHC_APP_NAME: &str = "OpenSWDHC";
+pub const SWDCF_APP_NAME: &str = "OpenSWDCF";
+
+/// Serializable snapshot of SWD5-family progress: the story flags the
+/// Lua `fon` / `foff` / `isfon` functions drive, plus where the player
+/// stands. Shared by SWD5, SWDHC and SWDCF under their own app names.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Swd5PersistentState {
+    app_name: String,
+    #[serde(default)]
+    map_id: i32,
+    #[serde(default)]
+    position: Option<Vec3>,
+    /// Flags that are switched on; everything else is off.
+    #[serde(default)]
+    flags: BTreeSet<i32>,
+    #[serde(default)]
+    playtime: f64,
+}
+
+impl Swd5PersistentState {
+    pub fn new(app_name: String) -> Self {
+        Self {
+            app_name,
+            map_id: 0,
+            position: None,
+            flags: BTreeSet::new(),
+            playtime: 0.,
+        }
+    }
+
+    /// Load the persistent state for `app_name` from the given slot.
+    /// Returns an error if the slot file is missing, malformed or from a
+    /// newer build.
+    pub fn load(app_name: &str, slot: i32) -> anyhow::Result<Self> {
+        saves::load_state(Self::kind_of(app_name), app_name, slot)
+    }
+
+    /// Persist this state to the given slot. Negative slots are ignored.
+    pub fn save(&self, slot: i32) {
+        if slot < 0 {
+            return;
+        }
+
+        let metadata = SaveMetadata::now(self.playtime, self.location());
+        let kind = Self::kind_of(&self.app_name);
+        match saves::write_slot(kind, &self.app_name, slot, self, metadata) {
+            Ok(()) => log::info!("Game saved to slot {}", slot),
+            Err(e) => log::error!("Cannot save: {:#}", e),
+        }
+    }
+
+    fn kind_of(app_name: &str) -> SaveKind {
+        match app_name {
+            SWDHC_APP_NAME => SaveKind::SwdHc,
+            SWDCF_APP_NAME => SaveKind::SwdCf,
+            _ => SaveKind::Swd5,
+        }
+    }
+
+    pub fn app_name(&self) -> &str {
+        self.app_name.as_str()
+    }
+
+    /// Map id label for save metadata.
+    pub fn location(&self) -> String {
+        self.map_id.to_string()
+    }
+
+    // --- Story flags ---------------------------------------------------
+
+    pub fn set_flag(&mut self, flag: i32, on: bool) {
+        if on {
+            self.flags.insert(flag);
+        } else {
+            self.flags.remove(&flag);
+        }
+    }
+
+    pub fn flag(&self, flag: i32) -> bool {
+        self.flags.contains(&flag)
+    }
+
+    /// The flags that are on, in ascending order.
+    pub fn flags(&self) -> impl Iterator<Item = i32> + '_ {
+        self.flags.iter().copied()
+    }
+
+    // --- Map / position ------------------------------------------------
+
+    pub fn map_id(&self) -> i32 {
+        self.map_id
+    }
+
+    pub fn set_map_id(&mut self, map_id: i32) {
+        self.map_id = map_id;
+    }
+
+    pub fn position(&self) -> Option<Vec3> {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: Option<Vec3>) {
+        self.position = position;
+    }
+
+    // --- Playtime ------------------------------------------------------
+
+    pub fn playtime(&self) -> f64 {
+        self.playtime
+    }
+
+    pub fn add_playtime(&mut self, delta_sec: f32) {
+        self.playtime += delta_sec as f64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_switch_on_and_off() {
+        let mut state = Swd5PersistentState::new(SWD5_APP_NAME.to_string());
+        assert!(!state.flag(12));
+        state.set_flag(12, true);
+        state.set_flag(3, true);
+        state.set_flag(3, true);
+        assert!(state.flag(12));
+        assert_eq!(state.flags().collect::<Vec<_>>(), vec![3, 12]);
+
+        state.set_flag(12, false);
+        state.set_flag(99, false);
+        assert_eq!(state.flags().collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn state_survives_json_round_trip() {
+        let mut state = Swd5PersistentState::new(SWDHC_APP_NAME.to_string());
+        state.set_flag(7, true);
+        state.set_map_id(21);
+        state.set_position(Some(Vec3::new(1., 2., 3.)));
+
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: Swd5PersistentState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.app_name(), SWDHC_APP_NAME);
+        assert!(restored.flag(7));
+        assert_eq!(restored.map_id(), 21);
+        assert_eq!(restored.position().map(|p| p.y), Some(2.));
+
+        // Fields added later default when missing.
+        let minimal: Swd5PersistentState =
+            serde_json::from_str(r#"{"app_name":"OpenSWD5"}"#).unwrap();
+        assert!(!minimal.flag(7));
+    }
+}
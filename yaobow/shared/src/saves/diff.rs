@@ -0,0 +1,79 @@
+//! Structural diff between two save files.
+
+use serde_json::Value;
+
+/// One differing leaf. `path` uses `/`-separated keys and array
+/// indices, e.g. `players/0/level`; a `None` side means the value is
+/// absent there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveDiff {
+    pub path: String,
+    pub left: Option<Value>,
+    pub right: Option<Value>,
+}
+
+/// Differences between `left` and `right`, in key order. Objects and
+/// arrays are compared element-wise; everything else by value.
+pub fn diff(left: &Value, right: &Value) -> Vec<SaveDiff> {
+    let mut out = vec![];
+    diff_into(&mut out, String::new(), Some(left), Some(right));
+    out
+}
+
+fn diff_into(out: &mut Vec<SaveDiff>, path: String, left: Option<&Value>, right: Option<&Value>) {
+    match (left, right) {
+        (Some(Value::Object(l)), Some(Value::Object(r))) => {
+            let mut keys: Vec<&String> = l.keys().chain(r.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                diff_into(out, join(&path, key), l.get(key), r.get(key));
+            }
+        }
+        (Some(Value::Array(l)), Some(Value::Array(r))) => {
+            for i in 0..l.len().max(r.len()) {
+                diff_into(out, join(&path, &i.to_string()), l.get(i), r.get(i));
+            }
+        }
+        (l, r) if l != r => out.push(SaveDiff {
+            path,
+            left: l.cloned(),
+            right: r.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}/{}", path, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn reports_changed_added_and_removed_leaves() {
+        let left = json!({ "money": 10, "script_globals": [1, 2], "scene_name": "m01" });
+        let right = json!({ "money": 20, "script_globals": [1, 2, 3], "block_name": "2", "scene_name": "m01" });
+
+        let paths: Vec<(String, Option<Value>, Option<Value>)> = diff(&left, &right)
+            .into_iter()
+            .map(|d| (d.path, d.left, d.right))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("block_name".to_string(), None, Some(json!("2"))),
+                ("money".to_string(), Some(json!(10)), Some(json!(20))),
+                ("script_globals/2".to_string(), None, Some(json!(3))),
+            ]
+        );
+    }
+}
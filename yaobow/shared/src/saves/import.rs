@@ -0,0 +1,658 @@
+//! Import of retail saves written by the original PAL3 / PAL4
+//! executables.
+//!
+//! Retail saves are fixed-layout binary blobs whose offsets differ
+//! between releases and patches, so the importer does not hard-code
+//! them: a [`RetailLayout`] (TOML, see `docs/saves.md`) says where each
+//! piece of state lives in one particular release. Everything that
+//! can't be carried over ends up in the [`ImportReport`]: state the
+//! layout doesn't describe, retail data our state has no field for,
+//! and the byte ranges no spec reads.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::Path;
+
+use byteorder::{ByteOrder, LittleEndian};
+use encoding::{DecoderTrap, Encoding};
+use radiance::math::Vec3;
+use serde::Deserialize;
+
+use super::SaveKind;
+use crate::openpal3::states::persistent_state::{PAL3_APP_NAME, PersistentState};
+use crate::openpal4::states::persistent_state::{PAL4_APP_NAME, PLAYER_COUNT, Pal4PersistentState};
+
+/// Little-endian value type of a scalar field.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScalarType {
+    I8,
+    U8,
+    I16,
+    U16,
+    #[default]
+    I32,
+    U32,
+    F32,
+}
+
+impl ScalarType {
+    fn size(&self) -> usize {
+        match self {
+            ScalarType::I8 | ScalarType::U8 => 1,
+            ScalarType::I16 | ScalarType::U16 => 2,
+            ScalarType::I32 | ScalarType::U32 | ScalarType::F32 => 4,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ScalarSpec {
+    pub offset: usize,
+    #[serde(default, rename = "type")]
+    pub ty: ScalarType,
+}
+
+/// NUL-padded GBK string in a fixed-size field.
+#[derive(Deserialize, Clone, Debug)]
+pub struct StringSpec {
+    pub offset: usize,
+    pub len: usize,
+}
+
+/// Three consecutive `f32`s.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Vec3Spec {
+    pub offset: usize,
+}
+
+/// Script globals stored as a flat array. Element `i` becomes variable
+/// `first_index + i * index_step` (PAL3 SCE globals are negative ids,
+/// so a PAL3 layout typically uses `-1` / `-1`).
+#[derive(Deserialize, Clone, Debug)]
+pub struct GlobalsSpec {
+    pub offset: usize,
+    pub count: usize,
+    #[serde(default, rename = "type")]
+    pub ty: ScalarType,
+    #[serde(default)]
+    pub first_index: i32,
+    #[serde(default = "default_index_step")]
+    pub index_step: i32,
+}
+
+fn default_index_step() -> i32 {
+    1
+}
+
+/// `count` fixed-size records starting at `offset`. Field offsets are
+/// relative to the start of each record.
+#[derive(Deserialize, Clone, Debug)]
+pub struct RecordSpec {
+    pub offset: usize,
+    pub count: usize,
+    pub stride: usize,
+    pub fields: BTreeMap<String, ScalarSpec>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct MagicSpec {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+/// Where each piece of state lives in one retail release's save file.
+/// Every section is optional; missing ones are listed as unmapped.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct RetailLayout {
+    /// `pal3` or `pal4`.
+    pub game: String,
+
+    /// Release the layout was verified against, for reports.
+    #[serde(default)]
+    pub name: String,
+
+    /// Exact file size, if the release always writes the same size.
+    #[serde(default)]
+    pub size: Option<usize>,
+
+    #[serde(default)]
+    pub magic: Option<MagicSpec>,
+
+    #[serde(default)]
+    pub globals: Option<GlobalsSpec>,
+    #[serde(default)]
+    pub scene: Option<StringSpec>,
+    /// PAL3 sub-scene or PAL4 block.
+    #[serde(default)]
+    pub sub_scene: Option<StringSpec>,
+    #[serde(default)]
+    pub position: Option<Vec3Spec>,
+    #[serde(default)]
+    pub direction: Option<ScalarSpec>,
+    #[serde(default)]
+    pub leader: Option<ScalarSpec>,
+    #[serde(default)]
+    pub money: Option<ScalarSpec>,
+    #[serde(default)]
+    pub quest_percentage: Option<ScalarSpec>,
+
+    /// One record per party member, in player-slot order. Known fields:
+    /// `level`, `hp`, `max_hp`, `mp`, `max_mp`, `in_team`.
+    #[serde(default)]
+    pub party: Option<RecordSpec>,
+
+    /// Item records with `id` and `count` fields. Records with a zero
+    /// id or non-positive count are empty slots.
+    #[serde(default)]
+    pub inventory: Option<RecordSpec>,
+}
+
+impl RetailLayout {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::from_toml(&text)
+    }
+
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    pub fn kind(&self) -> anyhow::Result<SaveKind> {
+        SaveKind::from_key(&self.game)
+            .ok_or_else(|| anyhow::anyhow!("layout targets unknown game '{}'", self.game))
+    }
+}
+
+/// What an import carried over and what it left behind.
+#[derive(Debug, Default, Clone)]
+pub struct ImportReport {
+    /// State fields filled from the retail save.
+    pub mapped: Vec<String>,
+
+    /// State that could not be carried over, with the reason.
+    pub unmapped: Vec<String>,
+
+    /// Suspicious values that were still imported.
+    pub warnings: Vec<String>,
+
+    /// Byte ranges of the retail file no spec read.
+    pub unread: Vec<Range<usize>>,
+}
+
+impl ImportReport {
+    pub fn unread_bytes(&self) -> usize {
+        self.unread.iter().map(|r| r.len()).sum()
+    }
+
+    fn not_in_layout(&mut self, field: &str) {
+        self.unmapped
+            .push(format!("{}: layout has no `{}` section", field, field));
+    }
+
+    fn no_target(&mut self, field: &str, state: &str) {
+        self.unmapped
+            .push(format!("{}: {} has no field for it", field, state));
+    }
+}
+
+/// Import a retail PAL3 save described by `layout`.
+pub fn import_pal3(
+    data: &[u8],
+    layout: &RetailLayout,
+) -> anyhow::Result<(PersistentState, ImportReport)> {
+    let mut reader = Reader::open(data, layout, SaveKind::Pal3)?;
+    let mut report = ImportReport::default();
+    let mut state = PersistentState::new(PAL3_APP_NAME.to_string());
+
+    match &layout.globals {
+        Some(spec) => {
+            for (var, value) in reader.globals(spec)? {
+                match i16::try_from(var) {
+                    Ok(var) => state.set_global(var, value as i32),
+                    Err(_) => report
+                        .warnings
+                        .push(format!("global {} is out of the SCE variable range", var)),
+                }
+            }
+            report.mapped.push("global_vars".to_string());
+        }
+        None => report.not_in_layout("globals"),
+    }
+
+    match (&layout.scene, &layout.sub_scene) {
+        (Some(scene), Some(sub_scene)) => {
+            let scene = reader.string(scene)?;
+            let sub_scene = reader.string(sub_scene)?;
+            if scene.is_empty() || sub_scene.is_empty() {
+                report
+                    .warnings
+                    .push("scene or sub_scene is empty; the save will not load".to_string());
+            }
+            state.set_scene_name(scene, sub_scene);
+            report.mapped.push("scene".to_string());
+            report.mapped.push("sub_scene".to_string());
+        }
+        (None, _) => report.not_in_layout("scene"),
+        (_, None) => report.not_in_layout("sub_scene"),
+    }
+
+    match &layout.position {
+        Some(spec) => {
+            state.set_position(reader.vec3(spec)?);
+            report.mapped.push("position".to_string());
+        }
+        None => report.not_in_layout("position"),
+    }
+
+    // PAL3 keeps party, money and items in SCE globals and role state
+    // we don't persist yet; report them instead of dropping silently.
+    for (field, present) in [
+        ("direction", layout.direction.is_some()),
+        ("leader", layout.leader.is_some()),
+        ("money", layout.money.is_some()),
+        ("quest_percentage", layout.quest_percentage.is_some()),
+        ("party", layout.party.is_some()),
+        ("inventory", layout.inventory.is_some()),
+    ] {
+        if present {
+            report.no_target(field, "PersistentState");
+        }
+    }
+
+    report.unread = reader.unread();
+    Ok((state, report))
+}
+
+/// Import a retail PAL4 save described by `layout`.
+pub fn import_pal4(
+    data: &[u8],
+    layout: &RetailLayout,
+) -> anyhow::Result<(Pal4PersistentState, ImportReport)> {
+    let mut reader = Reader::open(data, layout, SaveKind::Pal4)?;
+    let mut report = ImportReport::default();
+    let mut state = Pal4PersistentState::new(PAL4_APP_NAME.to_string());
+
+    // Retail saves are taken during free movement.
+    state.set_player_locked(false);
+
+    match &layout.globals {
+        Some(spec) => {
+            let mut globals = vec![];
+            for (index, value) in reader.globals(spec)? {
+                match usize::try_from(index) {
+                    Ok(index) => {
+                        if globals.len() <= index {
+                            globals.resize(index + 1, 0);
+                        }
+                        globals[index] = value as u32;
+                    }
+                    Err(_) => report
+                        .warnings
+                        .push(format!("global {} has a negative index", index)),
+                }
+            }
+            state.set_script_globals(globals);
+            report.mapped.push("script_globals".to_string());
+        }
+        None => report.not_in_layout("globals"),
+    }
+
+    match &layout.scene {
+        Some(spec) => {
+            let scene = reader.string(spec)?;
+            let block = match &layout.sub_scene {
+                Some(spec) => reader.string(spec)?,
+                None => {
+                    report.not_in_layout("sub_scene");
+                    String::new()
+                }
+            };
+            state.set_scene(scene, block);
+            report.mapped.push("scene_name".to_string());
+            if layout.sub_scene.is_some() {
+                report.mapped.push("block_name".to_string());
+            }
+        }
+        None => report.not_in_layout("scene"),
+    }
+
+    match &layout.position {
+        Some(spec) => {
+            state.set_position(Some(reader.vec3(spec)?));
+            report.mapped.push("position".to_string());
+        }
+        None => report.not_in_layout("position"),
+    }
+
+    match &layout.direction {
+        Some(spec) => {
+            state.set_direction(Some(reader.float(spec, 0)?));
+            report.mapped.push("direction".to_string());
+        }
+        None => report.not_in_layout("direction"),
+    }
+
+    match &layout.leader {
+        Some(spec) => {
+            let leader = reader.int(spec, 0)?;
+            if (0..PLAYER_COUNT as i64).contains(&leader) {
+                state.set_leader(leader as usize);
+                report.mapped.push("leader".to_string());
+            } else {
+                report
+                    .unmapped
+                    .push(format!("leader: {} is not a party slot", leader));
+            }
+        }
+        None => report.not_in_layout("leader"),
+    }
+
+    match &layout.money {
+        Some(spec) => {
+            state.add_money(reader.int(spec, 0)?.clamp(0, i32::MAX as i64) as i32);
+            report.mapped.push("money".to_string());
+        }
+        None => report.not_in_layout("money"),
+    }
+
+    match &layout.quest_percentage {
+        Some(spec) => {
+            let percentage = reader.int(spec, 0)?;
+            if !(0..=100).contains(&percentage) {
+                report
+                    .warnings
+                    .push(format!("quest percentage {} clamped", percentage));
+            }
+            state.add_quest_percentage(percentage.clamp(0, 100) as i32);
+            report.mapped.push("quest_percentage".to_string());
+        }
+        None => report.not_in_layout("quest_percentage"),
+    }
+
+    match &layout.party {
+        Some(spec) => {
+            for slot in 0..spec.count {
+                let base = spec.offset + slot * spec.stride;
+                for (name, field) in &spec.fields {
+                    let value = reader.int(field, base)?;
+                    let player = state.player_mut(slot);
+                    match name.as_str() {
+                        "level" => player.level = value as i32,
+                        "hp" => player.hp = value as i32,
+                        "max_hp" => player.max_hp = value as i32,
+                        "mp" => player.mp = value as i32,
+                        "max_mp" => player.max_mp = value as i32,
+                        "in_team" => player.in_team = value != 0,
+                        _ => {
+                            if slot == 0 {
+                                report.no_target(&format!("party.{}", name), "PlayerState");
+                            }
+                        }
+                    }
+                }
+            }
+            report.mapped.push("players".to_string());
+        }
+        None => report.not_in_layout("party"),
+    }
+
+    match &layout.inventory {
+        Some(spec) => {
+            let (id, count) = match (spec.fields.get("id"), spec.fields.get("count")) {
+                (Some(id), Some(count)) => (id, count),
+                _ => anyhow::bail!("inventory records need `id` and `count` fields"),
+            };
+            for record in 0..spec.count {
+                let base = spec.offset + record * spec.stride;
+                let item = reader.int(id, base)?;
+                let n = reader.int(count, base)?;
+                if item != 0 && n > 0 {
+                    state.add_equipment(item as i32, n as i32);
+                }
+            }
+            for name in spec.fields.keys().filter(|k| *k != "id" && *k != "count") {
+                report.no_target(&format!("inventory.{}", name), "the inventory");
+            }
+            report.mapped.push("inventory".to_string());
+        }
+        None => report.not_in_layout("inventory"),
+    }
+
+    report
+        .unmapped
+        .push("camera: retail saves restore the scene's default camera".to_string());
+
+    report.unread = reader.unread();
+    Ok((state, report))
+}
+
+/// Bounds-checked little-endian reader that remembers which bytes were
+/// consumed.
+struct Reader<'a> {
+    data: &'a [u8],
+    read: Vec<Range<usize>>,
+}
+
+impl<'a> Reader<'a> {
+    fn open(data: &'a [u8], layout: &RetailLayout, kind: SaveKind) -> anyhow::Result<Self> {
+        if layout.kind()? != kind {
+            anyhow::bail!(
+                "layout '{}' is for {}, not {}",
+                layout.name,
+                layout.game,
+                kind.key()
+            );
+        }
+
+        if let Some(size) = layout.size {
+            if data.len() != size {
+                anyhow::bail!(
+                    "file is {} bytes but layout '{}' expects {}",
+                    data.len(),
+                    layout.name,
+                    size
+                );
+            }
+        }
+
+        let mut reader = Self { data, read: vec![] };
+        if let Some(magic) = &layout.magic {
+            if reader.bytes(magic.offset, magic.bytes.len())? != magic.bytes.as_slice() {
+                anyhow::bail!("magic mismatch: not a '{}' save", layout.name);
+            }
+        }
+
+        Ok(reader)
+    }
+
+    fn bytes(&mut self, offset: usize, len: usize) -> anyhow::Result<&'a [u8]> {
+        let end = offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "0x{:x}+{} is past the end of the file ({} bytes)",
+                    offset,
+                    len,
+                    self.data.len()
+                )
+            })?;
+        self.read.push(offset..end);
+        Ok(&self.data[offset..end])
+    }
+
+    fn int(&mut self, spec: &ScalarSpec, base: usize) -> anyhow::Result<i64> {
+        let b = self.bytes(base + spec.offset, spec.ty.size())?;
+        Ok(match spec.ty {
+            ScalarType::I8 => b[0] as i8 as i64,
+            ScalarType::U8 => b[0] as i64,
+            ScalarType::I16 => LittleEndian::read_i16(b) as i64,
+            ScalarType::U16 => LittleEndian::read_u16(b) as i64,
+            ScalarType::I32 => LittleEndian::read_i32(b) as i64,
+            ScalarType::U32 => LittleEndian::read_u32(b) as i64,
+            ScalarType::F32 => LittleEndian::read_f32(b) as i64,
+        })
+    }
+
+    fn float(&mut self, spec: &ScalarSpec, base: usize) -> anyhow::Result<f32> {
+        match spec.ty {
+            ScalarType::F32 => Ok(LittleEndian::read_f32(self.bytes(base + spec.offset, 4)?)),
+            _ => Ok(self.int(spec, base)? as f32),
+        }
+    }
+
+    fn vec3(&mut self, spec: &Vec3Spec) -> anyhow::Result<Vec3> {
+        let b = self.bytes(spec.offset, 12)?;
+        Ok(Vec3::new(
+            LittleEndian::read_f32(&b[0..4]),
+            LittleEndian::read_f32(&b[4..8]),
+            LittleEndian::read_f32(&b[8..12]),
+        ))
+    }
+
+    fn string(&mut self, spec: &StringSpec) -> anyhow::Result<String> {
+        let b = self.bytes(spec.offset, spec.len)?;
+        let end = b.iter().position(|c| *c == 0).unwrap_or(b.len());
+        encoding::all::GBK
+            .decode(&b[..end], DecoderTrap::Strict)
+            .map_err(|e| anyhow::anyhow!("string at 0x{:x}: {}", spec.offset, e))
+    }
+
+    /// `(variable, value)` pairs of the non-zero globals; zero is what
+    /// an unset global reads as anyway.
+    fn globals(&mut self, spec: &GlobalsSpec) -> anyhow::Result<Vec<(i64, i64)>> {
+        let element = ScalarSpec {
+            offset: 0,
+            ty: spec.ty,
+        };
+        let mut globals = vec![];
+        for i in 0..spec.count {
+            let value = self.int(&element, spec.offset + i * spec.ty.size())?;
+            if value != 0 {
+                let var = spec.first_index as i64 + i as i64 * spec.index_step as i64;
+                globals.push((var, value));
+            }
+        }
+        Ok(globals)
+    }
+
+    /// Ranges of `data` no read touched, merged and sorted.
+    fn unread(&self) -> Vec<Range<usize>> {
+        let mut read = self.read.clone();
+        read.sort_by_key(|r| r.start);
+
+        let mut unread = vec![];
+        let mut cursor = 0;
+        for range in read {
+            if range.start > cursor {
+                unread.push(cursor..range.start);
+            }
+            cursor = cursor.max(range.end);
+        }
+        if cursor < self.data.len() {
+            unread.push(cursor..self.data.len());
+        }
+        unread
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn put_i32(data: &mut [u8], offset: usize, value: i32) {
+        LittleEndian::write_i32(&mut data[offset..offset + 4], value);
+    }
+
+    fn put_f32(data: &mut [u8], offset: usize, value: f32) {
+        LittleEndian::write_f32(&mut data[offset..offset + 4], value);
+    }
+
+    #[test]
+    fn pal3_maps_globals_scene_and_position() {
+        let layout = RetailLayout::from_toml(
+            r#"
+            game = "pal3"
+            name = "test"
+            magic = { offset = 0, bytes = [0x50, 0x33] }
+            globals = { offset = 4, count = 4, first_index = -1, index_step = -1 }
+            scene = { offset = 20, len = 8 }
+            sub_scene = { offset = 28, len = 8 }
+            position = { offset = 36 }
+            money = { offset = 48 }
+            "#,
+        )
+        .unwrap();
+
+        let mut data = vec![0u8; 64];
+        data[0..2].copy_from_slice(b"P3");
+        put_i32(&mut data, 4, 7);
+        put_i32(&mut data, 12, 42);
+        data[20..23].copy_from_slice(b"q01");
+        data[28..32].copy_from_slice(b"q01a");
+        put_f32(&mut data, 36, 1.5);
+        put_f32(&mut data, 44, -2.0);
+
+        let (state, report) = import_pal3(&data, &layout).unwrap();
+        assert_eq!(state.get_global(-1), Some(7));
+        assert_eq!(state.get_global(-2), None);
+        assert_eq!(state.get_global(-3), Some(42));
+        assert_eq!(state.scene_name().as_deref(), Some("q01"));
+        assert_eq!(state.sub_scene_name().as_deref(), Some("q01a"));
+        assert!(report.unmapped.iter().any(|u| u.starts_with("money:")));
+        assert_eq!(report.unread, vec![2..4, 48..64]);
+    }
+
+    #[test]
+    fn pal4_maps_party_and_inventory() {
+        let layout = RetailLayout::from_toml(
+            r#"
+            game = "pal4"
+            size = 64
+            globals = { offset = 0, count = 2 }
+            money = { offset = 8 }
+            leader = { offset = 12, type = "u8" }
+
+            [party]
+            offset = 16
+            count = 2
+            stride = 8
+            fields = { level = { offset = 0, type = "i16" }, in_team = { offset = 2, type = "u8" }, exp = { offset = 4 } }
+
+            [inventory]
+            offset = 32
+            count = 4
+            stride = 4
+            fields = { id = { offset = 0, type = "i16" }, count = { offset = 2, type = "i16" } }
+            "#,
+        )
+        .unwrap();
+
+        let mut data = vec![0u8; 64];
+        put_i32(&mut data, 4, 3);
+        put_i32(&mut data, 8, 1200);
+        data[12] = 1;
+        data[16] = 15;
+        data[18] = 1;
+        data[24] = 9;
+        LittleEndian::write_i16(&mut data[32..34], 501);
+        LittleEndian::write_i16(&mut data[34..36], 2);
+        LittleEndian::write_i16(&mut data[36..38], 777);
+
+        let (state, report) = import_pal4(&data, &layout).unwrap();
+        assert_eq!(state.script_globals(), &[0, 3]);
+        assert_eq!(state.money(), 1200);
+        assert_eq!(state.leader(), 1);
+        assert_eq!(state.player_level(0), 15);
+        assert!(state.player(0).unwrap().in_team);
+        assert_eq!(state.player_level(1), 9);
+        assert_eq!(state.equipment_count(501), 2);
+        assert!(!state.has_equipment(777));
+        assert!(!state.player_locked());
+        assert!(report.unmapped.iter().any(|u| u.starts_with("party.exp:")));
+        assert!(report.unmapped.iter().any(|u| u.starts_with("scene:")));
+
+        assert!(import_pal4(&data[..60], &layout).is_err());
+    }
+}
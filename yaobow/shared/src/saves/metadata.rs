@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::THUMBNAIL_SOURCE;
+
+/// Longest edge of a save thumbnail, in pixels.
+const THUMBNAIL_SIZE: u32 = 256;
+
+/// Sidecar written next to each slot (`<slot>.meta.json`). Purely
+/// informational: a missing or stale sidecar never blocks a load.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SaveMetadata {
+    /// Schema version of the state file this sidecar describes.
+    #[serde(default)]
+    pub save_version: u32,
+
+    /// Seconds since the Unix epoch at save time.
+    #[serde(default)]
+    pub timestamp: u64,
+
+    /// Accumulated in-game time of the playthrough, in seconds.
+    #[serde(default)]
+    pub playtime: f64,
+
+    /// Human-readable location, e.g. `q01/q01a` or `m05/3`.
+    #[serde(default)]
+    pub scene: String,
+
+    /// Thumbnail file name relative to the slot directory.
+    #[serde(default)]
+    pub thumbnail: Option<String>,
+}
+
+impl SaveMetadata {
+    /// Metadata stamped with the current time.
+    pub fn now(playtime: f64, scene: String) -> Self {
+        Self {
+            timestamp: unix_now(),
+            playtime,
+            scene,
+            ..Default::default()
+        }
+    }
+}
+
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// `YYYY-MM-DD HH:MM:SS` (UTC) for a Unix timestamp.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let secs = timestamp % 86400;
+
+    // Civil-from-days (Howard Hinnant), valid for the whole u64 range
+    // we care about.
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// Downscale the installed engine's last presented frame into `path`.
+/// Returns `Ok(false)` when no source is installed, the engine is busy,
+/// or the backend cannot capture.
+pub(super) fn capture_thumbnail(path: &Path) -> anyhow::Result<bool> {
+    let frame = THUMBNAIL_SOURCE.with(|source| {
+        let source = source.borrow();
+        let engine = source.as_ref()?;
+        let mut engine = engine.try_borrow_mut().ok()?;
+        engine.capture_last_frame()
+    });
+
+    let frame = match frame {
+        Some(frame) => frame,
+        None => return Ok(false),
+    };
+
+    let image = image::RgbaImage::from_raw(frame.width, frame.height, frame.rgba)
+        .ok_or_else(|| anyhow::anyhow!("captured frame has an unexpected size"))?;
+    let scale = THUMBNAIL_SIZE as f32 / frame.width.max(frame.height).max(1) as f32;
+    let (width, height) = if scale < 1. {
+        (
+            ((frame.width as f32 * scale) as u32).max(1),
+            ((frame.height as f32 * scale) as u32).max(1),
+        )
+    } else {
+        (frame.width, frame.height)
+    };
+
+    image::imageops::thumbnail(&image, width, height).save(path)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_format_as_utc() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00:00");
+        assert_eq!(format_timestamp(1_790_000_000), "2026-09-21 14:13:20");
+    }
+}
@@ -0,0 +1,305 @@
+//! Save-slot storage shared by the PAL3, PAL4, PAL5 and SWD5 persistent
+//! states.
+//!
+//! Slots are JSON files under `<save_dir>/<app_name>/Save/<slot>.json`.
+//! Every file carries a top-level `save_version`; files written before
+//! versioning existed count as version 0. Loading runs the raw JSON
+//! through [`schema::migrate`] before deserializing, so a state layout
+//! change ships as one more migration step instead of breaking every
+//! existing save.
+//!
+//! Each slot also gets a `<slot>.meta.json` sidecar ([`SaveMetadata`])
+//! and, when a presented frame is available, a `<slot>.png` thumbnail.
+//! `tools/save_tool` lists, validates, diffs and repairs slots offline
+//! using the same [`validate`] and [`diff`] helpers.
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use radiance::rendering::RenderingEngine;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::openpal3::states::persistent_state::{PAL3_APP_NAME, PersistentState};
+use crate::openpal4::states::persistent_state::{PAL4_APP_NAME, Pal4PersistentState};
+use crate::openpal5::states::persistent_state::{
+    PAL5_APP_NAME, PAL5Q_APP_NAME, Pal5PersistentState,
+};
+use crate::openswd5::states::persistent_state::{
+    SWD5_APP_NAME, SWDCF_APP_NAME, SWDHC_APP_NAME, Swd5PersistentState,
+};
+use crate::ydirs;
+
+pub mod diff;
+pub mod import;
+mod metadata;
+pub mod schema;
+pub mod validate;
+
+pub use metadata::{SaveMetadata, format_timestamp, unix_now};
+
+/// Games whose saves go through this module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SaveKind {
+    Pal3,
+    Pal4,
+    Pal5,
+    /// PAL5Q saves: the PAL5 state layout in its own namespace.
+    Pal5Q,
+    Swd5,
+    /// SWDHC and SWDCF saves: the SWD5 state layout in their own
+    /// namespaces.
+    SwdHc,
+    SwdCf,
+}
+
+impl SaveKind {
+    pub const ALL: [SaveKind; 7] = [
+        SaveKind::Pal3,
+        SaveKind::Pal4,
+        SaveKind::Pal5,
+        SaveKind::Pal5Q,
+        SaveKind::Swd5,
+        SaveKind::SwdHc,
+        SaveKind::SwdCf,
+    ];
+
+    /// Save namespace under the save dir.
+    pub fn app_name(&self) -> &'static str {
+        match self {
+            SaveKind::Pal3 => PAL3_APP_NAME,
+            SaveKind::Pal4 => PAL4_APP_NAME,
+            SaveKind::Pal5 => PAL5_APP_NAME,
+            SaveKind::Pal5Q => PAL5Q_APP_NAME,
+            SaveKind::Swd5 => SWD5_APP_NAME,
+            SaveKind::SwdHc => SWDHC_APP_NAME,
+            SaveKind::SwdCf => SWDCF_APP_NAME,
+        }
+    }
+
+    /// Config key used on the command line (`pal3`, `pal4`, `pal5`,
+    /// `pal5q`, `swd5`, `swdhc`, `swdcf`).
+    pub fn key(&self) -> &'static str {
+        match self {
+            SaveKind::Pal3 => "pal3",
+            SaveKind::Pal4 => "pal4",
+            SaveKind::Pal5 => "pal5",
+            SaveKind::Pal5Q => "pal5q",
+            SaveKind::Swd5 => "swd5",
+            SaveKind::SwdHc => "swdhc",
+            SaveKind::SwdCf => "swdcf",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.key().eq_ignore_ascii_case(key))
+    }
+
+    pub fn schema(&self) -> &'static schema::SaveSchema {
+        match self {
+            SaveKind::Pal3 => &schema::PAL3_SCHEMA,
+            SaveKind::Pal4 => &schema::PAL4_SCHEMA,
+            SaveKind::Pal5 | SaveKind::Pal5Q => &schema::PAL5_SCHEMA,
+            SaveKind::Swd5 | SaveKind::SwdHc | SaveKind::SwdCf => &schema::SWD5_SCHEMA,
+        }
+    }
+
+    /// A fresh state serialized to JSON. Repair fills missing or
+    /// malformed fields from it.
+    pub fn default_state(&self) -> Value {
+        let app_name = self.app_name().to_string();
+        let value = match self {
+            SaveKind::Pal3 => serde_json::to_value(PersistentState::new(app_name)),
+            SaveKind::Pal4 => serde_json::to_value(Pal4PersistentState::new(app_name)),
+            SaveKind::Pal5 | SaveKind::Pal5Q => {
+                serde_json::to_value(Pal5PersistentState::new(app_name))
+            }
+            SaveKind::Swd5 | SaveKind::SwdHc | SaveKind::SwdCf => {
+                serde_json::to_value(Swd5PersistentState::new(app_name))
+            }
+        };
+        value.expect("fresh persistent state serializes")
+    }
+
+    /// Deserialize `value` into this game's state type, discarding the
+    /// result. Used to catch type errors the field check can't see.
+    pub fn check(&self, value: &Value) -> anyhow::Result<()> {
+        match self {
+            SaveKind::Pal3 => {
+                serde_json::from_value::<PersistentState>(value.clone())?;
+            }
+            SaveKind::Pal4 => {
+                serde_json::from_value::<Pal4PersistentState>(value.clone())?;
+            }
+            SaveKind::Pal5 | SaveKind::Pal5Q => {
+                serde_json::from_value::<Pal5PersistentState>(value.clone())?;
+            }
+            SaveKind::Swd5 | SaveKind::SwdHc | SaveKind::SwdCf => {
+                serde_json::from_value::<Swd5PersistentState>(value.clone())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Files making up one save slot.
+pub struct SlotFiles {
+    pub state: PathBuf,
+    pub metadata: PathBuf,
+    pub thumbnail: PathBuf,
+}
+
+impl SlotFiles {
+    pub fn new(save_dir: &Path, slot: i32) -> Self {
+        Self {
+            state: save_dir.join(format!("{}.json", slot)),
+            metadata: save_dir.join(format!("{}.meta.json", slot)),
+            thumbnail: save_dir.join(format!("{}.png", slot)),
+        }
+    }
+}
+
+/// `<root>/<app_name>/Save`. `root` is normally [`ydirs::save_dir`].
+pub fn slot_dir(root: &Path, app_name: &str) -> PathBuf {
+    root.join(app_name).join("Save")
+}
+
+fn default_slot_dir(app_name: &str) -> PathBuf {
+    slot_dir(&ydirs::save_dir(), app_name)
+}
+
+/// Slot numbers with a state file in `save_dir`, ascending.
+pub fn list_slots(save_dir: &Path) -> Vec<i32> {
+    let mut slots: Vec<i32> = std::fs::read_dir(save_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| {
+                    let name = e.file_name();
+                    let name = name.to_str()?;
+                    name.strip_suffix(".json")?.parse::<i32>().ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    slots.sort();
+    slots
+}
+
+/// Read a state file and migrate it to the current schema. The file
+/// itself is left untouched; it is rewritten on the next save.
+pub fn read_state_value(kind: SaveKind, path: &Path) -> anyhow::Result<Value> {
+    let content = std::fs::read_to_string(path)?;
+    let mut value: Value = serde_json::from_str(&content)?;
+    let from = schema::migrate(kind, &mut value)?;
+    if from != kind.schema().version() {
+        log::info!(
+            "migrated {} from save version {} to {}",
+            path.display(),
+            from,
+            kind.schema().version()
+        );
+    }
+    Ok(value)
+}
+
+/// Load and migrate slot `slot` of `app_name`.
+pub fn load_state<T: DeserializeOwned>(
+    kind: SaveKind,
+    app_name: &str,
+    slot: i32,
+) -> anyhow::Result<T> {
+    let files = SlotFiles::new(&default_slot_dir(app_name), slot);
+    let value = read_state_value(kind, &files.state)?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Serialize `state` stamped with the current schema version.
+pub fn to_versioned_value<T: Serialize>(kind: SaveKind, state: &T) -> anyhow::Result<Value> {
+    let mut value = serde_json::to_value(state)?;
+    match value.as_object_mut() {
+        Some(map) => {
+            map.insert(
+                schema::VERSION_KEY.to_string(),
+                Value::from(kind.schema().version()),
+            );
+        }
+        None => anyhow::bail!("persistent state did not serialize to a JSON object"),
+    }
+    Ok(value)
+}
+
+/// Write `value` to `path` through a temporary file so a crash mid-save
+/// never leaves a truncated slot behind.
+pub fn write_json_atomic(path: &Path, value: &impl Serialize) -> anyhow::Result<()> {
+    let content = serde_json::to_string_pretty(value)?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, content)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Persist `state` to slot `slot` of `app_name` along with its metadata
+/// sidecar and, if a frame source is installed, a thumbnail.
+pub fn write_slot<T: Serialize>(
+    kind: SaveKind,
+    app_name: &str,
+    slot: i32,
+    state: &T,
+    metadata: SaveMetadata,
+) -> anyhow::Result<()> {
+    write_slot_in(kind, &default_slot_dir(app_name), slot, state, metadata)
+}
+
+/// [`write_slot`] into an explicit slot directory (see [`slot_dir`]).
+pub fn write_slot_in<T: Serialize>(
+    kind: SaveKind,
+    dir: &Path,
+    slot: i32,
+    state: &T,
+    mut metadata: SaveMetadata,
+) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let files = SlotFiles::new(dir, slot);
+
+    write_json_atomic(&files.state, &to_versioned_value(kind, state)?)?;
+
+    metadata.save_version = kind.schema().version();
+    metadata.thumbnail = match metadata::capture_thumbnail(&files.thumbnail) {
+        Ok(true) => files
+            .thumbnail
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned()),
+        Ok(false) => None,
+        Err(e) => {
+            log::warn!("Cannot write save thumbnail: {:#}", e);
+            None
+        }
+    };
+    if let Err(e) = write_json_atomic(&files.metadata, &metadata) {
+        log::warn!("Cannot write save metadata: {:#}", e);
+    }
+
+    Ok(())
+}
+
+/// Metadata sidecar of a slot, if present and readable.
+pub fn read_metadata(save_dir: &Path, slot: i32) -> Option<SaveMetadata> {
+    let content = std::fs::read_to_string(SlotFiles::new(save_dir, slot).metadata).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+thread_local! {
+    static THUMBNAIL_SOURCE: RefCell<Option<Rc<RefCell<dyn RenderingEngine>>>> =
+        const { RefCell::new(None) };
+}
+
+/// Set the rendering engine whose last presented frame becomes the
+/// thumbnail of subsequent saves. Game services install it when they
+/// build their director; `None` disables thumbnails.
+pub fn install_thumbnail_source(engine: Option<Rc<RefCell<dyn RenderingEngine>>>) {
+    THUMBNAIL_SOURCE.with(|source| *source.borrow_mut() = engine);
+}
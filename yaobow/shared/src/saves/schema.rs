@@ -0,0 +1,242 @@
+//! Save schema versions and the migrations between them.
+//!
+//! A schema lists the top-level fields of a game's state file and the
+//! ordered migrations that produced it: `migrations[n]` upgrades a
+//! version `n` file to version `n + 1`, so the current version is the
+//! number of migrations. To change a state layout, add the field to the
+//! struct and to the schema, then append a migration that rewrites older
+//! files (the field list is checked against the struct in tests).
+
+use serde_json::{Map, Value};
+
+use super::SaveKind;
+
+/// Top-level key holding the schema version. Absent means version 0.
+pub const VERSION_KEY: &str = "save_version";
+
+pub struct FieldSpec {
+    pub name: &'static str,
+
+    /// Deserialization fails without it (no serde default).
+    pub required: bool,
+}
+
+type Migration = fn(&mut Map<String, Value>) -> anyhow::Result<()>;
+
+pub struct SaveSchema {
+    pub fields: &'static [FieldSpec],
+    migrations: &'static [Migration],
+}
+
+impl SaveSchema {
+    pub fn version(&self) -> u32 {
+        self.migrations.len() as u32
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldSpec> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+const fn required(name: &'static str) -> FieldSpec {
+    FieldSpec {
+        name,
+        required: true,
+    }
+}
+
+const fn optional(name: &'static str) -> FieldSpec {
+    FieldSpec {
+        name,
+        required: false,
+    }
+}
+
+pub static PAL3_SCHEMA: SaveSchema = SaveSchema {
+    fields: &[
+        required("app_name"),
+        required("global_vars"),
+        required("position"),
+        optional("scene"),
+        optional("sub_scene"),
+        optional("playtime"),
+    ],
+    migrations: &[v0_add_playtime],
+};
+
+pub static PAL4_SCHEMA: SaveSchema = SaveSchema {
+    fields: &[
+        required("app_name"),
+        optional("money"),
+        optional("quest_percentage"),
+        optional("leader"),
+        optional("scene_name"),
+        optional("block_name"),
+        optional("position"),
+        optional("direction"),
+        optional("player_locked"),
+        optional("camera"),
+        optional("players"),
+        optional("inventory"),
+        optional("script_globals"),
+        optional("playtime"),
+    ],
+    migrations: &[v0_add_playtime],
+};
+
+/// Shared by PAL5 and PAL5Q.
+pub static PAL5_SCHEMA: SaveSchema = SaveSchema {
+    fields: &[
+        required("app_name"),
+        optional("money"),
+        optional("scene_name"),
+        optional("position"),
+        optional("players"),
+        optional("inventory"),
+        optional("formulas"),
+        optional("quests"),
+        optional("flags"),
+        optional("playtime"),
+    ],
+    migrations: &[v0_pal5_drop_unset_role_stats],
+};
+
+/// Shared by SWD5, SWDHC and SWDCF. Versioned from the start, so there
+/// is nothing to migrate yet.
+pub static SWD5_SCHEMA: SaveSchema = SaveSchema {
+    fields: &[
+        required("app_name"),
+        optional("map_id"),
+        optional("position"),
+        optional("flags"),
+        optional("playtime"),
+    ],
+    migrations: &[],
+};
+
+/// Version 1 introduced versioning itself plus accumulated playtime.
+/// Unversioned saves start the clock at zero.
+fn v0_add_playtime(state: &mut Map<String, Value>) -> anyhow::Result<()> {
+    state.entry("playtime").or_insert_with(|| Value::from(0.0));
+    Ok(())
+}
+
+fn v0_pal5_drop_unset_role_stats(state: &mut Map<String, Value>) -> anyhow::Result<()> {
+    let Some(players) = state.get_mut("players").and_then(Value::as_object_mut) else {
+        return Ok(());
+    };
+    for player in players.values_mut().filter_map(Value::as_object_mut) {
+        for stat in ["level", "max_hp", "max_mp"] {
+            if player.get(stat).and_then(Value::as_i64) == Some(0) {
+                player.remove(stat);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Schema version recorded in `value`.
+pub fn version_of(value: &Value) -> anyhow::Result<u32> {
+    match value.get(VERSION_KEY) {
+        None => Ok(0),
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| anyhow::anyhow!("{} is not a valid version: {}", VERSION_KEY, v)),
+    }
+}
+
+/// Bring `value` up to the current schema of `kind` in place and stamp
+/// the new version. Returns the version the file was written with.
+/// Fails on files from a newer build rather than guessing.
+pub fn migrate(kind: SaveKind, value: &mut Value) -> anyhow::Result<u32> {
+    let schema = kind.schema();
+    let from = version_of(value)?;
+    if from > schema.version() {
+        anyhow::bail!(
+            "save version {} is newer than this build supports ({})",
+            from,
+            schema.version()
+        );
+    }
+
+    let state = value
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("save file is not a JSON object"))?;
+    for (version, migration) in schema.migrations.iter().enumerate().skip(from as usize) {
+        migration(state).map_err(|e| {
+            e.context(format!(
+                "migrating {} save from version {}",
+                kind.key(),
+                version
+            ))
+        })?;
+    }
+
+    state.insert(VERSION_KEY.to_string(), Value::from(schema.version()));
+    Ok(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_fields_match_serialized_state() {
+        for kind in SaveKind::ALL {
+            let state = kind.default_state();
+            let mut keys: Vec<&str> = state
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(|k| k.as_str())
+                .collect();
+            let mut fields: Vec<&str> = kind.schema().fields.iter().map(|f| f.name).collect();
+            keys.sort();
+            fields.sort();
+            assert_eq!(keys, fields, "{} schema is out of date", kind.key());
+        }
+    }
+
+    #[test]
+    fn legacy_saves_migrate_to_current_version() {
+        let mut value = serde_json::json!({
+            "app_name": "OpenPAL4",
+            "scene_name": "m01",
+            "block_name": "1"
+        });
+        assert_eq!(migrate(SaveKind::Pal4, &mut value).unwrap(), 0);
+        assert_eq!(
+            version_of(&value).unwrap(),
+            SaveKind::Pal4.schema().version()
+        );
+        assert_eq!(value["playtime"], 0.0);
+
+        // Already-current files pass through unchanged.
+        let before = value.clone();
+        assert_eq!(
+            migrate(SaveKind::Pal4, &mut value).unwrap(),
+            SaveKind::Pal4.schema().version()
+        );
+        assert_eq!(value, before);
+    }
+
+    #[test]
+    fn pal5_saves_drop_zero_role_stats() {
+        let mut value = serde_json::json!({
+            "app_name": "OpenPAL5",
+            "players": { "1": { "level": 0, "hp": 90, "max_hp": 0, "max_mp": 120 } }
+        });
+        assert_eq!(migrate(SaveKind::Pal5, &mut value).unwrap(), 0);
+        assert_eq!(
+            value["players"]["1"],
+            serde_json::json!({ "hp": 90, "max_mp": 120 })
+        );
+    }
+
+    #[test]
+    fn newer_saves_are_rejected() {
+        let mut value = serde_json::json!({ "app_name": "OpenPAL3", "save_version": 999 });
+        assert!(migrate(SaveKind::Pal3, &mut value).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Field-level checks and repair of save files against their schema.
+
+use serde_json::{Map, Value};
+
+use super::SaveKind;
+use super::schema::{self, VERSION_KEY};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ValidationReport {
+    /// Version the file was written with, if it could be read.
+    pub version: Option<u32>,
+
+    /// Top-level keys the current schema does not know. Ignored on
+    /// load, dropped by [`repair`].
+    pub unknown_fields: Vec<String>,
+
+    /// Schema fields absent after migration. Optional ones load with
+    /// their defaults; required ones make the file unloadable.
+    pub missing_fields: Vec<String>,
+
+    /// Problems that prevent loading (bad version, required field
+    /// missing, wrong value types).
+    pub errors: Vec<String>,
+}
+
+impl ValidationReport {
+    /// Whether the game can load the file as is.
+    pub fn is_loadable(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Loadable and no unknown or missing fields.
+    pub fn is_clean(&self) -> bool {
+        self.is_loadable() && self.unknown_fields.is_empty() && self.missing_fields.is_empty()
+    }
+}
+
+/// Check `value` (a raw state file, any version) against the current
+/// schema of `kind`.
+pub fn validate(kind: SaveKind, value: &Value) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let mut value = value.clone();
+    match schema::migrate(kind, &mut value) {
+        Ok(version) => report.version = Some(version),
+        Err(e) => {
+            report.errors.push(format!("{:#}", e));
+            return report;
+        }
+    }
+
+    let state = value.as_object().unwrap();
+    let schema = kind.schema();
+    for key in state.keys() {
+        if key != VERSION_KEY && schema.field(key).is_none() {
+            report.unknown_fields.push(key.clone());
+        }
+    }
+
+    for field in schema.fields {
+        if !state.contains_key(field.name) {
+            report.missing_fields.push(field.name.to_string());
+            if field.required {
+                report
+                    .errors
+                    .push(format!("required field `{}` is missing", field.name));
+            }
+        }
+    }
+
+    if report.errors.is_empty() {
+        if let Err(e) = kind.check(&value) {
+            report.errors.push(e.to_string());
+        }
+    }
+
+    report
+}
+
+/// Rewrite `value` into a loadable current-version file: migrate, drop
+/// unknown fields, fill missing ones from a fresh state, and reset any
+/// field whose value fails to deserialize. Returns the repaired file and
+/// a description of each change.
+pub fn repair(kind: SaveKind, value: &Value) -> anyhow::Result<(Value, Vec<String>)> {
+    let mut value = value.clone();
+    let mut actions = vec![];
+
+    if !value.is_object() {
+        anyhow::bail!("save file is not a JSON object");
+    }
+
+    if schema::version_of(&value).is_err() {
+        value.as_object_mut().unwrap().remove(VERSION_KEY);
+        actions.push(format!("treated unreadable `{}` as 0", VERSION_KEY));
+    }
+
+    let from = schema::migrate(kind, &mut value)?;
+    let schema = kind.schema();
+    if from != schema.version() {
+        actions.push(format!(
+            "migrated from version {} to {}",
+            from,
+            schema.version()
+        ));
+    }
+
+    let defaults = kind.default_state();
+    let defaults = defaults.as_object().unwrap();
+    let state = value.as_object_mut().unwrap();
+
+    let unknown: Vec<String> = state
+        .keys()
+        .filter(|k| *k != VERSION_KEY && schema.field(k).is_none())
+        .cloned()
+        .collect();
+    for key in unknown {
+        state.remove(&key);
+        actions.push(format!("dropped unknown field `{}`", key));
+    }
+
+    for field in schema.fields {
+        if !state.contains_key(field.name) {
+            state.insert(field.name.to_string(), default_field(defaults, field.name));
+            actions.push(format!("added missing field `{}`", field.name));
+        }
+    }
+
+    // Serde only reports the first type error, so probe each field on
+    // its own against a fresh state and reset the ones that fail.
+    if kind.check(&value).is_err() {
+        let state = value.as_object_mut().unwrap();
+        for field in schema.fields {
+            let mut probe = defaults.clone();
+            probe.insert(field.name.to_string(), state[field.name].clone());
+            if kind.check(&Value::Object(probe)).is_err() {
+                state.insert(field.name.to_string(), default_field(defaults, field.name));
+                actions.push(format!("reset malformed field `{}`", field.name));
+            }
+        }
+    }
+
+    kind.check(&value)?;
+    Ok((value, actions))
+}
+
+fn default_field(defaults: &Map<String, Value>, name: &str) -> Value {
+    defaults.get(name).cloned().unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn reports_unknown_and_missing_fields() {
+        let value = json!({
+            "app_name": "OpenPAL3",
+            "global_vars": {},
+            "scene": "q01",
+            "sub_scene": "q01a",
+            "hp": 100
+        });
+
+        let report = validate(SaveKind::Pal3, &value);
+        assert_eq!(report.version, Some(0));
+        assert_eq!(report.unknown_fields, vec!["hp"]);
+        assert_eq!(report.missing_fields, vec!["position"]);
+        assert!(!report.is_loadable());
+    }
+
+    #[test]
+    fn repair_makes_broken_saves_loadable() {
+        let value = json!({
+            "app_name": "OpenPAL4",
+            "money": "lots",
+            "scene_name": "m05",
+            "players": { "0": { "level": 12 } },
+            "legacy_flag": true
+        });
+        assert!(!validate(SaveKind::Pal4, &value).is_loadable());
+
+        let (repaired, actions) = repair(SaveKind::Pal4, &value).unwrap();
+        let report = validate(SaveKind::Pal4, &repaired);
+        assert!(report.is_clean(), "{:?}", report);
+        assert_eq!(repaired["money"], 0);
+        assert_eq!(repaired["scene_name"], "m05");
+        assert_eq!(repaired["players"]["0"]["level"], 12);
+        assert!(actions.iter().any(|a| a.contains("legacy_flag")));
+        assert!(
+            actions
+                .iter()
+                .any(|a| a.contains("reset malformed field `money`"))
+        );
+    }
+}
@@ -0,0 +1,737 @@
+//! Side-effect-free expression evaluator backing the agent server's
+//! `/v1/script/eval`.
+//!
+//! The language is a deliberately tiny, assignment-free subset of
+//! AngelScript expression syntax:
+//!
+//! ```text
+//! expr    := or
+//! or      := and ("||" and)*
+//! and     := cmp ("&&" cmp)*
+//! cmp     := sum (("==" | "!=" | "<" | "<=" | ">" | ">=") sum)*
+//! sum     := product (("+" | "-") product)*
+//! product := unary (("*" | "/" | "%") unary)*
+//! unary   := ("!" | "-") unary | postfix
+//! postfix := primary ("." (ident | int) | "[" expr "]")*
+//! primary := int | float | string | "true" | "false" | "null"
+//!          | ident "(" (expr ("," expr)*)? ")"
+//!          | ident
+//!          | "(" expr ")"
+//! ```
+//!
+//! The evaluator knows nothing about any particular game: identifier
+//! paths (`g[12]`, `state.money`) and calls (`giGetMoney()`) are
+//! resolved through an [`EvalHost`]. Hosts that forward calls to real
+//! sysfns go through [`ScriptVm::call_sysfn_sandboxed`], which
+//! rejects anything that writes a global or suspends.
+//!
+//! [`ScriptVm::call_sysfn_sandboxed`]: super::ScriptVm::call_sysfn_sandboxed
+
+use std::fmt;
+
+/// A value produced by an [`Expr`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl EvalValue {
+    /// AngelScript truthiness: zero, `false`, `null` and `""` are
+    /// false, everything else is true.
+    pub fn truthy(&self) -> bool {
+        match self {
+            EvalValue::Null => false,
+            EvalValue::Bool(b) => *b,
+            EvalValue::Int(i) => *i != 0,
+            EvalValue::Float(f) => *f != 0.0,
+            EvalValue::Str(s) => !s.is_empty(),
+        }
+    }
+
+    fn as_number(&self) -> Option<Number> {
+        match self {
+            EvalValue::Bool(b) => Some(Number::Int(*b as i64)),
+            EvalValue::Int(i) => Some(Number::Int(*i)),
+            EvalValue::Float(f) => Some(Number::Float(*f)),
+            EvalValue::Null | EvalValue::Str(_) => None,
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            EvalValue::Null => "null",
+            EvalValue::Bool(_) => "bool",
+            EvalValue::Int(_) => "int",
+            EvalValue::Float(_) => "float",
+            EvalValue::Str(_) => "string",
+        }
+    }
+}
+
+impl fmt::Display for EvalValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalValue::Null => write!(f, "null"),
+            EvalValue::Bool(b) => write!(f, "{}", b),
+            EvalValue::Int(i) => write!(f, "{}", i),
+            EvalValue::Float(v) => write!(f, "{}", v),
+            EvalValue::Str(s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+/// One step of an identifier path: `.name` / `["name"]` or `[3]`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalKey {
+    Name(String),
+    Index(i64),
+}
+
+/// Why an evaluation failed. Hosts map [`EvalError::Rejected`] to a
+/// conflict and everything else to a bad request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// The source text doesn't parse.
+    Parse(String),
+    /// An identifier, path or function the host doesn't know.
+    Unknown(String),
+    /// Operand types don't fit the operator (or division by zero).
+    Type(String),
+    /// The expression tried to write state or suspend.
+    Rejected(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::Parse(msg) => write!(f, "parse error: {}", msg),
+            EvalError::Unknown(msg) => write!(f, "unknown: {}", msg),
+            EvalError::Type(msg) => write!(f, "type error: {}", msg),
+            EvalError::Rejected(msg) => write!(f, "rejected: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// Game-side name resolution for [`eval`].
+pub trait EvalHost {
+    /// Resolve `root` followed by `keys`, e.g. `g[12]` is
+    /// `("g", [Index(12)])`.
+    fn lookup(&mut self, root: &str, keys: &[EvalKey]) -> Result<EvalValue, EvalError>;
+
+    /// Call the function `name` with already-evaluated arguments.
+    fn call(&mut self, name: &str, args: &[EvalValue]) -> Result<EvalValue, EvalError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOp {
+    Not,
+    Neg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathStep {
+    Field(String),
+    Index(Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(EvalValue),
+    Path {
+        root: String,
+        steps: Vec<PathStep>,
+    },
+    Call {
+        name: String,
+        args: Vec<Expr>,
+    },
+    Unary {
+        op: UnaryOp,
+        expr: Box<Expr>,
+    },
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+}
+
+impl Expr {
+    pub fn parse(source: &str) -> Result<Expr, EvalError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(EvalError::Parse(format!("unexpected {}", token))),
+        }
+    }
+}
+
+/// Evaluate `expr` against `host`. `&&` and `||` short-circuit, so a
+/// guarded call (`g[3] == 1 && giGetMoney() > 100`) only reaches the
+/// host when the guard holds.
+pub fn eval(expr: &Expr, host: &mut dyn EvalHost) -> Result<EvalValue, EvalError> {
+    match expr {
+        Expr::Literal(value) => Ok(value.clone()),
+        Expr::Path { root, steps } => {
+            let mut keys = Vec::with_capacity(steps.len());
+            for step in steps {
+                keys.push(match step {
+                    PathStep::Field(name) => EvalKey::Name(name.clone()),
+                    PathStep::Index(index) => match eval(index, host)? {
+                        EvalValue::Int(i) => EvalKey::Index(i),
+                        EvalValue::Str(s) => EvalKey::Name(s),
+                        other => {
+                            return Err(EvalError::Type(format!(
+                                "cannot index {} with {}",
+                                root,
+                                other.type_name()
+                            )));
+                        }
+                    },
+                });
+            }
+            host.lookup(root, &keys)
+        }
+        Expr::Call { name, args } => {
+            let args = args
+                .iter()
+                .map(|arg| eval(arg, host))
+                .collect::<Result<Vec<_>, _>>()?;
+            host.call(name, &args)
+        }
+        Expr::Unary { op, expr } => {
+            let value = eval(expr, host)?;
+            match op {
+                UnaryOp::Not => Ok(EvalValue::Bool(!value.truthy())),
+                UnaryOp::Neg => match value.as_number() {
+                    Some(Number::Int(i)) => Ok(EvalValue::Int(i.wrapping_neg())),
+                    Some(Number::Float(f)) => Ok(EvalValue::Float(-f)),
+                    None => Err(EvalError::Type(format!(
+                        "cannot negate {}",
+                        value.type_name()
+                    ))),
+                },
+            }
+        }
+        Expr::Binary { op, lhs, rhs } => match op {
+            BinaryOp::Or => {
+                if eval(lhs, host)?.truthy() {
+                    return Ok(EvalValue::Bool(true));
+                }
+                Ok(EvalValue::Bool(eval(rhs, host)?.truthy()))
+            }
+            BinaryOp::And => {
+                if !eval(lhs, host)?.truthy() {
+                    return Ok(EvalValue::Bool(false));
+                }
+                Ok(EvalValue::Bool(eval(rhs, host)?.truthy()))
+            }
+            _ => {
+                let lhs = eval(lhs, host)?;
+                let rhs = eval(rhs, host)?;
+                binary(*op, &lhs, &rhs)
+            }
+        },
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Int(i) => i as f64,
+            Number::Float(f) => f,
+        }
+    }
+}
+
+fn binary(op: BinaryOp, lhs: &EvalValue, rhs: &EvalValue) -> Result<EvalValue, EvalError> {
+    let mismatch = || {
+        EvalError::Type(format!(
+            "{:?} is not defined for {} and {}",
+            op,
+            lhs.type_name(),
+            rhs.type_name()
+        ))
+    };
+
+    match op {
+        BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+            let ordering = match (lhs, rhs) {
+                (EvalValue::Str(a), EvalValue::Str(b)) => a.partial_cmp(b),
+                (EvalValue::Null, EvalValue::Null) => Some(std::cmp::Ordering::Equal),
+                (EvalValue::Null, _) | (_, EvalValue::Null) => None,
+                _ => {
+                    let (Some(a), Some(b)) = (lhs.as_number(), rhs.as_number()) else {
+                        return Err(mismatch());
+                    };
+                    match (a, b) {
+                        (Number::Int(a), Number::Int(b)) => Some(a.cmp(&b)),
+                        (a, b) => a.as_f64().partial_cmp(&b.as_f64()),
+                    }
+                }
+            };
+            let result = match op {
+                BinaryOp::Eq => ordering == Some(std::cmp::Ordering::Equal),
+                BinaryOp::Ne => ordering != Some(std::cmp::Ordering::Equal),
+                BinaryOp::Lt => ordering == Some(std::cmp::Ordering::Less),
+                BinaryOp::Le => matches!(
+                    ordering,
+                    Some(std::cmp::Ordering::Less | std::cmp::Ordering::Equal)
+                ),
+                BinaryOp::Gt => ordering == Some(std::cmp::Ordering::Greater),
+                _ => matches!(
+                    ordering,
+                    Some(std::cmp::Ordering::Greater | std::cmp::Ordering::Equal)
+                ),
+            };
+            Ok(EvalValue::Bool(result))
+        }
+        _ => {
+            let (Some(a), Some(b)) = (lhs.as_number(), rhs.as_number()) else {
+                return Err(mismatch());
+            };
+            match (a, b) {
+                (Number::Int(a), Number::Int(b)) => {
+                    let value = match op {
+                        BinaryOp::Add => a.wrapping_add(b),
+                        BinaryOp::Sub => a.wrapping_sub(b),
+                        BinaryOp::Mul => a.wrapping_mul(b),
+                        BinaryOp::Div | BinaryOp::Rem if b == 0 => {
+                            return Err(EvalError::Type("division by zero".to_string()));
+                        }
+                        BinaryOp::Div => a.wrapping_div(b),
+                        _ => a.wrapping_rem(b),
+                    };
+                    Ok(EvalValue::Int(value))
+                }
+                (a, b) => {
+                    let (a, b) = (a.as_f64(), b.as_f64());
+                    let value = match op {
+                        BinaryOp::Add => a + b,
+                        BinaryOp::Sub => a - b,
+                        BinaryOp::Mul => a * b,
+                        BinaryOp::Div => a / b,
+                        _ => a % b,
+                    };
+                    Ok(EvalValue::Float(value))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Int(i) => write!(f, "`{}`", i),
+            Token::Float(v) => write!(f, "`{}`", v),
+            Token::Str(s) => write!(f, "{:?}", s),
+            Token::Ident(s) => write!(f, "`{}`", s),
+            Token::Op(op) => write!(f, "`{}`", op),
+        }
+    }
+}
+
+/// Longest operators first so `<=` isn't read as `<` `=`.
+const OPERATORS: &[&str] = &[
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-", "*", "/", "%", "(", ")", "[", "]",
+    ".", ",",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, EvalError> {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            if c == '0' && matches!(chars.get(i + 1), Some('x' | 'X')) {
+                i += 2;
+                while i < chars.len() && chars[i].is_ascii_hexdigit() {
+                    i += 1;
+                }
+                let digits = chars[start + 2..i].iter().collect::<String>();
+                let value = i64::from_str_radix(&digits, 16)
+                    .map_err(|e| EvalError::Parse(format!("bad hex literal: {}", e)))?;
+                tokens.push(Token::Int(value));
+                continue;
+            }
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            // Only a `.` followed by a digit makes a float, so
+            // `state.players.0.level` keeps its path separators.
+            let is_float =
+                chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit());
+            if is_float {
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let text = chars[start..i].iter().collect::<String>();
+            if is_float {
+                tokens.push(Token::Float(text.parse().map_err(|e| {
+                    EvalError::Parse(format!("bad float literal {}: {}", text, e))
+                })?));
+            } else {
+                tokens.push(Token::Int(text.parse().map_err(|e| {
+                    EvalError::Parse(format!("bad int literal {}: {}", text, e))
+                })?));
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if c == '"' {
+            i += 1;
+            let mut s = String::new();
+            loop {
+                match chars.get(i) {
+                    None => return Err(EvalError::Parse("unterminated string".to_string())),
+                    Some('"') => break,
+                    Some('\\') => {
+                        match chars.get(i + 1) {
+                            Some('n') => s.push('\n'),
+                            Some(&escaped @ ('"' | '\\')) => s.push(escaped),
+                            other => {
+                                return Err(EvalError::Parse(format!(
+                                    "bad escape \\{}",
+                                    other.copied().unwrap_or(' ')
+                                )));
+                            }
+                        }
+                        i += 2;
+                    }
+                    Some(&c) => {
+                        s.push(c);
+                        i += 1;
+                    }
+                }
+            }
+            i += 1;
+            tokens.push(Token::Str(s));
+        } else {
+            let rest = chars[i..].iter().take(2).collect::<String>();
+            let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) else {
+                return Err(EvalError::Parse(format!("unexpected character {:?}", c)));
+            };
+            i += op.len();
+            tokens.push(Token::Op(op));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, EvalError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| EvalError::Parse("unexpected end of expression".to_string()))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, op: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Op(o)) if *o == op) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, op: &str) -> Result<(), EvalError> {
+        if self.eat(op) {
+            return Ok(());
+        }
+        match self.peek() {
+            Some(token) => Err(EvalError::Parse(format!(
+                "expected `{}`, found {}",
+                op, token
+            ))),
+            None => Err(EvalError::Parse(format!("expected `{}`", op))),
+        }
+    }
+
+    fn binary_level(
+        &mut self,
+        ops: &[(&str, BinaryOp)],
+        next: fn(&mut Self) -> Result<Expr, EvalError>,
+    ) -> Result<Expr, EvalError> {
+        let mut lhs = next(self)?;
+        'outer: loop {
+            for (text, op) in ops {
+                if self.eat(text) {
+                    let rhs = next(self)?;
+                    lhs = Expr::Binary {
+                        op: *op,
+                        lhs: Box::new(lhs),
+                        rhs: Box::new(rhs),
+                    };
+                    continue 'outer;
+                }
+            }
+            return Ok(lhs);
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, EvalError> {
+        self.binary_level(&[("||", BinaryOp::Or)], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Expr, EvalError> {
+        self.binary_level(&[("&&", BinaryOp::And)], Self::cmp)
+    }
+
+    fn cmp(&mut self) -> Result<Expr, EvalError> {
+        self.binary_level(
+            &[
+                ("==", BinaryOp::Eq),
+                ("!=", BinaryOp::Ne),
+                ("<=", BinaryOp::Le),
+                (">=", BinaryOp::Ge),
+                ("<", BinaryOp::Lt),
+                (">", BinaryOp::Gt),
+            ],
+            Self::sum,
+        )
+    }
+
+    fn sum(&mut self) -> Result<Expr, EvalError> {
+        self.binary_level(&[("+", BinaryOp::Add), ("-", BinaryOp::Sub)], Self::product)
+    }
+
+    fn product(&mut self) -> Result<Expr, EvalError> {
+        self.binary_level(
+            &[
+                ("*", BinaryOp::Mul),
+                ("/", BinaryOp::Div),
+                ("%", BinaryOp::Rem),
+            ],
+            Self::unary,
+        )
+    }
+
+    fn unary(&mut self) -> Result<Expr, EvalError> {
+        let op = if self.eat("!") {
+            UnaryOp::Not
+        } else if self.eat("-") {
+            UnaryOp::Neg
+        } else {
+            return self.postfix();
+        };
+        let expr = self.unary()?;
+        Ok(Expr::Unary {
+            op,
+            expr: Box::new(expr),
+        })
+    }
+
+    fn postfix(&mut self) -> Result<Expr, EvalError> {
+        let primary = self.primary()?;
+        let Expr::Path { root, mut steps } = primary else {
+            return Ok(primary);
+        };
+        loop {
+            if self.eat(".") {
+                match self.next()? {
+                    Token::Ident(name) => steps.push(PathStep::Field(name)),
+                    Token::Int(i) => steps.push(PathStep::Index(Expr::Literal(EvalValue::Int(i)))),
+                    token => {
+                        return Err(EvalError::Parse(format!(
+                            "expected a field name after `.`, found {}",
+                            token
+                        )));
+                    }
+                }
+            } else if self.eat("[") {
+                let index = self.or()?;
+                self.expect("]")?;
+                steps.push(PathStep::Index(index));
+            } else {
+                return Ok(Expr::Path { root, steps });
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, EvalError> {
+        match self.next()? {
+            Token::Int(i) => Ok(Expr::Literal(EvalValue::Int(i))),
+            Token::Float(f) => Ok(Expr::Literal(EvalValue::Float(f))),
+            Token::Str(s) => Ok(Expr::Literal(EvalValue::Str(s))),
+            Token::Ident(name) => match name.as_str() {
+                "true" => Ok(Expr::Literal(EvalValue::Bool(true))),
+                "false" => Ok(Expr::Literal(EvalValue::Bool(false))),
+                "null" => Ok(Expr::Literal(EvalValue::Null)),
+                _ if self.eat("(") => {
+                    let mut args = vec![];
+                    if !self.eat(")") {
+                        loop {
+                            args.push(self.or()?);
+                            if self.eat(")") {
+                                break;
+                            }
+                            self.expect(",")?;
+                        }
+                    }
+                    Ok(Expr::Call { name, args })
+                }
+                _ => Ok(Expr::Path {
+                    root: name,
+                    steps: vec![],
+                }),
+            },
+            Token::Op("(") => {
+                let expr = self.or()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            token => Err(EvalError::Parse(format!("unexpected {}", token))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EvalError, EvalHost, EvalKey, EvalValue, Expr, eval};
+
+    /// `g[i]` reads `globals`, `state.money` is 500, and every call is
+    /// logged so tests can check short-circuiting.
+    struct MockHost {
+        globals: Vec<i64>,
+        calls: Vec<String>,
+    }
+
+    impl EvalHost for MockHost {
+        fn lookup(&mut self, root: &str, keys: &[EvalKey]) -> Result<EvalValue, EvalError> {
+            match (root, keys) {
+                ("g", [EvalKey::Index(i)]) => self
+                    .globals
+                    .get(*i as usize)
+                    .map(|v| EvalValue::Int(*v))
+                    .ok_or_else(|| EvalError::Unknown(format!("g[{}]", i))),
+                ("state", [EvalKey::Name(field)]) if field == "money" => Ok(EvalValue::Int(500)),
+                _ => Err(EvalError::Unknown(root.to_string())),
+            }
+        }
+
+        fn call(&mut self, name: &str, args: &[EvalValue]) -> Result<EvalValue, EvalError> {
+            self.calls.push(name.to_string());
+            match name {
+                "giCheckEquipInInventory" => Ok(EvalValue::Bool(args == [EvalValue::Int(1203)])),
+                "giAddMoney" => Err(EvalError::Rejected(name.to_string())),
+                _ => Err(EvalError::Unknown(name.to_string())),
+            }
+        }
+    }
+
+    fn run(source: &str) -> Result<EvalValue, EvalError> {
+        let mut host = MockHost {
+            globals: vec![0, 7, 3],
+            calls: vec![],
+        };
+        eval(&Expr::parse(source)?, &mut host)
+    }
+
+    #[test]
+    fn precedence_and_arithmetic() {
+        assert_eq!(run("1 + 2 * 3"), Ok(EvalValue::Int(7)));
+        assert_eq!(run("(1 + 2) * 3"), Ok(EvalValue::Int(9)));
+        assert_eq!(run("-7 % 3"), Ok(EvalValue::Int(-1)));
+        assert_eq!(run("0x10 / 4.0"), Ok(EvalValue::Float(4.0)));
+        assert_eq!(run("!0 && 2 > 1"), Ok(EvalValue::Bool(true)));
+        assert_eq!(
+            run("1 / 0"),
+            Err(EvalError::Type("division by zero".to_string()))
+        );
+    }
+
+    #[test]
+    fn paths_and_calls_resolve_through_the_host() {
+        assert_eq!(run("g[1] == 7 && g[1 + 1] >= 3"), Ok(EvalValue::Bool(true)));
+        assert_eq!(run("state.money - 100"), Ok(EvalValue::Int(400)));
+        assert_eq!(
+            run("giCheckEquipInInventory(1203)"),
+            Ok(EvalValue::Bool(true))
+        );
+        assert!(matches!(run("g[9]"), Err(EvalError::Unknown(_))));
+        assert_eq!(run("\"Q01\" == \"Q01\""), Ok(EvalValue::Bool(true)));
+    }
+
+    #[test]
+    fn logical_operators_short_circuit() {
+        let mut host = MockHost {
+            globals: vec![0],
+            calls: vec![],
+        };
+        let expr = Expr::parse("g[0] == 1 && giAddMoney(5)").unwrap();
+        assert_eq!(eval(&expr, &mut host), Ok(EvalValue::Bool(false)));
+        let expr = Expr::parse("g[0] == 0 || giAddMoney(5)").unwrap();
+        assert_eq!(eval(&expr, &mut host), Ok(EvalValue::Bool(true)));
+        assert!(host.calls.is_empty());
+    }
+
+    #[test]
+    fn assignment_does_not_parse() {
+        assert!(matches!(run("g[1] = 3"), Err(EvalError::Parse(_))));
+        assert!(matches!(run("g[1] += 3"), Err(EvalError::Parse(_))));
+        assert!(matches!(run("(1 + 2"), Err(EvalError::Parse(_))));
+    }
+}
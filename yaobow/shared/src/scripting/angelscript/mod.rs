@@ -2,12 +2,14 @@
 pub mod debug;
 
 mod disassembler;
+pub mod eval;
 mod global_context;
 mod module;
 pub mod trace;
 mod vm;
 
 pub use disassembler::{AsInst, AsInstInstance, disasm};
+pub use eval::{EvalError, EvalHost, EvalKey, EvalValue, Expr};
 pub use global_context::{
     ContinuationState, GlobalFunctionContinuation, GlobalFunctionState, ScriptGlobalContext,
     ScriptGlobalFunction, not_implemented,
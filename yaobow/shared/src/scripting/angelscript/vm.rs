@@ -4,6 +4,7 @@ use std::{cell::RefCell, rc::Rc};
 use super::debug::{DebugIpcClient, Notification, Request};
 
 use super::{
+    eval::{EvalError, EvalValue},
     global_context::{GlobalFunctionContinuation, ScriptGlobalContext},
     module::{ScriptFunction, ScriptModule},
     trace::{BranchKind, GlobalScope, TraceEvent, TraceEventKind, TraceSink},
@@ -311,6 +312,127 @@ impl<TAppContext: 'static> ScriptVm<TAppContext> {
         return self.heap.len() - 1;
     }
 
+    /// Call the sysfn `name` outside of any script and hand back its
+    /// return value, leaving the VM exactly as it was.
+    ///
+    /// `args` are pushed the way the bytecode does (last argument
+    /// first); strings go onto the heap and are passed by handle. The
+    /// return value is `r1`, or the pushed stack word for the few
+    /// sysfns (`abs`) that return on the operand stack.
+    ///
+    /// Registers, operand stack, heap, execution context, pending
+    /// continuations, the trace sink, the shared globals and the
+    /// current module's globals are all checkpointed and restored
+    /// afterwards, so the call is invisible to the running script and
+    /// to trace capture. A call that yields or changes a global is
+    /// rolled back and reported as [`EvalError::Rejected`]. Effects
+    /// outside the VM (app-context state, the scene) are not
+    /// checkpointed — callers must only pass query sysfns.
+    pub fn call_sysfn_sandboxed(
+        &mut self,
+        name: &str,
+        args: &[EvalValue],
+    ) -> Result<u32, EvalError> {
+        let Some(index) = self
+            .g
+            .borrow()
+            .functions()
+            .iter()
+            .position(|f| f.name == name)
+        else {
+            return Err(EvalError::Unknown(format!("no sysfn named {}", name)));
+        };
+
+        let sp = self.sp;
+        let fp = self.fp;
+        let r1 = self.r1;
+        let r2 = self.r2;
+        let robj = self.robj;
+        let heap = self.heap.clone();
+        let context = self.context.clone();
+        let call_stack_len = self.call_stack.len();
+        let yield_len = self.yield_func.len();
+        let faulted = self.faulted.get();
+        let trace_sink = self.trace_sink.take();
+        let shared_globals = self.g.borrow().vars.clone();
+        let module_globals = context.as_ref().map(|c| c.module.borrow().globals.clone());
+
+        for arg in args.iter().rev() {
+            match arg {
+                EvalValue::Null => self.stack_push(0u32),
+                EvalValue::Bool(b) => self.stack_push(*b as i32),
+                EvalValue::Int(i) => self.stack_push(*i as i32),
+                EvalValue::Float(f) => self.stack_push(*f as f32),
+                EvalValue::Str(s) => {
+                    let handle = self.push_object(s.clone());
+                    self.stack_push(handle as i32);
+                }
+            }
+        }
+
+        let g = self.g.clone();
+        let state = g.borrow().call_function(self, index);
+        // Well-formed sysfns pop every argument, so anything left
+        // below the caller's `sp` is a stack-returned value.
+        let value = if self.sp < sp {
+            self.stack_pop::<u32>()
+        } else {
+            self.r1
+        };
+
+        let suspended = matches!(state, super::GlobalFunctionState::Yield(_));
+        let stack_faulted = self.faulted.get() && !faulted;
+        let shared_write = g
+            .borrow()
+            .vars
+            .iter()
+            .zip(shared_globals.iter())
+            .position(|(now, before)| now != before);
+        let module_write = match (&self.context, &module_globals) {
+            (Some(c), Some(before)) => *before != c.module.borrow().globals,
+            _ => false,
+        };
+
+        self.sp = sp;
+        self.fp = fp;
+        self.r1 = r1;
+        self.r2 = r2;
+        self.robj = robj;
+        self.heap = heap;
+        self.context = context;
+        self.call_stack.truncate(call_stack_len);
+        self.yield_func.truncate(yield_len);
+        self.faulted.set(faulted);
+        self.trace_sink = trace_sink;
+        g.borrow_mut().vars = shared_globals;
+        if let (Some(c), Some(before)) = (&self.context, module_globals) {
+            c.module.borrow_mut().globals = before;
+        }
+
+        if suspended {
+            return Err(EvalError::Rejected(format!("{} suspends", name)));
+        }
+        if let Some(slot) = shared_write {
+            return Err(EvalError::Rejected(format!(
+                "{} writes shared global {}",
+                name, slot
+            )));
+        }
+        if module_write {
+            return Err(EvalError::Rejected(format!(
+                "{} writes a module global",
+                name
+            )));
+        }
+        if stack_faulted {
+            return Err(EvalError::Type(format!(
+                "{} overran the operand stack; check the argument count",
+                name
+            )));
+        }
+        Ok(value)
+    }
+
     pub fn execute(&mut self, delta_sec: f32) {
         loop {
             if self.context.is_none() {
@@ -1580,11 +1702,14 @@ mod tests {
     use std::cell::RefCell;
     use std::rc::Rc;
 
-    use super::super::ScriptGlobalContext;
+    use super::super::eval::{EvalError, EvalValue};
     use super::super::module::{ScriptFunction, ScriptModule};
     use super::super::trace::{
         BranchKind, GlobalScope, TraceEventKind, TraceSink, test_support::VecSink,
     };
+    use super::super::{
+        ContinuationState, GlobalFunctionState, ScriptGlobalContext, ScriptGlobalFunction,
+    };
     use super::ScriptVm;
 
     /// Build a 4-byte-aligned opcode header (opcode byte + 3 pad).
@@ -2132,4 +2257,92 @@ mod tests {
             );
         }
     }
+
+    fn build_sandbox_vm() -> ScriptVm<()> {
+        let mut g = ScriptGlobalContext::<()>::new();
+        g.register_function(ScriptGlobalFunction::new(
+            "giSub",
+            Box::new(|_, vm: &mut ScriptVm<()>| {
+                let a = vm.stack_pop::<i32>();
+                let b = vm.stack_pop::<i32>();
+                vm.set_ret_value(a - b);
+                GlobalFunctionState::Completed
+            }),
+        ));
+        g.register_function(ScriptGlobalFunction::new(
+            "giStrLen",
+            Box::new(|_, vm: &mut ScriptVm<()>| {
+                let handle = vm.stack_pop::<i32>();
+                let len = vm.heap[handle as usize].as_ref().map_or(0, |s| s.len());
+                vm.set_ret_value(len as i32);
+                GlobalFunctionState::Completed
+            }),
+        ));
+        g.register_function(ScriptGlobalFunction::new(
+            "giSetFlag",
+            Box::new(|_, vm: &mut ScriptVm<()>| {
+                let context = vm.context.as_ref().unwrap();
+                context.module.borrow_mut().globals = vec![0, 1];
+                GlobalFunctionState::Completed
+            }),
+        ));
+        g.register_function(ScriptGlobalFunction::new(
+            "giWait",
+            Box::new(|_, _: &mut ScriptVm<()>| {
+                GlobalFunctionState::Yield(Box::new(|_, _| ContinuationState::Completed))
+            }),
+        ));
+        let module = ScriptModule::test_module(vec![ScriptFunction::test_function(
+            "test_main",
+            assemble(&[&op(108)]),
+        )]);
+        ScriptVm::new(
+            Rc::new(RefCell::new(g)),
+            Rc::new(RefCell::new(module)),
+            0,
+            (),
+        )
+    }
+
+    #[test]
+    fn sandboxed_call_returns_value_and_leaves_vm_untouched() {
+        let mut vm = build_sandbox_vm();
+        let sink = Rc::new(VecSink::new());
+        vm.set_trace_sink(Some(sink.clone() as Rc<dyn TraceSink>));
+        let sp = vm.sp;
+
+        // First argument is popped first, as in the bytecode, so this is
+        // 10 - 3.
+        let ret = vm.call_sysfn_sandboxed("giSub", &[EvalValue::Int(10), EvalValue::Int(3)]);
+        assert_eq!(ret, Ok(7));
+        let ret = vm.call_sysfn_sandboxed("giStrLen", &[EvalValue::Str("ob01".to_string())]);
+        assert_eq!(ret, Ok(4));
+        // `abs` returns on the operand stack rather than in `r1`.
+        let ret = vm.call_sysfn_sandboxed("abs", &[EvalValue::Int(-9)]);
+        assert_eq!(ret, Ok(9));
+
+        assert_eq!(vm.sp, sp);
+        assert_eq!(vm.r1, 0);
+        assert!(vm.heap.is_empty());
+        assert!(vm.has_trace_sink());
+        assert!(sink.snapshot().is_empty());
+        assert_eq!(vm.current_function_name().as_deref(), Some("test_main"));
+    }
+
+    #[test]
+    fn sandboxed_call_rejects_writes_and_suspends() {
+        let mut vm = build_sandbox_vm();
+
+        let ret = vm.call_sysfn_sandboxed("giSetFlag", &[]);
+        assert!(matches!(ret, Err(EvalError::Rejected(_))));
+        let context = vm.context.as_ref().unwrap();
+        assert!(context.module.borrow().globals.is_empty());
+
+        let ret = vm.call_sysfn_sandboxed("giWait", &[]);
+        assert!(matches!(ret, Err(EvalError::Rejected(_))));
+        assert!(vm.yield_func.is_empty());
+
+        let ret = vm.call_sysfn_sandboxed("giMissing", &[]);
+        assert!(matches!(ret, Err(EvalError::Unknown(_))));
+    }
 }
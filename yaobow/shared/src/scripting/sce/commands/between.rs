@@ -19,11 +19,7 @@ impl SceCommand for SceCommandBetween {
         _delta_sec: f32,
     ) -> bool {
         let lhs = if self.var < 0 {
-            state
-                .global_state_mut()
-                .persistent_state_mut()
-                .get_global(self.var)
-                .unwrap_or(0)
+            state.get_global(self.var).unwrap_or(0)
         } else {
             state.context_mut().get_local(self.var).unwrap_or(0)
         };
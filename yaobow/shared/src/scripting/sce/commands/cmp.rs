@@ -30,11 +30,7 @@ impl<FCmp: Clone + for<'a, 'b> Fn(&'a i32, &'b i32) -> bool> SceCommand for SceC
         _delta_sec: f32,
     ) -> bool {
         let lhs = if self.var < 0 {
-            state
-                .global_state_mut()
-                .persistent_state_mut()
-                .get_global(self.var)
-                .unwrap_or(0)
+            state.get_global(self.var).unwrap_or(0)
         } else {
             state.context_mut().get_local(self.var).unwrap_or(0)
         };
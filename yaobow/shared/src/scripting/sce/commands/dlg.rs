@@ -14,6 +14,7 @@ impl SceCommand for SceCommandDlg {
     fn initialize(&mut self, _scene_manager: ComRc<ISceneManager>, state: &mut SceState) {
         self.adv_input_enabled = state.global_state_mut().adv_input_enabled();
         state.global_state_mut().set_adv_input_enabled(false);
+        state.set_dialog_text(Some(self.text.clone()));
     }
 
     fn update(
@@ -26,6 +27,7 @@ impl SceCommand for SceCommandDlg {
         if self.dlg_end {
             // state.global_state_mut().set_adv_input_enabled(self.adv_input_enabled);
             state.clear_dialog_avatar();
+            state.set_dialog_text(None);
 
             return true;
         }
@@ -52,7 +54,7 @@ impl SceCommand for SceCommandDlg {
 impl SceCommandDlg {
     pub fn new(text: String) -> Self {
         Self {
-            text: text.replace("\\n", "\n"),
+            text: crate::localization::localize(&text).replace("\\n", "\n"),
             dlg_end: false,
             adv_input_enabled: false,
         }
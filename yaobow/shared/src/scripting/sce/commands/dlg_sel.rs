@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use crate::scripting::sce::{SceCommand, SceState};
 use crosscom::ComRc;
 use imgui::{Condition, Ui};
+use log::warn;
 use radiance::{comdef::ISceneManager, input::Key};
 
 lazy_static::lazy_static! {
@@ -12,6 +13,7 @@ lazy_static::lazy_static! {
 #[derive(Debug, Clone)]
 pub struct SceCommandDlgSel {
     list: Vec<String>,
+    published: bool,
 }
 
 impl SceCommand for SceCommandDlgSel {
@@ -22,6 +24,24 @@ impl SceCommand for SceCommandDlgSel {
         state: &mut SceState,
         _delta_sec: f32,
     ) -> bool {
+        // `DlgTime` forwards only `update`, so the choices are published
+        // here rather than in `initialize`.
+        if !self.published {
+            state.set_dialog_choices(self.list.clone());
+            self.published = true;
+        }
+
+        if let Some(choice) = state.take_dialog_choice() {
+            if (choice as usize) < self.list.len() {
+                return self.select(state, choice);
+            }
+            warn!(
+                "Buffered dialog choice {} is out of range for {} options",
+                choice + 1,
+                self.list.len()
+            );
+        }
+
         let [window_width, window_height] = ui.io().display_size;
         let (_dialog_x, _dialog_width) = {
             if window_width / window_height > 4. / 3. {
@@ -61,20 +81,15 @@ impl SceCommand for SceCommandDlgSel {
 
         if let Some(sel) = dlg_sel {
             if (sel as usize) < self.list.len() {
-                state
-                    .context_mut()
-                    .current_proc_context_mut()
-                    .set_dlgsel(sel);
-                return true;
+                return self.select(state, sel);
             }
         }
 
         if state.fast_forward() {
-            // PAL3 has no dialog-choice buffering yet (`/v1/dialog/choose`
-            // is not_implemented). Under agent fast-forward, pick the first
-            // option so automated runs don't stall waiting on a number key.
-            state.context_mut().current_proc_context_mut().set_dlgsel(0);
-            return true;
+            // No choice buffered via `/v1/dialog/choose`: under agent
+            // fast-forward, pick the first option so automated runs don't
+            // stall waiting on a number key.
+            return self.select(state, 0);
         }
 
         return false;
@@ -84,9 +99,31 @@ impl SceCommand for SceCommandDlgSel {
 }
 
 impl SceCommandDlgSel {
-    pub fn new(mut list: Vec<String>) -> Self {
+    pub fn new(list: Vec<String>) -> Self {
+        Self::with_display_list(
+            list.iter()
+                .map(|text| crate::localization::localize(text).into_owned())
+                .collect(),
+        )
+    }
+
+    /// Build from choices that are already localized and formatted for
+    /// display (used by `DlgTime`).
+    pub(super) fn with_display_list(mut list: Vec<String>) -> Self {
         list.reverse();
-        Self { list }
+        Self {
+            list,
+            published: false,
+        }
+    }
+
+    fn select(&self, state: &mut SceState, sel: i32) -> bool {
+        state
+            .context_mut()
+            .current_proc_context_mut()
+            .set_dlgsel(sel);
+        state.set_dialog_choices(Vec::new());
+        true
     }
 }
 
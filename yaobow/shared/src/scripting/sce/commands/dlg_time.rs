@@ -25,8 +25,8 @@ impl SceCommand for SceCommandDlgTime {
 impl SceCommandDlgTime {
     pub fn new(text: String) -> Self {
         Self {
-            dlg_sel: SceCommandDlgSel::new(vec![
-                format!("2. \"{}\"", text),
+            dlg_sel: SceCommandDlgSel::with_display_list(vec![
+                format!("2. \"{}\"", crate::localization::localize(&text)),
                 "1. \"……\"".to_string(),
             ]),
         }
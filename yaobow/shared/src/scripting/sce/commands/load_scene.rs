@@ -27,6 +27,7 @@ impl SceCommand for SceCommandLoadScene {
             .or(Some(true))
             .unwrap();
 
+        state.global_state_mut().stop_scene_sound_sources();
         scene_manager.pop_scene();
         scene_manager.push_scene(state.asset_mgr().load_scn(&self.name, &self.sub_name));
         let e = scene_manager.get_resolved_role(state, -1).unwrap();
@@ -1,6 +1,9 @@
 use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
-use crate::scripting::sce::{SceCommand, SceState};
+use crate::{
+    openpal3::{directors::SceneManagerExtensions, scene::ScnScene},
+    scripting::sce::{SceCommand, SceState},
+};
 
 use crosscom::ComRc;
 use imgui::Ui;
@@ -9,11 +12,19 @@ use radiance::{
     comdef::ISceneManager,
 };
 
+/// Distance within which a node's ambient loop plays at full volume.
+const AMBIENT_REFERENCE_DISTANCE: f32 = 150.0;
+/// Distance past which a node's ambient loop stops getting quieter.
+const AMBIENT_MAX_DISTANCE: f32 = 600.0;
+
 #[derive(Clone)]
 pub struct SceCommandPlaySound {
     name: String,
     times: i32,
     source: Option<Rc<RefCell<Box<dyn AudioMemorySource>>>>,
+    /// Scene (`cpk`, `scn`) whose node the source sits at, for sounds
+    /// placed in the world rather than at the listener.
+    node_scene: Option<(String, String)>,
 }
 
 impl Debug for SceCommandPlaySound {
@@ -26,16 +37,39 @@ impl Debug for SceCommandPlaySound {
 }
 
 impl SceCommand for SceCommandPlaySound {
-    fn initialize(&mut self, _scene_manager: ComRc<ISceneManager>, state: &mut SceState) {
+    fn initialize(&mut self, scene_manager: ComRc<ISceneManager>, state: &mut SceState) {
         let data = state.asset_mgr().load_snd_data(&self.name);
         match data {
             Ok(d) => {
                 let mut source = state.audio_engine().create_source();
                 source.set_data(d, Codec::Wav);
+
+                // A repeating sound started by a scene node's proc is that
+                // node's ambience (water, machinery): place it at the node
+                // so it fades with distance. Everything else stays
+                // head-locked.
+                let proc_id = state.context().current_proc_id();
+                let node = match (self.times > 1, proc_id, scene_manager.scn_scene()) {
+                    (true, Some(proc_id), Some(scn)) => {
+                        let scn = scn.inner::<ScnScene>();
+                        scn.node_position_for_proc(proc_id).map(|position| {
+                            let scene = (scn.name().to_string(), scn.sub_name().to_string());
+                            (position, scene)
+                        })
+                    }
+                    _ => None,
+                };
+                if let Some((position, scene)) = node {
+                    source.set_relative(false);
+                    source.set_reference_distance(AMBIENT_REFERENCE_DISTANCE);
+                    source.set_max_distance(AMBIENT_MAX_DISTANCE);
+                    source.set_position([position.x, position.y, position.z]);
+                    self.node_scene = Some(scene);
+                }
                 source.play(false);
 
                 let source = Rc::new(RefCell::new(source));
-                state.global_state_mut().add_sound_source(source.clone());
+                self.register(state, source.clone());
 
                 self.source = Some(source);
             }
@@ -45,9 +79,9 @@ impl SceCommand for SceCommandPlaySound {
 
     fn update(
         &mut self,
-        _scene_manager: ComRc<ISceneManager>,
+        scene_manager: ComRc<ISceneManager>,
         _ui: &Ui,
-        _state: &mut SceState,
+        state: &mut SceState,
         _delta_sec: f32,
     ) -> bool {
         if self.times <= 1 {
@@ -58,9 +92,25 @@ impl SceCommand for SceCommandPlaySound {
             return true;
         }
 
-        if self.source.as_mut().unwrap().borrow_mut().state() == AudioSourceState::Stopped {
+        // A node's loop ends with its scene; `LoadScene` has already
+        // stopped the source.
+        if let Some((name, sub_name)) = &self.node_scene {
+            let same_scene = scene_manager.scn_scene().map_or(false, |s| {
+                let s = s.inner::<ScnScene>();
+                s.name() == name && s.sub_name() == sub_name
+            });
+            if !same_scene {
+                return true;
+            }
+        }
+
+        let source = self.source.clone().unwrap();
+        if source.borrow().state() == AudioSourceState::Stopped {
             self.times -= 1;
-            self.source.as_mut().unwrap().borrow_mut().restart();
+            source.borrow_mut().restart();
+            // The global state drops stopped sources; re-register so the
+            // restarted one keeps being updated.
+            self.register(state, source);
         }
 
         false
@@ -73,6 +123,15 @@ impl SceCommandPlaySound {
             name,
             times,
             source: None,
+            node_scene: None,
+        }
+    }
+
+    fn register(&self, state: &mut SceState, source: Rc<RefCell<Box<dyn AudioMemorySource>>>) {
+        if self.node_scene.is_some() {
+            state.global_state_mut().add_scene_sound_source(source);
+        } else {
+            state.global_state_mut().add_sound_source(source);
         }
     }
 }
@@ -21,10 +21,7 @@ impl SceCommand for SceCommandRnd {
     ) -> bool {
         let value = self.rng.gen_range(0..self.max_value);
         if self.var < 0 {
-            state
-                .global_state_mut()
-                .persistent_state_mut()
-                .set_global(self.var, value)
+            state.set_global(self.var, value)
         } else {
             state.context_mut().set_local(self.var, value)
         }
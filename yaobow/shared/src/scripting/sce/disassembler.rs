@@ -0,0 +1,382 @@
+//! Static walker for PAL3 SCE proc bytecode.
+//!
+//! Decodes an instruction stream without executing it, using the same
+//! operand layouts [`super::vm::SceProcContext::get_next_cmd`] consumes.
+//! Used by offline tooling (dialog extraction for localization catalogs,
+//! plot dumps) that needs every string a proc carries, not only the ones
+//! a particular playthrough reaches.
+//!
+//! The layout table in [`opcode_layout`] must stay in sync with the VM
+//! dispatch: an opcode the VM learns without an entry here makes the
+//! walker stop at that instruction with an error.
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use encoding::{DecoderTrap, Encoding};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceOperandKind {
+    I16,
+    I32,
+    U32,
+    F32,
+    String,
+    List,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum SceOperand {
+    I16(i16),
+    I32(i32),
+    U32(u32),
+    F32(f32),
+    String(String),
+    List(Vec<String>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SceInstInstance {
+    pub addr: u32,
+    pub opcode: i16,
+    pub access_local_var: i16,
+    pub name: &'static str,
+    /// Operands in declaration order (the order the VM command
+    /// constructors take them), not the on-disk order.
+    pub operands: Vec<SceOperand>,
+}
+
+impl SceInstInstance {
+    /// Every string operand carried by this instruction, including the
+    /// entries of a `DlgSel` list.
+    pub fn strings(&self) -> Vec<&str> {
+        let mut ret = vec![];
+        for operand in &self.operands {
+            match operand {
+                SceOperand::String(s) => ret.push(s.as_str()),
+                SceOperand::List(list) => ret.extend(list.iter().map(|s| s.as_str())),
+                _ => {}
+            }
+        }
+
+        ret
+    }
+}
+
+/// Name and operand kinds (declaration order) for `opcode`. `GEQ` (10)
+/// is the only opcode whose layout depends on `access_local_var`.
+pub fn opcode_layout(
+    opcode: i16,
+    access_local_var: i16,
+) -> Option<(&'static str, &'static [SceOperandKind])> {
+    use SceOperandKind::*;
+
+    let layout: (&'static str, &'static [SceOperandKind]) = match opcode {
+        1 => ("Idle", &[F32]),
+        2 => ("ScriptRunMode", &[I32]),
+        3 => ("Goto", &[U32]),
+        5 => ("FOP", &[I32]),
+        6 => ("GT", &[I16, I32]),
+        7 => ("LS", &[I16, I32]),
+        8 => ("EQ", &[I16, I32]),
+        9 => ("NEQ", &[I16, I32]),
+        10 => match access_local_var {
+            1 => ("GEQ", &[I16, I32]),
+            3 => ("GEQ", &[I16, I16]),
+            _ => ("GEQ", &[]),
+        },
+        11 => ("LEQ", &[I16, I32]),
+        12 => ("TestGoto", &[U32]),
+        13 => ("Let", &[I16, I32]),
+        16 => ("Call", &[U32]),
+        17 => ("Rnd", &[I16, I32]),
+        19 => ("Between", &[I16, I32, I32]),
+        20 => ("RolePathTo", &[I32, I32, I32, I32]),
+        21 => ("RoleSetPos", &[I32, I32, I32]),
+        22 => ("RoleShowAction", &[I32, String, I32]),
+        23 => ("RoleSetFace", &[I32, I32]),
+        24 => ("RoleTurnFace", &[I32, I32]),
+        25 => ("TeamOpen", &[]),
+        26 => ("TeamClose", &[]),
+        27 => ("RoleInput", &[I32]),
+        28 => ("RoleActive", &[I32, I32]),
+        29 => ("RoleScript", &[I32, I32]),
+        30 => ("CameraFocusRole", &[I32]),
+        31 => ("CameraFocusPoint", &[F32, F32, F32]),
+        32 => ("CameraPush", &[F32, F32, I32]),
+        33 => ("CameraRotate", &[F32, F32, F32, I32]),
+        34 => ("CameraMove", &[F32, F32, F32, F32, F32]),
+        35 => ("CameraWag", &[F32, F32, F32, I32]),
+        36 => ("CameraSet", &[F32, F32, F32, F32, F32, F32]),
+        37 => ("CameraDefault", &[I32]),
+        38 => ("CameraPushState", &[]),
+        39 => ("CameraPopState", &[]),
+        42 => ("LK_Ghost", &[I32]),
+        43 => ("FavorAdd", &[I32, I32]),
+        46 => ("AddItem", &[I32, I32]),
+        47 => ("RemoveItem", &[I32]),
+        48 => ("AddMoney", &[I32]),
+        49 => ("GetMoney", &[I16]),
+        50 => ("GetFavor", &[I16, I32]),
+        51 => ("AddSkill", &[I32, I32]),
+        52 => ("GetFavorite", &[I16]),
+        54 => ("FullRoleAtt", &[I32, I32]),
+        62 => ("Dlg", &[String]),
+        63 => ("LoadScene", &[String, String]),
+        65 => ("DlgSel", &[List]),
+        66 => ("GetDlgSel", &[I16]),
+        67 => ("DlgFace", &[I32, String, I32]),
+        68 => ("Note", &[String]),
+        69 => ("FadeOut", &[]),
+        70 => ("FadeIn", &[]),
+        71 => ("RoleStop", &[I32]),
+        72 => ("RoleEmote", &[I32, I32]),
+        74 => ("Climb", &[I32, I32]),
+        76 => ("DlgTime", &[String]),
+        77 => ("GetTimeSel", &[I16]),
+        78 => ("HaveItem", &[I32]),
+        79 => ("PlaySound", &[String, I32]),
+        80 => ("CombatBoss", &[I32, I32, I32, I32, I32, I32]),
+        81 => ("FadeOutWhite", &[]),
+        82 => ("CombatMaxRound", &[I32]),
+        83 => ("CombatMustFail", &[]),
+        85 => ("ObjectActive", &[I32, I32]),
+        86 => ("Caption", &[String, I32]),
+        87 => ("OpenDoor", &[I32]),
+        88 => ("HY_Mode", &[I32]),
+        89 => ("HY_FLY", &[F32, F32, F32]),
+        90 => ("ObjectMove", &[I32, F32, F32, F32, F32]),
+        91 => ("FadeInWhite", &[]),
+        102 => ("SwitchRS", &[I32]),
+        104 => ("APPREntry", &[]),
+        106 => ("ENCAMP_Entry", &[I32]),
+        107 => ("SKEE_Entry", &[I32]),
+        108 => ("GetAppr", &[I16]),
+        109 => ("Enable_Sword", &[I32]),
+        111 => ("Specify_Compos", &[I32]),
+        113 => ("Start_HideFight", &[]),
+        115 => ("Movie", &[String]),
+        116 => ("SetRoleTexture", &[I32, String]),
+        117 => ("Rotate", &[I32, I32, I32]),
+        118 => ("Quake", &[F32, F32]),
+        119 => ("ShowChatRest", &[String, U32, U32, U32]),
+        124 => ("Trigger", &[I32]),
+        125 => ("SetBigMapElement", &[I32, I32]),
+        126 => ("GetSwitch", &[String, I32, I16]),
+        127 => ("EntryRow", &[I32, I32]),
+        128 => ("RotateInv", &[I32, I32, I32]),
+        130 => ("Dist", &[I16, I16]),
+        131 => ("CombatNotGameOver", &[]),
+        132 => ("GetCombat", &[I16]),
+        133 => ("Music", &[String, I32]),
+        134 => ("StopMusic", &[]),
+        135 => ("RoleFadeOut", &[I32]),
+        136 => ("RoleFadeIn", &[I32]),
+        137 => ("IfInTeam", &[I32]),
+        138 => ("Enable_SwordSkill", &[I32]),
+        140 => ("Snow", &[I32]),
+        141 => ("ScrEft", &[I32]),
+        142 => ("CEft_Pos", &[F32, F32, F32]),
+        143 => ("CEft", &[I32]),
+        144 => ("CEft_Role", &[I32]),
+        145 => ("AverageLv", &[I32, I32]),
+        147 => ("Switch2Menu", &[]),
+        148 => ("CEft_Load", &[I32]),
+        149 => ("GiveCloth", &[I16]),
+        150 => ("LoadAct", &[I32, String]),
+        152 => ("WaterMagic", &[I32]),
+        153 => ("FullTeamAtt", &[]),
+        155 => ("CameraYaw", &[F32]),
+        156 => ("XJ_Pic", &[]),
+        158 => ("ObjNotLoad", &[I32]),
+        159 => ("InitFlower", &[]),
+        201 => ("RolePathOut", &[I32, I32, I32, I32]),
+        202 => ("InTeam", &[I32, I32]),
+        203 => ("RoleSetLayer", &[I32, I32]),
+        204 => ("RoleCtrl", &[I32]),
+        205 => ("RoleOverlap", &[I32, I32]),
+        206 => ("RoleScale", &[I32, F32]),
+        207 => ("RoleActAutoStand", &[I32, I32]),
+        208 => ("RoleMoveBack", &[I32, F32]),
+        209 => ("RoleFaceRole", &[I32, I32]),
+        210 => ("RoleTurnFaceA", &[I32, I32]),
+        211 => ("TeamOpenA", &[]),
+        212 => ("TeamCloseA", &[]),
+        214 => ("RoleMovTo", &[I32, I32, I32, I32]),
+        221 => ("RoleEndAction", &[I32]),
+        250 => ("CameraFree", &[I32]),
+        251 => ("ObjectMove", &[I32, F32, F32, F32, F32]),
+        _ => return None,
+    };
+
+    Some(layout)
+}
+
+/// Walk a proc's instruction stream from the start to the end. Stops
+/// with an error at the first unknown opcode or truncated operand.
+pub fn disasm(inst: &[u8]) -> anyhow::Result<Vec<SceInstInstance>> {
+    match disasm_partial(inst) {
+        (insts, None) => Ok(insts),
+        (_, Some(e)) => Err(e),
+    }
+}
+
+/// Like [`disasm`], but keeps the instructions decoded before the
+/// first error. For tools that summarise a proc as far as it can be
+/// read.
+pub fn disasm_partial(inst: &[u8]) -> (Vec<SceInstInstance>, Option<anyhow::Error>) {
+    let mut reader = Reader { inst, pc: 0 };
+    let mut insts = vec![];
+
+    while reader.pc < inst.len() {
+        match reader.inst() {
+            Ok(i) => insts.push(i),
+            Err(e) => return (insts, Some(e)),
+        }
+    }
+
+    (insts, None)
+}
+
+struct Reader<'a> {
+    inst: &'a [u8],
+    pc: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn inst(&mut self) -> anyhow::Result<SceInstInstance> {
+        let addr = self.pc as u32;
+        let opcode = self.i16()?;
+        let access_local_var = self.i16()?;
+        let (name, kinds) = opcode_layout(opcode, access_local_var)
+            .ok_or_else(|| anyhow::anyhow!("unsupported sce opcode {} at 0x{:x}", opcode, addr))?;
+
+        // The VM's `command!` macro reads parameters last-to-first, so
+        // the on-disk order is the reverse of the declaration order.
+        let mut operands = kinds
+            .iter()
+            .rev()
+            .map(|kind| self.operand(*kind))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|e| e.context(format!("operands of {} at 0x{:x}", name, addr)))?;
+        operands.reverse();
+
+        Ok(SceInstInstance {
+            addr,
+            opcode,
+            access_local_var,
+            name,
+            operands,
+        })
+    }
+
+    fn read(&mut self, count: usize) -> anyhow::Result<&'a [u8]> {
+        let end = self.pc + count;
+        if end > self.inst.len() {
+            anyhow::bail!("unexpected end of proc at 0x{:x}", self.pc);
+        }
+
+        let ret = &self.inst[self.pc..end];
+        self.pc = end;
+        Ok(ret)
+    }
+
+    fn i16(&mut self) -> anyhow::Result<i16> {
+        Ok(self.read(2)?.read_i16::<LittleEndian>()?)
+    }
+
+    fn string(&mut self) -> anyhow::Result<String> {
+        let len = self.read(2)?.read_u16::<LittleEndian>()? as usize;
+        let bytes = self.read(len)?;
+        let bytes = &bytes[..len.saturating_sub(1)];
+        Ok(encoding::all::GBK
+            .decode(bytes, DecoderTrap::Ignore)
+            .unwrap_or_default())
+    }
+
+    fn operand(&mut self, kind: SceOperandKind) -> anyhow::Result<SceOperand> {
+        Ok(match kind {
+            SceOperandKind::I16 => SceOperand::I16(self.i16()?),
+            SceOperandKind::I32 => SceOperand::I32(self.read(4)?.read_i32::<LittleEndian>()?),
+            SceOperandKind::U32 => SceOperand::U32(self.read(4)?.read_u32::<LittleEndian>()?),
+            SceOperandKind::F32 => SceOperand::F32(self.read(4)?.read_f32::<LittleEndian>()?),
+            SceOperandKind::String => SceOperand::String(self.string()?),
+            SceOperandKind::List => {
+                let len = self.read(2)?.read_u16::<LittleEndian>()?;
+                let mut list = vec![];
+                for _ in 0..len {
+                    self.read(1)?;
+                    list.push(self.string()?);
+                }
+                SceOperand::List(list)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_string(buf: &mut Vec<u8>, text: &[u8]) {
+        buf.extend(((text.len() + 1) as u16).to_le_bytes());
+        buf.extend(text);
+        buf.push(0);
+    }
+
+    #[test]
+    fn operands_are_returned_in_declaration_order() {
+        let mut inst = vec![];
+        // DlgFace(id = 3, face = "a", side = 1): stored side, face, id.
+        inst.extend(67i16.to_le_bytes());
+        inst.extend(0i16.to_le_bytes());
+        inst.extend(1i32.to_le_bytes());
+        push_string(&mut inst, b"a");
+        inst.extend(3i32.to_le_bytes());
+        // DlgSel(["x", "y"])
+        inst.extend(65i16.to_le_bytes());
+        inst.extend(0i16.to_le_bytes());
+        inst.extend(2u16.to_le_bytes());
+        for s in [b"x", b"y"] {
+            inst.push(0);
+            push_string(&mut inst, s);
+        }
+
+        let insts = disasm(&inst).unwrap();
+        assert_eq!(insts.len(), 2);
+        assert_eq!(insts[0].name, "DlgFace");
+        assert_eq!(
+            insts[0].operands,
+            vec![
+                SceOperand::I32(3),
+                SceOperand::String("a".to_string()),
+                SceOperand::I32(1)
+            ]
+        );
+        assert_eq!(insts[1].addr, 16);
+        assert_eq!(insts[1].strings(), vec!["x", "y"]);
+    }
+
+    #[test]
+    fn unknown_opcode_is_an_error() {
+        let mut inst = vec![];
+        inst.extend(999i16.to_le_bytes());
+        inst.extend(0i16.to_le_bytes());
+        assert!(disasm(&inst).is_err());
+    }
+
+    #[test]
+    fn partial_walk_keeps_instructions_before_the_error() {
+        let mut inst = vec![];
+        // Goto 0x10, then an unknown opcode.
+        inst.extend(3i16.to_le_bytes());
+        inst.extend(0i16.to_le_bytes());
+        inst.extend(0x10u32.to_le_bytes());
+        inst.extend(999i16.to_le_bytes());
+        inst.extend(0i16.to_le_bytes());
+
+        let (insts, err) = disasm_partial(&inst);
+        assert_eq!(insts.len(), 1);
+        assert_eq!(insts[0].operands, vec![SceOperand::U32(0x10)]);
+        assert!(err.is_some());
+    }
+}
@@ -19,9 +19,12 @@ use crate::openpal3::{
     states::global_state::GlobalState,
 };
 
+use self::trace::SceTraceEvent;
 use self::vm::{SceExecutionContext, SceExecutionOptions};
 
 pub mod commands;
+pub mod disassembler;
+pub mod trace;
 pub mod vm;
 
 /// The scripted PAL3 dialog-box renderer, threaded from `Pal3Service`
@@ -55,6 +58,18 @@ impl<T: SceCommand + std::fmt::Debug> SceCommandDebug for T {
     }
 }
 
+/// What the dialog commands currently show, published for the agent
+/// server's `/v1/state`.
+#[derive(Debug, Clone, Default)]
+pub struct SceDialogState {
+    /// Localized text of the open `Dlg` box.
+    pub text: Option<String>,
+    /// `DlgFace` portrait side: 0 left, 1 right.
+    pub avatar_side: Option<i32>,
+    /// Options of the pending `DlgSel` / `DlgTime`, in display order.
+    pub choices: Vec<String>,
+}
+
 pub struct SceState {
     asset_mgr: Rc<AssetManager>,
     global_state: GlobalState,
@@ -68,6 +83,10 @@ pub struct SceState {
     /// waits (dialog / movie) and collapse timed tweens to their final
     /// state in a single frame. Defaults to `false` (real-time).
     fast_forward: bool,
+    dialog: SceDialogState,
+    /// Choice buffered by `/v1/dialog/choose` for the next `DlgSel`
+    /// (0-based).
+    dialog_choice: Option<i32>,
     ext: HashMap<String, Box<dyn Any>>,
     input_engine: Rc<RefCell<dyn InputEngine>>,
     audio_engine: Rc<dyn AudioEngine>,
@@ -112,6 +131,8 @@ impl SceState {
             run_mode: 1,
             curtain: 1.,
             fast_forward: false,
+            dialog: SceDialogState::default(),
+            dialog_choice: None,
             ext,
             input_engine,
             audio_engine,
@@ -129,6 +150,26 @@ impl SceState {
         &mut self.global_state
     }
 
+    /// Read an SCE global (a negative variable index), reporting the
+    /// access to the trace hooks. Unset globals read as `None`.
+    pub fn get_global(&self, var: i16) -> Option<i32> {
+        let value = self.global_state.persistent_state().get_global(var);
+        self.context.trace(&SceTraceEvent::GlobalRead {
+            var,
+            value: value.unwrap_or(0),
+        });
+        value
+    }
+
+    /// Write an SCE global, reporting the access to the trace hooks.
+    pub fn set_global(&mut self, var: i16, value: i32) {
+        self.global_state
+            .persistent_state_mut()
+            .set_global(var, value);
+        self.context
+            .trace(&SceTraceEvent::GlobalWrite { var, value });
+    }
+
     pub fn context_mut(&mut self) -> &mut SceExecutionContext {
         &mut self.context
     }
@@ -226,13 +267,38 @@ impl SceState {
     /// Set the dialog avatar/portrait (PAL3 `dlgface`). The p7 renderer
     /// loads the portrait sprite; called outside any `with_ui_host`
     /// scope so the texture-cache upload doesn't re-enter a held borrow.
-    pub fn set_dialog_avatar(&self, role: &str, face: &str, side: i32) {
+    pub fn set_dialog_avatar(&mut self, role: &str, face: &str, side: i32) {
         self.dialog_renderer.set_avatar(role, face, side);
+        self.dialog.avatar_side = Some(side);
     }
 
     /// Clear the dialog avatar/portrait (end of a `dlg` beat).
-    pub fn clear_dialog_avatar(&self) {
+    pub fn clear_dialog_avatar(&mut self) {
         self.dialog_renderer.clear_avatar();
+        self.dialog.avatar_side = None;
+    }
+
+    pub fn dialog(&self) -> &SceDialogState {
+        &self.dialog
+    }
+
+    pub fn set_dialog_text(&mut self, text: Option<String>) {
+        self.dialog.text = text;
+    }
+
+    pub fn set_dialog_choices(&mut self, choices: Vec<String>) {
+        self.dialog.choices = choices;
+    }
+
+    /// Buffer a 0-based choice for the next `DlgSel`. Wired to
+    /// `/v1/dialog/choose`; consumed by the first `DlgSel` that reads
+    /// it.
+    pub fn buffer_dialog_choice(&mut self, index: i32) {
+        self.dialog_choice = Some(index);
+    }
+
+    pub fn take_dialog_choice(&mut self) -> Option<i32> {
+        self.dialog_choice.take()
     }
 
     /// Compose the top-right status indicator for this frame. Called only
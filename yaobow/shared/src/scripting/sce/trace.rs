@@ -0,0 +1,219 @@
+//! Execution trace for the PAL3 SCE VM.
+//!
+//! The SCE VM reports proc entry/exit, every decoded command and every
+//! global-variable access to its [`SceProcHooks`] through
+//! [`SceProcHooks::trace`]. [`SceTraceHooks`] is the hook that turns
+//! those reports into the AngelScript VM's [`TraceEvent`]s, so the agent
+//! server's trace adapter (and any driver reading `/v1/script/trace/*`)
+//! handles PAL3 and PAL4 the same way:
+//!
+//! | SCE event     | Trace event    | Notes                                        |
+//! | ------------- | -------------- | -------------------------------------------- |
+//! | proc enter    | `FnEnter`      | `function_index` is the proc id              |
+//! | proc exit     | `FnExit`       |                                              |
+//! | command       | `CallSys`      | `sysfn_index` is the opcode; stack fields 0  |
+//! | global read   | `GlobalRead`   | `Shared` scope; `slot` is the var as `u16`   |
+//! | global write  | `GlobalWrite`  | same                                         |
+//!
+//! `pc` is the byte offset of the current command inside its proc.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::openpal3::states::global_state::GlobalState;
+use crate::scripting::angelscript::{GlobalScope, TraceEvent, TraceEventKind, TraceSink};
+
+use super::vm::SceProcHooks;
+
+/// Where a traced event happened.
+#[derive(Debug, Clone, Copy)]
+pub struct SceTraceSite<'a> {
+    pub sce_name: &'a str,
+    pub proc_id: u32,
+    pub proc_name: &'a str,
+    /// Proc call-stack depth. For `ProcEnter` it includes the entered
+    /// proc; for `ProcExit` it still includes the exiting one.
+    pub depth: usize,
+    /// Offset of the current command within the proc.
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceTraceEvent<'a> {
+    ProcEnter,
+    ProcExit,
+    /// A command was decoded and is about to run.
+    Command {
+        opcode: i16,
+        name: &'a str,
+    },
+    /// A global (negative-index) variable was read.
+    GlobalRead {
+        var: i16,
+        value: i32,
+    },
+    /// A global (negative-index) variable was written.
+    GlobalWrite {
+        var: i16,
+        value: i32,
+    },
+}
+
+/// [`SceProcHooks`] forwarding trace reports to a [`TraceSink`].
+/// Installed by [`SceVm::set_trace_sink`](super::vm::SceVm::set_trace_sink).
+pub struct SceTraceHooks {
+    sink: Rc<dyn TraceSink>,
+    seq: Cell<u64>,
+}
+
+impl SceTraceHooks {
+    pub fn new(sink: Rc<dyn TraceSink>) -> Self {
+        Self {
+            sink,
+            seq: Cell::new(0),
+        }
+    }
+}
+
+impl SceProcHooks for SceTraceHooks {
+    fn proc_begin(&self, _sce_name: &str, _proc_id: u32, _global_state: &mut GlobalState) {}
+
+    fn proc_end(&self, _sce_name: &str, _proc_id: u32, _global_state: &mut GlobalState) {}
+
+    fn trace(&self, site: &SceTraceSite, event: &SceTraceEvent) {
+        let seq = self.seq.get();
+        self.seq.set(seq.wrapping_add(1));
+        self.sink.record(TraceEvent {
+            seq,
+            kind: trace_event_kind(site, event),
+        });
+    }
+}
+
+pub fn trace_event_kind(site: &SceTraceSite, event: &SceTraceEvent) -> TraceEventKind {
+    let fn_name = site.proc_name.to_string();
+    match *event {
+        SceTraceEvent::ProcEnter => TraceEventKind::FnEnter {
+            name: fn_name,
+            function_index: site.proc_id as usize,
+            depth: site.depth,
+        },
+        SceTraceEvent::ProcExit => TraceEventKind::FnExit {
+            name: fn_name,
+            depth: site.depth,
+        },
+        SceTraceEvent::Command { opcode, name } => TraceEventKind::CallSys {
+            fn_name,
+            pc: site.offset,
+            sysfn_index: opcode as u16 as usize,
+            sysfn_name: name.to_string(),
+            sp_before: 0,
+            sp_after: 0,
+            r1_after: 0,
+        },
+        SceTraceEvent::GlobalRead { var, value } => TraceEventKind::GlobalRead {
+            fn_name,
+            pc: site.offset,
+            scope: GlobalScope::Shared,
+            slot: var as u16 as u32,
+            value: value as u32,
+        },
+        SceTraceEvent::GlobalWrite { var, value } => TraceEventKind::GlobalWrite {
+            fn_name,
+            pc: site.offset,
+            scope: GlobalScope::Shared,
+            slot: var as u16 as u32,
+            value: value as u32,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site() -> SceTraceSite<'static> {
+        SceTraceSite {
+            sce_name: "q01",
+            proc_id: 1415,
+            proc_name: "main",
+            depth: 2,
+            offset: 24,
+        }
+    }
+
+    #[test]
+    fn commands_map_to_call_sys() {
+        let kind = trace_event_kind(
+            &site(),
+            &SceTraceEvent::Command {
+                opcode: 62,
+                name: "Dlg",
+            },
+        );
+        match kind {
+            TraceEventKind::CallSys {
+                fn_name,
+                pc,
+                sysfn_index,
+                sysfn_name,
+                ..
+            } => {
+                assert_eq!(fn_name, "main");
+                assert_eq!(pc, 24);
+                assert_eq!(sysfn_index, 62);
+                assert_eq!(sysfn_name, "Dlg");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn globals_keep_the_var_bit_pattern() {
+        let kind = trace_event_kind(
+            &site(),
+            &SceTraceEvent::GlobalWrite {
+                var: -32768,
+                value: -1,
+            },
+        );
+        match kind {
+            TraceEventKind::GlobalWrite {
+                scope, slot, value, ..
+            } => {
+                assert_eq!(scope, GlobalScope::Shared);
+                assert_eq!(slot, 0x8000);
+                assert_eq!(value, u32::MAX);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn hooks_assign_monotonic_seq() {
+        use std::cell::RefCell;
+
+        struct Recorder(RefCell<Vec<TraceEvent>>);
+        impl TraceSink for Recorder {
+            fn record(&self, event: TraceEvent) {
+                self.0.borrow_mut().push(event);
+            }
+        }
+
+        let recorder = Rc::new(Recorder(RefCell::new(Vec::new())));
+        let hooks = SceTraceHooks::new(recorder.clone());
+        hooks.trace(&site(), &SceTraceEvent::ProcEnter);
+        hooks.trace(&site(), &SceTraceEvent::ProcExit);
+
+        let events = recorder.0.borrow();
+        assert_eq!(events.iter().map(|e| e.seq).collect::<Vec<_>>(), [0, 1]);
+        assert!(matches!(
+            events[0].kind,
+            TraceEventKind::FnEnter {
+                function_index: 1415,
+                depth: 2,
+                ..
+            }
+        ));
+    }
+}
@@ -1,8 +1,11 @@
 use crate::openpal3::asset_manager::AssetManager;
 use crate::openpal3::loaders::sce_loader::SceFile;
 use crate::openpal3::states::global_state::GlobalState;
+use crate::scripting::angelscript::TraceSink;
 use crate::scripting::sce::SceCommandDebug;
 
+use super::disassembler::opcode_layout;
+use super::trace::{SceTraceEvent, SceTraceHooks, SceTraceSite};
 use super::{SceCommand, SceState, commands::*};
 use crosscom::ComRc;
 use encoding::{DecoderTrap, Encoding};
@@ -20,6 +23,10 @@ pub struct SceExecutionOptions {
 pub trait SceProcHooks {
     fn proc_begin(&self, sce_name: &str, proc_id: u32, global_state: &mut GlobalState);
     fn proc_end(&self, sce_name: &str, proc_id: u32, global_state: &mut GlobalState);
+
+    /// Observe proc entry/exit, decoded commands and global-variable
+    /// accesses. See [`super::trace`].
+    fn trace(&self, _site: &SceTraceSite, _event: &SceTraceEvent) {}
 }
 
 pub struct SceVm {
@@ -158,6 +165,12 @@ impl SceVm {
         self.state.call_proc(proc_id)
     }
 
+    /// Record execution into `sink` (see [`super::trace`]), mirroring
+    /// `ScriptVm::set_trace_sink` for PAL4. `None` stops tracing.
+    pub fn set_trace_sink(&mut self, sink: Option<Rc<dyn TraceSink>>) {
+        self.state.context_mut().set_trace_sink(sink);
+    }
+
     pub fn state(&self) -> &SceState {
         &self.state
     }
@@ -222,7 +235,11 @@ macro_rules! nop_command {
 pub struct SceProcContext {
     sce: Rc<SceFile>,
     proc_id: u32,
+    proc_name: String,
     program_counter: usize,
+    /// Offset, opcode and local-var flag of the command most recently
+    /// decoded.
+    last_command: (usize, i16, i16),
     local_vars: HashMap<i16, i32>,
     dlgsel: i32,
 }
@@ -252,10 +269,13 @@ impl SceProcContext {
             "Start executing SceProc {} Id {} Offset {}",
             proc.name, proc.id, proc.offset
         );
+        let proc_name = proc.name.clone();
         Self {
             sce,
             proc_id,
+            proc_name,
             program_counter: 0,
+            last_command: (0, 0, 0),
             local_vars: HashMap::new(),
             dlgsel: 0,
         }
@@ -288,8 +308,10 @@ impl SceProcContext {
             return None;
         }
 
+        let offset = self.program_counter;
         let cmd = data_read::i16(self);
         let access_local_var = data_read::i16(self);
+        self.last_command = (offset, cmd, access_local_var);
         match cmd {
             1 => {
                 // Idle
@@ -956,6 +978,7 @@ pub struct SceExecutionContext {
     sce_name: String,
     proc_stack: Vec<SceProcContext>,
     options: Option<SceExecutionOptions>,
+    trace_hooks: Option<SceTraceHooks>,
 }
 
 impl SceExecutionContext {
@@ -965,9 +988,14 @@ impl SceExecutionContext {
             sce_name,
             proc_stack: vec![],
             options,
+            trace_hooks: None,
         }
     }
 
+    pub fn set_trace_sink(&mut self, sink: Option<Rc<dyn TraceSink>>) {
+        self.trace_hooks = sink.map(SceTraceHooks::new);
+    }
+
     pub fn set_sce(&mut self, sce: Rc<SceFile>, sce_name: String) {
         self.sce = sce;
         self.sce_name = sce_name;
@@ -977,6 +1005,7 @@ impl SceExecutionContext {
         self.proc_stack
             .push(SceProcContext::new_from_id(self.sce.clone(), proc_id));
         self.proc_begin(proc_id, global_state);
+        self.trace(&SceTraceEvent::ProcEnter);
     }
 
     pub fn try_call_proc_by_name(&mut self, proc_name: &str, global_state: &mut GlobalState) {
@@ -984,6 +1013,7 @@ impl SceExecutionContext {
         if let Some(c) = context {
             self.proc_begin(c.proc_id, global_state);
             self.proc_stack.push(c);
+            self.trace(&SceTraceEvent::ProcEnter);
         }
     }
 
@@ -1040,13 +1070,54 @@ impl SceExecutionContext {
             if p.proc_completed() {
                 debug!("Sce proc {} completed", p.proc_id);
                 self.proc_end(p.proc_id, global_state);
+                self.trace(&SceTraceEvent::ProcExit);
                 self.proc_stack.pop();
             } else {
                 break;
             }
         }
 
-        self.proc_stack.last_mut().and_then(|p| p.get_next_cmd())
+        let cmd = self.proc_stack.last_mut().and_then(|p| p.get_next_cmd())?;
+        if self.is_traced() {
+            let (_, opcode, access_local_var) = self.proc_stack.last().unwrap().last_command;
+            let name = opcode_layout(opcode, access_local_var).map_or("?", |(name, _)| name);
+            self.trace(&SceTraceEvent::Command { opcode, name });
+        }
+        Some(cmd)
+    }
+
+    fn is_traced(&self) -> bool {
+        self.trace_hooks.is_some()
+            || self
+                .options
+                .as_ref()
+                .is_some_and(|options| !options.proc_hooks.is_empty())
+    }
+
+    /// Report `event` at the current command of the top proc to every
+    /// hook. No-op when no proc is running.
+    pub fn trace(&self, event: &SceTraceEvent) {
+        if !self.is_traced() {
+            return;
+        }
+        let Some(proc) = self.proc_stack.last() else {
+            return;
+        };
+        let site = SceTraceSite {
+            sce_name: &self.sce_name,
+            proc_id: proc.proc_id,
+            proc_name: &proc.proc_name,
+            depth: self.proc_stack.len(),
+            offset: proc.last_command.0,
+        };
+        if let Some(options) = &self.options {
+            for hook in &options.proc_hooks {
+                hook.trace(&site, event);
+            }
+        }
+        if let Some(hooks) = &self.trace_hooks {
+            hooks.trace(&site, event);
+        }
     }
 
     fn proc_begin(&self, proc_id: u32, global_state: &mut GlobalState) {
@@ -0,0 +1,377 @@
+/*
+Ported from FFmpeg's Bink demuxer, libavformat/bink.c
+(https://ffmpeg.org)
+
+Copyright (c) 2008-2010 Peter Ross (pross@xvid.org)
+Copyright (c) 2009 Daniel Verkamp (daniel@drv.nu)
+
+FFmpeg is free software; you can redistribute it and/or
+modify it under the terms of the GNU Lesser General Public
+License as published by the Free Software Foundation; either
+version 2.1 of the License, or (at your option) any later version.
+
+FFmpeg is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+Lesser General Public License for more details.
+
+As section 3 of the LGPL 2.1 allows, this port is distributed under
+the GNU General Public License version 3, like the rest of OpenPAL3.
+*/
+
+//! Bink 1 (`.bik`) container.
+//!
+//! This parses the file header, the audio track table and the frame
+//! index, and splits each frame into its per-track audio packets and
+//! the video packet. The packets are decoded by [`super::bink_video`]
+//! and [`super::bink_audio`] when the `ffmpeg` feature is off.
+
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+pub const BINK_FLAG_ALPHA: u32 = 0x0010_0000;
+pub const BINK_FLAG_GRAY: u32 = 0x0002_0000;
+
+const BINK_AUD_16BITS: u16 = 0x4000;
+const BINK_AUD_STEREO: u16 = 0x2000;
+const BINK_AUD_USEDCT: u16 = 0x1000;
+
+const MAX_AUDIO_TRACKS: u32 = 256;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinkAudioTrack {
+    pub id: u32,
+    pub sample_rate: u16,
+    pub stereo: bool,
+    pub sixteen_bits: bool,
+    /// DCT-coded audio; RDFT otherwise.
+    pub use_dct: bool,
+}
+
+impl BinkAudioTrack {
+    pub fn channels(&self) -> u16 {
+        if self.stereo { 2 } else { 1 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BinkFrameEntry {
+    pub offset: u32,
+    pub size: u32,
+    pub keyframe: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct BinkHeader {
+    /// The `b`..`k` in `BIKb`..`BIKk`.
+    pub revision: u8,
+    pub width: u32,
+    pub height: u32,
+    pub fps_num: u32,
+    pub fps_den: u32,
+    pub video_flags: u32,
+    pub largest_frame_size: u32,
+    pub audio_tracks: Vec<BinkAudioTrack>,
+    pub frames: Vec<BinkFrameEntry>,
+}
+
+impl BinkHeader {
+    pub fn read(reader: &mut dyn Read) -> anyhow::Result<Self> {
+        let mut signature = [0u8; 4];
+        reader.read_exact(&mut signature)?;
+        if &signature[0..3] != b"BIK" {
+            anyhow::bail!("not a Bink 1 file: signature {:?}", signature);
+        }
+
+        let revision = signature[3];
+        let file_size = reader.read_u32::<LittleEndian>()? as u64 + 8;
+        let num_frames = reader.read_u32::<LittleEndian>()?;
+        let largest_frame_size = reader.read_u32::<LittleEndian>()?;
+        let _num_frames_again = reader.read_u32::<LittleEndian>()?;
+        let width = reader.read_u32::<LittleEndian>()?;
+        let height = reader.read_u32::<LittleEndian>()?;
+        let fps_num = reader.read_u32::<LittleEndian>()?;
+        let fps_den = reader.read_u32::<LittleEndian>()?;
+        let video_flags = reader.read_u32::<LittleEndian>()?;
+        let num_audio_tracks = reader.read_u32::<LittleEndian>()?;
+
+        if fps_num == 0 || fps_den == 0 {
+            anyhow::bail!("invalid frame rate {}/{}", fps_num, fps_den);
+        }
+
+        if num_audio_tracks > MAX_AUDIO_TRACKS {
+            anyhow::bail!("too many audio tracks: {}", num_audio_tracks);
+        }
+
+        let mut audio_tracks = vec![];
+        if num_audio_tracks > 0 {
+            if revision == b'k' {
+                let _unknown = reader.read_u32::<LittleEndian>()?;
+            }
+
+            for _ in 0..num_audio_tracks {
+                let _max_decoded_size = reader.read_u32::<LittleEndian>()?;
+            }
+
+            for _ in 0..num_audio_tracks {
+                let sample_rate = reader.read_u16::<LittleEndian>()?;
+                let flags = reader.read_u16::<LittleEndian>()?;
+                audio_tracks.push(BinkAudioTrack {
+                    id: 0,
+                    sample_rate,
+                    stereo: flags & BINK_AUD_STEREO != 0,
+                    sixteen_bits: flags & BINK_AUD_16BITS != 0,
+                    use_dct: flags & BINK_AUD_USEDCT != 0,
+                });
+            }
+
+            for track in &mut audio_tracks {
+                track.id = reader.read_u32::<LittleEndian>()?;
+            }
+        }
+
+        let mut frames = Vec::with_capacity(num_frames as usize);
+        let mut next = reader.read_u32::<LittleEndian>()?;
+        for i in 0..num_frames {
+            let keyframe = next & 1 != 0;
+            let offset = next & !1;
+            next = if i + 1 == num_frames {
+                u32::try_from(file_size)?
+            } else {
+                reader.read_u32::<LittleEndian>()?
+            };
+
+            let end = next & !1;
+            if end <= offset {
+                anyhow::bail!("frame {} has an invalid index entry", i);
+            }
+
+            frames.push(BinkFrameEntry {
+                offset,
+                size: end - offset,
+                keyframe,
+            });
+        }
+
+        Ok(Self {
+            revision,
+            width,
+            height,
+            fps_num,
+            fps_den,
+            video_flags,
+            largest_frame_size,
+            audio_tracks,
+            frames,
+        })
+    }
+
+    pub fn has_alpha(&self) -> bool {
+        self.video_flags & BINK_FLAG_ALPHA != 0
+    }
+
+    pub fn frame_duration_secs(&self) -> f64 {
+        self.fps_den as f64 / self.fps_num as f64
+    }
+}
+
+/// One demuxed frame.
+#[derive(Debug, Clone, Default)]
+pub struct BinkPacket {
+    /// One entry per audio track; `None` when the track has no audio in
+    /// this frame.
+    pub audio: Vec<Option<Vec<u8>>>,
+    pub video: Vec<u8>,
+}
+
+pub struct BinkDemuxer<R: Read + Seek> {
+    reader: R,
+    header: BinkHeader,
+}
+
+impl<R: Read + Seek> BinkDemuxer<R> {
+    pub fn new(mut reader: R) -> anyhow::Result<Self> {
+        reader.seek(SeekFrom::Start(0))?;
+        let header = BinkHeader::read(&mut reader)?;
+        Ok(Self { reader, header })
+    }
+
+    pub fn header(&self) -> &BinkHeader {
+        &self.header
+    }
+
+    pub fn read_packet(&mut self, frame: usize) -> anyhow::Result<BinkPacket> {
+        let Some(entry) = self.header.frames.get(frame).copied() else {
+            anyhow::bail!("frame {} out of range", frame);
+        };
+
+        self.reader.seek(SeekFrom::Start(entry.offset as u64))?;
+        let mut remaining = entry.size;
+        let mut audio = Vec::with_capacity(self.header.audio_tracks.len());
+        for track in 0..self.header.audio_tracks.len() {
+            let size = self.reader.read_u32::<LittleEndian>()?;
+            if remaining < 4 || size > remaining - 4 {
+                anyhow::bail!(
+                    "frame {}: audio packet of track {} overruns the frame",
+                    frame,
+                    track
+                );
+            }
+
+            remaining -= 4 + size;
+            let mut data = vec![0u8; size as usize];
+            self.reader.read_exact(&mut data)?;
+
+            // The first u32 of an audio packet is its decoded size; a
+            // shorter packet carries no samples.
+            audio.push(if size >= 4 { Some(data) } else { None });
+        }
+
+        let mut video = vec![0u8; remaining as usize];
+        self.reader.read_exact(&mut video)?;
+        Ok(BinkPacket { audio, video })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use byteorder::WriteBytesExt;
+
+    use super::*;
+
+    /// Build a `BIKi` file with one stereo track and the given
+    /// `(audio, video)` payloads per frame.
+    fn build(frames: &[(&[u8], &[u8])]) -> Vec<u8> {
+        let header_size = 44 + 4 + 4 + 4 + 4 * (frames.len() + 1);
+        let mut index = vec![];
+        let mut body = vec![];
+        for (i, (audio, video)) in frames.iter().enumerate() {
+            let offset = (header_size + body.len()) as u32;
+            index.push(if i == 0 { offset | 1 } else { offset });
+            body.write_u32::<LittleEndian>(audio.len() as u32).unwrap();
+            body.extend_from_slice(audio);
+            body.extend_from_slice(video);
+        }
+        let file_size = (header_size + body.len()) as u32;
+        index.push(file_size);
+
+        let mut out = b"BIKi".to_vec();
+        for v in [
+            file_size - 8,
+            frames.len() as u32,
+            64,
+            frames.len() as u32,
+            320,
+            240,
+            15,
+            1,
+            0,
+            1,
+            0x1000,
+        ] {
+            out.write_u32::<LittleEndian>(v).unwrap();
+        }
+        out.write_u16::<LittleEndian>(22050).unwrap();
+        out.write_u16::<LittleEndian>(BINK_AUD_STEREO | BINK_AUD_16BITS)
+            .unwrap();
+        out.write_u32::<LittleEndian>(7).unwrap();
+        for v in index {
+            out.write_u32::<LittleEndian>(v).unwrap();
+        }
+        assert_eq!(out.len(), header_size);
+        out.extend_from_slice(&body);
+        out
+    }
+
+    #[test]
+    fn header_and_index() {
+        let file = build(&[(&[4, 0, 0, 0, 9], &[1, 2, 3]), (&[], &[4, 5])]);
+        let demuxer = BinkDemuxer::new(Cursor::new(file)).unwrap();
+        let header = demuxer.header();
+
+        assert_eq!(header.revision, b'i');
+        assert_eq!((header.width, header.height), (320, 240));
+        assert_eq!(header.frame_duration_secs(), 1.0 / 15.0);
+        assert_eq!(
+            header.audio_tracks,
+            vec![BinkAudioTrack {
+                id: 7,
+                sample_rate: 22050,
+                stereo: true,
+                sixteen_bits: true,
+                use_dct: false,
+            }]
+        );
+        assert_eq!(header.frames.len(), 2);
+        assert!(header.frames[0].keyframe);
+        assert!(!header.frames[1].keyframe);
+        assert_eq!(header.frames[0].size, 4 + 5 + 3);
+        assert_eq!(header.frames[1].size, 4 + 2);
+    }
+
+    #[test]
+    fn packets_split_audio_and_video() {
+        let file = build(&[(&[4, 0, 0, 0, 9], &[1, 2, 3]), (&[1, 2], &[4, 5])]);
+        let mut demuxer = BinkDemuxer::new(Cursor::new(file)).unwrap();
+
+        let packet = demuxer.read_packet(0).unwrap();
+        assert_eq!(packet.audio, vec![Some(vec![4, 0, 0, 0, 9])]);
+        assert_eq!(packet.video, vec![1, 2, 3]);
+
+        let packet = demuxer.read_packet(1).unwrap();
+        assert_eq!(packet.audio, vec![None]);
+        assert_eq!(packet.video, vec![4, 5]);
+
+        assert!(demuxer.read_packet(2).is_err());
+    }
+
+    #[test]
+    fn rejects_broken_files() {
+        let mut file = build(&[(&[], &[1])]);
+        file[0] = b'K';
+        assert!(BinkDemuxer::new(Cursor::new(file)).is_err());
+
+        // Audio size pointing past the end of the frame.
+        let mut file = build(&[(&[], &[1])]);
+        let frame_offset = file.len() - 5;
+        file[frame_offset] = 0xff;
+        let mut demuxer = BinkDemuxer::new(Cursor::new(file)).unwrap();
+        assert!(demuxer.read_packet(0).is_err());
+    }
+
+    /// Demux every `.bik` under `BINK_MOVIE_DIR` (e.g. a PAL3 `movie`
+    /// folder) frame by frame.
+    #[test]
+    #[ignore = "requires BINK_MOVIE_DIR env var pointing to a folder of .bik movies"]
+    fn shipped_movies_demux() {
+        let dir = std::env::var("BINK_MOVIE_DIR").unwrap();
+        let mut count = 0;
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if !path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("bik"))
+            {
+                continue;
+            }
+
+            let file = std::fs::File::open(&path).unwrap();
+            let mut demuxer = BinkDemuxer::new(std::io::BufReader::new(file))
+                .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+            let header = demuxer.header().clone();
+            assert!(header.frames[0].keyframe, "{}", path.display());
+            for i in 0..header.frames.len() {
+                let packet = demuxer
+                    .read_packet(i)
+                    .unwrap_or_else(|e| panic!("{} frame {}: {}", path.display(), i, e));
+                assert_eq!(packet.audio.len(), header.audio_tracks.len());
+                assert!(!packet.video.is_empty(), "{} frame {}", path.display(), i);
+            }
+            count += 1;
+        }
+        assert!(count > 0, "no .bik files found");
+    }
+}
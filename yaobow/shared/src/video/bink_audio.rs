@@ -0,0 +1,489 @@
+/*
+Ported from FFmpeg's Bink audio decoder, libavcodec/binkaudio.c, with
+the critical band table of libavcodec/wma_freqs.c (https://ffmpeg.org)
+
+Copyright (c) 2007-2011 Peter Ross (pross@xvid.org)
+Copyright (c) 2009 Daniel Verkamp (daniel@drv.nu)
+
+FFmpeg is free software; you can redistribute it and/or
+modify it under the terms of the GNU Lesser General Public
+License as published by the Free Software Foundation; either
+version 2.1 of the License, or (at your option) any later version.
+
+FFmpeg is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+Lesser General Public License for more details.
+
+As section 3 of the LGPL 2.1 allows, this port is distributed under
+the GNU General Public License version 3, like the rest of OpenPAL3.
+*/
+
+//! Bink audio decoder.
+//!
+//! Decodes the audio packets of one track from
+//! [`super::bink::BinkDemuxer`] into interleaved `f32` samples, as
+//! FFmpeg's `binkaudio_rdft` / `binkaudio_dct` decoders do. Each packet
+//! holds whole transform blocks that overlap by 1/16 of their length.
+//!
+//! `sample_matches_pcm_md5` checks the output against the PCM hash of
+//! `testdata/sample.bik`, and `shipped_movies_match_ffmpeg` against
+//! `ffmpeg -f f32le` for the game movies.
+
+use std::f64::consts::PI;
+
+use super::bink::BinkAudioTrack;
+use super::bink_video::BitReader;
+
+/// Upper band edges in Hz, shared with WMA.
+const CRITICAL_FREQS: [u32; 25] = [
+    100, 200, 300, 400, 510, 630, 770, 920, 1080, 1270, 1480, 1720, 2000, 2320, 2700, 3150, 3700,
+    4400, 5300, 6400, 7700, 9500, 12000, 15500, 24500,
+];
+
+const RLE_LENGTHS: [usize; 16] = [2, 3, 4, 5, 6, 8, 9, 10, 11, 12, 13, 14, 15, 16, 32, 64];
+
+pub struct BinkAudioDecoder {
+    use_dct: bool,
+    version_b: bool,
+    /// Output channels.
+    channels: usize,
+    /// Channels coded separately: all of them for DCT, one interleaved
+    /// stream for RDFT.
+    coded_channels: usize,
+    sample_rate: u32,
+    frame_len: usize,
+    overlap_len: usize,
+    root: f32,
+    quant_table: [f32; 96],
+    bands: Vec<usize>,
+    previous: Vec<Vec<f32>>,
+    first: bool,
+}
+
+impl BinkAudioDecoder {
+    pub fn new(revision: u8, track: &BinkAudioTrack) -> anyhow::Result<Self> {
+        let channels = track.channels() as usize;
+        let sample_rate = track.sample_rate as u32;
+        if sample_rate == 0 {
+            anyhow::bail!("invalid Bink audio sample rate 0");
+        }
+
+        let mut frame_len_bits = if sample_rate < 22050 {
+            9
+        } else if sample_rate < 44100 {
+            10
+        } else {
+            11
+        };
+
+        let version_b = revision == b'b';
+        let (coded_channels, coded_rate) = if track.use_dct {
+            (channels, sample_rate)
+        } else {
+            // RDFT tracks code the interleaved samples as one channel.
+            if !version_b {
+                frame_len_bits += channels.ilog2();
+            }
+            (1, sample_rate * channels as u32)
+        };
+
+        let frame_len = 1usize << frame_len_bits;
+        let root = if track.use_dct {
+            frame_len as f64 / ((frame_len as f64).sqrt() * 32768.0)
+        } else {
+            2.0 / ((frame_len as f64).sqrt() * 32768.0)
+        } as f32;
+        let quant_table = std::array::from_fn(|i| (i as f32 * 0.152_891_65_f32).exp() * root);
+
+        let rate_half = (coded_rate as usize).div_ceil(2);
+        let mut num_bands = 1;
+        while num_bands < 25 && rate_half > CRITICAL_FREQS[num_bands - 1] as usize {
+            num_bands += 1;
+        }
+        let mut bands = vec![2];
+        for freq in &CRITICAL_FREQS[..num_bands - 1] {
+            bands.push((*freq as usize * frame_len / rate_half) & !1);
+        }
+        bands.push(frame_len);
+
+        let overlap_len = frame_len / 16;
+        Ok(Self {
+            use_dct: track.use_dct,
+            version_b,
+            channels,
+            coded_channels,
+            sample_rate,
+            frame_len,
+            overlap_len,
+            root,
+            quant_table,
+            bands,
+            previous: vec![vec![0.; overlap_len]; coded_channels],
+            first: true,
+        })
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Forget the overlap with the previous block, e.g. after seeking.
+    pub fn reset(&mut self) {
+        self.first = true;
+    }
+
+    /// Decode one packet (including its leading decoded-size field) and
+    /// append the interleaved samples to `out`. On error, `out` keeps
+    /// the blocks decoded before it.
+    pub fn decode(&mut self, packet: &[u8], out: &mut Vec<f32>) -> anyhow::Result<()> {
+        let mut gb = BitReader::new(packet);
+        gb.skip(32);
+        while gb.bits_left() > 0 {
+            let block = self.decode_block(&mut gb)?;
+            let len = self.frame_len - self.overlap_len;
+            if self.coded_channels == 1 {
+                out.extend_from_slice(&block[0][..len]);
+            } else {
+                for i in 0..len {
+                    out.extend(block.iter().map(|ch| ch[i]));
+                }
+            }
+            gb.align32();
+        }
+        Ok(())
+    }
+
+    fn read_float(&self, gb: &mut BitReader) -> f32 {
+        if self.version_b {
+            return f32::from_bits(gb.read(32));
+        }
+
+        let power = gb.read(5) as i32;
+        let f = gb.read(23) as f32 * 2f32.powi(power - 23);
+        if gb.read_bit() { -f } else { f }
+    }
+
+    fn decode_block(&mut self, gb: &mut BitReader) -> anyhow::Result<Vec<Vec<f32>>> {
+        let num_bands = self.bands.len() - 1;
+        if self.use_dct {
+            gb.skip(2);
+        }
+
+        let mut out = Vec::with_capacity(self.coded_channels);
+        for _ in 0..self.coded_channels {
+            let mut coeffs = vec![0f32; self.frame_len];
+            let float_bits = if self.version_b { 64 } else { 58 };
+            if gb.bits_left() < float_bits {
+                anyhow::bail!("out of data reading an audio block");
+            }
+            coeffs[0] = self.read_float(gb) * self.root;
+            coeffs[1] = self.read_float(gb) * self.root;
+
+            if gb.bits_left() < num_bands as isize * 8 {
+                anyhow::bail!("out of data reading audio band quantizers");
+            }
+            let quant: Vec<f32> = (0..num_bands)
+                .map(|_| self.quant_table[(gb.read(8) as usize).min(95)])
+                .collect();
+
+            let mut k = 0;
+            let mut q = quant[0];
+            let mut i = 2;
+            while i < self.frame_len {
+                let j = if self.version_b {
+                    i + 16
+                } else if gb.read_bit() {
+                    i + RLE_LENGTHS[gb.read(4) as usize] * 8
+                } else {
+                    i + 8
+                };
+                let j = j.min(self.frame_len);
+
+                let width = gb.read(4);
+                if width == 0 {
+                    coeffs[i..j].fill(0.);
+                    i = j;
+                    while self.bands[k] < i {
+                        q = quant[k];
+                        k += 1;
+                    }
+                } else {
+                    while i < j {
+                        if self.bands[k] == i {
+                            q = quant[k];
+                            k += 1;
+                        }
+                        let coeff = gb.read(width);
+                        coeffs[i] = if coeff == 0 {
+                            0.
+                        } else if gb.read_bit() {
+                            -q * coeff as f32
+                        } else {
+                            q * coeff as f32
+                        };
+                        i += 1;
+                    }
+                }
+            }
+
+            out.push(if self.use_dct {
+                coeffs[0] *= 2.;
+                inverse_dct(&coeffs)
+            } else {
+                inverse_rdft(&coeffs)
+            });
+        }
+
+        let count = (self.overlap_len * self.coded_channels) as f32;
+        for (ch, samples) in out.iter_mut().enumerate() {
+            if !self.first {
+                let previous = &self.previous[ch];
+                for (i, (sample, prev)) in samples.iter_mut().zip(previous).enumerate() {
+                    let j = (ch + i * self.coded_channels) as f32;
+                    *sample = (prev * (count - j) + *sample * j) / count;
+                }
+            }
+            self.previous[ch].copy_from_slice(&samples[self.frame_len - self.overlap_len..]);
+        }
+        self.first = false;
+
+        Ok(out)
+    }
+}
+
+/// In-place radix-2 FFT computing `sum(x[k] * e^(2 pi i k n / N))`.
+fn inverse_fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = 2. * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_im, w_re) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Real inverse DFT of packed coefficients: `c[0]` and `c[1]` are the
+/// DC and Nyquist terms, then `c[2k] + i c[2k+1]` with the sine terms
+/// added rather than subtracted.
+fn inverse_rdft(c: &[f32]) -> Vec<f32> {
+    let n = c.len();
+    let mut re = vec![0.; n];
+    let mut im = vec![0.; n];
+    re[0] = c[0] as f64 * 0.5;
+    re[n / 2] = c[1] as f64 * 0.5;
+    for k in 1..n / 2 {
+        re[k] = c[2 * k] as f64;
+        im[k] = -c[2 * k + 1] as f64;
+    }
+    inverse_fft(&mut re, &mut im);
+    re.into_iter().map(|v| v as f32).collect()
+}
+
+/// DCT-III scaled by `2 / N`, with the DC term halved.
+fn inverse_dct(c: &[f32]) -> Vec<f32> {
+    let n = c.len();
+    let mut re = vec![0.; 2 * n];
+    let mut im = vec![0.; 2 * n];
+    for (k, v) in c.iter().enumerate() {
+        let v = if k == 0 { *v as f64 } else { *v as f64 * 2. };
+        let (s, co) = (PI * k as f64 / (2 * n) as f64).sin_cos();
+        re[k] = v * co;
+        im[k] = v * s;
+    }
+    inverse_fft(&mut re, &mut im);
+    re[..n].iter().map(|v| (v / n as f64) as f32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Cursor};
+
+    use super::super::bink::BinkDemuxer;
+    use super::super::bink_video::BitWriter;
+    use super::*;
+
+    fn track(sample_rate: u16, stereo: bool, use_dct: bool) -> BinkAudioTrack {
+        BinkAudioTrack {
+            id: 0,
+            sample_rate,
+            stereo,
+            sixteen_bits: true,
+            use_dct,
+        }
+    }
+
+    #[test]
+    fn transforms_match_direct_sums() {
+        let n = 32;
+        let c: Vec<f32> = (0..n).map(|i| ((i * 37 % 11) as f32 - 5.) / 7.).collect();
+
+        let rdft = inverse_rdft(&c);
+        for (m, v) in rdft.iter().enumerate() {
+            let mut x = 0.5 * (c[0] as f64 + if m % 2 == 0 { 1. } else { -1. } * c[1] as f64);
+            for k in 1..n / 2 {
+                let t = 2. * PI * (k * m) as f64 / n as f64;
+                x += c[2 * k] as f64 * t.cos() + c[2 * k + 1] as f64 * t.sin();
+            }
+            assert!((*v as f64 - x).abs() < 1e-5, "rdft[{}]: {} != {}", m, v, x);
+        }
+
+        let dct = inverse_dct(&c);
+        for (m, v) in dct.iter().enumerate() {
+            let mut x = 0.5 * c[0] as f64;
+            for (k, ck) in c.iter().enumerate().skip(1) {
+                x += *ck as f64 * (PI * k as f64 * (m as f64 + 0.5) / n as f64).cos();
+            }
+            x *= 2. / n as f64;
+            assert!((*v as f64 - x).abs() < 1e-5, "dct[{}]: {} != {}", m, v, x);
+        }
+    }
+
+    #[test]
+    fn band_layout() {
+        let decoder = BinkAudioDecoder::new(b'i', &track(22050, true, false)).unwrap();
+        assert_eq!(decoder.frame_len, 2048);
+        assert_eq!(decoder.overlap_len, 128);
+        assert_eq!(decoder.bands.len(), 26);
+        assert_eq!(decoder.bands[..4], [2, 8, 18, 26]);
+        assert_eq!(*decoder.bands.last().unwrap(), 2048);
+
+        let decoder = BinkAudioDecoder::new(b'i', &track(44100, false, true)).unwrap();
+        assert_eq!(decoder.frame_len, 2048);
+        assert_eq!(decoder.coded_channels, 1);
+        assert_eq!(decoder.bands.len(), 26);
+    }
+
+    #[test]
+    fn silent_block() {
+        let mut decoder = BinkAudioDecoder::new(b'i', &track(22050, true, false)).unwrap();
+        let mut w = BitWriter::default();
+        w.write(32, 0);
+        // DC and Nyquist: power, mantissa, sign.
+        for _ in 0..2 {
+            w.write(29, 0);
+        }
+        for _ in 1..decoder.bands.len() {
+            w.write(8, 0);
+        }
+        // Every run of 64 * 8 coefficients is zero.
+        for _ in 0..4 {
+            w.write(1, 1);
+            w.write(4, 15);
+            w.write(4, 0);
+        }
+        w.align32();
+
+        let mut out = vec![];
+        decoder.decode(&w.finish(), &mut out).unwrap();
+        assert_eq!(out, vec![0.; 2048 - 128]);
+    }
+
+    /// The first audio track of `testdata/sample.bik` holds one DC-only
+    /// block per frame; see `make_sample.py`.
+    #[test]
+    fn sample_matches_pcm_md5() {
+        let mut demuxer =
+            BinkDemuxer::new(Cursor::new(include_bytes!("testdata/sample.bik"))).unwrap();
+        let header = demuxer.header().clone();
+        let mut decoder = BinkAudioDecoder::new(header.revision, &header.audio_tracks[0]).unwrap();
+        let mut samples = vec![];
+        for i in 0..header.frames.len() {
+            let audio = demuxer.read_packet(i).unwrap().audio[0].clone().unwrap();
+            decoder.decode(&audio, &mut samples).unwrap();
+        }
+
+        let mut context = md5::Context::new();
+        for sample in &samples {
+            context.consume(sample.to_le_bytes());
+        }
+        assert_eq!(
+            format!("MD5={:x}", context.compute()),
+            include_str!("testdata/sample.f32le.md5").trim()
+        );
+    }
+
+    /// Decode the first audio track of every `.bik` under
+    /// `BINK_MOVIE_DIR` that has a `<name>.f32` next to it, made with
+    /// `ffmpeg -i <name>.bik -map 0:a:0 -f f32le <name>.f32`.
+    #[test]
+    #[ignore = "requires BINK_MOVIE_DIR env var pointing to .bik movies with FFmpeg f32le dumps"]
+    fn shipped_movies_match_ffmpeg() {
+        let dir = std::env::var("BINK_MOVIE_DIR").unwrap();
+        let mut count = 0;
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let Ok(reference) = std::fs::read(path.with_extension("f32")) else {
+                continue;
+            };
+            if !path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("bik"))
+            {
+                continue;
+            }
+
+            let reference: Vec<f32> = reference
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect();
+
+            let file = std::fs::File::open(&path).unwrap();
+            let mut demuxer = BinkDemuxer::new(BufReader::new(file)).unwrap();
+            let header = demuxer.header().clone();
+            let mut decoder =
+                BinkAudioDecoder::new(header.revision, &header.audio_tracks[0]).unwrap();
+            let mut samples = vec![];
+            for i in 0..header.frames.len() {
+                if let Some(audio) = &demuxer.read_packet(i).unwrap().audio[0] {
+                    decoder
+                        .decode(audio, &mut samples)
+                        .unwrap_or_else(|e| panic!("{} frame {}: {}", path.display(), i, e));
+                }
+            }
+
+            assert_eq!(samples.len(), reference.len(), "{}", path.display());
+            for (i, (a, b)) in samples.iter().zip(&reference).enumerate() {
+                assert!(
+                    (a - b).abs() < 1e-4,
+                    "{} sample {}: {} != {}",
+                    path.display(),
+                    i,
+                    a,
+                    b
+                );
+            }
+            count += 1;
+        }
+        assert!(count > 0, "no .bik files with .f32 references found");
+    }
+}
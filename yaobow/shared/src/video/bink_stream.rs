@@ -0,0 +1,517 @@
+//! `VideoStream` over the native Bink decoders, used for `.bik` movies
+//! when the `ffmpeg` feature is off.
+//!
+//! Everything runs on the caller's thread: `get_texture` decodes the
+//! video frames that are due by the wall clock, and demuxes up to
+//! [`PACKET_LEAD_SECS`] ahead so the audio source always has samples
+//! queued.
+
+use std::{
+    collections::VecDeque,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use imgui::TextureId;
+use log::{error, warn};
+use radiance::{
+    audio::{AudioCustomDecoderSource, AudioEngine, Decoder, Samples},
+    rendering::{ComponentFactory, Texture},
+    utils::SeekRead,
+    video::{VideoStream, VideoStreamState},
+};
+
+use super::{
+    bink::{BinkDemuxer, BinkHeader},
+    bink_audio::BinkAudioDecoder,
+    bink_video::{BinkFrame, BinkVideoDecoder},
+};
+
+/// How far ahead of the displayed frame packets are demuxed.
+const PACKET_LEAD_SECS: f64 = 1.0;
+
+/// Samples handed out when the queue runs dry, like the FFmpeg stream.
+const SILENCE_SAMPLES: usize = 100;
+
+type SampleQueue = Arc<Mutex<VecDeque<Samples>>>;
+
+struct BinkAudioOutput {
+    queue: SampleQueue,
+    channels: usize,
+    sample_rate: i32,
+}
+
+impl Decoder for BinkAudioOutput {
+    fn fetch_samples(&mut self) -> anyhow::Result<Option<Samples>> {
+        let samples = self.queue.lock().unwrap().pop_front();
+        Ok(Some(samples.unwrap_or_else(|| Samples {
+            data: vec![0; SILENCE_SAMPLES * self.channels],
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+        })))
+    }
+
+    fn reset(&mut self) {}
+}
+
+struct AudioTrack {
+    decoder: BinkAudioDecoder,
+    queue: SampleQueue,
+    source: Box<dyn AudioCustomDecoderSource>,
+}
+
+struct Playback {
+    demuxer: BinkDemuxer<Box<dyn SeekRead>>,
+    header: BinkHeader,
+    video: BinkVideoDecoder,
+    audio: Option<AudioTrack>,
+    /// Demuxed video packets, starting at `next_frame`.
+    packets: VecDeque<Vec<u8>>,
+    /// Whether a frame was decoded since the last texture upload.
+    fresh: bool,
+    /// Frames counted from the clock origin, across loops.
+    next_frame: u64,
+    next_packet: u64,
+    start_time: Instant,
+    paused: Option<Instant>,
+}
+
+impl Playback {
+    fn new(reader: Box<dyn SeekRead>, audio_engine: &dyn AudioEngine) -> anyhow::Result<Self> {
+        let demuxer = BinkDemuxer::new(reader)?;
+        let header = demuxer.header().clone();
+        if header.frames.is_empty() || header.fps_num == 0 || header.fps_den == 0 {
+            anyhow::bail!("Bink movie has no frames or no frame rate");
+        }
+
+        let video = BinkVideoDecoder::new(
+            header.revision,
+            header.width,
+            header.height,
+            header.has_alpha(),
+        )?;
+
+        let audio = match header.audio_tracks.first() {
+            Some(track) => {
+                let decoder = BinkAudioDecoder::new(header.revision, track)?;
+                let queue = SampleQueue::default();
+                let mut source = audio_engine.create_custom_decoder_source();
+                source.set_decoder(Box::new(BinkAudioOutput {
+                    queue: queue.clone(),
+                    channels: decoder.channels(),
+                    sample_rate: decoder.sample_rate() as i32,
+                }));
+                Some(AudioTrack {
+                    decoder,
+                    queue,
+                    source,
+                })
+            }
+            None => None,
+        };
+
+        let now = Instant::now();
+        Ok(Self {
+            demuxer,
+            header,
+            video,
+            audio,
+            packets: VecDeque::new(),
+            fresh: false,
+            next_frame: 0,
+            next_packet: 0,
+            start_time: now,
+            paused: Some(now),
+        })
+    }
+
+    fn frame_count(&self) -> u64 {
+        self.header.frames.len() as u64
+    }
+
+    fn duration_ms(&self) -> i64 {
+        (self.frame_count() * self.header.fps_den as u64 * 1000 / self.header.fps_num as u64) as i64
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.paused
+            .unwrap_or_else(Instant::now)
+            .saturating_duration_since(self.start_time)
+    }
+
+    /// Frames counted from the clock origin that should be shown by now.
+    fn due_frames(&self) -> u64 {
+        (self.elapsed().as_secs_f64() * self.header.fps_num as f64 / self.header.fps_den as f64)
+            as u64
+    }
+
+    fn pause(&mut self) {
+        if self.paused.is_none() {
+            self.paused = Some(Instant::now());
+        }
+        if let Some(audio) = self.audio.as_mut() {
+            audio.source.pause();
+        }
+    }
+
+    fn play(&mut self) {
+        if let Some(paused) = self.paused.take() {
+            self.start_time += Instant::now() - paused;
+        }
+        if let Some(audio) = self.audio.as_mut() {
+            audio.source.play(false);
+        }
+    }
+
+    /// Read the packet of `next_packet`, queue its video and decode its
+    /// audio.
+    fn demux_next(&mut self) -> anyhow::Result<()> {
+        let frame = (self.next_packet % self.frame_count()) as usize;
+        let packet = self.demuxer.read_packet(frame)?;
+        if let (Some(audio), Some(Some(data))) = (self.audio.as_mut(), packet.audio.first()) {
+            let mut samples = vec![];
+            if let Err(e) = audio.decoder.decode(data, &mut samples) {
+                warn!("Bink frame {}: bad audio packet: {}", frame, e);
+            }
+            if !samples.is_empty() {
+                audio.queue.lock().unwrap().push_back(Samples {
+                    data: samples
+                        .into_iter()
+                        .map(|v| (v * 32768.).clamp(-32768., 32767.) as i16)
+                        .collect(),
+                    channels: audio.decoder.channels(),
+                    sample_rate: audio.decoder.sample_rate() as i32,
+                });
+            }
+        }
+
+        self.packets.push_back(packet.video);
+        self.next_packet += 1;
+        Ok(())
+    }
+
+    fn decode_video(&mut self, packet: &[u8]) -> anyhow::Result<()> {
+        self.video.decode(packet)?;
+        self.fresh = true;
+        Ok(())
+    }
+
+    /// Decode frames up to the clock. Returns whether the movie has
+    /// ended.
+    fn advance(&mut self, looping: bool) -> anyhow::Result<bool> {
+        let count = self.frame_count();
+        let due = self.due_frames();
+        while self.next_frame <= due {
+            if self.next_frame > 0 && self.next_frame.is_multiple_of(count) && !looping {
+                if let Some(audio) = self.audio.as_ref() {
+                    audio.queue.lock().unwrap().clear();
+                }
+                return Ok(true);
+            }
+
+            if self.packets.is_empty() {
+                self.demux_next()?;
+            }
+            let packet = self.packets.pop_front().unwrap();
+            self.decode_video(&packet)?;
+            self.next_frame += 1;
+        }
+
+        let lead =
+            (PACKET_LEAD_SECS * self.header.fps_num as f64 / self.header.fps_den as f64) as u64;
+        let end = if looping {
+            u64::MAX
+        } else {
+            self.next_frame.max(1).div_ceil(count) * count
+        };
+        while self.next_packet < (due + lead).min(end) {
+            self.demux_next()?;
+        }
+
+        if let Some(audio) = self.audio.as_mut() {
+            audio.source.update();
+        }
+        Ok(false)
+    }
+
+    /// Show the frame at `ms`: decode from the keyframe before it and
+    /// restart the clock and the audio there.
+    fn seek(&mut self, ms: i64) -> anyhow::Result<()> {
+        let target = ((ms.max(0) as u64 * self.header.fps_num as u64)
+            / (1000 * self.header.fps_den as u64))
+            .min(self.frame_count() - 1);
+        let keyframe = (0..=target)
+            .rev()
+            .find(|i| self.header.frames[*i as usize].keyframe)
+            .unwrap_or(0);
+
+        self.packets.clear();
+        if let Some(audio) = self.audio.as_mut() {
+            audio.queue.lock().unwrap().clear();
+            audio.decoder.reset();
+        }
+
+        for frame in keyframe..target {
+            let packet = self.demuxer.read_packet(frame as usize)?;
+            self.decode_video(&packet.video)?;
+        }
+        self.next_packet = target;
+        self.demux_next()?;
+        let packet = self.packets.pop_front().unwrap();
+        self.decode_video(&packet)?;
+        self.next_frame = target + 1;
+
+        let now = Instant::now();
+        let elapsed = Duration::from_secs_f64(
+            target as f64 * self.header.fps_den as f64 / self.header.fps_num as f64,
+        );
+        self.start_time = now.checked_sub(elapsed).unwrap_or(now);
+        if self.paused.is_some() {
+            self.paused = Some(now);
+        }
+        Ok(())
+    }
+}
+
+pub struct VideoStreamBink {
+    reader: Option<Box<dyn SeekRead>>,
+    factory: Rc<dyn ComponentFactory>,
+    audio_engine: Rc<dyn AudioEngine>,
+    state: VideoStreamState,
+    looping: bool,
+    playback: Option<Playback>,
+    rgba: Vec<u8>,
+    current_texture: Option<Box<dyn Texture>>,
+}
+
+impl VideoStreamBink {
+    pub fn new(factory: Rc<dyn ComponentFactory>, audio_engine: Rc<dyn AudioEngine>) -> Self {
+        Self {
+            reader: None,
+            factory,
+            audio_engine,
+            state: VideoStreamState::Stopped,
+            looping: false,
+            playback: None,
+            rgba: vec![],
+            current_texture: None,
+        }
+    }
+
+    pub fn create(
+        factory: Rc<dyn ComponentFactory>,
+        audio_engine: Rc<dyn AudioEngine>,
+    ) -> Box<dyn VideoStream> {
+        Box::new(Self::new(factory, audio_engine))
+    }
+
+    fn fail(&mut self, e: anyhow::Error) {
+        error!("Bink playback failed: {}", e);
+        self.state = VideoStreamState::Stopped;
+        if let Some(mut audio) = self.playback.take().and_then(|p| p.audio) {
+            audio.source.stop();
+        }
+    }
+}
+
+impl VideoStream for VideoStreamBink {
+    fn set_reader(&mut self, reader: Box<dyn SeekRead>) {
+        self.reader = Some(reader);
+    }
+
+    fn play(&mut self, looping: bool) -> (u32, u32) {
+        self.looping = looping;
+        let Some(reader) = self.reader.take() else {
+            return (0, 0);
+        };
+
+        let mut playback = match Playback::new(reader, self.audio_engine.as_ref()) {
+            Ok(playback) => playback,
+            Err(e) => {
+                self.fail(e);
+                return (0, 0);
+            }
+        };
+        playback.play();
+        let size = (playback.header.width, playback.header.height);
+        self.playback = Some(playback);
+        self.state = VideoStreamState::Playing;
+        size
+    }
+
+    fn stop(&mut self) {
+        self.state = VideoStreamState::Stopped;
+        if let Some(playback) = self.playback.as_mut() {
+            playback.pause();
+            if let Some(audio) = playback.audio.as_mut() {
+                audio.source.stop();
+            }
+        }
+    }
+
+    fn pause(&mut self) {
+        self.state = VideoStreamState::Paused;
+        if let Some(playback) = self.playback.as_mut() {
+            playback.pause();
+        }
+    }
+
+    fn resume(&mut self) {
+        if self.state == VideoStreamState::Paused {
+            self.state = VideoStreamState::Playing;
+            if let Some(playback) = self.playback.as_mut() {
+                playback.play();
+            }
+        }
+    }
+
+    fn duration_ms(&self) -> i64 {
+        self.playback.as_ref().map(|p| p.duration_ms()).unwrap_or(0)
+    }
+
+    fn position_ms(&self) -> i64 {
+        let Some(playback) = self.playback.as_ref() else {
+            return 0;
+        };
+        let duration = playback.duration_ms();
+        if duration <= 0 {
+            return 0;
+        }
+        playback.elapsed().as_millis() as i64 % duration
+    }
+
+    fn looping(&self) -> bool {
+        self.looping
+    }
+
+    fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    fn seek_ms(&mut self, ms: i64) {
+        let Some(playback) = self.playback.as_mut() else {
+            return;
+        };
+        let ms = ms.clamp(0, playback.duration_ms());
+        if let Err(e) = playback.seek(ms) {
+            self.fail(e);
+        }
+    }
+
+    fn restart(&mut self) {
+        self.seek_ms(0);
+        if self.state == VideoStreamState::Paused {
+            self.resume();
+        } else if self.state == VideoStreamState::Stopped && self.playback.is_some() {
+            self.state = VideoStreamState::Playing;
+            self.playback.as_mut().unwrap().play();
+        }
+    }
+
+    fn get_texture(&mut self, texture_id: Option<TextureId>) -> Option<TextureId> {
+        let looping = self.looping;
+        let playback = self.playback.as_mut()?;
+        if self.state == VideoStreamState::Playing {
+            match playback.advance(looping) {
+                Ok(false) => {}
+                Ok(true) => {
+                    self.state = VideoStreamState::Stopped;
+                    playback.pause();
+                }
+                Err(e) => {
+                    self.fail(e);
+                    return None;
+                }
+            }
+        }
+
+        let playback = self.playback.as_mut()?;
+        if std::mem::take(&mut playback.fresh) {
+            frame_to_rgba(playback.video.frame(), &mut self.rgba);
+        }
+        if self.rgba.is_empty() {
+            return None;
+        }
+
+        let (w, h) = (playback.header.width, playback.header.height);
+        let (texture, texture_id) = self
+            .factory
+            .create_imgui_texture(&self.rgba, w, w, h, texture_id);
+
+        // should keep current texture valid until
+        self.current_texture = Some(texture);
+
+        Some(texture_id)
+    }
+
+    fn get_state(&self) -> VideoStreamState {
+        self.state
+    }
+}
+
+/// Convert BT.601 limited range YUV 4:2:0 (plus alpha) to RGBA.
+fn frame_to_rgba(frame: &BinkFrame, rgba: &mut Vec<u8>) {
+    let [y, u, v] = [&frame.planes[0], &frame.planes[1], &frame.planes[2]];
+    let alpha = frame.planes.get(3);
+    rgba.resize(y.width * y.height * 4, 0);
+
+    let clamp = |v: i32| (v >> 8).clamp(0, 255) as u8;
+    for row in 0..y.height {
+        for col in 0..y.width {
+            let c = 298 * (y.data[row * y.stride + col] as i32 - 16) + 128;
+            let chroma = (row >> 1) * u.stride + (col >> 1);
+            let d = u.data[chroma] as i32 - 128;
+            let e = v.data[chroma] as i32 - 128;
+
+            let out = &mut rgba[(row * y.width + col) * 4..][..4];
+            out[0] = clamp(c + 409 * e);
+            out[1] = clamp(c - 100 * d - 208 * e);
+            out[2] = clamp(c + 516 * d);
+            out[3] = alpha.map_or(255, |a| a.data[row * a.stride + col]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::bink_video::BinkPlane;
+    use super::*;
+
+    fn plane(width: usize, height: usize, value: u8) -> BinkPlane {
+        BinkPlane {
+            data: vec![value; width * height],
+            stride: width,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn yuv_to_rgba() {
+        let mut rgba = vec![];
+        let frame = BinkFrame {
+            planes: vec![plane(2, 2, 235), plane(1, 1, 128), plane(1, 1, 128)],
+        };
+        frame_to_rgba(&frame, &mut rgba);
+        assert_eq!(rgba, [255; 16]);
+
+        let frame = BinkFrame {
+            planes: vec![
+                plane(2, 2, 16),
+                plane(1, 1, 128),
+                plane(1, 1, 128),
+                plane(2, 2, 7),
+            ],
+        };
+        frame_to_rgba(&frame, &mut rgba);
+        assert_eq!(rgba, [0, 0, 0, 7].repeat(4));
+
+        // Pure red in BT.601.
+        let frame = BinkFrame {
+            planes: vec![plane(2, 2, 81), plane(1, 1, 90), plane(1, 1, 240)],
+        };
+        frame_to_rgba(&frame, &mut rgba);
+        assert_eq!(&rgba[..4], &[255, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,1720 @@
+/*
+Ported from FFmpeg's Bink video decoder, libavcodec/bink.c and
+libavcodec/binkdsp.c (https://ffmpeg.org)
+
+Copyright (c) 2009 Konstantin Shishkov
+Copyright (C) 2011 Peter Ross <pross@xvid.org>
+
+FFmpeg is free software; you can redistribute it and/or
+modify it under the terms of the GNU Lesser General Public
+License as published by the Free Software Foundation; either
+version 2.1 of the License, or (at your option) any later version.
+
+FFmpeg is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+Lesser General Public License for more details.
+
+As section 3 of the LGPL 2.1 allows, this port is distributed under
+the GNU General Public License version 3, like the rest of OpenPAL3.
+*/
+
+//! Bink 1 video decoder.
+//!
+//! Decodes the video packets produced by [`super::bink::BinkDemuxer`]
+//! into YUV 4:2:0 planes (plus alpha for movies with
+//! [`super::bink::BINK_FLAG_ALPHA`]), following the bitstream as
+//! FFmpeg's `bink` decoder reads it for revisions `c` to `k`. The first
+//! revision, `BIKb`, uses a different block coding and is rejected.
+//!
+//! `sample_matches_framemd5` checks the output frame by frame against
+//! the `framemd5` of `testdata/sample.bik`, and
+//! `shipped_movies_match_ffmpeg` against `ffmpeg -f framemd5` for the
+//! game movies.
+
+use std::sync::OnceLock;
+
+const SRC_BLOCK_TYPES: usize = 0;
+const SRC_SUB_BLOCK_TYPES: usize = 1;
+const SRC_COLORS: usize = 2;
+const SRC_PATTERN: usize = 3;
+const SRC_X_OFF: usize = 4;
+const SRC_Y_OFF: usize = 5;
+const SRC_INTRA_DC: usize = 6;
+const SRC_INTER_DC: usize = 7;
+const SRC_RUN: usize = 8;
+const NB_SRC: usize = 9;
+
+const SKIP_BLOCK: i32 = 0;
+const SCALED_BLOCK: i32 = 1;
+const MOTION_BLOCK: i32 = 2;
+const RUN_BLOCK: i32 = 3;
+const RESIDUE_BLOCK: i32 = 4;
+const INTRA_BLOCK: i32 = 5;
+const FILL_BLOCK: i32 = 6;
+const INTER_BLOCK: i32 = 7;
+const PATTERN_BLOCK: i32 = 8;
+const RAW_BLOCK: i32 = 9;
+
+/// Bits of the first DC value in a DC bundle.
+const DC_START_BITS: u32 = 11;
+
+/// Run lengths of the block type escape codes 12..15.
+const RLE_LENS: [usize; 4] = [4, 8, 12, 32];
+
+/// Little-endian bit reader: bits are taken from the low end of each
+/// byte first. Reading past the end yields zeros.
+pub(super) struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn bits_left(&self) -> isize {
+        (self.data.len() * 8) as isize - self.pos as isize
+    }
+
+    fn peek(&self, n: u32) -> u32 {
+        debug_assert!(n <= 32);
+        if n == 0 {
+            return 0;
+        }
+
+        let byte = self.pos >> 3;
+        let mut window = 0u64;
+        for i in 0..8 {
+            if let Some(b) = self.data.get(byte + i) {
+                window |= (*b as u64) << (8 * i);
+            }
+        }
+
+        ((window >> (self.pos & 7)) & ((1u64 << n) - 1)) as u32
+    }
+
+    pub fn read(&mut self, n: u32) -> u32 {
+        let v = self.peek(n);
+        self.pos += n as usize;
+        v
+    }
+
+    pub fn read_bit(&mut self) -> bool {
+        self.read(1) != 0
+    }
+
+    pub fn skip(&mut self, n: usize) {
+        self.pos += n;
+    }
+
+    pub fn align32(&mut self) {
+        self.pos = (self.pos + 31) & !31;
+    }
+}
+
+/// Bit writer matching [`BitReader`], for building test bitstreams.
+#[cfg(test)]
+#[derive(Default)]
+pub(super) struct BitWriter {
+    data: Vec<u8>,
+    pos: usize,
+}
+
+#[cfg(test)]
+impl BitWriter {
+    pub fn write(&mut self, n: u32, v: u32) {
+        for i in 0..n {
+            if self.pos & 7 == 0 {
+                self.data.push(0);
+            }
+            self.data[self.pos >> 3] |= (((v >> i) & 1) as u8) << (self.pos & 7);
+            self.pos += 1;
+        }
+    }
+
+    pub fn align32(&mut self) {
+        while self.pos & 31 != 0 {
+            self.write(1, 0);
+        }
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// One of the 16 fixed Huffman codes plus the leaf-to-symbol mapping
+/// sent in the bitstream.
+#[derive(Clone, Copy)]
+struct Tree {
+    vlc: usize,
+    syms: [u8; 16],
+}
+
+impl Default for Tree {
+    fn default() -> Self {
+        Self {
+            vlc: 0,
+            syms: std::array::from_fn(|i| i as u8),
+        }
+    }
+}
+
+impl Tree {
+    fn read(gb: &mut BitReader) -> anyhow::Result<Self> {
+        if gb.bits_left() < 4 {
+            anyhow::bail!("out of data reading a Huffman tree");
+        }
+
+        let mut tree = Tree {
+            vlc: gb.read(4) as usize,
+            ..Default::default()
+        };
+        if tree.vlc == 0 {
+            return Ok(tree);
+        }
+
+        if gb.read_bit() {
+            let mut seen = [false; 16];
+            let mut len = gb.read(3) as usize;
+            for i in 0..=len {
+                tree.syms[i] = gb.read(4) as u8;
+                seen[tree.syms[i] as usize] = true;
+            }
+            for (sym, seen) in seen.iter().enumerate() {
+                if len >= 15 {
+                    break;
+                }
+                if !seen {
+                    len += 1;
+                    tree.syms[len] = sym as u8;
+                }
+            }
+        } else {
+            let depth = gb.read(2);
+            let mut input: [u8; 16] = std::array::from_fn(|i| i as u8);
+            let mut output = [0u8; 16];
+            for i in 0..=depth {
+                let size = 1 << i;
+                for t in (0..16).step_by(size << 1) {
+                    merge(
+                        gb,
+                        &mut output[t..t + 2 * size],
+                        &input[t..t + 2 * size],
+                        size,
+                    );
+                }
+                std::mem::swap(&mut input, &mut output);
+            }
+            tree.syms = input;
+        }
+
+        Ok(tree)
+    }
+
+    fn decode(&self, gb: &mut BitReader) -> i32 {
+        let (index, len) = huffman_tables()[self.vlc][gb.peek(HUFF_PEEK_BITS) as usize];
+        gb.skip(len as usize);
+        self.syms[index as usize] as i32
+    }
+}
+
+/// Merge the two sorted halves of `src` into `dst`, one bit per pick.
+fn merge(gb: &mut BitReader, dst: &mut [u8], src: &[u8], size: usize) {
+    let (mut a, mut b) = (0, size);
+    let (mut left_a, mut left_b) = (size, size);
+    let mut out = 0;
+    while left_a > 0 && left_b > 0 {
+        if !gb.read_bit() {
+            dst[out] = src[a];
+            a += 1;
+            left_a -= 1;
+        } else {
+            dst[out] = src[b];
+            b += 1;
+            left_b -= 1;
+        }
+        out += 1;
+    }
+    for _ in 0..left_a {
+        dst[out] = src[a];
+        a += 1;
+        out += 1;
+    }
+    for _ in 0..left_b {
+        dst[out] = src[b];
+        b += 1;
+        out += 1;
+    }
+}
+
+const HUFF_PEEK_BITS: u32 = 7;
+
+/// `(leaf, length)` for every 7-bit lookahead of every tree.
+fn huffman_tables() -> &'static [Vec<(u8, u8)>; 16] {
+    static TABLES: OnceLock<[Vec<(u8, u8)>; 16]> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        std::array::from_fn(|tree| {
+            let mut table = vec![(0u8, 0u8); 1 << HUFF_PEEK_BITS];
+            for leaf in 0..16 {
+                let code = BINK_TREE_BITS[tree][leaf] as usize;
+                let len = BINK_TREE_LENS[tree][leaf];
+                for high in 0..1usize << (HUFF_PEEK_BITS - len as u32) {
+                    table[code | (high << len)] = (leaf as u8, len);
+                }
+            }
+            table
+        })
+    })
+}
+
+/// A stream of symbols of one kind for a plane, decoded a block row
+/// at a time.
+#[derive(Default)]
+struct Bundle {
+    len: u32,
+    tree: Tree,
+    data: Vec<i16>,
+    /// Where the next decoded chunk goes; `None` once the bundle said
+    /// it has nothing more for this plane.
+    cur_dec: Option<usize>,
+    cur_ptr: usize,
+}
+
+impl Bundle {
+    /// The length of the next chunk, or `None` if nothing needs to be
+    /// decoded for this row.
+    fn chunk_len(&mut self, gb: &mut BitReader) -> Option<usize> {
+        let cur_dec = self.cur_dec?;
+        if cur_dec > self.cur_ptr {
+            return None;
+        }
+
+        let t = gb.read(self.len) as usize;
+        if t == 0 {
+            self.cur_dec = None;
+            return None;
+        }
+
+        Some(t)
+    }
+
+    fn chunk_end(&self, t: usize) -> anyhow::Result<usize> {
+        let end = self.cur_dec.unwrap_or(0) + t;
+        if end > self.data.len() {
+            anyhow::bail!("bundle run goes out of bounds");
+        }
+        Ok(end)
+    }
+
+    fn fill(&mut self, end: usize, v: i32) {
+        let start = self.cur_dec.unwrap_or(0);
+        self.data[start..end].fill(v as i16);
+        self.cur_dec = Some(end);
+    }
+
+    fn push(&mut self, v: i32) {
+        let cur = self.cur_dec.unwrap_or(0);
+        self.data[cur] = v as i16;
+        self.cur_dec = Some(cur + 1);
+    }
+
+    fn decoding(&self, end: usize) -> bool {
+        self.cur_dec.is_some_and(|cur| cur < end)
+    }
+
+    fn value(&mut self) -> i32 {
+        let v = self.data.get(self.cur_ptr).copied().unwrap_or(0);
+        self.cur_ptr += 1;
+        v as i32
+    }
+}
+
+/// One decoded plane. `stride` and the height leave room for the
+/// 16x16 blocks on the right and bottom edges.
+#[derive(Clone)]
+pub struct BinkPlane {
+    pub data: Vec<u8>,
+    pub stride: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl BinkPlane {
+    fn new(width: usize, height: usize, blocks_w: usize, blocks_h: usize) -> Self {
+        let stride = (blocks_w + 1) * 8;
+        Self {
+            data: vec![0; stride * (blocks_h + 1) * 8],
+            stride,
+            width,
+            height,
+        }
+    }
+
+    /// The visible `width` x `height` pixels, row by row.
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        self.data
+            .chunks(self.stride)
+            .take(self.height)
+            .map(|row| &row[..self.width])
+    }
+}
+
+/// A decoded frame: Y, U, V and, for movies with alpha, A.
+#[derive(Clone)]
+pub struct BinkFrame {
+    pub planes: Vec<BinkPlane>,
+}
+
+pub struct BinkVideoDecoder {
+    revision: u8,
+    width: usize,
+    height: usize,
+    has_alpha: bool,
+    swap_planes: bool,
+    bundles: [Bundle; NB_SRC],
+    col_high: [Tree; 16],
+    col_lastval: i32,
+    current: BinkFrame,
+    last: BinkFrame,
+}
+
+impl BinkVideoDecoder {
+    pub fn new(revision: u8, width: u32, height: u32, has_alpha: bool) -> anyhow::Result<Self> {
+        if !(b'c'..=b'k').contains(&revision) {
+            anyhow::bail!("unsupported Bink revision {:?}", revision as char);
+        }
+        if width == 0 || height == 0 || width > 7680 || height > 4800 {
+            anyhow::bail!("invalid Bink frame size {}x{}", width, height);
+        }
+
+        let (width, height) = (width as usize, height as usize);
+        let (bw, bh) = ((width + 7) >> 3, (height + 7) >> 3);
+        let blocks = bw * bh;
+        let bundles = std::array::from_fn(|i| Bundle {
+            // DC bundles hold 16-bit values in the same byte budget.
+            data: vec![
+                0;
+                if i == SRC_INTRA_DC || i == SRC_INTER_DC {
+                    blocks * 32
+                } else {
+                    blocks * 64
+                }
+            ],
+            ..Default::default()
+        });
+
+        let mut planes = vec![
+            BinkPlane::new(width, height, bw, bh),
+            BinkPlane::new(
+                (width + 1) >> 1,
+                (height + 1) >> 1,
+                (width + 15) >> 4,
+                (height + 15) >> 4,
+            ),
+        ];
+        planes.push(planes[1].clone());
+        if has_alpha {
+            planes.push(planes[0].clone());
+        }
+        let frame = BinkFrame { planes };
+
+        Ok(Self {
+            revision,
+            width,
+            height,
+            has_alpha,
+            swap_planes: revision >= b'h',
+            bundles,
+            col_high: Default::default(),
+            col_lastval: 0,
+            current: frame.clone(),
+            last: frame,
+        })
+    }
+
+    /// The most recently decoded frame.
+    pub fn frame(&self) -> &BinkFrame {
+        &self.last
+    }
+
+    pub fn decode(&mut self, packet: &[u8]) -> anyhow::Result<&BinkFrame> {
+        let mut gb = BitReader::new(packet);
+        let bits = (packet.len() * 8) as isize;
+        if self.has_alpha {
+            if self.revision >= b'i' {
+                gb.skip(32);
+            }
+            self.decode_plane(&mut gb, 3, false)?;
+        }
+        if self.revision >= b'i' {
+            gb.skip(32);
+        }
+
+        for plane in 0..3 {
+            let index = if plane == 0 || !self.swap_planes {
+                plane
+            } else {
+                plane ^ 3
+            };
+            self.decode_plane(&mut gb, index, plane != 0)?;
+            if gb.position() as isize >= bits {
+                break;
+            }
+        }
+
+        std::mem::swap(&mut self.current, &mut self.last);
+        Ok(&self.last)
+    }
+
+    fn init_lengths(&mut self, width: usize, bw: usize) {
+        let width = (width + 7) & !7;
+        let log2 = |v: usize| (usize::BITS - 1 - v.leading_zeros()) + 1;
+        self.bundles[SRC_BLOCK_TYPES].len = log2((width >> 3) + 511);
+        self.bundles[SRC_SUB_BLOCK_TYPES].len = log2((width >> 4) + 511);
+        self.bundles[SRC_COLORS].len = log2(bw * 64 + 511);
+        for src in [SRC_INTRA_DC, SRC_INTER_DC, SRC_X_OFF, SRC_Y_OFF] {
+            self.bundles[src].len = log2((width >> 3) + 511);
+        }
+        self.bundles[SRC_PATTERN].len = log2((bw << 3) + 511);
+        self.bundles[SRC_RUN].len = log2(bw * 48 + 511);
+    }
+
+    fn read_bundle(&mut self, gb: &mut BitReader, src: usize) -> anyhow::Result<()> {
+        if src == SRC_COLORS {
+            for tree in &mut self.col_high {
+                *tree = Tree::read(gb)?;
+            }
+            self.col_lastval = 0;
+        }
+        if src != SRC_INTRA_DC && src != SRC_INTER_DC {
+            self.bundles[src].tree = Tree::read(gb)?;
+        }
+        self.bundles[src].cur_dec = Some(0);
+        self.bundles[src].cur_ptr = 0;
+        Ok(())
+    }
+
+    fn read_runs(gb: &mut BitReader, b: &mut Bundle) -> anyhow::Result<()> {
+        let Some(t) = b.chunk_len(gb) else {
+            return Ok(());
+        };
+        let end = b.chunk_end(t)?;
+        if gb.read_bit() {
+            let v = gb.read(4) as i32;
+            b.fill(end, v);
+        } else {
+            while b.decoding(end) {
+                let v = b.tree.decode(gb);
+                b.push(v);
+            }
+        }
+        Ok(())
+    }
+
+    fn read_motion_values(gb: &mut BitReader, b: &mut Bundle) -> anyhow::Result<()> {
+        let Some(t) = b.chunk_len(gb) else {
+            return Ok(());
+        };
+        let end = b.chunk_end(t)?;
+        if gb.read_bit() {
+            let mut v = gb.read(4) as i32;
+            if v != 0 && gb.read_bit() {
+                v = -v;
+            }
+            b.fill(end, v);
+        } else {
+            while b.decoding(end) {
+                let mut v = b.tree.decode(gb);
+                if v != 0 && gb.read_bit() {
+                    v = -v;
+                }
+                b.push(v);
+            }
+        }
+        Ok(())
+    }
+
+    fn read_block_types(revision: u8, gb: &mut BitReader, b: &mut Bundle) -> anyhow::Result<()> {
+        let Some(mut t) = b.chunk_len(gb) else {
+            return Ok(());
+        };
+        if revision == b'k' {
+            t ^= 0xBB;
+            if t == 0 {
+                b.cur_dec = None;
+                return Ok(());
+            }
+        }
+        let end = b.chunk_end(t)?;
+        if gb.read_bit() {
+            let v = gb.read(4) as i32;
+            b.fill(end, v);
+        } else {
+            let mut last = 0;
+            while b.decoding(end) {
+                let v = b.tree.decode(gb);
+                if v < 12 {
+                    last = v;
+                    b.push(v);
+                } else {
+                    let run = RLE_LENS[(v - 12) as usize];
+                    let cur = b.cur_dec.unwrap_or(0);
+                    if end - cur < run {
+                        anyhow::bail!("block type run goes out of bounds");
+                    }
+                    b.fill(cur + run, last);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_patterns(gb: &mut BitReader, b: &mut Bundle) -> anyhow::Result<()> {
+        let Some(t) = b.chunk_len(gb) else {
+            return Ok(());
+        };
+        let end = b.chunk_end(t)?;
+        while b.decoding(end) {
+            if gb.bits_left() < 2 {
+                anyhow::bail!("out of data reading patterns");
+            }
+            let lo = b.tree.decode(gb);
+            let hi = b.tree.decode(gb);
+            b.push(lo | (hi << 4));
+        }
+        Ok(())
+    }
+
+    fn read_colors(&mut self, gb: &mut BitReader) -> anyhow::Result<()> {
+        let revision = self.revision;
+        let col_high = &self.col_high;
+        let col_lastval = &mut self.col_lastval;
+        let b = &mut self.bundles[SRC_COLORS];
+        let Some(t) = b.chunk_len(gb) else {
+            return Ok(());
+        };
+        let end = b.chunk_end(t)?;
+
+        let tree = b.tree;
+        let mut read_color = |gb: &mut BitReader| {
+            *col_lastval = col_high[*col_lastval as usize].decode(gb);
+            let mut v = (*col_lastval << 4) | tree.decode(gb);
+            if revision < b'i' {
+                let sign = (v as u8 as i8 as i32) >> 7;
+                v = ((v & 0x7F) ^ sign) - sign;
+                v = (v + 0x80) & 0xFF;
+            }
+            v
+        };
+
+        if gb.read_bit() {
+            let v = read_color(gb);
+            b.fill(end, v);
+        } else {
+            while b.decoding(end) {
+                if gb.bits_left() < 2 {
+                    anyhow::bail!("out of data reading colors");
+                }
+                let v = read_color(gb);
+                b.push(v);
+            }
+        }
+        Ok(())
+    }
+
+    fn read_dcs(gb: &mut BitReader, b: &mut Bundle, has_sign: bool) -> anyhow::Result<()> {
+        let Some(len) = b.chunk_len(gb) else {
+            return Ok(());
+        };
+        let start_bits = DC_START_BITS - has_sign as u32;
+        if gb.bits_left() < start_bits as isize {
+            anyhow::bail!("out of data reading DC values");
+        }
+
+        let mut v = gb.read(start_bits) as i32;
+        if v != 0 && has_sign && gb.read_bit() {
+            v = -v;
+        }
+        b.chunk_end(len)?;
+        b.push(v);
+
+        let len = len - 1;
+        for i in (0..len).step_by(8) {
+            let len2 = (len - i).min(8);
+            let bsize = gb.read(4);
+            for _ in 0..len2 {
+                if bsize != 0 {
+                    let mut v2 = gb.read(bsize) as i32;
+                    if v2 != 0 && gb.read_bit() {
+                        v2 = -v2;
+                    }
+                    v += v2;
+                    if !(-32768..=32767).contains(&v) {
+                        anyhow::bail!("DC value went out of bounds: {}", v);
+                    }
+                }
+                b.push(v);
+            }
+        }
+        Ok(())
+    }
+
+    fn read_row_bundles(&mut self, gb: &mut BitReader) -> anyhow::Result<()> {
+        Self::read_block_types(self.revision, gb, &mut self.bundles[SRC_BLOCK_TYPES])?;
+        Self::read_block_types(self.revision, gb, &mut self.bundles[SRC_SUB_BLOCK_TYPES])?;
+        self.read_colors(gb)?;
+        Self::read_patterns(gb, &mut self.bundles[SRC_PATTERN])?;
+        Self::read_motion_values(gb, &mut self.bundles[SRC_X_OFF])?;
+        Self::read_motion_values(gb, &mut self.bundles[SRC_Y_OFF])?;
+        Self::read_dcs(gb, &mut self.bundles[SRC_INTRA_DC], false)?;
+        Self::read_dcs(gb, &mut self.bundles[SRC_INTER_DC], true)?;
+        Self::read_runs(gb, &mut self.bundles[SRC_RUN])
+    }
+
+    fn value(&mut self, src: usize) -> i32 {
+        self.bundles[src].value()
+    }
+
+    /// Fill `out` (indexed through `scan`) from runs of colors.
+    fn read_run_block(
+        &mut self,
+        gb: &mut BitReader,
+        mut put: impl FnMut(usize, u8),
+    ) -> anyhow::Result<()> {
+        if gb.bits_left() < 4 {
+            anyhow::bail!("out of data reading a run block");
+        }
+        let scan = &BINK_PATTERNS[gb.read(4) as usize];
+        let mut pos = 0;
+        let mut i = 0;
+        loop {
+            let run = self.value(SRC_RUN) as usize + 1;
+            i += run;
+            if i > 64 {
+                anyhow::bail!("run block went out of bounds");
+            }
+            if gb.read_bit() {
+                let v = self.value(SRC_COLORS) as u8;
+                for _ in 0..run {
+                    put(scan[pos] as usize, v);
+                    pos += 1;
+                }
+            } else {
+                for _ in 0..run {
+                    let v = self.value(SRC_COLORS) as u8;
+                    put(scan[pos] as usize, v);
+                    pos += 1;
+                }
+            }
+            if i >= 63 {
+                break;
+            }
+        }
+        if i == 63 {
+            let v = self.value(SRC_COLORS) as u8;
+            put(scan[pos] as usize, v);
+        }
+        Ok(())
+    }
+
+    fn read_dct_block(
+        &mut self,
+        gb: &mut BitReader,
+        dc_src: usize,
+        quant: &[[i32; 64]; 16],
+    ) -> anyhow::Result<[i32; 64]> {
+        let mut block = [0i32; 64];
+        block[0] = self.value(dc_src);
+        let mut coef_idx = [0usize; 64];
+        let (coef_count, quant_idx) = read_dct_coeffs(gb, &mut block, &mut coef_idx)?;
+        let quant = &quant[quant_idx];
+        block[0] = block[0].wrapping_mul(quant[0]) >> 11;
+        for &idx in &coef_idx[..coef_count] {
+            let pos = BINK_SCAN[idx] as usize;
+            block[pos] = block[pos].wrapping_mul(quant[idx]) >> 11;
+        }
+        Ok(block)
+    }
+
+    fn decode_plane(
+        &mut self,
+        gb: &mut BitReader,
+        plane_idx: usize,
+        is_chroma: bool,
+    ) -> anyhow::Result<()> {
+        let shift = is_chroma as usize;
+        let (bw, bh) = if is_chroma {
+            ((self.width + 15) >> 4, (self.height + 15) >> 4)
+        } else {
+            ((self.width + 7) >> 3, (self.height + 7) >> 3)
+        };
+        let width = self.width >> shift;
+
+        if self.revision == b'k' && gb.read_bit() {
+            let fill = gb.read(8) as u8;
+            let plane = &mut self.current.planes[plane_idx];
+            for row in plane.data.chunks_mut(plane.stride).take(plane.height) {
+                row[..plane.width].fill(fill);
+            }
+        } else {
+            self.init_lengths(width.max(8), bw);
+            for src in 0..NB_SRC {
+                self.read_bundle(gb, src)?;
+            }
+            self.decode_blocks(gb, plane_idx, bw, bh)?;
+        }
+
+        gb.align32();
+        Ok(())
+    }
+
+    fn decode_blocks(
+        &mut self,
+        gb: &mut BitReader,
+        plane_idx: usize,
+        bw: usize,
+        bh: usize,
+    ) -> anyhow::Result<()> {
+        let mut dst_plane = std::mem::take(&mut self.current.planes[plane_idx].data);
+        let result = self.decode_block_rows(gb, &mut dst_plane, plane_idx, bw, bh);
+        self.current.planes[plane_idx].data = dst_plane;
+        result
+    }
+
+    fn decode_block_rows(
+        &mut self,
+        gb: &mut BitReader,
+        dst: &mut [u8],
+        plane_idx: usize,
+        bw: usize,
+        bh: usize,
+    ) -> anyhow::Result<()> {
+        let stride = self.current.planes[plane_idx].stride;
+        let ref_end = ((bw - 1) + stride * (bh - 1)) * 8;
+        let motion_ref = |prev: usize, xoff: i32, yoff: i32| -> anyhow::Result<usize> {
+            let r = prev as isize + xoff as isize + yoff as isize * stride as isize;
+            if r < 0 || r as usize > ref_end {
+                anyhow::bail!("motion vector out of bounds: ({}, {})", xoff, yoff);
+            }
+            Ok(r as usize)
+        };
+
+        for by in 0..bh {
+            self.read_row_bundles(gb)?;
+
+            let mut bx = 0;
+            while bx < bw {
+                let off = 8 * by * stride + 8 * bx;
+                let blk = self.value(SRC_BLOCK_TYPES);
+                // A 16x16 block on an odd row is the lower half of one
+                // already decoded.
+                if by & 1 == 1 && blk == SCALED_BLOCK {
+                    bx += 2;
+                    continue;
+                }
+
+                let prev = &self.last.planes[plane_idx].data;
+                match blk {
+                    SKIP_BLOCK => copy_block(dst, off, prev, off, stride),
+                    SCALED_BLOCK => {
+                        self.decode_scaled_block(gb, dst, off, stride)?;
+                        bx += 1;
+                    }
+                    MOTION_BLOCK => {
+                        let xoff = self.value(SRC_X_OFF);
+                        let yoff = self.value(SRC_Y_OFF);
+                        let r = motion_ref(off, xoff, yoff)?;
+                        copy_block(dst, off, &self.last.planes[plane_idx].data, r, stride);
+                    }
+                    RUN_BLOCK => {
+                        self.read_run_block(gb, |i, v| {
+                            dst[off + (i & 7) + (i >> 3) * stride] = v;
+                        })?;
+                    }
+                    RESIDUE_BLOCK => {
+                        let xoff = self.value(SRC_X_OFF);
+                        let yoff = self.value(SRC_Y_OFF);
+                        let r = motion_ref(off, xoff, yoff)?;
+                        copy_block(dst, off, &self.last.planes[plane_idx].data, r, stride);
+                        let mut block = [0i32; 64];
+                        let masks = gb.read(7) as i32;
+                        read_residue(gb, &mut block, masks)?;
+                        for (i, v) in block.iter().enumerate() {
+                            let p = &mut dst[off + (i & 7) + (i >> 3) * stride];
+                            *p = p.wrapping_add(*v as u8);
+                        }
+                    }
+                    INTRA_BLOCK => {
+                        let mut block = self.read_dct_block(gb, SRC_INTRA_DC, intra_quant())?;
+                        idct(&mut block);
+                        for (i, v) in block.iter().enumerate() {
+                            dst[off + (i & 7) + (i >> 3) * stride] = *v as u8;
+                        }
+                    }
+                    FILL_BLOCK => {
+                        let v = self.value(SRC_COLORS) as u8;
+                        for row in 0..8 {
+                            dst[off + row * stride..off + row * stride + 8].fill(v);
+                        }
+                    }
+                    INTER_BLOCK => {
+                        let xoff = self.value(SRC_X_OFF);
+                        let yoff = self.value(SRC_Y_OFF);
+                        let r = motion_ref(off, xoff, yoff)?;
+                        copy_block(dst, off, &self.last.planes[plane_idx].data, r, stride);
+                        let mut block = self.read_dct_block(gb, SRC_INTER_DC, inter_quant())?;
+                        idct(&mut block);
+                        for (i, v) in block.iter().enumerate() {
+                            let p = &mut dst[off + (i & 7) + (i >> 3) * stride];
+                            *p = p.wrapping_add(*v as u8);
+                        }
+                    }
+                    PATTERN_BLOCK => {
+                        let col = [self.value(SRC_COLORS) as u8, self.value(SRC_COLORS) as u8];
+                        for row in 0..8 {
+                            let mut v = self.value(SRC_PATTERN);
+                            for x in 0..8 {
+                                dst[off + row * stride + x] = col[(v & 1) as usize];
+                                v >>= 1;
+                            }
+                        }
+                    }
+                    RAW_BLOCK => {
+                        for row in 0..8 {
+                            for x in 0..8 {
+                                dst[off + row * stride + x] = self.value(SRC_COLORS) as u8;
+                            }
+                        }
+                    }
+                    _ => anyhow::bail!("unknown block type {}", blk),
+                }
+                bx += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A 16x16 block coded as an 8x8 one and upscaled 2x.
+    fn decode_scaled_block(
+        &mut self,
+        gb: &mut BitReader,
+        dst: &mut [u8],
+        off: usize,
+        stride: usize,
+    ) -> anyhow::Result<()> {
+        let mut ublock = [0u8; 64];
+        let blk = self.value(SRC_SUB_BLOCK_TYPES);
+        match blk {
+            RUN_BLOCK => self.read_run_block(gb, |i, v| ublock[i] = v)?,
+            INTRA_BLOCK => {
+                let mut block = self.read_dct_block(gb, SRC_INTRA_DC, intra_quant())?;
+                idct(&mut block);
+                for (u, v) in ublock.iter_mut().zip(block) {
+                    *u = v as u8;
+                }
+            }
+            FILL_BLOCK => {
+                let v = self.value(SRC_COLORS) as u8;
+                for row in 0..16 {
+                    dst[off + row * stride..off + row * stride + 16].fill(v);
+                }
+                return Ok(());
+            }
+            PATTERN_BLOCK => {
+                let col = [self.value(SRC_COLORS) as u8, self.value(SRC_COLORS) as u8];
+                for row in 0..8 {
+                    let mut v = self.value(SRC_PATTERN);
+                    for x in 0..8 {
+                        ublock[x + row * 8] = col[(v & 1) as usize];
+                        v >>= 1;
+                    }
+                }
+            }
+            RAW_BLOCK => {
+                for u in &mut ublock {
+                    *u = self.value(SRC_COLORS) as u8;
+                }
+            }
+            _ => anyhow::bail!("invalid 16x16 block type {}", blk),
+        }
+
+        for (i, v) in ublock.iter().enumerate() {
+            let p = off + (i & 7) * 2 + (i >> 3) * 2 * stride;
+            dst[p] = *v;
+            dst[p + 1] = *v;
+            dst[p + stride] = *v;
+            dst[p + stride + 1] = *v;
+        }
+        Ok(())
+    }
+}
+
+fn copy_block(dst: &mut [u8], dst_off: usize, src: &[u8], src_off: usize, stride: usize) {
+    for row in 0..8 {
+        let (d, s) = (dst_off + row * stride, src_off + row * stride);
+        dst[d..d + 8].copy_from_slice(&src[s..s + 8]);
+    }
+}
+
+/// Read a coefficient of `bits` magnitude bits (plus the implicit top
+/// bit) and its sign.
+fn read_coeff(gb: &mut BitReader, bits: u32) -> i32 {
+    if bits == 0 {
+        if gb.read_bit() { -1 } else { 1 }
+    } else {
+        let t = (gb.read(bits) | (1 << bits)) as i32;
+        if gb.read_bit() { -t } else { t }
+    }
+}
+
+/// Coefficient list shared by the DCT and residue coders: entries are
+/// `(coefficient, mode)`, new single coefficients are pushed at the
+/// front and new groups at the back.
+struct CoefList {
+    coef: [usize; 128],
+    mode: [u8; 128],
+    start: usize,
+    end: usize,
+}
+
+impl CoefList {
+    fn new(initial: &[(usize, u8)]) -> Self {
+        let mut list = Self {
+            coef: [0; 128],
+            mode: [0; 128],
+            start: 64,
+            end: 64,
+        };
+        for &(coef, mode) in initial {
+            list.push_back(coef, mode).unwrap();
+        }
+        list
+    }
+
+    fn push_back(&mut self, coef: usize, mode: u8) -> anyhow::Result<()> {
+        if self.end >= 128 {
+            anyhow::bail!("coefficient list overflow");
+        }
+        self.coef[self.end] = coef;
+        self.mode[self.end] = mode;
+        self.end += 1;
+        Ok(())
+    }
+
+    fn push_front(&mut self, coef: usize, mode: u8) -> anyhow::Result<()> {
+        if self.start == 0 {
+            anyhow::bail!("coefficient list overflow");
+        }
+        self.start -= 1;
+        self.coef[self.start] = coef;
+        self.mode[self.start] = mode;
+        Ok(())
+    }
+}
+
+/// Read the AC coefficients of a DCT block into `block` (in natural
+/// order) and their scan indices into `coef_idx`. Returns the number of
+/// coefficients read and the quantizer index.
+fn read_dct_coeffs(
+    gb: &mut BitReader,
+    block: &mut [i32; 64],
+    coef_idx: &mut [usize; 64],
+) -> anyhow::Result<(usize, usize)> {
+    if gb.bits_left() < 4 {
+        anyhow::bail!("out of data reading DCT coefficients");
+    }
+
+    let mut list = CoefList::new(&[(4, 0), (24, 0), (44, 0), (1, 3), (2, 3), (3, 3)]);
+    let mut coef_count = 0;
+    let mut put = |block: &mut [i32; 64], ccoef: usize, t: i32| -> anyhow::Result<()> {
+        if ccoef >= 64 || coef_count >= 64 {
+            anyhow::bail!("DCT coefficient out of range");
+        }
+        block[BINK_SCAN[ccoef] as usize] = t;
+        coef_idx[coef_count] = ccoef;
+        coef_count += 1;
+        Ok(())
+    };
+
+    let top = gb.read(4) as i32;
+    for bits in (0..top).rev() {
+        let bits = bits as u32;
+        let mut pos = list.start;
+        while pos < list.end {
+            if (list.coef[pos] == 0 && list.mode[pos] == 0) || !gb.read_bit() {
+                pos += 1;
+                continue;
+            }
+
+            let mut ccoef = list.coef[pos];
+            let mode = list.mode[pos];
+            match mode {
+                0 | 2 => {
+                    if mode == 0 {
+                        list.coef[pos] = ccoef + 4;
+                        list.mode[pos] = 1;
+                    } else {
+                        list.coef[pos] = 0;
+                        list.mode[pos] = 0;
+                        pos += 1;
+                    }
+                    for _ in 0..4 {
+                        if gb.read_bit() {
+                            list.push_front(ccoef, 3)?;
+                            // The front grew under the cursor.
+                        } else {
+                            let t = read_coeff(gb, bits);
+                            put(block, ccoef, t)?;
+                        }
+                        ccoef += 1;
+                    }
+                }
+                1 => {
+                    list.mode[pos] = 2;
+                    for _ in 0..3 {
+                        ccoef += 4;
+                        list.push_back(ccoef, 2)?;
+                    }
+                }
+                _ => {
+                    let t = read_coeff(gb, bits);
+                    put(block, ccoef, t)?;
+                    list.coef[pos] = 0;
+                    list.mode[pos] = 0;
+                    pos += 1;
+                }
+            }
+        }
+    }
+
+    let quant_idx = gb.read(4) as usize;
+    Ok((coef_count, quant_idx))
+}
+
+/// Read a residue block: bit planes from the top `mask` down, refining
+/// already set coefficients before adding new ones. `masks_count`
+/// bounds the number of refinements.
+fn read_residue(
+    gb: &mut BitReader,
+    block: &mut [i32; 64],
+    mut masks_count: i32,
+) -> anyhow::Result<()> {
+    let mut list = CoefList::new(&[(4, 0), (24, 0), (44, 0), (0, 2)]);
+    let mut nz_coeff = [0usize; 64];
+    let mut nz_count = 0;
+
+    let mut mask = 1i32 << gb.read(3);
+    while mask != 0 {
+        for &pos in &nz_coeff[..nz_count] {
+            if !gb.read_bit() {
+                continue;
+            }
+            if block[pos] < 0 {
+                block[pos] -= mask;
+            } else {
+                block[pos] += mask;
+            }
+            masks_count -= 1;
+            if masks_count < 0 {
+                return Ok(());
+            }
+        }
+
+        let mut pos = list.start;
+        while pos < list.end {
+            if (list.coef[pos] == 0 && list.mode[pos] == 0) || !gb.read_bit() {
+                pos += 1;
+                continue;
+            }
+
+            let mut ccoef = list.coef[pos];
+            let mode = list.mode[pos];
+            match mode {
+                0 | 2 => {
+                    if mode == 0 {
+                        list.coef[pos] = ccoef + 4;
+                        list.mode[pos] = 1;
+                    } else {
+                        list.coef[pos] = 0;
+                        list.mode[pos] = 0;
+                        pos += 1;
+                    }
+                    for _ in 0..4 {
+                        if gb.read_bit() {
+                            list.push_front(ccoef, 3)?;
+                        } else {
+                            if ccoef >= 64 || nz_count >= 64 {
+                                anyhow::bail!("residue coefficient out of range");
+                            }
+                            let p = BINK_SCAN[ccoef] as usize;
+                            nz_coeff[nz_count] = p;
+                            nz_count += 1;
+                            block[p] = if gb.read_bit() { -mask } else { mask };
+                            masks_count -= 1;
+                            if masks_count < 0 {
+                                return Ok(());
+                            }
+                        }
+                        ccoef += 1;
+                    }
+                }
+                1 => {
+                    list.mode[pos] = 2;
+                    for _ in 0..3 {
+                        ccoef += 4;
+                        list.push_back(ccoef, 2)?;
+                    }
+                }
+                _ => {
+                    if ccoef >= 64 || nz_count >= 64 {
+                        anyhow::bail!("residue coefficient out of range");
+                    }
+                    let p = BINK_SCAN[ccoef] as usize;
+                    nz_coeff[nz_count] = p;
+                    nz_count += 1;
+                    block[p] = if gb.read_bit() { -mask } else { mask };
+                    list.coef[pos] = 0;
+                    list.mode[pos] = 0;
+                    pos += 1;
+                    masks_count -= 1;
+                    if masks_count < 0 {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+        mask >>= 1;
+    }
+
+    Ok(())
+}
+
+const A1: i32 = 2896;
+const A2: i32 = 2217;
+const A3: i32 = 3784;
+const A4: i32 = -5352;
+
+fn mul(x: i32, y: i32) -> i32 {
+    x.wrapping_mul(y) >> 11
+}
+
+/// The 1-D Bink IDCT over `src[s * i]`, written to `dst[d * i]`.
+fn idct_1d(src: &[i32], s: usize, dst: &mut [i32], d: usize, munge: impl Fn(i32) -> i32) {
+    let x = |i: usize| src[s * i];
+    let a0 = x(0).wrapping_add(x(4));
+    let a1 = x(0).wrapping_sub(x(4));
+    let a2 = x(2).wrapping_add(x(6));
+    let a3 = mul(A1, x(2).wrapping_sub(x(6)));
+    let a4 = x(5).wrapping_add(x(3));
+    let a5 = x(5).wrapping_sub(x(3));
+    let a6 = x(1).wrapping_add(x(7));
+    let a7 = x(1).wrapping_sub(x(7));
+    let b0 = a4.wrapping_add(a6);
+    let b1 = mul(A3, a5.wrapping_add(a7));
+    let b2 = mul(A4, a5).wrapping_sub(b0).wrapping_add(b1);
+    let b3 = mul(A1, a6.wrapping_sub(a4)).wrapping_sub(b2);
+    let b4 = mul(A2, a7).wrapping_add(b3).wrapping_sub(b1);
+    let out = [
+        a0.wrapping_add(a2).wrapping_add(b0),
+        a1.wrapping_add(a3).wrapping_sub(a2).wrapping_add(b2),
+        a1.wrapping_sub(a3).wrapping_add(a2).wrapping_add(b3),
+        a0.wrapping_sub(a2).wrapping_sub(b4),
+        a0.wrapping_sub(a2).wrapping_add(b4),
+        a1.wrapping_sub(a3).wrapping_add(a2).wrapping_sub(b3),
+        a1.wrapping_add(a3).wrapping_sub(a2).wrapping_sub(b2),
+        a0.wrapping_add(a2).wrapping_sub(b0),
+    ];
+    for (i, v) in out.into_iter().enumerate() {
+        dst[d * i] = munge(v);
+    }
+}
+
+/// In-place 8x8 IDCT; the results are pixel values (or differences)
+/// left unclipped, as Bink wraps them into bytes.
+fn idct(block: &mut [i32; 64]) {
+    let mut temp = [0i32; 64];
+    for i in 0..8 {
+        if (1..8).all(|r| block[i + 8 * r] == 0) {
+            for r in 0..8 {
+                temp[i + 8 * r] = block[i];
+            }
+        } else {
+            idct_1d(&block[i..], 8, &mut temp[i..], 8, |v| v);
+        }
+    }
+    for i in 0..8 {
+        idct_1d(&temp[8 * i..], 1, &mut block[8 * i..], 1, |v| {
+            v.wrapping_add(0x7F) >> 8
+        });
+    }
+}
+
+/// `sqrt(2) * cos(i * pi / 16)`, 1 for `i = 0`.
+fn aan_scale(i: usize) -> f64 {
+    if i == 0 {
+        1.0
+    } else {
+        std::f64::consts::SQRT_2 * (i as f64 * std::f64::consts::PI / 16.0).cos()
+    }
+}
+
+/// Quantizer step of each of the 16 quantizers, relative to the first.
+/// It doubles every three steps.
+const QUANT_SCALES: [f64; 16] = [
+    1.0,
+    4.0 / 3.0,
+    5.0 / 3.0,
+    2.0,
+    8.0 / 3.0,
+    10.0 / 3.0,
+    4.0,
+    16.0 / 3.0,
+    20.0 / 3.0,
+    8.0,
+    32.0 / 3.0,
+    40.0 / 3.0,
+    16.0,
+    64.0 / 3.0,
+    80.0 / 3.0,
+    32.0,
+];
+
+/// Dequantization factors in 16.16 fixed point indexed by scan
+/// position, with the IDCT's AAN scaling folded in.
+fn build_quant(seed: &[u8; 64]) -> [[i32; 64]; 16] {
+    std::array::from_fn(|q| {
+        std::array::from_fn(|k| {
+            let pos = BINK_SCAN[k] as usize;
+            let v = seed[pos] as f64
+                * aan_scale(pos & 7)
+                * aan_scale(pos >> 3)
+                * QUANT_SCALES[q]
+                * 4096.0;
+            v.round() as i32
+        })
+    })
+}
+
+fn intra_quant() -> &'static [[i32; 64]; 16] {
+    static QUANT: OnceLock<[[i32; 64]; 16]> = OnceLock::new();
+    QUANT.get_or_init(|| build_quant(&INTRA_QUANT_SEED))
+}
+
+fn inter_quant() -> &'static [[i32; 64]; 16] {
+    static QUANT: OnceLock<[[i32; 64]; 16]> = OnceLock::new();
+    QUANT.get_or_init(|| build_quant(&INTER_QUANT_SEED))
+}
+
+#[rustfmt::skip]
+const INTRA_QUANT_SEED: [u8; 64] = [
+    16, 16, 16, 19, 16, 19, 22, 22,
+    22, 22, 26, 24, 26, 22, 22, 27,
+    27, 27, 26, 26, 26, 29, 29, 29,
+    27, 27, 27, 26, 34, 34, 34, 29,
+    29, 29, 27, 27, 37, 34, 34, 32,
+    32, 29, 29, 38, 37, 35, 35, 34,
+    35, 40, 40, 40, 38, 38, 48, 48,
+    46, 46, 58, 56, 56, 69, 69, 83,
+];
+
+#[rustfmt::skip]
+const INTER_QUANT_SEED: [u8; 64] = [
+    16, 17, 17, 18, 18, 18, 19, 19,
+    19, 19, 20, 20, 20, 20, 20, 21,
+    21, 21, 21, 21, 21, 22, 22, 22,
+    22, 22, 22, 22, 23, 23, 23, 23,
+    23, 23, 23, 23, 24, 24, 24, 25,
+    24, 24, 24, 25, 26, 26, 26, 26,
+    25, 27, 27, 27, 27, 27, 28, 28,
+    28, 28, 30, 30, 30, 31, 31, 33,
+];
+
+/// Coefficient order of DCT and residue blocks.
+#[rustfmt::skip]
+const BINK_SCAN: [u8; 64] = [
+     0,  1,  8,  9,  2,  3, 10, 11,
+     4,  5, 12, 13,  6,  7, 14, 15,
+    20, 21, 28, 29, 22, 23, 30, 31,
+    16, 17, 24, 25, 32, 33, 40, 41,
+    34, 35, 42, 43, 48, 49, 56, 57,
+    50, 51, 58, 59, 18, 19, 26, 27,
+    36, 37, 44, 45, 38, 39, 46, 47,
+    52, 53, 60, 61, 54, 55, 62, 63,
+];
+
+/// Codes (read LSB first) of the 16 fixed Huffman trees.
+#[rustfmt::skip]
+const BINK_TREE_BITS: [[u8; 16]; 16] = [
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F],
+    [0x00, 0x01, 0x03, 0x05, 0x07, 0x09, 0x0B, 0x0D, 0x0F, 0x13, 0x15, 0x17, 0x19, 0x1B, 0x1D, 0x1F],
+    [0x00, 0x02, 0x01, 0x09, 0x05, 0x15, 0x0D, 0x1D, 0x03, 0x13, 0x0B, 0x1B, 0x07, 0x17, 0x0F, 0x1F],
+    [0x00, 0x02, 0x06, 0x01, 0x09, 0x05, 0x0D, 0x1D, 0x03, 0x13, 0x0B, 0x1B, 0x07, 0x17, 0x0F, 0x1F],
+    [0x00, 0x04, 0x02, 0x06, 0x01, 0x09, 0x05, 0x0D, 0x03, 0x13, 0x0B, 0x1B, 0x07, 0x17, 0x0F, 0x1F],
+    [0x00, 0x04, 0x02, 0x0A, 0x06, 0x0E, 0x01, 0x09, 0x05, 0x0D, 0x03, 0x0B, 0x07, 0x17, 0x0F, 0x1F],
+    [0x00, 0x02, 0x0A, 0x06, 0x0E, 0x01, 0x09, 0x05, 0x0D, 0x03, 0x0B, 0x1B, 0x07, 0x17, 0x0F, 0x1F],
+    [0x00, 0x01, 0x05, 0x03, 0x13, 0x0B, 0x1B, 0x3B, 0x07, 0x27, 0x17, 0x37, 0x0F, 0x2F, 0x1F, 0x3F],
+    [0x00, 0x01, 0x03, 0x13, 0x0B, 0x2B, 0x1B, 0x3B, 0x07, 0x27, 0x17, 0x37, 0x0F, 0x2F, 0x1F, 0x3F],
+    [0x00, 0x01, 0x05, 0x0D, 0x03, 0x13, 0x0B, 0x1B, 0x07, 0x27, 0x17, 0x37, 0x0F, 0x2F, 0x1F, 0x3F],
+    [0x00, 0x02, 0x01, 0x05, 0x0D, 0x03, 0x13, 0x0B, 0x1B, 0x07, 0x17, 0x37, 0x0F, 0x2F, 0x1F, 0x3F],
+    [0x00, 0x01, 0x09, 0x05, 0x0D, 0x03, 0x13, 0x0B, 0x1B, 0x07, 0x17, 0x37, 0x0F, 0x2F, 0x1F, 0x3F],
+    [0x00, 0x02, 0x01, 0x03, 0x13, 0x0B, 0x1B, 0x3B, 0x07, 0x27, 0x17, 0x37, 0x0F, 0x2F, 0x1F, 0x3F],
+    [0x00, 0x01, 0x05, 0x03, 0x07, 0x27, 0x17, 0x37, 0x0F, 0x4F, 0x2F, 0x6F, 0x1F, 0x5F, 0x3F, 0x7F],
+    [0x00, 0x01, 0x05, 0x03, 0x07, 0x17, 0x37, 0x77, 0x0F, 0x4F, 0x2F, 0x6F, 0x1F, 0x5F, 0x3F, 0x7F],
+    [0x00, 0x02, 0x01, 0x05, 0x03, 0x07, 0x27, 0x17, 0x37, 0x0F, 0x2F, 0x6F, 0x1F, 0x5F, 0x3F, 0x7F],
+];
+
+#[rustfmt::skip]
+const BINK_TREE_LENS: [[u8; 16]; 16] = [
+    [4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4],
+    [1, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+    [2, 2, 4, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+    [2, 3, 3, 4, 4, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+    [3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 5, 5, 5, 5],
+    [3, 3, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 5, 5, 5, 5],
+    [2, 4, 4, 4, 4, 4, 4, 4, 4, 4, 5, 5, 5, 5, 5, 5],
+    [1, 3, 3, 5, 5, 5, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6],
+    [1, 2, 5, 5, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6],
+    [1, 3, 4, 4, 5, 5, 5, 5, 6, 6, 6, 6, 6, 6, 6, 6],
+    [2, 2, 3, 4, 4, 5, 5, 5, 5, 5, 6, 6, 6, 6, 6, 6],
+    [1, 4, 4, 4, 4, 5, 5, 5, 5, 5, 6, 6, 6, 6, 6, 6],
+    [2, 2, 2, 5, 5, 5, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6],
+    [1, 3, 3, 3, 6, 6, 6, 6, 7, 7, 7, 7, 7, 7, 7, 7],
+    [1, 3, 3, 3, 5, 6, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7],
+    [2, 2, 3, 3, 3, 6, 6, 6, 6, 6, 7, 7, 7, 7, 7, 7],
+];
+
+/// Pixel orders of run blocks.
+#[rustfmt::skip]
+const BINK_PATTERNS: [[u8; 64]; 16] = [
+    [
+        0x00, 0x08, 0x10, 0x18, 0x20, 0x28, 0x30, 0x38, 0x39, 0x31, 0x29, 0x21, 0x19, 0x11, 0x09, 0x01,
+        0x02, 0x0A, 0x12, 0x1A, 0x22, 0x2A, 0x32, 0x3A, 0x3B, 0x33, 0x2B, 0x23, 0x1B, 0x13, 0x0B, 0x03,
+        0x04, 0x0C, 0x14, 0x1C, 0x24, 0x2C, 0x34, 0x3C, 0x3D, 0x35, 0x2D, 0x25, 0x1D, 0x15, 0x0D, 0x05,
+        0x06, 0x0E, 0x16, 0x1E, 0x26, 0x2E, 0x36, 0x3E, 0x3F, 0x37, 0x2F, 0x27, 0x1F, 0x17, 0x0F, 0x07,
+    ],
+    [
+        0x3B, 0x3A, 0x39, 0x38, 0x30, 0x31, 0x32, 0x33, 0x2B, 0x2A, 0x29, 0x28, 0x20, 0x21, 0x22, 0x23,
+        0x1B, 0x1A, 0x19, 0x18, 0x10, 0x11, 0x12, 0x13, 0x0B, 0x0A, 0x09, 0x08, 0x00, 0x01, 0x02, 0x03,
+        0x04, 0x05, 0x06, 0x07, 0x0F, 0x0E, 0x0D, 0x0C, 0x14, 0x15, 0x16, 0x17, 0x1F, 0x1E, 0x1D, 0x1C,
+        0x24, 0x25, 0x26, 0x27, 0x2F, 0x2E, 0x2D, 0x2C, 0x34, 0x35, 0x36, 0x37, 0x3F, 0x3E, 0x3D, 0x3C,
+    ],
+    [
+        0x19, 0x11, 0x12, 0x1A, 0x1B, 0x13, 0x0B, 0x03, 0x02, 0x0A, 0x09, 0x01, 0x00, 0x08, 0x10, 0x18,
+        0x20, 0x28, 0x30, 0x38, 0x39, 0x31, 0x29, 0x2A, 0x32, 0x3A, 0x3B, 0x33, 0x2B, 0x23, 0x22, 0x21,
+        0x1D, 0x15, 0x16, 0x1E, 0x1F, 0x17, 0x0F, 0x07, 0x06, 0x0E, 0x0D, 0x05, 0x04, 0x0C, 0x14, 0x1C,
+        0x24, 0x2C, 0x34, 0x3C, 0x3D, 0x35, 0x2D, 0x2E, 0x36, 0x3E, 0x3F, 0x37, 0x2F, 0x27, 0x26, 0x25,
+    ],
+    [
+        0x03, 0x0B, 0x02, 0x0A, 0x01, 0x09, 0x00, 0x08, 0x10, 0x18, 0x11, 0x19, 0x12, 0x1A, 0x13, 0x1B,
+        0x23, 0x2B, 0x22, 0x2A, 0x21, 0x29, 0x20, 0x28, 0x30, 0x38, 0x31, 0x39, 0x32, 0x3A, 0x33, 0x3B,
+        0x3C, 0x34, 0x3D, 0x35, 0x3E, 0x36, 0x3F, 0x37, 0x2F, 0x27, 0x2E, 0x26, 0x2D, 0x25, 0x2C, 0x24,
+        0x1C, 0x14, 0x1D, 0x15, 0x1E, 0x16, 0x1F, 0x17, 0x0F, 0x07, 0x0E, 0x06, 0x0D, 0x05, 0x0C, 0x04,
+    ],
+    [
+        0x18, 0x19, 0x10, 0x11, 0x08, 0x09, 0x00, 0x01, 0x02, 0x03, 0x0A, 0x0B, 0x12, 0x13, 0x1A, 0x1B,
+        0x1C, 0x1D, 0x14, 0x15, 0x0C, 0x0D, 0x04, 0x05, 0x06, 0x07, 0x0E, 0x0F, 0x16, 0x17, 0x1E, 0x1F,
+        0x27, 0x26, 0x2F, 0x2E, 0x37, 0x36, 0x3F, 0x3E, 0x3D, 0x3C, 0x35, 0x34, 0x2D, 0x2C, 0x25, 0x24,
+        0x23, 0x22, 0x2B, 0x2A, 0x33, 0x32, 0x3B, 0x3A, 0x39, 0x38, 0x31, 0x30, 0x29, 0x28, 0x21, 0x20,
+    ],
+    [
+        0x00, 0x01, 0x02, 0x03, 0x08, 0x09, 0x0A, 0x0B, 0x10, 0x11, 0x12, 0x13, 0x18, 0x19, 0x1A, 0x1B,
+        0x20, 0x21, 0x22, 0x23, 0x28, 0x29, 0x2A, 0x2B, 0x30, 0x31, 0x32, 0x33, 0x38, 0x39, 0x3A, 0x3B,
+        0x04, 0x05, 0x06, 0x07, 0x0C, 0x0D, 0x0E, 0x0F, 0x14, 0x15, 0x16, 0x17, 0x1C, 0x1D, 0x1E, 0x1F,
+        0x24, 0x25, 0x26, 0x27, 0x2C, 0x2D, 0x2E, 0x2F, 0x34, 0x35, 0x36, 0x37, 0x3C, 0x3D, 0x3E, 0x3F,
+    ],
+    [
+        0x06, 0x07, 0x0F, 0x0E, 0x0D, 0x05, 0x0C, 0x04, 0x03, 0x0B, 0x02, 0x0A, 0x09, 0x01, 0x00, 0x08,
+        0x10, 0x18, 0x11, 0x19, 0x12, 0x1A, 0x13, 0x1B, 0x14, 0x1C, 0x15, 0x1D, 0x16, 0x1E, 0x17, 0x1F,
+        0x27, 0x2F, 0x26, 0x2E, 0x25, 0x2D, 0x24, 0x2C, 0x23, 0x2B, 0x22, 0x2A, 0x21, 0x29, 0x20, 0x28,
+        0x31, 0x30, 0x38, 0x39, 0x3A, 0x32, 0x3B, 0x33, 0x3C, 0x34, 0x3D, 0x35, 0x36, 0x37, 0x3F, 0x3E,
+    ],
+    [
+        0x00, 0x08, 0x09, 0x01, 0x02, 0x03, 0x0B, 0x0A, 0x12, 0x13, 0x1B, 0x1A, 0x19, 0x11, 0x10, 0x18,
+        0x20, 0x28, 0x29, 0x21, 0x22, 0x23, 0x2B, 0x2A, 0x32, 0x31, 0x30, 0x38, 0x39, 0x3A, 0x3B, 0x33,
+        0x34, 0x3C, 0x3D, 0x3E, 0x3F, 0x37, 0x36, 0x35, 0x2D, 0x2C, 0x24, 0x25, 0x26, 0x2E, 0x2F, 0x27,
+        0x1F, 0x17, 0x16, 0x1E, 0x1D, 0x1C, 0x14, 0x15, 0x0D, 0x0C, 0x04, 0x05, 0x06, 0x0E, 0x0F, 0x07,
+    ],
+    [
+        0x18, 0x19, 0x10, 0x11, 0x08, 0x09, 0x00, 0x01, 0x02, 0x03, 0x0A, 0x0B, 0x12, 0x13, 0x1A, 0x1B,
+        0x1C, 0x1D, 0x14, 0x15, 0x0C, 0x0D, 0x04, 0x05, 0x06, 0x07, 0x0E, 0x0F, 0x16, 0x17, 0x1E, 0x1F,
+        0x26, 0x27, 0x2E, 0x2F, 0x36, 0x37, 0x3E, 0x3F, 0x3C, 0x3D, 0x34, 0x35, 0x2C, 0x2D, 0x24, 0x25,
+        0x22, 0x23, 0x2A, 0x2B, 0x32, 0x33, 0x3A, 0x3B, 0x38, 0x39, 0x30, 0x31, 0x28, 0x29, 0x20, 0x21,
+    ],
+    [
+        0x00, 0x08, 0x01, 0x09, 0x02, 0x0A, 0x03, 0x0B, 0x13, 0x1B, 0x12, 0x1A, 0x11, 0x19, 0x10, 0x18,
+        0x20, 0x28, 0x21, 0x29, 0x22, 0x2A, 0x23, 0x2B, 0x33, 0x3B, 0x32, 0x3A, 0x31, 0x39, 0x30, 0x38,
+        0x3C, 0x34, 0x3D, 0x35, 0x3E, 0x36, 0x3F, 0x37, 0x2F, 0x27, 0x2E, 0x26, 0x2D, 0x25, 0x2C, 0x24,
+        0x1F, 0x17, 0x1E, 0x16, 0x1D, 0x15, 0x1C, 0x14, 0x0C, 0x04, 0x0D, 0x05, 0x0E, 0x06, 0x0F, 0x07,
+    ],
+    [
+        0x00, 0x08, 0x10, 0x18, 0x19, 0x1A, 0x1B, 0x13, 0x0B, 0x03, 0x02, 0x01, 0x09, 0x11, 0x12, 0x0A,
+        0x04, 0x0C, 0x14, 0x1C, 0x1D, 0x1E, 0x1F, 0x17, 0x0F, 0x07, 0x06, 0x05, 0x0D, 0x15, 0x16, 0x0E,
+        0x24, 0x2C, 0x34, 0x3C, 0x3D, 0x3E, 0x3F, 0x37, 0x2F, 0x27, 0x26, 0x25, 0x2D, 0x35, 0x36, 0x2E,
+        0x20, 0x28, 0x30, 0x38, 0x39, 0x3A, 0x3B, 0x33, 0x2B, 0x23, 0x22, 0x21, 0x29, 0x31, 0x32, 0x2A,
+    ],
+    [
+        0x00, 0x08, 0x10, 0x18, 0x19, 0x11, 0x09, 0x01, 0x02, 0x0A, 0x12, 0x1A, 0x1B, 0x13, 0x0B, 0x03,
+        0x04, 0x0C, 0x14, 0x1C, 0x1D, 0x15, 0x0D, 0x05, 0x06, 0x0E, 0x16, 0x1E, 0x1F, 0x17, 0x0F, 0x07,
+        0x27, 0x2F, 0x37, 0x3F, 0x3E, 0x36, 0x2E, 0x26, 0x25, 0x2D, 0x35, 0x3D, 0x3C, 0x34, 0x2C, 0x24,
+        0x23, 0x2B, 0x33, 0x3B, 0x3A, 0x32, 0x2A, 0x22, 0x21, 0x29, 0x31, 0x39, 0x38, 0x30, 0x28, 0x20,
+    ],
+    [
+        0x00, 0x01, 0x02, 0x03, 0x08, 0x09, 0x0A, 0x0B, 0x10, 0x11, 0x12, 0x13, 0x18, 0x19, 0x1A, 0x1B,
+        0x20, 0x21, 0x22, 0x23, 0x28, 0x29, 0x2A, 0x2B, 0x30, 0x31, 0x32, 0x33, 0x38, 0x39, 0x3A, 0x3B,
+        0x3C, 0x3D, 0x3E, 0x3F, 0x34, 0x35, 0x36, 0x37, 0x2C, 0x2D, 0x2E, 0x2F, 0x24, 0x25, 0x26, 0x27,
+        0x1C, 0x1D, 0x1E, 0x1F, 0x14, 0x15, 0x16, 0x17, 0x0C, 0x0D, 0x0E, 0x0F, 0x04, 0x05, 0x06, 0x07,
+    ],
+    [
+        0x18, 0x10, 0x08, 0x00, 0x01, 0x09, 0x11, 0x19, 0x1A, 0x12, 0x0A, 0x02, 0x03, 0x0B, 0x13, 0x1B,
+        0x1C, 0x14, 0x0C, 0x04, 0x05, 0x0D, 0x15, 0x1D, 0x1E, 0x16, 0x0E, 0x06, 0x07, 0x0F, 0x17, 0x1F,
+        0x27, 0x2F, 0x37, 0x3F, 0x3E, 0x36, 0x2E, 0x26, 0x25, 0x2D, 0x35, 0x3D, 0x3C, 0x34, 0x2C, 0x24,
+        0x23, 0x2B, 0x33, 0x3B, 0x3A, 0x32, 0x2A, 0x22, 0x21, 0x29, 0x31, 0x39, 0x38, 0x30, 0x28, 0x20,
+    ],
+    [
+        0x00, 0x08, 0x01, 0x09, 0x02, 0x0A, 0x12, 0x1A, 0x11, 0x19, 0x10, 0x18, 0x20, 0x28, 0x21, 0x29,
+        0x22, 0x2A, 0x32, 0x3A, 0x31, 0x39, 0x30, 0x38, 0x3B, 0x33, 0x3C, 0x34, 0x2B, 0x23, 0x2C, 0x24,
+        0x25, 0x2D, 0x26, 0x2E, 0x35, 0x3D, 0x36, 0x3E, 0x3F, 0x37, 0x2F, 0x27, 0x1F, 0x17, 0x0F, 0x07,
+        0x0E, 0x06, 0x0D, 0x05, 0x0C, 0x04, 0x03, 0x0B, 0x13, 0x1B, 0x1C, 0x14, 0x1D, 0x15, 0x1E, 0x16,
+    ],
+    [
+        0x00, 0x08, 0x09, 0x01, 0x02, 0x03, 0x0B, 0x0A, 0x12, 0x13, 0x1B, 0x1A, 0x19, 0x11, 0x10, 0x18,
+        0x20, 0x28, 0x29, 0x21, 0x22, 0x23, 0x2B, 0x2A, 0x32, 0x31, 0x30, 0x38, 0x39, 0x3A, 0x3B, 0x33,
+        0x34, 0x3C, 0x3D, 0x3E, 0x3F, 0x37, 0x36, 0x35, 0x2D, 0x2C, 0x24, 0x25, 0x26, 0x2E, 0x2F, 0x27,
+        0x1F, 0x17, 0x16, 0x1E, 0x1D, 0x1C, 0x14, 0x15, 0x0D, 0x0C, 0x04, 0x05, 0x06, 0x0E, 0x0F, 0x07,
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Cursor, Read, Seek};
+
+    use super::super::bink::BinkDemuxer;
+    use super::*;
+
+    const SAMPLE_BIK: &[u8] = include_bytes!("testdata/sample.bik");
+
+    /// A `BIKi` plane of one 8x8 block (or one 16x16 chroma block)
+    /// whose bundles all use the identity tree.
+    fn write_plane(w: &mut BitWriter, block_type: u32, color: Option<u8>) {
+        // Block types, sub block types, 16 high color trees + colors,
+        // patterns, x and y offsets and runs.
+        for _ in 0..1 + 1 + 17 + 1 + 2 + 1 {
+            w.write(4, 0);
+        }
+
+        // All bundle lengths are 10 bits except sub block types (9).
+        w.write(10, 1);
+        w.write(1, 1);
+        w.write(4, block_type);
+        w.write(9, 0);
+        match color {
+            Some(color) => {
+                w.write(10, 1);
+                w.write(1, 1);
+                w.write(4, color as u32 >> 4);
+                w.write(4, color as u32 & 15);
+            }
+            None => w.write(10, 0),
+        }
+        for _ in 0..6 {
+            w.write(10, 0);
+        }
+        w.align32();
+    }
+
+    fn write_frame(planes: [(u32, Option<u8>); 3]) -> Vec<u8> {
+        let mut w = BitWriter::default();
+        w.write(32, 0);
+        for (block_type, color) in planes {
+            write_plane(&mut w, block_type, color);
+        }
+        w.finish()
+    }
+
+    fn plane_values(frame: &BinkFrame) -> Vec<Vec<u8>> {
+        frame
+            .planes
+            .iter()
+            .map(|plane| plane.rows().flatten().copied().collect())
+            .collect()
+    }
+
+    #[test]
+    fn fill_and_skip_frames() {
+        let mut decoder = BinkVideoDecoder::new(b'i', 8, 8, false).unwrap();
+
+        // Y, then V, then U since revision `h`.
+        let frame = write_frame([
+            (FILL_BLOCK as u32, Some(0x5A)),
+            (FILL_BLOCK as u32, Some(0x81)),
+            (FILL_BLOCK as u32, Some(0x23)),
+        ]);
+        let planes = plane_values(decoder.decode(&frame).unwrap());
+        assert_eq!(planes[0], vec![0x5A; 64]);
+        assert_eq!(planes[1], vec![0x23; 16]);
+        assert_eq!(planes[2], vec![0x81; 16]);
+
+        let frame = write_frame([
+            (SKIP_BLOCK as u32, None),
+            (SKIP_BLOCK as u32, None),
+            (FILL_BLOCK as u32, Some(0x40)),
+        ]);
+        let planes = plane_values(decoder.decode(&frame).unwrap());
+        assert_eq!(planes[0], vec![0x5A; 64]);
+        assert_eq!(planes[1], vec![0x40; 16]);
+        assert_eq!(planes[2], vec![0x81; 16]);
+    }
+
+    #[test]
+    fn rejects_unsupported_streams() {
+        assert!(BinkVideoDecoder::new(b'b', 8, 8, false).is_err());
+        assert!(BinkVideoDecoder::new(b'i', 0, 8, false).is_err());
+
+        let mut decoder = BinkVideoDecoder::new(b'i', 8, 8, false).unwrap();
+        let frame = write_frame([(12, None), (0, None), (0, None)]);
+        assert!(decoder.decode(&frame).is_err());
+    }
+
+    #[test]
+    fn huffman_trees_are_complete_prefix_codes() {
+        for (tree, (bits, lens)) in BINK_TREE_BITS.iter().zip(&BINK_TREE_LENS).enumerate() {
+            let kraft: f64 = lens.iter().map(|&len| 0.5f64.powi(len as i32)).sum();
+            assert_eq!(kraft, 1.0, "tree {}", tree);
+
+            for (leaf, entry) in huffman_tables()[tree].iter().enumerate() {
+                assert_ne!(entry.1, 0, "tree {} lookahead {}", tree, leaf);
+            }
+            for leaf in 0..16 {
+                let (found, len) = huffman_tables()[tree][bits[leaf] as usize];
+                assert_eq!((found as usize, len), (leaf, lens[leaf]), "tree {}", tree);
+            }
+        }
+    }
+
+    #[test]
+    fn scan_orders_are_permutations() {
+        let is_permutation = |order: &[u8; 64]| {
+            let mut sorted = *order;
+            sorted.sort();
+            sorted.iter().enumerate().all(|(i, &v)| i == v as usize)
+        };
+
+        assert!(is_permutation(&BINK_SCAN));
+        for (i, pattern) in BINK_PATTERNS.iter().enumerate() {
+            assert!(is_permutation(pattern), "pattern {}", i);
+        }
+    }
+
+    #[test]
+    fn quantizers() {
+        assert_eq!(
+            intra_quant()[0][..8],
+            [
+                0x10000, 0x16315, 0x1E83D, 0x2A535, 0x14E7B, 0x16577, 0x2F1E6, 0x2724C
+            ]
+        );
+        assert_eq!(
+            inter_quant()[0][..8],
+            [
+                0x10000, 0x17946, 0x1A5A9, 0x248DC, 0x16363, 0x152A7, 0x243EC, 0x209EA
+            ]
+        );
+        assert_eq!(intra_quant()[15][0], 0x10000 * 32);
+    }
+
+    #[test]
+    fn idct_of_dc_is_flat() {
+        let mut block = [0; 64];
+        block[0] = 100 << 8;
+        idct(&mut block);
+        assert_eq!(block, [100; 64]);
+    }
+
+    /// The per-frame hashes of an `ffmpeg -f framemd5` listing.
+    fn framemd5_hashes(listing: &str) -> Vec<String> {
+        listing
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split(',').nth(5).map(|h| h.trim().to_string()))
+            .collect()
+    }
+
+    /// The MD5 of every frame of a movie, hashed plane by plane and row
+    /// by row as `framemd5` hashes YUV420P.
+    fn decode_frame_md5s<R: Read + Seek>(
+        demuxer: &mut BinkDemuxer<R>,
+    ) -> anyhow::Result<Vec<String>> {
+        let header = demuxer.header().clone();
+        let mut decoder = BinkVideoDecoder::new(
+            header.revision,
+            header.width,
+            header.height,
+            header.has_alpha(),
+        )?;
+        let mut hashes = vec![];
+        for i in 0..header.frames.len() {
+            let packet = demuxer.read_packet(i)?;
+            let frame = decoder
+                .decode(&packet.video)
+                .map_err(|e| e.context(format!("frame {}", i)))?;
+            let mut context = md5::Context::new();
+            for plane in &frame.planes {
+                for row in plane.rows() {
+                    context.consume(row);
+                }
+            }
+            hashes.push(format!("{:x}", context.compute()));
+        }
+        Ok(hashes)
+    }
+
+    /// `testdata/sample.bik` covers fill, raw, pattern, skip, motion and
+    /// scaled blocks in both luma and chroma; see `make_sample.py`.
+    #[test]
+    fn sample_matches_framemd5() {
+        let mut demuxer = BinkDemuxer::new(Cursor::new(SAMPLE_BIK)).unwrap();
+        assert_eq!(
+            decode_frame_md5s(&mut demuxer).unwrap(),
+            framemd5_hashes(include_str!("testdata/sample.framemd5"))
+        );
+    }
+
+    /// Decode every `.bik` under `BINK_MOVIE_DIR` that has a
+    /// `<name>.framemd5` next to it, made with
+    /// `ffmpeg -i <name>.bik -map 0:v -f framemd5 <name>.framemd5`, and
+    /// compare each frame.
+    #[test]
+    #[ignore = "requires BINK_MOVIE_DIR env var pointing to .bik movies with FFmpeg framemd5 files"]
+    fn shipped_movies_match_ffmpeg() {
+        let dir = std::env::var("BINK_MOVIE_DIR").unwrap();
+        let mut count = 0;
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let Ok(reference) = std::fs::read_to_string(path.with_extension("framemd5")) else {
+                continue;
+            };
+            if !path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("bik"))
+            {
+                continue;
+            }
+
+            let file = std::fs::File::open(&path).unwrap();
+            let mut demuxer = BinkDemuxer::new(BufReader::new(file)).unwrap();
+            let hashes = decode_frame_md5s(&mut demuxer)
+                .unwrap_or_else(|e| panic!("{}: {:#}", path.display(), e));
+            assert_eq!(hashes, framemd5_hashes(&reference), "{}", path.display());
+            count += 1;
+        }
+        assert!(count > 0, "no .bik files with .framemd5 references found");
+    }
+}
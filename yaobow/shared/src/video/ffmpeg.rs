@@ -44,6 +44,9 @@ use radiance::video::{VideoStream, VideoStreamState};
 
 const OUTPUT_AUDIO_BUFFER_MAX: usize = 20;
 
+/// Units of `Input::duration` / `Input::seek` (microseconds).
+const AV_TIME_BASE: f64 = 1_000_000.;
+
 const VIDEO_PACKET_QUEUE_MAX: usize = 1024;
 const AUDIO_PACKET_QUEUE_MAX: usize = 512;
 
@@ -124,13 +127,20 @@ impl StreamData {
             .time_base()
             .and_then(|time_base| Some(time_base.approx()))
             .unwrap_or(0.);
-        // calculate duration in ms
-        let input_duration = input.duration();
+        // calculate duration in ms. The container duration is in
+        // AV_TIME_BASE units; WebM and Ogg often only report that one.
         let stream_duration = stream.duration();
-        let input_duration_s = input.duration().map(|d| d as f64 * f64::from(time_base));
+        let input_duration_s = input.duration().map(|d| d as f64 / AV_TIME_BASE);
         let stream_duration_s = stream.duration().map(|d| d as f64 * f64::from(time_base));
-        let duration_pts = stream_duration.unwrap_or(input_duration.unwrap());
-        let duration_s = stream_duration_s.unwrap_or(input_duration_s.unwrap());
+        let duration_pts = stream_duration.unwrap_or_else(|| {
+            let seconds = input_duration_s.unwrap_or(0.);
+            if time_base > 0. {
+                (seconds / time_base) as i64
+            } else {
+                0
+            }
+        });
+        let duration_s = stream_duration_s.unwrap_or(input_duration_s.unwrap_or(0.));
         let duration = (duration_s * 1000_f64) as i64;
         Self {
             stream_index: stream.index(),
@@ -227,7 +237,8 @@ struct VideoState {
     input: Input,
     loop_count: u32,
     video: Arc<Mutex<VideoStreamData>>,
-    audio: Arc<Mutex<AudioStreamData>>,
+    /// `None` for silent movies (common for WebM / Theora mod cutscenes).
+    audio: Option<Arc<Mutex<AudioStreamData>>>,
     time: Arc<RwLock<TimeData>>,
 }
 
@@ -371,8 +382,8 @@ impl VideoStream for VideoStreamFFmpeg {
             // Keep `scaled_frame` so the UI doesn't blank between the
             // seek and the first post-seek decoded frame.
         }
-        {
-            let mut audio = st.audio.lock().unwrap();
+        if let Some(audio) = st.audio.as_ref() {
+            let mut audio = audio.lock().unwrap();
             audio.stream.packet_queue.clear();
             audio.stream.packet_queue.push_back(PacketData::Flush);
         }
@@ -488,25 +499,22 @@ impl VideoStreamFFmpeg {
         let duration = video.lock().unwrap().stream.duration;
         let duration_pts = video.lock().unwrap().stream.duration_pts;
 
-        // Now create the audio stream data.
-        let resampled_frames = Arc::new(Mutex::new(VecDeque::new()));
-        let mut audio_source = self.audio_engine.create_custom_decoder_source();
-        audio_source.set_decoder(Box::new(AudioFFmpegDecoder::new(resampled_frames.clone())));
-        let audio_output_stream = Arc::new(OutputAudioStream {
-            stream_source: Mutex::new(audio_source),
-            resampled_frames,
+        // Now create the audio stream data, if the movie has any.
+        let audio = input.streams().best(Type::Audio).map(|stream| {
+            let resampled_frames = Arc::new(Mutex::new(VecDeque::new()));
+            let mut audio_source = self.audio_engine.create_custom_decoder_source();
+            audio_source.set_decoder(Box::new(AudioFFmpegDecoder::new(resampled_frames.clone())));
+            let audio_output_stream = Arc::new(OutputAudioStream {
+                stream_source: Mutex::new(audio_source),
+                resampled_frames,
+            });
+
+            Arc::new(Mutex::new(AudioStreamData::new(
+                StreamData::new(&input, &stream, Decoder::new_audio, Arc::clone(&time)),
+                audio_output_stream,
+            )))
         });
-
-        let audio = Arc::new(Mutex::new(AudioStreamData::new(
-            StreamData::new(
-                &input,
-                &input.streams().best(Type::Audio).unwrap(),
-                Decoder::new_audio,
-                Arc::clone(&time),
-            ),
-            audio_output_stream,
-        )));
-        let weak_audio = Arc::downgrade(&audio);
+        let weak_audio = audio.as_ref().map(Arc::downgrade);
         // Create the state.
         let state = Arc::new(Mutex::new(VideoState {
             loop_count: 0,
@@ -531,9 +539,11 @@ impl VideoStreamFFmpeg {
         self.threads.push(thread::spawn(move || {
             run_player_thread(weak_video, "video player".into(), play_video)
         }));
-        self.threads.push(thread::spawn(|| {
-            run_player_thread(weak_audio, "audio player".into(), play_audio)
-        }));
+        if let Some(weak_audio) = weak_audio {
+            self.threads.push(thread::spawn(|| {
+                run_player_thread(weak_audio, "audio player".into(), play_audio)
+            }));
+        }
 
         // Wait until the first frame has been decoded and we know the video size.
         let size = rx.recv().unwrap();
@@ -952,11 +962,13 @@ where
 
 fn enqueue_next_packet(state: &mut VideoState) -> LoopState {
     let video = state.video.lock().unwrap();
-    let audio = state.audio.lock().unwrap();
+    let audio = state.audio.as_ref().map(|audio| audio.lock().unwrap());
 
     // sleep if the queues are full
     if video.stream.packet_queue.len() >= VIDEO_PACKET_QUEUE_MAX
-        || audio.stream.packet_queue.len() >= AUDIO_PACKET_QUEUE_MAX
+        || audio
+            .as_ref()
+            .is_some_and(|audio| audio.stream.packet_queue.len() >= AUDIO_PACKET_QUEUE_MAX)
     {
         return LoopState::Sleep(QUEUE_FULL_SLEEP);
     }
@@ -968,7 +980,7 @@ fn enqueue_next_packet(state: &mut VideoState) -> LoopState {
     // read input packets and queue them to the correct queue
     let packet = state.input.packets().next();
     let mut video = state.video.lock().unwrap();
-    let mut audio = state.audio.lock().unwrap();
+    let mut audio = state.audio.as_ref().map(|audio| audio.lock().unwrap());
     match packet {
         Some(_packet) => match _packet {
             Ok((stream, packet)) => {
@@ -978,7 +990,10 @@ fn enqueue_next_packet(state: &mut VideoState) -> LoopState {
                         .stream
                         .packet_queue
                         .push_back(PacketData::Packet(packet, state.loop_count));
-                } else if idx == audio.stream.stream_index {
+                } else if let Some(audio) = audio
+                    .as_mut()
+                    .filter(|audio| idx == audio.stream.stream_index)
+                {
                     audio
                         .stream
                         .packet_queue
@@ -998,7 +1013,9 @@ fn enqueue_next_packet(state: &mut VideoState) -> LoopState {
             // looping -> seek to beginning?
             let _ = state.input.seek(0, 0..i64::max_value());
             video.stream.packet_queue.push_back(PacketData::Flush);
-            audio.stream.packet_queue.push_back(PacketData::Flush);
+            if let Some(audio) = audio.as_mut() {
+                audio.stream.packet_queue.push_back(PacketData::Flush);
+            }
             state.loop_count += 1;
         }
     }
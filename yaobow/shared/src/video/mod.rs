@@ -1,6 +1,193 @@
+use std::{
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use mini_fs::{MiniFs, Store, StoreExt};
+use radiance::{
+    asset::ypk::YpkFs,
+    utils::SeekRead,
+    video::{Codec, has_video_decoder},
+};
+
+pub mod bink;
+pub mod bink_audio;
+pub mod bink_stream;
+pub mod bink_video;
+#[cfg(feature = "ffmpeg")]
 mod ffmpeg;
 
+/// Containers tried for a movie, in order. Open formats come first so
+/// a `movie01.ogv` or `movie01.webm` shipped next to (or mounted over)
+/// `movie01.bik` replaces it.
+const MOVIE_FORMATS: &[(&str, Codec)] = &[
+    ("ogv", Codec::Theora),
+    ("webm", Codec::Webm),
+    ("bik", Codec::Bik),
+];
+
+/// Folder inside a mod package that mirrors the game data for movies.
+const MOD_MOVIE_DIR: &str = "movies";
+
+lazy_static::lazy_static! {
+    static ref MOVIE_MODS: Mutex<Vec<PathBuf>> = Mutex::new(vec![]);
+}
+
+/// Set the enabled mod packages searched for movies before the game
+/// data, in load order. See [`open_movie`].
+pub fn set_movie_mods(packages: Vec<PathBuf>) {
+    *MOVIE_MODS.lock().unwrap() = packages;
+}
+
 pub fn register_opengb_video_decoders() {
-    use radiance::video::{Codec, register_video_decoder};
-    register_video_decoder(Codec::Bik, ffmpeg::VideoStreamFFmpeg::create);
+    #[cfg(feature = "ffmpeg")]
+    {
+        use radiance::video::register_video_decoder;
+        register_video_decoder(Codec::Bik, ffmpeg::VideoStreamFFmpeg::create);
+        register_video_decoder(Codec::Webm, ffmpeg::VideoStreamFFmpeg::create);
+        register_video_decoder(Codec::Theora, ffmpeg::VideoStreamFFmpeg::create);
+    }
+    #[cfg(not(feature = "ffmpeg"))]
+    radiance::video::register_video_decoder(Codec::Bik, bink_stream::VideoStreamBink::create);
+}
+
+/// Open the movie at `path` (its extension is ignored) in the first
+/// format of [`MOVIE_FORMATS`] that exists.
+///
+/// Enabled mod packages are searched first, in load order, under
+/// `movies/<path>`; the game `vfs` comes last. Replacement formats are
+/// only picked when a decoder is registered for them; the `.bik` is
+/// always tried so a build without decoders still finds it and lets
+/// `VideoPlayer::play` skip it.
+pub fn open_movie<P: AsRef<Path>>(
+    vfs: &MiniFs,
+    path: P,
+) -> anyhow::Result<(Box<dyn SeekRead>, Codec)> {
+    let mods = MOVIE_MODS.lock().unwrap().clone();
+    open_movie_in(&mods, vfs, path.as_ref(), has_video_decoder)
+}
+
+fn open_movie_in(
+    mods: &[PathBuf],
+    vfs: &MiniFs,
+    path: &Path,
+    can_decode: impl Fn(Codec) -> bool,
+) -> anyhow::Result<(Box<dyn SeekRead>, Codec)> {
+    let relative = path.strip_prefix("/").unwrap_or(path);
+    for package in mods {
+        let store = match YpkFs::new(package) {
+            Ok(store) => store,
+            Err(e) => {
+                log::warn!("Cannot open mod {:?} for movies: {}", package, e);
+                continue;
+            }
+        };
+
+        let mod_path = Path::new(MOD_MOVIE_DIR).join(relative);
+        if let Some(movie) = open_formats(&store, &mod_path, &can_decode) {
+            return Ok(movie);
+        }
+    }
+
+    open_formats(vfs, path, &can_decode)
+        .ok_or_else(|| anyhow::anyhow!("Movie not found: {:?}", path))
+}
+
+fn open_formats<S: Store<File = mini_fs::File>>(
+    store: &S,
+    path: &Path,
+    can_decode: &impl Fn(Codec) -> bool,
+) -> Option<(Box<dyn SeekRead>, Codec)> {
+    for &(extension, codec) in MOVIE_FORMATS {
+        if codec != Codec::Bik && !can_decode(codec) {
+            continue;
+        }
+
+        let candidate = path.with_extension(extension);
+        if let Ok(file) = store.open(&candidate) {
+            log::debug!("Opening movie {:?} as {:?}", candidate, codec);
+            return Some((Box::new(BufReader::new(file)), codec));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use mini_fs::LocalFs;
+    use radiance::asset::ypk::YpkWriter;
+
+    use super::*;
+
+    fn read(
+        mods: &[PathBuf],
+        vfs: &MiniFs,
+        path: &str,
+        decodable: &[Codec],
+    ) -> anyhow::Result<(String, Codec)> {
+        let (mut reader, codec) =
+            open_movie_in(mods, vfs, Path::new(path), |c| decodable.contains(&c))?;
+        let mut data = String::new();
+        reader.read_to_string(&mut data).unwrap();
+        Ok((data, codec))
+    }
+
+    #[test]
+    fn open_formats_replace_bik_once_decodable() {
+        let dir = std::env::temp_dir().join(format!("yaobow-open-movie-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("movie")).unwrap();
+        std::fs::write(dir.join("movie/movie01.bik"), b"bik").unwrap();
+        std::fs::write(dir.join("movie/movie01.ogv"), b"ogv").unwrap();
+        let vfs = MiniFs::new(false).mount("/", LocalFs::new(&dir));
+
+        // Nothing can decode Theora yet, so the original stays.
+        assert_eq!(
+            read(&[], &vfs, "/movie/movie01.bik", &[]).unwrap(),
+            ("bik".to_string(), Codec::Bik)
+        );
+        assert_eq!(
+            read(&[], &vfs, "/movie/movie01.bik", &[Codec::Theora]).unwrap(),
+            ("ogv".to_string(), Codec::Theora)
+        );
+        assert!(read(&[], &vfs, "/movie/movie02", &[Codec::Theora]).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn mod_movies_come_before_the_game_data() {
+        let dir = std::env::temp_dir().join(format!("yaobow-mod-movie-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("game/movie")).unwrap();
+        std::fs::write(dir.join("game/movie/movie01.bik"), b"bik").unwrap();
+        std::fs::write(dir.join("game/movie/movie02.bik"), b"bik").unwrap();
+        let vfs = MiniFs::new(false).mount("/", LocalFs::new(dir.join("game")));
+
+        let package = dir.join("remaster.ypk");
+        let file = std::fs::File::create(&package).unwrap();
+        let mut ypk = YpkWriter::new(Box::new(file)).unwrap();
+        ypk.write_file("movies/movie/movie01.ogv", b"mod ogv")
+            .unwrap();
+        ypk.finish().unwrap();
+        let mods = [dir.join("missing.ypk"), package];
+
+        assert_eq!(
+            read(&mods, &vfs, "/movie/movie01.bik", &[Codec::Theora]).unwrap(),
+            ("mod ogv".to_string(), Codec::Theora)
+        );
+        // Without a Theora decoder the game's own movie plays.
+        assert_eq!(
+            read(&mods, &vfs, "/movie/movie01.bik", &[]).unwrap(),
+            ("bik".to_string(), Codec::Bik)
+        );
+        assert_eq!(
+            read(&mods, &vfs, "/movie/movie02.bik", &[Codec::Theora]).unwrap(),
+            ("bik".to_string(), Codec::Bik)
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,255 @@
+"""Write the Bink 1 sample used by the decoder tests.
+
+    python3 make_sample.py <dir>
+
+writes `sample.bik`, a 16x16 `BIKi` with three frames and one mono
+22050 Hz RDFT audio track, next to `sample.framemd5` (the layout of
+`ffmpeg -i sample.bik -map 0:v -f framemd5 -`) and `sample.f32le.md5`
+(the layout of `ffmpeg -i sample.bik -map 0:a -c:a pcm_f32le -f md5 -`).
+
+Every bundle uses the identity Huffman tree, and the blocks are chosen
+so that the expected pixels and samples follow from the bitstream
+alone: fill, raw, pattern, skip, motion and scaled fill blocks, and an
+audio block whose only coefficient is DC. The hashes below are worked
+out from those values, not decoded.
+"""
+
+import hashlib, struct, sys
+
+SKIP, SCALED, MOTION, FILL, PATTERN, RAW = 0, 1, 2, 6, 8, 9
+W = H = 16
+
+
+class Bits:
+    def __init__(self):
+        self.bits = []
+
+    def w(self, n, v):
+        for i in range(n):
+            self.bits.append((v >> i) & 1)
+
+    def align32(self):
+        while len(self.bits) % 32:
+            self.bits.append(0)
+
+    def bytes(self):
+        assert len(self.bits) % 8 == 0
+        out = bytearray()
+        for i in range(0, len(self.bits), 8):
+            out.append(sum(b << j for j, b in enumerate(self.bits[i:i + 8])))
+        return bytes(out)
+
+
+def log2p1(v):
+    return v.bit_length()
+
+
+def lengths(width, bw):
+    width = max(width, 8)
+    width = (width + 7) & ~7
+    return {
+        'types': log2p1((width >> 3) + 511),
+        'sub': log2p1((width >> 4) + 511),
+        'colors': log2p1(bw * 64 + 511),
+        'pattern': log2p1((bw << 3) + 511),
+        'xoff': log2p1((width >> 3) + 511),
+        'yoff': log2p1((width >> 3) + 511),
+        'idc': log2p1((width >> 3) + 511),
+        'ndc': log2p1((width >> 3) + 511),
+        'run': log2p1(bw * 48 + 511),
+    }
+
+
+ORDER = ['types', 'sub', 'colors', 'pattern', 'xoff', 'yoff', 'idc', 'ndc', 'run']
+
+
+def encode_chunk(b, kind, vals):
+    if kind in ('types', 'sub'):
+        if len(vals) > 1 and len(set(vals)) == 1:
+            b.w(1, 1); b.w(4, vals[0])
+        else:
+            b.w(1, 0)
+            for v in vals:
+                assert v < 12
+                b.w(4, v)
+    elif kind == 'colors':
+        if len(vals) > 1 and len(set(vals)) == 1:
+            b.w(1, 1); b.w(4, vals[0] >> 4); b.w(4, vals[0] & 15)
+        else:
+            b.w(1, 0)
+            for v in vals:
+                b.w(4, v >> 4); b.w(4, v & 15)
+    elif kind == 'pattern':
+        for v in vals:
+            b.w(4, v & 15); b.w(4, v >> 4)
+    elif kind in ('xoff', 'yoff'):
+        b.w(1, 0)
+        for v in vals:
+            b.w(4, abs(v))
+            if v:
+                b.w(1, 1 if v < 0 else 0)
+    else:
+        raise ValueError(kind)
+
+
+def encode_plane(b, width, bw, rows):
+    """rows: per block row, dict kind -> values consumed in that row."""
+    lens = lengths(width, bw)
+    # 16 high color trees + one tree per bundle, all the identity tree.
+    for _ in range(1 + 1 + 17 + 1 + 2 + 1):
+        b.w(4, 0)
+    allvals = {k: [v for r in rows for v in r.get(k, [])] for k in ORDER}
+    state = {k: [0, 0] for k in ORDER}  # cur_dec (None when done), cur_ptr
+    for r in rows:
+        for k in ORDER:
+            dec, ptr = state[k]
+            if dec is not None and dec <= ptr:
+                rest = allvals[k][dec:]
+                b.w(lens[k], len(rest))
+                if rest:
+                    encode_chunk(b, k, rest)
+                    state[k][0] = dec + len(rest)
+                else:
+                    state[k][0] = None
+        for k in ORDER:
+            state[k][1] += len(r.get(k, []))
+            assert state[k][0] is not None and state[k][1] <= state[k][0] or not r.get(k)
+    b.align32()
+
+
+def plane(w, h, v=0):
+    return [[v] * w for _ in range(h)]
+
+
+def put_block(p, bx, by, block):
+    for y in range(8):
+        for x in range(8):
+            p[by * 8 + y][bx * 8 + x] = block[y][x]
+
+
+def get_block(p, px, py):
+    return [row[px:px + 8] for row in p[py:py + 8]]
+
+
+RAMP = [[(y * 8 + x) * 4 for x in range(8)] for y in range(8)]
+PAT = [0x55, 0xAA, 0x0F, 0xF0, 0x81, 0x42, 0x24, 0x18]
+
+
+def pattern_block(c0, c1, pats):
+    return [[c1 if (pats[y] >> x) & 1 else c0 for x in range(8)] for y in range(8)]
+
+
+frames = []
+expected = []
+
+# Frame 0: luma fill, raw, pattern, fill; V pattern, U fill.
+Y = plane(16, 16)
+put_block(Y, 0, 0, plane(8, 8, 0x10))
+put_block(Y, 1, 0, RAMP)
+put_block(Y, 0, 1, pattern_block(0x20, 0xE0, PAT))
+put_block(Y, 1, 1, plane(8, 8, 0x80))
+V = pattern_block(0x30, 0xB0, PAT[::-1])
+U = plane(8, 8, 0x60)
+b = Bits()
+b.w(32, 0)
+encode_plane(b, 16, 2, [
+    {'types': [FILL, RAW], 'colors': [0x10] + [v for r in RAMP for v in r]},
+    {'types': [PATTERN, FILL], 'colors': [0x20, 0xE0, 0x80], 'pattern': PAT},
+])
+encode_plane(b, 8, 1, [{'types': [PATTERN], 'colors': [0x30, 0xB0], 'pattern': PAT[::-1]}])
+encode_plane(b, 8, 1, [{'types': [FILL], 'colors': [0x60]}])
+frames.append(b.bytes())
+expected.append((Y, U, V))
+prev = (Y, U, V)
+
+# Frame 1: luma motion from the right, skip, fill, motion from the
+# top left; V fill, U skip.
+Y = [row[:] for row in prev[0]]
+put_block(Y, 0, 0, get_block(prev[0], 8, 0))
+put_block(Y, 0, 1, plane(8, 8, 0x33))
+put_block(Y, 1, 1, get_block(prev[0], 0, 0))
+V = plane(8, 8, 0xA0)
+U = [row[:] for row in prev[1]]
+b = Bits()
+b.w(32, 0)
+encode_plane(b, 16, 2, [
+    {'types': [MOTION, SKIP], 'xoff': [8], 'yoff': [0]},
+    {'types': [FILL, MOTION], 'colors': [0x33], 'xoff': [-8], 'yoff': [-8]},
+])
+encode_plane(b, 8, 1, [{'types': [FILL], 'colors': [0xA0]}])
+encode_plane(b, 8, 1, [{'types': [SKIP]}])
+frames.append(b.bytes())
+expected.append((Y, U, V))
+prev = (Y, U, V)
+
+# Frame 2: one scaled 16x16 fill for luma, both chroma planes skipped.
+Y = plane(16, 16, 0xC8)
+b = Bits()
+b.w(32, 0)
+encode_plane(b, 16, 2, [
+    {'types': [SCALED], 'sub': [FILL], 'colors': [0xC8]},
+    {'types': [SCALED]},
+])
+encode_plane(b, 8, 1, [{'types': [SKIP]}])
+encode_plane(b, 8, 1, [{'types': [SKIP]}])
+frames.append(b.bytes())
+expected.append((Y, prev[1], prev[2]))
+
+# Audio: one mono 22050 Hz RDFT block per frame whose only coefficient
+# is DC, so every sample is 0.25. A 1024-point block scales its floats
+# by 2 / (sqrt(1024) * 32768) = 2^-19, and the inverse RDFT halves DC:
+# 2^22 * 2^(19 - 23) * 2^-19 / 2 = 0.25.
+FRAME_LEN, OVERLAP, NUM_BANDS = 1024, 64, 23
+a = Bits()
+a.w(32, (FRAME_LEN - OVERLAP) * 4)
+a.w(5, 19); a.w(23, 1 << 22); a.w(1, 0)
+a.w(29, 0)
+for _ in range(NUM_BANDS):
+    a.w(8, 0)
+for _ in range(2):
+    a.w(1, 1); a.w(4, 15); a.w(4, 0)
+a.align32()
+audio = a.bytes()
+pcm = struct.pack('<f', 0.25) * ((FRAME_LEN - OVERLAP) * len(frames))
+
+# Container: BIKi, 16x16 at 15 fps, one audio track.
+header_size = 44 + 4 + 4 + 4 + 4 * (len(frames) + 1)
+body = bytearray()
+index = []
+for i, video in enumerate(frames):
+    off = header_size + len(body)
+    index.append(off | 1 if i == 0 else off)
+    body += struct.pack('<I', len(audio)) + audio + video
+file_size = header_size + len(body)
+index.append(file_size)
+largest = max(4 + len(audio) + len(v) for v in frames)
+out = bytearray(b'BIKi')
+out += struct.pack('<11I', file_size - 8, len(frames), largest, len(frames), W, H, 15, 1, 0, 1,
+                   len(pcm) // len(frames))
+out += struct.pack('<HH', 22050, 0x4000)
+out += struct.pack('<I', 0)
+for v in index:
+    out += struct.pack('<I', v)
+assert len(out) == header_size
+out += body
+
+dst = sys.argv[1]
+open(dst + '/sample.bik', 'wb').write(out)
+
+lines = [
+    '#format: frame checksums',
+    '#version: 2',
+    '#hash: MD5',
+    '#tb 0: 1/15',
+    '#media_type 0: video',
+    '#codec_id 0: rawvideo',
+    '#dimensions 0: 16x16',
+    '#sar 0: 0/1',
+    '#stream#, dts,        pts, duration,     size, hash',
+]
+for i, planes in enumerate(expected):
+    data = bytes(v for p in planes for row in p for v in row)
+    lines.append('0, %10d, %10d, %8d, %8d, %s' % (i, i, 1, len(data), hashlib.md5(data).hexdigest()))
+open(dst + '/sample.framemd5', 'w').write('\n'.join(lines) + '\n')
+open(dst + '/sample.f32le.md5', 'w').write('MD5=%s\n' % hashlib.md5(pcm).hexdigest())
+print(len(out), 'bytes')
@@ -39,3 +39,23 @@ pub fn config_dir() -> PathBuf {
         PathBuf::from("ux0:yaobow")
     }
 }
+
+/// Root of the processed-asset cache (see `radiance::asset::cache`).
+/// Everything under it can be regenerated, so it lives in the OS cache
+/// dir where the system may reclaim it.
+pub fn cache_dir() -> PathBuf {
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    {
+        dirs::cache_dir().unwrap().join("yaobow")
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        save_dir().join("cache")
+    }
+
+    #[cfg(vita)]
+    {
+        PathBuf::from("ux0:yaobow/cache")
+    }
+}
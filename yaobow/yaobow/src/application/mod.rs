@@ -10,13 +10,19 @@ use agent_server::AgentServer;
 use crosscom::ComRc;
 use radiance::{
     application::Application,
+    asset::cache::AssetCache,
     comdef::{
         IApplication, IApplicationExt, IApplicationLoaderComponent, IComponentImpl, IDirector,
         ISceneManager,
     },
     input::SyntheticInputBridge,
+    radiance::UiLayerHandle,
 };
-use radiance_scripting::install_imgui_ui_renderer;
+use radiance_scripting::comdef::services::IHostContext;
+use radiance_scripting::console::install_script_console;
+use radiance_scripting::hot_reload::install_hot_reloader;
+use radiance_scripting::mods::{self, LoadedMods, ModPackage};
+use radiance_scripting::{HostError, ScriptHost, install_imgui_ui_renderer};
 use shared::agent_common::{
     AgentBootOptions, AgentBridge, install_global_log_sink, start_agent_server,
 };
@@ -25,8 +31,9 @@ use shared::{GameType, config::YaobowConfig};
 
 pub type Pal4AgentBootOptions = AgentBootOptions;
 
+use crate::application::yaobow_host_context::YAOBOW_HOST_CONTEXT_TYPE_TAG;
 use crate::comdef::yaobow_services::{IYaobowHostContext, IYaobowScriptApp};
-use crate::script_source::install_script_factory;
+use crate::script_source::{self, bootstrap_script_app, install_script_factory, load_script_mods};
 
 /// Single boot-option bundle for the yaobow application. Replaces
 /// the previous family of `create_application_*` / `run_*` variants
@@ -101,7 +108,7 @@ impl BootOptions {
 ///     services + `IYaobowHostContext`, bootstraps + reverse-wraps the
 ///     script app, and installs the PAL4 `IPal4ScriptFactory` on
 ///     `Pal4Service`); the loader holds the returned factory +
-///     host-context handles.
+///     host-context handles, then loads any user mods on top.
 ///  2. Install the imgui UI renderer (so script-side directors that
 ///     also implement `IUiLayer` get their `render` driven inside the
 ///     imgui frame scope).
@@ -109,9 +116,10 @@ impl BootOptions {
 ///     `--pal4 --agent-port` was passed) and the scripted actor
 ///     controller factory.
 ///  4. Push the initial director: either the script-side title
-///     director (default) or — for `--palX` CLI direct boot — the
-///     per-game director constructed via `host.palX().create_director()`
-///     directly (no title page flicker).
+///     director (default, or a mod's `title` override) or — for
+///     `--palX` CLI direct boot — the per-game director constructed
+///     via `host.palX().create_director()` directly (no title page
+///     flicker).
 ///
 /// During normal play, per-game launches are dispatched entirely
 /// from the script side (`title.p7`'s click handler) — no Rust
@@ -122,7 +130,9 @@ pub struct YaobowApplicationLoader {
     /// The reverse-wrapped script app factory (`make_title_director`).
     /// Held for the loader lifetime so the script
     /// box stays rooted. Set in `on_loading`.
-    factory: RefCell<Option<ComRc<IYaobowScriptApp>>>,
+    /// Shared with the hot reloader's rebootstrap, which swaps in the
+    /// factory built against the reloaded scripts.
+    factory: Rc<RefCell<Option<ComRc<IYaobowScriptApp>>>>,
     /// The canonical host context, used to reach the per-game services.
     /// Set in `on_loading`.
     host_context: RefCell<Option<ComRc<IYaobowHostContext>>>,
@@ -133,6 +143,15 @@ pub struct YaobowApplicationLoader {
     /// lifetime so the listener thread is joined exactly once at
     /// process exit.
     agent_server: RefCell<Option<AgentServer>>,
+    /// F1 script console registration; the console lives as long as
+    /// this handle.
+    script_console: RefCell<Option<UiLayerHandle>>,
+    /// User mod packages mounted at boot, reloaded after every script
+    /// hot reload.
+    mod_packages: Rc<RefCell<Vec<ModPackage>>>,
+    /// Mods loaded into the current interpreter: their UI layers stay
+    /// registered while this is set. Replaced by the hot reloader.
+    mods: Rc<RefCell<Option<LoadedMods>>>,
 }
 
 ComObject_YaobowApplicationLoader!(super::YaobowApplicationLoader);
@@ -143,10 +162,22 @@ impl IComponentImpl for YaobowApplicationLoader {
 
         // Bootstrap the script root. This also QIs the script's PAL4
         // factory surface and installs it on `Pal4Service`.
-        let (factory, host_context) = install_script_factory(&self.app, self.config.clone());
+        let (factory, host_context, mod_packages) =
+            install_script_factory(&self.app, self.config.clone(), self.initial_game);
         self.factory.replace(Some(factory.clone()));
         self.host_context.replace(Some(host_context.clone()));
 
+        // User mods load after the built-in package so they can import
+        // and extend it.
+        let host = ScriptHost::install(&self.app.engine().borrow());
+        self.mod_packages.replace(mod_packages);
+        self.mods.replace(Some(load_script_mods(
+            &self.app,
+            &host,
+            &host_context,
+            &self.mod_packages.borrow(),
+        )));
+
         // Hook the imgui texture cache into Pal3Service + Pal4Service.
         let pal3 = host_context.pal3();
         let pal4 = host_context.pal4();
@@ -156,6 +187,15 @@ impl IComponentImpl for YaobowApplicationLoader {
         pal4.inner::<shared::openpal4::service::Pal4Service>()
             .set_texture_cache(texture_cache);
 
+        // The PAL4 start menu is built on demand by the mode router, so
+        // hand it a hook that consults whichever mods are loaded then.
+        let mods_slot = self.mods.clone();
+        pal4.inner::<shared::openpal4::service::Pal4Service>()
+            .set_start_menu_override(Box::new(move || {
+                let mods = mods_slot.borrow();
+                mod_director(&host, mods.as_ref()?, "pal4_start_menu")
+            }));
+
         let scene_manager = self.app.engine().borrow().scene_manager().clone();
 
         match self.initial_game {
@@ -180,6 +220,9 @@ impl IComponentImpl for YaobowApplicationLoader {
             }
             None => self.install_title_director(&scene_manager, &factory),
         }
+
+        self.install_script_console(&host_context);
+        self.install_hot_reloader(host_context);
     }
 
     fn on_unloading(&self) {
@@ -195,7 +238,13 @@ impl IComponentImpl for YaobowApplicationLoader {
                 .pal4()
                 .inner::<shared::openpal4::service::Pal4Service>()
                 .clear_script_factory();
+            host_context
+                .pal5()
+                .inner::<crate::openpal5::Pal5Service>()
+                .set_loading_overlay(None);
         }
+        self.script_console.replace(None);
+        self.mods.replace(None);
         self.factory.replace(None);
         self.host_context.replace(None);
     }
@@ -238,12 +287,15 @@ impl YaobowApplicationLoader {
         Self {
             app,
             config: Rc::new(RefCell::new(YaobowConfig::load())),
-            factory: RefCell::new(None),
+            factory: Rc::new(RefCell::new(None)),
             host_context: RefCell::new(None),
             initial_game: None,
             initial_asset_path: RefCell::new(None),
             initial_agent_opts: RefCell::new(None),
             agent_server: RefCell::new(None),
+            script_console: RefCell::new(None),
+            mod_packages: Rc::new(RefCell::new(Vec::new())),
+            mods: Rc::new(RefCell::new(None)),
         }
     }
 
@@ -253,15 +305,57 @@ impl YaobowApplicationLoader {
         loader
     }
 
+    fn install_script_console(&self, host_context: &ComRc<IYaobowHostContext>) {
+        let host = ScriptHost::install(&self.app.engine().borrow());
+        let host_context = host_context
+            .query_interface::<IHostContext>()
+            .expect("IYaobowHostContext extends IHostContext");
+        let handle = install_script_console(&self.app, host, host_context);
+        self.script_console.replace(Some(handle));
+    }
+
+    /// Opt-in script hot reload (`RADIANCE_SCRIPT_HOT_RELOAD`). The
+    /// rebootstrap rebuilds the script app against the reloaded
+    /// sources, re-points the PAL3/PAL4 script factories at it,
+    /// reloads user mods and hands back a fresh title director.
+    fn install_hot_reloader(&self, host_context: ComRc<IYaobowHostContext>) {
+        let app = self.app.clone();
+        let host = ScriptHost::install(&self.app.engine().borrow());
+        let factory_slot = self.factory.clone();
+        let mod_packages = self.mod_packages.clone();
+        let mods_slot = self.mods.clone();
+        let host_arg = mods::mod_host_arg(host_context.clone(), YAOBOW_HOST_CONTEXT_TYPE_TAG);
+        install_hot_reloader(
+            &self.app,
+            host,
+            script_source::SCRIPT_ROOT,
+            Box::new(move |host| {
+                // The old mods' layers wrap script boxes from the
+                // previous interpreter; unregister them first.
+                mods_slot.replace(None);
+                let factory = bootstrap_script_app(host, &host_context)?;
+                factory_slot.replace(Some(factory.clone()));
+                let mods = load_script_mods(&app, host, &host_context, &mod_packages.borrow());
+                let director = title_director(host, &factory, &mods);
+                mods_slot.replace(Some(mods));
+                director
+            }),
+            host_arg,
+        );
+    }
+
     fn install_title_director(
         &self,
         scene_manager: &ComRc<ISceneManager>,
         factory: &ComRc<IYaobowScriptApp>,
     ) {
-        let director = factory
-            .make_title_director()
-            .query_interface::<IDirector>()
-            .expect("initial script director must be created");
+        let host = ScriptHost::install(&self.app.engine().borrow());
+        let mods = self.mods.borrow();
+        let director = match mods.as_ref() {
+            Some(mods) => title_director(&host, factory, mods),
+            None => stock_title_director(factory),
+        }
+        .expect("initial script director must be created");
         scene_manager.set_director(director);
     }
 
@@ -453,6 +547,38 @@ impl YaobowApplicationLoader {
     }
 }
 
+/// The title director: a mod's `title` override if one loaded and
+/// builds, the script app's own otherwise.
+fn title_director(
+    host: &ScriptHost,
+    factory: &ComRc<IYaobowScriptApp>,
+    mods: &LoadedMods,
+) -> Result<ComRc<IDirector>, HostError> {
+    match mod_director(host, mods, "title") {
+        Some(director) => Ok(director),
+        None => stock_title_director(factory),
+    }
+}
+
+/// A mod's override for director `slot`, or `None` (logging why) when
+/// no mod overrides it or the override fails to build.
+fn mod_director(host: &ScriptHost, mods: &LoadedMods, slot: &str) -> Option<ComRc<IDirector>> {
+    match mods.make_director(host, slot)? {
+        Ok(director) => Some(director),
+        Err(err) => {
+            log::error!("{err}; using the built-in {slot} director");
+            None
+        }
+    }
+}
+
+fn stock_title_director(factory: &ComRc<IYaobowScriptApp>) -> Result<ComRc<IDirector>, HostError> {
+    factory
+        .make_title_director()
+        .query_interface::<IDirector>()
+        .ok_or_else(|| HostError::message("title director does not implement IDirector"))
+}
+
 /// Convert a `GameType` to the same ordinal the script side uses
 /// (via `radiance_scripting::services::game_registry`).
 fn ordinal_for_game(game: GameType) -> i32 {
@@ -530,6 +656,33 @@ pub fn run_app(opts: BootOptions) {
     app.run();
 }
 
+/// Install the on-disk processed-asset cache configured under `[cache]`
+/// in `yaobow.toml`. `rebuild` (the `--rebuild-cache` switch) empties
+/// it first so every asset is decoded from source again. Call once at
+/// startup, before any game loads.
+pub fn install_asset_cache(rebuild: bool) {
+    let config = YaobowConfig::load();
+    if !config.cache.enabled {
+        log::info!("asset cache disabled");
+        return;
+    }
+
+    let cache = AssetCache::new(config.cache_dir(), config.cache_max_bytes())
+        .with_compressed_textures(config.cache.compress_textures);
+    if rebuild {
+        match cache.clear() {
+            Ok(()) => log::info!("asset cache cleared: {}", cache.root().display()),
+            Err(e) => log::warn!("cannot clear asset cache {}: {}", cache.root().display(), e),
+        }
+    }
+    log::info!(
+        "asset cache at {} ({} bytes)",
+        cache.root().display(),
+        cache.size()
+    );
+    radiance::asset::cache::install(cache);
+}
+
 /// Resolve the asset path for `game`, honouring per-platform
 /// fallbacks: desktop reads `YaobowConfig` and falls back to a
 /// hardcoded dev path when the config slot is empty; Android / Vita
@@ -54,6 +54,10 @@ pub mod script_bridges {
     pub mod pal4_debug {
         pub use shared::script_bridges::pal4_debug::*;
     }
+
+    pub mod loading {
+        pub use shared::script_bridges::loading::*;
+    }
 }
 
 pub mod script_source {
@@ -72,7 +76,10 @@ pub mod script_source {
 
     use crosscom::ComRc;
     use radiance::comdef::{IApplication, IApplicationExt};
-    use radiance_scripting::{ScriptHost, bootstrap_script_root_from_path};
+    use radiance_scripting::mods::{self, LoadedMods, ModEnvironment, ModPackage};
+    use radiance_scripting::{HostError, ScriptHost, bootstrap_script_root_from_path};
+    use shared::GameType;
+    use shared::config::YaobowConfig;
 
     use crate::application::yaobow_host_context::{
         YAOBOW_HOST_CONTEXT_TYPE_TAG, YaobowHostContext,
@@ -90,9 +97,13 @@ pub mod script_source {
     /// `import yaobow.yaobow_services;`, etc. and the app root
     /// resolves at `/yaobow/app.p7`.
     pub fn mount_scripts(assets: &radiance::asset::AssetManager) {
-        assets
-            .mount_ypk_bytes("/yaobow", YAOBOW_YPK)
-            .expect("yaobow.ypk must mount");
+        radiance_scripting::hot_reload::mount_script_bundle(
+            assets,
+            "/yaobow",
+            YAOBOW_YPK,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/scripts"),
+        )
+        .expect("yaobow.ypk must mount");
     }
 
     /// Construct the dedicated script `AssetManager` used by the
@@ -124,11 +135,21 @@ pub mod script_source {
     /// so the PAL4 launch path (start menu, debug overlay, actor
     /// controllers) dispatches straight through the COM vtable.
     ///
+    /// User mods from the configured mods dir are discovered for
+    /// `initial_game` and mounted on the script `AssetManager` here,
+    /// and handed to the movie lookup; the accepted packages are
+    /// returned for [`load_script_mods`].
+    ///
     /// Called once from `YaobowApplicationLoader::on_loading`.
     pub fn install_script_factory(
         app: &ComRc<IApplication>,
-        config: Rc<RefCell<shared::config::YaobowConfig>>,
-    ) -> (ComRc<IYaobowScriptApp>, ComRc<IYaobowHostContext>) {
+        config: Rc<RefCell<YaobowConfig>>,
+        initial_game: Option<GameType>,
+    ) -> (
+        ComRc<IYaobowScriptApp>,
+        ComRc<IYaobowHostContext>,
+        Vec<ModPackage>,
+    ) {
         let engine_rc = app.engine();
         let engine = engine_rc.borrow();
 
@@ -138,20 +159,103 @@ pub mod script_source {
         let pal5 = crate::openpal5::Pal5Service::create(app.clone());
         let swd5 = shared::openswd5::service::Swd5Service::create(app.clone());
         let host_context =
-            YaobowHostContext::create(app.clone(), config, pal3.clone(), pal4.clone(), pal5, swd5);
+            YaobowHostContext::create(app.clone(), config.clone(), pal3, pal4, pal5, swd5);
 
         let host = ScriptHost::install(&engine);
         // Install the dedicated script `AssetManager` so the VFS-backed
         // `ModuleProvider` can resolve every `import <crate>.<module>;`.
-        host.set_script_assets(install_script_assets());
+        let assets = install_script_assets();
+        let mod_packages = mods::mount_mods(
+            &assets,
+            discover_script_mods(&config.borrow(), initial_game),
+        );
+        shared::video::set_movie_mods(mod_packages.iter().map(|p| p.path.clone()).collect());
+        host.set_script_assets(assets);
+        let factory = bootstrap_script_app(&host, &host_context)
+            .expect("yaobow app script init must succeed");
+
+        (factory, host_context, mod_packages)
+    }
+
+    /// Director slots user mods may override through `[directors]` in
+    /// their `mod.toml`: the title screen and the PAL4 start menu.
+    pub const MOD_DIRECTOR_SLOTS: &[&str] = &["title", "pal4_start_menu"];
+
+    /// Accessors `IYaobowHostContext` adds on top of `IHostContext`.
+    const YAOBOW_HOST_INTERFACES: &[&str] = &[
+        "IPal3Service",
+        "IPal4Service",
+        "IPal5Service",
+        "ISwd5Service",
+    ];
+
+    /// Scan the configured mods dir, logging every rejected or
+    /// disabled package. Direct boot only keeps mods that support
+    /// `game`; the title page keeps them all.
+    pub fn discover_script_mods(config: &YaobowConfig, game: Option<GameType>) -> Vec<ModPackage> {
+        let known_games: Vec<&str> = GameType::all().iter().map(|g| g.config_key()).collect();
+        let host_interfaces: Vec<&str> = mods::HOST_CONTEXT_INTERFACES
+            .iter()
+            .chain(YAOBOW_HOST_INTERFACES)
+            .copied()
+            .collect();
+        let env = ModEnvironment {
+            game: game.map(|g| g.config_key()),
+            known_games: &known_games,
+            host_interfaces: &host_interfaces,
+            director_slots: MOD_DIRECTOR_SLOTS,
+        };
+
+        let discovery = mods::discover_mods(&config.mods_dir(), &env);
+        for rejected in &discovery.rejected {
+            log::warn!("mod {}", rejected);
+        }
+        discovery
+            .packages
+            .into_iter()
+            .filter(|package| {
+                let disabled = config.mods.disabled.contains(&package.manifest.name);
+                if disabled {
+                    log::info!("mod {} disabled in config", package.manifest.name);
+                }
+                !disabled
+            })
+            .collect()
+    }
+
+    /// Load `packages` into `host` after the built-in package, handing
+    /// their factories the yaobow host context. Redone by the hot
+    /// reloader after every rebootstrap.
+    pub fn load_script_mods(
+        app: &ComRc<IApplication>,
+        host: &ScriptHost,
+        host_context: &ComRc<IYaobowHostContext>,
+        packages: &[ModPackage],
+    ) -> LoadedMods {
+        let ui = app.engine().borrow().ui_manager();
+        let host_arg = mods::mod_host_arg(host_context.clone(), YAOBOW_HOST_CONTEXT_TYPE_TAG);
+        mods::load_mods(host, &ui, packages, host_arg)
+    }
+
+    /// Root source of the yaobow script package.
+    pub const SCRIPT_ROOT: &str = "/yaobow/app.p7";
+
+    /// Run `app.p7`'s `init`, reverse-wrap the returned app root and
+    /// hand its PAL3 / PAL4 factory surfaces to the per-game services.
+    /// Split out of [`install_script_factory`] so script hot reload
+    /// can redo it on a freshly reloaded host with the same host
+    /// context.
+    pub fn bootstrap_script_app(
+        host: &ScriptHost,
+        host_context: &ComRc<IYaobowHostContext>,
+    ) -> Result<ComRc<IYaobowScriptApp>, HostError> {
         let app_data = bootstrap_script_root_from_path(
-            &host,
-            "/yaobow/app.p7",
+            host,
+            SCRIPT_ROOT,
             host_context.clone(),
             YAOBOW_HOST_CONTEXT_TYPE_TAG,
             "init",
-        )
-        .expect("yaobow app script init must succeed");
+        )?;
 
         // The `app.p7` struct conforms to `IYaobowScriptApp`,
         // `openpal3.IPal3ScriptFactory`, and `openpal4.IPal4ScriptFactory`.
@@ -165,15 +269,16 @@ pub mod script_source {
         let factory = crate::script_bridges::yaobow_services::wrap_yaobow_script_app(
             &host.runtime_handle(),
             app_data,
-        )
-        .expect("reverse-wrap yaobow script app root must succeed");
+        )?;
 
         // Hand the PAL3 factory surface to Pal3Service (QI to the
         // shared `IPal3ScriptFactory` slot of the same fat CCW).
         let pal3_factory = factory
             .query_interface::<shared::openpal3::comdef::IPal3ScriptFactory>()
             .expect("script app must conform to IPal3ScriptFactory");
-        pal3.inner::<crate::openpal3::Pal3Service>()
+        host_context
+            .pal3()
+            .inner::<crate::openpal3::Pal3Service>()
             .set_script_factory(pal3_factory);
 
         // Hand the PAL4 factory surface to Pal4Service (QI to the
@@ -181,10 +286,18 @@ pub mod script_source {
         let script_factory = factory
             .query_interface::<shared::openpal4::comdef::IPal4ScriptFactory>()
             .expect("script app must conform to IPal4ScriptFactory");
-        pal4.inner::<shared::openpal4::service::Pal4Service>()
+        // PAL5 has no loading screen of its own; it borrows the PAL4
+        // overlay script for its streamed scene loads.
+        host_context
+            .pal5()
+            .inner::<crate::openpal5::Pal5Service>()
+            .set_loading_overlay(Some(script_factory.make_loading_overlay()));
+        host_context
+            .pal4()
+            .inner::<shared::openpal4::service::Pal4Service>()
             .set_script_factory(script_factory);
 
-        (factory, host_context)
+        Ok(factory)
     }
 }
 
@@ -193,10 +306,10 @@ pub mod openpal3;
 pub mod openpal5;
 
 pub use application::{
-    BootOptions, Pal4AgentBootOptions, boot_for, create_application, resolve_asset_path, run_app,
-    run_opengujian, run_openpal4, run_openpal4_with_agent, run_openpal5, run_openpal5_with_agent,
-    run_openpal5q, run_openpal5q_with_agent, run_openswd5, run_openswd5_with_agent,
-    run_title_selection,
+    BootOptions, Pal4AgentBootOptions, boot_for, create_application, install_asset_cache,
+    resolve_asset_path, run_app, run_opengujian, run_openpal4, run_openpal4_with_agent,
+    run_openpal5, run_openpal5_with_agent, run_openpal5q, run_openpal5q_with_agent, run_openswd5,
+    run_openswd5_with_agent, run_title_selection,
 };
 pub use openpal3::{
     run_openpal3, run_openpal3_with_agent, run_openpal3a, run_openpal3a_with_agent,
@@ -204,5 +317,6 @@ pub use openpal3::{
 
 #[cfg_attr(target_os = "android", ndk_glue::main(backtrace = "on"))]
 pub fn android_entry() {
+    install_asset_cache(false);
     openpal3::run_openpal3();
 }
@@ -4,7 +4,8 @@ use agent_server::AgentLogSink;
 use log::{Level, LevelFilter, Log, Metadata, Record};
 use shared::video::register_opengb_video_decoders;
 use yaobow_lib::{
-    Pal4AgentBootOptions, run_opengujian, run_openpal3, run_openpal3_with_agent, run_openpal4,
+    Pal4AgentBootOptions, install_asset_cache, run_opengujian, run_openpal3,
+    run_openpal3_with_agent, run_openpal4,
     run_openpal4_with_agent, run_openpal5, run_openpal5_with_agent, run_openpal5q,
     run_openpal3a, run_openpal3a_with_agent, run_openpal5q_with_agent, run_openswd5,
     run_openswd5_with_agent, run_title_selection,
@@ -17,12 +18,17 @@ pub fn main() {
     {
         init_logger(None);
         register_opengb_video_decoders();
+        install_asset_cache(false);
         run_openpal4();
     }
 
     #[cfg(not(vita))]
     {
-        let args = std::env::args().collect::<Vec<String>>();
+        // `--rebuild-cache` may appear anywhere; strip it so the
+        // positional game flag and the agent flags parse as before.
+        let mut args = std::env::args().collect::<Vec<String>>();
+        let rebuild_cache = args.iter().any(|arg| arg == "--rebuild-cache");
+        args.retain(|arg| arg != "--rebuild-cache");
         let agent_opts: Option<Pal4AgentBootOptions> = if args.len() > 2
             && matches!(
                 args[1].as_str(),
@@ -39,6 +45,7 @@ pub fn main() {
         // re-register a logger) and leave `/v1/log/tail` empty.
         init_logger(agent_opts.is_some().then(|| AgentLogSink::new(4096)));
         register_opengb_video_decoders();
+        install_asset_cache(rebuild_cache);
 
         if args.len() <= 1 {
             run_title_selection();
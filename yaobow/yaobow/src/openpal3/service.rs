@@ -28,7 +28,6 @@ use radiance::audio::Codec as AudioCodec;
 use radiance::comdef::{IApplication, IApplicationExt, IDirector, ISceneManager, IUiLayer};
 use radiance::input::{InputEngine, SyntheticInputBridge};
 use radiance::radiance::{UiLayerBand, UiLayerHandle};
-use radiance::video::Codec as VideoCodec;
 use radiance_scripting::comdef::services::IAudioSource;
 use radiance_scripting::comdef::services::IVideoHandle;
 use radiance_scripting::comdef::services::ISpriteService;
@@ -227,14 +226,14 @@ impl Pal3Service {
         let engine_rc = self.app.engine();
         let engine = engine_rc.borrow();
         let component_factory = engine.rendering_component_factory();
+        let asset_streamer = engine.asset_streamer();
         drop(engine);
 
         let vfs = init_virtual_fs(&PathBuf::from(asset_path), None);
-        let am = Rc::new(AssetManager::new_for_game(
-            component_factory,
-            Rc::new(vfs),
-            self.last_game.get(),
-        ));
+        let am = Rc::new(
+            AssetManager::new_for_game(component_factory, Rc::new(vfs), self.last_game.get())
+                .with_asset_streamer(asset_streamer),
+        );
         self.asset_managers
             .borrow_mut()
             .insert(asset_path.to_string(), am.clone());
@@ -462,6 +461,14 @@ impl IPal3ServiceImpl for Pal3Service {
                 .add_game_font(&bytes, shared::GameType::PAL3.ui_font_scale());
         }
 
+        // Dialog language pack + script conversion from `[localization]`.
+        shared::localization::install_for_game(game);
+
+        // Saves capture the last presented frame as their thumbnail.
+        shared::saves::install_thumbnail_source(Some(
+            self.app.engine().borrow().rendering_engine(),
+        ));
+
         // Warm the AssetManager up front so the menu + adventure
         // director see a consistent VFS. The debug layer install needs
         // an exclusive engine borrow, which is not safe to take from
@@ -630,13 +637,13 @@ impl IPal3ServiceImpl for Pal3Service {
         let audio_engine = engine.audio_engine();
         drop(engine);
 
-        let reader = asset_mgr.load_movie_data("LOGO");
+        let (reader, codec) = asset_mgr.load_movie_data("LOGO");
         let mut player = component_factory.create_video_player();
         let size = player.play(
             component_factory.clone(),
             audio_engine,
             reader,
-            VideoCodec::Bik,
+            codec,
             false,
         )?;
         self.intro_played.set(true);
@@ -19,7 +19,8 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use agent_server::protocol::{
-    AgentCommand, AgentError, AgentResponse, DialogSnapshot, StateSnapshot,
+    AgentCommand, AgentError, AgentResponse, DialogSnapshot, InventoryEntry, PartyMember,
+    StateSnapshot,
 };
 use radiance::input::Key;
 use shared::agent_common::AgentBridge;
@@ -91,15 +92,70 @@ pub fn dispatch_pal5_command(ctx: &Pal5DispatchCtx, command: AgentCommand) -> Ag
             AgentResponse::Ok
         }
 
+        // PAL5 controls a single party leader; the `player` slot is
+        // accepted but ignored.
+        C::TeleportPlayer(p) => match ctx.context.as_ref() {
+            Some(context) => {
+                use radiance::math::Vec3;
+                let pos = Vec3::new(p.pos[0], p.pos[1], p.pos[2]);
+                if context.borrow_mut().teleport_leader(pos) {
+                    AgentResponse::Ok
+                } else {
+                    AgentResponse::err(AgentError::conflict(
+                        "PAL5 leader not created yet — nothing to teleport",
+                    ))
+                }
+            }
+            None => AgentResponse::err(AgentError::conflict(
+                "PAL5 story director not installed yet",
+            )),
+        },
+
+        C::SaveSlot(p) => match ctx.context.as_ref() {
+            Some(_) if p.slot < 0 => AgentResponse::err(AgentError::bad_request(format!(
+                "PAL5 save slot must be non-negative, got {}",
+                p.slot
+            ))),
+            Some(context) => {
+                context.borrow_mut().save_game(p.slot);
+                AgentResponse::Ok
+            }
+            None => AgentResponse::err(AgentError::conflict(
+                "PAL5 story director not installed yet — nothing to save",
+            )),
+        },
+
+        // PAL5 has no start menu, so the fresh-boot intent resolves to
+        // the same in-place restore as `/v1/load`.
+        C::LoadSlot(p) | C::EnterLoadGame(p) => match ctx.context.as_ref() {
+            Some(context) => {
+                let mut context = context.borrow_mut();
+                match context.read_save(p.slot) {
+                    Ok(state) => match context.load_game(state) {
+                        Ok(()) => {
+                            log::info!("PAL5: loaded save slot {}", p.slot);
+                            AgentResponse::Ok
+                        }
+                        Err(e) => AgentResponse::err(AgentError::conflict(format!(
+                            "PAL5 cannot load slot {} here: {:#}",
+                            p.slot, e
+                        ))),
+                    },
+                    Err(e) => AgentResponse::err(AgentError::bad_request(format!(
+                        "PAL5 cannot load slot {}: {:#}",
+                        p.slot, e
+                    ))),
+                }
+            }
+            None => AgentResponse::err(AgentError::conflict(
+                "PAL5 story director not installed yet — nothing to load into",
+            )),
+        },
+
         // --- not yet implemented for PAL5 ---------------------------------
-        C::TeleportPlayer(_) => AgentResponse::err(AgentError::not_implemented(
-            "PAL5 has no controlled-role teleport surface yet",
+        C::EnterNewGame => AgentResponse::err(AgentError::not_implemented(
+            "PAL5 mode control is not implemented (single bootstrap script)",
         )),
-        C::SaveSlot(_) | C::EnterNewGame | C::EnterLoadGame(_) | C::LoadSlot(_) => {
-            AgentResponse::err(AgentError::not_implemented(
-                "PAL5 save/load + mode control are not implemented (single bootstrap script)",
-            ))
-        }
         C::ExitGame => AgentResponse::err(AgentError::not_implemented(
             "PAL5 exit-to-menu is not implemented (no menu mode)",
         )),
@@ -151,11 +207,39 @@ pub fn build_snapshot(ctx: &Pal5DispatchCtx) -> StateSnapshot {
         if let Some(pos) = context.leader_position() {
             snap.leader_pos = pos;
         }
-        // The VM is "running" whenever it isn't parked in a `Wait`.
-        snap.script_running = !context.is_sleeping();
+        // The VM is "running" whenever it isn't parked in a `Wait` or
+        // behind a streaming scene load.
+        snap.script_running = !context.is_sleeping() && !context.is_scene_loading();
         let (eye, look) = context.camera_pose();
         snap.camera_eye = [eye.x, eye.y, eye.z];
         snap.camera_target = [look.x, look.y, look.z];
+
+        // PAL5 keys the party by role id; `slot` carries it.
+        let state = context.persistent_state();
+        snap.money = state.money();
+        snap.party = state
+            .players()
+            .into_iter()
+            .map(|(role, p)| PartyMember {
+                slot: role.max(0) as usize,
+                level: p.level,
+                hp: p.hp,
+                max_hp: p.max_hp,
+                mp: p.mp,
+                max_mp: p.max_mp,
+                in_team: p.in_team,
+            })
+            .collect();
+        let mut inventory: Vec<InventoryEntry> = state
+            .inventory_iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(id, count)| InventoryEntry {
+                id: *id,
+                count: *count,
+            })
+            .collect();
+        inventory.sort_by_key(|entry| entry.id);
+        snap.inventory = inventory;
     }
 
     // PAL5 dialog text isn't structured for the agent yet; leave the
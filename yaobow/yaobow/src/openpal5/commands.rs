@@ -1,10 +1,10 @@
 //! PAL5 Lua command bridge: `extern "C"` trampolines, namespaced
 //! registration, the dispatch harness, and the `__pal5_load`/
-//! `__pal5_done` engine hooks.
+//! `__pal5_done`/`__pal5_next_event`/`__pal5_npc_busy` engine hooks.
 //!
 //! PAL5's script API is table-namespaced (`global.Wait`, `npc.Create`,
-//! …) and coroutine-driven (`global.Wait` / `WaitForCameraLerp` yield
-//! the script thread). Because Lua 5.0 cannot `yield` across a C-call
+//! …) and coroutine-driven (`global.Wait` / `WaitForCameraLerp` /
+//! `BeginScene` yield the script thread). Because Lua 5.0 cannot `yield` across a C-call
 //! boundary, `Include`/`CallScript` are implemented in the Lua harness
 //! (so the dispatched script's inner `Wait` stays a pure Lua→Lua call),
 //! and only the leaf commands are C functions.
@@ -21,12 +21,27 @@ use super::context::Pal5ScriptContext;
 /// the other namespace tables) already exist here because the C command
 /// registration created them. `__pal5_load` returns the loaded script's
 /// entry function (or nil), so `CallScript` invokes it as a Lua→Lua call
-/// — keeping the inner `Wait` yield legal.
+/// — keeping the inner `Wait` yield legal. Once `NewGame` returns the
+/// leader is under player control and the main loop runs the scripts of
+/// the `map.AddEvent` regions the leader walks into, one at a time.
+/// The `WaitForNpc*` waits poll `__pal5_npc_busy` a frame at a time
+/// for the same reason `CallScript` lives here: they have to yield.
 const HARNESS: &str = r#"
 function global.Include(id)
   __pal5_load(id)
 end
 
+local function __pal5_wait_npc(h, what)
+  while __pal5_npc_busy(h, what) ~= 0 do
+    global.Wait(0)
+  end
+end
+
+function global.WaitForNpcPos(h) __pal5_wait_npc(h, 0) end
+function global.WaitForNpcPos3D(h) __pal5_wait_npc(h, 0) end
+function global.WaitForNpcTurn(h) __pal5_wait_npc(h, 1) end
+function global.WaitForNpcAnim(h) __pal5_wait_npc(h, 2) end
+
 function global.CallScript(id)
   local f = __pal5_load(id)
   if f then return f() end
@@ -35,6 +50,14 @@ end
 function __pal5_main()
   NewGame()
   __pal5_done()
+  while true do
+    local id = __pal5_next_event()
+    if id then
+      global.CallScript(id)
+    else
+      global.Wait(0)
+    end
+  end
 end
 "#;
 
@@ -99,7 +122,8 @@ extern "C" fn pal5_wait(state: *mut lua_State) -> i32 {
     }
 }
 
-/// `global.WaitForCameraLerp()` — yields the remaining camera-lerp time.
+/// `global.WaitForCameraLerp()` — yields the remaining camera lerp or
+/// camera path time.
 extern "C" fn pal5_wait_camera_lerp(state: *mut lua_State) -> i32 {
     unsafe {
         let context = borrow_ctx!(state);
@@ -109,16 +133,56 @@ extern "C" fn pal5_wait_camera_lerp(state: *mut lua_State) -> i32 {
     }
 }
 
-/// `__pal5_done()` — flags the story as finished (the coroutine returns
-/// right after, so it is never resumed again).
+/// `global.BeginScene(id)` — starts streaming the scene in and yields a
+/// zero sleep; the director doesn't resume the script until the load
+/// has finished.
+extern "C" fn pal5_begin_scene(state: *mut lua_State) -> i32 {
+    unsafe {
+        let context = borrow_ctx!(state);
+        let scene_id = lua50_32_sys::lua_tonumber(state, 1);
+        lua50_32_sys::lua_remove(state, 1);
+        context.borrow_mut().global_begin_scene(scene_id);
+        lua50_32_sys::lua_pushnumber(state, 0.0);
+        lua50_32_sys::lua_yield(state, 1)
+    }
+}
+
+/// `__pal5_done()` — the entry script returned; hands the leader to the
+/// player.
 extern "C" fn pal5_done(state: *mut lua_State) -> i32 {
     unsafe {
         let context = borrow_ctx!(state);
-        context.borrow_mut().mark_finished();
+        context.borrow_mut().enter_free_roam();
     }
     0
 }
 
+/// `__pal5_next_event()` — the script id of the next triggered event
+/// region, or nil when none is pending.
+extern "C" fn pal5_next_event(state: *mut lua_State) -> i32 {
+    unsafe {
+        let context = borrow_ctx!(state);
+        match context.borrow_mut().next_event() {
+            Some(id) => lua50_32_sys::lua_pushnumber(state, id as f64),
+            None => lua50_32_sys::lua_pushnil(state),
+        }
+    }
+    1
+}
+
+/// `__pal5_npc_busy(handle, what)` — non-zero while the NPC is still
+/// moving (0), turning (1) or playing a one-shot action (2).
+extern "C" fn pal5_npc_busy(state: *mut lua_State) -> i32 {
+    unsafe {
+        let context = borrow_ctx!(state);
+        let handle = lua50_32_sys::lua_tonumber(state, 1);
+        let what = lua50_32_sys::lua_tonumber(state, 2);
+        let busy = context.borrow_mut().npc_busy(handle, what);
+        lua50_32_sys::lua_pushnumber(state, busy);
+    }
+    1
+}
+
 /// `__pal5_load(id)` — resolve, read (auto-decrypt) and execute a script
 /// by id so its functions are defined on the shared state, then push and
 /// return its entry function (or nil). Used by the harness'
@@ -185,14 +249,16 @@ pub fn create_lua_vm(
     // Engine hooks used by the harness.
     vm.register("__pal5_load", Some(pal5_load));
     vm.register("__pal5_done", Some(pal5_done));
+    vm.register("__pal5_next_event", Some(pal5_next_event));
+    vm.register("__pal5_npc_busy", Some(pal5_npc_busy));
 
     // Coroutine yields.
     vm.register_namespaced("global", "Wait", Some(pal5_wait));
     vm.register_namespaced("global", "WaitForCameraLerp", Some(pal5_wait_camera_lerp));
+    vm.register_namespaced("global", "BeginScene", Some(pal5_begin_scene));
 
     // ---- global ----
     cmd!(vm, "global", "Print", global_print, t: string);
-    cmd!(vm, "global", "BeginScene", global_begin_scene, a: number);
     cmd!(vm, "global", "EndScene", global_end_scene);
     cmd!(vm, "global", "SetWideScreen", global_set_wide_screen, a: number);
     cmd!(vm, "global", "PlayMusic", global_play_music, a: number, b: number);
@@ -214,6 +280,14 @@ pub fn create_lua_vm(
     cmd!(vm, "player", "Remove", player_remove, a: number);
     cmd!(vm, "player", "IsPlayerInTeam", player_is_in_team, a: number => num);
     cmd!(vm, "player", "GetItemCount", player_get_item_count, a: number => num);
+    cmd!(vm, "player", "AddItem", player_add_item, a: number, b: number);
+    cmd!(vm, "player", "AddMagic", player_add_magic, a: number, b: number);
+    cmd!(vm, "player", "AddEquip", player_add_equip, a: number, b: number);
+    cmd!(vm, "player", "AddFormula", player_add_formula, a: number);
+    cmd!(vm, "player", "ChangeHP", player_change_hp, a: number, b: number);
+    cmd!(vm, "player", "ChangeMP", player_change_mp, a: number, b: number);
+    cmd!(vm, "player", "Control", player_control, a: number);
+    cmd!(vm, "player", "Stop", player_stop);
 
     // ---- npc ----
     cmd!(vm, "npc", "Create", npc_create, a: number, b: number, c: number, d: number);
@@ -221,6 +295,15 @@ pub fn create_lua_vm(
     cmd!(vm, "npc", "SetPos3D", npc_set_pos_3d, a: number, b: number, c: number, d: number);
     cmd!(vm, "npc", "MoveTo", npc_move_to, a: number, b: number, c: number);
     cmd!(vm, "npc", "RunTo", npc_run_to, a: number, b: number, c: number);
+    cmd!(vm, "npc", "FloatTo", npc_float_to, a: number, b: number, c: number, d: number);
+    cmd!(vm, "npc", "SetSpeed", npc_set_speed, a: number, b: number);
+    cmd!(vm, "npc", "SetAnim", npc_set_anim, a: number, b: number, c: number);
+    cmd!(vm, "npc", "AddAnimChain", npc_add_anim_chain, a: number, b: number);
+    cmd!(vm, "npc", "TurnTo", npc_turn_to, a: number, b: number);
+    cmd!(vm, "npc", "TurnToNpc", npc_turn_to_npc, a: number, b: number);
+    cmd!(vm, "npc", "TurnToPos", npc_turn_to_pos, a: number, b: number, c: number);
+    cmd!(vm, "npc", "AddPatrolPoint", npc_add_patrol_point, a: number, b: number, c: number);
+    cmd!(vm, "npc", "SetPatrolType", npc_set_patrol_type, a: number, b: number);
     cmd!(vm, "npc", "SetVisible", npc_set_visible, a: number, b: number);
     cmd!(vm, "npc", "Destroy", npc_destroy, a: number);
     cmd!(vm, "npc", "IsCreated", npc_is_created, a: number => num);
@@ -231,6 +314,12 @@ pub fn create_lua_vm(
     cmd!(vm, "camera", "ChangeCameraStaticEye", camera_change_static_eye,
         a: number, b: number, c: number, d: number, e: number, f: number);
     cmd!(vm, "camera", "ResetLerp", camera_reset_lerp, a: number);
+    cmd!(vm, "camera", "ChangeCameraPath", camera_change_path, a: number);
+    cmd!(vm, "camera", "ChangeCameraStaticToNpc", camera_change_static_to_npc,
+        a: number, b: number, c: number, d: number);
+    cmd!(vm, "camera", "Save", camera_save);
+    cmd!(vm, "camera", "Resume", camera_resume);
+    cmd!(vm, "camera", "Shake", camera_shake, a: number, b: number);
 
     // ---- effect ----
     cmd!(vm, "effect", "FadeIn", effect_fade_in, a: number, b: number);
@@ -240,10 +329,13 @@ pub fn create_lua_vm(
     cmd!(vm, "ui", "Dialog", ui_dialog, t: string);
     cmd!(vm, "ui", "Message", ui_message, t: string);
     cmd!(vm, "ui", "CloseStartMenu", ui_close_start_menu);
+    cmd!(vm, "ui", "AddQuest", ui_add_quest, a: number);
 
     // ---- map ----
     cmd!(vm, "map", "ChangeNoScript", map_change_no_script, a: number, b: number);
     cmd!(vm, "map", "GetCurrentMapID", map_get_current_map_id => num);
+    cmd!(vm, "map", "AddEvent", map_add_event,
+        a: number, b: number, c: number, d: number, e: number);
 
     // ---- stubs (logged no-ops for the bootstrap) ----
     for (ns, name) in STUBS {
@@ -255,55 +347,25 @@ pub fn create_lua_vm(
 }
 
 /// Commands registered as no-ops for the first-segment bootstrap. These
-/// either have no visible effect for the intro (item/magic grants,
-/// patrol AI) or are deferred (movies, anim chains, camera paths). They
-/// MUST still be registered so the scripts don't hit `call nil`.
+/// either have no visible effect for the intro or are deferred
+/// (movies). They MUST still be registered so the scripts don't hit
+/// `call nil`.
 const STUBS: &[(&str, &str)] = &[
-    // global waits whose conditions are already satisfied (NPC moves
-    // and anims resolve instantly in the bootstrap) or are deferred.
-    ("global", "WaitForNpcPos"),
-    ("global", "WaitForNpcPos3D"),
-    ("global", "WaitForNpcAnim"),
-    ("global", "WaitForNpcTurn"),
+    // deferred global waits.
     ("global", "WaitForCgEnd"),
-    // player grants / control.
-    ("player", "AddItem"),
-    ("player", "AddMagic"),
-    ("player", "AddEquip"),
-    ("player", "AddFormula"),
-    ("player", "Control"),
-    ("player", "Stop"),
+    // player placement extras.
     ("player", "SetAt"),
-    ("player", "ChangeMP"),
-    ("player", "ChangeHP"),
     // npc behaviour not visible in a single static frame.
     ("npc", "SetAt"),
     ("npc", "SetAtPos"),
-    ("npc", "SetAnim"),
-    ("npc", "AddAnimChain"),
-    ("npc", "TurnTo"),
-    ("npc", "TurnToNpc"),
-    ("npc", "TurnToPos"),
-    ("npc", "AddPatrolPoint"),
-    ("npc", "SetPatrolType"),
-    ("npc", "SetSpeed"),
-    ("npc", "FloatTo"),
     ("npc", "CreateSE"),
     ("npc", "CreateObject"),
     ("npc", "CreateChest"),
-    // camera extras.
-    ("camera", "ChangeCameraPath"),
-    ("camera", "ChangeCameraStaticToNpc"),
-    ("camera", "Save"),
-    ("camera", "Resume"),
-    ("camera", "Shake"),
     // map / effect / ui extras.
-    ("map", "AddEvent"),
     ("map", "CreateNameSE"),
     ("map", "Change"),
     ("effect", "SetFilterTexture"),
     ("ui", "SetDialogFontSize"),
     ("ui", "MirrorPic"),
     ("ui", "Dialog_t"),
-    ("ui", "AddQuest"),
 ];
@@ -6,36 +6,54 @@
 //! in [`super::commands`]; the per-frame driver in [`super::director`].
 //!
 //! This is the "functional bootstrap" surface: the essentials (scene
-//! load, NPC/player create+place, static/lerp camera, fades,
-//! dialog/print, best-effort audio) are implemented; the rest (battle,
-//! item/magic grants, patrol AI, anim chains) are logged no-ops so the
-//! `NewGame -> m001_1` intro runs end-to-end without erroring.
+//! load, NPC/player create+place, static/lerp/path/NPC-tracking camera
+//! with save/resume and shake, fades, dialog/print, best-effort audio,
+//! leader control, `map.AddEvent` regions, NPC movement/animation/
+//! patrols and the party/bag/quest/flag state in
+//! [`Pal5PersistentState`]) are implemented; the rest (battle) are
+//! logged no-ops so the `NewGame -> m001_1` intro runs end-to-end
+//! without erroring.
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::os::raw::c_char;
 use std::rc::Rc;
+use std::time::Duration;
 
 use crosscom::ComRc;
 use encoding::{DecoderTrap, Encoding};
 use radiance::audio::{AudioEngine, AudioMemorySource, AudioSourceState};
-use radiance::comdef::{IEntity, IEntityExt, ISceneManager};
+use radiance::comdef::{IEntity, IEntityExt, ISceneExt, ISceneManager, IUiHost};
+use radiance::components::collision::CollisionWorldComponent;
 use radiance::input::{InputEngine, Key};
 use radiance::math::Vec3;
-use radiance::radiance::UiManager;
+use radiance::radiance::{AssetStreamer, UiManager};
 use radiance::rendering::ComponentFactory;
 use radiance::utils::act_drop::ActDrop;
 use radiance::utils::interp_value::InterpValue;
 
+use shared::comdef::loading::ILoadingOverlay;
 use shared::openpal5::asset_loader::AssetLoader;
-use shared::openpal5::scene::Pal5Scene;
+use shared::openpal5::camera::{CameraShake, CameraTrack};
+use shared::openpal5::npc::{Pal5Npc, PatrolType};
+use shared::openpal5::role::{Pal5RoleController, RoleMotion};
+use shared::openpal5::scene::{Pal5Scene, Pal5SceneLoader};
 use shared::openpal5::script::ScriptIndex;
+use shared::openpal5::states::persistent_state::Pal5PersistentState;
 
 /// The single scene name the bootstrap loads for the first segment.
 /// Map-id → scene-name resolution (`map.xml` / `MapInfo.ini`) is a
 /// follow-up; the狂风寨 intro only needs this one scene.
 const BOOTSTRAP_SCENE: &str = "kuangfengzhai";
 
+/// Main-thread time per frame spent finishing streamed scene assets.
+/// Half a 60 FPS frame, so the window keeps painting during map entry.
+const SCENE_STREAM_BUDGET: Duration = Duration::from_millis(8);
+
+/// `ILoadingOverlay::tick` phase asking for the load to be reported
+/// done (see `openpal4.idl`).
+const OVERLAY_LOAD_READY: i32 = 2;
+
 struct CameraLerp {
     from_eye: Vec3,
     from_look: Vec3,
@@ -45,11 +63,40 @@ struct CameraLerp {
     elapsed: f32,
 }
 
+/// A `camera.ChangeCameraPath` track being played.
+struct CameraPath {
+    track: CameraTrack,
+    elapsed: f32,
+}
+
+/// How far above an NPC's feet `camera.ChangeCameraStaticToNpc` aims.
+const NPC_LOOK_HEIGHT: f32 = 120.0;
+
 struct Dialog {
     name: Option<String>,
     text: String,
 }
 
+/// A `map.AddEvent` region: an XZ rectangle that runs `script_id` when
+/// the leader walks into it.
+struct EventRegion {
+    script_id: i32,
+    min_x: f32,
+    min_z: f32,
+    max_x: f32,
+    max_z: f32,
+}
+
+impl EventRegion {
+    fn contains(&self, pos: &Vec3) -> bool {
+        (self.min_x..=self.max_x).contains(&pos.x) && (self.min_z..=self.max_z).contains(&pos.z)
+    }
+}
+
+/// Half-height of the trigger box built for an event region. Regions
+/// are 2D; the box only needs to span any height the leader walks at.
+const EVENT_REGION_HALF_HEIGHT: f32 = 100000.0;
+
 pub struct Pal5ScriptContext {
     asset_loader: Rc<AssetLoader>,
     script_index: Rc<ScriptIndex>,
@@ -58,18 +105,42 @@ pub struct Pal5ScriptContext {
     audio_engine: Rc<dyn AudioEngine>,
     input_engine: Rc<RefCell<dyn InputEngine>>,
     ui: Rc<UiManager>,
+    asset_streamer: Rc<AssetStreamer>,
+    /// The scripted loading overlay shown over `scene_load`. `None`
+    /// when no script app is installed.
+    loading_overlay: Option<ComRc<ILoadingOverlay>>,
 
     scene: Option<Pal5Scene>,
     scene_loaded: bool,
+    /// In-flight streaming load of `scene`. The director holds the
+    /// script until it completes.
+    scene_load: Option<Pal5SceneLoader>,
 
-    flags: HashMap<i32, i32>,
-    npcs: HashMap<i32, ComRc<IEntity>>,
+    /// Party, bag, quests and story flags; what a save slot holds.
+    persistent_state: Pal5PersistentState,
+    npcs: HashMap<i32, Pal5Npc>,
     players: HashMap<i32, ComRc<IEntity>>,
+    /// The party leader: the first player the scripts create.
+    leader: Option<Pal5RoleController>,
+    /// `player.Control` state; the leader only moves while it is on
+    /// and no event script is running.
+    control_enabled: bool,
+    /// Segment-trigger ids index this list.
+    event_regions: Vec<EventRegion>,
+    pending_events: VecDeque<i32>,
+    event_running: bool,
 
     cam_eye: Vec3,
     cam_look: Vec3,
     pending_lerp_ms: f32,
     lerp: Option<CameraLerp>,
+    camera_path: Option<CameraPath>,
+    /// `camera.ChangeCameraStaticToNpc`: a fixed eye that keeps looking
+    /// at the NPC with this handle.
+    camera_follow: Option<(i32, Vec3)>,
+    /// `camera.Save` / `camera.Resume` stack of `(eye, look)` poses.
+    camera_stack: Vec<(Vec3, Vec3)>,
+    shake: Option<CameraShake>,
 
     actdrop: ActDrop,
     dialog: Option<Dialog>,
@@ -92,6 +163,9 @@ impl Pal5ScriptContext {
         audio_engine: Rc<dyn AudioEngine>,
         input_engine: Rc<RefCell<dyn InputEngine>>,
         ui: Rc<UiManager>,
+        asset_streamer: Rc<AssetStreamer>,
+        loading_overlay: Option<ComRc<ILoadingOverlay>>,
+        persistent_state: Pal5PersistentState,
     ) -> Self {
         let bgm = audio_engine.create_source();
         Self {
@@ -102,15 +176,27 @@ impl Pal5ScriptContext {
             audio_engine,
             input_engine,
             ui,
+            asset_streamer,
+            loading_overlay,
             scene: None,
             scene_loaded: false,
-            flags: HashMap::new(),
+            scene_load: None,
+            persistent_state,
             npcs: HashMap::new(),
             players: HashMap::new(),
+            leader: None,
+            control_enabled: false,
+            event_regions: Vec::new(),
+            pending_events: VecDeque::new(),
+            event_running: false,
             cam_eye: Vec3::new(0.0, 0.0, 0.0),
             cam_look: Vec3::new(0.0, 0.0, 1.0),
             pending_lerp_ms: 0.0,
             lerp: None,
+            camera_path: None,
+            camera_follow: None,
+            camera_stack: Vec::new(),
+            shake: None,
             actdrop: ActDrop::new(),
             dialog: None,
             anykey_latch: false,
@@ -148,11 +234,25 @@ impl Pal5ScriptContext {
         self.finished
     }
 
+    /// Whether a scene is still streaming in.
+    pub fn is_scene_loading(&self) -> bool {
+        self.scene_load.is_some()
+    }
+
+    /// Seconds left on the running camera lerp or camera path, whichever
+    /// ends later; `WaitForCameraLerp` sleeps this long.
     pub fn camera_lerp_remaining(&self) -> f32 {
-        self.lerp
+        let lerp = self
+            .lerp
             .as_ref()
             .map(|l| (l.duration - l.elapsed).max(0.0))
-            .unwrap_or(0.0)
+            .unwrap_or(0.0);
+        let path = self
+            .camera_path
+            .as_ref()
+            .map(|p| (p.track.duration() - p.elapsed).max(0.0))
+            .unwrap_or(0.0);
+        lerp.max(path)
     }
 
     /// Current scene name for the agent snapshot — empty until the
@@ -165,14 +265,150 @@ impl Pal5ScriptContext {
         }
     }
 
-    /// Leader (player 1) world position for the agent snapshot, if the
-    /// player entity has been created.
+    /// Leader world position for the agent snapshot, if the leader has
+    /// been created.
     pub fn leader_position(&self) -> Option<[f32; 3]> {
-        let entity = self.players.get(&1)?;
-        let pos = entity.transform().borrow().position();
+        let leader = self.leader.as_ref()?;
+        let pos = leader.entity().transform().borrow().position();
         Some([pos.x, pos.y, pos.z])
     }
 
+    pub fn persistent_state(&self) -> &Pal5PersistentState {
+        &self.persistent_state
+    }
+
+    /// Record where the party stands and write the persistent state to
+    /// `slot` (agent `/v1/save`).
+    pub fn save_game(&mut self, slot: i32) {
+        self.persistent_state
+            .set_scene_name(self.current_scene_name());
+        self.persistent_state
+            .set_position(self.leader_position().map(|p| Vec3::new(p[0], p[1], p[2])));
+        self.persistent_state.save(slot);
+    }
+
+    /// Read save `slot` without touching the running story; apply it
+    /// with [`Self::load_game`].
+    pub fn read_save(&self, slot: i32) -> anyhow::Result<Pal5PersistentState> {
+        Pal5PersistentState::load(self.persistent_state.app_name(), slot)
+    }
+
+    /// Replace the persistent state with `state` and bring the party on
+    /// screen in line with it (agent `/v1/load`): players that left the
+    /// team are removed, missing members are created, and the party is
+    /// put back where the leader stood. The running Lua story carries
+    /// on; only the state it reads changes.
+    ///
+    /// Fails, leaving the state untouched, before a scene is loaded or
+    /// when the save was made in a different scene: the saved leader
+    /// position belongs to that scene's map.
+    pub fn load_game(&mut self, state: Pal5PersistentState) -> anyhow::Result<()> {
+        if self.scene.is_none() {
+            anyhow::bail!("no scene loaded yet");
+        }
+        if state.scene_name() != self.current_scene_name() {
+            anyhow::bail!(
+                "save was made in scene '{}', the story is in '{}'",
+                state.scene_name(),
+                self.current_scene_name()
+            );
+        }
+        self.persistent_state = state;
+        self.restore_party();
+        Ok(())
+    }
+
+    fn restore_party(&mut self) {
+        let leaving: Vec<i32> = self
+            .players
+            .keys()
+            .copied()
+            .filter(|role| !self.persistent_state.in_team(*role))
+            .collect();
+        for role in leaving {
+            self.despawn_player(role);
+        }
+
+        let position = self.persistent_state.position();
+        let (x, z) = position.map_or((0.0, 0.0), |p| (p.x, p.z));
+        let joining: Vec<i32> = self
+            .persistent_state
+            .players()
+            .into_iter()
+            .filter(|(role, player)| player.in_team && !self.players.contains_key(role))
+            .map(|(role, _)| role)
+            .collect();
+        for role in joining {
+            self.spawn_player(role, x, z);
+        }
+
+        if let Some(leader) = self.leader.as_mut() {
+            leader.stop();
+        }
+        if let Some(position) = position {
+            self.player_set_pos(position.x as f64, position.z as f64);
+        }
+    }
+
+    /// Create role `role_id`'s model at `(x, z)`; the first player
+    /// created becomes the leader.
+    fn spawn_player(&mut self, role_id: i32, x: f32, z: f32) -> bool {
+        let Some(e) = self.spawn_model(role_id, x, z) else {
+            return false;
+        };
+        if self.leader.is_none() {
+            let model_path = self.asset_loader.index[&(role_id as u32)]
+                .file_path
+                .to_string();
+            self.leader = Some(Pal5RoleController::new(
+                &self.asset_loader,
+                e.clone(),
+                &model_path,
+            ));
+        }
+        self.players.insert(role_id, e);
+        true
+    }
+
+    fn despawn_player(&mut self, role_id: i32) {
+        let Some(e) = self.players.remove(&role_id) else {
+            return;
+        };
+        e.set_visible(false);
+        if self
+            .leader
+            .as_ref()
+            .is_some_and(|leader| leader.entity().ptr_value() == e.ptr_value())
+        {
+            self.leader = None;
+        }
+    }
+
+    /// Move the leader to `pos` (agent `/v1/player/teleport`). Returns
+    /// `false` when no leader has been created yet.
+    pub fn teleport_leader(&mut self, pos: Vec3) -> bool {
+        let Some(leader) = self.leader.as_mut() else {
+            return false;
+        };
+        leader.stop();
+        leader.entity().transform().borrow_mut().set_position(&pos);
+        true
+    }
+
+    /// The entry script has returned: hand the leader to the player.
+    pub fn enter_free_roam(&mut self) {
+        log::info!("PAL5: entry script done; leader control enabled");
+        self.set_control(true);
+    }
+
+    /// Pop the next `map.AddEvent` script to run, marking an event as
+    /// running until the harness asks again.
+    pub fn next_event(&mut self) -> Option<i32> {
+        let next = self.pending_events.pop_front();
+        self.event_running = next.is_some();
+        next
+    }
+
     /// Agent fast-forward tick: collapse any pending `Wait` sleep and
     /// dismiss the current dialog so the Lua VM resumes immediately
     /// this frame. Mirrors PAL3's SCE fast-forward, which skips
@@ -180,10 +416,20 @@ impl Pal5ScriptContext {
     pub fn fast_forward_skip(&mut self) {
         self.sleep_sec = 0.0;
         self.dialog = None;
+        for npc in self.npcs.values_mut() {
+            npc.finish();
+        }
+        if let Some(path) = self.camera_path.as_mut() {
+            path.elapsed = path.track.duration();
+        }
+        if let Some(shake) = self.shake.as_mut() {
+            shake.update(f32::INFINITY);
+        }
     }
 
     /// Per-frame update (runs every frame, even while sleeping).
     pub fn update(&mut self, delta_sec: f32) {
+        self.persistent_state.add_playtime(delta_sec);
         if self.is_sleeping() {
             self.sleep_sec -= delta_sec;
             // A keypress fast-forwards the current Wait (skip dialog).
@@ -192,12 +438,125 @@ impl Pal5ScriptContext {
             }
         }
 
-        self.update_camera_lerp(delta_sec);
+        self.update_scene_load();
+        self.update_loading_overlay(delta_sec);
+        self.update_leader(delta_sec);
+        self.update_npcs(delta_sec);
+        self.update_camera(delta_sec);
         self.actdrop.update(self.ui.ui(), delta_sec);
         self.update_audio();
         self.update_dialog();
     }
 
+    fn update_scene_load(&mut self) {
+        let Some(load) = self.scene_load.as_mut() else {
+            return;
+        };
+        let done = load.step(SCENE_STREAM_BUDGET);
+        if let Some(overlay) = &self.loading_overlay {
+            overlay.set_progress(load.progress());
+        }
+        if done {
+            log::info!("PAL5: loaded scene '{}'", load.scene_name());
+            self.scene_load = None;
+        }
+    }
+
+    /// Drive the loading overlay's state machine; once the scene has
+    /// finished streaming it holds briefly and dismisses itself.
+    fn update_loading_overlay(&self, delta_sec: f32) {
+        let Some(overlay) = &self.loading_overlay else {
+            return;
+        };
+        if !overlay.is_active() {
+            return;
+        }
+        if overlay.tick(delta_sec) == OVERLAY_LOAD_READY && self.scene_load.is_none() {
+            overlay.notify_load_complete();
+        }
+    }
+
+    /// Paint the loading overlay while a scene load is on screen.
+    pub fn render_loading_overlay(&self, ui: ComRc<IUiHost>, delta_sec: f32) {
+        if let Some(overlay) = &self.loading_overlay {
+            if overlay.is_active() {
+                overlay.render(ui, delta_sec);
+            }
+        }
+    }
+
+    fn update_npcs(&mut self, delta_sec: f32) {
+        let Some(scene) = self.scene.as_ref() else {
+            return;
+        };
+        let heights = scene.heights.borrow();
+        for npc in self.npcs.values_mut() {
+            npc.update(&heights, delta_sec);
+        }
+    }
+
+    fn update_leader(&mut self, delta_sec: f32) {
+        let controllable = self.control_enabled
+            && !self.event_running
+            && self.dialog.is_none()
+            && self.scene_load.is_none();
+        let (Some(leader), Some(scene)) = (self.leader.as_mut(), self.scene.as_ref()) else {
+            return;
+        };
+        if !controllable {
+            return;
+        }
+
+        let fired = leader.update(
+            self.input_engine.clone(),
+            &scene.scene,
+            &scene.heights.borrow(),
+            delta_sec,
+        );
+        (self.cam_eye, self.cam_look) = leader.camera_pose();
+
+        // A segment trigger fires on the way in and on the way out;
+        // only entering the region runs its script.
+        let Some(region) = fired.and_then(|id| self.event_regions.get(id as usize)) else {
+            return;
+        };
+        let pos = leader.entity().transform().borrow().position();
+        if region.contains(&pos) && !self.pending_events.contains(&region.script_id) {
+            log::info!(
+                "PAL5: leader entered event region -> script {}",
+                region.script_id
+            );
+            self.pending_events.push_back(region.script_id);
+        }
+    }
+
+    fn set_control(&mut self, on: bool) {
+        if let Some(leader) = self.leader.as_mut() {
+            if !on {
+                leader.stop();
+            } else if !self.control_enabled {
+                // Start the follow camera from the last scripted shot.
+                leader.align_camera(&self.cam_eye);
+            }
+        }
+        if on {
+            // The leader's follow camera owns the shot from here on.
+            self.camera_path = None;
+            self.camera_follow = None;
+        }
+        self.control_enabled = on;
+    }
+
+    /// Scripted camera motion, then the shake on top of it. None of it
+    /// runs while the debug camera freezes the plot (the director skips
+    /// `update` entirely).
+    fn update_camera(&mut self, delta_sec: f32) {
+        self.update_camera_lerp(delta_sec);
+        self.update_camera_path(delta_sec);
+        self.update_camera_follow();
+        self.update_camera_shake(delta_sec);
+    }
+
     fn update_camera_lerp(&mut self, delta_sec: f32) {
         let Some(lerp) = self.lerp.as_mut() else {
             return;
@@ -217,6 +576,51 @@ impl Pal5ScriptContext {
         }
     }
 
+    fn update_camera_path(&mut self, delta_sec: f32) {
+        let Some(path) = self.camera_path.as_mut() else {
+            return;
+        };
+        path.elapsed += delta_sec;
+        let (eye, look) = path.track.sample(path.elapsed);
+        let done = path.elapsed >= path.track.duration();
+        self.apply_camera(eye, look);
+        if done {
+            self.camera_path = None;
+        }
+    }
+
+    fn update_camera_follow(&mut self) {
+        let Some((handle, eye)) = self.camera_follow else {
+            return;
+        };
+        let Some(npc) = self.npcs.get(&handle) else {
+            self.camera_follow = None;
+            return;
+        };
+        let mut look = npc.position();
+        look.y += NPC_LOOK_HEIGHT;
+        self.apply_camera(eye, look);
+    }
+
+    /// Offsets the scene camera from the scripted pose without touching
+    /// `cam_eye`/`cam_look`, so the jitter never accumulates; the last
+    /// frame's offset is zero, which puts the camera back.
+    fn update_camera_shake(&mut self, delta_sec: f32) {
+        let Some(shake) = self.shake.as_mut() else {
+            return;
+        };
+        let running = shake.update(delta_sec);
+        let offset = shake.offset();
+        if !running {
+            self.shake = None;
+        }
+        if let Some(cam) = self.scene_manager.camera() {
+            let (eye, look) = (self.cam_eye, self.cam_look);
+            cam.set_position(eye.x + offset.x, eye.y + offset.y, eye.z + offset.z);
+            cam.look_at(look.x + offset.x, look.y + offset.y, look.z + offset.z);
+        }
+    }
+
     fn update_audio(&mut self) {
         for s in &self.sounds {
             s.borrow_mut().update();
@@ -270,14 +674,25 @@ impl Pal5ScriptContext {
             return;
         }
         self.scene_loaded = true;
-        match Pal5Scene::load(&self.asset_loader, BOOTSTRAP_SCENE) {
-            Ok(scene) => {
-                self.scene_manager.push_scene(scene.scene.clone());
-                self.scene = Some(scene);
-                log::info!("PAL5: loaded bootstrap scene '{}'", BOOTSTRAP_SCENE);
-            }
-            Err(e) => log::error!("PAL5: failed to load scene '{}': {}", BOOTSTRAP_SCENE, e),
+
+        // The scene is pushed right away and fills in as the load
+        // streams; `update_scene_load` drives it to completion.
+        let load = Pal5SceneLoader::new(
+            self.asset_loader.clone(),
+            self.asset_streamer.clone(),
+            BOOTSTRAP_SCENE,
+        );
+        let scene = load.scene();
+        self.scene_manager.push_scene(scene.clone());
+        self.scene = Some(Pal5Scene {
+            scene,
+            heights: load.heights(),
+        });
+        self.scene_load = Some(load);
+        if let Some(overlay) = &self.loading_overlay {
+            overlay.request(BOOTSTRAP_SCENE, "");
         }
+        log::info!("PAL5: streaming bootstrap scene '{}'", BOOTSTRAP_SCENE);
     }
 
     fn spawn_model(&self, model_id: i32, x: f32, z: f32) -> Option<ComRc<IEntity>> {
@@ -288,10 +703,11 @@ impl Pal5ScriptContext {
         }
         match self.asset_loader.load_model(&file_path) {
             Ok(entity) => {
-                entity
-                    .transform()
-                    .borrow_mut()
-                    .set_position(&Vec3::new(x, 0.0, z));
+                entity.transform().borrow_mut().set_position(&Vec3::new(
+                    x,
+                    self.ground_height(x, z),
+                    z,
+                ));
                 if let Some(scene) = &self.scene {
                     scene.scene.add_entity(entity.clone());
                 }
@@ -304,6 +720,14 @@ impl Pal5ScriptContext {
         }
     }
 
+    /// Terrain height at `(x, z)`, or 0 off the map / before a scene.
+    fn ground_height(&self, x: f32, z: f32) -> f32 {
+        self.scene
+            .as_ref()
+            .and_then(|scene| scene.heights.borrow().height_at(x, z))
+            .unwrap_or(0.0)
+    }
+
     // ---- command handlers: global --------------------------------
 
     pub fn global_print(&mut self, text: *const c_char) {
@@ -353,27 +777,37 @@ impl Pal5ScriptContext {
     // ---- command handlers: flag ----------------------------------
 
     pub fn flag_set_value(&mut self, flag: f64, value: f64) {
-        self.flags.insert(flag as i32, value as i32);
+        self.persistent_state.set_flag(flag as i32, value as i32);
     }
 
     pub fn flag_get_value(&mut self, flag: f64) -> f64 {
-        *self.flags.get(&(flag as i32)).unwrap_or(&0) as f64
+        self.persistent_state.flag(flag as i32) as f64
     }
 
     // ---- command handlers: player --------------------------------
 
     pub fn player_create(&mut self, role_id: f64, x: f64, z: f64) {
-        if let Some(e) = self.spawn_model(role_id as i32, x as f32, z as f32) {
-            self.players.insert(role_id as i32, e);
+        if self.spawn_player(role_id as i32, x as f32, z as f32) {
+            self.persistent_state.set_in_team(role_id as i32, true);
         }
     }
 
     pub fn player_set_pos(&mut self, x: f64, z: f64) {
-        // Player 1 is the leader; reposition every known player entity.
+        // Reposition the whole party; the leader leads.
+        let (x, z) = (x as f32, z as f32);
+        let y = self.ground_height(x, z);
         for e in self.players.values() {
-            e.transform()
-                .borrow_mut()
-                .set_position(&Vec3::new(x as f32, 0.0, z as f32));
+            e.transform().borrow_mut().set_position(&Vec3::new(x, y, z));
+        }
+    }
+
+    pub fn player_control(&mut self, on: f64) {
+        self.set_control(on != 0.0);
+    }
+
+    pub fn player_stop(&mut self) {
+        if let Some(leader) = self.leader.as_mut() {
+            leader.stop();
         }
     }
 
@@ -384,62 +818,176 @@ impl Pal5ScriptContext {
     }
 
     pub fn player_remove(&mut self, role_id: f64) {
-        if let Some(e) = self.players.remove(&(role_id as i32)) {
-            e.set_visible(false);
-        }
+        self.persistent_state.set_in_team(role_id as i32, false);
+        self.despawn_player(role_id as i32);
     }
 
-    pub fn player_is_in_team(&mut self, _role_id: f64) -> f64 {
-        1.0
+    pub fn player_is_in_team(&mut self, role_id: f64) -> f64 {
+        self.persistent_state.in_team(role_id as i32) as i32 as f64
     }
 
-    pub fn player_get_item_count(&mut self, _item_id: f64) -> f64 {
-        0.0
+    pub fn player_get_item_count(&mut self, item_id: f64) -> f64 {
+        self.persistent_state.item_count(item_id as i32) as f64
+    }
+
+    pub fn player_add_item(&mut self, item_id: f64, count: f64) {
+        self.persistent_state.add_item(item_id as i32, count as i32);
+    }
+
+    pub fn player_add_magic(&mut self, role_id: f64, magic_id: f64) {
+        self.persistent_state
+            .add_magic(role_id as i32, magic_id as i32);
+    }
+
+    pub fn player_add_equip(&mut self, role_id: f64, equip_id: f64) {
+        self.persistent_state
+            .add_player_equip(role_id as i32, equip_id as i32);
+    }
+
+    pub fn player_add_formula(&mut self, formula_id: f64) {
+        self.persistent_state.add_formula(formula_id as i32);
+    }
+
+    pub fn player_change_hp(&mut self, role_id: f64, delta: f64) {
+        self.persistent_state
+            .change_hp(role_id as i32, delta as i32);
+    }
+
+    pub fn player_change_mp(&mut self, role_id: f64, delta: f64) {
+        self.persistent_state
+            .change_mp(role_id as i32, delta as i32);
     }
 
     // ---- command handlers: npc -----------------------------------
 
     pub fn npc_create(&mut self, model_id: f64, handle: f64, x: f64, z: f64) {
-        if let Some(e) = self.spawn_model(model_id as i32, x as f32, z as f32) {
-            self.npcs.insert(handle as i32, e);
-        }
+        let Some(entity) = self.spawn_model(model_id as i32, x as f32, z as f32) else {
+            return;
+        };
+        let model_path = self.asset_loader.index[&(model_id as u32)]
+            .file_path
+            .to_string();
+        let npc = Pal5Npc::new(self.asset_loader.clone(), entity, &model_path);
+        self.npcs.insert(handle as i32, npc);
     }
 
     pub fn npc_set_pos(&mut self, handle: f64, x: f64, z: f64) {
-        if let Some(e) = self.npcs.get(&(handle as i32)) {
-            e.transform()
-                .borrow_mut()
-                .set_position(&Vec3::new(x as f32, 0.0, z as f32));
+        let y = self.ground_height(x as f32, z as f32);
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.set_position(&Vec3::new(x as f32, y, z as f32));
         }
     }
 
     pub fn npc_set_pos_3d(&mut self, handle: f64, x: f64, y: f64, z: f64) {
-        if let Some(e) = self.npcs.get(&(handle as i32)) {
-            e.transform()
-                .borrow_mut()
-                .set_position(&Vec3::new(x as f32, y as f32, z as f32));
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.set_position(&Vec3::new(x as f32, y as f32, z as f32));
         }
     }
 
     pub fn npc_move_to(&mut self, handle: f64, x: f64, z: f64) {
-        // Movement snaps instantly for the bootstrap (WaitForNpcPos is
-        // then immediately satisfied).
-        self.npc_set_pos(handle, x, z);
+        self.npc_walk(handle, x, z, RoleMotion::Walk);
     }
 
     pub fn npc_run_to(&mut self, handle: f64, x: f64, z: f64) {
-        self.npc_set_pos(handle, x, z);
+        self.npc_walk(handle, x, z, RoleMotion::Run);
+    }
+
+    fn npc_walk(&mut self, handle: f64, x: f64, z: f64, motion: RoleMotion) {
+        let y = self.ground_height(x as f32, z as f32);
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.move_to(Vec3::new(x as f32, y, z as f32), motion);
+        }
+    }
+
+    pub fn npc_float_to(&mut self, handle: f64, x: f64, y: f64, z: f64) {
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.float_to(Vec3::new(x as f32, y as f32, z as f32));
+        }
+    }
+
+    pub fn npc_set_speed(&mut self, handle: f64, speed: f64) {
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.set_speed(speed as f32);
+        }
+    }
+
+    pub fn npc_set_anim(&mut self, handle: f64, anim: f64, looping: f64) {
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.set_action(&Self::act_name(anim), looping != 0.0);
+        }
+    }
+
+    pub fn npc_add_anim_chain(&mut self, handle: f64, anim: f64) {
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.add_chain(&Self::act_name(anim));
+        }
+    }
+
+    /// PAL5 scripts address actions by number; the model directories
+    /// name them `C01.anm`, `C02.anm`, ...
+    fn act_name(anim: f64) -> String {
+        format!("C{:02}", anim as i32)
+    }
+
+    pub fn npc_turn_to(&mut self, handle: f64, degrees: f64) {
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.turn_to((degrees as f32).to_radians());
+        }
+    }
+
+    pub fn npc_turn_to_npc(&mut self, handle: f64, other: f64) {
+        let Some(target) = self.npcs.get(&(other as i32)).map(|npc| npc.position()) else {
+            return;
+        };
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.turn_towards(&target);
+        }
+    }
+
+    pub fn npc_turn_to_pos(&mut self, handle: f64, x: f64, z: f64) {
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            let y = npc.position().y;
+            npc.turn_towards(&Vec3::new(x as f32, y, z as f32));
+        }
+    }
+
+    pub fn npc_add_patrol_point(&mut self, handle: f64, x: f64, z: f64) {
+        let y = self.ground_height(x as f32, z as f32);
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.add_patrol_point(Vec3::new(x as f32, y, z as f32));
+        }
+    }
+
+    pub fn npc_set_patrol_type(&mut self, handle: f64, patrol_type: f64) {
+        if let Some(npc) = self.npcs.get_mut(&(handle as i32)) {
+            npc.set_patrol_type(PatrolType::from_i32(patrol_type as i32));
+        }
+    }
+
+    /// Backs the harness `WaitForNpc*` loops: 0 = still moving,
+    /// 1 = still turning, 2 = still playing a one-shot action/chain.
+    /// Unknown handles never block.
+    pub fn npc_busy(&mut self, handle: f64, what: f64) -> f64 {
+        let Some(npc) = self.npcs.get(&(handle as i32)) else {
+            return 0.0;
+        };
+        let busy = match what as i32 {
+            0 => npc.is_moving(),
+            1 => npc.is_turning(),
+            _ => npc.is_acting(),
+        };
+        busy as i32 as f64
     }
 
     pub fn npc_set_visible(&mut self, handle: f64, visible: f64) {
-        if let Some(e) = self.npcs.get(&(handle as i32)) {
-            e.set_visible(visible != 0.0);
+        if let Some(npc) = self.npcs.get(&(handle as i32)) {
+            npc.entity().set_visible(visible != 0.0);
         }
     }
 
     pub fn npc_destroy(&mut self, handle: f64) {
-        if let Some(e) = self.npcs.remove(&(handle as i32)) {
-            e.set_visible(false);
+        if let Some(npc) = self.npcs.remove(&(handle as i32)) {
+            npc.entity().set_visible(false);
         }
     }
 
@@ -452,6 +1000,14 @@ impl Pal5ScriptContext {
     pub fn camera_change_static(&mut self, ex: f64, ey: f64, ez: f64, lx: f64, ly: f64, lz: f64) {
         let eye = Vec3::new(ex as f32, ey as f32, ez as f32);
         let look = Vec3::new(lx as f32, ly as f32, lz as f32);
+        self.move_camera(eye, look);
+    }
+
+    /// Cut (or, after `ResetLerp`, lerp) to a static shot, ending any
+    /// path or NPC tracking.
+    fn move_camera(&mut self, eye: Vec3, look: Vec3) {
+        self.camera_path = None;
+        self.camera_follow = None;
         if self.pending_lerp_ms > 0.0 {
             self.lerp = Some(CameraLerp {
                 from_eye: self.cam_eye,
@@ -484,13 +1040,66 @@ impl Pal5ScriptContext {
         self.pending_lerp_ms = ms as f32;
     }
 
+    pub fn camera_change_path(&mut self, path_id: f64) {
+        let Some(track) = self
+            .asset_loader
+            .load_camera_path(path_id as u32)
+            .and_then(|path| CameraTrack::new(&path))
+        else {
+            return;
+        };
+        self.lerp = None;
+        self.camera_follow = None;
+        self.pending_lerp_ms = 0.0;
+        let (eye, look) = track.sample(0.0);
+        self.apply_camera(eye, look);
+        self.camera_path = Some(CameraPath {
+            track,
+            elapsed: 0.0,
+        });
+    }
+
+    pub fn camera_change_static_to_npc(&mut self, handle: f64, ex: f64, ey: f64, ez: f64) {
+        if !self.npcs.contains_key(&(handle as i32)) {
+            return;
+        }
+        self.lerp = None;
+        self.camera_path = None;
+        self.pending_lerp_ms = 0.0;
+        let eye = Vec3::new(ex as f32, ey as f32, ez as f32);
+        self.camera_follow = Some((handle as i32, eye));
+        self.update_camera_follow();
+    }
+
+    pub fn camera_save(&mut self) {
+        self.camera_stack.push((self.cam_eye, self.cam_look));
+    }
+
+    /// Return to the last `camera.Save`d shot, lerping if a `ResetLerp`
+    /// is pending.
+    pub fn camera_resume(&mut self) {
+        let Some((eye, look)) = self.camera_stack.pop() else {
+            log::warn!("PAL5: camera.Resume with no saved camera");
+            return;
+        };
+        self.move_camera(eye, look);
+    }
+
+    pub fn camera_shake(&mut self, ms: f64, amplitude: f64) {
+        self.shake = Some(CameraShake::new(amplitude as f32, ms as f32 / 1000.0));
+    }
+
     /// Place the camera at an absolute pose (used by the agent server's
-    /// `/v1/camera/pose`). Cancels any in-flight lerp and pending-lerp
-    /// request so the pose is not immediately animated away. Stable only
+    /// `/v1/camera/pose`). Cancels any in-flight lerp, path, NPC
+    /// tracking, shake and pending-lerp request so the pose is not
+    /// immediately animated away. Stable only
     /// while the debug camera is enabled (plot frozen); otherwise the
     /// next scripted camera command will overwrite it.
     pub fn set_camera_pose(&mut self, eye: Vec3, look: Vec3) {
         self.lerp = None;
+        self.camera_path = None;
+        self.camera_follow = None;
+        self.shake = None;
         self.pending_lerp_ms = 0.0;
         self.apply_camera(eye, look);
     }
@@ -518,7 +1127,7 @@ impl Pal5ScriptContext {
     pub fn ui_dialog(&mut self, text: *const c_char) {
         self.dialog = Some(Dialog {
             name: None,
-            text: decode_gbk(text),
+            text: shared::localization::localize(&decode_gbk(text)).into_owned(),
         });
     }
 
@@ -528,6 +1137,10 @@ impl Pal5ScriptContext {
 
     pub fn ui_close_start_menu(&mut self) {}
 
+    pub fn ui_add_quest(&mut self, quest_id: f64) {
+        self.persistent_state.add_quest(quest_id as i32);
+    }
+
     // ---- command handlers: map -----------------------------------
 
     pub fn map_change_no_script(&mut self, _map_id: f64, _sub_id: f64) {
@@ -537,6 +1150,43 @@ impl Pal5ScriptContext {
     pub fn map_get_current_map_id(&mut self) -> f64 {
         1.0
     }
+
+    /// `map.AddEvent(script, x1, z1, x2, z2)`: run `script` when the
+    /// leader walks into the rectangle.
+    pub fn map_add_event(&mut self, script_id: f64, x1: f64, z1: f64, x2: f64, z2: f64) {
+        let Some(scene) = &self.scene else {
+            log::warn!("PAL5: AddEvent({}) before any scene", script_id);
+            return;
+        };
+        let region = EventRegion {
+            script_id: script_id as i32,
+            min_x: x1.min(x2) as f32,
+            min_z: z1.min(z2) as f32,
+            max_x: x1.max(x2) as f32,
+            max_z: z1.max(z2) as f32,
+        };
+
+        let ring = [
+            (region.min_x, region.min_z),
+            (region.max_x, region.min_z),
+            (region.max_x, region.max_z),
+            (region.min_x, region.max_z),
+        ];
+        let corners = [-EVENT_REGION_HALF_HEIGHT, EVENT_REGION_HALF_HEIGHT]
+            .iter()
+            .flat_map(|&y| ring.iter().map(move |&(x, z)| Vec3::new(x, y, z)))
+            .collect();
+        scene
+            .scene
+            .collision_world()
+            .inner::<CollisionWorldComponent>()
+            .attach_segment_trigger(
+                corners,
+                self.event_regions.len() as i64,
+                region.script_id.to_string(),
+            );
+        self.event_regions.push(region);
+    }
 }
 
 fn lerp_vec3(a: &Vec3, b: &Vec3, t: f32) -> Vec3 {
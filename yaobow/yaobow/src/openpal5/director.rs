@@ -7,7 +7,7 @@ use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 use crosscom::ComRc;
-use radiance::comdef::{IDirector, IDirectorImpl, ISceneExt, ISceneManager};
+use radiance::comdef::{IDirector, IDirectorImpl, ISceneExt, ISceneManager, IUiHost, IUiLayerImpl};
 use radiance::input::{InputEngine, Key};
 use radiance::math::Vec3;
 use radiance::radiance::UiManager;
@@ -67,8 +67,8 @@ impl Pal5StoryDirector {
         let vm = create_lua_vm(context.clone())?;
 
         // Load the `NewGame` entry script, then enter via the harness'
-        // `__pal5_main` wrapper (which calls `NewGame()` and flags
-        // completion when it returns).
+        // `__pal5_main` wrapper (which calls `NewGame()`, then hands the
+        // leader to the player and runs triggered event scripts).
         let (_name, source) = {
             let c = context.borrow();
             c.script_index()
@@ -212,7 +212,13 @@ impl IDirectorImpl for Pal5StoryDirector {
             self.context.borrow_mut().fast_forward_skip();
         }
 
-        if advance && !self.context.borrow().is_sleeping() {
+        // `BeginScene` yields right after starting a streamed scene
+        // load; the script stays parked until the scene is complete.
+        let held = {
+            let context = self.context.borrow();
+            context.is_sleeping() || context.is_scene_loading()
+        };
+        if advance && !held {
             match self.vm.execute() {
                 Ok(sleep) => {
                     let sleep = if fast_forward { 0.0 } else { sleep };
@@ -230,3 +236,9 @@ impl IDirectorImpl for Pal5StoryDirector {
 
     fn deactivate(&self) {}
 }
+
+impl IUiLayerImpl for Pal5StoryDirector {
+    fn render(&self, ui: ComRc<IUiHost>, dt: f32) {
+        self.context.borrow().render_loading_overlay(ui, dt);
+    }
+}
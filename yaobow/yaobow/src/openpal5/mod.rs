@@ -23,8 +23,10 @@ use radiance::scene::CoreScene;
 
 use shared::GameType;
 use shared::agent_common::AgentBridge;
+use shared::comdef::loading::ILoadingOverlay;
 use shared::openpal5::asset_loader::AssetLoader;
 use shared::openpal5::script::ScriptIndex;
+use shared::openpal5::states::persistent_state::Pal5PersistentState;
 
 use context::Pal5ScriptContext;
 
@@ -40,6 +42,9 @@ use context::Pal5ScriptContext;
 /// effect) and the context reads the bridge's synthetic-input overlay
 /// so `/v1/input/*` reaches the Lua VM.
 ///
+/// Scene loads report their progress through `loading_overlay` when
+/// one is given.
+///
 /// Returns `None` when the scriptlist can't be read (e.g. missing PAL5
 /// assets at `asset_path`) so the caller can surface a clear error.
 pub fn create_story_director(
@@ -47,6 +52,7 @@ pub fn create_story_director(
     asset_path: &str,
     game: GameType,
     agent_bridge: Option<Rc<AgentBridge>>,
+    loading_overlay: Option<ComRc<ILoadingOverlay>>,
 ) -> Option<ComRc<IDirector>> {
     let engine_rc = app.engine();
     let engine = engine_rc.borrow();
@@ -54,6 +60,7 @@ pub fn create_story_director(
     let audio_engine = engine.audio_engine();
     let scene_manager = engine.scene_manager().clone();
     let ui = engine.ui_manager();
+    let asset_streamer = engine.asset_streamer();
     drop(engine);
 
     // Synthetic-input overlay when the agent server is enabled,
@@ -91,6 +98,9 @@ pub fn create_story_director(
         audio_engine,
         input_engine.clone(),
         ui.clone(),
+        asset_streamer,
+        loading_overlay,
+        Pal5PersistentState::new(game.app_name().to_string()),
     );
 
     match Pal5StoryDirector::with_agent_bridge(
@@ -32,6 +32,7 @@ use radiance::comdef::{IApplication, IApplicationExt, IDirector};
 
 use shared::GameType;
 use shared::agent_common::AgentBridge;
+use shared::comdef::loading::ILoadingOverlay;
 use shared::openpal5::comdef::{IPal5Service, IPal5ServiceImpl};
 
 use super::agent::{Pal5DispatchCtx, dispatch_pal5_command};
@@ -44,6 +45,9 @@ pub struct Pal5Service {
     /// constructed `Pal5StoryDirector` honour pause/step + see
     /// synthetic input.
     agent_bridge: RefCell<Option<Rc<AgentBridge>>>,
+    /// Script-built loading overlay handed to every story director for
+    /// its scene loads. Set alongside the PAL4 script factory.
+    loading_overlay: RefCell<Option<ComRc<ILoadingOverlay>>>,
 }
 
 ComObject_Pal5Service!(super::Pal5Service);
@@ -53,6 +57,7 @@ impl Pal5Service {
         ComRc::from_object(Self {
             app,
             agent_bridge: RefCell::new(None),
+            loading_overlay: RefCell::new(None),
         })
     }
 
@@ -64,6 +69,12 @@ impl Pal5Service {
         *self.agent_bridge.borrow_mut() = Some(bridge);
     }
 
+    /// Install (or, with `None`, drop) the loading overlay the next
+    /// `create_director` shows over scene loads.
+    pub fn set_loading_overlay(&self, overlay: Option<ComRc<ILoadingOverlay>>) {
+        *self.loading_overlay.borrow_mut() = overlay;
+    }
+
     /// Drain the agent-server command queue, dispatch each command
     /// against PAL5 state, then publish frame telemetry and clear
     /// synthetic-input edges. Called once per frame by
@@ -129,8 +140,10 @@ impl IPal5ServiceImpl for Pal5Service {
             radiance_scripting::services::game_registry::ordinal_to_config_key(game_ordinal as i32)
                 .and_then(GameType::from_config_key)
                 .unwrap_or(GameType::PAL5);
+        shared::localization::install_for_game(game);
 
         let bridge = self.agent_bridge.borrow().clone();
-        super::create_story_director(self.app.clone(), asset_path, game, bridge)
+        let overlay = self.loading_overlay.borrow().clone();
+        super::create_story_director(self.app.clone(), asset_path, game, bridge, overlay)
     }
 }
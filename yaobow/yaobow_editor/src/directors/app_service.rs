@@ -2,6 +2,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use crosscom::ComRc;
+use p7::interpreter::context::Data;
 use radiance::comdef::{IApplication, IApplicationExt, IDirector};
 use radiance_scripting::comdef::services::{IAppService, IAppServiceImpl};
 use radiance_scripting::services::ImguiTextureCache;
@@ -79,6 +80,7 @@ impl IAppServiceImpl for AppService {
                     self.app.engine().borrow().audio_engine(),
                     self.app.engine().borrow().input_engine(),
                     raw_vfs,
+                    self.app.engine().borrow().asset_streamer(),
                 ))
             }
             GameType::PAL5 => DevToolsAssetLoader::Pal5(
@@ -95,7 +97,8 @@ impl IAppServiceImpl for AppService {
                 shared::openpal3::asset_manager::AssetManager::new(
                     factory.clone(),
                     Rc::new(raw_vfs),
-                ),
+                )
+                .with_asset_streamer(self.app.engine().borrow().asset_streamer()),
             )),
         };
         let vfs_rc = asset_loader.vfs_rc();
@@ -143,7 +146,10 @@ impl IAppServiceImpl for AppService {
             .ok()?;
         let director_data = self
             .script_host
-            .call_returning_data("init_main_editor", vec![host_box])
+            .call_returning_data(
+                "init_main_editor",
+                vec![host_box, Data::Int(ordinal as i64)],
+            )
             .ok()?;
 
         // Reverse-wrap via the runtime-typed CCW factory. The fat
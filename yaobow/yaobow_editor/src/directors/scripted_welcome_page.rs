@@ -14,9 +14,11 @@ use std::rc::Rc;
 use crosscom::ComRc;
 use radiance::asset::AssetManager;
 use radiance::comdef::{IApplication, IApplicationExt, IDirector};
+use radiance_scripting::hot_reload::install_hot_reloader;
+use radiance_scripting::mods::mod_host_arg;
 use radiance_scripting::services::ImguiTextureCache;
 use radiance_scripting::{
-    ScriptHost, bootstrap_script_root_from_path, install_imgui_ui_renderer_with_cache,
+    HostError, ScriptHost, bootstrap_script_root_from_path, install_imgui_ui_renderer_with_cache,
     wrap_director,
 };
 use shared::config::YaobowConfig;
@@ -33,6 +35,8 @@ use shared::config_service::ConfigService;
 const EDITOR_HOST_CONTEXT_TYPE_TAG: &str =
     "yaobow_editor.comdef.editor_services.IEditorHostContext";
 
+const WELCOME_SCRIPT_ROOT: &str = "/yaobow_editor/main.p7";
+
 pub struct ScriptedWelcomePage;
 
 impl ScriptedWelcomePage {
@@ -77,14 +81,6 @@ impl ScriptedWelcomePage {
         // root via `bootstrap_script_root_from_path`.
         let assets = build_editor_script_assets();
         host.set_script_assets(assets);
-        let director_data = bootstrap_script_root_from_path(
-            &host,
-            "/yaobow_editor/main.p7",
-            host_ctx,
-            EDITOR_HOST_CONTEXT_TYPE_TAG,
-            "init",
-        )
-        .expect("welcome script init must succeed");
 
         // Reverse-wrap the script-side `box<radiance.IDirector>`
         // through the runtime-typed CCW factory. The fat CCW gives us
@@ -92,10 +88,27 @@ impl ScriptedWelcomePage {
         // `IUiLayer` (for the engine UI renderer) from a single
         // `wrap_director` call, because the script struct's
         // `conforming_to` list backs every advertised interface with
-        // its own slot.
-        let runtime_handle = host.runtime_handle();
-        let director: ComRc<IDirector> =
-            wrap_director(&runtime_handle, director_data).expect("wrap_director must succeed");
+        // its own slot. The hot reloader reruns the same bootstrap
+        // against the reloaded sources.
+        let host_arg = mod_host_arg(host_ctx.clone(), EDITOR_HOST_CONTEXT_TYPE_TAG);
+        let mut boot = move |host: &ScriptHost| -> Result<ComRc<IDirector>, HostError> {
+            let director_data = bootstrap_script_root_from_path(
+                host,
+                WELCOME_SCRIPT_ROOT,
+                host_ctx.clone(),
+                EDITOR_HOST_CONTEXT_TYPE_TAG,
+                "init",
+            )?;
+            wrap_director(&host.runtime_handle(), director_data)
+        };
+        let director = boot(&host).expect("welcome script init must succeed");
+        install_hot_reloader(
+            &app,
+            host,
+            WELCOME_SCRIPT_ROOT,
+            Box::new(boot),
+            host_arg,
+        );
 
         // Install the engine-side UI renderer so `CoreRadianceEngine::update`
         // drives the active director's `render` (via its `IUiLayer` slot)
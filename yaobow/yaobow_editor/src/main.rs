@@ -24,6 +24,19 @@ fn main() {
     // returns null.
     register_opengb_video_decoders();
 
+    // Make the IDL interfaces visible to `crosscom::reflect` so the
+    // inspector can list component properties without per-type code.
+    crosscom::register_reflection();
+    radiance::comdef::register_reflection();
+    radiance_scripting::comdef::services::register_reflection();
+    shared::comdef::register_reflection();
+    shared::openpal3::comdef::register_reflection();
+    shared::openpal4::comdef::register_reflection();
+    shared::openpal4::comdef::pal4_debug::register_reflection();
+    shared::openpal5::comdef::register_reflection();
+    shared::openswd5::comdef::register_reflection();
+    yaobow_editor::comdef::editor_services::register_reflection();
+
     // let mut line = String::new();
     // let stdin = std::io::stdin();
     // stdin.lock().read_line(&mut line).unwrap();
@@ -16,7 +16,11 @@ const EDITOR_YPK: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/yaobow_edito
 /// Mounts this crate's `yaobow_editor.ypk` at `/yaobow_editor/` on
 /// the script `AssetManager`.
 pub fn mount_scripts(assets: &AssetManager) {
-    assets
-        .mount_ypk_bytes("/yaobow_editor", EDITOR_YPK)
-        .expect("yaobow_editor.ypk must mount");
+    radiance_scripting::hot_reload::mount_script_bundle(
+        assets,
+        "/yaobow_editor",
+        EDITOR_YPK,
+        concat!(env!("CARGO_MANIFEST_DIR"), "/scripts"),
+    )
+    .expect("yaobow_editor.ypk must mount");
 }
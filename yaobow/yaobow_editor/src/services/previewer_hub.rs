@@ -132,7 +132,7 @@ fn classify_path(path: &str) -> i32 {
         Some("h" | "asm" | "ini" | "txt" | "conf" | "cfg" | "log" | "lua") => KIND_TEXT,
         Some("tga" | "png" | "dds") => KIND_IMAGE,
         Some("mp3" | "smp" | "wav" | "ogg") => KIND_AUDIO,
-        Some("bik") => KIND_VIDEO,
+        Some("bik" | "webm" | "ogv") => KIND_VIDEO,
         Some("mv3" | "cvd" | "dff" | "anm" | "bsp" | "pol") => KIND_MODEL,
         Some("scn" | "nav" | "sce" | "nod" | "tli") => KIND_STRUCTURED,
         // .xml is content-classified separately: see `classify_xml` —
@@ -214,7 +214,11 @@ impl IPreviewerHubImpl for PreviewerHub {
     fn dump_structured(&self, vfs_path: &str) -> &str {
         let path = PathBuf::from(vfs_path);
         let text = match extension(vfs_path).as_deref() {
-            Some("scn") => jsonify(&scn_load_from_file(&self.vfs, &path, shared::GameType::PAL3)),
+            Some("scn") => jsonify(&scn_load_from_file(
+                &self.vfs,
+                &path,
+                shared::GameType::PAL3,
+            )),
             Some("nav") => jsonify(&nav_load_from_file(&self.vfs, &path)),
             Some("sce") => jsonify(&sce_load_from_file(&self.vfs, &path)),
             Some("anm") => match load_anm(&self.vfs, &path) {
@@ -299,10 +303,7 @@ impl IPreviewerHubImpl for PreviewerHub {
     fn open_video(&self, vfs_path: &str) -> Option<ComRc<IVideoHandle>> {
         let path = PathBuf::from(vfs_path);
         let extension = extension(vfs_path)?;
-        let codec = match extension.as_str() {
-            "bik" => VideoCodec::Bik,
-            _ => return None,
-        };
+        let codec = VideoCodec::from_extension(&extension)?;
 
         let reader = Box::new(BufReader::new(self.vfs.open(&path).ok()?));
         let mut player = self.factory.create_video_player();
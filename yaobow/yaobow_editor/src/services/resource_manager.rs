@@ -34,7 +34,7 @@ fn classify(ext: &str) -> usize {
         "mv3" | "cvd" | "dff" | "anm" | "pol" => 1,
         "tga" | "png" | "dds" | "bmp" | "jpg" | "jpeg" => 2,
         "mp3" | "smp" | "wav" | "ogg" => 3,
-        "bik" => 4,
+        "bik" | "webm" | "ogv" => 4,
         "sce" | "nod" | "h" | "asm" => 5,
         "ini" | "txt" | "conf" | "cfg" | "log" | "csv" | "xml" | "json" => 6,
         _ => 7,
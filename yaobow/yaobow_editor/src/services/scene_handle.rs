@@ -10,7 +10,8 @@
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
-use crosscom::ComRc;
+use crosscom::reflect;
+use crosscom::{ComRc, IUnknown};
 use radiance::comdef::{IEntity, IEntityExt, IScene, ISceneExt};
 use radiance::math::{Vec3, transform_aabb};
 use radiance::rendering::ComponentFactory;
@@ -545,6 +546,7 @@ impl InspectorView {
                     format!("{:.1}, {:.1}, {:.1}", e.x, e.y, e.z),
                 ));
                 f.push(("visible".to_string(), n.entity.visible().to_string()));
+                push_component_properties(&mut f, &n.entity);
                 f
             }
         };
@@ -565,6 +567,28 @@ impl InspectorView {
     }
 }
 
+/// One `component` row per attached component, naming the interfaces
+/// it implements, followed by its `[property]` values. Only interfaces
+/// registered with `crosscom::reflect` (see `main.rs`) show up.
+fn push_component_properties(fields: &mut Vec<(String, String)>, entity: &ComRc<IEntity>) {
+    for (_, component) in entity.components() {
+        let Some(object) = component.query_interface::<IUnknown>() else {
+            continue;
+        };
+        fields.push((
+            "component".to_string(),
+            reflect::Variant::Object(object.clone()).to_string(),
+        ));
+        for property in reflect::properties(&object) {
+            let value = match property.value {
+                Ok(value) => value.to_string(),
+                Err(e) => format!("<{e}>"),
+            };
+            fields.push((format!("  {}.{}", property.interface, property.name), value));
+        }
+    }
+}
+
 fn kind_label(kind: i32) -> &'static str {
     match kind {
         0 => "root",